@@ -0,0 +1,275 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2024-2025 ByteDance and/or its affiliates.
+ */
+
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use g3_types::net::UdpListenConfig;
+
+static FRONTEND_CONFIG_LOCK: OnceLock<Arc<FrontendConfig>> = OnceLock::new();
+
+pub(crate) fn get_config() -> Option<Arc<FrontendConfig>> {
+    FRONTEND_CONFIG_LOCK.get().cloned()
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct ResponseFields {
+    pub(crate) country: bool,
+    pub(crate) continent: bool,
+    pub(crate) as_number: bool,
+    pub(crate) isp_name: bool,
+    pub(crate) isp_domain: bool,
+}
+
+impl Default for ResponseFields {
+    fn default() -> Self {
+        ResponseFields {
+            country: true,
+            continent: true,
+            as_number: true,
+            isp_name: true,
+            isp_domain: true,
+        }
+    }
+}
+
+impl ResponseFields {
+    fn empty() -> Self {
+        ResponseFields {
+            country: false,
+            continent: false,
+            as_number: false,
+            isp_name: false,
+            isp_domain: false,
+        }
+    }
+
+    pub(crate) fn use_country_db(&self) -> bool {
+        self.country || self.continent
+    }
+
+    pub(crate) fn use_asn_db(&self) -> bool {
+        self.as_number || self.isp_name || self.isp_domain
+    }
+
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut fields = ResponseFields::empty();
+        let names = g3_yaml::value::as_list(value, g3_yaml::value::as_string)?;
+        for name in names {
+            match g3_yaml::key::normalize(&name).as_str() {
+                "country" => fields.country = true,
+                "continent" => fields.continent = true,
+                "as_number" | "asn" => fields.as_number = true,
+                "isp_name" | "as_name" => fields.isp_name = true,
+                "isp_domain" | "as_domain" => fields.isp_domain = true,
+                _ => return Err(anyhow!("unsupported response field {name}")),
+            }
+        }
+        Ok(fields)
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) enum ResponseFormat {
+    /// msgpack map with integer key ids, which is used by g3proxy
+    #[default]
+    Msgpack,
+    /// json object with string keys, mainly for debugging and integration
+    Json,
+}
+
+impl ResponseFormat {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let s = g3_yaml::value::as_string(value)?;
+        match g3_yaml::key::normalize(&s).as_str() {
+            "msgpack" | "binary" => Ok(ResponseFormat::Msgpack),
+            "json" => Ok(ResponseFormat::Json),
+            _ => Err(anyhow!("unsupported response format {s}")),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct FrontendConfig {
+    pub(crate) listen: Vec<UdpListenConfig>,
+    #[cfg(unix)]
+    pub(crate) unix_listen: Option<PathBuf>,
+    pub(crate) response_fields: ResponseFields,
+    pub(crate) response_format: ResponseFormat,
+    pub(crate) log_request: bool,
+}
+
+impl FrontendConfig {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for the frontend config should be 'map'"
+            ));
+        };
+        let mut config = FrontendConfig::default();
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "listen" => {
+                config.listen = g3_yaml::value::as_list(v, |v| {
+                    let mut listen = g3_yaml::value::as_udp_listen_config(v)?;
+                    listen.check()?;
+                    Ok(listen)
+                })
+                .context(format!("invalid udp listen config list value for key {k}"))?;
+                Ok(())
+            }
+            #[cfg(unix)]
+            "unix_listen" => {
+                let path = g3_yaml::value::as_absolute_path(v)
+                    .context(format!("invalid absolute path value for key {k}"))?;
+                config.unix_listen = Some(path);
+                Ok(())
+            }
+            "response_fields" => {
+                config.response_fields = ResponseFields::parse_yaml(v)
+                    .context(format!("invalid response fields value for key {k}"))?;
+                Ok(())
+            }
+            "response_format" => {
+                config.response_format = ResponseFormat::parse_yaml(v)
+                    .context(format!("invalid response format value for key {k}"))?;
+                Ok(())
+            }
+            "log_request" => {
+                config.log_request = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        Ok(config)
+    }
+}
+
+pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
+    let config = FrontendConfig::parse_yaml(value)?;
+    FRONTEND_CONFIG_LOCK
+        .set(Arc::new(config))
+        .map_err(|_| anyhow!("duplicate frontend config"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use yaml_rust::YamlLoader;
+
+    fn load_yaml(s: &str) -> Yaml {
+        YamlLoader::load_from_str(s).unwrap().pop().unwrap()
+    }
+
+    #[test]
+    fn response_fields() {
+        let fields = ResponseFields::default();
+        assert!(fields.use_country_db());
+        assert!(fields.use_asn_db());
+
+        let fields = ResponseFields::parse_yaml(&load_yaml("[country, continent]")).unwrap();
+        assert!(fields.country);
+        assert!(fields.continent);
+        assert!(fields.use_country_db());
+        assert!(!fields.use_asn_db());
+
+        let fields = ResponseFields::parse_yaml(&load_yaml("[asn, as_name, as-domain]")).unwrap();
+        assert!(!fields.use_country_db());
+        assert!(fields.as_number);
+        assert!(fields.isp_name);
+        assert!(fields.isp_domain);
+
+        let fields = ResponseFields::parse_yaml(&load_yaml("[as_number]")).unwrap();
+        assert!(fields.use_asn_db());
+        assert!(!fields.isp_name);
+        assert!(!fields.isp_domain);
+
+        assert!(ResponseFields::parse_yaml(&load_yaml("[city]")).is_err());
+        assert!(ResponseFields::parse_yaml(&load_yaml("country")).is_ok());
+    }
+
+    #[test]
+    fn response_format() {
+        let config = FrontendConfig::parse_yaml(&load_yaml("log_request: true")).unwrap();
+        assert!(matches!(config.response_format, ResponseFormat::Msgpack));
+
+        let config = FrontendConfig::parse_yaml(&load_yaml("response_format: json")).unwrap();
+        assert!(matches!(config.response_format, ResponseFormat::Json));
+
+        for s in ["msgpack", "binary", "Binary"] {
+            let format = ResponseFormat::parse_yaml(&Yaml::String(s.to_string())).unwrap();
+            assert!(matches!(format, ResponseFormat::Msgpack));
+        }
+        assert!(ResponseFormat::parse_yaml(&Yaml::String("xml".to_string())).is_err());
+        assert!(FrontendConfig::parse_yaml(&load_yaml("response_format: xml")).is_err());
+    }
+
+    #[test]
+    fn listen() {
+        let config = FrontendConfig::parse_yaml(&load_yaml(
+            r#"
+            listen:
+              - 127.0.0.1:2888
+              - "[::1]:2888"
+            "#,
+        ))
+        .unwrap();
+        assert_eq!(config.listen.len(), 2);
+        assert_eq!(
+            config.listen[0].address(),
+            SocketAddr::from_str("127.0.0.1:2888").unwrap()
+        );
+        assert_eq!(
+            config.listen[1].address(),
+            SocketAddr::from_str("[::1]:2888").unwrap()
+        );
+
+        let config = FrontendConfig::parse_yaml(&load_yaml("listen: 127.0.0.1:2888")).unwrap();
+        assert_eq!(config.listen.len(), 1);
+
+        let config = FrontendConfig::parse_yaml(&load_yaml("log_request: true")).unwrap();
+        assert!(config.listen.is_empty());
+        assert!(config.log_request);
+
+        assert!(FrontendConfig::parse_yaml(&load_yaml("listen: [127.0.0.1]")).is_err());
+        assert!(FrontendConfig::parse_yaml(&load_yaml("listen_addr: 127.0.0.1:2888")).is_err());
+        assert!(FrontendConfig::parse_yaml(&load_yaml("[127.0.0.1:2888]")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_listen() {
+        let config =
+            FrontendConfig::parse_yaml(&load_yaml("unix_listen: /run/g3iploc.sock")).unwrap();
+        assert_eq!(
+            config.unix_listen.as_deref(),
+            Some(std::path::Path::new("/run/g3iploc.sock"))
+        );
+        assert!(config.listen.is_empty());
+
+        let config = FrontendConfig::parse_yaml(&load_yaml(
+            r#"
+            listen: 127.0.0.1:2888
+            unix_listen: /run/g3iploc.sock
+            "#,
+        ))
+        .unwrap();
+        assert_eq!(config.listen.len(), 1);
+        assert!(config.unix_listen.is_some());
+
+        let config = FrontendConfig::parse_yaml(&load_yaml("log_request: false")).unwrap();
+        assert!(config.unix_listen.is_none());
+
+        assert!(FrontendConfig::parse_yaml(&load_yaml("unix_listen: g3iploc.sock")).is_err());
+    }
+}
@@ -5,10 +5,32 @@
 
 use std::path::Path;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use anyhow::anyhow;
 use yaml_rust::Yaml;
 
+static COUNTRY_DB_RELOAD_TIME: AtomicU64 = AtomicU64::new(0);
+static ASN_DB_RELOAD_TIME: AtomicU64 = AtomicU64::new(0);
+
+/// the unix timestamp of the last successful load of the country db, 0 if never loaded
+pub(crate) fn country_db_reload_time() -> u64 {
+    COUNTRY_DB_RELOAD_TIME.load(Ordering::Relaxed)
+}
+
+/// the unix timestamp of the last successful load of the asn db, 0 if never loaded
+pub(crate) fn asn_db_reload_time() -> u64 {
+    ASN_DB_RELOAD_TIME.load(Ordering::Relaxed)
+}
+
+fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     if let Yaml::Hash(map) = v {
         g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
@@ -16,12 +38,14 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                 let path = g3_yaml::value::as_file_path(v, conf_dir, false)?;
                 let db = g3_geoip_db::file::load_country(&path)?;
                 g3_geoip_db::store::store_country(Arc::new(db));
+                COUNTRY_DB_RELOAD_TIME.store(now_timestamp(), Ordering::Relaxed);
                 Ok(())
             }
             "asn" => {
                 let path = g3_yaml::value::as_file_path(v, conf_dir, false)?;
                 let db = g3_geoip_db::file::load_asn(&path)?;
                 g3_geoip_db::store::store_asn(Arc::new(db));
+                ASN_DB_RELOAD_TIME.store(now_timestamp(), Ordering::Relaxed);
                 Ok(())
             }
             _ => Err(anyhow!("invalid key {k}")),
@@ -30,3 +54,50 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
         Err(anyhow!("invalid value type"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use yaml_rust::YamlLoader;
+
+    fn create_temp_file(content: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".csv").unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file.flush().unwrap();
+        file
+    }
+
+    #[test]
+    fn load_db() {
+        assert_eq!(country_db_reload_time(), 0);
+        assert_eq!(asn_db_reload_time(), 0);
+
+        let country = create_temp_file("1.0.0.0/24,AU\n1.0.1.0/24,CN\n2001:db8::/32,CN\n");
+        let asn = create_temp_file("1.0.0.0/24,13335\n");
+        let conf = format!(
+            "country: {}\nasn: {}\n",
+            country.path().display(),
+            asn.path().display()
+        );
+        let yaml = YamlLoader::load_from_str(&conf).unwrap().pop().unwrap();
+        load(&yaml, &std::env::temp_dir()).unwrap();
+
+        assert!(country_db_reload_time() > 0);
+        assert!(asn_db_reload_time() > 0);
+        assert_eq!(g3_geoip_db::store::load_country().unwrap().len(), (2, 1));
+        assert_eq!(g3_geoip_db::store::load_asn().unwrap().len(), (1, 0));
+
+        let yaml = YamlLoader::load_from_str("city: city.csv")
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert!(load(&yaml, &std::env::temp_dir()).is_err());
+        let yaml = YamlLoader::load_from_str("country.csv")
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert!(load(&yaml, &std::env::temp_dir()).is_err());
+    }
+}
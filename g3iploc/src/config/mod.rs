@@ -8,7 +8,11 @@ use std::path::Path;
 use anyhow::anyhow;
 use yaml_rust::{Yaml, yaml};
 
+mod frontend;
+pub(crate) use frontend::{ResponseFields, ResponseFormat, get_config as get_frontend_config};
+
 mod geoip;
+pub(crate) use geoip::{asn_db_reload_time, country_db_reload_time};
 
 pub fn load() -> anyhow::Result<&'static Path> {
     let config_file =
@@ -23,6 +27,39 @@ pub fn load() -> anyhow::Result<&'static Path> {
     Ok(config_file)
 }
 
+/// Check if all the geoip dbs used by the response fields have been loaded
+pub fn geoip_db_ready() -> bool {
+    let fields = get_frontend_config()
+        .map(|config| config.response_fields)
+        .unwrap_or_default();
+    (!fields.use_country_db() || country_db_reload_time() > 0)
+        && (!fields.use_asn_db() || asn_db_reload_time() > 0)
+}
+
+pub(crate) async fn reload_geoip_db() -> anyhow::Result<()> {
+    tokio::task::spawn_blocking(reload_geoip_db_blocking)
+        .await
+        .map_err(|e| anyhow!("failed to join reload task: {e}"))?
+}
+
+fn reload_geoip_db_blocking() -> anyhow::Result<()> {
+    let config_file =
+        g3_daemon::opts::config_file().ok_or_else(|| anyhow!("no config file set"))?;
+    let conf_dir =
+        g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
+
+    // only the geoip db can be reloaded
+    g3_yaml::foreach_doc(config_file, |_, doc| match doc {
+        Yaml::Hash(map) => {
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "geoip_db" => geoip::load(v, conf_dir),
+                _ => Ok(()),
+            })
+        }
+        _ => Err(anyhow!("yaml doc root should be hash")),
+    })
+}
+
 fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     let conf_dir =
         g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
@@ -30,6 +67,7 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "runtime" => g3_daemon::runtime::config::load(v),
         "worker" => g3_daemon::runtime::config::load_worker(v),
         "stat" => g3_daemon::stat::config::load(v, crate::build::PKG_NAME),
+        "frontend" => frontend::load_config(v),
         "geoip_db" => geoip::load(v, conf_dir),
         _ => Err(anyhow!("invalid key {k} in main conf")),
     })?;
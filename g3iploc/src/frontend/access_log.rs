@@ -0,0 +1,67 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::net::IpAddr;
+
+use g3_geoip_types::IpLocation;
+use g3_ip_locate::response_key;
+
+pub(super) fn format_hit(ip: IpAddr, location: &IpLocation) -> String {
+    let mut s = format!(
+        "{}={ip} {}={}",
+        response_key::IP,
+        response_key::NETWORK,
+        location.network_addr()
+    );
+    if let Some(country) = location.country() {
+        let _ = write!(s, " {}={}", response_key::COUNTRY, country.alpha2_code());
+    }
+    if let Some(continent) = location.continent() {
+        let _ = write!(s, " {}={}", response_key::CONTINENT, continent.code());
+    }
+    if let Some(number) = location.network_asn() {
+        let _ = write!(s, " {}={number}", response_key::AS_NUMBER);
+    }
+    if let Some(name) = location.isp_name() {
+        let _ = write!(s, " {}={name:?}", response_key::ISP_NAME);
+    }
+    if let Some(domain) = location.isp_domain() {
+        let _ = write!(s, " {}={domain}", response_key::ISP_DOMAIN);
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_geoip_types::{ContinentCode, IpLocationBuilder, IsoCountryCode};
+
+    #[test]
+    fn hit() {
+        let ip = IpAddr::from([1, 1, 1, 1]);
+
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network("1.1.1.0/24".parse().unwrap());
+        builder.set_country(IsoCountryCode::AU);
+        builder.set_continent(ContinentCode::OC);
+        let location = builder.build().unwrap();
+        assert_eq!(
+            format_hit(ip, &location),
+            "ip=1.1.1.1 network=1.1.1.0/24 country=AU continent=OC"
+        );
+
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network("1.1.1.0/24".parse().unwrap());
+        builder.set_as_number(13335);
+        builder.set_isp_name("Cloudflare, Inc.".to_string());
+        builder.set_isp_domain("cloudflare.com".to_string());
+        let location = builder.build().unwrap();
+        assert_eq!(
+            format_hit(ip, &location),
+            "ip=1.1.1.1 network=1.1.1.0/24 as_number=13335 isp_name=\"Cloudflare, Inc.\" isp_domain=cloudflare.com"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2024-2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+
+use anyhow::anyhow;
+use serde_json::{Map, Value};
+
+use g3_geoip_types::IpLocation;
+use g3_ip_locate::response_key;
+
+pub(super) fn encode_response(
+    ip: IpAddr,
+    location: &IpLocation,
+    ttl: u32,
+) -> anyhow::Result<Vec<u8>> {
+    let mut map = Map::with_capacity(8);
+    map.insert(response_key::IP.to_string(), Value::String(ip.to_string()));
+    map.insert(
+        response_key::NETWORK.to_string(),
+        Value::String(location.network_addr().to_string()),
+    );
+    map.insert(response_key::TTL.to_string(), Value::from(ttl));
+    if let Some(country) = location.country() {
+        map.insert(
+            response_key::COUNTRY.to_string(),
+            Value::String(country.alpha2_code().to_string()),
+        );
+    }
+    if let Some(continent) = location.continent() {
+        map.insert(
+            response_key::CONTINENT.to_string(),
+            Value::String(continent.code().to_string()),
+        );
+    }
+    if let Some(number) = location.network_asn() {
+        map.insert(response_key::AS_NUMBER.to_string(), Value::from(number));
+    }
+    if let Some(name) = location.isp_name() {
+        map.insert(
+            response_key::ISP_NAME.to_string(),
+            Value::String(name.to_string()),
+        );
+    }
+    if let Some(domain) = location.isp_domain() {
+        map.insert(
+            response_key::ISP_DOMAIN.to_string(),
+            Value::String(domain.to_string()),
+        );
+    }
+    serde_json::to_vec(&Value::Object(map)).map_err(|e| anyhow!("json encode failed: {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use ip_network::IpNetwork;
+
+    use g3_geoip_types::{IpLocationBuilder, IsoCountryCode};
+
+    #[test]
+    fn encode() {
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network(IpNetwork::from_str("1.0.0.0/24").unwrap());
+        builder.set_country(IsoCountryCode::AU);
+        builder.set_as_number(13335);
+        builder.set_isp_name("Cloudflare".to_string());
+        let location = builder.build().unwrap();
+
+        let ip = IpAddr::from_str("1.0.0.1").unwrap();
+        let data = encode_response(ip, &location, 300).unwrap();
+        let v: Value = serde_json::from_slice(&data).unwrap();
+        assert_eq!(v["ip"], "1.0.0.1");
+        assert_eq!(v["network"], "1.0.0.0/24");
+        assert_eq!(v["ttl"], 300);
+        assert_eq!(v["country"], "AU");
+        assert_eq!(v["continent"], "OC");
+        assert_eq!(v["as_number"], 13335);
+        assert_eq!(v["isp_name"], "Cloudflare");
+        assert!(v.get("isp_domain").is_none());
+
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network(IpNetwork::from_str("2001:db8::/32").unwrap());
+        let location = builder.build().unwrap();
+        let ip = IpAddr::from_str("2001:db8::1").unwrap();
+        let data = encode_response(ip, &location, 60).unwrap();
+        let v: Value = serde_json::from_slice(&data).unwrap();
+        assert_eq!(v.as_object().unwrap().len(), 3);
+        assert_eq!(v["network"], "2001:db8::/32");
+        assert_eq!(v["ttl"], 60);
+    }
+}
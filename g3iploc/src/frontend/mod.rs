@@ -3,36 +3,90 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
+use std::io;
 use std::net::IpAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::sync::Arc;
 
-use log::warn;
+use log::{info, warn};
 use tokio::sync::broadcast;
 
 use g3_geoip_types::{IpLocation, IpLocationBuilder};
 use g3_ip_locate::{Request, Response};
 use g3_types::net::UdpListenConfig;
 
+use crate::config::{ResponseFields, ResponseFormat};
+
 mod stats;
 pub(crate) use stats::FrontendStats;
 
+mod access_log;
+mod json;
+
 mod udp_dgram;
 use udp_dgram::UdpDgramFrontend;
 
-pub(super) struct Frontend {
-    io: UdpDgramFrontend,
+#[cfg(unix)]
+mod unix_dgram;
+#[cfg(unix)]
+use unix_dgram::UnixDgramFrontend;
+
+pub(crate) trait FrontendIo {
+    type PeerAddr;
+
+    async fn recv_req(&self, buf: &mut [u8]) -> io::Result<(usize, Self::PeerAddr)>;
+    async fn send_rsp(&self, data: &[u8], peer: Self::PeerAddr) -> io::Result<()>;
+}
+
+pub(super) struct Frontend<T> {
+    io: T,
+    fields: ResponseFields,
+    format: ResponseFormat,
+    log_request: bool,
     stats: Arc<FrontendStats>,
 }
 
-impl Frontend {
-    pub(super) fn new(
+impl Frontend<UdpDgramFrontend> {
+    pub(super) fn new_udp(
         listen_config: &UdpListenConfig,
+        fields: ResponseFields,
+        format: ResponseFormat,
+        log_request: bool,
         stats: Arc<FrontendStats>,
     ) -> anyhow::Result<Self> {
         let io = UdpDgramFrontend::new(listen_config)?;
-        Ok(Frontend { io, stats })
+        Ok(Frontend {
+            io,
+            fields,
+            format,
+            log_request,
+            stats,
+        })
+    }
+}
+
+#[cfg(unix)]
+impl Frontend<UnixDgramFrontend> {
+    pub(super) fn new_unix(
+        path: &Path,
+        fields: ResponseFields,
+        format: ResponseFormat,
+        log_request: bool,
+        stats: Arc<FrontendStats>,
+    ) -> anyhow::Result<Self> {
+        let io = UnixDgramFrontend::new(path)?;
+        Ok(Frontend {
+            io,
+            fields,
+            format,
+            log_request,
+            stats,
+        })
     }
+}
 
+impl<T: FrontendIo> Frontend<T> {
     pub(super) async fn run(
         self,
         mut quit_receiver: broadcast::Receiver<()>,
@@ -62,10 +116,21 @@ impl Frontend {
                             };
 
                             let Some(location) = self.fetch(ip) else {
+                                self.stats.add_request_miss();
+                                if self.log_request {
+                                    info!("ip={ip} miss");
+                                }
                                 continue;
                             };
+                            if self.log_request {
+                                info!("{}", access_log::format_hit(ip, &location));
+                            }
 
-                            match Response::encode_new(ip, location, 300) {
+                            let r = match self.format {
+                                ResponseFormat::Msgpack => Response::encode_new(ip, location, 300),
+                                ResponseFormat::Json => json::encode_response(ip, &location, 300),
+                            };
+                            match r {
                                 Ok(buf) => {
                                     self.stats.add_response_total();
                                     if self.io.send_rsp(&buf, addr).await.is_err() {
@@ -90,23 +155,36 @@ impl Frontend {
     fn fetch(&self, ip: IpAddr) -> Option<IpLocation> {
         let mut builder = IpLocationBuilder::default();
 
-        if let Some(db) = g3_geoip_db::store::load_country()
+        if self.fields.use_country_db()
+            && let Some(db) = g3_geoip_db::store::load_country()
             && let Some((net, v)) = db.longest_match(ip)
         {
             builder.set_network(net);
-            builder.set_country(v.country);
-            builder.set_continent(v.continent);
+            if self.fields.country {
+                builder.set_country(v.country);
+            }
+            if self.fields.continent {
+                builder.set_continent(v.continent);
+            }
         }
 
-        if let Some(asn_db) = g3_geoip_db::store::load_asn()
+        // the asn fields will be omitted if no asn db is loaded
+        if self.fields.use_asn_db()
+            && let Some(asn_db) = g3_geoip_db::store::load_asn()
             && let Some((net, v)) = asn_db.longest_match(ip)
         {
             builder.set_network(net);
-            builder.set_as_number(v.number);
-            if let Some(name) = v.isp_name() {
+            if self.fields.as_number {
+                builder.set_as_number(v.number);
+            }
+            if self.fields.isp_name
+                && let Some(name) = v.isp_name()
+            {
                 builder.set_isp_name(name.to_string());
             }
-            if let Some(domain) = v.isp_domain() {
+            if self.fields.isp_domain
+                && let Some(domain) = v.isp_domain()
+            {
                 builder.set_isp_domain(domain.to_string());
             }
         }
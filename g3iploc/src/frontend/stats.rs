@@ -3,12 +3,17 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
+#[cfg(unix)]
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 
-#[derive(Default)]
 pub(crate) struct FrontendStats {
+    transport: &'static str,
+    listen_addr: String,
     request_total: AtomicU64,
     request_invalid: AtomicU64,
+    request_miss: AtomicU64,
     response_total: AtomicU64,
     response_fail: AtomicU64,
 }
@@ -26,8 +31,86 @@ macro_rules! impl_for_field {
 }
 
 impl FrontendStats {
+    pub(crate) fn new_udp(listen_addr: SocketAddr) -> Self {
+        FrontendStats::new("udp", listen_addr.to_string())
+    }
+
+    #[cfg(unix)]
+    pub(crate) fn new_unix(listen_path: &Path) -> Self {
+        FrontendStats::new("unix", listen_path.display().to_string())
+    }
+
+    fn new(transport: &'static str, listen_addr: String) -> Self {
+        FrontendStats {
+            transport,
+            listen_addr,
+            request_total: Default::default(),
+            request_invalid: Default::default(),
+            request_miss: Default::default(),
+            response_total: Default::default(),
+            response_fail: Default::default(),
+        }
+    }
+
+    pub(crate) fn transport(&self) -> &'static str {
+        self.transport
+    }
+
+    pub(crate) fn listen_addr(&self) -> &str {
+        &self.listen_addr
+    }
+
     impl_for_field!(add_request_total, take_request_total, request_total);
     impl_for_field!(add_request_invalid, take_request_invalid, request_invalid);
+    impl_for_field!(add_request_miss, take_request_miss, request_miss);
     impl_for_field!(add_response_total, take_response_total, response_total);
     impl_for_field!(add_response_fail, take_response_fail, response_fail);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn udp() {
+        let stats = FrontendStats::new_udp(SocketAddr::from_str("127.0.0.1:2888").unwrap());
+        assert_eq!(stats.transport(), "udp");
+        assert_eq!(stats.listen_addr(), "127.0.0.1:2888");
+
+        let stats = FrontendStats::new_udp(SocketAddr::from_str("[::1]:2888").unwrap());
+        assert_eq!(stats.listen_addr(), "[::1]:2888");
+
+        stats.add_request_total();
+        stats.add_request_total();
+        stats.add_response_total();
+        assert_eq!(stats.take_request_total(), 2);
+        assert_eq!(stats.take_request_total(), 0);
+        assert_eq!(stats.take_response_total(), 1);
+        assert_eq!(stats.take_response_fail(), 0);
+    }
+
+    #[test]
+    fn miss() {
+        let stats = FrontendStats::new_udp(SocketAddr::from_str("127.0.0.1:2888").unwrap());
+        for _ in 0..4 {
+            stats.add_request_total();
+        }
+        stats.add_request_miss();
+        assert_eq!(stats.take_request_total(), 4);
+        assert_eq!(stats.take_request_miss(), 1);
+        assert_eq!(stats.take_request_miss(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix() {
+        let stats = FrontendStats::new_unix(Path::new("/run/g3iploc.sock"));
+        assert_eq!(stats.transport(), "unix");
+        assert_eq!(stats.listen_addr(), "/run/g3iploc.sock");
+
+        stats.add_request_invalid();
+        assert_eq!(stats.take_request_invalid(), 1);
+        assert_eq!(stats.take_request_total(), 0);
+    }
+}
@@ -10,6 +10,8 @@ use tokio::net::UdpSocket;
 
 use g3_types::net::UdpListenConfig;
 
+use super::FrontendIo;
+
 pub(crate) struct UdpDgramFrontend {
     socket: UdpSocket,
 }
@@ -21,12 +23,16 @@ impl UdpDgramFrontend {
             socket: UdpSocket::from_std(socket)?,
         })
     }
+}
+
+impl FrontendIo for UdpDgramFrontend {
+    type PeerAddr = SocketAddr;
 
-    pub(crate) async fn recv_req(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+    async fn recv_req(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
         self.socket.recv_from(buf).await
     }
 
-    pub(crate) async fn send_rsp(&self, data: &[u8], peer: SocketAddr) -> io::Result<()> {
+    async fn send_rsp(&self, data: &[u8], peer: SocketAddr) -> io::Result<()> {
         let nw = self.socket.send_to(data, peer).await?;
         if nw != data.len() {
             Err(io::Error::other(format!(
@@ -0,0 +1,56 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2024-2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs;
+use std::io;
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+
+use tokio::net::UnixDatagram;
+use tokio::net::unix::SocketAddr;
+
+use super::FrontendIo;
+
+pub(crate) struct UnixDgramFrontend {
+    socket: UnixDatagram,
+}
+
+impl UnixDgramFrontend {
+    pub(crate) fn new(path: &Path) -> io::Result<Self> {
+        // remove the stale socket file left by the previous process
+        if let Ok(meta) = fs::symlink_metadata(path)
+            && meta.file_type().is_socket()
+        {
+            fs::remove_file(path)?;
+        }
+        let socket = UnixDatagram::bind(path)?;
+        Ok(UnixDgramFrontend { socket })
+    }
+}
+
+impl FrontendIo for UnixDgramFrontend {
+    type PeerAddr = SocketAddr;
+
+    async fn recv_req(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        self.socket.recv_from(buf).await
+    }
+
+    async fn send_rsp(&self, data: &[u8], peer: SocketAddr) -> io::Result<()> {
+        let Some(path) = peer.as_pathname() else {
+            return Err(io::Error::other(
+                "the peer unix socket is not bound to a path",
+            ));
+        };
+        let nw = self.socket.send_to(data, path).await?;
+        if nw != data.len() {
+            Err(io::Error::other(format!(
+                "not all data written, only {nw}/{}",
+                data.len()
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -5,9 +5,12 @@
 
 use std::sync::Arc;
 
+use anyhow::Context;
 use log::{debug, warn};
 use tokio::sync::{broadcast, mpsc};
 
+use g3_daemon::control::LocalController;
+
 pub mod config;
 
 mod build;
@@ -17,32 +20,109 @@ use opts::ProcArgs;
 
 mod stat;
 
+pub mod signal;
+
 mod frontend;
 use frontend::{Frontend, FrontendStats};
 
 pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
-    let frontend_stats = Arc::new(FrontendStats::default());
+    // the unique controller serves the text control commands, such as alive and ready
+    let unique_ctl = LocalController::start_unique(build::PKG_NAME, opts::daemon_group())
+        .context("failed to start unique controller")?;
+    tokio::spawn(async move {
+        unique_ctl.await;
+    });
+
+    let frontend_config = config::get_frontend_config();
+    let listen_configs = match &frontend_config {
+        Some(config) if !config.listen.is_empty() => config.listen.as_slice(),
+        _ => std::slice::from_ref(proc_args.listen_config()),
+    };
+
+    let (response_fields, response_format, log_request) = frontend_config
+        .as_ref()
+        .map(|config| {
+            (
+                config.response_fields,
+                config.response_format,
+                config.log_request,
+            )
+        })
+        .unwrap_or_default();
+    #[cfg(unix)]
+    let unix_listen = frontend_config
+        .as_ref()
+        .and_then(|config| config.unix_listen.as_deref());
+
+    let all_frontend_stats = listen_configs
+        .iter()
+        .map(|listen| Arc::new(FrontendStats::new_udp(listen.address())))
+        .collect::<Vec<_>>();
+    #[cfg(unix)]
+    let unix_frontend_stats = unix_listen.map(|path| Arc::new(FrontendStats::new_unix(path)));
     let (quit_sender, _) = broadcast::channel(1);
     let (wait_sender, mut wait_receiver) =
-        mpsc::channel(g3_daemon::runtime::worker::worker_count().max(1));
+        mpsc::channel(g3_daemon::runtime::worker::worker_count().max(1) * listen_configs.len() + 1);
 
     if let Some(stats_config) = g3_daemon::stat::config::get_global_stat_config() {
-        stat::spawn_working_thread(stats_config, frontend_stats.clone())?;
+        let stats_list = all_frontend_stats.clone();
+        #[cfg(unix)]
+        let stats_list = stats_list
+            .into_iter()
+            .chain(unix_frontend_stats.clone())
+            .collect();
+        stat::spawn_working_thread(stats_config, stats_list)?;
     }
 
-    let workers = g3_daemon::runtime::worker::foreach(|h| {
-        let frontend = Frontend::new(proc_args.listen_config(), frontend_stats.clone())?;
-        let quit_receiver = quit_sender.subscribe();
-        let wait_sender = wait_sender.clone();
-        let id = h.id;
-        h.handle.spawn(async move {
-            let _ = frontend.run(quit_receiver).await;
-            let _ = wait_sender.try_send(Some(id));
-        });
-        Ok::<(), anyhow::Error>(())
-    })?;
-    if workers < 1 {
-        let frontend = Frontend::new(proc_args.listen_config(), frontend_stats.clone())?;
+    for (listen_config, frontend_stats) in listen_configs.iter().zip(all_frontend_stats) {
+        let workers = g3_daemon::runtime::worker::foreach(|h| {
+            let frontend = Frontend::new_udp(
+                listen_config,
+                response_fields,
+                response_format,
+                log_request,
+                frontend_stats.clone(),
+            )?;
+            let quit_receiver = quit_sender.subscribe();
+            let wait_sender = wait_sender.clone();
+            let id = h.id;
+            h.handle.spawn(async move {
+                let _ = frontend.run(quit_receiver).await;
+                let _ = wait_sender.try_send(Some(id));
+            });
+            Ok::<(), anyhow::Error>(())
+        })?;
+        if workers < 1 {
+            let frontend = Frontend::new_udp(
+                listen_config,
+                response_fields,
+                response_format,
+                log_request,
+                frontend_stats,
+            )?;
+            let quit_receiver = quit_sender.subscribe();
+            let wait_sender = wait_sender.clone();
+            tokio::spawn(async move {
+                let _ = frontend.run(quit_receiver).await;
+                let _ = wait_sender.try_send(None);
+            });
+        }
+    }
+
+    #[cfg(unix)]
+    if let (Some(path), Some(frontend_stats)) = (unix_listen, unix_frontend_stats) {
+        // the unix socket can only be bound once, so run it in the main runtime
+        let frontend = Frontend::new_unix(
+            path,
+            response_fields,
+            response_format,
+            log_request,
+            frontend_stats,
+        )
+        .context(format!(
+            "failed to create unix frontend at {}",
+            path.display()
+        ))?;
         let quit_receiver = quit_sender.subscribe();
         let wait_sender = wait_sender.clone();
         tokio::spawn(async move {
@@ -51,10 +131,13 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
         });
     }
 
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_LISTEN);
+
     if let Err(e) = tokio::signal::ctrl_c().await {
         warn!("failed to recv Ctrl-C signal: {e}");
     }
     debug!("received Ctrl-C signal, start shutdown now");
+    g3_daemon::health::set_not_ready(g3_daemon::health::COMPONENT_LISTEN);
     drop(quit_sender);
 
     drop(wait_sender);
@@ -64,5 +147,6 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
         }
     }
     debug!("all requests served, quit now");
+    LocalController::abort_unique().await;
     Ok(())
 }
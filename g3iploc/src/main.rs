@@ -17,11 +17,18 @@ fn main() -> anyhow::Result<()> {
 
     // set up process logger early, only proc args is used inside
     g3_daemon::log::process::setup(&proc_args.daemon_config);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_CONFIG);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_GEOIP_DB);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_LISTEN);
 
     g3_daemon::runtime::config::set_default_thread_number(0); // default to use current thread
     let config_file = g3iploc::config::load()
         .context(format!("failed to load config, opts: {:?}", &proc_args))?;
     debug!("loaded config from {}", config_file.display());
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_CONFIG);
+    if g3iploc::config::geoip_db_ready() {
+        g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_GEOIP_DB);
+    }
 
     if proc_args.daemon_config.test_config {
         info!("the format of the config file is ok");
@@ -32,6 +39,8 @@ fn main() -> anyhow::Result<()> {
     #[cfg(unix)]
     g3_daemon::daemonize::check_enter(&proc_args.daemon_config)?;
 
+    let _workers_guard =
+        g3_daemon::runtime::worker::spawn_workers().context("failed to spawn workers")?;
     let ret = tokio_run(&proc_args);
 
     match ret {
@@ -48,7 +57,7 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
         .start()
         .context("failed to start runtime")?;
     rt.block_on(async {
-        // TODO setup signal handler
+        g3iploc::signal::register().context("failed to setup signal handler")?;
 
         g3iploc::run(args).await
     })
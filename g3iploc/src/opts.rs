@@ -18,6 +18,7 @@ use g3_types::net::UdpListenConfig;
 const GLOBAL_ARG_VERSION: &str = "version";
 const GLOBAL_ARG_GROUP_NAME: &str = "group-name";
 const GLOBAL_ARG_CONFIG_FILE: &str = "config-file";
+const GLOBAL_ARG_CONTROL_DIR: &str = "control-dir";
 
 static DAEMON_GROUP: OnceLock<String> = OnceLock::new();
 
@@ -73,6 +74,17 @@ fn build_cli_args() -> Command {
                 .short('c')
                 .long("config-file"),
         )
+        .arg(
+            Arg::new(GLOBAL_ARG_CONTROL_DIR)
+                .help("Control socket directory")
+                .num_args(1)
+                .value_name("CONTROL DIR")
+                .value_hint(ValueHint::DirPath)
+                .value_parser(value_parser!(PathBuf))
+                .default_value(g3_daemon::opts::DEFAULT_CONTROL_DIR)
+                .short('C')
+                .long("control-dir"),
+        )
 }
 
 pub fn parse_clap() -> anyhow::Result<Option<ProcArgs>> {
@@ -96,6 +108,11 @@ pub fn parse_clap() -> anyhow::Result<Option<ProcArgs>> {
     } else {
         return Err(anyhow!("no config file given"));
     }
+    #[cfg(unix)]
+    if let Some(control_dir) = args.get_one::<PathBuf>(GLOBAL_ARG_CONTROL_DIR) {
+        g3_daemon::opts::validate_and_set_control_dir(control_dir)
+            .context(format!("invalid control dir: {}", control_dir.display()))?;
+    }
 
     if let Some(group_name) = args.get_one::<String>(GLOBAL_ARG_GROUP_NAME) {
         DAEMON_GROUP
@@ -119,3 +136,7 @@ pub fn parse_clap() -> anyhow::Result<Option<ProcArgs>> {
 
     Ok(Some(proc_args))
 }
+
+pub(crate) fn daemon_group() -> &'static str {
+    DAEMON_GROUP.get().map(|s| s.as_str()).unwrap_or_default()
+}
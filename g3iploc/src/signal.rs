@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use log::{info, warn};
+use tokio::sync::Mutex;
+
+use g3_daemon::metrics::reload::ReloadFailureType;
+use g3_daemon::signal::AsyncSignalAction;
+
+static RELOAD_MUTEX: Mutex<()> = Mutex::const_new(());
+
+async fn do_reload() {
+    let _guard = RELOAD_MUTEX.lock().await;
+    info!("reloading geoip db");
+
+    if let Err(e) = crate::config::reload_geoip_db().await {
+        warn!("error reloading geoip db: {e:?}");
+        warn!("reload aborted");
+        g3_daemon::metrics::reload::add_failure(ReloadFailureType::Config);
+        return;
+    }
+    g3_daemon::metrics::reload::add_success();
+    if crate::config::geoip_db_ready() {
+        g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_GEOIP_DB);
+    }
+
+    info!("reload finished");
+}
+
+#[derive(Clone, Copy)]
+struct ReloadAction {}
+
+impl AsyncSignalAction for ReloadAction {
+    async fn run(&self) {
+        do_reload().await
+    }
+}
+
+pub fn register() -> anyhow::Result<()> {
+    g3_daemon::signal::register_reload(ReloadAction {})
+}
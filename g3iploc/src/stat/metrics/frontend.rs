@@ -3,20 +3,30 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
-use g3_statsd_client::StatsdClient;
+use g3_daemon::metrics::TAG_KEY_TRANSPORT;
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 
 use crate::FrontendStats;
 
+const TAG_KEY_LISTEN_ADDR: &str = "listen_addr";
+
 pub(crate) fn emit_stats(client: &mut StatsdClient, s: &FrontendStats) {
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_TRANSPORT, s.transport());
+    common_tags.add_tag(TAG_KEY_LISTEN_ADDR, s.listen_addr());
+
     macro_rules! emit_count {
         ($take:ident, $name:literal) => {
             let v = s.$take();
-            client.count(concat!("frontend.", $name), v).send();
+            client
+                .count_with_tags(concat!("frontend.", $name), v, &common_tags)
+                .send();
         };
     }
 
     emit_count!(take_request_total, "request_total");
     emit_count!(take_request_invalid, "request_invalid");
+    emit_count!(take_request_miss, "request_miss");
     emit_count!(take_response_total, "response_total");
     emit_count!(take_response_fail, "response_fail");
 }
@@ -0,0 +1,52 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2024-2025 ByteDance and/or its affiliates.
+ */
+
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+
+const TAG_KEY_DB_TYPE: &str = "db_type";
+const TAG_KEY_IP_FAMILY: &str = "ip_family";
+
+const METRIC_NAME_RECORD_COUNT: &str = "geoip_db.record_count";
+const METRIC_NAME_RELOAD_TIME: &str = "geoip_db.reload_time";
+
+pub(crate) fn emit_stats(client: &mut StatsdClient) {
+    if let Some(db) = g3_geoip_db::store::load_country() {
+        let (v4, v6) = db.len();
+        emit_db_stats(
+            client,
+            "country",
+            v4,
+            v6,
+            crate::config::country_db_reload_time(),
+        );
+    }
+    if let Some(db) = g3_geoip_db::store::load_asn() {
+        let (v4, v6) = db.len();
+        emit_db_stats(client, "asn", v4, v6, crate::config::asn_db_reload_time());
+    }
+}
+
+fn emit_db_stats(
+    client: &mut StatsdClient,
+    db_type: &str,
+    v4_count: usize,
+    v6_count: usize,
+    reload_time: u64,
+) {
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_DB_TYPE, db_type);
+
+    client
+        .gauge_with_tags(METRIC_NAME_RECORD_COUNT, v4_count, &common_tags)
+        .with_tag(TAG_KEY_IP_FAMILY, "ipv4")
+        .send();
+    client
+        .gauge_with_tags(METRIC_NAME_RECORD_COUNT, v6_count, &common_tags)
+        .with_tag(TAG_KEY_IP_FAMILY, "ipv6")
+        .send();
+    client
+        .gauge_with_tags(METRIC_NAME_RELOAD_TIME, reload_time, &common_tags)
+        .send();
+}
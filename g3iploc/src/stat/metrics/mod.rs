@@ -4,3 +4,4 @@
  */
 
 pub(super) mod frontend;
+pub(super) mod geoip;
@@ -17,7 +17,7 @@ mod metrics;
 
 pub(crate) fn spawn_working_thread(
     config: StatsdClientConfig,
-    frontend_stats: Arc<FrontendStats>,
+    frontend_stats: Vec<Arc<FrontendStats>>,
 ) -> anyhow::Result<JoinHandle<()>> {
     let mut client = config
         .build()
@@ -29,7 +29,12 @@ pub(crate) fn spawn_working_thread(
             loop {
                 let instant_start = Instant::now();
 
-                metrics::frontend::emit_stats(&mut client, &frontend_stats);
+                for stats in &frontend_stats {
+                    metrics::frontend::emit_stats(&mut client, stats);
+                }
+
+                metrics::geoip::emit_stats(&mut client);
+                g3_daemon::metrics::reload::emit_stats(&mut client);
 
                 client.flush_sink();
 
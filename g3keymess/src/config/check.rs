@@ -0,0 +1,26 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use g3_daemon::opts::CheckReport;
+
+use super::store::AnyKeyStoreConfig;
+
+/// Check all the loaded config nodes
+pub fn check() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    for c in crate::config::store::get_all() {
+        let r#type = match c.as_ref() {
+            AnyKeyStoreConfig::Local(_) => "local",
+            AnyKeyStoreConfig::Redis(_) => "redis",
+        };
+        report.add_node("store", c.name().as_str(), r#type, Vec::new());
+    }
+    for c in crate::config::server::get_all() {
+        report.add_node("server", c.name().as_str(), "keyless", Vec::new());
+    }
+
+    report
+}
@@ -124,6 +124,8 @@ impl KeyServerRuntime {
         listen_config: &TcpListenConfig,
         server_reload_sender: &broadcast::Sender<ServerReloadCommand>,
     ) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        g3_socket::inherit::check_tcp_listeners(listen_config.address(), 1)?;
         let listener = g3_socket::tcp::new_listen_to(listen_config)?;
         let server_reload_receiver = server_reload_sender.subscribe();
         tokio::spawn(async move {
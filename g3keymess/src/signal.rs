@@ -6,6 +6,7 @@
 use log::{error, info, warn};
 use tokio::sync::Mutex;
 
+use g3_daemon::metrics::reload::ReloadFailureType;
 use g3_daemon::signal::AsyncSignalAction;
 
 static RELOAD_MUTEX: Mutex<()> = Mutex::const_new(());
@@ -14,16 +15,26 @@ async fn do_reload() {
     let _guard = RELOAD_MUTEX.lock().await;
     info!("reloading config");
 
+    let mut failure_type = None;
+
     if let Err(e) = crate::config::reload().await {
         warn!("error reloading config: {e:?}");
         warn!("reload aborted");
+        failure_type = Some(ReloadFailureType::Config);
     }
 
     if let Err(e) = crate::store::reload_all().await {
         error!("failed to reload all key store: {e:?}");
+        failure_type.get_or_insert(ReloadFailureType::Apply);
     }
     if let Err(e) = crate::serve::spawn_all().await {
         error!("failed to reload all servers: {e:?}");
+        failure_type.get_or_insert(ReloadFailureType::Apply);
+    }
+
+    match failure_type {
+        Some(failure_type) => g3_daemon::metrics::reload::add_failure(failure_type),
+        None => g3_daemon::metrics::reload::add_success(),
     }
 
     info!("reload finished");
@@ -38,7 +49,6 @@ impl AsyncSignalAction for QuitAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct OfflineAction {}
 
@@ -48,7 +58,6 @@ impl AsyncSignalAction for OfflineAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct ReloadAction {}
 
@@ -59,9 +68,7 @@ impl AsyncSignalAction for ReloadAction {
 }
 
 pub fn register() -> anyhow::Result<()> {
-    #[cfg(unix)]
     g3_daemon::signal::register_offline(OfflineAction {})?;
-    #[cfg(unix)]
     g3_daemon::signal::register_reload(ReloadAction {})?;
     g3_daemon::signal::register_quit(QuitAction {})
 }
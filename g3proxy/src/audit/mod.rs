@@ -10,7 +10,7 @@ use anyhow::Context;
 use g3_dpi::ProtocolPortMap;
 use g3_icap_client::IcapServiceClient;
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslTicketKey, RollingTicketer};
+use g3_types::net::{OpensslTicketKey, RollingTicketer, TlsClientCertInfo};
 
 use crate::config::audit::AuditorConfig;
 use crate::inspect::tls::TlsInterceptionContext;
@@ -20,11 +20,14 @@ pub use ops::load_all;
 pub(crate) use ops::reload;
 
 mod registry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{foreach_stats, get_names, get_or_insert_default};
 
 mod handle;
 pub(crate) use handle::AuditHandle;
 
+mod stats;
+pub(crate) use stats::AuditorStats;
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
@@ -41,6 +44,7 @@ pub(crate) struct Auditor {
     icap_respmod_service: Option<Arc<IcapServiceClient>>,
     #[cfg(feature = "quic")]
     stream_detour_service: Option<Arc<StreamDetourClient>>,
+    stats: Arc<AuditorStats>,
 }
 
 impl Auditor {
@@ -57,6 +61,7 @@ impl Auditor {
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            stats: Arc::new(AuditorStats::new(name)),
         };
         Arc::new(auditor)
     }
@@ -72,6 +77,7 @@ impl Auditor {
         } else {
             None
         };
+        let stats = Arc::new(AuditorStats::new(config.name()));
         let mut auditor = Auditor {
             config: Arc::new(config),
             server_tcp_portmap,
@@ -81,6 +87,7 @@ impl Auditor {
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            stats,
         };
         auditor.set_agent_clients()?;
         Ok(Arc::new(auditor))
@@ -108,6 +115,7 @@ impl Auditor {
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            stats: self.stats.clone(),
         };
         auditor.set_agent_clients()?;
         Ok(Arc::new(auditor))
@@ -132,6 +140,10 @@ impl Auditor {
         Ok(())
     }
 
+    pub(crate) fn get_stats(&self) -> Arc<AuditorStats> {
+        self.stats.clone()
+    }
+
     pub(crate) fn build_handle(&self) -> anyhow::Result<Arc<AuditHandle>> {
         let mut handle = AuditHandle::new(self);
 
@@ -154,6 +166,8 @@ impl Auditor {
                 client_config,
                 server_config,
                 self.config.tls_stream_dump,
+                self.config.tls_sni_mismatch,
+                self.stats.clone(),
             )?;
             handle.set_tls_interception(ctx);
         }
@@ -165,11 +179,24 @@ impl Auditor {
 #[derive(Clone, Default)]
 pub(crate) struct AuditContext {
     handle: Option<Arc<AuditHandle>>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
 }
 
 impl AuditContext {
     pub(crate) fn new(handle: Option<Arc<AuditHandle>>) -> Self {
-        AuditContext { handle }
+        AuditContext {
+            handle,
+            client_cert: None,
+        }
+    }
+
+    pub(crate) fn set_client_cert(&mut self, cert: Option<Arc<TlsClientCertInfo>>) {
+        self.client_cert = cert;
+    }
+
+    /// The certificate sent by the client if TLS client auth is enabled on the server side
+    pub(crate) fn client_cert(&self) -> Option<&Arc<TlsClientCertInfo>> {
+        self.client_cert.as_ref()
     }
 
     pub(crate) fn set_handle(&mut self, handle: Arc<AuditHandle>) {
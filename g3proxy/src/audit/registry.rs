@@ -10,7 +10,7 @@ use foldhash::fast::FixedState;
 
 use g3_types::metrics::NodeName;
 
-use super::Auditor;
+use super::{Auditor, AuditorStats};
 use crate::audit::AuditorConfig;
 
 static RUNTIME_AUDITOR_REGISTRY: Mutex<HashMap<NodeName, Arc<Auditor>, FixedState>> =
@@ -40,6 +40,16 @@ pub(crate) fn get_names() -> HashSet<NodeName> {
     names
 }
 
+pub(crate) fn foreach_stats<F>(mut f: F)
+where
+    F: FnMut(Arc<AuditorStats>),
+{
+    let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
+    for auditor in ht.values() {
+        f(auditor.get_stats());
+    }
+}
+
 pub(super) fn get_config(name: &NodeName) -> Option<AuditorConfig> {
     let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
     ht.get(name).map(|a| a.config.as_ref().clone())
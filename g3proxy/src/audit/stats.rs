@@ -0,0 +1,43 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+pub(crate) struct AuditorStats {
+    id: StatId,
+    name: NodeName,
+    tls_sni_mismatch: AtomicU64,
+}
+
+impl AuditorStats {
+    pub(crate) fn new(name: &NodeName) -> Self {
+        AuditorStats {
+            id: StatId::new_unique(),
+            name: name.clone(),
+            tls_sni_mismatch: AtomicU64::new(0),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    pub(crate) fn add_tls_sni_mismatch(&self) {
+        self.tls_sni_mismatch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn tls_sni_mismatch(&self) -> u64 {
+        self.tls_sni_mismatch.load(Ordering::Relaxed)
+    }
+}
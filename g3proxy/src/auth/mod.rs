@@ -33,6 +33,9 @@ use site::UserSites;
 mod user;
 pub(crate) use user::{User, UserContext};
 
+mod quota;
+pub(crate) use quota::UserTrafficQuota;
+
 mod stats;
 pub(crate) use stats::{
     UserForbiddenSnapshot, UserForbiddenStats, UserRequestSnapshot, UserRequestStats,
@@ -0,0 +1,242 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+
+use g3_io_ext::{GlobalLimitGroup, GlobalStreamLimit, StreamLimitAction};
+
+use crate::config::auth::{UserTrafficQuotaConfig, UserTrafficQuotaWindow};
+
+/// Recheck the quota at least in this interval when exhausted
+const EXHAUSTED_MAX_DELAY_MILLIS: u64 = 1000;
+
+trait QuotaWindowExt {
+    fn window_id(&self, now: &DateTime<Utc>) -> u64;
+    fn next_start_millis(&self, now: &DateTime<Utc>) -> i64;
+}
+
+impl QuotaWindowExt for UserTrafficQuotaWindow {
+    fn window_id(&self, now: &DateTime<Utc>) -> u64 {
+        match self {
+            UserTrafficQuotaWindow::Monthly => now.year() as u64 * 12 + now.month0() as u64,
+            UserTrafficQuotaWindow::Fixed(d) => now.timestamp() as u64 / d.as_secs(),
+        }
+    }
+
+    fn next_start_millis(&self, now: &DateTime<Utc>) -> i64 {
+        match self {
+            UserTrafficQuotaWindow::Monthly => {
+                let (year, month) = if now.month() == 12 {
+                    (now.year() + 1, 1)
+                } else {
+                    (now.year(), now.month() + 1)
+                };
+                Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .single()
+                    .map(|t| t.timestamp_millis())
+                    .unwrap_or(i64::MAX)
+            }
+            UserTrafficQuotaWindow::Fixed(d) => {
+                let secs = d.as_secs();
+                ((now.timestamp() as u64 / secs + 1) * secs * 1000) as i64
+            }
+        }
+    }
+}
+
+/// The traffic quota of a user, shared by all tasks of this user
+pub(crate) struct UserTrafficQuota {
+    config: UserTrafficQuotaConfig,
+    window_id: AtomicU64,
+    used: AtomicU64,
+}
+
+impl UserTrafficQuota {
+    pub(crate) fn new(config: &UserTrafficQuotaConfig) -> Self {
+        let window_id = config.window.window_id(&Utc::now());
+        UserTrafficQuota {
+            config: config.clone(),
+            window_id: AtomicU64::new(window_id),
+            used: AtomicU64::new(config.used.unwrap_or_default()),
+        }
+    }
+
+    pub(crate) fn new_for_reload(self: &Arc<Self>, config: &UserTrafficQuotaConfig) -> Arc<Self> {
+        if self.config.eq(config) {
+            return self.clone();
+        }
+
+        let new = UserTrafficQuota::new(config);
+        if config.used.is_none() || config.used == self.config.used {
+            // keep the used bytes if not changed by the config
+            if config.window == self.config.window {
+                new.window_id
+                    .store(self.window_id.load(Ordering::Relaxed), Ordering::Relaxed);
+                new.used
+                    .store(self.used.load(Ordering::Relaxed), Ordering::Relaxed);
+            } else {
+                new.used.store(0, Ordering::Relaxed);
+            }
+        }
+        Arc::new(new)
+    }
+
+    pub(crate) fn used(&self) -> u64 {
+        self.roll_window(&Utc::now());
+        self.used.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_exhausted(&self) -> bool {
+        self.used() >= self.config.limit
+    }
+
+    fn roll_window(&self, now: &DateTime<Utc>) {
+        let window_id = self.config.window.window_id(now);
+        let old_id = self.window_id.load(Ordering::Relaxed);
+        if old_id != window_id
+            && self
+                .window_id
+                .compare_exchange(old_id, window_id, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.used.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn check_at(&self, now: &DateTime<Utc>, to_advance: usize) -> StreamLimitAction {
+        self.roll_window(now);
+
+        let size = to_advance as u64;
+        let prev = self.used.fetch_add(size, Ordering::Relaxed);
+        if prev >= self.config.limit {
+            self.release(to_advance);
+            let wait = self.config.window.next_start_millis(now) - now.timestamp_millis();
+            return StreamLimitAction::DelayFor(
+                (wait.max(1) as u64).min(EXHAUSTED_MAX_DELAY_MILLIS),
+            );
+        }
+
+        let left = self.config.limit - prev;
+        if left < size {
+            self.release((size - left) as usize);
+            StreamLimitAction::AdvanceBy(left as usize)
+        } else {
+            StreamLimitAction::AdvanceBy(to_advance)
+        }
+    }
+}
+
+impl GlobalStreamLimit for UserTrafficQuota {
+    fn group(&self) -> GlobalLimitGroup {
+        GlobalLimitGroup::User
+    }
+
+    fn check(&self, to_advance: usize) -> StreamLimitAction {
+        self.check_at(&Utc::now(), to_advance)
+    }
+
+    fn release(&self, size: usize) {
+        let _ = self
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |v| {
+                Some(v.saturating_sub(size as u64))
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn quota(limit: u64, window: UserTrafficQuotaWindow) -> UserTrafficQuota {
+        UserTrafficQuota::new(&UserTrafficQuotaConfig {
+            limit,
+            window,
+            used: None,
+        })
+    }
+
+    #[test]
+    fn window() {
+        let now = Utc.with_ymd_and_hms(2025, 12, 31, 23, 59, 59).unwrap();
+        let monthly = UserTrafficQuotaWindow::Monthly;
+        assert_eq!(monthly.window_id(&now), 2025 * 12 + 11);
+        assert_eq!(
+            monthly.next_start_millis(&now),
+            now.timestamp_millis() + 1000
+        );
+
+        let daily = UserTrafficQuotaWindow::Fixed(Duration::from_secs(86400));
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        assert_eq!(
+            daily.next_start_millis(&now),
+            now.timestamp_millis() + 12 * 3600 * 1000
+        );
+        let next_day = Utc.with_ymd_and_hms(2025, 6, 2, 0, 0, 0).unwrap();
+        assert_eq!(daily.window_id(&next_day), daily.window_id(&now) + 1);
+    }
+
+    #[test]
+    fn check_and_release() {
+        let now = Utc::now();
+        let quota = quota(100, UserTrafficQuotaWindow::Monthly);
+
+        assert_eq!(quota.check_at(&now, 60), StreamLimitAction::AdvanceBy(60));
+        assert_eq!(quota.check_at(&now, 60), StreamLimitAction::AdvanceBy(40));
+        assert!(quota.is_exhausted());
+        assert!(matches!(
+            quota.check_at(&now, 1),
+            StreamLimitAction::DelayFor(_)
+        ));
+
+        quota.release(10);
+        assert_eq!(quota.used(), 90);
+        assert_eq!(quota.check_at(&now, 60), StreamLimitAction::AdvanceBy(10));
+    }
+
+    #[test]
+    fn reset_window() {
+        let quota = quota(100, UserTrafficQuotaWindow::Fixed(Duration::from_secs(60)));
+        let now = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 30).unwrap();
+        quota
+            .window_id
+            .store(quota.config.window.window_id(&now), Ordering::Relaxed);
+
+        assert_eq!(quota.check_at(&now, 100), StreamLimitAction::AdvanceBy(100));
+        assert_eq!(
+            quota.check_at(&now, 1),
+            StreamLimitAction::DelayFor(EXHAUSTED_MAX_DELAY_MILLIS)
+        );
+
+        let next = now + chrono::TimeDelta::seconds(30);
+        assert_eq!(quota.check_at(&next, 1), StreamLimitAction::AdvanceBy(1));
+    }
+
+    #[test]
+    fn reload() {
+        let config = UserTrafficQuotaConfig {
+            limit: 100,
+            window: UserTrafficQuotaWindow::Monthly,
+            used: Some(10),
+        };
+        let quota = Arc::new(UserTrafficQuota::new(&config));
+        assert_eq!(quota.used(), 10);
+        let _ = quota.check_at(&Utc::now(), 20);
+
+        let mut new_config = config.clone();
+        new_config.limit = 200;
+        let new = quota.new_for_reload(&new_config);
+        assert_eq!(new.config.limit, 200);
+        assert_eq!(new.used(), 30);
+
+        new_config.used = Some(50);
+        let new = new.new_for_reload(&new_config);
+        assert_eq!(new.used(), 50);
+    }
+}
@@ -16,7 +16,7 @@ use radix_trie::Trie;
 use rustc_hash::FxHashMap;
 
 use g3_types::metrics::{MetricTagMap, NodeName};
-use g3_types::net::{Host, OpensslClientConfig, UpstreamAddr};
+use g3_types::net::{Host, HttpHeaderRuleSet, OpensslClientConfig, UpstreamAddr};
 use g3_types::resolve::ResolveStrategy;
 
 use super::stats::{UserSiteDurationRecorder, UserSiteStats};
@@ -112,6 +112,16 @@ impl UserSite {
         self.config.http_rsp_hdr_recv_timeout
     }
 
+    #[inline]
+    pub(crate) fn http_req_header_rules(&self) -> &HttpHeaderRuleSet {
+        &self.config.http_req_header_rules
+    }
+
+    #[inline]
+    pub(crate) fn http_rsp_header_rules(&self) -> &HttpHeaderRuleSet {
+        &self.config.http_rsp_header_rules
+    }
+
     pub(crate) fn fetch_duration_recorder(
         &self,
         user_type: UserType,
@@ -30,6 +30,7 @@ pub(crate) struct UserForbiddenStats {
     dest_denied: AtomicU64,
     ip_blocked: AtomicU64,
     ua_blocked: AtomicU64,
+    quota_exceeded: AtomicU64,
     log_skipped: AtomicU64,
 }
 
@@ -45,6 +46,7 @@ pub(crate) struct UserForbiddenSnapshot {
     pub(crate) dest_denied: u64,
     pub(crate) ip_blocked: u64,
     pub(crate) ua_blocked: u64,
+    pub(crate) quota_exceeded: u64,
     pub(crate) log_skipped: u64,
 }
 
@@ -73,6 +75,7 @@ impl UserForbiddenStats {
             dest_denied: Default::default(),
             ip_blocked: Default::default(),
             ua_blocked: Default::default(),
+            quota_exceeded: Default::default(),
             log_skipped: Default::default(),
         }
     }
@@ -119,6 +122,7 @@ impl UserForbiddenStats {
             dest_denied: self.dest_denied.load(Ordering::Relaxed),
             ip_blocked: self.ip_blocked.load(Ordering::Relaxed),
             ua_blocked: self.ua_blocked.load(Ordering::Relaxed),
+            quota_exceeded: self.quota_exceeded.load(Ordering::Relaxed),
             log_skipped: self.log_skipped.load(Ordering::Relaxed),
         }
     }
@@ -163,6 +167,10 @@ impl UserForbiddenStats {
         self.ua_blocked.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub(crate) fn add_quota_exceeded(&self) {
+        self.quota_exceeded.fetch_add(1, Ordering::Relaxed);
+    }
+
     pub(crate) fn add_log_skipped(&self) {
         self.log_skipped.fetch_add(1, Ordering::Relaxed);
     }
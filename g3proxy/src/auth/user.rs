@@ -25,7 +25,7 @@ use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
 
 use super::{
     UserForbiddenStats, UserRequestStats, UserSite, UserSiteDurationRecorder, UserSiteStats,
-    UserSites, UserTrafficStats, UserType, UserUpstreamTrafficStats,
+    UserSites, UserTrafficQuota, UserTrafficStats, UserType, UserUpstreamTrafficStats,
 };
 use crate::config::auth::{UserAuditConfig, UserConfig};
 
@@ -45,6 +45,7 @@ pub(crate) struct User {
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
     resolve_redirection: Option<ResolveRedirection>,
     log_rate_limit: Option<Arc<RateLimiter<GlobalRateLimitState>>>,
+    traffic_quota: Option<Arc<UserTrafficQuota>>,
     forbid_stats: Arc<Mutex<HashMap<NodeName, Arc<UserForbiddenStats>>>>,
     req_stats: Arc<Mutex<HashMap<NodeName, Arc<UserRequestStats>>>>,
     io_stats: Arc<Mutex<HashMap<NodeName, Arc<UserTrafficStats>>>>,
@@ -59,6 +60,16 @@ impl User {
         self.config.task_idle_max_count
     }
 
+    #[inline]
+    pub(crate) fn upgraded_task_max_idle_count(&self) -> Option<usize> {
+        self.config.upgraded_task_idle_max_count
+    }
+
+    #[inline]
+    pub(crate) fn task_max_duration(&self) -> Option<Duration> {
+        self.config.task_max_duration
+    }
+
     fn update_ingress_net_filter(&mut self) {
         self.ingress_net_filter = self
             .config
@@ -129,6 +140,11 @@ impl User {
             None
         };
 
+        let traffic_quota = config
+            .traffic_quota
+            .as_ref()
+            .map(|c| Arc::new(UserTrafficQuota::new(c)));
+
         let is_expired = AtomicBool::new(config.is_expired(datetime_now));
         let is_blocked = Arc::new(AtomicBool::new(config.block_and_delay.is_some()));
 
@@ -151,6 +167,7 @@ impl User {
             dst_host_filter: None,
             resolve_redirection: None,
             log_rate_limit,
+            traffic_quota,
             forbid_stats: Arc::new(Mutex::new(HashMap::default())),
             req_stats: Arc::new(Mutex::new(HashMap::default())),
             io_stats: Arc::new(Mutex::new(HashMap::default())),
@@ -277,6 +294,13 @@ impl User {
             None
         };
 
+        // keep the used bytes in the old quota when possible
+        let traffic_quota = match (&config.traffic_quota, &self.traffic_quota) {
+            (Some(c), Some(old)) => Some(old.new_for_reload(c)),
+            (Some(c), None) => Some(Arc::new(UserTrafficQuota::new(c))),
+            (None, _) => None,
+        };
+
         // use the expired state from new config for new tasks
         let is_expired = AtomicBool::new(config.is_expired(datetime_now));
 
@@ -309,6 +333,7 @@ impl User {
             dst_host_filter: None,
             resolve_redirection: None,
             log_rate_limit,
+            traffic_quota,
             forbid_stats: Arc::clone(&self.forbid_stats),
             req_stats: Arc::clone(&self.req_stats),
             io_stats: Arc::clone(&self.io_stats),
@@ -550,6 +575,16 @@ impl User {
         Ok(())
     }
 
+    fn check_traffic_quota(&self, forbid_stats: &Arc<UserForbiddenStats>) -> Result<(), ()> {
+        if let Some(quota) = &self.traffic_quota
+            && quota.is_exhausted()
+        {
+            forbid_stats.add_quota_exceeded();
+            return Err(());
+        }
+        Ok(())
+    }
+
     fn acquire_request_semaphore(
         &self,
         forbid_stats: &Arc<UserForbiddenStats>,
@@ -647,6 +682,11 @@ impl User {
         self.config.log_uri_max_chars
     }
 
+    #[inline]
+    pub(crate) fn traffic_quota(&self) -> Option<&Arc<UserTrafficQuota>> {
+        self.traffic_quota.as_ref()
+    }
+
     #[inline]
     pub(crate) fn tcp_all_upload_speed_limit(&self) -> Option<&Arc<GlobalStreamLimiter>> {
         self.tcp_all_upload_speed_limit.as_ref()
@@ -868,6 +908,11 @@ impl UserContext {
             .check_rate_limit(self.reused_client_connection, &self.forbid_stats)
     }
 
+    #[inline]
+    pub(crate) fn check_traffic_quota(&self) -> Result<(), ()> {
+        self.user.check_traffic_quota(&self.forbid_stats)
+    }
+
     #[inline]
     pub(crate) fn acquire_request_semaphore(&self) -> Result<GaugeSemaphorePermit, ()> {
         self.user.acquire_request_semaphore(&self.forbid_stats)
@@ -904,4 +949,20 @@ impl UserContext {
             .and_then(|site| site.http_rsp_hdr_recv_timeout())
             .or(self.user.config.http_rsp_hdr_recv_timeout)
     }
+
+    /// Apply the user level and then the user site level request header rules
+    pub(crate) fn apply_http_req_header_rules(&self, headers: &mut HttpHeaderMap) {
+        self.user.config.http_req_header_rules.apply(headers);
+        if let Some(site) = &self.user_site {
+            site.http_req_header_rules().apply(headers);
+        }
+    }
+
+    /// Apply the user level and then the user site level response header rules
+    pub(crate) fn apply_http_rsp_header_rules(&self, headers: &mut HttpHeaderMap) {
+        self.user.config.http_rsp_header_rules.apply(headers);
+        if let Some(site) = &self.user_site {
+            site.http_rsp_header_rules().apply(headers);
+        }
+    }
 }
@@ -26,6 +26,7 @@ use g3_yaml::YamlDocPosition;
 
 #[cfg(feature = "quic")]
 use super::AuditStreamDetourConfig;
+use super::TlsSniMismatchConfig;
 
 #[derive(Clone)]
 pub(crate) struct AuditorConfig {
@@ -39,6 +40,7 @@ pub(crate) struct AuditorConfig {
     pub(crate) tls_interception_client: OpensslInterceptionClientConfigBuilder,
     pub(crate) tls_interception_server: OpensslInterceptionServerConfigBuilder,
     pub(crate) tls_stream_dump: Option<StreamDumpConfig>,
+    pub(crate) tls_sni_mismatch: Option<TlsSniMismatchConfig>,
     pub(crate) log_uri_max_chars: usize,
     pub(crate) h1_interception: H1InterceptionConfig,
     pub(crate) h2_inspect_policy: ProtocolInspectPolicyBuilder,
@@ -76,6 +78,7 @@ impl AuditorConfig {
             tls_interception_client: Default::default(),
             tls_interception_server: Default::default(),
             tls_stream_dump: None,
+            tls_sni_mismatch: None,
             log_uri_max_chars: 1024,
             h1_interception: Default::default(),
             h2_inspect_policy: Default::default(),
@@ -175,6 +178,12 @@ impl AuditorConfig {
                 self.tls_stream_dump = Some(dump);
                 Ok(())
             }
+            "tls_sni_mismatch" => {
+                let config = TlsSniMismatchConfig::parse_yaml(v)
+                    .context(format!("invalid tls sni mismatch config value for key {k}"))?;
+                self.tls_sni_mismatch = Some(config);
+                Ok(())
+            }
             "log_uri_max_chars" | "uri_log_max_chars" => {
                 self.log_uri_max_chars = g3_yaml::value::as_usize(v)
                     .context(format!("invalid usize value for key {k}"))?;
@@ -16,6 +16,9 @@ pub(crate) use registry::{clear, get_all};
 mod auditor;
 pub(crate) use auditor::AuditorConfig;
 
+mod tls_sni;
+pub(crate) use tls_sni::{TlsSniMismatchAction, TlsSniMismatchConfig};
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
@@ -0,0 +1,158 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TlsSniMatchMode {
+    /// the SNI should be the same as the upstream domain
+    #[default]
+    Exact,
+    /// the SNI should be the same as or a child domain of the upstream domain
+    Suffix,
+}
+
+impl FromStr for TlsSniMatchMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "exact" => Ok(TlsSniMatchMode::Exact),
+            "suffix" | "child" => Ok(TlsSniMatchMode::Suffix),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum TlsSniMismatchAction {
+    #[default]
+    Log,
+    Block,
+}
+
+impl FromStr for TlsSniMismatchAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "log" | "log_only" => Ok(TlsSniMismatchAction::Log),
+            "block" | "reject" => Ok(TlsSniMismatchAction::Block),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TlsSniMismatchConfig {
+    pub(crate) match_mode: TlsSniMatchMode,
+    pub(crate) action: TlsSniMismatchAction,
+}
+
+impl TlsSniMismatchConfig {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut config = TlsSniMismatchConfig::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "match" | "match_mode" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        config.match_mode = TlsSniMatchMode::from_str(&s)
+                            .map_err(|_| anyhow!("invalid tls sni match mode {s}"))?;
+                        Ok(())
+                    }
+                    "action" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        config.action = TlsSniMismatchAction::from_str(&s)
+                            .map_err(|_| anyhow!("invalid tls sni mismatch action {s}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::String(_) => {
+                let s = g3_yaml::value::as_string(value)?;
+                config.action = TlsSniMismatchAction::from_str(&s)
+                    .map_err(|_| anyhow!("invalid tls sni mismatch action {s}"))
+                    .context("invalid string value")?;
+            }
+            _ => return Err(anyhow!("invalid yaml value type")),
+        }
+        Ok(config)
+    }
+
+    /// Check whether the SNI sent by the client matches the upstream domain
+    pub(crate) fn is_matched(&self, sni: &str, domain: &str) -> bool {
+        let sni = sni.strip_suffix('.').unwrap_or(sni);
+        let domain = domain.strip_suffix('.').unwrap_or(domain);
+        if sni.eq_ignore_ascii_case(domain) {
+            return true;
+        }
+
+        match self.match_mode {
+            TlsSniMatchMode::Exact => false,
+            TlsSniMatchMode::Suffix => {
+                let Some(dot_offset) = sni.len().checked_sub(domain.len() + 1) else {
+                    return false;
+                };
+                dot_offset > 0
+                    && sni.as_bytes()[dot_offset] == b'.'
+                    && sni
+                        .get(dot_offset + 1..)
+                        .map(|s| s.eq_ignore_ascii_case(domain))
+                        .unwrap_or(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn exact_match() {
+        let config = TlsSniMismatchConfig::default();
+        assert!(config.is_matched("www.example.com", "www.example.com"));
+        assert!(config.is_matched("WWW.Example.com", "www.example.com."));
+        assert!(!config.is_matched("www.example.com", "example.com"));
+        assert!(!config.is_matched("example.net", "example.com"));
+    }
+
+    #[test]
+    fn suffix_match() {
+        let config = TlsSniMismatchConfig {
+            match_mode: TlsSniMatchMode::Suffix,
+            action: TlsSniMismatchAction::Block,
+        };
+        assert!(config.is_matched("example.com", "example.com"));
+        assert!(config.is_matched("www.example.com", "example.com"));
+        assert!(config.is_matched("a.b.Example.COM", "example.com"));
+        assert!(!config.is_matched("wwwexample.com", "example.com"));
+        assert!(!config.is_matched(".example.com", "example.com"));
+        assert!(!config.is_matched("example.com", "www.example.com"));
+        assert!(!config.is_matched("www.example.net", "example.com"));
+    }
+
+    #[test]
+    fn parse_yaml() {
+        let doc = YamlLoader::load_from_str("match: suffix\naction: block").unwrap();
+        let config = TlsSniMismatchConfig::parse_yaml(&doc[0]).unwrap();
+        assert_eq!(config.match_mode, TlsSniMatchMode::Suffix);
+        assert_eq!(config.action, TlsSniMismatchAction::Block);
+
+        let config = TlsSniMismatchConfig::parse_yaml(&Yaml::String("log".to_string())).unwrap();
+        assert_eq!(config.match_mode, TlsSniMatchMode::Exact);
+        assert_eq!(config.action, TlsSniMismatchAction::Log);
+
+        let doc = YamlLoader::load_from_str("match: prefix").unwrap();
+        assert!(TlsSniMismatchConfig::parse_yaml(&doc[0]).is_err());
+        assert!(TlsSniMismatchConfig::parse_yaml(&Yaml::Integer(1)).is_err());
+    }
+}
@@ -20,7 +20,7 @@ use g3_types::limit::{
 };
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    HttpKeepAliveConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts,
+    HttpHeaderRuleSet, HttpKeepAliveConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts,
     TcpSockSpeedLimitConfig, UdpMiscSockOpts, UdpSockSpeedLimitConfig,
 };
 use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
@@ -36,6 +36,9 @@ pub(crate) use site::UserSiteConfig;
 mod audit;
 pub(crate) use audit::UserAuditConfig;
 
+mod quota;
+pub(crate) use quota::{UserTrafficQuotaConfig, UserTrafficQuotaWindow};
+
 mod json;
 mod yaml;
 
@@ -54,6 +57,8 @@ pub(crate) struct UserConfig {
     udp_client_misc_opts: Option<UdpMiscSockOpts>,
     pub(crate) http_upstream_keepalive: HttpKeepAliveConfig,
     pub(crate) http_rsp_hdr_recv_timeout: Option<Duration>,
+    pub(crate) http_req_header_rules: HttpHeaderRuleSet,
+    pub(crate) http_rsp_header_rules: HttpHeaderRuleSet,
     pub(crate) request_alive_max: usize,
     pub(crate) request_rate_limit: Option<RateLimitQuota>,
     pub(crate) connection_rate_limit: Option<RateLimitQuota>,
@@ -64,6 +69,7 @@ pub(crate) struct UserConfig {
     pub(crate) udp_all_upload_speed_limit: Option<GlobalDatagramSpeedLimitConfig>,
     pub(crate) udp_all_download_speed_limit: Option<GlobalDatagramSpeedLimitConfig>,
     pub(crate) log_rate_limit: Option<RateLimitQuota>,
+    pub(crate) traffic_quota: Option<UserTrafficQuotaConfig>,
     pub(crate) log_uri_max_chars: Option<usize>,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) proxy_request_filter: Option<AclProxyRequestRule>,
@@ -73,6 +79,8 @@ pub(crate) struct UserConfig {
     pub(crate) resolve_strategy: Option<ResolveStrategy>,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
     pub(crate) task_idle_max_count: Option<usize>,
+    pub(crate) upgraded_task_idle_max_count: Option<usize>,
+    pub(crate) task_max_duration: Option<Duration>,
     pub(crate) socks_use_udp_associate: bool,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     pub(crate) explicit_sites: BTreeMap<NodeName, Arc<UserSiteConfig>>,
@@ -94,6 +102,8 @@ impl Default for UserConfig {
             udp_client_misc_opts: None,
             http_upstream_keepalive: Default::default(),
             http_rsp_hdr_recv_timeout: None,
+            http_req_header_rules: HttpHeaderRuleSet::default(),
+            http_rsp_header_rules: HttpHeaderRuleSet::default(),
             request_alive_max: 0,
             request_rate_limit: None,
             connection_rate_limit: None,
@@ -104,6 +114,7 @@ impl Default for UserConfig {
             udp_all_upload_speed_limit: None,
             udp_all_download_speed_limit: None,
             log_rate_limit: None,
+            traffic_quota: None,
             log_uri_max_chars: None,
             ingress_net_filter: None,
             proxy_request_filter: None,
@@ -113,6 +124,8 @@ impl Default for UserConfig {
             resolve_strategy: None,
             resolve_redirection: None,
             task_idle_max_count: None,
+            upgraded_task_idle_max_count: None,
+            task_max_duration: None,
             socks_use_udp_associate: false,
             egress_path_selection: None,
             explicit_sites: BTreeMap::new(),
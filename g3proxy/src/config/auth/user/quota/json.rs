@@ -0,0 +1,50 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use serde_json::Value;
+
+use super::UserTrafficQuotaConfig;
+
+impl UserTrafficQuotaConfig {
+    pub(crate) fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        let mut config = UserTrafficQuotaConfig::default();
+        match v {
+            Value::Object(map) => {
+                for (k, v) in map {
+                    match g3_json::key::normalize(k).as_str() {
+                        "limit" => {
+                            config.limit = g3_json::humanize::as_u64(v)
+                                .context(format!("invalid humanize u64 value for key {k}"))?;
+                        }
+                        "window" | "reset_window" => {
+                            if let Value::String(s) = v
+                                && s.chars().all(|c| c.is_ascii_alphabetic())
+                            {
+                                config.set_window(s)?;
+                            } else {
+                                let d = g3_json::humanize::as_duration(v).context(format!(
+                                    "invalid humanize duration value for key {k}"
+                                ))?;
+                                config.set_window_duration(d)?;
+                            }
+                        }
+                        "used" => {
+                            let used = g3_json::humanize::as_u64(v)
+                                .context(format!("invalid humanize u64 value for key {k}"))?;
+                            config.used = Some(used);
+                        }
+                        _ => return Err(anyhow!("invalid key {k}")),
+                    }
+                }
+            }
+            _ => {
+                config.limit = g3_json::humanize::as_u64(v)?;
+            }
+        }
+        config.check()?;
+        Ok(config)
+    }
+}
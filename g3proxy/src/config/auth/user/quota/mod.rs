@@ -0,0 +1,70 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+mod json;
+mod yaml;
+
+/// The reset window of the traffic quota, aligned to UTC
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum UserTrafficQuotaWindow {
+    #[default]
+    Monthly,
+    Fixed(Duration),
+}
+
+impl FromStr for UserTrafficQuotaWindow {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "monthly" | "month" => Ok(UserTrafficQuotaWindow::Monthly),
+            "weekly" | "week" => Ok(UserTrafficQuotaWindow::Fixed(Duration::from_secs(
+                7 * 24 * 3600,
+            ))),
+            "daily" | "day" => Ok(UserTrafficQuotaWindow::Fixed(Duration::from_secs(
+                24 * 3600,
+            ))),
+            "hourly" | "hour" => Ok(UserTrafficQuotaWindow::Fixed(Duration::from_secs(3600))),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct UserTrafficQuotaConfig {
+    /// max bytes of client side tcp traffic in both directions
+    pub(crate) limit: u64,
+    pub(crate) window: UserTrafficQuotaWindow,
+    /// the used bytes that will be set when this value changes
+    pub(crate) used: Option<u64>,
+}
+
+impl UserTrafficQuotaConfig {
+    fn set_window(&mut self, s: &str) -> anyhow::Result<()> {
+        self.window = UserTrafficQuotaWindow::from_str(s)
+            .map_err(|_| anyhow!("invalid traffic quota window {s}"))?;
+        Ok(())
+    }
+
+    fn set_window_duration(&mut self, d: Duration) -> anyhow::Result<()> {
+        if d.as_secs() == 0 {
+            return Err(anyhow!("traffic quota window should be at least 1s"));
+        }
+        self.window = UserTrafficQuotaWindow::Fixed(d);
+        Ok(())
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.limit == 0 {
+            return Err(anyhow!("traffic quota limit is not set"));
+        }
+        Ok(())
+    }
+}
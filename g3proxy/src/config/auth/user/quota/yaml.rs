@@ -0,0 +1,49 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use super::UserTrafficQuotaConfig;
+
+impl UserTrafficQuotaConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = UserTrafficQuotaConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "limit" => {
+                        config.limit = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "window" | "reset_window" => {
+                        if let Yaml::String(s) = v
+                            && s.chars().all(|c| c.is_ascii_alphabetic())
+                        {
+                            config.set_window(s)
+                        } else {
+                            let d = g3_yaml::humanize::as_duration(v)
+                                .context(format!("invalid humanize duration value for key {k}"))?;
+                            config.set_window_duration(d)
+                        }
+                    }
+                    "used" => {
+                        let used = g3_yaml::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        config.used = Some(used);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                config.limit = g3_yaml::humanize::as_u64(v)?;
+            }
+        }
+        config.check()?;
+        Ok(config)
+    }
+}
@@ -82,6 +82,16 @@ impl UserSiteConfig {
                 self.http_rsp_hdr_recv_timeout = Some(timeout);
                 Ok(())
             }
+            "http_req_header_rules" => {
+                self.http_req_header_rules = g3_json::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
+            "http_rsp_header_rules" => {
+                self.http_rsp_header_rules = g3_json::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
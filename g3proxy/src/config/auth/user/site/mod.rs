@@ -13,7 +13,7 @@ use ip_network::IpNetwork;
 
 use g3_histogram::HistogramMetricsConfig;
 use g3_types::metrics::NodeName;
-use g3_types::net::{Host, OpensslClientConfigBuilder};
+use g3_types::net::{Host, HttpHeaderRuleSet, OpensslClientConfigBuilder};
 use g3_types::resolve::ResolveStrategy;
 
 mod json;
@@ -31,6 +31,8 @@ pub(crate) struct UserSiteConfig {
     pub(crate) duration_stats: HistogramMetricsConfig,
     pub(crate) tls_client: Option<OpensslClientConfigBuilder>,
     pub(crate) http_rsp_hdr_recv_timeout: Option<Duration>,
+    pub(crate) http_req_header_rules: HttpHeaderRuleSet,
+    pub(crate) http_rsp_header_rules: HttpHeaderRuleSet,
 }
 
 impl UserSiteConfig {
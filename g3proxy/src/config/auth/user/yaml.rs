@@ -11,7 +11,7 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_yaml::YamlDocPosition;
 
-use super::{PasswordToken, UserConfig, UserSiteConfig};
+use super::{PasswordToken, UserConfig, UserSiteConfig, UserTrafficQuotaConfig};
 
 impl UserConfig {
     pub(crate) fn parse_yaml(
@@ -146,6 +146,16 @@ impl UserConfig {
                 self.http_rsp_hdr_recv_timeout = Some(timeout);
                 Ok(())
             }
+            "http_req_header_rules" => {
+                self.http_req_header_rules = g3_yaml::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
+            "http_rsp_header_rules" => {
+                self.http_rsp_header_rules = g3_yaml::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
             "tcp_conn_rate_limit" | "tcp_conn_limit_quota" => {
                 warn!("deprecated config key {k}, please use 'connection_rate_limit' instead");
                 self.set_yaml("connection_rate_limit", v, position)
@@ -210,6 +220,13 @@ impl UserConfig {
                 self.resolve_redirection = Some(builder);
                 Ok(())
             }
+            "traffic_quota" => {
+                let quota = UserTrafficQuotaConfig::parse_yaml(v).context(format!(
+                    "invalid user traffic quota config value for key {k}"
+                ))?;
+                self.traffic_quota = Some(quota);
+                Ok(())
+            }
             "log_rate_limit" | "log_limit_quota" => {
                 let quota = g3_yaml::value::as_rate_limit_quota(v)
                     .context(format!("invalid request quota value for key {k}"))?;
@@ -228,6 +245,18 @@ impl UserConfig {
                 self.task_idle_max_count = Some(count);
                 Ok(())
             }
+            "upgraded_task_idle_max_count" => {
+                let count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                self.upgraded_task_idle_max_count = Some(count);
+                Ok(())
+            }
+            "task_max_duration" => {
+                let duration = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.task_max_duration = Some(duration);
+                Ok(())
+            }
             "socks_use_udp_associate" => {
                 self.socks_use_udp_associate = g3_yaml::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
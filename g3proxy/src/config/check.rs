@@ -0,0 +1,135 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::Context;
+
+use g3_daemon::opts::CheckReport;
+use g3_types::metrics::NodeName;
+
+/// Check all the loaded config nodes and the references between them
+pub fn check() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let auditors: BTreeSet<NodeName> = crate::config::audit::get_all()
+        .iter()
+        .map(|c| c.name().clone())
+        .collect();
+    for name in &auditors {
+        report.add_node("auditor", name.as_str(), "", Vec::new());
+    }
+
+    let user_groups: BTreeSet<NodeName> = crate::config::auth::get_all()
+        .iter()
+        .map(|c| c.name().clone())
+        .collect();
+    for name in &user_groups {
+        report.add_node("user_group", name.as_str(), "", Vec::new());
+    }
+
+    let resolvers = check_resolver(&mut report);
+    let escapers = check_escaper(&mut report, &resolvers);
+    check_server(&mut report, &escapers, &user_groups, &auditors);
+
+    report
+}
+
+fn check_resolver(report: &mut CheckReport) -> BTreeSet<NodeName> {
+    let all_resolver = match crate::config::resolver::get_all_sorted()
+        .context("failed to get all resolver config")
+    {
+        Ok(v) => v,
+        Err(e) => {
+            report.add_error("resolver", e);
+            return BTreeSet::new();
+        }
+    };
+
+    let names: BTreeSet<NodeName> = all_resolver.iter().map(|c| c.name().clone()).collect();
+    for c in &all_resolver {
+        let mut errors = Vec::new();
+        if let Some(d) = c.dependent_resolver() {
+            for v in d {
+                if !names.contains(&v) {
+                    errors.push(format!("dependent resolver {v} not found"));
+                }
+            }
+        }
+        report.add_node("resolver", c.name().as_str(), c.r#type(), errors);
+    }
+    names
+}
+
+fn check_escaper(report: &mut CheckReport, resolvers: &BTreeSet<NodeName>) -> BTreeSet<NodeName> {
+    let all_escaper = match crate::config::escaper::get_all_sorted()
+        .context("failed to get all escaper config")
+    {
+        Ok(v) => v,
+        Err(e) => {
+            report.add_error("escaper", e);
+            return BTreeSet::new();
+        }
+    };
+
+    let names: BTreeSet<NodeName> = all_escaper.iter().map(|c| c.name().clone()).collect();
+    for c in &all_escaper {
+        let mut errors = Vec::new();
+        if let Some(d) = c.dependent_escaper() {
+            for v in d {
+                if !names.contains(&v) {
+                    errors.push(format!("dependent escaper {v} not found"));
+                }
+            }
+        }
+        let r = c.resolver();
+        if !r.is_empty() && !resolvers.contains(r) {
+            errors.push(format!("resolver {r} not found"));
+        }
+        report.add_node("escaper", c.name().as_str(), c.r#type(), errors);
+    }
+    names
+}
+
+fn check_server(
+    report: &mut CheckReport,
+    escapers: &BTreeSet<NodeName>,
+    user_groups: &BTreeSet<NodeName>,
+    auditors: &BTreeSet<NodeName>,
+) {
+    let all_server =
+        match crate::config::server::get_all_sorted().context("failed to get all server config") {
+            Ok(v) => v,
+            Err(e) => {
+                report.add_error("server", e);
+                return;
+            }
+        };
+
+    let names: BTreeSet<NodeName> = all_server.iter().map(|c| c.name().clone()).collect();
+    for c in &all_server {
+        let mut errors = Vec::new();
+        if let Some(d) = c.dependent_server() {
+            for v in d {
+                if !names.contains(&v) {
+                    errors.push(format!("dependent server {v} not found"));
+                }
+            }
+        }
+        let e = c.escaper();
+        if !e.is_empty() && !escapers.contains(e) {
+            errors.push(format!("escaper {e} not found"));
+        }
+        let u = c.user_group();
+        if !u.is_empty() && !user_groups.contains(u) {
+            errors.push(format!("user group {u} not found"));
+        }
+        let a = c.auditor();
+        if !a.is_empty() && !auditors.contains(a) {
+            errors.push(format!("auditor {a} not found"));
+        }
+        report.add_node("server", c.name().as_str(), c.r#type(), errors);
+    }
+}
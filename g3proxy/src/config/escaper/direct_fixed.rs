@@ -5,6 +5,7 @@
 
 use std::net::IpAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use ascii::AsciiString;
@@ -22,7 +23,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 ))]
 use g3_types::net::Interface;
 use g3_types::net::{
-    HappyEyeballsConfig, ProxyProtocolVersion, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts,
+    HappyEyeballsConfig, ProxyProtocolVersion, TcpConnectTimeoutRulesBuilder, TcpKeepAliveConfig,
+    TcpMiscSockOpts, UdpMiscSockOpts,
 };
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
@@ -54,11 +56,14 @@ pub(crate) struct DirectFixedEscaperConfig {
     pub(crate) egress_net_filter: AclNetworkRuleBuilder,
     pub(crate) general: GeneralEscaperConfig,
     pub(crate) happy_eyeballs: HappyEyeballsConfig,
+    pub(crate) tcp_connect_timeout_rules: Option<TcpConnectTimeoutRulesBuilder>,
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
     pub(crate) enable_path_selection: bool,
     pub(crate) use_proxy_protocol: Option<ProxyProtocolVersion>,
+    pub(crate) upstream_down_cooldown: Option<Duration>,
+    pub(crate) upstream_down_threshold: usize,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
 
@@ -86,11 +91,14 @@ impl DirectFixedEscaperConfig {
             egress_net_filter: AclNetworkRuleBuilder::new_egress(AclAction::Permit),
             general: Default::default(),
             happy_eyeballs: Default::default(),
+            tcp_connect_timeout_rules: None,
             tcp_keepalive: Default::default(),
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
             enable_path_selection: false,
             use_proxy_protocol: None,
+            upstream_down_cooldown: None,
+            upstream_down_threshold: 3,
             extra_metrics_tags: None,
         }
     }
@@ -216,6 +224,13 @@ impl DirectFixedEscaperConfig {
                     .context(format!("invalid tcp connect value for key {k}"))?;
                 Ok(())
             }
+            "tcp_connect_timeout_rules" => {
+                let rules = g3_yaml::value::as_tcp_connect_timeout_rules_builder(v).context(
+                    format!("invalid tcp connect timeout rules value for key {k}"),
+                )?;
+                self.tcp_connect_timeout_rules = if rules.is_empty() { None } else { Some(rules) };
+                Ok(())
+            }
             "happy_eyeballs" => {
                 self.happy_eyeballs = g3_yaml::value::as_happy_eyeballs_config(v)
                     .context(format!("invalid happy eyeballs config value for key {k}"))?;
@@ -227,6 +242,22 @@ impl DirectFixedEscaperConfig {
                 self.use_proxy_protocol = Some(version);
                 Ok(())
             }
+            "upstream_down_cooldown" => {
+                let cooldown = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.upstream_down_cooldown = if cooldown.is_zero() {
+                    None
+                } else {
+                    Some(cooldown)
+                };
+                Ok(())
+            }
+            "upstream_down_threshold" => {
+                self.upstream_down_threshold = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?
+                    .get();
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
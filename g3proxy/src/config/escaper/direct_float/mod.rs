@@ -13,7 +13,10 @@ use yaml_rust::{Yaml, yaml};
 
 use g3_types::acl::{AclAction, AclNetworkRuleBuilder};
 use g3_types::metrics::{MetricTagMap, NodeName};
-use g3_types::net::{HappyEyeballsConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts};
+use g3_types::net::{
+    HappyEyeballsConfig, TcpConnectTimeoutRulesBuilder, TcpKeepAliveConfig, TcpMiscSockOpts,
+    UdpMiscSockOpts,
+};
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
 
@@ -39,6 +42,7 @@ pub(crate) struct DirectFloatEscaperConfig {
     pub(crate) egress_net_filter: AclNetworkRuleBuilder,
     pub(crate) general: GeneralEscaperConfig,
     pub(crate) happy_eyeballs: HappyEyeballsConfig,
+    pub(crate) tcp_connect_timeout_rules: Option<TcpConnectTimeoutRulesBuilder>,
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
@@ -61,6 +65,7 @@ impl DirectFloatEscaperConfig {
             egress_net_filter: AclNetworkRuleBuilder::new_egress(AclAction::Permit),
             general: Default::default(),
             happy_eyeballs: Default::default(),
+            tcp_connect_timeout_rules: None,
             tcp_keepalive: TcpKeepAliveConfig::default_enabled(),
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
@@ -162,6 +167,13 @@ impl DirectFloatEscaperConfig {
                     .context(format!("invalid tcp connect value for key {k}"))?;
                 Ok(())
             }
+            "tcp_connect_timeout_rules" => {
+                let rules = g3_yaml::value::as_tcp_connect_timeout_rules_builder(v).context(
+                    format!("invalid tcp connect timeout rules value for key {k}"),
+                )?;
+                self.tcp_connect_timeout_rules = if rules.is_empty() { None } else { Some(rules) };
+                Ok(())
+            }
             "happy_eyeballs" => {
                 self.happy_eyeballs = g3_yaml::value::as_happy_eyeballs_config(v)
                     .context(format!("invalid happy eyeballs config value for key {k}"))?;
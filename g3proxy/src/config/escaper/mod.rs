@@ -85,6 +85,7 @@ pub(crate) struct GeneralEscaperConfig {
 #[derive(Clone, AnyConfig)]
 #[def_fn(name, &NodeName)]
 #[def_fn(position, Option<YamlDocPosition>)]
+#[def_fn(r#type, &str)]
 #[def_fn(dependent_escaper, Option<BTreeSet<NodeName>>)]
 #[def_fn(resolver, &NodeName)]
 #[def_fn(diff_action, &Self, EscaperConfigDiffAction)]
@@ -149,7 +150,7 @@ pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<Any
     }
 }
 
-fn load_escaper(
+pub(crate) fn load_escaper(
     map: &yaml::Hash,
     position: Option<YamlDocPosition>,
 ) -> anyhow::Result<AnyEscaperConfig> {
@@ -13,7 +13,7 @@ use yaml_rust::{Yaml, yaml};
 use g3_geoip_types::{ContinentCode, IsoCountryCode};
 use g3_ip_locate::IpLocateServiceConfig;
 use g3_types::metrics::NodeName;
-use g3_types::resolve::ResolveStrategy;
+use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
@@ -26,6 +26,7 @@ pub(crate) struct RouteGeoIpEscaperConfig {
     position: Option<YamlDocPosition>,
     pub(crate) resolver: NodeName,
     pub(crate) resolve_strategy: ResolveStrategy,
+    pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
     pub(crate) resolution_delay: Duration,
     pub(crate) ip_locate_service: IpLocateServiceConfig,
     pub(crate) lpm_rules: BTreeMap<NodeName, BTreeSet<IpNetwork>>,
@@ -42,6 +43,7 @@ impl RouteGeoIpEscaperConfig {
             position,
             resolver: NodeName::default(),
             resolve_strategy: Default::default(),
+            resolve_redirection: None,
             resolution_delay: Duration::from_millis(50),
             ip_locate_service: IpLocateServiceConfig::default(),
             lpm_rules: BTreeMap::new(),
@@ -79,6 +81,12 @@ impl RouteGeoIpEscaperConfig {
                 self.resolve_strategy = g3_yaml::value::as_resolve_strategy(v)?;
                 Ok(())
             }
+            "resolve_redirection" => {
+                let redirect = g3_yaml::value::as_resolve_redirection_builder(v)
+                    .context(format!("invalid resolve redirection value for key {k}"))?;
+                self.resolve_redirection = Some(redirect);
+                Ok(())
+            }
             "resolution_delay" => {
                 self.resolution_delay = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
@@ -12,7 +12,7 @@ use ip_network::IpNetwork;
 use yaml_rust::{Yaml, yaml};
 
 use g3_types::metrics::NodeName;
-use g3_types::resolve::ResolveStrategy;
+use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
@@ -25,6 +25,7 @@ pub(crate) struct RouteResolvedEscaperConfig {
     position: Option<YamlDocPosition>,
     pub(crate) resolver: NodeName,
     pub(crate) resolve_strategy: ResolveStrategy,
+    pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
     pub(crate) resolution_delay: Duration,
     pub(crate) lpm_rules: BTreeMap<NodeName, BTreeSet<IpNetwork>>,
     pub(crate) default_next: NodeName,
@@ -37,6 +38,7 @@ impl RouteResolvedEscaperConfig {
             position,
             resolver: NodeName::default(),
             resolve_strategy: Default::default(),
+            resolve_redirection: None,
             resolution_delay: Duration::from_millis(50),
             lpm_rules: BTreeMap::new(),
             default_next: NodeName::default(),
@@ -70,6 +72,12 @@ impl RouteResolvedEscaperConfig {
                 self.resolve_strategy = g3_yaml::value::as_resolve_strategy(v)?;
                 Ok(())
             }
+            "resolve_redirection" => {
+                let redirect = g3_yaml::value::as_resolve_redirection_builder(v)
+                    .context(format!("invalid resolve redirection value for key {k}"))?;
+                self.resolve_redirection = Some(redirect);
+                Ok(())
+            }
             "resolution_delay" => {
                 self.resolution_delay = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
@@ -8,6 +8,9 @@ use std::path::Path;
 use anyhow::anyhow;
 use yaml_rust::{Yaml, yaml};
 
+mod check;
+pub use check::check;
+
 mod graphviz;
 pub use graphviz::graphviz_graph;
 
@@ -5,8 +5,9 @@
 
 use std::collections::BTreeSet;
 use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
 use g3_resolver::driver::c_ares::CAresDriverConfig;
@@ -18,12 +19,14 @@ use super::{AnyResolverConfig, ResolverConfigDiffAction};
 
 const RESOLVER_CONFIG_TYPE: &str = "c-ares";
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub(crate) struct CAresResolverConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     runtime: ResolverRuntimeConfig,
+    per_worker_runtime: bool,
     driver: CAresDriverConfig,
+    suffix_drivers: Vec<(String, CAresDriverConfig)>,
 }
 
 impl From<&CAresResolverConfig> for g3_resolver::ResolverConfig {
@@ -32,6 +35,11 @@ impl From<&CAresResolverConfig> for g3_resolver::ResolverConfig {
             name: c.name.to_string(),
             runtime: c.runtime.clone(),
             driver: AnyResolveDriverConfig::CAres(c.driver.clone()),
+            suffix_drivers: c
+                .suffix_drivers
+                .iter()
+                .map(|(s, d)| (s.clone(), AnyResolveDriverConfig::CAres(d.clone())))
+                .collect(),
         }
     }
 }
@@ -42,10 +50,17 @@ impl CAresResolverConfig {
             name: NodeName::default(),
             position,
             runtime: Default::default(),
+            per_worker_runtime: false,
             driver: Default::default(),
+            suffix_drivers: Vec::new(),
         }
     }
 
+    #[inline]
+    pub(crate) fn per_worker_runtime(&self) -> bool {
+        self.per_worker_runtime
+    }
+
     pub(crate) fn get_bind_ipv4(&self) -> Option<Ipv4Addr> {
         self.driver.get_bind_ipv4()
     }
@@ -85,14 +100,81 @@ impl CAresResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "inflight_query_timeout" => {
+                self.runtime.inflight_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "per_worker_runtime" => {
+                self.per_worker_runtime = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "suffix_drivers" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.suffix_drivers = Vec::with_capacity(map.len());
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let driver = self.parse_suffix_driver(v)?;
+                    self.suffix_drivers.push((k.to_string(), driver));
+                    Ok(())
+                })
+                .context(format!("invalid value for key {k}"))?;
+                Ok(())
+            }
+            "warm_up_domains" => {
+                let domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)?;
+                self.runtime.warm_up_domains = domains.into_iter().map(Arc::from).collect();
+                Ok(())
+            }
+            "warm_up_refresh_interval" => {
+                self.runtime.warm_up_refresh_interval = Some(g3_yaml::humanize::as_duration(v)?);
+                Ok(())
+            }
+            "warm_up_batch_size" => {
+                self.runtime.warm_up_batch_size = g3_yaml::value::as_nonzero_usize(v)?.get();
+                Ok(())
+            }
+            "prefetch_ratio" => {
+                let ratio = g3_yaml::value::as_f64(v)?;
+                if ratio <= 0.0 || ratio >= 1.0 {
+                    return Err(anyhow!(
+                        "invalid prefetch ratio {ratio}, should be in range (0, 1)"
+                    ));
+                }
+                self.runtime.prefetch_ratio = Some(ratio);
+                Ok(())
+            }
+            "negative_cache_max_size" => {
+                self.runtime.negative_cache_max_size = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
             _ => self.driver.set_by_yaml_kv(k, v),
         }
     }
 
+    fn parse_suffix_driver(&self, v: &Yaml) -> anyhow::Result<CAresDriverConfig> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("the driver config should be a map"));
+        };
+        let mut driver = CAresDriverConfig::default();
+        g3_yaml::foreach_kv(map, |k, v| {
+            driver
+                .set_by_yaml_kv(k, v)
+                .context(format!("invalid value for key {k}"))
+        })?;
+        driver.check()?;
+        Ok(driver)
+    }
+
     fn check(&mut self) -> anyhow::Result<()> {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        for (suffix, _) in &self.suffix_drivers {
+            if suffix.trim_start_matches('.').is_empty() {
+                return Err(anyhow!("empty domain suffix in suffix_drivers"));
+            }
+        }
         self.driver.check()
     }
 }
@@ -115,6 +197,10 @@ impl super::ResolverConfig for CAresResolverConfig {
             return ResolverConfigDiffAction::SpawnNew;
         };
 
+        if self.per_worker_runtime != new.per_worker_runtime {
+            return ResolverConfigDiffAction::SpawnNew;
+        }
+
         if self.eq(new) {
             return ResolverConfigDiffAction::NoAction;
         }
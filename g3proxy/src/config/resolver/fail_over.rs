@@ -17,7 +17,7 @@ use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
 
 const RESOLVER_CONFIG_TYPE: &str = "fail-over";
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub(crate) struct FailOverResolverConfig {
     position: Option<YamlDocPosition>,
     name: NodeName,
@@ -89,6 +89,10 @@ impl FailOverResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "inflight_query_timeout" => {
+                self.runtime.inflight_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -5,12 +5,14 @@
 
 use std::collections::BTreeSet;
 use std::net::IpAddr;
+use std::str::FromStr;
+use std::sync::Arc;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
 use g3_resolver::driver::hickory::HickoryDriverConfig;
-use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
+use g3_resolver::{AnyResolveDriverConfig, ClientSubnet, ResolverRuntimeConfig};
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
@@ -24,7 +26,9 @@ pub(crate) struct HickoryResolverConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     runtime: ResolverRuntimeConfig,
+    per_worker_runtime: bool,
     driver: HickoryDriverConfig,
+    suffix_drivers: Vec<(String, HickoryDriverConfig)>,
 }
 
 impl From<&HickoryResolverConfig> for g3_resolver::ResolverConfig {
@@ -33,6 +37,16 @@ impl From<&HickoryResolverConfig> for g3_resolver::ResolverConfig {
             name: c.name.to_string(),
             runtime: c.runtime.clone(),
             driver: AnyResolveDriverConfig::Hickory(Box::new(c.driver.clone())),
+            suffix_drivers: c
+                .suffix_drivers
+                .iter()
+                .map(|(s, d)| {
+                    (
+                        s.clone(),
+                        AnyResolveDriverConfig::Hickory(Box::new(d.clone())),
+                    )
+                })
+                .collect(),
         }
     }
 }
@@ -43,10 +57,17 @@ impl HickoryResolverConfig {
             name: NodeName::default(),
             position,
             runtime: Default::default(),
+            per_worker_runtime: false,
             driver: Default::default(),
+            suffix_drivers: Vec::new(),
         }
     }
 
+    #[inline]
+    pub(crate) fn per_worker_runtime(&self) -> bool {
+        self.per_worker_runtime
+    }
+
     #[inline]
     pub(crate) fn get_bind_addr(&self) -> BindAddr {
         self.driver.get_bind_addr()
@@ -93,6 +114,61 @@ impl HickoryResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "inflight_query_timeout" => {
+                self.runtime.inflight_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "per_worker_runtime" => {
+                self.per_worker_runtime = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "suffix_drivers" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                self.suffix_drivers = Vec::with_capacity(map.len());
+                g3_yaml::foreach_kv(map, |k, v| {
+                    let driver = self.parse_suffix_driver(v)?;
+                    self.suffix_drivers.push((k.to_string(), driver));
+                    Ok(())
+                })
+                .context(format!("invalid value for key {k}"))?;
+                Ok(())
+            }
+            "warm_up_domains" => {
+                let domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)?;
+                self.runtime.warm_up_domains = domains.into_iter().map(Arc::from).collect();
+                Ok(())
+            }
+            "warm_up_refresh_interval" => {
+                self.runtime.warm_up_refresh_interval = Some(g3_yaml::humanize::as_duration(v)?);
+                Ok(())
+            }
+            "warm_up_batch_size" => {
+                self.runtime.warm_up_batch_size = g3_yaml::value::as_nonzero_usize(v)?.get();
+                Ok(())
+            }
+            "prefetch_ratio" => {
+                let ratio = g3_yaml::value::as_f64(v)?;
+                if ratio <= 0.0 || ratio >= 1.0 {
+                    return Err(anyhow!(
+                        "invalid prefetch ratio {ratio}, should be in range (0, 1)"
+                    ));
+                }
+                self.runtime.prefetch_ratio = Some(ratio);
+                Ok(())
+            }
+            "negative_cache_max_size" => {
+                self.runtime.negative_cache_max_size = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            "client_subnet" => {
+                let s = g3_yaml::value::as_string(v)?;
+                let subnet = ClientSubnet::from_str(&s)
+                    .context(format!("invalid client subnet value for key {k}"))?;
+                self.runtime.client_subnet = Some(subnet);
+                Ok(())
+            }
             _ => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 self.driver.set_by_yaml_kv(k, v, Some(lookup_dir))
@@ -100,10 +176,30 @@ impl HickoryResolverConfig {
         }
     }
 
+    fn parse_suffix_driver(&self, v: &Yaml) -> anyhow::Result<HickoryDriverConfig> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("the driver config should be a map"));
+        };
+        let mut driver = HickoryDriverConfig::default();
+        let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+        g3_yaml::foreach_kv(map, |k, v| {
+            driver
+                .set_by_yaml_kv(k, v, Some(lookup_dir))
+                .context(format!("invalid value for key {k}"))
+        })?;
+        driver.check()?;
+        Ok(driver)
+    }
+
     fn check(&mut self) -> anyhow::Result<()> {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        for (suffix, _) in &self.suffix_drivers {
+            if suffix.trim_start_matches('.').is_empty() {
+                return Err(anyhow!("empty domain suffix in suffix_drivers"));
+            }
+        }
         self.driver.check()
     }
 }
@@ -126,6 +222,10 @@ impl super::ResolverConfig for HickoryResolverConfig {
             return ResolverConfigDiffAction::SpawnNew;
         };
 
+        if self.per_worker_runtime != new.per_worker_runtime {
+            return ResolverConfigDiffAction::SpawnNew;
+        }
+
         if self.eq(new) {
             return ResolverConfigDiffAction::NoAction;
         }
@@ -0,0 +1,121 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::anyhow;
+use yaml_rust::{Yaml, yaml};
+
+use g3_resolver::driver::hosts::HostsDriverConfig;
+use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyResolverConfig, ResolverConfigDiffAction};
+
+const RESOLVER_CONFIG_TYPE: &str = "hosts";
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct HostsResolverConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    runtime: ResolverRuntimeConfig,
+    driver: HostsDriverConfig,
+}
+
+impl From<&HostsResolverConfig> for g3_resolver::ResolverConfig {
+    fn from(c: &HostsResolverConfig) -> Self {
+        g3_resolver::ResolverConfig {
+            name: c.name.to_string(),
+            runtime: c.runtime.clone(),
+            driver: AnyResolveDriverConfig::Hosts(c.driver.clone()),
+            suffix_drivers: Vec::new(),
+        }
+    }
+}
+
+impl HostsResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        HostsResolverConfig {
+            name: NodeName::default(),
+            position,
+            runtime: Default::default(),
+            driver: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_hosts_file(&self) -> Option<&Path> {
+        self.driver.get_hosts_file()
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "graceful_stop_wait" => {
+                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            _ => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                self.driver.set_by_yaml_kv(k, v, Some(lookup_dir))
+            }
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        self.driver.check()
+    }
+}
+
+impl super::ResolverConfig for HostsResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn r#type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::Hosts(new) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ResolverConfigDiffAction::NoAction;
+        }
+
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        None
+    }
+}
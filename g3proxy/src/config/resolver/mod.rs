@@ -21,6 +21,7 @@ pub(crate) mod hickory;
 
 pub(crate) mod deny_all;
 pub(crate) mod fail_over;
+pub(crate) mod hosts;
 
 mod registry;
 pub(crate) use registry::clear;
@@ -46,6 +47,7 @@ pub(crate) trait ResolverConfig {
 #[derive(Clone, AnyConfig)]
 #[def_fn(name, &NodeName)]
 #[def_fn(position, Option<YamlDocPosition>)]
+#[def_fn(r#type, &'static str)]
 #[def_fn(dependent_resolver, Option<BTreeSet<NodeName>>)]
 #[def_fn(diff_action, &Self, ResolverConfigDiffAction)]
 pub(crate) enum AnyResolverConfig {
@@ -54,6 +56,7 @@ pub(crate) enum AnyResolverConfig {
     Hickory(Box<hickory::HickoryResolverConfig>),
     DenyAll(deny_all::DenyAllResolverConfig),
     FailOver(fail_over::FailOverResolverConfig),
+    Hosts(hosts::HostsResolverConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
@@ -121,6 +124,11 @@ fn load_resolver(
                 .context("failed to load this FailOver resolver")?;
             Ok(AnyResolverConfig::FailOver(resolver))
         }
+        "hosts" | "hosts_file" | "static" => {
+            let resolver = hosts::HostsResolverConfig::parse(map, position)
+                .context("failed to load this hosts resolver")?;
+            Ok(AnyResolverConfig::Hosts(resolver))
+        }
         _ => Err(anyhow!("unsupported resolver type {resolver_type}")),
     }
 }
@@ -0,0 +1,192 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use http::HeaderName;
+use yaml_rust::Yaml;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+const DEFAULT_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+const DEFAULT_MAX_HEADER_SIZE: usize = 8192;
+
+/// The external authorization service to call before forwarding http requests
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ExtAuthzConfig {
+    pub(crate) addr: SocketAddr,
+    pub(crate) path: String,
+    pub(crate) timeout: Duration,
+    /// allow the request if the authorization service is unavailable
+    pub(crate) fail_open: bool,
+    /// the client request headers that will be sent to the authorization service
+    pub(crate) forward_headers: Vec<HeaderName>,
+    /// the authorization response headers that will be set to the upstream request
+    pub(crate) upstream_headers: Vec<HeaderName>,
+    pub(crate) max_header_size: usize,
+    /// decisions will be cached if this is not zero
+    pub(crate) cache_ttl: Duration,
+    pub(crate) cache_size: NonZeroUsize,
+}
+
+impl ExtAuthzConfig {
+    fn new(addr: SocketAddr) -> Self {
+        ExtAuthzConfig {
+            addr,
+            path: "/".to_string(),
+            timeout: DEFAULT_TIMEOUT,
+            fail_open: false,
+            forward_headers: Vec::new(),
+            upstream_headers: Vec::new(),
+            max_header_size: DEFAULT_MAX_HEADER_SIZE,
+            cache_ttl: Duration::ZERO,
+            cache_size: DEFAULT_CACHE_SIZE,
+        }
+    }
+
+    pub(crate) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let v = g3_yaml::hash_get_required(map, "addr")?;
+                let addr = g3_yaml::value::as_env_sockaddr(v)
+                    .context("invalid socket address value for key addr")?;
+                let mut c = Self::new(addr);
+                g3_yaml::foreach_kv(map, |k, v| c.set(k, v))?;
+                c.check()?;
+                Ok(c)
+            }
+            Yaml::String(_) => {
+                let addr = g3_yaml::value::as_env_sockaddr(value)?;
+                Ok(Self::new(addr))
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'ExtAuthzConfig' should be 'map' or 'string'"
+            )),
+        }
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "addr" => Ok(()),
+            "path" => {
+                self.path = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            "timeout" => {
+                self.timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "fail_open" => {
+                self.fail_open = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
+            "forward_headers" => {
+                self.forward_headers =
+                    g3_yaml::value::as_list(v, g3_yaml::value::as_http_header_name)
+                        .context(format!("invalid http header name list value for key {k}"))?;
+                Ok(())
+            }
+            "upstream_headers" => {
+                self.upstream_headers =
+                    g3_yaml::value::as_list(v, g3_yaml::value::as_http_header_name)
+                        .context(format!("invalid http header name list value for key {k}"))?;
+                Ok(())
+            }
+            "max_header_size" => {
+                self.max_header_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "cache_ttl" => {
+                self.cache_ttl = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "cache_size" => {
+                self.cache_size = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if !self.path.starts_with('/') {
+            return Err(anyhow!("path should start with '/'"));
+        }
+        if self.timeout.is_zero() {
+            return Err(anyhow!("timeout should not be zero"));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::{yaml_doc, yaml_str};
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_string() {
+        let config = ExtAuthzConfig::parse(&yaml_str!("127.0.0.1:9000")).unwrap();
+        assert_eq!(config.addr, "127.0.0.1:9000".parse().unwrap());
+        assert_eq!(config.path, "/");
+        assert_eq!(config.timeout, DEFAULT_TIMEOUT);
+        assert!(!config.fail_open);
+        assert!(config.cache_ttl.is_zero());
+    }
+
+    #[test]
+    fn parse_map() {
+        let doc = yaml_doc!(
+            r#"
+                addr: "[::1]:9000"
+                path: /authz
+                timeout: 500ms
+                fail_open: true
+                forward_headers:
+                  - authorization
+                  - user-agent
+                upstream_headers: x-user-id
+                cache_ttl: 30s
+                cache_size: 256
+            "#
+        );
+        let config = ExtAuthzConfig::parse(&doc).unwrap();
+        assert_eq!(config.addr, "[::1]:9000".parse().unwrap());
+        assert_eq!(config.path, "/authz");
+        assert_eq!(config.timeout, Duration::from_millis(500));
+        assert!(config.fail_open);
+        assert_eq!(
+            config.forward_headers,
+            [http::header::AUTHORIZATION, http::header::USER_AGENT]
+        );
+        assert_eq!(
+            config.upstream_headers,
+            [HeaderName::from_static("x-user-id")]
+        );
+        assert_eq!(config.cache_ttl, Duration::from_secs(30));
+        assert_eq!(config.cache_size.get(), 256);
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(ExtAuthzConfig::parse(&yaml_str!("127.0.0.1")).is_err());
+        assert!(ExtAuthzConfig::parse(&yaml_doc!("{ path: /authz }")).is_err());
+        assert!(
+            ExtAuthzConfig::parse(&yaml_doc!("{ addr: 127.0.0.1:9000, path: authz }")).is_err()
+        );
+        assert!(ExtAuthzConfig::parse(&yaml_doc!("{ addr: 127.0.0.1:9000, timeout: 0 }")).is_err());
+        assert!(ExtAuthzConfig::parse(&yaml_doc!("{ addr: 127.0.0.1:9000, mode: grpc }")).is_err());
+        assert!(ExtAuthzConfig::parse(&Yaml::Boolean(true)).is_err());
+    }
+}
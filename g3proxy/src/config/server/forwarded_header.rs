@@ -0,0 +1,246 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use http::{HeaderName, header};
+use ip_network::IpNetwork;
+use yaml_rust::Yaml;
+
+use g3_types::net::{HttpForwardedHeaderType, HttpForwardedHeaderValue, HttpHeaderMap};
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum ForwardedHeaderMode {
+    /// append our entry to the received chain
+    #[default]
+    Append,
+    /// drop the received chain and set our entry
+    Replace,
+    /// drop the received chain
+    Strip,
+}
+
+impl FromStr for ForwardedHeaderMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "append" => Ok(ForwardedHeaderMode::Append),
+            "replace" | "override" => Ok(ForwardedHeaderMode::Replace),
+            "strip" | "remove" => Ok(ForwardedHeaderMode::Strip),
+            _ => Err(()),
+        }
+    }
+}
+
+/// How to handle the X-Forwarded-For and Forwarded headers in forwarded requests
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ForwardedHeaderPolicy {
+    pub(crate) mode: ForwardedHeaderMode,
+    pub(crate) header_type: HttpForwardedHeaderType,
+    /// the received chain will only be kept if the peer is in these networks
+    pub(crate) trusted_peers: Vec<IpNetwork>,
+}
+
+impl Default for ForwardedHeaderPolicy {
+    fn default() -> Self {
+        ForwardedHeaderPolicy {
+            mode: ForwardedHeaderMode::Append,
+            header_type: HttpForwardedHeaderType::Classic,
+            trusted_peers: Vec::new(),
+        }
+    }
+}
+
+impl ForwardedHeaderPolicy {
+    pub(crate) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        let mut c = Self::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| c.set(k, v))?;
+            }
+            Yaml::String(s) => {
+                c.mode = ForwardedHeaderMode::from_str(s)
+                    .map_err(|_| anyhow!("invalid forwarded header mode {s}"))?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'ForwardedHeaderPolicy' should be 'map' or 'string'"
+                ));
+            }
+        }
+        Ok(c)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "mode" => {
+                let s = g3_yaml::value::as_string(v)?;
+                self.mode = ForwardedHeaderMode::from_str(&s)
+                    .map_err(|_| anyhow!("invalid forwarded header mode {s}"))?;
+                Ok(())
+            }
+            "type" | "header_type" => {
+                let s = g3_yaml::value::as_string(v)?;
+                self.header_type = HttpForwardedHeaderType::from_str(&s)
+                    .map_err(|_| anyhow!("invalid forwarded header type {s}"))?;
+                if self.header_type == HttpForwardedHeaderType::Disable {
+                    return Err(anyhow!(
+                        "forwarded header type should not be disabled, use strip mode instead"
+                    ));
+                }
+                Ok(())
+            }
+            "trusted_peers" | "trusted_proxies" => {
+                self.trusted_peers = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid ip network list value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn trust_peer(&self, peer: &SocketAddr) -> bool {
+        self.trusted_peers.is_empty() || self.trusted_peers.iter().any(|n| n.contains(peer.ip()))
+    }
+
+    pub(crate) fn apply(
+        &self,
+        headers: &mut HttpHeaderMap,
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+    ) {
+        let keep_received = match self.mode {
+            ForwardedHeaderMode::Append => self.trust_peer(&client_addr),
+            ForwardedHeaderMode::Replace | ForwardedHeaderMode::Strip => false,
+        };
+        if !keep_received {
+            headers.remove(X_FORWARDED_FOR);
+            headers.remove(header::FORWARDED);
+        }
+        if self.mode == ForwardedHeaderMode::Strip {
+            return;
+        }
+
+        let value = match self.header_type {
+            HttpForwardedHeaderType::Classic => {
+                HttpForwardedHeaderValue::new_classic(client_addr.ip())
+            }
+            HttpForwardedHeaderType::Standard => {
+                HttpForwardedHeaderValue::new_standard(client_addr, server_addr)
+            }
+            HttpForwardedHeaderType::Disable => return,
+        };
+        value.append_to(headers);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use g3_yaml::{yaml_doc, yaml_str};
+    use yaml_rust::YamlLoader;
+
+    const CLIENT: &str = "192.168.1.10:40000";
+    const SERVER: &str = "10.0.0.1:8080";
+
+    fn received() -> HttpHeaderMap {
+        let mut headers = HttpHeaderMap::default();
+        headers.append(X_FORWARDED_FOR, HttpHeaderValue::from_static("1.1.1.1"));
+        headers.append(
+            header::FORWARDED,
+            HttpHeaderValue::from_static("for=1.1.1.1"),
+        );
+        headers
+    }
+
+    fn apply(policy: &ForwardedHeaderPolicy) -> HttpHeaderMap {
+        let mut headers = received();
+        policy.apply(
+            &mut headers,
+            SocketAddr::from_str(CLIENT).unwrap(),
+            SocketAddr::from_str(SERVER).unwrap(),
+        );
+        headers
+    }
+
+    fn values(headers: &HttpHeaderMap, name: HeaderName) -> Vec<&str> {
+        headers.get_all(name).iter().map(|v| v.to_str()).collect()
+    }
+
+    #[test]
+    fn append() {
+        let policy = ForwardedHeaderPolicy::parse(&yaml_str!("append")).unwrap();
+        let headers = apply(&policy);
+        assert_eq!(
+            values(&headers, X_FORWARDED_FOR),
+            ["1.1.1.1", "192.168.1.10"]
+        );
+        assert_eq!(values(&headers, header::FORWARDED), ["for=1.1.1.1"]);
+    }
+
+    #[test]
+    fn append_with_trusted_peers() {
+        let doc = yaml_doc!(
+            r#"
+                mode: append
+                type: standard
+                trusted_peers:
+                  - 192.168.1.0/24
+            "#
+        );
+        let policy = ForwardedHeaderPolicy::parse(&doc).unwrap();
+        let headers = apply(&policy);
+        assert_eq!(values(&headers, X_FORWARDED_FOR), ["1.1.1.1"]);
+        assert_eq!(
+            values(&headers, header::FORWARDED),
+            ["for=1.1.1.1", "for=192.168.1.10:40000; by=10.0.0.1:8080"]
+        );
+
+        let doc = yaml_doc!(
+            r#"
+                trusted_peers: 172.16.0.1
+            "#
+        );
+        let policy = ForwardedHeaderPolicy::parse(&doc).unwrap();
+        let headers = apply(&policy);
+        assert_eq!(values(&headers, X_FORWARDED_FOR), ["192.168.1.10"]);
+        assert!(!headers.contains_key(header::FORWARDED));
+    }
+
+    #[test]
+    fn replace() {
+        let doc = yaml_doc!(
+            r#"
+                mode: replace
+                trusted_peers: 192.168.1.0/24
+            "#
+        );
+        let policy = ForwardedHeaderPolicy::parse(&doc).unwrap();
+        let headers = apply(&policy);
+        assert_eq!(values(&headers, X_FORWARDED_FOR), ["192.168.1.10"]);
+        assert!(!headers.contains_key(header::FORWARDED));
+    }
+
+    #[test]
+    fn strip() {
+        let policy = ForwardedHeaderPolicy::parse(&yaml_str!("strip")).unwrap();
+        let headers = apply(&policy);
+        assert!(headers.is_empty());
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(ForwardedHeaderPolicy::parse(&yaml_str!("keep")).is_err());
+        assert!(ForwardedHeaderPolicy::parse(&yaml_doc!("{ type: none }")).is_err());
+        assert!(ForwardedHeaderPolicy::parse(&yaml_doc!("{ trusted_peers: abc }")).is_err());
+        assert!(ForwardedHeaderPolicy::parse(&Yaml::Boolean(true)).is_err());
+    }
+}
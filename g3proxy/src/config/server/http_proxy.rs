@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -22,14 +22,16 @@ use g3_types::acl::{AclExactPortRule, AclNetworkRuleBuilder};
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::net::{
-    Host, HttpKeepAliveConfig, HttpServerId, OpensslClientConfigBuilder, RustlsServerConfigBuilder,
-    TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
+    AlpnProtocol, Host, HttpHeaderRuleSet, HttpKeepAliveConfig, HttpServerId, HttpViaHeaderConfig,
+    OpensslClientConfigBuilder, RustlsServerConfigBuilder, SocketBufferConfig, TcpListenConfig,
+    TcpMiscSockOpts, TcpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
 
 use super::{
-    AnyServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_DEFAULT_MAX_COUNT,
-    IDLE_CHECK_MAXIMUM_DURATION, ServerConfig, ServerConfigDiffAction, UsernameParamsConfig,
+    AnyServerConfig, ExtAuthzConfig, ForwardedHeaderPolicy, IDLE_CHECK_DEFAULT_DURATION,
+    IDLE_CHECK_DEFAULT_MAX_COUNT, IDLE_CHECK_MAXIMUM_DURATION, RealClientIpConfig, ServerConfig,
+    ServerConfigDiffAction, UpstreamCloseStatusPolicy, UsernameParamsConfig,
 };
 
 const SERVER_CONFIG_TYPE: &str = "HttpProxy";
@@ -52,6 +54,33 @@ impl Default for HttpProxyServerTimeoutConfig {
     }
 }
 
+/// The protocol to use for https forward requests to the upstream
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum HttpForwardUpstreamProtocol {
+    /// use h2 if enabled and selected by the upstream, or fallback to http/1.1
+    #[default]
+    Auto,
+    /// only offer http/1.1 in ALPN
+    Http11,
+    /// only use h2, the request will fail if h2 is not selected by the upstream
+    Http2,
+}
+
+impl FromStr for HttpForwardUpstreamProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(HttpForwardUpstreamProtocol::Auto);
+        }
+        match AlpnProtocol::from_str(s)? {
+            AlpnProtocol::Http11 => Ok(HttpForwardUpstreamProtocol::Http11),
+            AlpnProtocol::Http2 => Ok(HttpForwardUpstreamProtocol::Http2),
+            p => Err(anyhow!("unsupported http forward upstream protocol {p}")),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct HttpProxyServerConfig {
     name: NodeName,
@@ -76,26 +105,49 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) timeout: HttpProxyServerTimeoutConfig,
     pub(crate) task_idle_check_interval: Duration,
     pub(crate) task_idle_max_count: usize,
+    pub(crate) upgraded_task_idle_max_count: Option<usize>,
+    pub(crate) task_max_duration: Option<Duration>,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) task_log_slow_threshold: Option<Duration>,
     pub(crate) tcp_copy: StreamCopyConfig,
+    pub(crate) tcp_copy_buffer_budget: Option<usize>,
+    pub(crate) tcp_splice: bool,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) req_hdr_max_size: usize,
     pub(crate) rsp_hdr_max_size: usize,
+    pub(crate) rsp_interim_max_count: usize,
+    pub(crate) rsp_trailer_max_size: usize,
     pub(crate) log_uri_max_chars: usize,
+    pub(crate) log_client_cert: bool,
     pub(crate) pipeline_size: NonZeroUsize,
     pub(crate) pipeline_read_idle_timeout: Duration,
+    pub(crate) allow_pipelined_request: bool,
+    pub(crate) client_idle_max_count: usize,
     pub(crate) no_early_error_reply: bool,
     pub(crate) allow_custom_host: bool,
     pub(crate) drop_default_port_in_host: bool,
     pub(crate) body_line_max_len: usize,
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
+    pub(crate) http_forward_upstream_close_on_status: UpstreamCloseStatusPolicy,
     pub(crate) http_forward_mark_upstream: bool,
+    pub(crate) http_forward_upstream_h2: bool,
+    pub(crate) http_forward_upstream_h2_max_streams: NonZeroUsize,
+    pub(crate) http_forward_upstream_protocol: HttpForwardUpstreamProtocol,
+    pub(crate) http_forward_upstream_protocol_by_host: HashMap<Host, HttpForwardUpstreamProtocol>,
     pub(crate) echo_chained_info: bool,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) egress_path_selection_header: Option<HeaderName>,
     pub(crate) steal_forwarded_for: bool,
+    pub(crate) via_header: Option<HttpViaHeaderConfig>,
+    pub(crate) forwarded_header: Option<ForwardedHeaderPolicy>,
+    pub(crate) real_client_ip: Option<RealClientIpConfig>,
+    pub(crate) ext_authz: Option<ExtAuthzConfig>,
+    pub(crate) http_req_header_rules: HttpHeaderRuleSet,
+    pub(crate) http_rsp_header_rules: HttpHeaderRuleSet,
+    pub(crate) allow_connect_udp: bool,
+    pub(crate) udp_socket_buffer: SocketBufferConfig,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
     // Optional: derive next-hop escaper addr from username params
     pub(crate) username_params: Option<UsernameParamsConfig>,
@@ -126,26 +178,49 @@ impl HttpProxyServerConfig {
             timeout: HttpProxyServerTimeoutConfig::default(),
             task_idle_check_interval: IDLE_CHECK_DEFAULT_DURATION,
             task_idle_max_count: IDLE_CHECK_DEFAULT_MAX_COUNT,
+            upgraded_task_idle_max_count: None,
+            task_max_duration: None,
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            task_log_slow_threshold: None,
             tcp_copy: Default::default(),
+            tcp_copy_buffer_budget: None,
+            tcp_splice: false,
             tcp_misc_opts: Default::default(),
             req_hdr_max_size: 65536, // 64KiB
             rsp_hdr_max_size: 65536, // 64KiB
+            rsp_interim_max_count: 16,
+            rsp_trailer_max_size: 65536, // 64KiB
             log_uri_max_chars: 1024,
+            log_client_cert: false,
             pipeline_size: NonZeroUsize::new(10).unwrap(),
             pipeline_read_idle_timeout: Duration::from_secs(300),
+            allow_pipelined_request: true,
+            client_idle_max_count: 0,
             no_early_error_reply: false,
             allow_custom_host: true,
             drop_default_port_in_host: false,
             body_line_max_len: 8192,
             http_forward_upstream_keepalive: Default::default(),
+            http_forward_upstream_close_on_status: Default::default(),
             http_forward_mark_upstream: false,
+            http_forward_upstream_h2: false,
+            http_forward_upstream_h2_max_streams: NonZeroUsize::new(100).unwrap(),
+            http_forward_upstream_protocol: HttpForwardUpstreamProtocol::Auto,
+            http_forward_upstream_protocol_by_host: HashMap::new(),
             echo_chained_info: false,
             untrusted_read_limit: None,
             egress_path_selection_header: None,
             steal_forwarded_for: false,
+            via_header: None,
+            forwarded_header: None,
+            real_client_ip: None,
+            ext_authz: None,
+            http_req_header_rules: HttpHeaderRuleSet::default(),
+            http_rsp_header_rules: HttpHeaderRuleSet::default(),
+            allow_connect_udp: false,
+            udp_socket_buffer: SocketBufferConfig::default(),
             extra_metrics_tags: None,
             username_params: None,
         }
@@ -299,6 +374,16 @@ impl HttpProxyServerConfig {
                 self.tcp_copy.set_yield_size(yield_size);
                 Ok(())
             }
+            "tcp_copy_buffer_budget" => {
+                let budget = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.tcp_copy_buffer_budget = Some(budget);
+                Ok(())
+            }
+            "tcp_splice" => {
+                self.tcp_splice = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
             "tcp_misc_opts" => {
                 self.tcp_misc_opts = g3_yaml::value::as_tcp_misc_sock_opts(v)
                     .context(format!("invalid tcp misc sock opts value for key {k}"))?;
@@ -318,6 +403,18 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid usize value for key {k}"))?;
                 Ok(())
             }
+            "upgraded_task_idle_max_count" => {
+                let count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                self.upgraded_task_idle_max_count = Some(count);
+                Ok(())
+            }
+            "task_max_duration" => {
+                let duration = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.task_max_duration = Some(duration);
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -332,6 +429,12 @@ impl HttpProxyServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "task_log_slow_threshold" => {
+                let threshold = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.task_log_slow_threshold = Some(threshold);
+                Ok(())
+            }
             "req_header_recv_timeout" => {
                 self.timeout.recv_req_header = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
@@ -352,11 +455,26 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid humanize usize value for key {k}"))?;
                 Ok(())
             }
+            "rsp_interim_max_count" => {
+                self.rsp_interim_max_count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "rsp_trailer_max_size" => {
+                self.rsp_trailer_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
             "log_uri_max_chars" | "uri_log_max_chars" => {
                 self.log_uri_max_chars = g3_yaml::value::as_usize(v)
                     .context(format!("invalid usize value for key {k}"))?;
                 Ok(())
             }
+            "log_client_cert" => {
+                self.log_client_cert = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
             "pipeline_size" => {
                 self.pipeline_size = g3_yaml::value::as_nonzero_usize(v)
                     .context(format!("invalid nonzero usize value for key {k}"))?;
@@ -367,6 +485,16 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "allow_pipelined_request" => {
+                self.allow_pipelined_request = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "client_idle_max_count" => {
+                self.client_idle_max_count = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
             "no_early_error_reply" => {
                 self.no_early_error_reply = g3_yaml::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
@@ -392,10 +520,52 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid http keepalive config value for key {k}"))?;
                 Ok(())
             }
+            "http_forward_upstream_close_on_status" => {
+                self.http_forward_upstream_close_on_status = UpstreamCloseStatusPolicy::parse(v)
+                    .context(format!(
+                        "invalid upstream close status policy value for key {k}"
+                    ))?;
+                Ok(())
+            }
             "http_forward_mark_upstream" => {
                 self.http_forward_mark_upstream = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "http_forward_upstream_h2" => {
+                self.http_forward_upstream_h2 = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "http_forward_upstream_h2_max_streams" => {
+                self.http_forward_upstream_h2_max_streams = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                Ok(())
+            }
+            "http_forward_upstream_protocol" => {
+                let s = g3_yaml::value::as_string(v)?;
+                self.http_forward_upstream_protocol = HttpForwardUpstreamProtocol::from_str(&s)
+                    .context(format!(
+                        "invalid http forward upstream protocol value for key {k}"
+                    ))?;
+                Ok(())
+            }
+            "http_forward_upstream_protocol_by_host" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid yaml value type for key {k}, expect map"));
+                };
+                self.http_forward_upstream_protocol_by_host.clear();
+                for (host, protocol) in map {
+                    let host = g3_yaml::value::as_host(host)
+                        .context(format!("invalid host as key in {k}"))?;
+                    let s = g3_yaml::value::as_string(protocol)?;
+                    let protocol = HttpForwardUpstreamProtocol::from_str(&s).context(format!(
+                        "invalid http forward upstream protocol value for host {host}"
+                    ))?;
+                    self.http_forward_upstream_protocol_by_host
+                        .insert(host, protocol);
+                }
+                Ok(())
+            }
             "echo_chained_info" => {
                 self.echo_chained_info = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -425,6 +595,58 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid boolean value for key {k}"))?;
                 Ok(())
             }
+            "forwarded_header" => {
+                if let Yaml::Boolean(enable) = v {
+                    self.forwarded_header = enable.then(ForwardedHeaderPolicy::default);
+                } else {
+                    let policy = ForwardedHeaderPolicy::parse(v)
+                        .context(format!("invalid forwarded header policy value for key {k}"))?;
+                    self.forwarded_header = Some(policy);
+                }
+                Ok(())
+            }
+            "real_client_ip" => {
+                let config = RealClientIpConfig::parse(v)
+                    .context(format!("invalid real client ip config value for key {k}"))?;
+                self.real_client_ip = Some(config);
+                Ok(())
+            }
+            "ext_authz" => {
+                let config = ExtAuthzConfig::parse(v)
+                    .context(format!("invalid ext authz config value for key {k}"))?;
+                self.ext_authz = Some(config);
+                Ok(())
+            }
+            "http_req_header_rules" => {
+                self.http_req_header_rules = g3_yaml::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
+            "http_rsp_header_rules" => {
+                self.http_rsp_header_rules = g3_yaml::value::as_http_header_rule_set(v)
+                    .context(format!("invalid http header rule set value for key {k}"))?;
+                Ok(())
+            }
+            "via_header" => {
+                if let Yaml::Boolean(enable) = v {
+                    self.via_header = enable.then(HttpViaHeaderConfig::default);
+                } else {
+                    let config = g3_yaml::value::as_http_via_header_config(v)
+                        .context(format!("invalid http via header config value for key {k}"))?;
+                    self.via_header = Some(config);
+                }
+                Ok(())
+            }
+            "allow_connect_udp" => {
+                self.allow_connect_udp = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
+            "udp_socket_buffer" => {
+                self.udp_socket_buffer = g3_yaml::value::as_socket_buffer_config(v)
+                    .context(format!("invalid socket buffer config value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -445,6 +667,37 @@ impl HttpProxyServerConfig {
                 "server_id is required as http_forward_mark_upstream is on"
             ));
         }
+        if self.steal_forwarded_for && self.real_client_ip.is_some() {
+            return Err(anyhow!(
+                "steal_forwarded_for should not be enabled if real_client_ip is set"
+            ));
+        }
+        if !self.http_forward_upstream_h2 {
+            if self.http_forward_upstream_protocol == HttpForwardUpstreamProtocol::Http2 {
+                return Err(anyhow!(
+                    "http_forward_upstream_h2 should be enabled to use h2 as upstream protocol"
+                ));
+            }
+            if let Some((host, _)) = self
+                .http_forward_upstream_protocol_by_host
+                .iter()
+                .find(|(_, p)| **p == HttpForwardUpstreamProtocol::Http2)
+            {
+                return Err(anyhow!(
+                    "http_forward_upstream_h2 should be enabled to use h2 as upstream protocol for host {host}"
+                ));
+            }
+        }
+        if let Some(via) = &mut self.via_header
+            && via.pseudonym.is_none()
+        {
+            let pseudonym = match &self.server_id {
+                Some(id) => id.clone(),
+                None => HttpServerId::from_str(self.name.as_str())
+                    .map_err(|e| anyhow!("server name can not be used as via pseudonym: {e}"))?,
+            };
+            via.pseudonym = Some(pseudonym);
+        }
         if self.task_idle_check_interval > IDLE_CHECK_MAXIMUM_DURATION {
             self.task_idle_check_interval = IDLE_CHECK_MAXIMUM_DURATION;
         }
@@ -453,6 +706,26 @@ impl HttpProxyServerConfig {
     }
 }
 
+impl HttpProxyServerConfig {
+    pub(crate) fn http_forward_upstream_protocol(
+        &self,
+        host: &Host,
+    ) -> HttpForwardUpstreamProtocol {
+        self.http_forward_upstream_protocol_by_host
+            .get(host)
+            .copied()
+            .unwrap_or(self.http_forward_upstream_protocol)
+    }
+
+    pub(crate) fn force_http11_upstream(&self) -> bool {
+        self.http_forward_upstream_protocol == HttpForwardUpstreamProtocol::Http11
+            || self
+                .http_forward_upstream_protocol_by_host
+                .values()
+                .any(|p| *p == HttpForwardUpstreamProtocol::Http11)
+    }
+}
+
 impl ServerConfig for HttpProxyServerConfig {
     fn name(&self) -> &NodeName {
         &self.name
@@ -511,6 +784,11 @@ impl ServerConfig for HttpProxyServerConfig {
     fn task_max_idle_count(&self) -> usize {
         self.task_idle_max_count
     }
+
+    #[inline]
+    fn upgraded_task_max_idle_count(&self) -> Option<usize> {
+        self.upgraded_task_idle_max_count
+    }
 }
 
 #[cfg(test)]
@@ -543,4 +821,87 @@ mod tests {
         assert_eq!(u.http_port, 12345);
         assert_eq!(u.socks5_port, 23456);
     }
+
+    #[test]
+    fn parse_upstream_protocol() {
+        let doc = yaml_doc!(
+            r#"
+                type: http_proxy
+                name: s1
+                escaper: e1
+                http_forward_upstream_h2: true
+                http_forward_upstream_protocol: http/1.1
+                http_forward_upstream_protocol_by_host:
+                  example.net: h2
+                  192.168.1.1: auto
+            "#
+        );
+        let map = doc.as_hash().unwrap();
+        let cfg = HttpProxyServerConfig::parse(map, None).unwrap();
+        assert!(cfg.force_http11_upstream());
+        assert_eq!(
+            cfg.http_forward_upstream_protocol(&Host::from_str("example.net").unwrap()),
+            HttpForwardUpstreamProtocol::Http2
+        );
+        assert_eq!(
+            cfg.http_forward_upstream_protocol(&Host::from_str("192.168.1.1").unwrap()),
+            HttpForwardUpstreamProtocol::Auto
+        );
+        assert_eq!(
+            cfg.http_forward_upstream_protocol(&Host::from_str("example.com").unwrap()),
+            HttpForwardUpstreamProtocol::Http11
+        );
+
+        let doc = yaml_doc!(
+            r#"
+                type: http_proxy
+                name: s1
+                escaper: e1
+                http_forward_upstream_protocol: h2
+            "#
+        );
+        let map = doc.as_hash().unwrap();
+        assert!(HttpProxyServerConfig::parse(map, None).is_err());
+
+        assert!(HttpForwardUpstreamProtocol::from_str("http/1.0").is_err());
+    }
+
+    #[test]
+    fn parse_header_rules() {
+        let doc = yaml_doc!(
+            r#"
+                type: http_proxy
+                name: s1
+                escaper: e1
+                http_req_header_rules:
+                  - action: remove
+                    name: X-Tracking-Id
+                  - action: add
+                    name: X-Forwarded-Proto
+                    value: https
+                http_rsp_header_rules:
+                  action: set
+                  name: Server
+                  value: g3proxy
+            "#
+        );
+        let map = doc.as_hash().unwrap();
+        let cfg = HttpProxyServerConfig::parse(map, None).unwrap();
+        assert_eq!(cfg.http_req_header_rules.rules().len(), 2);
+        assert_eq!(cfg.http_rsp_header_rules.rules().len(), 1);
+
+        let doc = yaml_doc!(
+            r#"
+                type: http_proxy
+                name: s1
+                escaper: e1
+                http_req_header_rules:
+                  - action: set
+                    name: Content-Length
+                    value: 0
+            "#
+        );
+        let map = doc.as_hash().unwrap();
+        assert!(HttpProxyServerConfig::parse(map, None).is_err());
+    }
 }
@@ -45,6 +45,8 @@ impl YamlMapCallback for HttpHostConfig {
         value: &Yaml,
         doc: Option<&YamlDocPosition>,
     ) -> anyhow::Result<()> {
+        const KEYS: &[&str] = &["upstream", "tls_server", "tls_client", "tls_name"];
+
         match key {
             "upstream" => {
                 self.upstream = g3_yaml::value::as_upstream_addr(value, 80)
@@ -78,7 +80,7 @@ impl YamlMapCallback for HttpHostConfig {
                     .context(format!("invalid tls name value for key {key}"))?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {key}")),
+            _ => Err(g3_yaml::key::invalid_at(key, KEYS, doc)),
         }
     }
 
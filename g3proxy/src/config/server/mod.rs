@@ -43,6 +43,18 @@ pub(crate) mod tcp_stream;
 pub(crate) mod tcp_tproxy;
 pub(crate) mod tls_stream;
 
+pub(crate) mod ext_authz;
+pub(crate) use ext_authz::ExtAuthzConfig;
+
+pub(crate) mod forwarded_header;
+pub(crate) use forwarded_header::ForwardedHeaderPolicy;
+
+pub(crate) mod real_client_ip;
+pub(crate) use real_client_ip::RealClientIpConfig;
+
+pub(crate) mod upstream_close;
+pub(crate) use upstream_close::UpstreamCloseStatusPolicy;
+
 pub(crate) mod username_params;
 pub(crate) use username_params::UsernameParamsConfig;
 
@@ -100,6 +112,10 @@ pub(crate) trait ServerConfig {
     fn task_max_idle_count(&self) -> usize {
         1
     }
+    /// the max idle count for tasks that have been upgraded by a 101 response
+    fn upgraded_task_max_idle_count(&self) -> Option<usize> {
+        None
+    }
 
     fn get_user_group(&self) -> Option<Arc<UserGroup>> {
         if self.user_group().is_empty() {
@@ -150,7 +166,7 @@ pub(crate) enum AnyServerConfig {
     TlsStream(tls_stream::TlsStreamServerConfig),
     SniProxy(sni_proxy::SniProxyServerConfig),
     SocksProxy(socks_proxy::SocksProxyServerConfig),
-    HttpProxy(http_proxy::HttpProxyServerConfig),
+    HttpProxy(Box<http_proxy::HttpProxyServerConfig>),
     HttpRProxy(http_rproxy::HttpRProxyServerConfig),
 }
 
@@ -265,7 +281,7 @@ fn load_server(
         "http_proxy" | "httpproxy" => {
             let server = http_proxy::HttpProxyServerConfig::parse(map, position)
                 .context("failed to load this HttpProxy server")?;
-            Ok(AnyServerConfig::HttpProxy(server))
+            Ok(AnyServerConfig::HttpProxy(Box::new(server)))
         }
         "http_rproxy" | "httprproxy" | "http_reverse_proxy" | "httpreverseproxy"
         | "http_gateway" | "httpgateway" => {
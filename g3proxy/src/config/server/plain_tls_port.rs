@@ -20,6 +20,30 @@ use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
 
 const SERVER_CONFIG_TYPE: &str = "PlainTlsPort";
 
+/// The action to take if a TLS ClientHello with the outer ECH extension is received
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) enum TlsEchAction {
+    /// Don't check the ECH extension
+    #[default]
+    Ignore,
+    /// Log the outer SNI and continue the handshake without ECH
+    Log,
+    /// Reject the handshake with a handshake_failure alert
+    Reject,
+}
+
+impl TlsEchAction {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let s = g3_yaml::value::as_string(v)?;
+        match s.to_lowercase().as_str() {
+            "ignore" => Ok(TlsEchAction::Ignore),
+            "log" => Ok(TlsEchAction::Log),
+            "reject" | "block" => Ok(TlsEchAction::Reject),
+            _ => Err(anyhow!("invalid tls ech action {s}")),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct PlainTlsPortConfig {
     name: NodeName,
@@ -29,6 +53,8 @@ pub(crate) struct PlainTlsPortConfig {
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) server_tls_config: Option<RustlsServerConfigBuilder>,
     pub(crate) tls_ticketer: Option<TlsTicketConfig>,
+    pub(crate) tls_ech_action: TlsEchAction,
+    pub(crate) tls_handshake_max_size: Option<usize>,
     pub(crate) server: NodeName,
     pub(crate) proxy_protocol: Option<ProxyProtocolVersion>,
     pub(crate) proxy_protocol_read_timeout: Duration,
@@ -44,6 +70,8 @@ impl PlainTlsPortConfig {
             ingress_net_filter: None,
             server_tls_config: None,
             tls_ticketer: None,
+            tls_ech_action: TlsEchAction::Ignore,
+            tls_handshake_max_size: None,
             server: NodeName::default(),
             proxy_protocol: None,
             proxy_protocol_read_timeout: Duration::from_secs(5),
@@ -99,6 +127,17 @@ impl PlainTlsPortConfig {
                 self.tls_ticketer = Some(ticketer);
                 Ok(())
             }
+            "tls_ech_action" => {
+                self.tls_ech_action = TlsEchAction::parse_yaml(v)
+                    .context(format!("invalid tls ech action value for key {k}"))?;
+                Ok(())
+            }
+            "tls_handshake_max_size" => {
+                let size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.tls_handshake_max_size = Some(size);
+                Ok(())
+            }
             "server" => {
                 self.server = g3_yaml::value::as_metric_node_name(v)?;
                 Ok(())
@@ -183,3 +222,37 @@ impl ServerConfig for PlainTlsPortConfig {
         Some(set)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tls_ech_action() {
+        for (s, action) in [
+            ("ignore", TlsEchAction::Ignore),
+            ("log", TlsEchAction::Log),
+            ("Log", TlsEchAction::Log),
+            ("reject", TlsEchAction::Reject),
+            ("block", TlsEchAction::Reject),
+        ] {
+            let v = Yaml::String(s.to_string());
+            assert_eq!(TlsEchAction::parse_yaml(&v).unwrap(), action);
+        }
+        assert!(TlsEchAction::parse_yaml(&Yaml::String("drop".to_string())).is_err());
+        assert!(TlsEchAction::parse_yaml(&Yaml::Integer(1)).is_err());
+        assert_eq!(TlsEchAction::default(), TlsEchAction::Ignore);
+
+        let mut config = PlainTlsPortConfig::new(None);
+        assert_eq!(config.tls_ech_action, TlsEchAction::Ignore);
+        config
+            .set("tls_ech_action", &Yaml::String("reject".to_string()))
+            .unwrap();
+        assert_eq!(config.tls_ech_action, TlsEchAction::Reject);
+        assert!(
+            config
+                .set("tls_ech_action", &Yaml::String("drop".to_string()))
+                .is_err()
+        );
+    }
+}
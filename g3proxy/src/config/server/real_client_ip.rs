@@ -0,0 +1,203 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow};
+use http::HeaderName;
+use ip_network::IpNetwork;
+use yaml_rust::Yaml;
+
+use g3_types::net::HttpHeaderMap;
+
+const X_FORWARDED_FOR: HeaderName = HeaderName::from_static("x-forwarded-for");
+
+/// How to find the real client ip in the X-Forwarded-For chain
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct RealClientIpConfig {
+    /// the max number of entries to walk back in the chain
+    pub(crate) trusted_hops: usize,
+    /// only walk back if the current hop is in these networks
+    pub(crate) trusted_peers: Vec<IpNetwork>,
+}
+
+impl Default for RealClientIpConfig {
+    fn default() -> Self {
+        RealClientIpConfig {
+            trusted_hops: 1,
+            trusted_peers: Vec::new(),
+        }
+    }
+}
+
+impl RealClientIpConfig {
+    pub(crate) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        let mut c = Self::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| c.set(k, v))?;
+            }
+            Yaml::Integer(_) => {
+                c.trusted_hops = g3_yaml::value::as_usize(value)?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'RealClientIpConfig' should be 'map' or 'usize'"
+                ));
+            }
+        }
+        c.check()?;
+        Ok(c)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "trusted_hops" => {
+                self.trusted_hops = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "trusted_peers" | "trusted_proxies" => {
+                self.trusted_peers = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid ip network list value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.trusted_hops == 0 {
+            return Err(anyhow!("trusted_hops should not be 0"));
+        }
+        Ok(())
+    }
+
+    fn trust(&self, ip: IpAddr) -> bool {
+        self.trusted_peers.is_empty() || self.trusted_peers.iter().any(|n| n.contains(ip))
+    }
+
+    /// Walk back the X-Forwarded-For chain from the peer ip, while the current hop is trusted.
+    ///
+    /// The walk stops at the first invalid entry, and the last valid hop will be returned.
+    pub(crate) fn find(&self, peer_ip: IpAddr, headers: &HttpHeaderMap) -> IpAddr {
+        let mut chain = Vec::new();
+        for value in headers.get_all(X_FORWARDED_FOR) {
+            chain.extend(value.to_str().split(',').map(str::trim));
+        }
+
+        let mut ip = peer_ip;
+        for _ in 0..self.trusted_hops {
+            if !self.trust(ip) {
+                break;
+            }
+            let Some(next) = chain.pop().and_then(parse_chain_entry) else {
+                break;
+            };
+            ip = next;
+        }
+        ip
+    }
+}
+
+fn parse_chain_entry(s: &str) -> Option<IpAddr> {
+    if let Ok(ip) = IpAddr::from_str(s) {
+        return Some(ip);
+    }
+    if let Ok(addr) = SocketAddr::from_str(s) {
+        return Some(addr.ip());
+    }
+    s.strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .and_then(|s| IpAddr::from_str(s).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    const PEER: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 2));
+
+    fn chain(values: &[&'static str]) -> HttpHeaderMap {
+        let mut headers = HttpHeaderMap::default();
+        for v in values {
+            headers.append(X_FORWARDED_FOR, HttpHeaderValue::from_static(v));
+        }
+        headers
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn single_hop() {
+        let config = RealClientIpConfig::parse(&Yaml::Integer(1)).unwrap();
+        let headers = chain(&["1.1.1.1, 2.2.2.2"]);
+        assert_eq!(config.find(PEER, &headers), ip("2.2.2.2"));
+        assert_eq!(config.find(PEER, &HttpHeaderMap::default()), PEER);
+    }
+
+    #[test]
+    fn multi_hop() {
+        let config = RealClientIpConfig::parse(&Yaml::Integer(2)).unwrap();
+        let headers = chain(&["1.1.1.1, 2.2.2.2", "3.3.3.3"]);
+        assert_eq!(config.find(PEER, &headers), ip("2.2.2.2"));
+
+        let config = RealClientIpConfig::parse(&Yaml::Integer(5)).unwrap();
+        assert_eq!(config.find(PEER, &headers), ip("1.1.1.1"));
+    }
+
+    #[test]
+    fn multi_hop_with_trusted_peers() {
+        let doc = yaml_doc!(
+            r#"
+                trusted_hops: 3
+                trusted_peers:
+                  - 10.0.0.0/8
+                  - 192.168.0.0/16
+            "#
+        );
+        let config = RealClientIpConfig::parse(&doc).unwrap();
+
+        // stop at the first untrusted hop
+        let headers = chain(&["1.1.1.1, 2.2.2.2, 192.168.1.1"]);
+        assert_eq!(config.find(PEER, &headers), ip("2.2.2.2"));
+
+        // the forged entries before the untrusted hop are ignored
+        let headers = chain(&["10.1.1.1, 2.2.2.2, 10.2.2.2"]);
+        assert_eq!(config.find(PEER, &headers), ip("2.2.2.2"));
+
+        // the hop limit is reached
+        let headers = chain(&["1.1.1.1, 10.3.3.3, 10.2.2.2, 10.1.1.1"]);
+        assert_eq!(config.find(PEER, &headers), ip("10.3.3.3"));
+
+        // the peer is not trusted
+        let headers = chain(&["1.1.1.1"]);
+        assert_eq!(config.find(ip("172.16.0.1"), &headers), ip("172.16.0.1"));
+    }
+
+    #[test]
+    fn invalid_entry() {
+        let config = RealClientIpConfig::parse(&Yaml::Integer(3)).unwrap();
+        let headers = chain(&["1.1.1.1, unknown, [2001:db8::1]:8080"]);
+        assert_eq!(config.find(PEER, &headers), ip("2001:db8::1"));
+
+        let headers = chain(&["[2001:db8::2], 192.168.1.1:1234"]);
+        assert_eq!(config.find(PEER, &headers), ip("2001:db8::2"));
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(RealClientIpConfig::parse(&Yaml::Integer(0)).is_err());
+        assert!(RealClientIpConfig::parse(&yaml_doc!("{ trusted_peers: abc }")).is_err());
+        assert!(RealClientIpConfig::parse(&yaml_doc!("{ hops: 1 }")).is_err());
+        assert!(RealClientIpConfig::parse(&Yaml::Boolean(true)).is_err());
+    }
+}
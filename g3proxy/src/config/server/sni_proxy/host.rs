@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use anyhow::{Context, anyhow};
+use anyhow::Context;
 use yaml_rust::Yaml;
 
 use g3_types::net::{Host, UpstreamAddr};
@@ -44,8 +44,10 @@ impl YamlMapCallback for SniHostConfig {
         &mut self,
         key: &str,
         value: &Yaml,
-        _doc: Option<&YamlDocPosition>,
+        doc: Option<&YamlDocPosition>,
     ) -> anyhow::Result<()> {
+        const KEYS: &[&str] = &["redirect_host", "redirect_port"];
+
         match g3_yaml::key::normalize(key).as_str() {
             "redirect_host" => {
                 let host = g3_yaml::value::as_host(value)
@@ -59,7 +61,7 @@ impl YamlMapCallback for SniHostConfig {
                 self.redirect_port = Some(port);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {key}")),
+            _ => Err(g3_yaml::key::invalid_at(key, KEYS, doc)),
         }
     }
 
@@ -75,6 +75,7 @@ pub(crate) struct SocksProxyServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) log_client_cert: bool,
     pub(crate) tcp_copy: StreamCopyConfig,
     pub(crate) udp_relay: LimitedUdpRelayConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
@@ -112,6 +113,7 @@ impl SocksProxyServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            log_client_cert: false,
             tcp_copy: Default::default(),
             udp_relay: Default::default(),
             tcp_misc_opts: Default::default(),
@@ -324,6 +326,11 @@ impl SocksProxyServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "log_client_cert" => {
+                self.log_client_cert = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
             "transmute_udp_echo_ip" => {
                 if let Yaml::Hash(_) = v {
                     let map = g3_yaml::value::as_hashmap(
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -48,7 +49,11 @@ pub(crate) struct TcpStreamServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) log_client_cert: bool,
     pub(crate) tcp_copy: StreamCopyConfig,
+    pub(crate) tcp_copy_buffer_budget: Option<usize>,
+    pub(crate) tcp_splice: bool,
+    pub(crate) traffic_mirror: Option<SocketAddr>,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
@@ -74,7 +79,11 @@ impl TcpStreamServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            log_client_cert: false,
             tcp_copy: Default::default(),
+            tcp_copy_buffer_budget: None,
+            tcp_splice: false,
+            traffic_mirror: None,
             tcp_misc_opts: Default::default(),
             extra_metrics_tags: None,
         }
@@ -193,6 +202,21 @@ impl TcpStreamServerConfig {
                 self.tcp_copy.set_yield_size(yield_size);
                 Ok(())
             }
+            "tcp_copy_buffer_budget" => {
+                let budget = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.tcp_copy_buffer_budget = Some(budget);
+                Ok(())
+            }
+            "tcp_splice" => {
+                self.tcp_splice = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "traffic_mirror" => {
+                let addr = g3_yaml::value::as_env_sockaddr(v)?;
+                self.traffic_mirror = Some(addr);
+                Ok(())
+            }
             "tcp_misc_opts" => {
                 self.tcp_misc_opts = g3_yaml::value::as_tcp_misc_sock_opts(v)
                     .context(format!("invalid tcp misc sock opts value for key {k}"))?;
@@ -226,6 +250,11 @@ impl TcpStreamServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "log_client_cert" => {
+                self.log_client_cert = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
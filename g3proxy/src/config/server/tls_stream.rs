@@ -51,7 +51,9 @@ pub(crate) struct TlsStreamServerConfig {
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
+    pub(crate) log_client_cert: bool,
     pub(crate) tcp_copy: StreamCopyConfig,
+    pub(crate) tcp_copy_buffer_budget: Option<usize>,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) extra_metrics_tags: Option<Arc<MetricTagMap>>,
 }
@@ -79,7 +81,9 @@ impl TlsStreamServerConfig {
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
+            log_client_cert: false,
             tcp_copy: Default::default(),
+            tcp_copy_buffer_budget: None,
             tcp_misc_opts: Default::default(),
             extra_metrics_tags: None,
         }
@@ -212,6 +216,12 @@ impl TlsStreamServerConfig {
                 self.tcp_copy.set_yield_size(yield_size);
                 Ok(())
             }
+            "tcp_copy_buffer_budget" => {
+                let budget = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                self.tcp_copy_buffer_budget = Some(budget);
+                Ok(())
+            }
             "tcp_misc_opts" => {
                 self.tcp_misc_opts = g3_yaml::value::as_tcp_misc_sock_opts(v)
                     .context(format!("invalid tcp misc sock opts value for key {k}"))?;
@@ -245,6 +255,11 @@ impl TlsStreamServerConfig {
                 self.task_log_flush_interval = Some(interval);
                 Ok(())
             }
+            "log_client_cert" => {
+                self.log_client_cert = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid boolean value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
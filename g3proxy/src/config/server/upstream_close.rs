@@ -0,0 +1,118 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+/// Force to close the upstream connection, instead of saving it for reuse,
+/// if the response status code matches
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct UpstreamCloseStatusPolicy {
+    /// the first digit of the status codes, such as 5 for 5xx
+    classes: BTreeSet<u16>,
+    codes: BTreeSet<u16>,
+}
+
+impl UpstreamCloseStatusPolicy {
+    pub(crate) fn matches(&self, code: u16) -> bool {
+        self.codes.contains(&code) || self.classes.contains(&(code / 100))
+    }
+
+    pub(crate) fn parse(value: &Yaml) -> anyhow::Result<Self> {
+        let mut policy = UpstreamCloseStatusPolicy::default();
+        match value {
+            Yaml::Array(seq) => {
+                for (i, v) in seq.iter().enumerate() {
+                    policy
+                        .add(v)
+                        .context(format!("invalid status code value for #{i}"))?;
+                }
+            }
+            _ => policy.add(value)?,
+        }
+        Ok(policy)
+    }
+
+    fn add(&mut self, value: &Yaml) -> anyhow::Result<()> {
+        match value {
+            Yaml::Integer(i) => {
+                let code =
+                    u16::try_from(*i).map_err(|_| anyhow!("out of range status code {i}"))?;
+                self.add_code(code)
+            }
+            Yaml::String(s) => {
+                let s = s.to_lowercase();
+                if let Some(class) = s.strip_suffix("xx") {
+                    let class = class
+                        .parse::<u16>()
+                        .map_err(|e| anyhow!("invalid status code class {s}: {e}"))?;
+                    if !(1..=5).contains(&class) {
+                        return Err(anyhow!("out of range status code class {s}"));
+                    }
+                    self.classes.insert(class);
+                    Ok(())
+                } else {
+                    let code = s
+                        .parse::<u16>()
+                        .map_err(|e| anyhow!("invalid status code {s}: {e}"))?;
+                    self.add_code(code)
+                }
+            }
+            _ => Err(anyhow!(
+                "yaml value type for status code should be 'string' or 'integer'"
+            )),
+        }
+    }
+
+    fn add_code(&mut self, code: u16) -> anyhow::Result<()> {
+        if !(100..=599).contains(&code) {
+            return Err(anyhow!("out of range status code {code}"));
+        }
+        self.codes.insert(code);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse() {
+        let policy = UpstreamCloseStatusPolicy::parse(&yaml_doc!("5xx")).unwrap();
+        assert!(policy.matches(500));
+        assert!(policy.matches(503));
+        assert!(!policy.matches(404));
+
+        let policy = UpstreamCloseStatusPolicy::parse(&yaml_doc!(
+            r#"
+                - 5XX
+                - 429
+                - "408"
+            "#
+        ))
+        .unwrap();
+        assert!(policy.matches(502));
+        assert!(policy.matches(429));
+        assert!(policy.matches(408));
+        assert!(!policy.matches(400));
+        assert!(!policy.matches(200));
+
+        assert!(!UpstreamCloseStatusPolicy::default().matches(500));
+    }
+
+    #[test]
+    fn parse_err() {
+        assert!(UpstreamCloseStatusPolicy::parse(&yaml_doc!("6xx")).is_err());
+        assert!(UpstreamCloseStatusPolicy::parse(&yaml_doc!("xx")).is_err());
+        assert!(UpstreamCloseStatusPolicy::parse(&yaml_doc!("1000")).is_err());
+        assert!(UpstreamCloseStatusPolicy::parse(&yaml_doc!("- 99")).is_err());
+        assert!(UpstreamCloseStatusPolicy::parse(&yaml_doc!("{a: 1}")).is_err());
+    }
+}
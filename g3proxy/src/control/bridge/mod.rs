@@ -3,6 +3,9 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+mod quiesce;
+pub(super) use quiesce::quiesce_server;
+
 mod reload;
 pub(super) use reload::{
     reload_auditor, reload_escaper, reload_resolver, reload_server, reload_user_group,
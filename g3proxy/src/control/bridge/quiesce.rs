@@ -0,0 +1,21 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use g3_types::metrics::NodeName;
+
+pub(in crate::control) async fn quiesce_server(
+    name: NodeName,
+    wait_timeout: Duration,
+) -> anyhow::Result<()> {
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(async move { crate::serve::quiesce(&name, wait_timeout).await })
+        .await
+        .map_err(|e| anyhow!("failed to spawn quiesce task: {e}"))?
+}
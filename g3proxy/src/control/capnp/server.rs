@@ -3,12 +3,16 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::time::Duration;
+
 use capnp::capability::Promise;
+use capnp_rpc::pry;
 
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::server_capnp::server_control;
 
+use super::set_operation_result;
 use crate::serve::ArcServer;
 
 pub(super) struct ServerControlImpl {
@@ -35,6 +39,10 @@ impl server_control::Server for ServerControlImpl {
             builder.set_alive_task_count(stats.get_alive_count());
             builder.set_total_conn_count(stats.get_conn_total());
             builder.set_total_task_count(stats.get_task_total());
+            if let Some((in_rate, out_rate)) = crate::serve::get_io_rate(stats.stat_id()) {
+                builder.set_in_rate(in_rate);
+                builder.set_out_rate(out_rate);
+            }
             Promise::ok(())
         } else {
             Promise::err(capnp::Error::failed(
@@ -42,4 +50,18 @@ impl server_control::Server for ServerControlImpl {
             ))
         }
     }
+
+    fn quiesce(
+        &mut self,
+        params: server_control::QuiesceParams,
+        mut results: server_control::QuiesceResults,
+    ) -> Promise<(), capnp::Error> {
+        let wait_timeout = Duration::from_secs(pry!(params.get()).get_wait_timeout() as u64);
+        let name = self.server.name().clone();
+        Promise::from_future(async move {
+            let r = crate::control::bridge::quiesce_server(name, wait_timeout).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
 }
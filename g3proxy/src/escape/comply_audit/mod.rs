@@ -66,9 +66,12 @@ impl ComplyAuditEscaper {
         Ok(Arc::new(escaper))
     }
 
-    pub(super) fn prepare_initial(config: ComplyAuditEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: ComplyAuditEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        ComplyAuditEscaper::new_obj(config, stats, super::registry::get_or_insert_default)
+        ComplyAuditEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
@@ -0,0 +1,130 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use g3_types::net::UpstreamAddr;
+
+const MAX_TRACKED_UPSTREAMS: usize = 4096;
+
+struct UpstreamDownState {
+    failures: usize,
+    last_failure: Instant,
+    down_until: Option<Instant>,
+}
+
+/// Track the upstreams that failed to connect repeatedly,
+/// so new connections to them can fail fast during the cooldown
+pub(super) struct UpstreamKnownDownCache {
+    cooldown: Duration,
+    threshold: usize,
+    inner: Mutex<HashMap<UpstreamAddr, UpstreamDownState>>,
+}
+
+impl UpstreamKnownDownCache {
+    pub(super) fn new(cooldown: Duration, threshold: usize) -> Self {
+        UpstreamKnownDownCache {
+            cooldown,
+            threshold: threshold.max(1),
+            inner: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check if the upstream is known down, the record will be reset if the cooldown is over
+    pub(super) fn is_down(&self, upstream: &UpstreamAddr) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(state) = inner.get(upstream) else {
+            return false;
+        };
+        match state.down_until {
+            Some(t) if t > Instant::now() => true,
+            Some(_) => {
+                inner.remove(upstream);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub(super) fn add_failure(&self, upstream: &UpstreamAddr) {
+        let now = Instant::now();
+        let mut inner = self.inner.lock().unwrap();
+        if inner.len() >= MAX_TRACKED_UPSTREAMS && !inner.contains_key(upstream) {
+            inner.retain(|_, state| match state.down_until {
+                Some(t) => t > now,
+                None => now.duration_since(state.last_failure) < self.cooldown,
+            });
+            if inner.len() >= MAX_TRACKED_UPSTREAMS {
+                return;
+            }
+        }
+
+        let state = inner
+            .entry(upstream.clone())
+            .or_insert_with(|| UpstreamDownState {
+                failures: 0,
+                last_failure: now,
+                down_until: None,
+            });
+        if now.duration_since(state.last_failure) >= self.cooldown {
+            // the previous failures are too old to be counted
+            state.failures = 0;
+        }
+        state.failures += 1;
+        state.last_failure = now;
+        if state.failures >= self.threshold {
+            state.down_until = Some(now + self.cooldown);
+        }
+    }
+
+    pub(super) fn add_success(&self, upstream: &UpstreamAddr) {
+        let mut inner = self.inner.lock().unwrap();
+        if !inner.is_empty() {
+            inner.remove(upstream);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn down_after_threshold() {
+        let cache = UpstreamKnownDownCache::new(Duration::from_millis(50), 2);
+        let ups = UpstreamAddr::from_str("www.example.net:80").unwrap();
+        let other = UpstreamAddr::from_str("www.example.net:443").unwrap();
+
+        assert!(!cache.is_down(&ups));
+        cache.add_failure(&ups);
+        assert!(!cache.is_down(&ups));
+        cache.add_failure(&ups);
+        assert!(cache.is_down(&ups));
+        assert!(!cache.is_down(&other));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(!cache.is_down(&ups));
+        cache.add_failure(&ups);
+        assert!(!cache.is_down(&ups));
+    }
+
+    #[test]
+    fn reset_on_success() {
+        let cache = UpstreamKnownDownCache::new(Duration::from_secs(10), 2);
+        let ups = UpstreamAddr::from_str("127.0.0.1:80").unwrap();
+
+        cache.add_failure(&ups);
+        cache.add_success(&ups);
+        cache.add_failure(&ups);
+        assert!(!cache.is_down(&ups));
+        cache.add_failure(&ups);
+        assert!(cache.is_down(&ups));
+        cache.add_success(&ups);
+        assert!(!cache.is_down(&ups));
+    }
+}
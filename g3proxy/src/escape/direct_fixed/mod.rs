@@ -17,7 +17,9 @@ use g3_socket::BindAddr;
 use g3_socket::util::AddressFamily;
 use g3_types::acl::AclNetworkRule;
 use g3_types::metrics::NodeName;
-use g3_types::net::{Host, ProxyProtocolEncoder, ProxyProtocolVersion, UpstreamAddr};
+use g3_types::net::{
+    Host, ProxyProtocolEncoder, ProxyProtocolVersion, TcpConnectTimeoutRules, UpstreamAddr,
+};
 use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
 
 use super::{ArcEscaper, ArcEscaperStats, Escaper, EscaperInternal, EscaperRegistry, EscaperStats};
@@ -34,7 +36,8 @@ use crate::module::http_forward::{
     DirectHttpForwardContext,
 };
 use crate::module::tcp_connect::{
-    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes,
+    TcpSpliceConnectResult, TlsConnectTaskConf,
 };
 use crate::module::udp_connect::{
     ArcUdpConnectTaskRemoteStats, UdpConnectResult, UdpConnectTaskConf, UdpConnectTaskNotes,
@@ -48,6 +51,9 @@ use crate::serve::ServerTaskNotes;
 mod stats;
 pub(crate) use stats::DirectFixedEscaperStats;
 
+mod known_down;
+use known_down::UpstreamKnownDownCache;
+
 mod ftp_connect;
 pub(crate) mod http_forward;
 pub(crate) mod tcp_connect;
@@ -61,6 +67,8 @@ pub(super) struct DirectFixedEscaper {
     resolver_handle: ArcIntegratedResolverHandle,
     egress_net_filter: Arc<AclNetworkRule>,
     resolve_redirection: Option<ResolveRedirection>,
+    tcp_connect_timeout_rules: Option<TcpConnectTimeoutRules>,
+    upstream_down_cache: Option<UpstreamKnownDownCache>,
     escape_logger: Option<Logger>,
 }
 
@@ -76,6 +84,13 @@ impl DirectFixedEscaper {
             .resolve_redirection
             .as_ref()
             .map(|builder| builder.build());
+        let tcp_connect_timeout_rules = config
+            .tcp_connect_timeout_rules
+            .as_ref()
+            .map(|builder| builder.build());
+        let upstream_down_cache = config
+            .upstream_down_cooldown
+            .map(|cooldown| UpstreamKnownDownCache::new(cooldown, config.upstream_down_threshold));
 
         let escape_logger = config.get_escape_logger();
 
@@ -87,6 +102,8 @@ impl DirectFixedEscaper {
             resolver_handle,
             egress_net_filter,
             resolve_redirection,
+            tcp_connect_timeout_rules,
+            upstream_down_cache,
             escape_logger,
         };
 
@@ -309,6 +326,25 @@ impl Escaper for DirectFixedEscaper {
             .await
     }
 
+    async fn tcp_setup_splice_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+        _audit_ctx: &mut AuditContext,
+    ) -> TcpSpliceConnectResult {
+        if self.config.general.tcp_sock_speed_limit.shift_millis > 0 {
+            // speed limit can only be applied on the buffered copy
+            return Err(TcpConnectError::MethodUnavailable);
+        }
+
+        self.stats.interface.add_tcp_connect_attempted();
+        tcp_notes.escaper.clone_from(&self.config.name);
+        self.tcp_new_splice_connection(task_conf, tcp_notes, task_notes, task_stats)
+            .await
+    }
+
     async fn tls_setup_connection(
         &self,
         task_conf: &TlsConnectTaskConf<'_>,
@@ -14,8 +14,8 @@ use tokio::time::Instant;
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
 use g3_io_ext::{LimitedReader, LimitedWriter};
-use g3_socket::BindAddr;
 use g3_socket::util::AddressFamily;
+use g3_socket::{BindAddr, RawSocket};
 use g3_types::acl::AclAction;
 use g3_types::net::{
     ConnectError, Host, TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UpstreamAddr,
@@ -25,7 +25,7 @@ use super::DirectFixedEscaper;
 use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectRemoteWrapperStats, TcpConnectResult, TcpConnectTaskConf,
-    TcpConnectTaskNotes,
+    TcpConnectTaskNotes, TcpSpliceConnectResult,
 };
 use crate::resolve::HappyEyeballsResolveJob;
 use crate::serve::ServerTaskNotes;
@@ -131,6 +131,9 @@ impl DirectFixedEscaper {
                     .map_err(TcpConnectError::SetupSocketFailed)?;
                 self.stats.tcp.connect.add_established();
                 tcp_notes.local = Some(local_addr);
+                if config.misc_opts.socket_buffer_set() {
+                    tcp_notes.socket_buffer = RawSocket::from(&ups_stream).get_buf_opts().ok();
+                }
                 tcp_notes.chained.target_addr = Some(peer);
                 tcp_notes.chained.outgoing_addr = Some(local_addr);
                 Ok(ups_stream)
@@ -258,6 +261,10 @@ impl DirectFixedEscaper {
                                             .map_err(TcpConnectError::SetupSocketFailed)?;
                                         self.stats.tcp.connect.add_established();
                                         tcp_notes.local = Some(local_addr);
+                                        if config.misc_opts.socket_buffer_set() {
+                                            tcp_notes.socket_buffer =
+                                                RawSocket::from(&ups_stream).get_buf_opts().ok();
+                                        }
                                         tcp_notes.chained.target_addr = Some(peer_addr);
                                         tcp_notes.chained.outgoing_addr = Some(local_addr);
                                         return Ok(ups_stream);
@@ -340,6 +347,11 @@ impl DirectFixedEscaper {
             keepalive: self.config.tcp_keepalive,
             misc_opts: Cow::Borrowed(&self.config.tcp_misc_opts),
         };
+        if let Some(rules) = &self.tcp_connect_timeout_rules
+            && let Some(timeout) = rules.get(task_conf.upstream.host())
+        {
+            config.connect.set_each_timeout(timeout);
+        }
 
         if let Some(user_ctx) = task_notes.user_ctx() {
             let user_config = user_ctx.user_config();
@@ -352,7 +364,13 @@ impl DirectFixedEscaper {
             config.misc_opts = user_config.tcp_remote_misc_opts(&self.config.tcp_misc_opts);
         }
 
-        match task_conf.upstream.host() {
+        if let Some(cache) = &self.upstream_down_cache
+            && cache.is_down(task_conf.upstream)
+        {
+            return Err(TcpConnectError::UpstreamKnownDown);
+        }
+
+        let r = match task_conf.upstream.host() {
             Host::Ip(ip) => {
                 self.fixed_try_connect(*ip, config, task_conf, tcp_notes, task_notes)
                     .await
@@ -367,7 +385,20 @@ impl DirectFixedEscaper {
                 self.happy_try_connect(resolver_job, config, task_conf, tcp_notes, task_notes)
                     .await
             }
+        };
+
+        if let Some(cache) = &self.upstream_down_cache {
+            match &r {
+                Ok(_) => cache.add_success(task_conf.upstream),
+                Err(
+                    TcpConnectError::ConnectFailed(_)
+                    | TcpConnectError::TimeoutByRule
+                    | TcpConnectError::NoAddressConnected,
+                ) => cache.add_failure(task_conf.upstream),
+                Err(_) => {}
+            }
         }
+        r
     }
 
     pub(super) async fn tcp_connect_to_again(
@@ -386,6 +417,11 @@ impl DirectFixedEscaper {
             keepalive: TcpKeepAliveConfig::default(),
             misc_opts: Cow::Borrowed(&self.config.tcp_misc_opts),
         };
+        if let Some(rules) = &self.tcp_connect_timeout_rules
+            && let Some(timeout) = rules.get(task_conf.upstream.host())
+        {
+            config.connect.set_each_timeout(timeout);
+        }
 
         if let Some(user_ctx) = task_notes.user_ctx() {
             if let Some(user_config) = &user_ctx.user_config().tcp_connect {
@@ -478,4 +514,24 @@ impl DirectFixedEscaper {
 
         Ok((Box::new(r), Box::new(w)))
     }
+
+    pub(super) async fn tcp_new_splice_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+    ) -> TcpSpliceConnectResult {
+        let mut stream = self
+            .tcp_connect_to(task_conf, tcp_notes, task_notes)
+            .await?;
+        if let Some(version) = self.config.use_proxy_protocol {
+            self.send_tcp_proxy_protocol_header(version, &mut stream, task_notes, true)
+                .await?;
+        }
+
+        let mut wrapper_stats = TcpConnectRemoteWrapperStats::new(self.stats.clone(), task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        Ok((stream, Arc::new(wrapper_stats)))
+    }
 }
@@ -19,7 +19,7 @@ use g3_resolver::ResolveError;
 use g3_socket::util::AddressFamily;
 use g3_types::acl::AclNetworkRule;
 use g3_types::metrics::NodeName;
-use g3_types::net::{Host, UpstreamAddr};
+use g3_types::net::{Host, TcpConnectTimeoutRules, UpstreamAddr};
 use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
 
 use super::{
@@ -66,6 +66,7 @@ pub(super) struct DirectFloatEscaper {
     resolver_handle: ArcIntegratedResolverHandle,
     egress_net_filter: Arc<AclNetworkRule>,
     resolve_redirection: Option<ResolveRedirection>,
+    tcp_connect_timeout_rules: Option<TcpConnectTimeoutRules>,
     bind_v4: ArcSwap<BindSet>,
     bind_v6: ArcSwap<BindSet>,
     escape_logger: Option<Logger>,
@@ -85,6 +86,10 @@ impl DirectFloatEscaper {
             .resolve_redirection
             .as_ref()
             .map(|builder| builder.build());
+        let tcp_connect_timeout_rules = config
+            .tcp_connect_timeout_rules
+            .as_ref()
+            .map(|builder| builder.build());
 
         let escape_logger = config.get_escape_logger();
 
@@ -98,6 +103,7 @@ impl DirectFloatEscaper {
             resolver_handle,
             egress_net_filter,
             resolve_redirection,
+            tcp_connect_timeout_rules,
             bind_v4,
             bind_v6,
             escape_logger,
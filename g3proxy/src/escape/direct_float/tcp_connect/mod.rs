@@ -14,8 +14,8 @@ use tokio::time::Instant;
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
 use g3_io_ext::{LimitedReader, LimitedWriter};
-use g3_socket::BindAddr;
 use g3_socket::util::AddressFamily;
+use g3_socket::{BindAddr, RawSocket};
 use g3_types::acl::AclAction;
 use g3_types::net::{ConnectError, Host, TcpKeepAliveConfig, UpstreamAddr};
 
@@ -130,6 +130,9 @@ impl DirectFloatEscaper {
                     .map_err(TcpConnectError::SetupSocketFailed)?;
                 self.stats.tcp.connect.add_established();
                 tcp_notes.local = Some(local_addr);
+                if config.misc_opts.socket_buffer_set() {
+                    tcp_notes.socket_buffer = RawSocket::from(&ups_stream).get_buf_opts().ok();
+                }
                 tcp_notes.chained.target_addr = Some(peer);
                 tcp_notes.chained.outgoing_addr = Some(local_addr);
                 Ok((ups_stream, bind))
@@ -259,6 +262,10 @@ impl DirectFloatEscaper {
                                             .map_err(TcpConnectError::SetupSocketFailed)?;
                                         self.stats.tcp.connect.add_established();
                                         tcp_notes.local = Some(local_addr);
+                                        if config.misc_opts.socket_buffer_set() {
+                                            tcp_notes.socket_buffer =
+                                                RawSocket::from(&ups_stream).get_buf_opts().ok();
+                                        }
                                         tcp_notes.chained.target_addr = Some(peer_addr);
                                         tcp_notes.chained.outgoing_addr = Some(local_addr);
                                         return Ok((ups_stream, bind));
@@ -341,6 +348,11 @@ impl DirectFloatEscaper {
             keepalive: self.config.tcp_keepalive,
             misc_opts: Cow::Borrowed(&self.config.tcp_misc_opts),
         };
+        if let Some(rules) = &self.tcp_connect_timeout_rules
+            && let Some(timeout) = rules.get(task_conf.upstream.host())
+        {
+            config.connect.set_each_timeout(timeout);
+        }
 
         if let Some(user_ctx) = task_notes.user_ctx() {
             let user_config = user_ctx.user_config();
@@ -387,6 +399,11 @@ impl DirectFloatEscaper {
             keepalive: TcpKeepAliveConfig::default(),
             misc_opts: Cow::Borrowed(&self.config.tcp_misc_opts),
         };
+        if let Some(rules) = &self.tcp_connect_timeout_rules
+            && let Some(timeout) = rules.get(task_conf.upstream.host())
+        {
+            config.connect.set_each_timeout(timeout);
+        }
 
         if let Some(user_ctx) = task_notes.user_ctx() {
             if let Some(user_config) = &user_ctx.user_config().tcp_connect {
@@ -3,6 +3,8 @@
  * Copyright 2024-2025 ByteDance and/or its affiliates.
  */
 
+use std::hash::{Hash, Hasher};
+
 use foldhash::HashMap;
 
 use g3_types::metrics::NodeName;
@@ -66,6 +68,26 @@ impl EgressPathSelection {
     }
 }
 
+fn sorted_entries<V>(map: &HashMap<NodeName, V>) -> Vec<(&NodeName, &V)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+impl Hash for EgressPathSelection {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        sorted_entries(&self.number).hash(state);
+        sorted_entries(&self.string).hash(state);
+        sorted_entries(&self.upstream).hash(state);
+        let json = sorted_entries(&self.json);
+        json.len().hash(state);
+        for (escaper, v) in json {
+            escaper.hash(state);
+            v.to_string().hash(state);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -97,4 +119,30 @@ mod tests {
         egress_path.set_number_id(ESCAPER.clone(), 61);
         assert_eq!(Some(0), egress_path.select_number_id(&ESCAPER, LENGTH));
     }
+
+    #[test]
+    fn hash_value() {
+        use std::hash::DefaultHasher;
+
+        fn hash(v: &EgressPathSelection) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            v.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut a = EgressPathSelection::default();
+        a.set_number_id(NodeName::new_static("a"), 1);
+        a.set_number_id(NodeName::new_static("b"), 2);
+        a.set_json_value(NodeName::new_static("c"), serde_json::json!({"k": "v"}));
+
+        let mut b = EgressPathSelection::default();
+        b.set_json_value(NodeName::new_static("c"), serde_json::json!({"k": "v"}));
+        b.set_number_id(NodeName::new_static("b"), 2);
+        b.set_number_id(NodeName::new_static("a"), 1);
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+
+        b.set_string_id(NodeName::new_static("a"), "1".to_string());
+        assert_ne!(hash(&a), hash(&b));
+    }
 }
@@ -23,7 +23,8 @@ use crate::module::http_forward::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, BoxHttpForwardContext,
 };
 use crate::module::tcp_connect::{
-    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes,
+    TcpSpliceConnectResult, TlsConnectTaskConf,
 };
 use crate::module::udp_connect::{
     ArcUdpConnectTaskRemoteStats, UdpConnectResult, UdpConnectTaskConf, UdpConnectTaskNotes,
@@ -35,6 +36,8 @@ use crate::serve::ServerTaskNotes;
 
 mod registry;
 use registry::EscaperRegistry;
+
+mod staging;
 pub(crate) use registry::{foreach as foreach_escaper, get_names, get_or_insert_default};
 
 mod stats;
@@ -50,6 +53,8 @@ pub(crate) use egress_path::EgressPathSelection;
 
 mod comply_audit;
 mod direct_fixed;
+pub(crate) use direct_fixed::DirectFixedEscaperStats;
+pub(crate) use direct_fixed::http_forward::{DirectHttpForwardReader, DirectHttpForwardWriter};
 mod direct_float;
 mod divert_tcp;
 mod dummy_deny;
@@ -168,6 +173,21 @@ pub(crate) trait Escaper: EscaperInternal {
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult;
 
+    /// Setup a raw tcp connection that can be used for zero-copy relay.
+    ///
+    /// MethodUnavailable will be returned if not supported by this escaper,
+    /// the caller should use `tcp_setup_connection` instead.
+    async fn tcp_setup_splice_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        _tcp_notes: &mut TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcTcpConnectionTaskRemoteStats,
+        _audit_ctx: &mut AuditContext,
+    ) -> TcpSpliceConnectResult {
+        Err(TcpConnectError::MethodUnavailable)
+    }
+
     async fn tls_setup_connection(
         &self,
         task_conf: &TlsConnectTaskConf<'_>,
@@ -14,62 +14,58 @@ use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
 use super::registry;
-use crate::config::escaper::{AnyEscaperConfig, EscaperConfigDiffAction};
+use super::staging::EscaperStaging;
+use crate::config::escaper::AnyEscaperConfig;
 use crate::escape::ArcEscaper;
 
-use super::comply_audit::ComplyAuditEscaper;
-use super::direct_fixed::DirectFixedEscaper;
-use super::direct_float::DirectFloatEscaper;
-use super::divert_tcp::DivertTcpEscaper;
-use super::dummy_deny::DummyDenyEscaper;
-use super::proxy_float::ProxyFloatEscaper;
-use super::proxy_http::ProxyHttpEscaper;
-use super::proxy_https::ProxyHttpsEscaper;
-use super::proxy_socks5::ProxySocks5Escaper;
-use super::proxy_socks5s::ProxySocks5sEscaper;
-use super::route_client::RouteClientEscaper;
-use super::route_failover::RouteFailoverEscaper;
-use super::route_geoip::RouteGeoIpEscaper;
-use super::route_mapping::RouteMappingEscaper;
-use super::route_query::RouteQueryEscaper;
-use super::route_resolved::RouteResolvedEscaper;
-use super::route_select::RouteSelectEscaper;
-use super::route_upstream::RouteUpstreamEscaper;
-use super::trick_float::TrickFloatEscaper;
-
 static ESCAPER_OPS_LOCK: Mutex<()> = Mutex::const_new(());
 
+/// Apply all escaper configs, it's all or nothing.
+///
+/// All the changes will be staged and validated first, and then committed to the running registry
+/// only if all of them succeeded. The running escapers will not be changed if any of them failed.
 pub async fn load_all() -> anyhow::Result<()> {
     let _guard = ESCAPER_OPS_LOCK.lock().await;
 
-    let mut new_names = HashSet::<NodeName>::new();
-
     let all_config = crate::config::escaper::get_all_sorted()?;
+
+    let mut staging = EscaperStaging::new();
+    let old_names = staging.running_names();
+    let mut new_names = HashSet::<NodeName>::new();
     for config in all_config {
         let name = config.name();
         new_names.insert(name.clone());
-        match registry::get_config(name) {
+        match staging.get_config(name) {
             Some(old) => {
                 debug!("reloading escaper {name}");
-                reload_unlocked(old, config.as_ref().clone()).await?;
+                staging
+                    .reload(old, config.as_ref().clone())
+                    .await
+                    .context(format!("failed to reload escaper {name}"))?;
                 debug!("escaper {name} reload OK");
             }
             None => {
                 debug!("creating escaper {name}");
-                spawn_new_unlocked(config.as_ref().clone()).await?;
+                staging
+                    .spawn_new(config.as_ref().clone())
+                    .await
+                    .context(format!("failed to create escaper {name}"))?;
                 debug!("escaper {name} create OK");
             }
         }
     }
 
-    for name in &registry::get_names() {
-        if !new_names.contains(name) {
+    for name in old_names {
+        if !new_names.contains(&name) {
             debug!("deleting escaper {name}");
-            delete_existed_unlocked(name).await;
+            staging
+                .delete(&name)
+                .context(format!("failed to delete escaper {name}"))?;
             debug!("escaper {name} deleted");
         }
     }
 
+    staging.commit().await;
     Ok(())
 }
 
@@ -117,7 +113,9 @@ pub(crate) async fn reload(
     }
 
     debug!("reloading escaper {name} from position {position}");
-    reload_unlocked(old_config, config).await?;
+    let mut staging = EscaperStaging::new();
+    staging.reload(old_config, config).await?;
+    staging.commit().await;
     debug!("escaper {name} reload OK");
     Ok(())
 }
@@ -198,32 +196,6 @@ async fn update_dependency_to_escaper_unlocked(target: &NodeName, status: &str)
     }
 }
 
-async fn reload_unlocked(old: AnyEscaperConfig, new: AnyEscaperConfig) -> anyhow::Result<()> {
-    let name = old.name();
-    match old.diff_action(&new) {
-        EscaperConfigDiffAction::NoAction => {
-            debug!("escaper {name} reload: no action is needed");
-            Ok(())
-        }
-        EscaperConfigDiffAction::SpawnNew => {
-            debug!("escaper {name} reload: will create a totally new one");
-            spawn_new_unlocked(new).await
-        }
-        EscaperConfigDiffAction::Reload => {
-            debug!("escaper {name} reload: will reload from existed");
-            reload_existed_unlocked(name, Some(new)).await
-        }
-    }
-}
-
-async fn delete_existed_unlocked(name: &NodeName) {
-    const STATUS: &str = "deleted";
-
-    registry::del(name);
-    update_dependency_to_escaper_unlocked(name, STATUS).await;
-    crate::serve::update_dependency_to_escaper(name, STATUS).await;
-}
-
 async fn reload_existed_unlocked(
     name: &NodeName,
     new: Option<AnyEscaperConfig>,
@@ -235,34 +207,3 @@ async fn reload_existed_unlocked(
     crate::serve::update_dependency_to_escaper(name, STATUS).await;
     Ok(())
 }
-
-async fn spawn_new_unlocked(config: AnyEscaperConfig) -> anyhow::Result<()> {
-    const STATUS: &str = "spawned";
-
-    let name = config.name().clone();
-    let escaper = match config {
-        AnyEscaperConfig::ComplyAudit(c) => ComplyAuditEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::DirectFixed(c) => DirectFixedEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::DirectFloat(c) => DirectFloatEscaper::prepare_initial(c).await?,
-        AnyEscaperConfig::DivertTcp(c) => DivertTcpEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::DummyDeny(c) => DummyDenyEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::ProxyFloat(c) => ProxyFloatEscaper::prepare_initial(c).await?,
-        AnyEscaperConfig::ProxyHttp(c) => ProxyHttpEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::ProxyHttps(c) => ProxyHttpsEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::ProxySocks5(c) => ProxySocks5Escaper::prepare_initial(c)?,
-        AnyEscaperConfig::ProxySocks5s(c) => ProxySocks5sEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteFailover(c) => RouteFailoverEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteResolved(c) => RouteResolvedEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteGeoIp(c) => RouteGeoIpEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteMapping(c) => RouteMappingEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteQuery(c) => RouteQueryEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteSelect(c) => RouteSelectEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteUpstream(c) => RouteUpstreamEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::RouteClient(c) => RouteClientEscaper::prepare_initial(c)?,
-        AnyEscaperConfig::TrickFloat(c) => TrickFloatEscaper::prepare_initial(c)?,
-    };
-    registry::add(name.clone(), escaper);
-    update_dependency_to_escaper_unlocked(&name, STATUS).await;
-    crate::serve::update_dependency_to_escaper(&name, STATUS).await;
-    Ok(())
-}
@@ -17,12 +17,13 @@ use crate::config::escaper::AnyEscaperConfig;
 
 static RUNTIME_ESCAPER_REGISTRY: Mutex<EscaperRegistry> = Mutex::new(EscaperRegistry::new());
 
+#[derive(Clone)]
 pub(crate) struct EscaperRegistry {
     inner: HashMap<NodeName, ArcEscaper, FixedState>,
 }
 
 impl EscaperRegistry {
-    const fn new() -> Self {
+    pub(super) const fn new() -> Self {
         EscaperRegistry {
             inner: HashMap::with_hasher(FixedState::with_seed(0)),
         }
@@ -40,7 +41,17 @@ impl EscaperRegistry {
         }
     }
 
-    fn foreach<F>(&self, mut f: F)
+    /// Insert without cleaning the replaced one, which is returned
+    pub(super) fn insert(&mut self, name: NodeName, escaper: ArcEscaper) -> Option<ArcEscaper> {
+        self.inner.insert(name, escaper)
+    }
+
+    /// Remove without cleaning the removed one, which is returned
+    pub(super) fn remove(&mut self, name: &NodeName) -> Option<ArcEscaper> {
+        self.inner.remove(name)
+    }
+
+    pub(super) fn foreach<F>(&self, mut f: F)
     where
         F: FnMut(&NodeName, &ArcEscaper),
     {
@@ -49,15 +60,15 @@ impl EscaperRegistry {
         }
     }
 
-    fn get_names(&self) -> HashSet<NodeName> {
+    pub(super) fn get_names(&self) -> HashSet<NodeName> {
         self.inner.keys().cloned().collect()
     }
 
-    fn get_escaper(&self, name: &NodeName) -> Option<ArcEscaper> {
+    pub(super) fn get_escaper(&self, name: &NodeName) -> Option<ArcEscaper> {
         self.inner.get(name).cloned()
     }
 
-    fn get_config(&self, name: &NodeName) -> Option<AnyEscaperConfig> {
+    pub(super) fn get_config(&self, name: &NodeName) -> Option<AnyEscaperConfig> {
         self.inner.get(name).map(|escaper| escaper._clone_config())
     }
 
@@ -66,15 +77,24 @@ impl EscaperRegistry {
         name: &NodeName,
         config: Option<AnyEscaperConfig>,
     ) -> anyhow::Result<()> {
+        let escaper = self.prepare_reload(name, config)?;
+        self.add(name.clone(), escaper);
+        Ok(())
+    }
+
+    /// Create the reloaded escaper without adding it
+    pub(super) fn prepare_reload(
+        &mut self,
+        name: &NodeName,
+        config: Option<AnyEscaperConfig>,
+    ) -> anyhow::Result<ArcEscaper> {
         let Some(old_escaper) = self.inner.get(name) else {
             return Err(anyhow!("no escaper with name {name} found"));
         };
 
         let old_escaper = old_escaper.clone();
         let config = config.unwrap_or_else(|| old_escaper._clone_config());
-        let escaper = old_escaper._reload(config, self)?;
-        self.add(name.clone(), escaper);
-        Ok(())
+        old_escaper._reload(config, self)
     }
 
     pub(super) fn get_or_insert_default(&mut self, name: &NodeName) -> ArcEscaper {
@@ -85,6 +105,12 @@ impl EscaperRegistry {
     }
 }
 
+/// Get a copy of the running registry, the escapers are shared
+pub(super) fn snapshot() -> EscaperRegistry {
+    let r = RUNTIME_ESCAPER_REGISTRY.lock().unwrap();
+    r.clone()
+}
+
 pub(super) fn add(name: NodeName, escaper: ArcEscaper) {
     let mut r = RUNTIME_ESCAPER_REGISTRY.lock().unwrap();
     r.add(name, escaper);
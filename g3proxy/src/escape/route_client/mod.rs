@@ -95,9 +95,12 @@ impl RouteClientEscaper {
         Ok(Arc::new(escaper))
     }
 
-    pub(super) fn prepare_initial(config: RouteClientEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: RouteClientEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteClientEscaper::new_obj(config, stats, super::registry::get_or_insert_default)
+        RouteClientEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
@@ -72,9 +72,10 @@ impl RouteFailoverEscaper {
 
     pub(super) fn prepare_initial(
         config: RouteFailoverEscaperConfig,
+        registry: &mut EscaperRegistry,
     ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteFailoverEscaper::new_obj(config, stats, crate::escape::get_or_insert_default)
+        RouteFailoverEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
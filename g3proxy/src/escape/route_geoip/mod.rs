@@ -20,6 +20,7 @@ use g3_ip_locate::IpLocationServiceHandle;
 use g3_resolver::ResolveError;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
+use g3_types::resolve::ResolveRedirection;
 
 use super::{ArcEscaper, Escaper, EscaperInternal, EscaperRegistry, RouteEscaperStats};
 use crate::audit::AuditContext;
@@ -49,6 +50,7 @@ pub(super) struct RouteGeoIpEscaper {
     config: RouteGeoIpEscaperConfig,
     stats: Arc<RouteEscaperStats>,
     resolver_handle: ArcIntegratedResolverHandle,
+    resolve_redirection: Option<ResolveRedirection>,
     ip_locate_handle: IpLocationServiceHandle,
     next_table: BTreeMap<NodeName, ArcEscaper>,
     lpm_table: IpNetworkTable<ArcEscaper>,
@@ -71,6 +73,10 @@ impl RouteGeoIpEscaper {
         F: FnMut(&NodeName) -> ArcEscaper,
     {
         let resolver_handle = crate::resolve::get_handle(config.resolver())?;
+        let resolve_redirection = config
+            .resolve_redirection
+            .as_ref()
+            .map(|builder| builder.build());
         let ip_locate_handle = config.ip_locate_service.spawn_ip_locate_agent()?;
 
         let mut next_table = BTreeMap::new();
@@ -126,6 +132,7 @@ impl RouteGeoIpEscaper {
             config,
             stats,
             resolver_handle,
+            resolve_redirection,
             ip_locate_handle,
             next_table,
             lpm_table,
@@ -141,9 +148,12 @@ impl RouteGeoIpEscaper {
         Ok(Arc::new(escaper))
     }
 
-    pub(super) fn prepare_initial(config: RouteGeoIpEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: RouteGeoIpEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteGeoIpEscaper::new_obj(config, stats, super::registry::get_or_insert_default)
+        RouteGeoIpEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
@@ -162,11 +172,21 @@ impl RouteGeoIpEscaper {
         match ups {
             Host::Ip(ip) => Ok(*ip),
             Host::Domain(domain) => {
-                let mut resolver_job = HappyEyeballsResolveJob::new_dyn(
-                    self.config.resolve_strategy,
-                    &self.resolver_handle,
-                    domain.clone(),
-                )?;
+                let mut resolver_job = if let Some(redirect) = &self.resolve_redirection
+                    && let Some(v) = redirect.query_value(domain)
+                {
+                    HappyEyeballsResolveJob::new_redirected(
+                        self.config.resolve_strategy,
+                        &self.resolver_handle,
+                        v,
+                    )?
+                } else {
+                    HappyEyeballsResolveJob::new_dyn(
+                        self.config.resolve_strategy,
+                        &self.resolver_handle,
+                        domain.clone(),
+                    )?
+                };
                 let v = resolver_job
                     .get_r1_or_first(self.config.resolution_delay, usize::MAX)
                     .await?;
@@ -65,13 +65,14 @@ impl RouteMappingEscaper {
         Arc::new(escaper)
     }
 
-    pub(super) fn prepare_initial(config: RouteMappingEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: RouteMappingEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        Ok(RouteMappingEscaper::new_obj(
-            config,
-            stats,
-            super::registry::get_or_insert_default,
-        ))
+        Ok(RouteMappingEscaper::new_obj(config, stats, |name| {
+            registry.get_or_insert_default(name)
+        }))
     }
 
     fn prepare_reload(
@@ -79,13 +79,14 @@ impl RouteQueryEscaper {
         Ok(Arc::new(escaper))
     }
 
-    pub(super) fn prepare_initial(config: RouteQueryEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: RouteQueryEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteQueryEscaper::new_obj(
-            Arc::new(config),
-            stats,
-            super::registry::get_or_insert_default,
-        )
+        RouteQueryEscaper::new_obj(Arc::new(config), stats, |name| {
+            registry.get_or_insert_default(name)
+        })
     }
 
     fn prepare_reload(
@@ -15,6 +15,7 @@ use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
 use g3_resolver::ResolveError;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
+use g3_types::resolve::ResolveRedirection;
 
 use super::{ArcEscaper, Escaper, EscaperInternal, EscaperRegistry, RouteEscaperStats};
 use crate::audit::AuditContext;
@@ -44,6 +45,7 @@ pub(super) struct RouteResolvedEscaper {
     config: RouteResolvedEscaperConfig,
     stats: Arc<RouteEscaperStats>,
     resolver_handle: ArcIntegratedResolverHandle,
+    resolve_redirection: Option<ResolveRedirection>,
     next_table: BTreeMap<NodeName, ArcEscaper>,
     lpm_table: IpNetworkTable<ArcEscaper>,
     default_next: ArcEscaper,
@@ -59,6 +61,10 @@ impl RouteResolvedEscaper {
         F: FnMut(&NodeName) -> ArcEscaper,
     {
         let resolver_handle = crate::resolve::get_handle(config.resolver())?;
+        let resolve_redirection = config
+            .resolve_redirection
+            .as_ref()
+            .map(|builder| builder.build());
 
         let mut next_table = BTreeMap::new();
         if let Some(escapers) = config.dependent_escaper() {
@@ -82,6 +88,7 @@ impl RouteResolvedEscaper {
             config,
             stats,
             resolver_handle,
+            resolve_redirection,
             next_table,
             lpm_table,
             default_next,
@@ -92,9 +99,10 @@ impl RouteResolvedEscaper {
 
     pub(super) fn prepare_initial(
         config: RouteResolvedEscaperConfig,
+        registry: &mut EscaperRegistry,
     ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteResolvedEscaper::new_obj(config, stats, super::registry::get_or_insert_default)
+        RouteResolvedEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
@@ -115,11 +123,21 @@ impl RouteResolvedEscaper {
         match ups {
             Host::Ip(ip) => Ok(*ip),
             Host::Domain(domain) => {
-                let mut resolver_job = HappyEyeballsResolveJob::new_dyn(
-                    self.config.resolve_strategy,
-                    &self.resolver_handle,
-                    domain.clone(),
-                )?;
+                let mut resolver_job = if let Some(redirect) = &self.resolve_redirection
+                    && let Some(v) = redirect.query_value(domain)
+                {
+                    HappyEyeballsResolveJob::new_redirected(
+                        self.config.resolve_strategy,
+                        &self.resolver_handle,
+                        v,
+                    )?
+                } else {
+                    HappyEyeballsResolveJob::new_dyn(
+                        self.config.resolve_strategy,
+                        &self.resolver_handle,
+                        domain.clone(),
+                    )?
+                };
                 let v = resolver_job
                     .get_r1_or_first(self.config.resolution_delay, usize::MAX)
                     .await?;
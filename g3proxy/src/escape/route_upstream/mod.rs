@@ -93,9 +93,10 @@ impl RouteUpstreamEscaper {
 
     pub(super) fn prepare_initial(
         config: RouteUpstreamEscaperConfig,
+        registry: &mut EscaperRegistry,
     ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        RouteUpstreamEscaper::new_obj(config, stats, super::registry::get_or_insert_default)
+        RouteUpstreamEscaper::new_obj(config, stats, |name| registry.get_or_insert_default(name))
     }
 
     fn prepare_reload(
@@ -0,0 +1,318 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::Context;
+use log::debug;
+
+use g3_types::metrics::NodeName;
+
+use super::ArcEscaper;
+use super::registry::{self, EscaperRegistry};
+use crate::config::escaper::{AnyEscaperConfig, EscaperConfigDiffAction};
+
+use super::comply_audit::ComplyAuditEscaper;
+use super::direct_fixed::DirectFixedEscaper;
+use super::direct_float::DirectFloatEscaper;
+use super::divert_tcp::DivertTcpEscaper;
+use super::dummy_deny::DummyDenyEscaper;
+use super::proxy_float::ProxyFloatEscaper;
+use super::proxy_http::ProxyHttpEscaper;
+use super::proxy_https::ProxyHttpsEscaper;
+use super::proxy_socks5::ProxySocks5Escaper;
+use super::proxy_socks5s::ProxySocks5sEscaper;
+use super::route_client::RouteClientEscaper;
+use super::route_failover::RouteFailoverEscaper;
+use super::route_geoip::RouteGeoIpEscaper;
+use super::route_mapping::RouteMappingEscaper;
+use super::route_query::RouteQueryEscaper;
+use super::route_resolved::RouteResolvedEscaper;
+use super::route_select::RouteSelectEscaper;
+use super::route_upstream::RouteUpstreamEscaper;
+use super::trick_float::TrickFloatEscaper;
+
+/// A copy of the running escapers, to which all the changes will be applied first.
+///
+/// The running registry will only be changed if the changes are committed,
+/// and the staged escapers will be cleaned if dropped without commit.
+pub(super) struct EscaperStaging {
+    running: EscaperRegistry,
+    staged: EscaperRegistry,
+}
+
+impl EscaperStaging {
+    pub(super) fn new() -> Self {
+        let running = registry::snapshot();
+        let staged = running.clone();
+        EscaperStaging { running, staged }
+    }
+
+    pub(super) fn running_names(&self) -> HashSet<NodeName> {
+        self.running.get_names()
+    }
+
+    pub(super) fn get_config(&self, name: &NodeName) -> Option<AnyEscaperConfig> {
+        self.staged.get_config(name)
+    }
+
+    pub(super) async fn reload(
+        &mut self,
+        old: AnyEscaperConfig,
+        new: AnyEscaperConfig,
+    ) -> anyhow::Result<()> {
+        let name = old.name();
+        match old.diff_action(&new) {
+            EscaperConfigDiffAction::NoAction => {
+                debug!("escaper {name} reload: no action is needed");
+                Ok(())
+            }
+            EscaperConfigDiffAction::SpawnNew => {
+                debug!("escaper {name} reload: will create a totally new one");
+                self.spawn_new(new).await
+            }
+            EscaperConfigDiffAction::Reload => {
+                debug!("escaper {name} reload: will reload from existed");
+                self.reload_existed(name, Some(new))
+            }
+        }
+    }
+
+    pub(super) async fn spawn_new(&mut self, config: AnyEscaperConfig) -> anyhow::Result<()> {
+        let name = config.name().clone();
+        let registry = &mut self.staged;
+        let escaper = match config {
+            AnyEscaperConfig::ComplyAudit(c) => ComplyAuditEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::DirectFixed(c) => DirectFixedEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::DirectFloat(c) => DirectFloatEscaper::prepare_initial(c).await?,
+            AnyEscaperConfig::DivertTcp(c) => DivertTcpEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::DummyDeny(c) => DummyDenyEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::ProxyFloat(c) => ProxyFloatEscaper::prepare_initial(c).await?,
+            AnyEscaperConfig::ProxyHttp(c) => ProxyHttpEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::ProxyHttps(c) => ProxyHttpsEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::ProxySocks5(c) => ProxySocks5Escaper::prepare_initial(c)?,
+            AnyEscaperConfig::ProxySocks5s(c) => ProxySocks5sEscaper::prepare_initial(c)?,
+            AnyEscaperConfig::RouteFailover(c) => {
+                RouteFailoverEscaper::prepare_initial(c, registry)?
+            }
+            AnyEscaperConfig::RouteResolved(c) => {
+                RouteResolvedEscaper::prepare_initial(c, registry)?
+            }
+            AnyEscaperConfig::RouteGeoIp(c) => RouteGeoIpEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::RouteMapping(c) => RouteMappingEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::RouteQuery(c) => RouteQueryEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::RouteSelect(c) => RouteSelectEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::RouteUpstream(c) => {
+                RouteUpstreamEscaper::prepare_initial(c, registry)?
+            }
+            AnyEscaperConfig::RouteClient(c) => RouteClientEscaper::prepare_initial(c, registry)?,
+            AnyEscaperConfig::TrickFloat(c) => TrickFloatEscaper::prepare_initial(c, registry)?,
+        };
+        self.replace(name.clone(), escaper);
+        self.update_dependency(&name)
+    }
+
+    fn reload_existed(
+        &mut self,
+        name: &NodeName,
+        new: Option<AnyEscaperConfig>,
+    ) -> anyhow::Result<()> {
+        let escaper = self.staged.prepare_reload(name, new)?;
+        self.replace(name.clone(), escaper);
+        self.update_dependency(name)
+    }
+
+    pub(super) fn delete(&mut self, name: &NodeName) -> anyhow::Result<()> {
+        if let Some(old) = self.staged.remove(name) {
+            self.clean_staged(name, &old);
+        }
+        self.update_dependency(name)
+    }
+
+    /// Reload the escapers that depend on `target`
+    fn update_dependency(&mut self, target: &NodeName) -> anyhow::Result<()> {
+        let mut names = Vec::<NodeName>::new();
+        self.staged.foreach(|name, escaper| {
+            if escaper._depend_on_escaper(target) {
+                names.push(name.clone());
+            }
+        });
+
+        for name in names.iter() {
+            debug!("escaper {name}: will reload as it depends on escaper {target}");
+            let escaper = self.staged.prepare_reload(name, None).context(format!(
+                "failed to reload escaper {name} which depends on escaper {target}"
+            ))?;
+            self.replace(name.clone(), escaper);
+        }
+
+        // finish those in the same level first, then go in depth
+        for name in names.iter() {
+            self.update_dependency(name)?;
+        }
+        Ok(())
+    }
+
+    fn replace(&mut self, name: NodeName, escaper: ArcEscaper) {
+        if let Some(old) = self.staged.insert(name.clone(), escaper) {
+            self.clean_staged(&name, &old);
+        }
+    }
+
+    /// Clean the escaper if it's only staged but not running
+    fn clean_staged(&self, name: &NodeName, escaper: &ArcEscaper) {
+        if !is_running(&self.running, name, escaper) {
+            escaper._clean_to_offline();
+        }
+    }
+
+    /// Apply all the changes to the running registry, and then notify the servers
+    pub(super) async fn commit(mut self) {
+        let staged = std::mem::replace(&mut self.staged, EscaperRegistry::new());
+
+        let mut changed = Vec::<(NodeName, ArcEscaper, &'static str)>::new();
+        staged.foreach(|name, escaper| match self.running.get_escaper(name) {
+            Some(old) if Arc::ptr_eq(&old, escaper) => {}
+            Some(_) => changed.push((name.clone(), escaper.clone(), "reloaded")),
+            None => changed.push((name.clone(), escaper.clone(), "spawned")),
+        });
+        let staged_names = staged.get_names();
+        let deleted: Vec<NodeName> = self
+            .running
+            .get_names()
+            .into_iter()
+            .filter(|name| !staged_names.contains(name))
+            .collect();
+
+        for (name, escaper, _) in &changed {
+            registry::add(name.clone(), escaper.clone());
+        }
+        for name in &deleted {
+            registry::del(name);
+        }
+
+        for (name, _, status) in changed {
+            crate::serve::update_dependency_to_escaper(&name, status).await;
+        }
+        for name in deleted {
+            crate::serve::update_dependency_to_escaper(&name, "deleted").await;
+        }
+    }
+}
+
+impl Drop for EscaperStaging {
+    fn drop(&mut self) {
+        let running = &self.running;
+        self.staged.foreach(|name, escaper| {
+            if !is_running(running, name, escaper) {
+                escaper._clean_to_offline();
+            }
+        });
+    }
+}
+
+fn is_running(running: &EscaperRegistry, name: &NodeName, escaper: &ArcEscaper) -> bool {
+    running
+        .get_escaper(name)
+        .map(|r| Arc::ptr_eq(&r, escaper))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::{Yaml, YamlLoader};
+
+    fn config(s: &str) -> AnyEscaperConfig {
+        let docs = YamlLoader::load_from_str(s).unwrap();
+        let Yaml::Hash(map) = &docs[0] else {
+            panic!("yaml doc root should be hash");
+        };
+        crate::config::escaper::load_escaper(map, None).unwrap()
+    }
+
+    #[tokio::test]
+    async fn commit() {
+        let deny = NodeName::new_static("staging_commit_deny");
+        let select = NodeName::new_static("staging_commit_select");
+
+        let mut staging = EscaperStaging::new();
+        staging
+            .spawn_new(config("name: staging_commit_deny\ntype: dummy_deny"))
+            .await
+            .unwrap();
+        staging
+            .spawn_new(config(
+                "name: staging_commit_select\ntype: route_select\nnext_nodes: staging_commit_deny",
+            ))
+            .await
+            .unwrap();
+        // not applied before commit
+        assert!(registry::get_escaper(&deny).is_none());
+        assert!(registry::get_escaper(&select).is_none());
+        staging.commit().await;
+
+        let select_escaper = registry::get_escaper(&select).unwrap();
+        assert!(select_escaper._depend_on_escaper(&deny));
+        assert!(registry::get_escaper(&deny).is_some());
+
+        let mut staging = EscaperStaging::new();
+        staging.delete(&select).unwrap();
+        assert!(registry::get_escaper(&select).is_some());
+        staging.commit().await;
+        assert!(registry::get_escaper(&select).is_none());
+        assert!(registry::get_escaper(&deny).is_some());
+    }
+
+    #[tokio::test]
+    async fn discard() {
+        let select = NodeName::new_static("staging_discard_select");
+
+        let mut staging = EscaperStaging::new();
+        staging
+            .spawn_new(config("name: staging_discard_deny\ntype: dummy_deny"))
+            .await
+            .unwrap();
+        staging
+            .spawn_new(config(
+                "name: staging_discard_select\ntype: route_select\nnext_nodes: staging_discard_deny",
+            ))
+            .await
+            .unwrap();
+        staging.commit().await;
+        let select_escaper = registry::get_escaper(&select).unwrap();
+
+        let mut staging = EscaperStaging::new();
+        // the missing next escaper will be added as a default one
+        let old = staging.get_config(&select).unwrap();
+        staging
+            .reload(
+                old,
+                config(
+                    "name: staging_discard_select\ntype: route_select\n\
+                     next_nodes: staging_discard_missing",
+                ),
+            )
+            .await
+            .unwrap();
+        // no next escaper with positive weight
+        let r = staging
+            .spawn_new(config(
+                "name: staging_discard_bad\ntype: route_select\n\
+                 next_nodes:\n  - name: staging_discard_deny\n    weight: 0",
+            ))
+            .await;
+        assert!(r.is_err());
+        drop(staging);
+
+        // the running ones are not changed
+        let running = registry::get_escaper(&select).unwrap();
+        assert!(Arc::ptr_eq(&running, &select_escaper));
+        assert!(!running._depend_on_escaper(&NodeName::new_static("staging_discard_missing")));
+        assert!(registry::get_escaper(&NodeName::new_static("staging_discard_missing")).is_none());
+        assert!(registry::get_escaper(&NodeName::new_static("staging_discard_bad")).is_none());
+    }
+}
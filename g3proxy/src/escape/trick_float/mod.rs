@@ -68,13 +68,14 @@ impl TrickFloatEscaper {
         Arc::new(escaper)
     }
 
-    pub(super) fn prepare_initial(config: TrickFloatEscaperConfig) -> anyhow::Result<ArcEscaper> {
+    pub(super) fn prepare_initial(
+        config: TrickFloatEscaperConfig,
+        registry: &mut EscaperRegistry,
+    ) -> anyhow::Result<ArcEscaper> {
         let stats = Arc::new(RouteEscaperStats::new(config.name()));
-        Ok(TrickFloatEscaper::new_obj(
-            config,
-            stats,
-            super::registry::get_or_insert_default,
-        ))
+        Ok(TrickFloatEscaper::new_obj(config, stats, |name| {
+            registry.get_or_insert_default(name)
+        }))
     }
 
     fn prepare_reload(
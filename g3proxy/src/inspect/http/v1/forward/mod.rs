@@ -212,6 +212,9 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                 if let Some(username) = self.ctx.raw_user_name() {
                     adapter.set_client_username(username.clone());
                 }
+                if let Some(cert) = self.ctx.client_cert() {
+                    adapter.set_client_cert(cert.clone());
+                }
                 adapter
             }
             Err(e) => {
@@ -464,6 +467,8 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                 &self.req.method,
                 self.req.keep_alive(),
                 self.ctx.h1_interception().rsp_head_max_size,
+                self.ctx.h1_interception().rsp_reason_max_len,
+                self.ctx.h1_interception().rsp_head_allow_obs_fold,
             ),
         )
         .await
@@ -613,6 +618,8 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
             &self.req.method,
             self.req.keep_alive(),
             self.ctx.h1_interception().rsp_head_max_size,
+            self.ctx.h1_interception().rsp_reason_max_len,
+            self.ctx.h1_interception().rsp_head_allow_obs_fold,
         )
         .await
         .map_err(|e| e.into())
@@ -687,6 +694,9 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
                     if let Some(username) = self.ctx.raw_user_name() {
                         adapter.set_client_username(username.clone());
                     }
+                    if let Some(cert) = self.ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     adapter.set_respond_shared_headers(adaptation_respond_shared_headers);
                     let r = self
                         .send_response_with_adaptation(rsp, rsp_io, adapter, &mut adaptation_state)
@@ -276,6 +276,9 @@ where
                 if let Some(username) = self.ctx.raw_user_name() {
                     adapter.set_client_username(username.clone());
                 }
+                if let Some(cert) = self.ctx.client_cert() {
+                    adapter.set_client_cert(cert.clone());
+                }
                 let mut adaptation_state =
                     ReqmodAdaptationRunState::new(self.http_notes.receive_ins);
                 self.forward_with_adaptation(rsp_io, adapter, &mut adaptation_state)
@@ -397,6 +400,8 @@ where
                 &self.req.method,
                 self.req.keep_alive(),
                 self.ctx.h1_interception().rsp_head_max_size,
+                self.ctx.h1_interception().rsp_reason_max_len,
+                self.ctx.h1_interception().rsp_head_allow_obs_fold,
             ),
         )
         .await
@@ -560,6 +565,7 @@ where
 
         let mut ctx = self.ctx;
         ctx.increase_inspection_depth();
+        ctx.switch_to_upgraded();
 
         match protocol {
             HttpUpgradeToken::Http(Version::HTTP_2) => {
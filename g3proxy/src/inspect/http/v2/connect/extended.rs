@@ -192,6 +192,7 @@ where
                 intercept_log!(self, "ok");
 
                 self.ctx.increase_inspection_depth();
+                self.ctx.switch_to_upgraded();
                 StreamInspectLog::new(&self.ctx)
                     .log(InspectSource::H2ExtendedConnect, Protocol::Websocket);
                 let websocket_obj = crate::inspect::websocket::H2WebsocketInterceptObject::new(
@@ -182,6 +182,9 @@ impl<'a, SC: ServerConfig> ExchangeHead<'a, SC> {
                     if let Some(username) = self.ctx.raw_user_name() {
                         adapter.set_client_username(username.clone());
                     }
+                    if let Some(cert) = self.ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     return self
                         .forward_with_adaptation(
                             ups_send_req,
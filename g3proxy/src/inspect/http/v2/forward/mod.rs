@@ -245,6 +245,9 @@ where
                     if let Some(username) = self.ctx.raw_user_name() {
                         adapter.set_client_username(username.clone());
                     }
+                    if let Some(cert) = self.ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     let r = self
                         .forward_with_adaptation(
                             ups_send_req,
@@ -548,6 +551,9 @@ where
                     if let Some(username) = self.ctx.raw_user_name() {
                         adapter.set_client_username(username);
                     }
+                    if let Some(cert) = self.ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     adapter.set_respond_shared_headers(adaptation_respond_shared_headers);
                     let r = self
                         .send_response_with_adaptation(
@@ -17,7 +17,7 @@ use g3_dpi::{
     ProtocolInspectAction, ProtocolInspector, SmtpInterceptionConfig,
 };
 use g3_io_ext::IdleWheel;
-use g3_types::net::{Host, OpensslClientConfig};
+use g3_types::net::{Host, OpensslClientConfig, TlsClientCertInfo};
 
 use crate::audit::AuditHandle;
 use crate::auth::{User, UserForbiddenStats, UserSite};
@@ -67,6 +67,7 @@ pub(crate) struct StreamInspectTaskNotes {
     pub(crate) server_addr: SocketAddr,
     worker_id: Option<usize>,
     user_ctx: Option<StreamInspectUserContext>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
 }
 
 impl StreamInspectTaskNotes {
@@ -99,6 +100,7 @@ impl From<&ServerTaskNotes> for StreamInspectTaskNotes {
                 user_site: ctx.user_site().cloned(),
                 forbidden_stats: ctx.forbidden_stats().clone(),
             }),
+            client_cert: None,
         }
     }
 }
@@ -189,6 +191,15 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.task_notes.raw_username()
     }
 
+    pub(crate) fn set_client_cert(&mut self, cert: Option<Arc<TlsClientCertInfo>>) {
+        self.task_notes.client_cert = cert;
+    }
+
+    #[inline]
+    fn client_cert(&self) -> Option<&Arc<TlsClientCertInfo>> {
+        self.task_notes.client_cert.as_ref()
+    }
+
     #[inline]
     pub(crate) fn server_task_id(&self) -> &Uuid {
         self.task_notes.task_id()
@@ -247,6 +258,18 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.inspection_depth += 1;
     }
 
+    /// Switch to the idle config for upgraded tasks, should be called after the upgrade succeeded
+    fn switch_to_upgraded(&mut self) {
+        self.server_stats.add_upgraded_task();
+
+        if let Some(count) = crate::serve::upgraded_task_max_idle_count(
+            self.user().and_then(|u| u.upgraded_task_max_idle_count()),
+            self.server_config.upgraded_task_max_idle_count(),
+        ) {
+            self.max_idle_count = count;
+        }
+    }
+
     #[inline]
     pub(crate) fn tls_interception(&self) -> Option<TlsInterceptionContext> {
         self.audit_handle.tls_interception()
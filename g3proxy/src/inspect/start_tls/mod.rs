@@ -40,6 +40,7 @@ macro_rules! intercept_log {
                 "upstream" => LtUpstreamAddr(&$obj.upstream),
                 "protocol" => Protocol::from($obj.protocol).as_str(),
                 "tls_server_verify" => $obj.server_verify_result.map(LtX509VerifyResult),
+                "tls_sni_mismatch" => $obj.sni_mismatch,
             );
         }
     };
@@ -84,6 +85,7 @@ pub(crate) struct StartTlsInterceptObject<SC: ServerConfig> {
     tls_interception: TlsInterceptionContext,
     protocol: StartTlsProtocol,
     server_verify_result: Option<X509VerifyResult>,
+    sni_mismatch: bool,
 }
 
 impl<SC> StartTlsInterceptObject<SC>
@@ -103,6 +105,7 @@ where
             tls_interception: tls,
             protocol,
             server_verify_result: None,
+            sni_mismatch: false,
         }
     }
 
@@ -148,6 +151,9 @@ where
             .tls_interception
             .read_client_hello(&mut clt_r, &mut clt_r_buf)
             .await?;
+        self.sni_mismatch = self
+            .tls_interception
+            .check_sni_mismatch(&client_hello, &self.upstream)?;
 
         self.set_io(clt_r, clt_w, ups_r, ups_w);
         if client_hello.version.is_tlcp() {
@@ -11,6 +11,8 @@ use tokio::time::Instant;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::{MaybeProtocol, ProtocolInspectionConfig, ProtocolInspector};
+#[cfg(target_os = "linux")]
+use g3_io_ext::SpliceCopy;
 use g3_io_ext::{IdleInterval, OptionalInterval, StreamCopy, StreamCopyConfig, StreamCopyError};
 use g3_slog_types::LtUuid;
 use g3_types::net::UpstreamAddr;
@@ -145,6 +147,87 @@ pub(crate) trait StreamTransitTask {
         }
     }
 
+    /// Relay by splice(2), the write direction will be shutdown by the splice copy itself
+    #[cfg(target_os = "linux")]
+    async fn transit_splice(
+        &self,
+        mut clt_to_ups: SpliceCopy<'_>,
+        mut ups_to_clt: SpliceCopy<'_>,
+    ) -> ServerTaskResult<()> {
+        let mut idle_interval = self.idle_check_interval();
+        let mut log_interval = self
+            .log_flush_interval()
+            .map(|log_interval| {
+                let interval =
+                    tokio::time::interval_at(Instant::now() + log_interval, log_interval);
+                OptionalInterval::with(interval)
+            })
+            .unwrap_or_default();
+        let mut idle_count = 0;
+        let max_idle_count = self
+            .user()
+            .and_then(|u| u.task_max_idle_count())
+            .unwrap_or(self.max_idle_count());
+        let mut clt_shutdown = false;
+        let mut ups_shutdown = false;
+        loop {
+            tokio::select! {
+                r = &mut clt_to_ups, if !clt_shutdown => {
+                    match r {
+                        Ok(_) => {
+                            self.log_client_shutdown();
+                            if ups_shutdown {
+                                return Ok(());
+                            }
+                            clt_shutdown = true;
+                        }
+                        Err(StreamCopyError::ReadFailed(e)) => return Err(ServerTaskError::ClientTcpReadFailed(e)),
+                        Err(StreamCopyError::WriteFailed(e)) => return Err(ServerTaskError::UpstreamWriteFailed(e)),
+                    }
+                }
+                r = &mut ups_to_clt, if !ups_shutdown => {
+                    match r {
+                        Ok(_) => {
+                            self.log_upstream_shutdown();
+                            if clt_shutdown {
+                                return Ok(());
+                            }
+                            ups_shutdown = true;
+                        }
+                        Err(StreamCopyError::ReadFailed(e)) => return Err(ServerTaskError::UpstreamReadFailed(e)),
+                        Err(StreamCopyError::WriteFailed(e)) => return Err(ServerTaskError::ClientTcpWriteFailed(e)),
+                    }
+                }
+                _ = log_interval.tick() => {
+                    self.log_periodic();
+                }
+                n = idle_interval.tick() => {
+                    if clt_to_ups.is_idle() && ups_to_clt.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        clt_to_ups.reset_active();
+                        ups_to_clt.reset_active();
+                    }
+
+                    if let Some(user) = self.user()
+                        && user.is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.quit_policy().force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+
     async fn transit_north<CR, UW>(
         &self,
         mut clt_to_ups: StreamCopy<'_, CR, UW>,
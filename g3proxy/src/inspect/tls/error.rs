@@ -13,6 +13,8 @@ pub(crate) enum TlsInterceptionError {
     ClientHandshakeTimeout,
     #[error("client handshake failed: {0:?}")]
     ClientHandshakeFailed(anyhow::Error),
+    #[error("client sni {0} mismatch with upstream")]
+    ClientSniMismatch(String),
     #[error("upstream prepare failed: {0:?}")]
     UpstreamPrepareFailed(anyhow::Error),
     #[error("upstream handshake timeout")]
@@ -20,7 +20,7 @@ use g3_dpi::{Protocol, ProtocolInspector};
 use g3_io_ext::{AsyncStream, FlexBufReader, OnceBufReader};
 use g3_slog_types::{LtUpstreamAddr, LtUuid, LtX509VerifyResult};
 use g3_types::net::{
-    AlpnProtocol, OpensslInterceptionClientConfig, OpensslInterceptionServerConfig, TlsAlpn,
+    AlpnProtocol, Host, OpensslInterceptionClientConfig, OpensslInterceptionServerConfig, TlsAlpn,
     TlsServerName, UpstreamAddr,
 };
 use g3_udpdump::{
@@ -30,6 +30,8 @@ use g3_udpdump::{
 use super::{
     BoxAsyncRead, BoxAsyncWrite, InterceptionError, StreamInspectContext, StreamInspection,
 };
+use crate::audit::AuditorStats;
+use crate::config::audit::{TlsSniMismatchAction, TlsSniMismatchConfig};
 use crate::config::server::ServerConfig;
 use crate::log::inspect::{InspectSource, stream::StreamInspectLog};
 use crate::serve::ServerTaskResult;
@@ -87,6 +89,8 @@ pub(crate) struct TlsInterceptionContext {
     pub(super) client_config: Arc<OpensslInterceptionClientConfig>,
     pub(super) server_config: Arc<OpensslInterceptionServerConfig>,
     stream_dumper: Arc<Vec<StreamDumper>>,
+    sni_mismatch: Option<TlsSniMismatchConfig>,
+    auditor_stats: Arc<AuditorStats>,
 }
 
 impl TlsInterceptionContext {
@@ -95,6 +99,8 @@ impl TlsInterceptionContext {
         client_config: OpensslInterceptionClientConfig,
         server_config: OpensslInterceptionServerConfig,
         dump_config: Option<StreamDumpConfig>,
+        sni_mismatch: Option<TlsSniMismatchConfig>,
+        auditor_stats: Arc<AuditorStats>,
     ) -> anyhow::Result<Self> {
         let mut stream_dumper = Vec::new();
         if let Some(dump) = dump_config {
@@ -124,9 +130,42 @@ impl TlsInterceptionContext {
             client_config: Arc::new(client_config),
             server_config: Arc::new(server_config),
             stream_dumper: Arc::new(stream_dumper),
+            sni_mismatch,
+            auditor_stats,
         })
     }
 
+    /// Check the SNI in client hello against the upstream domain.
+    ///
+    /// Return true if mismatched and only logging is required.
+    pub(super) fn check_sni_mismatch(
+        &self,
+        client_hello: &ParsedClientHello,
+        upstream: &UpstreamAddr,
+    ) -> Result<bool, TlsInterceptionError> {
+        let Some(config) = &self.sni_mismatch else {
+            return Ok(false);
+        };
+        let Some(sni) = &client_hello.sni else {
+            return Ok(false);
+        };
+        // there is no way to compare if the upstream is an IP address
+        let Host::Domain(domain) = upstream.host() else {
+            return Ok(false);
+        };
+        if config.is_matched(sni.as_ref(), domain) {
+            return Ok(false);
+        }
+
+        self.auditor_stats.add_tls_sni_mismatch();
+        match config.action {
+            TlsSniMismatchAction::Log => Ok(true),
+            TlsSniMismatchAction::Block => {
+                Err(TlsInterceptionError::ClientSniMismatch(sni.to_string()))
+            }
+        }
+    }
+
     pub(super) fn get_stream_dumper(&self, worker_id: Option<usize>) -> Option<&StreamDumper> {
         if self.stream_dumper.is_empty() {
             return None;
@@ -229,6 +268,7 @@ pub(crate) struct TlsInterceptObject<SC: ServerConfig> {
     upstream: UpstreamAddr,
     tls_interception: TlsInterceptionContext,
     server_verify_result: Option<X509VerifyResult>,
+    sni_mismatch: bool,
 }
 
 macro_rules! intercept_log {
@@ -240,6 +280,7 @@ macro_rules! intercept_log {
                 "depth" => $obj.ctx.inspection_depth,
                 "upstream" => LtUpstreamAddr(&$obj.upstream),
                 "tls_server_verify" => $obj.server_verify_result.map(LtX509VerifyResult),
+                "tls_sni_mismatch" => $obj.sni_mismatch,
             );
         }
     };
@@ -257,6 +298,7 @@ impl<SC: ServerConfig> TlsInterceptObject<SC> {
             upstream,
             tls_interception: tls,
             server_verify_result: None,
+            sni_mismatch: false,
         }
     }
 
@@ -340,6 +382,9 @@ where
             .tls_interception
             .read_client_hello(&mut clt_r, &mut clt_r_buf)
             .await?;
+        self.sni_mismatch = self
+            .tls_interception
+            .check_sni_mismatch(&client_hello, &self.upstream)?;
 
         self.set_io(clt_r_buf, clt_r, clt_w, ups_r, ups_w);
 
@@ -174,4 +174,35 @@ impl TaskLogForFtpOverHttp<'_> {
             "ftp_d_wr_bytes" => self.ftp_d_wr_bytes,
         )
     }
+
+    /// Log the slow task with the breakdown timings
+    pub(crate) fn log_slow(&self, e: &ServerTaskError) {
+        if let Some(user_ctx) = self.task_notes.user_ctx()
+            && user_ctx.skip_log()
+        {
+            return;
+        }
+
+        slog_info!(self.logger, "{}", e;
+            "task_type" => "FtpOverHttp",
+            "task_id" => LtUuid(&self.task_notes.id),
+            "task_event" => TaskEvent::Slow.as_str(),
+            "stage" => self.task_notes.stage.brief(),
+            "start_at" => LtDateTime(&self.task_notes.start_at),
+            "user" => self.task_notes.raw_user_name(),
+            "server_addr" => self.task_notes.server_addr(),
+            "client_addr" => self.task_notes.client_addr(),
+            "upstream" => LtUpstreamAddr(self.ftp_notes.upstream()),
+            "escaper" => self.ftp_notes.control_tcp_notes.escaper.as_str(),
+            "reason" => e.brief(),
+            "method" => LtHttpMethod(&self.ftp_notes.method),
+            "uri" => LtHttpUri::new(&self.ftp_notes.uri, self.ftp_notes.uri_log_max_chars),
+            "rsp_status" => self.ftp_notes.rsp_status,
+            "wait_time" => LtDuration(self.task_notes.wait_time),
+            "ready_time" => LtDuration(self.task_notes.ready_time),
+            "ftp_c_connect_spend" => LtDuration(self.ftp_notes.control_tcp_notes.duration),
+            "ftp_d_connect_spend" => LtDuration(self.ftp_notes.transfer_tcp_notes.duration),
+            "total_time" => LtDuration(self.task_notes.time_elapsed()),
+        )
+    }
 }
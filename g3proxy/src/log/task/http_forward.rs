@@ -8,7 +8,7 @@ use slog::{Logger, slog_info};
 use g3_slog_types::{
     LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtIpAddr, LtUpstreamAddr, LtUuid,
 };
-use g3_types::net::UpstreamAddr;
+use g3_types::net::{TlsClientCertInfo, UpstreamAddr};
 
 use super::TaskEvent;
 use crate::module::http_forward::HttpForwardTaskNotes;
@@ -26,6 +26,8 @@ pub(crate) struct TaskLogForHttpForward<'a> {
     pub(crate) client_wr_bytes: u64,
     pub(crate) remote_rd_bytes: u64,
     pub(crate) remote_wr_bytes: u64,
+    /// only set if logging of the TLS client cert is enabled
+    pub(crate) client_cert: Option<&'a TlsClientCertInfo>,
 }
 
 impl TaskLogForHttpForward<'_> {
@@ -76,6 +78,8 @@ impl TaskLogForHttpForward<'_> {
             "next_bound_addr" => self.tcp_notes.local,
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
+            "chained_target_addr" => self.tcp_notes.chained.target_addr,
+            "chained_outgoing_addr" => self.tcp_notes.chained.outgoing_addr,
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
@@ -110,6 +114,8 @@ impl TaskLogForHttpForward<'_> {
             "next_bound_addr" => self.tcp_notes.local,
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
+            "chained_target_addr" => self.tcp_notes.chained.target_addr,
+            "chained_outgoing_addr" => self.tcp_notes.chained.outgoing_addr,
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
@@ -155,6 +161,8 @@ impl TaskLogForHttpForward<'_> {
             "next_bound_addr" => self.tcp_notes.local,
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
+            "chained_target_addr" => self.tcp_notes.chained.target_addr,
+            "chained_outgoing_addr" => self.tcp_notes.chained.outgoing_addr,
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
             "reason" => e.brief(),
@@ -176,6 +184,137 @@ impl TaskLogForHttpForward<'_> {
             "c_wr_bytes" => self.client_wr_bytes,
             "r_rd_bytes" => self.remote_rd_bytes,
             "r_wr_bytes" => self.remote_wr_bytes,
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_issuer" => self.client_cert.map(|c| c.issuer()),
+            "client_cert_fingerprint" => self.client_cert.map(|c| c.fingerprint()),
         )
     }
+
+    /// Log the slow task with the breakdown timings
+    pub(crate) fn log_slow(&self, e: &ServerTaskError) {
+        if let Some(user_ctx) = self.task_notes.user_ctx()
+            && user_ctx.skip_log()
+        {
+            return;
+        }
+
+        slog_info!(self.logger, "{}", e;
+            "task_type" => "HttpForward",
+            "task_id" => LtUuid(&self.task_notes.id),
+            "task_event" => TaskEvent::Slow.as_str(),
+            "stage" => self.task_notes.stage.brief(),
+            "start_at" => LtDateTime(&self.task_notes.start_at),
+            "user" => self.task_notes.raw_user_name(),
+            "server_addr" => self.task_notes.server_addr(),
+            "client_addr" => self.task_notes.client_addr(),
+            "upstream" => LtUpstreamAddr(self.upstream),
+            "escaper" => self.tcp_notes.escaper.as_str(),
+            "next_peer_addr" => self.tcp_notes.next,
+            "reason" => e.brief(),
+            "reuse_connection" => self.http_notes.reused_connection,
+            "method" => LtHttpMethod(&self.http_notes.method),
+            "uri" => LtHttpUri::new(&self.http_notes.uri, self.http_notes.uri_log_max_chars),
+            "rsp_status" => self.http_notes.rsp_status,
+            "wait_time" => LtDuration(self.task_notes.wait_time),
+            "ready_time" => LtDuration(self.task_notes.ready_time),
+            "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
+            "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
+            "dur_req_send_hdr" => LtDuration(self.http_notes.dur_req_send_hdr),
+            "dur_req_send_all" => LtDuration(self.http_notes.dur_req_send_all),
+            "dur_rsp_recv_hdr" => LtDuration(self.http_notes.dur_rsp_recv_hdr),
+            "dur_rsp_recv_all" => LtDuration(self.http_notes.dur_rsp_recv_all),
+            "total_time" => LtDuration(self.task_notes.time_elapsed()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fmt;
+    use std::net::SocketAddr;
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use http::{Method, Uri};
+    use slog::{Drain, KV, Key, OwnedKVList, Record, Serializer, o};
+    use tokio::time::Instant;
+
+    use g3_daemon::server::ClientConnectionInfo;
+
+    type Fields = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+    struct FieldsSerializer(HashMap<String, String>);
+
+    impl Serializer for FieldsSerializer {
+        fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+            self.0.insert(key.to_string(), val.to_string());
+            Ok(())
+        }
+    }
+
+    struct CaptureDrain(Fields);
+
+    impl Drain for CaptureDrain {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record, _values: &OwnedKVList) -> Result<(), slog::Never> {
+            let mut serializer = FieldsSerializer(HashMap::new());
+            record.kv().serialize(record, &mut serializer).unwrap();
+            self.0.lock().unwrap().push(serializer.0);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn chained_fields() {
+        let fields = Fields::default();
+        let logger = Logger::root(CaptureDrain(fields.clone()), o!());
+
+        let upstream = UpstreamAddr::from_str("www.example.net:80").unwrap();
+        let cc_info = ClientConnectionInfo::new(
+            SocketAddr::from_str("192.0.2.1:40000").unwrap(),
+            SocketAddr::from_str("192.0.2.2:8080").unwrap(),
+        );
+        let task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+        let now = Instant::now();
+        let http_notes = HttpForwardTaskNotes::new(
+            now,
+            now,
+            Method::GET,
+            Uri::from_static("http://www.example.net/"),
+            1024,
+        );
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        tcp_notes.chained.target_addr = Some(SocketAddr::from_str("198.51.100.1:80").unwrap());
+        tcp_notes.chained.outgoing_addr = Some(SocketAddr::from_str("203.0.113.1:50000").unwrap());
+
+        let task_log = TaskLogForHttpForward {
+            logger: &logger,
+            upstream: &upstream,
+            task_notes: &task_notes,
+            http_notes: &http_notes,
+            http_user_agent: None,
+            tcp_notes: &tcp_notes,
+            client_rd_bytes: 0,
+            client_wr_bytes: 0,
+            remote_rd_bytes: 0,
+            remote_wr_bytes: 0,
+            client_cert: None,
+        };
+        task_log.log_connected();
+        task_log.log_periodic();
+        task_log.log(&ServerTaskError::ClosedByClient);
+
+        let records = fields.lock().unwrap();
+        assert_eq!(records.len(), 3);
+        for (record, event) in records.iter().zip(["Connected", "Periodic", "Finished"]) {
+            assert_eq!(record["task_event"], event);
+            assert_eq!(record["chained_target_addr"], "198.51.100.1:80");
+            assert_eq!(record["chained_outgoing_addr"], "203.0.113.1:50000");
+        }
+    }
 }
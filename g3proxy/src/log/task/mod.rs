@@ -49,6 +49,7 @@ pub(crate) enum TaskEvent {
     ClientShutdown,
     UpstreamShutdown,
     Finished,
+    Slow,
 }
 
 impl TaskEvent {
@@ -60,6 +61,7 @@ impl TaskEvent {
             TaskEvent::ClientShutdown => "ClientShutdown",
             TaskEvent::UpstreamShutdown => "UpstreamShutdown",
             TaskEvent::Finished => "Finished",
+            TaskEvent::Slow => "Slow",
         }
     }
 }
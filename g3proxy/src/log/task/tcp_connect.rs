@@ -6,7 +6,7 @@
 use slog::{Logger, slog_info};
 
 use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtUpstreamAddr, LtUuid};
-use g3_types::net::UpstreamAddr;
+use g3_types::net::{TlsClientCertInfo, UpstreamAddr};
 
 use super::TaskEvent;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
@@ -21,6 +21,8 @@ pub(crate) struct TaskLogForTcpConnect<'a> {
     pub(crate) client_wr_bytes: u64,
     pub(crate) remote_rd_bytes: u64,
     pub(crate) remote_wr_bytes: u64,
+    /// only set if logging of the TLS client cert is enabled
+    pub(crate) client_cert: Option<&'a TlsClientCertInfo>,
 }
 
 impl TaskLogForTcpConnect<'_> {
@@ -69,6 +71,8 @@ impl TaskLogForTcpConnect<'_> {
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
+            "next_sock_send_buf" => self.tcp_notes.socket_buffer.and_then(|c| c.send_size()),
+            "next_sock_recv_buf" => self.tcp_notes.socket_buffer.and_then(|c| c.recv_size()),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
         )
@@ -173,6 +177,9 @@ impl TaskLogForTcpConnect<'_> {
             "c_wr_bytes" => self.client_wr_bytes,
             "r_rd_bytes" => self.remote_rd_bytes,
             "r_wr_bytes" => self.remote_wr_bytes,
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_issuer" => self.client_cert.map(|c| c.issuer()),
+            "client_cert_fingerprint" => self.client_cert.map(|c| c.fingerprint()),
         )
     }
 }
@@ -11,6 +11,10 @@ use g3_daemon::control::{QuitAction, UpgradeAction};
 use g3proxy::opts::ProcArgs;
 
 fn main() -> anyhow::Result<()> {
+    // load before any other threads are spawned, as the environment variables will be changed
+    #[cfg(unix)]
+    let inherited_sockets = g3_daemon::listen::load_inherited_sockets();
+
     #[cfg(feature = "openssl-probe")]
     unsafe {
         openssl_probe::init_openssl_env_vars();
@@ -40,6 +44,12 @@ fn main() -> anyhow::Result<()> {
 
     // set up process logger early, only proc args is used inside
     g3_daemon::log::process::setup(&proc_args.daemon_config);
+    #[cfg(unix)]
+    g3_daemon::listen::log_inherited_sockets(inherited_sockets);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_CONFIG);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_RESOLVER);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_ESCAPER);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_LISTEN);
     if proc_args.daemon_config.need_daemon_controller() {
         g3proxy::control::UpgradeActor::connect_to_old_daemon();
     }
@@ -48,11 +58,18 @@ fn main() -> anyhow::Result<()> {
         Ok(c) => c,
         Err(e) => {
             g3_daemon::control::upgrade::cancel_old_shutdown();
+            if proc_args.daemon_config.check_config {
+                g3_daemon::opts::CheckReport::print_load_error(&e);
+            }
             return Err(e.context(format!("failed to load config, opts: {:?}", &proc_args)));
         }
     };
     debug!("loaded config from {}", config_file.display());
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_CONFIG);
 
+    if proc_args.daemon_config.check_config {
+        return g3proxy::config::check().print();
+    }
     if proc_args.daemon_config.test_config {
         info!("the format of the config file is ok");
         return Ok(());
@@ -164,9 +181,11 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3proxy::resolve::spawn_all()
         .await
         .context("failed to spawn all resolvers")?;
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_RESOLVER);
     g3proxy::escape::load_all()
         .await
         .context("failed to load all escapers")?;
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_ESCAPER);
     g3proxy::auth::load_all()
         .await
         .context("failed to load all user groups")?;
@@ -174,8 +193,10 @@ async fn load_and_spawn() -> anyhow::Result<()> {
         .await
         .context("failed to load all auditors")?;
     g3proxy::serve::spawn_offline_clean();
+    g3proxy::serve::spawn_io_rate_sample();
     g3proxy::serve::spawn_all()
         .await
         .context("failed to spawn all servers")?;
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_LISTEN);
     Ok(())
 }
@@ -0,0 +1,234 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use http::{HeaderName, Method};
+use log::debug;
+use lru::LruCache;
+use tokio::io::{AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_types::net::HttpHeaderValue;
+
+use super::{ExtAuthzError, ExtAuthzRequest};
+use crate::config::server::ext_authz::ExtAuthzConfig;
+
+#[derive(Clone, Debug)]
+pub(crate) enum ExtAuthzDecision {
+    /// allowed, with the headers that should be set to the upstream request
+    Allow(Vec<(HeaderName, HttpHeaderValue)>),
+    Deny,
+}
+
+/// Cached decisions keyed by the request header, with the expire time
+type ExtAuthzCache = LruCache<Vec<u8>, (Instant, ExtAuthzDecision)>;
+
+pub(crate) struct ExtAuthzClient {
+    config: ExtAuthzConfig,
+    cache: Option<Mutex<ExtAuthzCache>>,
+}
+
+impl ExtAuthzClient {
+    pub(crate) fn new(config: &ExtAuthzConfig) -> Self {
+        let cache = if config.cache_ttl.is_zero() {
+            None
+        } else {
+            Some(Mutex::new(LruCache::new(config.cache_size)))
+        };
+        ExtAuthzClient {
+            config: config.clone(),
+            cache,
+        }
+    }
+
+    /// Get the decision for the request, the fail policy will be applied if the service is unavailable
+    pub(crate) async fn check(&self, req: &ExtAuthzRequest<'_>) -> ExtAuthzDecision {
+        let header = req.serialize(&self.config);
+
+        if let Some(decision) = self.get_cached(&header) {
+            return decision;
+        }
+
+        match tokio::time::timeout(self.config.timeout, self.query(&header)).await {
+            Ok(Ok(decision)) => {
+                self.set_cached(header, &decision);
+                decision
+            }
+            Ok(Err(e)) => self.fail(e),
+            Err(_) => self.fail(ExtAuthzError::Timeout),
+        }
+    }
+
+    fn fail(&self, e: ExtAuthzError) -> ExtAuthzDecision {
+        debug!(
+            "ext authz service {} unavailable: {e}, fail_open={}",
+            self.config.addr, self.config.fail_open
+        );
+        if self.config.fail_open {
+            ExtAuthzDecision::Allow(Vec::new())
+        } else {
+            ExtAuthzDecision::Deny
+        }
+    }
+
+    fn get_cached(&self, key: &[u8]) -> Option<ExtAuthzDecision> {
+        let mut cache = self.cache.as_ref()?.lock().unwrap();
+        let (expire, decision) = cache.get(key)?;
+        if *expire > Instant::now() {
+            Some(decision.clone())
+        } else {
+            cache.pop(key);
+            None
+        }
+    }
+
+    fn set_cached(&self, key: Vec<u8>, decision: &ExtAuthzDecision) {
+        if let Some(cache) = &self.cache {
+            let expire = Instant::now() + self.config.cache_ttl;
+            let mut cache = cache.lock().unwrap();
+            cache.put(key, (expire, decision.clone()));
+        }
+    }
+
+    async fn query(&self, header: &[u8]) -> Result<ExtAuthzDecision, ExtAuthzError> {
+        let mut stream = TcpStream::connect(self.config.addr)
+            .await
+            .map_err(ExtAuthzError::ConnectFailed)?;
+        stream
+            .write_all(header)
+            .await
+            .map_err(ExtAuthzError::WriteFailed)?;
+
+        let mut reader = BufReader::new(stream);
+        let rsp = HttpForwardRemoteResponse::parse(
+            &mut reader,
+            &Method::POST,
+            false,
+            self.config.max_header_size,
+        )
+        .await?;
+
+        match rsp.code {
+            200..=299 => {
+                let mut headers = Vec::new();
+                for name in &self.config.upstream_headers {
+                    for value in rsp.end_to_end_headers.get_all(name) {
+                        headers.push((name.clone(), value.clone()));
+                    }
+                }
+                Ok(ExtAuthzDecision::Allow(headers))
+            }
+            400..=499 => Ok(ExtAuthzDecision::Deny),
+            code => Err(ExtAuthzError::UnexpectedStatus(code)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, SocketAddr};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use http::Uri;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    use g3_types::net::{HttpHeaderMap, UpstreamAddr};
+
+    /// Spawn an authorization service that always replies with the same response
+    async fn spawn_service(rsp: &'static str) -> (SocketAddr, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let count = Arc::new(AtomicUsize::new(0));
+        let served = count.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                served.fetch_add(1, Ordering::Relaxed);
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream.write_all(rsp.as_bytes()).await;
+            }
+        });
+        (addr, count)
+    }
+
+    fn config(addr: SocketAddr) -> ExtAuthzConfig {
+        let mut config = ExtAuthzConfig::parse(&yaml_rust::Yaml::String(addr.to_string())).unwrap();
+        config.timeout = Duration::from_millis(500);
+        config
+    }
+
+    async fn check(client: &ExtAuthzClient) -> ExtAuthzDecision {
+        let uri = Uri::from_static("http://www.example.net/");
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.net", 80).unwrap();
+        let headers = HttpHeaderMap::default();
+        let req = ExtAuthzRequest {
+            client_ip: IpAddr::from([127, 0, 0, 1]),
+            method: &Method::GET,
+            uri: &uri,
+            upstream: &upstream,
+            is_https: false,
+            user: None,
+            headers: &headers,
+        };
+        client.check(&req).await
+    }
+
+    #[tokio::test]
+    async fn allow_with_headers() {
+        let (addr, _) = spawn_service(
+            "HTTP/1.1 200 OK\r\nX-User-Id: 1001\r\nX-Other: a\r\nContent-Length: 0\r\n\r\n",
+        )
+        .await;
+        let mut config = config(addr);
+        config.upstream_headers = vec![HeaderName::from_static("x-user-id")];
+        let client = ExtAuthzClient::new(&config);
+
+        let ExtAuthzDecision::Allow(headers) = check(&client).await else {
+            panic!("should be allowed");
+        };
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].0.as_str(), "x-user-id");
+        assert_eq!(headers[0].1.to_str(), "1001");
+    }
+
+    #[tokio::test]
+    async fn deny() {
+        let (addr, _) = spawn_service("HTTP/1.1 403 Forbidden\r\nContent-Length: 0\r\n\r\n").await;
+        let client = ExtAuthzClient::new(&config(addr));
+        assert!(matches!(check(&client).await, ExtAuthzDecision::Deny));
+    }
+
+    #[tokio::test]
+    async fn fail_policy() {
+        let (addr, _) =
+            spawn_service("HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n").await;
+        let mut config = config(addr);
+        let client = ExtAuthzClient::new(&config);
+        assert!(matches!(check(&client).await, ExtAuthzDecision::Deny));
+
+        config.fail_open = true;
+        let client = ExtAuthzClient::new(&config);
+        assert!(matches!(check(&client).await, ExtAuthzDecision::Allow(_)));
+    }
+
+    #[tokio::test]
+    async fn cache() {
+        let (addr, count) = spawn_service("HTTP/1.1 204 No Content\r\n\r\n").await;
+        let mut config = config(addr);
+        config.cache_ttl = Duration::from_secs(60);
+        let client = ExtAuthzClient::new(&config);
+
+        assert!(matches!(check(&client).await, ExtAuthzDecision::Allow(_)));
+        assert!(matches!(check(&client).await, ExtAuthzDecision::Allow(_)));
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+}
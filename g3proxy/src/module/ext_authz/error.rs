@@ -0,0 +1,24 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+
+use thiserror::Error;
+
+use g3_http::client::HttpResponseParseError;
+
+#[derive(Error, Debug)]
+pub(crate) enum ExtAuthzError {
+    #[error("connect failed: {0:?}")]
+    ConnectFailed(io::Error),
+    #[error("write failed: {0:?}")]
+    WriteFailed(io::Error),
+    #[error("invalid response: {0}")]
+    InvalidResponse(#[from] HttpResponseParseError),
+    #[error("unexpected response status code {0}")]
+    UnexpectedStatus(u16),
+    #[error("timed out")]
+    Timeout,
+}
@@ -0,0 +1,13 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod error;
+pub(crate) use error::ExtAuthzError;
+
+mod request;
+pub(crate) use request::ExtAuthzRequest;
+
+mod client;
+pub(crate) use client::{ExtAuthzClient, ExtAuthzDecision};
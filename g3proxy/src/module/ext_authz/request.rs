@@ -0,0 +1,122 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+use std::net::IpAddr;
+
+use http::{HeaderName, Method, Uri};
+
+use g3_types::net::{HttpHeaderMap, UpstreamAddr};
+
+use crate::config::server::ext_authz::ExtAuthzConfig;
+
+/// The metadata of the client request that will be sent to the authorization service
+pub(crate) struct ExtAuthzRequest<'a> {
+    pub(crate) client_ip: IpAddr,
+    pub(crate) method: &'a Method,
+    pub(crate) uri: &'a Uri,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) is_https: bool,
+    pub(crate) user: Option<&'a str>,
+    pub(crate) headers: &'a HttpHeaderMap,
+}
+
+impl ExtAuthzRequest<'_> {
+    /// Build the request header, which is also used as the cache key of the decision
+    pub(crate) fn serialize(&self, config: &ExtAuthzConfig) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(512);
+        let _ = write!(
+            buf,
+            "POST {} HTTP/1.1\r\nHost: {}\r\n",
+            config.path, config.addr
+        );
+        let _ = write!(buf, "X-Authz-Client-IP: {}\r\n", self.client_ip);
+        let _ = write!(buf, "X-Authz-Method: {}\r\n", self.method);
+        let _ = write!(buf, "X-Authz-Uri: {}\r\n", self.uri);
+        let _ = write!(buf, "X-Authz-Upstream: {}\r\n", self.upstream);
+        let protocol = if self.is_https { "https" } else { "http" };
+        let _ = write!(buf, "X-Authz-Protocol: {protocol}\r\n");
+        if let Some(user) = self.user
+            && is_valid_value(user.as_bytes())
+        {
+            let _ = write!(buf, "X-Authz-User: {user}\r\n");
+        }
+        for name in &config.forward_headers {
+            self.write_forward_header(name, &mut buf);
+        }
+        buf.extend_from_slice(b"Content-Length: 0\r\nConnection: close\r\n\r\n");
+        buf
+    }
+
+    fn write_forward_header(&self, name: &HeaderName, buf: &mut Vec<u8>) {
+        for value in self.headers.get_all(name) {
+            if is_valid_value(value.as_bytes()) {
+                value.write_to_buf(name, buf);
+            }
+        }
+    }
+}
+
+fn is_valid_value(value: &[u8]) -> bool {
+    value
+        .iter()
+        .all(|b| *b == b'\t' || (*b >= 0x20 && *b != 0x7f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_types::net::HttpHeaderValue;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn serialize() {
+        let config = ExtAuthzConfig::parse(&yaml_doc!(
+            r#"
+                addr: 127.0.0.1:9000
+                path: /authz
+                forward_headers:
+                  - authorization
+                  - x-empty
+            "#
+        ))
+        .unwrap();
+
+        let mut headers = HttpHeaderMap::default();
+        headers.append(
+            http::header::AUTHORIZATION,
+            HttpHeaderValue::from_static("Bearer abc"),
+        );
+        headers.append(
+            http::header::USER_AGENT,
+            HttpHeaderValue::from_static("curl"),
+        );
+        let uri = Uri::from_static("http://www.example.net/index.html");
+        let upstream = UpstreamAddr::from_host_str_and_port("www.example.net", 80).unwrap();
+        let req = ExtAuthzRequest {
+            client_ip: IpAddr::from([192, 168, 1, 10]),
+            method: &Method::GET,
+            uri: &uri,
+            upstream: &upstream,
+            is_https: false,
+            user: Some("alice\r\nx-injected: 1"),
+            headers: &headers,
+        };
+        assert_eq!(
+            std::str::from_utf8(&req.serialize(&config)).unwrap(),
+            "POST /authz HTTP/1.1\r\n\
+             Host: 127.0.0.1:9000\r\n\
+             X-Authz-Client-IP: 192.168.1.10\r\n\
+             X-Authz-Method: GET\r\n\
+             X-Authz-Uri: http://www.example.net/index.html\r\n\
+             X-Authz-Upstream: www.example.net:80\r\n\
+             X-Authz-Protocol: http\r\n\
+             authorization: Bearer abc\r\n\
+             Content-Length: 0\r\n\
+             Connection: close\r\n\r\n"
+        );
+    }
+}
@@ -58,6 +58,9 @@ pub(crate) trait HttpForwardContext {
         task_notes: &ServerTaskNotes,
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError>;
+    /// Use a https connection made outside of this context, such as the one made in a failed
+    /// h2 negotiation
+    fn adopt_https_connection(&mut self, tcp_notes: &TcpConnectTaskNotes);
     fn save_alive_connection(&mut self, c: BoxHttpForwardConnection);
     fn fetch_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes);
 }
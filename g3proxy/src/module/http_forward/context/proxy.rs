@@ -137,6 +137,11 @@ impl HttpForwardContext for ProxyHttpForwardContext {
             .await
     }
 
+    fn adopt_https_connection(&mut self, tcp_notes: &TcpConnectTaskNotes) {
+        self.last_is_tls = true;
+        self.tcp_notes.clone_from(tcp_notes);
+    }
+
     fn save_alive_connection(&mut self, c: BoxHttpForwardConnection) {
         let eof_poller = HttpConnectionEofPoller::spawn(c);
         self.last_connection = Some((Instant::now(), eof_poller));
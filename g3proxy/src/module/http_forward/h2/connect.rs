@@ -0,0 +1,115 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use h2::client::SendRequest;
+
+use g3_daemon::stat::remote::TcpConnectionTaskRemoteStats;
+use g3_io_ext::{AsyncStream, LimitedBufReader, LimitedWriter, NilLimitedReaderStats};
+use g3_openssl::SslConnector;
+use g3_types::net::AlpnProtocol;
+
+use crate::audit::AuditContext;
+use crate::escape::{
+    ArcEscaper, DirectFixedEscaperStats, DirectHttpForwardReader, DirectHttpForwardWriter,
+};
+use crate::module::http_forward::{
+    ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpForwardTaskRemoteWrapperStats,
+};
+use crate::module::tcp_connect::{TcpConnectError, TcpConnectTaskNotes, TlsConnectTaskConf};
+use crate::serve::ServerTaskNotes;
+
+/// The shared connection is not counted in any task stats
+struct H2ConnectionRemoteStats;
+
+impl TcpConnectionTaskRemoteStats for H2ConnectionRemoteStats {
+    fn add_read_bytes(&self, _size: u64) {}
+
+    fn add_write_bytes(&self, _size: u64) {}
+}
+
+const H2_OR_HTTP11_ALPN: &[u8] = b"\x02h2\x08http/1.1";
+
+pub(crate) enum H2ConnectResult<F> {
+    /// h2 is selected, with the request sender and the connection driver
+    Http2(SendRequest<Bytes>, F),
+    /// h2 is not selected, the TLS connection can be used to forward the request in the HTTP/1.1 way
+    Http11(BoxHttpForwardConnection),
+}
+
+/// Setup a new h2 connection to the upstream through the escaper.
+///
+/// The established TLS connection will be returned if h2 is not selected by the upstream in the
+/// ALPN negotiation, so no new connection need to be made for the HTTP/1.1 request.
+pub(crate) async fn new_h2_connection(
+    escaper: &ArcEscaper,
+    task_conf: &TlsConnectTaskConf<'_>,
+    tcp_notes: &mut TcpConnectTaskNotes,
+    task_notes: &ServerTaskNotes,
+    task_stats: ArcHttpForwardTaskRemoteStats,
+    audit_ctx: &mut AuditContext,
+    max_header_list_size: u32,
+) -> Result<
+    H2ConnectResult<impl Future<Output = Result<(), h2::Error>> + Send + 'static>,
+    TcpConnectError,
+> {
+    let (ups_r, ups_w) = escaper
+        .tcp_setup_connection(
+            &task_conf.tcp,
+            tcp_notes,
+            task_notes,
+            Arc::new(H2ConnectionRemoteStats),
+            audit_ctx,
+        )
+        .await?;
+
+    let mut ssl = task_conf.build_ssl()?;
+    ssl.set_alpn_protos(H2_OR_HTTP11_ALPN)
+        .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow!("failed to set alpn: {e}")))?;
+    let connector = SslConnector::new(ssl, tokio::io::join(ups_r, ups_w))
+        .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
+    let tls_stream =
+        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => {
+                return Err(TcpConnectError::UpstreamTlsHandshakeFailed(
+                    anyhow::Error::new(e),
+                ));
+            }
+            Err(_) => return Err(TcpConnectError::UpstreamTlsHandshakeTimeout),
+        };
+    if tls_stream.ssl().selected_alpn_protocol() != Some(AlpnProtocol::Http2.as_str().as_bytes()) {
+        // the tcp level traffic is not counted in task stats, so add them at the tls level,
+        // the same as the https forward connections made by escapers
+        let (ups_r, ups_w) = tls_stream.into_split();
+        let wrapper_stats = Arc::new(HttpForwardTaskRemoteWrapperStats::new(task_stats));
+        let ups_r = LimitedBufReader::new_unlimited(
+            ups_r,
+            Arc::new(NilLimitedReaderStats::default()),
+            wrapper_stats.clone(),
+        );
+        let ups_w = LimitedWriter::new(ups_w, wrapper_stats);
+
+        let writer = DirectHttpForwardWriter::<_, DirectFixedEscaperStats>::new(ups_w, None);
+        let reader = DirectHttpForwardReader::new(ups_r);
+        return Ok(H2ConnectResult::Http11((
+            Box::new(writer),
+            Box::new(reader),
+        )));
+    }
+
+    let mut client_builder = h2::client::Builder::new();
+    client_builder.max_header_list_size(max_header_list_size);
+    let (send_request, connection) = client_builder
+        .handshake::<_, Bytes>(tls_stream)
+        .await
+        .map_err(|e| {
+            TcpConnectError::UpstreamTlsHandshakeFailed(anyhow!("h2 handshake failed: {e}"))
+        })?;
+    Ok(H2ConnectResult::Http2(send_request, connection))
+}
@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod pool;
+pub(crate) use pool::{H2ConnectionKey, H2ConnectionPool};
+
+mod connect;
+pub(crate) use connect::{H2ConnectResult, new_h2_connection};
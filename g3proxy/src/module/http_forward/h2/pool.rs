@@ -0,0 +1,361 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::hash::{DefaultHasher, Hasher};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+use bytes::Bytes;
+use h2::client::SendRequest;
+use lru::LruCache;
+
+use g3_types::metrics::NodeName;
+use g3_types::net::{AlpnProtocol, UpstreamAddr};
+
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+use crate::serve::ServerTaskNotes;
+
+const UNSUPPORTED_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+const UNSUPPORTED_CACHE_EXPIRE: Duration = Duration::from_secs(600);
+
+/// The high bit of the stream count is used to mark the connection as closed
+const CONNECTION_CLOSED_FLAG: usize = 1 << (usize::BITS - 1);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct H2ConnectionKey {
+    upstream: UpstreamAddr,
+    alpn: AlpnProtocol,
+    escaper: NodeName,
+    egress: u64,
+}
+
+impl H2ConnectionKey {
+    /// Connections can only be shared by tasks which will select the same egress path
+    pub(crate) fn new(
+        upstream: &UpstreamAddr,
+        escaper: &NodeName,
+        task_notes: &ServerTaskNotes,
+    ) -> Self {
+        let mut hasher = DefaultHasher::new();
+        task_notes.hash_egress_selection(&mut hasher);
+        H2ConnectionKey::with_egress(upstream, escaper, hasher.finish())
+    }
+
+    fn with_egress(upstream: &UpstreamAddr, escaper: &NodeName, egress: u64) -> Self {
+        H2ConnectionKey {
+            upstream: upstream.clone(),
+            alpn: AlpnProtocol::Http2,
+            escaper: escaper.clone(),
+            egress,
+        }
+    }
+}
+
+struct H2PooledConnection {
+    send_request: SendRequest<Bytes>,
+    tcp_notes: TcpConnectTaskNotes,
+    streams: AtomicUsize,
+}
+
+impl H2PooledConnection {
+    fn is_closed(&self) -> bool {
+        self.streams.load(Ordering::Acquire) & CONNECTION_CLOSED_FLAG != 0
+    }
+
+    fn try_acquire(&self, max_streams: usize) -> bool {
+        let max_streams = max_streams.min(self.send_request.current_max_send_streams());
+        self.streams
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |v| {
+                if v & CONNECTION_CLOSED_FLAG != 0 || v >= max_streams {
+                    None
+                } else {
+                    Some(v + 1)
+                }
+            })
+            .is_ok()
+    }
+
+    fn try_close_idle(&self) -> bool {
+        self.streams
+            .compare_exchange(
+                0,
+                CONNECTION_CLOSED_FLAG,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            )
+            .is_ok()
+    }
+
+    fn mark_closed(&self) {
+        self.streams
+            .fetch_or(CONNECTION_CLOSED_FLAG, Ordering::AcqRel);
+    }
+}
+
+/// A stream slot on a shared h2 connection, which will be released when dropped
+pub(crate) struct H2StreamHandle {
+    connection: Arc<H2PooledConnection>,
+}
+
+impl H2StreamHandle {
+    pub(crate) fn send_request(&self) -> SendRequest<Bytes> {
+        self.connection.send_request.clone()
+    }
+
+    pub(crate) fn tcp_notes(&self) -> &TcpConnectTaskNotes {
+        &self.connection.tcp_notes
+    }
+}
+
+impl Drop for H2StreamHandle {
+    fn drop(&mut self) {
+        self.connection.streams.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Upstream h2 connections shared by all tasks of a server
+pub(crate) struct H2ConnectionPool {
+    max_streams: usize,
+    idle_expire: Duration,
+    connections: Mutex<AHashMap<H2ConnectionKey, Vec<Arc<H2PooledConnection>>>>,
+    unsupported: Mutex<LruCache<H2ConnectionKey, Instant>>,
+}
+
+impl H2ConnectionPool {
+    pub(crate) fn new(max_streams: NonZeroUsize, idle_expire: Duration) -> Self {
+        H2ConnectionPool {
+            max_streams: max_streams.get(),
+            idle_expire: idle_expire.max(Duration::from_secs(1)),
+            connections: Mutex::new(AHashMap::new()),
+            unsupported: Mutex::new(LruCache::new(UNSUPPORTED_CACHE_SIZE)),
+        }
+    }
+
+    /// Get a free stream slot on an existing connection
+    pub(crate) fn acquire(&self, key: &H2ConnectionKey) -> Option<H2StreamHandle> {
+        let mut connections = self.connections.lock().unwrap();
+        let all = connections.get_mut(key)?;
+        all.retain(|c| !c.is_closed());
+        if all.is_empty() {
+            connections.remove(key);
+            return None;
+        }
+        let connection = all.iter().find(|c| c.try_acquire(self.max_streams))?;
+        Some(H2StreamHandle {
+            connection: connection.clone(),
+        })
+    }
+
+    /// Add a new connection to the pool, and take the first stream slot on it
+    pub(crate) fn insert<F>(
+        &self,
+        key: H2ConnectionKey,
+        send_request: SendRequest<Bytes>,
+        driver: F,
+        tcp_notes: TcpConnectTaskNotes,
+    ) -> H2StreamHandle
+    where
+        F: Future<Output = Result<(), h2::Error>> + Send + 'static,
+    {
+        let connection = Arc::new(H2PooledConnection {
+            send_request,
+            tcp_notes,
+            streams: AtomicUsize::new(1),
+        });
+
+        let pooled = connection.clone();
+        let idle_expire = self.idle_expire;
+        tokio::spawn(async move {
+            tokio::pin!(driver);
+            let mut check_interval = tokio::time::interval(idle_expire);
+            check_interval.tick().await;
+            let mut idle_count = 0;
+            loop {
+                tokio::select! {
+                    _ = &mut driver => break,
+                    _ = check_interval.tick() => {
+                        let streams = pooled.streams.load(Ordering::Acquire);
+                        if streams == CONNECTION_CLOSED_FLAG {
+                            // flushed from the pool, and no more streams in use
+                            return;
+                        } else if streams == 0 {
+                            idle_count += 1;
+                            if idle_count > 1 && pooled.try_close_idle() {
+                                // drop the connection
+                                return;
+                            }
+                        } else {
+                            idle_count = 0;
+                        }
+                    }
+                }
+            }
+            pooled.mark_closed();
+        });
+
+        self.connections
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_default()
+            .push(connection.clone());
+        H2StreamHandle { connection }
+    }
+
+    pub(crate) fn is_unsupported(&self, key: &H2ConnectionKey) -> bool {
+        let mut unsupported = self.unsupported.lock().unwrap();
+        match unsupported.get(key) {
+            Some(expire) if *expire > Instant::now() => true,
+            Some(_) => {
+                unsupported.pop(key);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Mark the upstream as not supporting h2, so the following requests will use HTTP/1.1
+    pub(crate) fn mark_unsupported(&self, key: H2ConnectionKey) {
+        let expire = Instant::now() + UNSUPPORTED_CACHE_EXPIRE;
+        self.unsupported.lock().unwrap().put(key, expire);
+    }
+
+    /// Drop all connections, the in use ones will be closed after all their streams released
+    pub(crate) fn flush(&self) {
+        let connections = std::mem::take(&mut *self.connections.lock().unwrap());
+        for c in connections.values().flatten() {
+            c.mark_closed();
+        }
+        self.unsupported.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const ESCAPER: NodeName = NodeName::new_static("default");
+
+    fn build_key(upstream: &str, escaper: &NodeName, egress: u64) -> H2ConnectionKey {
+        let upstream = UpstreamAddr::from_str(upstream).unwrap();
+        H2ConnectionKey::with_egress(&upstream, escaper, egress)
+    }
+
+    async fn insert_connection(
+        pool: &H2ConnectionPool,
+        key: &H2ConnectionKey,
+    ) -> (H2StreamHandle, tokio::io::DuplexStream) {
+        let (clt_io, ups_io) = tokio::io::duplex(65536);
+        let (send_request, driver) = h2::client::handshake(clt_io).await.unwrap();
+        let handle = pool.insert(
+            key.clone(),
+            send_request,
+            driver,
+            TcpConnectTaskNotes::default(),
+        );
+        (handle, ups_io)
+    }
+
+    #[tokio::test]
+    async fn acquire_with_stream_cap() {
+        let pool = H2ConnectionPool::new(NonZeroUsize::new(2).unwrap(), Duration::from_secs(60));
+        let key = build_key("example.net:443", &ESCAPER, 0);
+        assert!(pool.acquire(&key).is_none());
+        assert!(!pool.is_unsupported(&key));
+
+        let (first, _ups_io) = insert_connection(&pool, &key).await;
+
+        let second = pool.acquire(&key).unwrap();
+        assert!(pool.acquire(&key).is_none());
+        drop(first);
+        let third = pool.acquire(&key).unwrap();
+        assert!(pool.acquire(&key).is_none());
+        drop(second);
+        drop(third);
+
+        let other = build_key("example.net:8443", &ESCAPER, 0);
+        assert!(pool.acquire(&other).is_none());
+    }
+
+    #[tokio::test]
+    async fn acquire_isolated() {
+        let pool = H2ConnectionPool::new(NonZeroUsize::new(8).unwrap(), Duration::from_secs(60));
+        let key = build_key("example.net:443", &ESCAPER, 1);
+        let (_handle, _ups_io) = insert_connection(&pool, &key).await;
+        assert!(pool.acquire(&key).is_some());
+
+        let other_escaper = NodeName::new_static("other");
+        let key = build_key("example.net:443", &other_escaper, 1);
+        assert!(pool.acquire(&key).is_none());
+
+        let key = build_key("example.net:443", &ESCAPER, 2);
+        assert!(pool.acquire(&key).is_none());
+    }
+
+    #[tokio::test]
+    async fn close_on_upstream_gone() {
+        let pool = H2ConnectionPool::new(NonZeroUsize::new(8).unwrap(), Duration::from_secs(60));
+        let key = build_key("example.net:443", &ESCAPER, 0);
+        let (handle, ups_io) = insert_connection(&pool, &key).await;
+        assert!(pool.acquire(&key).is_some());
+
+        drop(ups_io);
+        for _ in 0..100 {
+            if handle.connection.is_closed() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(handle.connection.is_closed());
+        assert!(pool.acquire(&key).is_none());
+        assert!(pool.connections.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn flush() {
+        let pool = H2ConnectionPool::new(NonZeroUsize::new(8).unwrap(), Duration::from_secs(60));
+        let key = build_key("example.net:443", &ESCAPER, 0);
+        let (handle, _ups_io) = insert_connection(&pool, &key).await;
+        let other = build_key("example.net:8443", &ESCAPER, 0);
+        pool.mark_unsupported(other.clone());
+
+        pool.flush();
+        assert!(handle.connection.is_closed());
+        assert!(pool.acquire(&key).is_none());
+        assert!(!pool.is_unsupported(&other));
+
+        // the stream in use should still be released
+        let connection = handle.connection.clone();
+        drop(handle);
+        assert_eq!(
+            connection.streams.load(Ordering::Acquire),
+            CONNECTION_CLOSED_FLAG
+        );
+        assert!(!connection.try_close_idle());
+    }
+
+    #[test]
+    fn unsupported() {
+        let pool = H2ConnectionPool::new(NonZeroUsize::new(8).unwrap(), Duration::from_secs(60));
+        let key = build_key("example.net:443", &ESCAPER, 0);
+        assert!(!pool.is_unsupported(&key));
+        pool.mark_unsupported(key.clone());
+        assert!(pool.is_unsupported(&key));
+
+        let other = build_key("example.net:443", &ESCAPER, 1);
+        assert!(!pool.is_unsupported(&other));
+
+        pool.unsupported
+            .lock()
+            .unwrap()
+            .put(key.clone(), Instant::now() - Duration::from_secs(1));
+        assert!(!pool.is_unsupported(&key));
+        assert!(pool.unsupported.lock().unwrap().is_empty());
+    }
+}
@@ -5,6 +5,7 @@
 
 mod connection;
 mod context;
+mod h2;
 mod response;
 mod stats;
 mod task;
@@ -18,6 +19,7 @@ pub(crate) use context::{
     BoxHttpForwardContext, DirectHttpForwardContext, FailoverHttpForwardContext,
     HttpForwardContext, ProxyHttpForwardContext, RouteHttpForwardContext,
 };
+pub(crate) use h2::{H2ConnectResult, H2ConnectionKey, H2ConnectionPool, new_h2_connection};
 pub(crate) use response::HttpProxyClientResponse;
 pub(crate) use stats::{
     ArcHttpForwardTaskRemoteStats, HttpForwardRemoteWrapperStats, HttpForwardTaskRemoteStats,
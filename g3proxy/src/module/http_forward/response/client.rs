@@ -274,6 +274,11 @@ impl HttpProxyClientResponse {
             TcpConnectError::NoAddressConnected => {
                 HttpProxyClientResponse::from_standard(StatusCode::BAD_GATEWAY, version, close)
             }
+            TcpConnectError::UpstreamKnownDown => HttpProxyClientResponse::from_standard(
+                StatusCode::SERVICE_UNAVAILABLE,
+                version,
+                close,
+            ),
             TcpConnectError::ForbiddenAddressFamily | TcpConnectError::ForbiddenRemoteAddress => {
                 HttpProxyClientResponse::from_standard(StatusCode::FORBIDDEN, version, close)
             }
@@ -392,7 +397,7 @@ impl HttpProxyClientResponse {
                 version,
                 true,
             ),
-            ServerTaskError::UpstreamAppTimeout(_) => {
+            ServerTaskError::UpstreamAppTimeout(_) | ServerTaskError::DeadlineExceeded(_) => {
                 HttpProxyClientResponse::from_standard(StatusCode::GATEWAY_TIMEOUT, version, true)
             }
             ServerTaskError::ClientAppTimeout(_) => {
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+pub(crate) mod ext_authz;
 pub(crate) mod ftp_over_http;
 pub(crate) mod http_forward;
 pub(crate) mod http_header;
@@ -31,6 +31,8 @@ pub(crate) enum TcpConnectError {
     TimeoutByRule,
     #[error("no address connected")]
     NoAddressConnected,
+    #[error("upstream known down")]
+    UpstreamKnownDown,
     #[error("forbidden address family")]
     ForbiddenAddressFamily,
     #[error("forbidden remote address")]
@@ -73,6 +75,7 @@ impl TcpConnectError {
             TcpConnectError::ConnectFailed(_) => "ConnectFailed",
             TcpConnectError::TimeoutByRule => "TimeoutByRule",
             TcpConnectError::NoAddressConnected => "NoAddressConnected",
+            TcpConnectError::UpstreamKnownDown => "UpstreamKnownDown",
             TcpConnectError::ForbiddenAddressFamily => "ForbiddenAddressFamily",
             TcpConnectError::ForbiddenRemoteAddress => "ForbiddenRemoteAddress",
             TcpConnectError::ProxyProtocolEncodeError(_) => "ProxyProtocolEncodeError",
@@ -107,7 +110,9 @@ impl From<TcpConnectError> for ServerTaskError {
             TcpConnectError::TimeoutByRule => {
                 ServerTaskError::UpstreamNotConnected(ConnectError::TimedOut)
             }
-            TcpConnectError::NoAddressConnected => ServerTaskError::UpstreamNotAvailable,
+            TcpConnectError::NoAddressConnected | TcpConnectError::UpstreamKnownDown => {
+                ServerTaskError::UpstreamNotAvailable
+            }
             TcpConnectError::ForbiddenAddressFamily | TcpConnectError::ForbiddenRemoteAddress => {
                 ServerTaskError::ForbiddenByRule(ServerTaskForbiddenError::IpBlocked)
             }
@@ -178,9 +183,9 @@ impl From<&TcpConnectError> for Socks5Reply {
                 ConnectError::TimedOut => Socks5Reply::ConnectionTimedOut,
                 ConnectError::UnspecifiedError(_) => Socks5Reply::GeneralServerFailure,
             },
-            TcpConnectError::ResolveFailed(_) | TcpConnectError::NoAddressConnected => {
-                Socks5Reply::HostUnreachable
-            }
+            TcpConnectError::ResolveFailed(_)
+            | TcpConnectError::NoAddressConnected
+            | TcpConnectError::UpstreamKnownDown => Socks5Reply::HostUnreachable,
             TcpConnectError::TimeoutByRule => Socks5Reply::ConnectionTimedOut,
             TcpConnectError::EscaperNotUsable(_)
             | TcpConnectError::SetupSocketFailed(_)
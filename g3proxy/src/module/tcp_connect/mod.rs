@@ -3,7 +3,10 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::sync::Arc;
+
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
 
 mod error;
 mod stats;
@@ -18,3 +21,7 @@ pub(crate) type TcpConnection = (
     Box<dyn AsyncWrite + Unpin + Send + Sync>,
 );
 pub(crate) type TcpConnectResult = Result<TcpConnection, TcpConnectError>;
+
+/// The raw upstream socket and the stats that should be updated when relaying on it
+pub(crate) type TcpSpliceConnection = (TcpStream, Arc<TcpConnectRemoteWrapperStats>);
+pub(crate) type TcpSpliceConnectResult = Result<TcpSpliceConnection, TcpConnectError>;
@@ -11,7 +11,7 @@ use openssl::ssl::Ssl;
 
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
-use g3_types::net::{EgressInfo, Host, OpensslClientConfig, UpstreamAddr};
+use g3_types::net::{EgressInfo, Host, OpensslClientConfig, SocketBufferConfig, UpstreamAddr};
 
 use super::TcpConnectError;
 
@@ -58,6 +58,7 @@ pub(crate) struct TcpConnectTaskNotes {
     pub(crate) next: Option<SocketAddr>,
     pub(crate) tries: usize,
     pub(crate) local: Option<SocketAddr>,
+    pub(crate) socket_buffer: Option<SocketBufferConfig>,
     pub(crate) expire: Option<DateTime<Utc>>,
     pub(crate) egress: Option<EgressInfo>,
     pub(crate) chained: TcpConnectChainedNotes,
@@ -72,6 +73,7 @@ impl TcpConnectTaskNotes {
         self.next = None;
         self.tries = 0;
         self.local = None;
+        self.socket_buffer = None;
         self.expire = None;
         self.egress = None;
         self.chained.reset();
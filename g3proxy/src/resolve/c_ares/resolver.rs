@@ -29,6 +29,9 @@ impl CAresResolver {
     pub(crate) fn new_obj(config: CAresResolverConfig) -> anyhow::Result<BoxResolverInternal> {
         let mut builder = g3_resolver::ResolverBuilder::new((&config).into());
         builder.thread_name(format!("res-{}", config.name()));
+        if config.per_worker_runtime() {
+            builder.worker_handles(crate::resolve::worker_handles());
+        }
         let resolver = builder.build()?;
 
         let logger = crate::log::resolve::get_logger(config.r#type(), config.name());
@@ -43,6 +43,7 @@ impl FailOverResolver {
             name: config.name().to_string(),
             runtime: config.runtime.clone(),
             driver: g3_resolver::AnyResolveDriverConfig::FailOver(driver_config.clone()),
+            suffix_drivers: Vec::new(),
         };
         let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
         builder.thread_name(format!("res-{}", config.name()));
@@ -89,6 +90,7 @@ impl ResolverInternal for FailOverResolver {
                 name: config.name().to_string(),
                 runtime: config.runtime.clone(),
                 driver: g3_resolver::AnyResolveDriverConfig::FailOver(driver_config.clone()),
+                suffix_drivers: Vec::new(),
             };
 
             self.inner
@@ -124,6 +126,7 @@ impl ResolverInternal for FailOverResolver {
             name: self.config.name().to_string(),
             runtime: self.config.runtime.clone(),
             driver: g3_resolver::AnyResolveDriverConfig::FailOver(driver_config.clone()),
+            suffix_drivers: Vec::new(),
         };
 
         self.inner
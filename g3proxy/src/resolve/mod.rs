@@ -32,11 +32,21 @@ mod hickory;
 
 mod deny_all;
 mod fail_over;
+mod hosts;
 
 mod ops;
 pub use ops::spawn_all;
 pub(crate) use ops::{foreach_resolver, reload};
 
+fn worker_handles() -> Vec<tokio::runtime::Handle> {
+    let mut handles = Vec::with_capacity(g3_daemon::runtime::worker::worker_count());
+    let _ = g3_daemon::runtime::worker::foreach(|h| {
+        handles.push(h.handle.clone());
+        Ok::<(), ()>(())
+    });
+    handles
+}
+
 pub(crate) trait Resolver {
     fn get_handle(&self) -> ArcIntegratedResolverHandle;
     fn get_stats(&self) -> Arc<ResolverStats>;
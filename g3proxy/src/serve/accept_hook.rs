@@ -0,0 +1,67 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+
+use async_trait::async_trait;
+use tokio::net::TcpStream;
+
+use g3_daemon::listen::TcpAcceptHook;
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::acl::{AclAction, AclNetworkRule};
+
+/// Veto the connections that are forbidden by the ingress network filter
+pub(crate) struct IngressNetworkFilterHook {
+    filter: AclNetworkRule,
+}
+
+impl IngressNetworkFilterHook {
+    pub(crate) fn new(filter: AclNetworkRule) -> Self {
+        IngressNetworkFilterHook { filter }
+    }
+
+    fn permit(&self, ip: IpAddr) -> bool {
+        let (_, action) = self.filter.check(ip);
+        match action {
+            AclAction::Permit | AclAction::PermitAndLog => true,
+            AclAction::Forbid | AclAction::ForbidAndLog => false,
+        }
+    }
+}
+
+#[async_trait]
+impl TcpAcceptHook for IngressNetworkFilterHook {
+    async fn check(&self, _stream: &TcpStream, cc_info: &ClientConnectionInfo) -> bool {
+        self.permit(cc_info.client_addr().ip())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use ip_network::IpNetwork;
+
+    use g3_types::acl::AclNetworkRuleBuilder;
+
+    #[test]
+    fn permit() {
+        let mut builder = AclNetworkRuleBuilder::new(AclAction::Forbid);
+        builder.add_network(
+            IpNetwork::from_str("192.168.0.0/16").unwrap(),
+            AclAction::Permit,
+        );
+        builder.add_network(
+            IpNetwork::from_str("192.168.1.0/24").unwrap(),
+            AclAction::ForbidAndLog,
+        );
+        let hook = IngressNetworkFilterHook::new(builder.build());
+
+        assert!(hook.permit(IpAddr::from_str("192.168.0.1").unwrap()));
+        assert!(!hook.permit(IpAddr::from_str("192.168.1.1").unwrap()));
+        assert!(!hook.permit(IpAddr::from_str("10.0.0.1").unwrap()));
+    }
+}
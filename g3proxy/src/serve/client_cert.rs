@@ -0,0 +1,127 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use log::debug;
+use lru::LruCache;
+use openssl::error::ErrorStack;
+use openssl::ssl::SslRef;
+use rustls::ServerConnection;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::net::TlsClientCertInfo;
+
+const CLIENT_CERT_CACHE_SIZE: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
+/// Get the TLS client cert info, each distinct certificate will only be parsed once
+pub(crate) struct ClientCertParser {
+    cache: Mutex<LruCache<Vec<u8>, Arc<TlsClientCertInfo>>>,
+}
+
+impl Default for ClientCertParser {
+    fn default() -> Self {
+        ClientCertParser {
+            cache: Mutex::new(LruCache::new(CLIENT_CERT_CACHE_SIZE)),
+        }
+    }
+}
+
+impl ClientCertParser {
+    pub(crate) fn parse_rustls(
+        &self,
+        conn: &ServerConnection,
+        cc_info: &ClientConnectionInfo,
+    ) -> Option<Arc<TlsClientCertInfo>> {
+        let cert = conn.peer_certificates()?.first()?;
+        self.parse_der(cert.as_ref())
+            .inspect_err(|e| log_invalid(cc_info, e))
+            .ok()
+    }
+
+    pub(crate) fn parse_openssl(
+        &self,
+        ssl: &SslRef,
+        cc_info: &ClientConnectionInfo,
+    ) -> Option<Arc<TlsClientCertInfo>> {
+        let cert = ssl.peer_certificate()?;
+        cert.to_der()
+            .and_then(|der| self.parse_der(&der))
+            .inspect_err(|e| log_invalid(cc_info, e))
+            .ok()
+    }
+
+    fn parse_der(&self, der: &[u8]) -> Result<Arc<TlsClientCertInfo>, ErrorStack> {
+        if let Some(info) = self.cache.lock().unwrap().get(der) {
+            return Ok(info.clone());
+        }
+
+        let info = Arc::new(TlsClientCertInfo::parse_der(der)?);
+        self.cache.lock().unwrap().put(der.to_vec(), info.clone());
+        Ok(info)
+    }
+}
+
+fn log_invalid(cc_info: &ClientConnectionInfo, e: &ErrorStack) {
+    debug!(
+        "{} - {} invalid tls client cert: {e}",
+        cc_info.sock_local_addr(),
+        cc_info.sock_peer_addr()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::{X509, X509NameBuilder};
+
+    fn build_cert(cn: &str) -> Vec<u8> {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, cn).unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        builder.build().to_der().unwrap()
+    }
+
+    #[test]
+    fn cached() {
+        let parser = ClientCertParser::default();
+
+        let der1 = build_cert("client1");
+        let info1 = parser.parse_der(&der1).unwrap();
+        assert_eq!(info1.subject(), "CN=client1");
+        let info1_cached = parser.parse_der(&der1).unwrap();
+        assert!(Arc::ptr_eq(&info1, &info1_cached));
+
+        let der2 = build_cert("client2");
+        let info2 = parser.parse_der(&der2).unwrap();
+        assert_eq!(info2.subject(), "CN=client2");
+        assert!(!Arc::ptr_eq(&info1, &info2));
+
+        assert!(parser.parse_der(b"invalid").is_err());
+        assert_eq!(parser.cache.lock().unwrap().len(), 2);
+    }
+}
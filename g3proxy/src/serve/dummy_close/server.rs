@@ -124,6 +124,10 @@ impl ServerInternal for DummyCloseServer {
     fn _abort_runtime(&self) {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for DummyCloseServer {
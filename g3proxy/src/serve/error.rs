@@ -48,6 +48,10 @@ pub(crate) enum ServerTaskForbiddenError {
     UaBlocked,
     #[error("user blocked")]
     UserBlocked,
+    #[error("denied by ext authz")]
+    ExtAuthzDenied,
+    #[error("traffic quota exceeded")]
+    QuotaExceeded,
 }
 
 #[derive(Error, Debug)]
@@ -118,6 +122,8 @@ pub(crate) enum ServerTaskError {
     CanceledAsServerQuit,
     #[error("idle after {0:?} x {1}")]
     Idle(Duration, usize),
+    #[error("deadline exceeded after {0:?}")]
+    DeadlineExceeded(Duration),
     #[error("{0} interception error: {1}")]
     InterceptionError(Protocol, InterceptionError),
     #[error("finished")]
@@ -162,6 +168,7 @@ impl ServerTaskError {
             ServerTaskError::CanceledAsUserBlocked => "CanceledAsUserBlocked",
             ServerTaskError::CanceledAsServerQuit => "CanceledAsServerQuit",
             ServerTaskError::Idle(_, _) => "Idle",
+            ServerTaskError::DeadlineExceeded(_) => "DeadlineExceeded",
             ServerTaskError::InterceptionError(_, _) => "InterceptionError",
             ServerTaskError::Finished => "Finished",
             ServerTaskError::UnclassifiedError(_) => "UnclassifiedError",
@@ -305,6 +312,12 @@ impl From<HttpResponseParseError> for ServerTaskError {
             HttpResponseParseError::TooLargeHeader(_) => {
                 ServerTaskError::InvalidUpstreamProtocol("too large header in remote response")
             }
+            HttpResponseParseError::TooLongReason(_) => {
+                ServerTaskError::InvalidUpstreamProtocol("too long reason in remote response")
+            }
+            HttpResponseParseError::ObsFoldedHeader => {
+                ServerTaskError::InvalidUpstreamProtocol("obs-fold header in remote response")
+            }
             HttpResponseParseError::IoFailed(e) => ServerTaskError::UpstreamReadFailed(e),
             _ => ServerTaskError::InvalidUpstreamProtocol("invalid remote response"),
         }
@@ -0,0 +1,114 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::Notify;
+
+/// Track the client connections that are idle waiting for the next request
+#[derive(Default)]
+pub(crate) struct HttpProxyIdleClients {
+    next_id: AtomicU64,
+    /// the notifier of each idle connection, the oldest one comes first
+    idle: Mutex<BTreeMap<u64, Arc<Notify>>>,
+}
+
+impl HttpProxyIdleClients {
+    pub(crate) fn count(&self) -> usize {
+        self.idle.lock().unwrap().len()
+    }
+
+    /// Mark the connection as idle, the oldest idle connections will be told to close
+    /// if there are more than `max_count` idle connections, 0 means no limit
+    pub(crate) fn enter(self: &Arc<Self>, max_count: usize) -> HttpProxyIdleClientGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let notify = Arc::new(Notify::new());
+
+        let mut idle = self.idle.lock().unwrap();
+        idle.insert(id, notify.clone());
+        if max_count > 0 {
+            while idle.len() > max_count {
+                let Some((_, oldest)) = idle.pop_first() else {
+                    break;
+                };
+                oldest.notify_one();
+            }
+        }
+        drop(idle);
+
+        HttpProxyIdleClientGuard {
+            clients: self.clone(),
+            id,
+            notify,
+        }
+    }
+}
+
+pub(crate) struct HttpProxyIdleClientGuard {
+    clients: Arc<HttpProxyIdleClients>,
+    id: u64,
+    notify: Arc<Notify>,
+}
+
+impl HttpProxyIdleClientGuard {
+    /// Wait until this connection is evicted as there are too many idle connections
+    pub(crate) async fn evicted(&self) {
+        self.notify.notified().await
+    }
+}
+
+impl Drop for HttpProxyIdleClientGuard {
+    fn drop(&mut self) {
+        self.clients.idle.lock().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    async fn is_evicted(guard: &HttpProxyIdleClientGuard) -> bool {
+        tokio::time::timeout(Duration::from_millis(10), guard.evicted())
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn evict_oldest() {
+        let clients = Arc::new(HttpProxyIdleClients::default());
+
+        let g1 = clients.enter(2);
+        let g2 = clients.enter(2);
+        assert_eq!(clients.count(), 2);
+        assert!(!is_evicted(&g1).await);
+
+        let g3 = clients.enter(2);
+        assert_eq!(clients.count(), 2);
+        assert!(is_evicted(&g1).await);
+        assert!(!is_evicted(&g2).await);
+        assert!(!is_evicted(&g3).await);
+
+        drop(g1);
+        assert_eq!(clients.count(), 2);
+        drop(g2);
+        assert_eq!(clients.count(), 1);
+        drop(g3);
+        assert_eq!(clients.count(), 0);
+    }
+
+    #[tokio::test]
+    async fn no_limit() {
+        let clients = Arc::new(HttpProxyIdleClients::default());
+
+        let guards = (0..10).map(|_| clients.enter(0)).collect::<Vec<_>>();
+        assert_eq!(clients.count(), 10);
+        assert!(!is_evicted(&guards[0]).await);
+        drop(guards);
+        assert_eq!(clients.count(), 0);
+    }
+}
@@ -6,6 +6,9 @@
 mod stats;
 use stats::HttpProxyServerStats;
 
+mod idle;
+use idle::HttpProxyIdleClients;
+
 mod task;
 
 mod server;
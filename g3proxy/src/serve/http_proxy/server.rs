@@ -27,7 +27,8 @@ use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    AlpnProtocol, OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConnectionExt,
+    AlpnProtocol, OpensslClientConfig, OpensslTicketKey, RollingTicketer,
+    RustlsServerConnectionExt, TlsClientCertInfo,
 };
 
 use super::HttpProxyServerStats;
@@ -40,9 +41,11 @@ use crate::auth::UserGroup;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::ext_authz::ExtAuthzClient;
+use crate::module::http_forward::H2ConnectionPool;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, ClientCertParser, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, WrapArcServer,
 };
 
 pub(crate) struct HttpProxyServer {
@@ -53,6 +56,10 @@ pub(crate) struct HttpProxyServer {
     tls_acceptor: Option<TlsAcceptor>,
     tls_accept_timeout: Duration,
     tls_client_config: Arc<OpensslClientConfig>,
+    tls_client_http11_config: Option<Arc<OpensslClientConfig>>,
+    client_cert_parser: ClientCertParser,
+    h2_connection_pool: Option<Arc<H2ConnectionPool>>,
+    ext_authz_client: Option<Arc<ExtAuthzClient>>,
     ingress_net_filter: Option<AclNetworkRule>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
@@ -94,6 +101,27 @@ impl HttpProxyServer {
             .client_tls_config
             .build()
             .context("failed to build tls client config")?;
+        let tls_client_http11_config = if config.force_http11_upstream() {
+            let tls_config = config
+                .client_tls_config
+                .build_with_alpn_protocols(Some(vec![AlpnProtocol::Http11]))
+                .context("failed to build http/1.1 only tls client config")?;
+            Some(Arc::new(tls_config))
+        } else {
+            None
+        };
+
+        let h2_connection_pool = config.http_forward_upstream_h2.then(|| {
+            Arc::new(H2ConnectionPool::new(
+                config.http_forward_upstream_h2_max_streams,
+                config.http_forward_upstream_keepalive.idle_expire(),
+            ))
+        });
+
+        let ext_authz_client = config
+            .ext_authz
+            .as_ref()
+            .map(|c| Arc::new(ExtAuthzClient::new(c)));
 
         let ingress_net_filter = config
             .ingress_net_filter
@@ -123,6 +151,10 @@ impl HttpProxyServer {
             tls_acceptor,
             tls_accept_timeout,
             tls_client_config: Arc::new(tls_client_config),
+            tls_client_http11_config,
+            client_cert_parser: ClientCertParser::default(),
+            h2_connection_pool,
+            ext_authz_client,
             ingress_net_filter,
             dst_host_filter,
             reload_sender,
@@ -161,7 +193,7 @@ impl HttpProxyServer {
 
     fn prepare_reload(&self, config: AnyServerConfig) -> anyhow::Result<HttpProxyServer> {
         if let AnyServerConfig::HttpProxy(config) = config {
-            let config = Arc::new(config);
+            let config = Arc::new(*config);
             let server_stats = Arc::clone(&self.server_stats);
             let listen_stats = Arc::clone(&self.listen_stats);
 
@@ -202,6 +234,9 @@ impl HttpProxyServer {
             escaper: self.escaper.load().as_ref().clone(),
             cc_info,
             tls_client_config: self.tls_client_config.clone(),
+            tls_client_http11_config: self.tls_client_http11_config.clone(),
+            h2_connection_pool: self.h2_connection_pool.clone(),
+            ext_authz_client: self.ext_authz_client.clone(),
             task_logger: self.task_logger.clone(),
             dst_host_filter: self.dst_host_filter.clone(),
         })
@@ -228,8 +263,12 @@ impl HttpProxyServer {
         AuditContext::new(self.audit_handle.load_full())
     }
 
-    async fn spawn_stream_task<T>(&self, stream: T, cc_info: ClientConnectionInfo)
-    where
+    async fn spawn_stream_task<T>(
+        &self,
+        stream: T,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<TlsClientCertInfo>>,
+    ) where
         T: AsyncStream,
         T::R: AsyncRead + Send + Sync + Unpin + 'static,
         T::W: AsyncWrite + Send + Sync + Unpin + 'static,
@@ -240,11 +279,14 @@ impl HttpProxyServer {
 
         // NOTE tls underlying traffic is not counted in (server/task/user) stats
 
+        let mut audit_ctx = self.audit_context();
+        audit_ctx.set_client_cert(client_cert);
+
         let (clt_r, clt_w) = stream.into_split();
         let r_task = HttpProxyPipelineReaderTask::new(&ctx, task_sender, clt_r, &pipeline_stats);
         let w_task = HttpProxyPipelineWriterTask::new(
             &ctx,
-            self.audit_context(),
+            audit_ctx,
             self.user_group.load_full(),
             task_receiver,
             clt_w,
@@ -284,7 +326,7 @@ impl HttpProxyServer {
 
 impl ServerInternal for HttpProxyServer {
     fn _clone_config(&self) -> AnyServerConfig {
-        AnyServerConfig::HttpProxy(self.config.as_ref().clone())
+        AnyServerConfig::HttpProxy(Box::new(self.config.as_ref().clone()))
     }
 
     fn _depend_on_server(&self, _name: &NodeName) -> bool {
@@ -301,6 +343,10 @@ impl ServerInternal for HttpProxyServer {
     fn _update_escaper_in_place(&self) {
         let escaper = crate::escape::get_or_insert_default(self.config.escaper());
         self.escaper.store(Arc::new(escaper));
+        if let Some(pool) = &self.h2_connection_pool {
+            // the connections may be established through the old escaper
+            pool.flush();
+        }
     }
 
     fn _update_user_group_in_place(&self) {
@@ -351,6 +397,10 @@ impl ServerInternal for HttpProxyServer {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
         self.server_stats.set_offline();
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for HttpProxyServer {
@@ -386,7 +436,11 @@ impl AcceptTcpServer for HttpProxyServer {
                         // Quick ACK is needed with session resumption
                         cc_info.tcp_sock_try_quick_ack();
                     }
-                    self.spawn_stream_task(tls_stream, cc_info).await
+                    let client_cert = self
+                        .client_cert_parser
+                        .parse_rustls(tls_stream.get_ref().1, &cc_info);
+                    self.spawn_stream_task(tls_stream, cc_info, client_cert)
+                        .await
                 }
                 Ok(Err(e)) => {
                     self.listen_stats.add_failed();
@@ -408,7 +462,7 @@ impl AcceptTcpServer for HttpProxyServer {
                 }
             }
         } else {
-            self.spawn_stream_task(stream, cc_info).await;
+            self.spawn_stream_task(stream, cc_info, None).await;
         }
     }
 }
@@ -480,7 +534,10 @@ impl Server for HttpProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        let client_cert = self
+            .client_cert_parser
+            .parse_rustls(stream.get_ref().1, &cc_info);
+        self.spawn_stream_task(stream, cc_info, client_cert).await;
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
@@ -490,6 +547,9 @@ impl Server for HttpProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        let client_cert = self
+            .client_cert_parser
+            .parse_openssl(stream.ssl(), &cc_info);
+        self.spawn_stream_task(stream, cc_info, client_cert).await;
     }
 }
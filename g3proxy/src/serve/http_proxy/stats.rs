@@ -12,8 +12,10 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
+use super::HttpProxyIdleClients;
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ServerBufferStats, ServerForbiddenSnapshot, ServerForbiddenStats,
+    ServerForwardConnectionSnapshot, ServerForwardConnectionStats, ServerPerTaskStats, ServerStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -30,11 +32,19 @@ pub(crate) struct HttpProxyServerStats {
 
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_connect: ServerPerTaskStats,
+    pub task_http_connect_udp: ServerPerTaskStats,
     pub task_http_forward: ServerPerTaskStats,
+    pub forward_connection: ServerForwardConnectionStats,
     pub task_ftp_over_http: ServerPerTaskStats,
+    task_upgraded: AtomicU64,
+    task_deadline_exceeded: AtomicU64,
+    pub(crate) buffer: Arc<ServerBufferStats>,
+    pub(crate) idle_clients: Arc<HttpProxyIdleClients>,
 
     pub io_http: TcpIoStats,
     pub io_connect: TcpIoStats,
+    pub io_connect_udp: TcpIoStats,
+    pub io_connect_splice: TcpIoStats,
     pub io_untrusted: TcpIoStats,
 }
 
@@ -49,10 +59,18 @@ impl HttpProxyServerStats {
             forbidden: Default::default(),
             task_http_untrusted: Default::default(),
             task_http_connect: Default::default(),
+            task_http_connect_udp: Default::default(),
             task_http_forward: Default::default(),
+            forward_connection: Default::default(),
             task_ftp_over_http: Default::default(),
+            task_upgraded: AtomicU64::new(0),
+            task_deadline_exceeded: AtomicU64::new(0),
+            buffer: Default::default(),
+            idle_clients: Arc::new(HttpProxyIdleClients::default()),
             io_http: Default::default(),
             io_connect: Default::default(),
+            io_connect_udp: Default::default(),
+            io_connect_splice: Default::default(),
             io_untrusted: Default::default(),
         }
     }
@@ -106,6 +124,7 @@ impl ServerStats for HttpProxyServerStats {
     fn get_task_total(&self) -> u64 {
         // untrusted stats is not counted in
         self.task_http_connect.get_task_total()
+            + self.task_http_connect_udp.get_task_total()
             + self.task_http_forward.get_task_total()
             + self.task_ftp_over_http.get_task_total()
     }
@@ -113,6 +132,7 @@ impl ServerStats for HttpProxyServerStats {
     fn get_alive_count(&self) -> i32 {
         // untrusted stats is not counted in
         self.task_http_connect.get_alive_count()
+            + self.task_http_connect_udp.get_alive_count()
             + self.task_http_forward.get_alive_count()
             + self.task_ftp_over_http.get_alive_count()
     }
@@ -120,7 +140,11 @@ impl ServerStats for HttpProxyServerStats {
     fn tcp_io_snapshot(&self) -> Option<TcpIoSnapshot> {
         // the untrusted read stats is collected as buffer stats,
         // which has been contained in io_http
-        Some(self.io_http.snapshot() + self.io_connect.snapshot())
+        Some(self.io_http.snapshot() + self.io_connect.snapshot() + self.io_connect_udp.snapshot())
+    }
+
+    fn tcp_splice_io_snapshot(&self) -> Option<TcpIoSnapshot> {
+        Some(self.io_connect_splice.snapshot())
     }
 
     #[inline]
@@ -128,6 +152,34 @@ impl ServerStats for HttpProxyServerStats {
         self.forbidden.snapshot()
     }
 
+    fn get_buffer_memory(&self) -> Option<usize> {
+        Some(self.buffer.get_used())
+    }
+
+    fn add_upgraded_task(&self) {
+        self.task_upgraded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get_upgraded_task_total(&self) -> Option<u64> {
+        Some(self.task_upgraded.load(Ordering::Relaxed))
+    }
+
+    fn add_deadline_exceeded_task(&self) {
+        self.task_deadline_exceeded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn get_deadline_exceeded_task_total(&self) -> Option<u64> {
+        Some(self.task_deadline_exceeded.load(Ordering::Relaxed))
+    }
+
+    fn forward_connection_snapshot(&self) -> Option<ServerForwardConnectionSnapshot> {
+        Some(self.forward_connection.snapshot())
+    }
+
+    fn get_idle_client_count(&self) -> Option<usize> {
+        Some(self.idle_clients.count())
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
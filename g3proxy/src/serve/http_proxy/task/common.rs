@@ -19,7 +19,8 @@ use g3_types::net::{OpensslClientConfig, UpstreamAddr};
 
 use super::{HttpProxyServerConfig, HttpProxyServerStats};
 use crate::escape::ArcEscaper;
-use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::ext_authz::ExtAuthzClient;
+use crate::module::http_forward::{H2ConnectionPool, HttpProxyClientResponse};
 use crate::module::http_header;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
 use crate::serve::{ServerIdleChecker, ServerQuitPolicy, ServerTaskNotes};
@@ -33,6 +34,9 @@ pub(crate) struct CommonTaskContext {
     pub(crate) escaper: ArcEscaper,
     pub(crate) cc_info: ClientConnectionInfo,
     pub(crate) tls_client_config: Arc<OpensslClientConfig>,
+    pub(crate) tls_client_http11_config: Option<Arc<OpensslClientConfig>>,
+    pub(crate) h2_connection_pool: Option<Arc<H2ConnectionPool>>,
+    pub(crate) ext_authz_client: Option<Arc<ExtAuthzClient>>,
     pub(crate) task_logger: Option<Logger>,
 
     pub(crate) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
@@ -44,6 +48,11 @@ impl CommonTaskContext {
         self.cc_info.client_addr()
     }
 
+    #[inline]
+    pub(crate) fn server_addr(&self) -> SocketAddr {
+        self.cc_info.server_addr()
+    }
+
     pub(crate) fn idle_checker(&self, task_notes: &ServerTaskNotes) -> ServerIdleChecker {
         ServerIdleChecker::new(
             self.idle_wheel.clone(),
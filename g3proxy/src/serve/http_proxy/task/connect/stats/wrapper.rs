@@ -33,6 +33,7 @@ pub(crate) struct TcpConnectTaskCltWrapperStats {
     server: Arc<HttpProxyServerStats>,
     task: Arc<TcpStreamTaskStats>,
     others: Vec<ArcTcpConnectTaskCltStatsWrapper>,
+    splice: bool,
 }
 
 impl TcpConnectTaskCltWrapperStats {
@@ -41,9 +42,16 @@ impl TcpConnectTaskCltWrapperStats {
             server: Arc::clone(server),
             task: Arc::clone(task),
             others: Vec::with_capacity(2),
+            splice: false,
         }
     }
 
+    /// The bytes will also be counted as splice bytes in server stats
+    #[cfg(target_os = "linux")]
+    pub(crate) fn set_splice(&mut self) {
+        self.splice = true;
+    }
+
     pub(crate) fn push_user_io_stats(&mut self, all: Vec<Arc<UserTrafficStats>>) {
         for s in all {
             self.others.push(s);
@@ -56,6 +64,9 @@ impl LimitedReaderStats for TcpConnectTaskCltWrapperStats {
         let size = size as u64;
         self.task.clt.read.add_bytes(size);
         self.server.io_connect.add_in_bytes(size);
+        if self.splice {
+            self.server.io_connect_splice.add_in_bytes(size);
+        }
         self.others.iter().for_each(|s| s.add_read_bytes(size));
     }
 }
@@ -65,6 +76,9 @@ impl LimitedWriterStats for TcpConnectTaskCltWrapperStats {
         let size = size as u64;
         self.task.clt.write.add_bytes(size);
         self.server.io_connect.add_out_bytes(size);
+        if self.splice {
+            self.server.io_connect_splice.add_out_bytes(size);
+        }
         self.others.iter().for_each(|s| s.add_write_bytes(size));
     }
 }
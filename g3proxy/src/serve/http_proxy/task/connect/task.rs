@@ -3,15 +3,23 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+#[cfg(target_os = "linux")]
+use std::any::{Any, TypeId};
 use std::borrow::Cow;
 use std::sync::Arc;
 use std::time::Duration;
 
 use http::Version;
 use tokio::io::{AsyncRead, AsyncWrite};
+#[cfg(target_os = "linux")]
+use tokio::net::TcpStream;
+#[cfg(target_os = "linux")]
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
+#[cfg(target_os = "linux")]
+use g3_io_ext::SpliceCopy;
 use g3_io_ext::{IdleInterval, LimitedReader, LimitedWriter, StreamCopyConfig};
 use g3_types::acl::AclAction;
 use g3_types::net::{ProxyRequestType, UpstreamAddr};
@@ -27,21 +35,29 @@ use crate::module::http_forward::HttpProxyClientResponse;
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TcpConnection,
 };
+#[cfg(target_os = "linux")]
+use crate::module::tcp_connect::{TcpConnectRemoteWrapperStats, TcpSpliceConnection};
 use crate::serve::{
-    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
-    ServerTaskStage,
+    ServerBufferGuard, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
+    ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct HttpProxyConnectTask {
     ctx: Arc<CommonTaskContext>,
     upstream: UpstreamAddr,
     stream_ups: Option<TcpConnection>,
+    #[cfg(target_os = "linux")]
+    splice_client: bool,
+    #[cfg(target_os = "linux")]
+    splice_ups: Option<TcpSpliceConnection>,
     back_to_http: bool,
     task_notes: ServerTaskNotes,
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
     audit_ctx: AuditContext,
     http_version: Version,
+    copy_config: StreamCopyConfig,
+    buffer_guard: Option<ServerBufferGuard>,
     started: bool,
 }
 
@@ -65,12 +81,18 @@ impl HttpProxyConnectTask {
             ctx: Arc::clone(ctx),
             upstream: req.upstream.clone(),
             stream_ups: None,
+            #[cfg(target_os = "linux")]
+            splice_client: false,
+            #[cfg(target_os = "linux")]
+            splice_ups: None,
             back_to_http: false,
             task_notes,
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(TcpStreamTaskStats::default()),
             audit_ctx,
             http_version: req.inner.version,
+            copy_config: ctx.server_config.tcp_copy,
+            buffer_guard: None,
             started: false,
         }
     }
@@ -145,10 +167,17 @@ impl HttpProxyConnectTask {
         }
     }
 
-    pub(crate) async fn connect_to_upstream<W>(&mut self, clt_w: &mut W)
+    pub(crate) async fn connect_to_upstream<CDR, CDW>(&mut self, clt_w: &mut HttpClientWriter<CDW>)
     where
-        W: AsyncWrite + Unpin,
+        CDR: 'static,
+        CDW: AsyncWrite + Unpin + 'static,
     {
+        #[cfg(target_os = "linux")]
+        {
+            self.splice_client = TypeId::of::<CDR>() == TypeId::of::<OwnedReadHalf>()
+                && TypeId::of::<CDW>() == TypeId::of::<OwnedWriteHalf>();
+        }
+
         self.pre_start();
         match self.run_connect(clt_w).await {
             Ok(()) => {
@@ -273,6 +302,13 @@ impl HttpProxyConnectTask {
                 ));
             }
 
+            if user_ctx.check_traffic_quota().is_err() {
+                self.reply_too_many_requests(clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
@@ -319,6 +355,34 @@ impl HttpProxyConnectTask {
         let task_conf = TcpConnectTaskConf {
             upstream: &self.upstream,
         };
+
+        #[cfg(target_os = "linux")]
+        if self.splice_allowed() {
+            match self
+                .ctx
+                .escaper
+                .tcp_setup_splice_connection(
+                    &task_conf,
+                    &mut self.tcp_notes,
+                    &self.task_notes,
+                    self.task_stats.clone(),
+                    &mut self.audit_ctx,
+                )
+                .await
+            {
+                Ok(connection) => {
+                    self.task_notes.stage = ServerTaskStage::Connected;
+                    self.splice_ups = Some(connection);
+                    return Ok(());
+                }
+                Err(TcpConnectError::MethodUnavailable) => {}
+                Err(e) => {
+                    self.reply_connect_err(&e, clt_w).await;
+                    return Err(e.into());
+                }
+            }
+        }
+
         match self
             .ctx
             .escaper
@@ -343,6 +407,30 @@ impl HttpProxyConnectTask {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    fn splice_allowed(&self) -> bool {
+        if !self.splice_client
+            || !self.ctx.server_config.tcp_splice
+            || self.audit_ctx.handle().is_some()
+            || self.ctx.server_config.tcp_sock_speed_limit.shift_millis > 0
+        {
+            return false;
+        }
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user = user_ctx.user();
+            if user_ctx.user_config().tcp_sock_speed_limit.shift_millis > 0
+                || user.tcp_all_upload_speed_limit().is_some()
+                || user.tcp_all_download_speed_limit().is_some()
+                || user.traffic_quota().is_some()
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
     pub(crate) fn back_to_http(&self) -> bool {
         self.back_to_http
     }
@@ -350,6 +438,12 @@ impl HttpProxyConnectTask {
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_connect.add_task();
         self.ctx.server_stats.task_http_connect.inc_alive_task();
+        let (copy_config, buffer_guard) = self.ctx.server_stats.buffer.reserve(
+            self.ctx.server_config.tcp_copy,
+            self.ctx.server_config.tcp_copy_buffer_budget,
+        );
+        self.copy_config = copy_config;
+        self.buffer_guard = Some(buffer_guard);
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -394,6 +488,12 @@ impl HttpProxyConnectTask {
                 client_wr_bytes: self.task_stats.clt.write.get_bytes(),
                 remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
                 remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+                client_cert: self
+                    .ctx
+                    .server_config
+                    .log_client_cert
+                    .then(|| self.audit_ctx.client_cert().map(|c| c.as_ref()))
+                    .flatten(),
             })
     }
 
@@ -402,6 +502,20 @@ impl HttpProxyConnectTask {
         CDR: AsyncRead + Send + Sync + Unpin + 'static,
         CDW: AsyncWrite + Send + Sync + Unpin + 'static,
     {
+        #[cfg(target_os = "linux")]
+        if let Some(ups) = self.splice_ups.take() {
+            tokio::spawn(async move {
+                let e = match self.run_splice_connected(clt_r, clt_w, ups).await {
+                    Ok(_) => ServerTaskError::Finished,
+                    Err(e) => e,
+                };
+                if let Some(log_ctx) = self.get_log_context() {
+                    log_ctx.log(e);
+                }
+            });
+            return;
+        }
+
         if self.stream_ups.is_none() {
             return;
         }
@@ -454,6 +568,84 @@ impl HttpProxyConnectTask {
         self.relay(clt_r, clt_w, ups_r, ups_w).await
     }
 
+    #[cfg(target_os = "linux")]
+    async fn run_splice_connected<CDR, CDW>(
+        &mut self,
+        clt_r: CDR,
+        mut clt_w: HttpClientWriter<CDW>,
+        (ups, ups_stats): TcpSpliceConnection,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Sync + Unpin + 'static,
+        CDW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        self.task_notes.stage = ServerTaskStage::Replying;
+        self.reply_ok(&mut clt_w).await?;
+
+        self.task_notes.mark_relaying();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_ready.add_http_connect();
+            });
+        }
+
+        let (Some(clt_r), Some(clt_w)) = (
+            downcast::<CDR, OwnedReadHalf>(clt_r),
+            downcast::<CDW, OwnedWriteHalf>(clt_w.into_inner()),
+        ) else {
+            return Err(ServerTaskError::InternalServerError(
+                "the client connection is not a plain tcp stream",
+            ));
+        };
+        let clt = clt_r.reunite(clt_w).map_err(|_| {
+            ServerTaskError::InternalServerError("failed to reunite client tcp stream")
+        })?;
+        self.splice_relay(&clt, &ups, ups_stats).await
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn splice_relay(
+        &mut self,
+        clt: &TcpStream,
+        ups: &TcpStream,
+        ups_stats: Arc<TcpConnectRemoteWrapperStats>,
+    ) -> ServerTaskResult<()> {
+        let mut wrapper_stats =
+            TcpConnectTaskCltWrapperStats::new(&self.ctx.server_stats, &self.task_stats);
+        wrapper_stats.set_splice();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            wrapper_stats.push_user_io_stats(user_ctx.fetch_traffic_stats(
+                self.ctx.server_config.name(),
+                self.ctx.server_stats.share_extra_tags(),
+            ));
+        }
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let copy_config = self.copy_config();
+        let clt_to_ups = SpliceCopy::new(
+            clt,
+            ups,
+            &copy_config,
+            wrapper_stats.clone(),
+            ups_stats.clone(),
+        )
+        .map_err(|_| ServerTaskError::InternalServerError("failed to create splice pipe"))?;
+        let ups_to_clt = SpliceCopy::new(ups, clt, &copy_config, ups_stats, wrapper_stats)
+            .map_err(|_| ServerTaskError::InternalServerError("failed to create splice pipe"))?;
+        if let Some(guard) = &mut self.buffer_guard {
+            // no userspace buffer will be allocated
+            guard.recharge(clt_to_ups.pipe_size() + ups_to_clt.pipe_size());
+        }
+
+        self.transit_splice(clt_to_ups, ups_to_clt).await
+    }
+
     async fn relay<CDR, CDW, UR, UW>(
         &mut self,
         clt_r: CDR,
@@ -483,7 +675,7 @@ impl HttpProxyConnectTask {
                 .unwrap_or_else(|| audit_handle.do_task_audit());
 
             if audit_task {
-                let ctx = StreamInspectContext::new(
+                let mut ctx = StreamInspectContext::new(
                     audit_handle.clone(),
                     self.ctx.server_config.clone(),
                     self.ctx.server_stats.clone(),
@@ -492,6 +684,7 @@ impl HttpProxyConnectTask {
                     &self.task_notes,
                     &self.tcp_notes,
                 );
+                ctx.set_client_cert(self.audit_ctx.client_cert().cloned());
                 return crate::inspect::stream::transit_with_inspection(
                     clt_r,
                     clt_w,
@@ -556,6 +749,10 @@ impl HttpProxyConnectTask {
             if let Some(limiter) = user.tcp_all_download_speed_limit() {
                 clt_w.add_global_limiter(limiter.clone());
             }
+            if let Some(quota) = user.traffic_quota() {
+                clt_r.add_global_limiter(quota.clone());
+                clt_w.add_global_limiter(quota.clone());
+            }
         }
 
         (clt_r, clt_w)
@@ -564,7 +761,7 @@ impl HttpProxyConnectTask {
 
 impl StreamTransitTask for HttpProxyConnectTask {
     fn copy_config(&self) -> StreamCopyConfig {
-        self.ctx.server_config.tcp_copy
+        self.copy_config
     }
 
     fn idle_check_interval(&self) -> IdleInterval {
@@ -605,3 +802,11 @@ impl StreamTransitTask for HttpProxyConnectTask {
         self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
 }
+
+#[cfg(target_os = "linux")]
+fn downcast<T: 'static, U: 'static>(v: T) -> Option<U> {
+    let mut v = Some(v);
+    (&mut v as &mut dyn Any)
+        .downcast_mut::<Option<U>>()
+        .and_then(Option::take)
+}
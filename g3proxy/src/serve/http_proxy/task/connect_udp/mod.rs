@@ -0,0 +1,12 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use super::{CommonTaskContext, HttpProxyServerStats, protocol};
+
+mod task;
+pub(super) use task::HttpProxyConnectUdpTask;
+
+mod stats;
+use stats::{ConnectUdpTaskCltWrapperStats, ConnectUdpTaskStats};
@@ -0,0 +1,93 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use g3_daemon::stat::task::UdpConnectConnectionStats;
+use g3_io_ext::{LimitedReaderStats, LimitedWriterStats};
+
+use super::HttpProxyServerStats;
+use crate::auth::UserTrafficStats;
+use crate::module::udp_connect::UdpConnectTaskRemoteStats;
+
+/// The client side stats are counted by UDP payload
+#[derive(Default)]
+pub(crate) struct ConnectUdpTaskStats {
+    pub(crate) clt: UdpConnectConnectionStats,
+    pub(crate) ups: UdpConnectConnectionStats,
+}
+
+impl UdpConnectTaskRemoteStats for ConnectUdpTaskStats {
+    fn add_recv_bytes(&self, size: u64) {
+        self.ups.recv.add_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.ups.recv.add_packets(n);
+    }
+
+    fn add_send_bytes(&self, size: u64) {
+        self.ups.send.add_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.ups.send.add_packets(n);
+    }
+}
+
+trait ConnectUdpTaskCltStatsWrapper {
+    fn add_read_bytes(&self, size: u64);
+    fn add_write_bytes(&self, size: u64);
+}
+
+type ArcConnectUdpTaskCltStatsWrapper = Arc<dyn ConnectUdpTaskCltStatsWrapper + Send + Sync>;
+
+impl ConnectUdpTaskCltStatsWrapper for UserTrafficStats {
+    fn add_read_bytes(&self, size: u64) {
+        self.io.http_connect_udp.add_in_bytes(size);
+    }
+
+    fn add_write_bytes(&self, size: u64) {
+        self.io.http_connect_udp.add_out_bytes(size);
+    }
+}
+
+/// The client side stream stats, which are counted by capsule stream bytes
+#[derive(Clone)]
+pub(crate) struct ConnectUdpTaskCltWrapperStats {
+    server: Arc<HttpProxyServerStats>,
+    others: Vec<ArcConnectUdpTaskCltStatsWrapper>,
+}
+
+impl ConnectUdpTaskCltWrapperStats {
+    pub(crate) fn new(server: &Arc<HttpProxyServerStats>) -> Self {
+        ConnectUdpTaskCltWrapperStats {
+            server: Arc::clone(server),
+            others: Vec::with_capacity(2),
+        }
+    }
+
+    pub(crate) fn push_user_io_stats(&mut self, all: Vec<Arc<UserTrafficStats>>) {
+        for s in all {
+            self.others.push(s);
+        }
+    }
+}
+
+impl LimitedReaderStats for ConnectUdpTaskCltWrapperStats {
+    fn add_read_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.io_connect_udp.add_in_bytes(size);
+        self.others.iter().for_each(|s| s.add_read_bytes(size));
+    }
+}
+
+impl LimitedWriterStats for ConnectUdpTaskCltWrapperStats {
+    fn add_write_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.io_connect_udp.add_out_bytes(size);
+        self.others.iter().for_each(|s| s.add_write_bytes(size));
+    }
+}
@@ -0,0 +1,523 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::future::poll_fn;
+use std::sync::Arc;
+
+use bytes::BytesMut;
+use http::{StatusCode, Version};
+use slog::Logger;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite};
+
+use g3_http::capsule::HttpCapsule;
+use g3_io_ext::{LimitedWriteExt, UdpCopyRemoteError, UdpCopyRemoteRecv, UdpCopyRemoteSend};
+use g3_types::acl::AclAction;
+use g3_types::net::{ProxyRequestType, UpstreamAddr};
+
+use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest};
+use super::{CommonTaskContext, ConnectUdpTaskCltWrapperStats, ConnectUdpTaskStats};
+use crate::config::server::ServerConfig;
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{
+    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
+    ServerTaskStage, upgraded_task_max_idle_count,
+};
+
+const MAX_UDP_PAYLOAD_SIZE: usize = 65535;
+// the context id and the udp payload
+const MAX_DATAGRAM_CAPSULE_SIZE: usize = MAX_UDP_PAYLOAD_SIZE + 8;
+
+type UdpConnection = (
+    Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+    Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+    Option<Logger>,
+);
+
+pub(crate) struct HttpProxyConnectUdpTask {
+    ctx: Arc<CommonTaskContext>,
+    upstream: UpstreamAddr,
+    ups_conn: Option<UdpConnection>,
+    task_notes: ServerTaskNotes,
+    udp_notes: UdpConnectTaskNotes,
+    task_stats: Arc<ConnectUdpTaskStats>,
+    http_version: Version,
+    max_idle_count: usize,
+    started: bool,
+}
+
+impl Drop for HttpProxyConnectUdpTask {
+    fn drop(&mut self) {
+        if self.started {
+            self.post_stop();
+            self.started = false;
+        }
+    }
+}
+
+impl HttpProxyConnectUdpTask {
+    pub(crate) fn new(
+        ctx: &Arc<CommonTaskContext>,
+        req: &HttpProxyRequest<impl AsyncRead>,
+        task_notes: ServerTaskNotes,
+    ) -> Self {
+        let max_idle_count = task_notes
+            .user_ctx()
+            .and_then(|c| c.user().task_max_idle_count())
+            .unwrap_or(ctx.server_config.task_idle_max_count);
+        HttpProxyConnectUdpTask {
+            ctx: Arc::clone(ctx),
+            upstream: req.upstream.clone(),
+            ups_conn: None,
+            task_notes,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_stats: Arc::new(ConnectUdpTaskStats::default()),
+            http_version: req.inner.version,
+            max_idle_count,
+            started: false,
+        }
+    }
+
+    fn get_log_context(&self) -> Option<TaskLogForUdpConnect<'_>> {
+        self.ctx
+            .task_logger
+            .as_ref()
+            .map(|logger| TaskLogForUdpConnect {
+                logger,
+                task_notes: &self.task_notes,
+                tcp_server_addr: self.ctx.cc_info.server_addr(),
+                tcp_client_addr: self.ctx.client_addr(),
+                udp_listen_addr: None,
+                udp_client_addr: None,
+                upstream: Some(&self.upstream),
+                udp_notes: &self.udp_notes,
+                client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+                client_rd_packets: self.task_stats.clt.recv.get_packets(),
+                client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+                client_wr_packets: self.task_stats.clt.send.get_packets(),
+                remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+                remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+                remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+                remote_wr_packets: self.task_stats.ups.send.get_packets(),
+            })
+    }
+
+    fn pre_start(&mut self) {
+        self.ctx.server_stats.task_http_connect_udp.add_task();
+        self.ctx.server_stats.task_http_connect_udp.inc_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_total.add_http_connect_udp();
+                s.req_alive.add_http_connect_udp();
+            });
+        }
+
+        if self.ctx.server_config.flush_task_log_on_created
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_created();
+        }
+
+        self.started = true;
+    }
+
+    fn post_stop(&mut self) {
+        self.ctx.server_stats.task_http_connect_udp.dec_alive_task();
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_alive.del_http_connect_udp();
+            });
+
+            if let Some(user_req_alive_permit) = self.task_notes.user_req_alive_permit.take() {
+                drop(user_req_alive_permit);
+            }
+        }
+    }
+
+    async fn reply_error<W>(&self, rsp: HttpProxyClientResponse, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        // no custom header is set
+        let _ = rsp.reply_err_to_request(clt_w).await;
+    }
+
+    async fn reply_upgraded<W>(&self, clt_w: &mut W) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut rsp = HttpProxyClientResponse::from_standard(
+            StatusCode::SWITCHING_PROTOCOLS,
+            self.http_version,
+            false,
+        );
+        rsp.add_extra_header("Connection: Upgrade\r\n".to_string());
+        rsp.add_extra_header("Upgrade: connect-udp\r\n".to_string());
+        rsp.add_extra_header("Capsule-Protocol: ?1\r\n".to_string());
+        rsp.reply_ok_to_connect(clt_w)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)
+    }
+
+    fn check_acl_action(action: AclAction) -> bool {
+        match action {
+            AclAction::Permit => false,
+            AclAction::PermitAndLog => {
+                // TODO log permit
+                false
+            }
+            AclAction::Forbid => true,
+            AclAction::ForbidAndLog => {
+                // TODO log forbid
+                true
+            }
+        }
+    }
+
+    /// Setup the remote udp socket, the error response will be sent to the client if failed
+    pub(crate) async fn setup_upstream<W>(&mut self, clt_w: &mut W)
+    where
+        W: AsyncWrite + Unpin,
+    {
+        self.pre_start();
+        if let Err(e) = self.run_setup(clt_w).await
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log(e);
+        }
+    }
+
+    async fn run_setup<W>(&mut self, clt_w: &mut W) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user_ctx = user_ctx.clone();
+
+            if user_ctx.check_rate_limit().is_err() {
+                let rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
+                self.reply_error(rsp, clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::RateLimited,
+                ));
+            }
+
+            if user_ctx.check_traffic_quota().is_err() {
+                let rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
+                self.reply_error(rsp, clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
+            match user_ctx.acquire_request_semaphore() {
+                Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
+                Err(_) => {
+                    let rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
+                    self.reply_error(rsp, clt_w).await;
+                    return Err(ServerTaskError::ForbiddenByRule(
+                        ServerTaskForbiddenError::FullyLoaded,
+                    ));
+                }
+            }
+
+            let action = user_ctx.check_proxy_request(ProxyRequestType::HttpConnectUdp);
+            if Self::check_acl_action(action) {
+                let rsp = HttpProxyClientResponse::method_not_allowed(self.http_version);
+                self.reply_error(rsp, clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::ProtoBanned,
+                ));
+            }
+
+            let action = user_ctx.check_upstream(&self.upstream);
+            if Self::check_acl_action(action) {
+                let rsp = HttpProxyClientResponse::forbidden(self.http_version);
+                self.reply_error(rsp, clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::DestDenied,
+                ));
+            }
+        }
+
+        // server level dst host/port acl rules
+        let action = self.ctx.check_upstream(&self.upstream);
+        if Self::check_acl_action(action) {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                // also add to user level forbidden stats
+                user_ctx.add_dest_denied();
+            }
+
+            let rsp = HttpProxyClientResponse::forbidden(self.http_version);
+            self.reply_error(rsp, clt_w).await;
+            return Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::DestDenied,
+            ));
+        }
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        match self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await
+        {
+            Ok(connection) => {
+                self.task_notes.stage = ServerTaskStage::Connected;
+                self.ups_conn = Some(connection);
+                Ok(())
+            }
+            Err(e) => {
+                let e = ServerTaskError::from(e);
+                let rsp = HttpProxyClientResponse::from_task_err(&e, self.http_version, true)
+                    .unwrap_or_else(|| HttpProxyClientResponse::bad_gateway(self.http_version));
+                self.reply_error(rsp, clt_w).await;
+                Err(e)
+            }
+        }
+    }
+
+    pub(crate) fn into_running<CDR, CDW>(
+        mut self,
+        clt_r: HttpClientReader<CDR>,
+        clt_w: HttpClientWriter<CDW>,
+    ) where
+        CDR: AsyncRead + Send + Sync + Unpin + 'static,
+        CDW: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        let Some((ups_r, ups_w, escape_logger)) = self.ups_conn.take() else {
+            return;
+        };
+
+        tokio::spawn(async move {
+            let e = match self
+                .run_connected(clt_r, clt_w, ups_r, ups_w, escape_logger)
+                .await
+            {
+                Ok(_) => ServerTaskError::ClosedByClient,
+                Err(e) => e,
+            };
+            if let Some(log_ctx) = self.get_log_context() {
+                log_ctx.log(e);
+            }
+        });
+    }
+
+    async fn run_connected<CDR, CDW>(
+        &mut self,
+        mut clt_r: HttpClientReader<CDR>,
+        mut clt_w: HttpClientWriter<CDW>,
+        ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+        escape_logger: Option<Logger>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Unpin,
+        CDW: AsyncWrite + Unpin,
+    {
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        self.update_clt(&mut clt_r, &mut clt_w);
+
+        self.task_notes.stage = ServerTaskStage::Replying;
+        self.reply_upgraded(&mut clt_w).await?;
+        self.switch_to_upgraded();
+
+        self.task_notes.mark_relaying();
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.foreach_req_stats(|s| {
+                s.req_ready.add_http_connect_udp();
+            });
+        }
+        self.relay(clt_r, clt_w, ups_r, ups_w, escape_logger).await
+    }
+
+    /// Switch to the idle config for upgraded tasks, should be called after the 101 response is sent
+    fn switch_to_upgraded(&mut self) {
+        self.ctx.server_stats.add_upgraded_task();
+
+        if let Some(count) = upgraded_task_max_idle_count(
+            self.task_notes
+                .user_ctx()
+                .and_then(|c| c.user().upgraded_task_max_idle_count()),
+            self.ctx.server_config.upgraded_task_max_idle_count(),
+        ) {
+            self.max_idle_count = count;
+        }
+    }
+
+    fn update_clt<CDR, CDW>(
+        &self,
+        clt_r: &mut HttpClientReader<CDR>,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) where
+        CDR: AsyncRead + Unpin,
+        CDW: AsyncWrite + Unpin,
+    {
+        let mut wrapper_stats = ConnectUdpTaskCltWrapperStats::new(&self.ctx.server_stats);
+
+        let limit_config = if let Some(user_ctx) = self.task_notes.user_ctx() {
+            wrapper_stats.push_user_io_stats(user_ctx.fetch_traffic_stats(
+                self.ctx.server_config.name(),
+                self.ctx.server_stats.share_extra_tags(),
+            ));
+
+            user_ctx
+                .user_config()
+                .tcp_sock_speed_limit
+                .shrink_as_smaller(&self.ctx.server_config.tcp_sock_speed_limit)
+        } else {
+            self.ctx.server_config.tcp_sock_speed_limit
+        };
+
+        let wrapper_stats = Arc::new(wrapper_stats);
+        clt_r.reset_buffer_stats(wrapper_stats.clone());
+        clt_r.reset_direct_stats(wrapper_stats.clone());
+        clt_r.reset_local_limit(limit_config.shift_millis, limit_config.max_north);
+        clt_w.reset_stats(wrapper_stats);
+        clt_w.reset_local_limit(limit_config.shift_millis, limit_config.max_south);
+
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user = user_ctx.user();
+            if let Some(limiter) = user.tcp_all_upload_speed_limit() {
+                clt_r.add_global_limiter(limiter.clone());
+            }
+            if let Some(limiter) = user.tcp_all_download_speed_limit() {
+                clt_w.add_global_limiter(limiter.clone());
+            }
+            if let Some(quota) = user.traffic_quota() {
+                clt_r.add_global_limiter(quota.clone());
+                clt_w.add_global_limiter(quota.clone());
+            }
+        }
+    }
+
+    fn log_escape_error(&self, escape_logger: &Option<Logger>, e: &UdpCopyRemoteError) {
+        if let Some(logger) = escape_logger {
+            EscapeLogForUdpConnectSendTo {
+                task_id: &self.task_notes.id,
+                upstream: Some(&self.upstream),
+                udp_notes: &self.udp_notes,
+            }
+            .log(logger, e);
+        }
+    }
+
+    async fn relay<CDR, CDW>(
+        &mut self,
+        mut clt_r: HttpClientReader<CDR>,
+        mut clt_w: HttpClientWriter<CDW>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+        escape_logger: Option<Logger>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Unpin,
+        CDW: AsyncWrite + Unpin,
+    {
+        let mut clt_buf = BytesMut::with_capacity(MAX_DATAGRAM_CAPSULE_SIZE);
+        let ups_hdr_len = ups_r.max_hdr_len();
+        let mut ups_buf = vec![0u8; ups_hdr_len + MAX_UDP_PAYLOAD_SIZE];
+        let mut capsule_buf = Vec::with_capacity(MAX_DATAGRAM_CAPSULE_SIZE + 8);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let mut idle_count = 0;
+        let mut active = false;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = clt_r.read_buf(&mut clt_buf) => {
+                    let nr = r.map_err(ServerTaskError::ClientTcpReadFailed)?;
+                    if nr == 0 {
+                        return Ok(());
+                    }
+                    active = true;
+
+                    while let Some(capsule) = HttpCapsule::decode(&mut clt_buf, MAX_DATAGRAM_CAPSULE_SIZE)
+                        .map_err(|_| ServerTaskError::InvalidClientProtocol("invalid http capsule"))?
+                    {
+                        // drop unknown capsules and datagrams with unknown context id
+                        let Some(payload) = capsule.udp_payload() else {
+                            continue;
+                        };
+                        self.task_stats.clt.recv.add_bytes(payload.len() as u64);
+                        self.task_stats.clt.recv.add_packet();
+
+                        if let Err(e) = poll_fn(|cx| ups_w.poll_send_packet(cx, payload)).await {
+                            self.log_escape_error(&escape_logger, &e);
+                            return Err(e.into());
+                        }
+                    }
+                }
+                r = poll_fn(|cx| ups_r.poll_recv_packet(cx, &mut ups_buf)) => {
+                    let (off, nr) = match r {
+                        Ok(v) => v,
+                        Err(e) => {
+                            self.log_escape_error(&escape_logger, &e);
+                            return Err(e.into());
+                        }
+                    };
+                    active = true;
+
+                    let payload = &ups_buf[off..nr];
+                    capsule_buf.clear();
+                    HttpCapsule::encode_udp_datagram(&mut capsule_buf, payload);
+                    clt_w
+                        .write_all_flush(&capsule_buf)
+                        .await
+                        .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+                    self.task_stats.clt.send.add_bytes(payload.len() as u64);
+                    self.task_stats.clt.send.add_packet();
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if active {
+                        idle_count = 0;
+                        active = false;
+                    } else {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return Err(ServerTaskError::Idle(idle_interval.period(), idle_count));
+                        }
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
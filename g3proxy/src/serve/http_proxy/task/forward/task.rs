@@ -8,11 +8,19 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
+use bytes::Bytes;
 use futures_util::FutureExt;
-use http::header;
-use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
-
-use g3_http::client::HttpForwardRemoteResponse;
+use h2::client::SendRequest;
+use h2::{RecvStream, SendStream};
+use http::uri::{Authority, Scheme};
+use http::{HeaderMap, Request, Uri, Version, header};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use g3_h2::{
+    H2BodyEncodeTransfer, H2StreamBodyEncodeTransferError, H2StreamReader,
+    H2StreamToChunkedTransfer, H2StreamToChunkedTransferError,
+};
+use g3_http::client::{HttpForwardRemoteResponse, HttpTransparentH2Response};
 use g3_http::server::HttpProxyClientRequest;
 use g3_http::{HttpBodyReader, HttpBodyType};
 use g3_icap_client::reqmod::h1::{
@@ -23,8 +31,8 @@ use g3_icap_client::respmod::h1::{
     HttpResponseAdapter, RespmodAdaptationEndState, RespmodAdaptationRunState,
 };
 use g3_io_ext::{
-    GlobalLimitGroup, LimitedBufReadExt, LimitedReadExt, LimitedWriteExt, StreamCopy,
-    StreamCopyError,
+    GlobalLimitGroup, LimitedBufReadExt, LimitedReadExt, LimitedWriteExt, OptionalDeadline,
+    StreamCopy, StreamCopyConfig, StreamCopyError,
 };
 use g3_types::acl::AclAction;
 use g3_types::net::{HttpHeaderMap, ProxyRequestType, UpstreamAddr};
@@ -36,18 +44,20 @@ use super::{
 };
 use crate::audit::AuditContext;
 use crate::config::server::ServerConfig;
+use crate::config::server::http_proxy::HttpForwardUpstreamProtocol;
 use crate::log::task::http_forward::TaskLogForHttpForward;
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
-    HttpForwardTaskNotes, HttpProxyClientResponse,
+    H2ConnectResult, H2ConnectionKey, H2ConnectionPool, HttpForwardTaskNotes,
+    HttpProxyClientResponse, new_h2_connection,
 };
 use crate::module::http_header;
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
 use crate::serve::{
-    ServerIdleChecker, ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes,
-    ServerTaskResult, ServerTaskStage,
+    ServerBufferGuard, ServerIdleChecker, ServerStats, ServerTaskError, ServerTaskForbiddenError,
+    ServerTaskNotes, ServerTaskResult, ServerTaskStage,
 };
 
 pub(crate) struct HttpProxyForwardTask<'a> {
@@ -63,6 +73,12 @@ pub(crate) struct HttpProxyForwardTask<'a> {
     tcp_notes: TcpConnectTaskNotes,
     task_stats: Arc<HttpForwardTaskStats>,
     max_idle_count: usize,
+    deadline: OptionalDeadline,
+    copy_config: StreamCopyConfig,
+    _buffer_guard: Option<ServerBufferGuard>,
+    clt_body_finished: bool,
+    close_upstream: bool,
+    ext_authz_denied: bool,
     started: bool,
 }
 
@@ -98,6 +114,11 @@ impl<'a> HttpProxyForwardTask<'a> {
             .user_ctx()
             .and_then(|c| c.user().task_max_idle_count())
             .unwrap_or(ctx.server_config.task_idle_max_count);
+        let max_duration = task_notes
+            .user_ctx()
+            .and_then(|c| c.user().task_max_duration())
+            .or(ctx.server_config.task_max_duration);
+        let deadline = OptionalDeadline::new(task_notes.task_created_instant(), max_duration);
         HttpProxyForwardTask {
             ctx: Arc::clone(ctx),
             audit_ctx,
@@ -111,6 +132,12 @@ impl<'a> HttpProxyForwardTask<'a> {
             tcp_notes: TcpConnectTaskNotes::default(),
             task_stats: Arc::new(HttpForwardTaskStats::default()),
             max_idle_count,
+            deadline,
+            copy_config: ctx.server_config.tcp_copy,
+            _buffer_guard: None,
+            clt_body_finished: req.inner.body_type().is_none(),
+            close_upstream: false,
+            ext_authz_denied: false,
             started: false,
         }
     }
@@ -120,6 +147,20 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.should_close
     }
 
+    /// Check if the request body has been fully read in.
+    ///
+    /// The client reader can only be reused for the next request if this is true,
+    /// or the remaining body data will be mixed with the next request.
+    #[inline]
+    pub(crate) fn clt_body_finished(&self) -> bool {
+        self.clt_body_finished
+    }
+
+    /// The request is denied by the ext authz service, and it will be forbidden after the acl checks
+    pub(crate) fn set_ext_authz_denied(&mut self) {
+        self.ext_authz_denied = true;
+    }
+
     async fn reply_too_many_requests<W>(&mut self, clt_w: &mut W)
     where
         W: AsyncWrite + Unpin,
@@ -247,6 +288,12 @@ impl<'a> HttpProxyForwardTask<'a> {
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
             remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
             remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+            client_cert: self
+                .ctx
+                .server_config
+                .log_client_cert
+                .then(|| self.audit_ctx.client_cert().map(|c| c.as_ref()))
+                .flatten(),
         })
     }
 
@@ -264,14 +311,33 @@ impl<'a> HttpProxyForwardTask<'a> {
             Ok(()) => ServerTaskError::Finished,
             Err(e) => e,
         };
+        if matches!(e, ServerTaskError::DeadlineExceeded(_)) {
+            self.ctx.server_stats.add_deadline_exceeded_task();
+        }
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(&e);
+            if self.is_slow() {
+                log_ctx.log_slow(&e);
+            }
         }
     }
 
+    fn is_slow(&self) -> bool {
+        self.ctx
+            .server_config
+            .task_log_slow_threshold
+            .is_some_and(|threshold| self.task_notes.time_elapsed() >= threshold)
+    }
+
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_forward.add_task();
         self.ctx.server_stats.task_http_forward.inc_alive_task();
+        let (copy_config, buffer_guard) = self.ctx.server_stats.buffer.reserve(
+            self.ctx.server_config.tcp_copy,
+            self.ctx.server_config.tcp_copy_buffer_budget,
+        );
+        self.copy_config = copy_config;
+        self._buffer_guard = Some(buffer_guard);
 
         if let Some(user_ctx) = self.task_notes.user_ctx() {
             user_ctx.foreach_req_stats(|s| {
@@ -521,6 +587,10 @@ impl<'a> HttpProxyForwardTask<'a> {
                 if let Some(limiter) = user.tcp_all_download_speed_limit() {
                     clt_w.add_global_limiter(limiter.clone());
                 }
+                if let Some(quota) = user.traffic_quota() {
+                    br.add_global_limiter(quota.clone());
+                    clt_w.add_global_limiter(quota.clone());
+                }
             }
         } else {
             clt_w.reset_stats(clt_w_stats);
@@ -535,6 +605,9 @@ impl<'a> HttpProxyForwardTask<'a> {
                 if let Some(limiter) = user.tcp_all_download_speed_limit() {
                     clt_w.add_global_limiter(limiter.clone());
                 }
+                if let Some(quota) = user.traffic_quota() {
+                    clt_w.add_global_limiter(quota.clone());
+                }
             }
         }
     }
@@ -563,6 +636,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                 ));
             }
 
+            if user_ctx.check_traffic_quota().is_err() {
+                self.reply_too_many_requests(clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
@@ -618,6 +698,19 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
         }
 
+        if self.ext_authz_denied {
+            self.ctx.server_stats.forbidden.add_dest_denied();
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                // also add to user level forbidden stats
+                user_ctx.add_dest_denied();
+            }
+
+            self.reply_forbidden(clt_w).await;
+            return Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::ExtAuthzDenied,
+            ));
+        }
+
         // set client side socket options
         self.ctx
             .cc_info
@@ -628,6 +721,13 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
+        if !audit_task
+            && let Some(pool) = self.h2_connection_pool()
+            && let Some(r) = self.run_h2_forward(&pool, fwd_ctx, clt_r, clt_w).await
+        {
+            return r;
+        }
+
         fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
@@ -640,6 +740,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         {
             self.task_notes.stage = ServerTaskStage::Connected;
             self.http_notes.reused_connection = true;
+            self.ctx.server_stats.forward_connection.add_reused();
             fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
             self.http_notes.retry_new_connection = false;
             if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -686,6 +787,22 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
 
         let connection = self.get_new_connection(fwd_ctx, clt_w).await?;
+        self.run_with_new_connection(fwd_ctx, clt_r, clt_w, connection, audit_task)
+            .await
+    }
+
+    async fn run_with_new_connection<CDR, CDW>(
+        &mut self,
+        fwd_ctx: &mut BoxHttpForwardContext,
+        clt_r: &mut Option<HttpClientReader<CDR>>,
+        clt_w: &mut HttpClientWriter<CDW>,
+        connection: BoxHttpForwardConnection,
+        audit_task: bool,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        CDW: AsyncWrite + Send + Unpin,
+    {
         match self
             .run_with_connection(fwd_ctx, clt_r, clt_w, connection, audit_task)
             .await
@@ -717,8 +834,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                 let _ = connection.0.shutdown().await;
             }
             let _ = clt_w.shutdown().await;
-        } else if let Some(connection) = ups_s {
-            fwd_ctx.save_alive_connection(connection);
+        } else if let Some(mut connection) = ups_s {
+            if self.close_upstream {
+                self.ctx.server_stats.forward_connection.add_policy_closed();
+                let _ = connection.0.shutdown().await;
+            } else {
+                fwd_ctx.save_alive_connection(connection);
+            }
         }
     }
 
@@ -735,17 +857,7 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         match self.make_new_connection(fwd_ctx).await {
             Ok(mut connection) => {
-                self.task_notes.stage = ServerTaskStage::Connected;
-                fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
-
-                if self.ctx.server_config.flush_task_log_on_connected
-                    && let Some(log_ctx) = self.get_log_context()
-                {
-                    log_ctx.log_connected();
-                }
-
-                connection.0.prepare_new(&self.task_notes, &self.upstream);
-                self.mark_relaying();
+                self.mark_new_connected(fwd_ctx, &mut connection);
                 Ok(connection)
             }
             Err(e) => {
@@ -757,6 +869,25 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn mark_new_connected(
+        &mut self,
+        fwd_ctx: &BoxHttpForwardContext,
+        connection: &mut BoxHttpForwardConnection,
+    ) {
+        self.ctx.server_stats.forward_connection.add_new();
+        self.task_notes.stage = ServerTaskStage::Connected;
+        fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
+
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+
+        connection.0.prepare_new(&self.task_notes, &self.upstream);
+        self.mark_relaying();
+    }
+
     async fn make_new_connection(
         &self,
         fwd_ctx: &mut BoxHttpForwardContext,
@@ -769,6 +900,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                 .user_ctx()
                 .and_then(|ctx| ctx.user_site())
                 .and_then(|site| site.tls_client())
+                .or_else(|| {
+                    if self.upstream_protocol() == HttpForwardUpstreamProtocol::Http11 {
+                        self.ctx.tls_client_http11_config.as_deref()
+                    } else {
+                        None
+                    }
+                })
                 .unwrap_or(&self.ctx.tls_client_config);
 
             let task_conf = TlsConnectTaskConf {
@@ -791,6 +929,451 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn upstream_protocol(&self) -> HttpForwardUpstreamProtocol {
+        self.ctx
+            .server_config
+            .http_forward_upstream_protocol(self.upstream.host())
+    }
+
+    /// Get the h2 connection pool if this request can be sent over a shared h2 connection
+    fn h2_connection_pool(&self) -> Option<Arc<H2ConnectionPool>> {
+        if !self.is_https {
+            return None;
+        }
+        let pool = self.ctx.h2_connection_pool.as_ref()?;
+        if self.upstream_protocol() == HttpForwardUpstreamProtocol::Http11 {
+            return None;
+        }
+        if self.req.hop_by_hop_headers.contains_key(header::UPGRADE)
+            || self.req.end_to_end_headers.contains_key(header::EXPECT)
+        {
+            return None;
+        }
+        if let Some(host) = &self.req.host
+            && host.host() != self.upstream.host()
+        {
+            // the tls name is different
+            return None;
+        }
+        if self
+            .task_notes
+            .user_ctx()
+            .and_then(|ctx| ctx.user_site())
+            .and_then(|site| site.tls_client())
+            .is_some()
+        {
+            return None;
+        }
+        Some(pool.clone())
+    }
+
+    /// Forward the request over a shared h2 connection.
+    ///
+    /// `None` will be returned if the upstream doesn't support h2,
+    /// and the request should be forwarded in the HTTP/1.1 way,
+    /// unless h2 is forced for this upstream.
+    /// If h2 is not selected on the new TLS connection,
+    /// the request will be forwarded on it in the HTTP/1.1 way.
+    async fn run_h2_forward<CDR, CDW>(
+        &mut self,
+        pool: &H2ConnectionPool,
+        fwd_ctx: &mut BoxHttpForwardContext,
+        clt_r: &mut Option<HttpClientReader<CDR>>,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) -> Option<ServerTaskResult<()>>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        CDW: AsyncWrite + Send + Unpin,
+    {
+        let key = H2ConnectionKey::new(&self.upstream, self.ctx.escaper.name(), &self.task_notes);
+        let stream = match pool.acquire(&key) {
+            Some(stream) => {
+                self.http_notes.reused_connection = true;
+                self.ctx
+                    .server_stats
+                    .forward_connection
+                    .add_h2_multiplexed();
+                if let Some(user_ctx) = self.task_notes.user_ctx() {
+                    user_ctx.foreach_req_stats(|s| s.req_reuse.add_http_forward(self.is_https));
+                }
+                stream
+            }
+            None => {
+                let force_h2 = self.upstream_protocol() == HttpForwardUpstreamProtocol::Http2;
+                if pool.is_unsupported(&key) {
+                    if force_h2 {
+                        return Some(self.reply_h2_not_negotiated(clt_w).await);
+                    }
+                    return None;
+                }
+
+                self.task_notes.stage = ServerTaskStage::Connecting;
+                self.http_notes.reused_connection = false;
+                let task_conf = TlsConnectTaskConf {
+                    tcp: TcpConnectTaskConf {
+                        upstream: &self.upstream,
+                    },
+                    tls_config: &self.ctx.tls_client_config,
+                    tls_name: self.upstream.host(),
+                };
+                let mut tcp_notes = TcpConnectTaskNotes::default();
+                match new_h2_connection(
+                    &self.ctx.escaper,
+                    &task_conf,
+                    &mut tcp_notes,
+                    &self.task_notes,
+                    self.task_stats.clone(),
+                    &mut self.audit_ctx,
+                    self.ctx.server_config.rsp_hdr_max_size as u32,
+                )
+                .await
+                {
+                    Ok(H2ConnectResult::Http2(send_request, driver)) => {
+                        self.ctx.server_stats.forward_connection.add_h2_new();
+                        pool.insert(key, send_request, driver, tcp_notes)
+                    }
+                    Ok(H2ConnectResult::Http11(_)) if force_h2 => {
+                        self.tcp_notes = tcp_notes;
+                        pool.mark_unsupported(key);
+                        return Some(self.reply_h2_not_negotiated(clt_w).await);
+                    }
+                    Ok(H2ConnectResult::Http11(mut connection)) => {
+                        pool.mark_unsupported(key);
+                        fwd_ctx.prepare_connection(&self.upstream, true);
+                        fwd_ctx.adopt_https_connection(&tcp_notes);
+                        self.mark_new_connected(fwd_ctx, &mut connection);
+                        return Some(
+                            self.run_with_new_connection(fwd_ctx, clt_r, clt_w, connection, false)
+                                .await,
+                        );
+                    }
+                    Err(TcpConnectError::UpstreamTlsHandshakeFailed(_))
+                    | Err(TcpConnectError::UpstreamTlsHandshakeTimeout)
+                        if !force_h2 =>
+                    {
+                        // the TLS error will be reported by the following HTTP/1.1 connection
+                        pool.mark_unsupported(key);
+                        return None;
+                    }
+                    Err(e) => {
+                        self.tcp_notes = tcp_notes;
+                        self.should_close = true;
+                        self.reply_connect_err(&e, clt_w).await;
+                        return Some(Err(e.into()));
+                    }
+                }
+            }
+        };
+
+        self.task_notes.stage = ServerTaskStage::Connected;
+        self.tcp_notes.clone_from(stream.tcp_notes());
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+        self.mark_relaying();
+
+        let r = self
+            .run_with_h2_stream(stream.send_request(), clt_r, clt_w)
+            .await;
+        drop(stream);
+        match r {
+            Ok(()) => {
+                if self.should_close {
+                    let _ = clt_w.shutdown().await;
+                }
+                Some(Ok(()))
+            }
+            Err(e) => {
+                self.should_close = true;
+                if self.send_error_response {
+                    self.reply_task_err(&e, clt_w).await;
+                }
+                Some(Err(e))
+            }
+        }
+    }
+
+    async fn reply_h2_not_negotiated<CDW>(
+        &mut self,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) -> ServerTaskResult<()>
+    where
+        CDW: AsyncWrite + Send + Unpin,
+    {
+        let e = ServerTaskError::UpstreamNotNegotiated(format!(
+            "h2 is not selected by upstream {}",
+            self.upstream
+        ));
+        self.should_close = true;
+        self.reply_task_err(&e, clt_w).await;
+        Err(e)
+    }
+
+    fn build_h2_request(&self) -> ServerTaskResult<Request<()>> {
+        let mut headers = HeaderMap::from(&self.req.end_to_end_headers);
+        let authority = match headers.remove(header::HOST) {
+            Some(v) => Authority::try_from(v.as_bytes()),
+            None => Authority::try_from(self.upstream.to_string()),
+        }
+        .map_err(|_| ServerTaskError::InvalidClientProtocol("invalid host for h2 request"))?;
+        let path = self
+            .req
+            .uri
+            .path_and_query()
+            .map(|pa| pa.as_str())
+            .unwrap_or("/");
+        let uri = Uri::builder()
+            .scheme(Scheme::HTTPS)
+            .authority(authority)
+            .path_and_query(path)
+            .build()
+            .map_err(|_| ServerTaskError::InvalidClientProtocol("invalid uri for h2 request"))?;
+
+        let mut req = Request::new(());
+        *req.method_mut() = self.req.method.clone();
+        *req.uri_mut() = uri;
+        *req.version_mut() = Version::HTTP_2;
+        *req.headers_mut() = headers;
+        Ok(req)
+    }
+
+    async fn run_with_h2_stream<CDR, CDW>(
+        &mut self,
+        send_request: SendRequest<Bytes>,
+        clt_r: &mut Option<HttpClientReader<CDR>>,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        CDW: AsyncWrite + Send + Unpin,
+    {
+        let ups_req = self.build_h2_request()?;
+        let body_type = self.req.body_type();
+
+        let mut send_request = send_request
+            .ready()
+            .await
+            .map_err(|e| ServerTaskError::UpstreamAppError(anyhow!("h2 connection error: {e}")))?;
+        let (rsp_fut, mut send_stream) = send_request
+            .send_request(ups_req, body_type.is_none())
+            .map_err(|e| {
+                ServerTaskError::UpstreamAppError(anyhow!("h2 send request error: {e}"))
+            })?;
+        self.http_notes.mark_req_send_hdr();
+
+        match body_type {
+            Some(body_type) => {
+                let Some(clt_r) = clt_r else {
+                    return Err(ServerTaskError::InternalServerError(
+                        "http body is expected but no body reader supplied",
+                    ));
+                };
+
+                let mut clt_body_reader =
+                    HttpBodyReader::new(clt_r, body_type, self.ctx.server_config.body_line_max_len);
+                self.send_h2_request_body(&mut clt_body_reader, &mut send_stream)
+                    .await?;
+                self.clt_body_finished = true;
+                self.http_notes.mark_req_send_all();
+            }
+            None => self.http_notes.mark_req_no_body(),
+        }
+
+        let ups_rsp = match tokio::time::timeout(self.rsp_hdr_recv_timeout(), rsp_fut).await {
+            Ok(Ok(rsp)) => rsp,
+            Ok(Err(e)) => {
+                return Err(ServerTaskError::UpstreamAppError(anyhow!(
+                    "h2 recv response error: {e}"
+                )));
+            }
+            Err(_) => {
+                return Err(ServerTaskError::UpstreamAppTimeout(
+                    "timeout to receive response header",
+                ));
+            }
+        };
+        self.http_notes.mark_rsp_recv_hdr();
+
+        let (parts, mut recv_stream) = ups_rsp.into_parts();
+        let h2_rsp = HttpTransparentH2Response::parse(&parts)?;
+        let mut rsp_header =
+            HttpForwardRemoteResponse::from_h2(&h2_rsp, &self.req.method, self.req.keep_alive());
+        if self.should_close {
+            rsp_header.set_no_keep_alive();
+        }
+        if !rsp_header.keep_alive() {
+            self.should_close = true;
+        }
+        self.http_notes.origin_status = rsp_header.code;
+        self.http_notes.rsp_status = 0;
+        self.update_response_header(&mut rsp_header);
+
+        self.send_error_response = false;
+        match rsp_header.body_type(&self.req.method) {
+            Some(HttpBodyType::Chunked) => {
+                self.send_response_header(clt_w, &rsp_header).await?;
+                self.http_notes.rsp_status = rsp_header.code;
+                self.send_h2_response_body_chunked(clt_w, &mut recv_stream)
+                    .await
+            }
+            Some(body_type) => {
+                let mut buf = Vec::with_capacity(self.copy_config.buffer_size());
+                rsp_header.serialize_to(&mut buf);
+                self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
+                let mut ups_r = BufReader::with_capacity(
+                    self.copy_config.buffer_size(),
+                    H2StreamReader::new(recv_stream),
+                );
+                self.send_response_body(buf, clt_w, &mut ups_r, body_type)
+                    .await
+            }
+            None => {
+                self.send_response_header(clt_w, &rsp_header).await?;
+                self.http_notes.rsp_status = rsp_header.code;
+                self.http_notes.mark_rsp_no_body();
+                Ok(())
+            }
+        }
+    }
+
+    async fn send_h2_request_body<R>(
+        &mut self,
+        clt_body_reader: &mut HttpBodyReader<'_, R>,
+        send_stream: &mut SendStream<Bytes>,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let mut clt_to_ups =
+            H2BodyEncodeTransfer::new(clt_body_reader, send_stream, &self.copy_config);
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut clt_to_ups => {
+                    return match r {
+                        Ok(_) => {
+                            let (_, send_stream) = clt_to_ups.into_io();
+                            send_stream
+                                .send_data(Bytes::new(), true)
+                                .map_err(|e| ServerTaskError::UpstreamAppError(anyhow!("h2 send end of stream error: {e}")))
+                        }
+                        Err(H2StreamBodyEncodeTransferError::ReadError(e)) => Err(ServerTaskError::ClientTcpReadFailed(e)),
+                        Err(e) => Err(ServerTaskError::UpstreamAppError(anyhow!("h2 send request body error: {e}"))),
+                    };
+                }
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if clt_to_ups.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return if clt_to_ups.no_cached_data() {
+                                Err(ServerTaskError::ClientAppTimeout("idle while reading request body"))
+                            } else {
+                                Err(ServerTaskError::UpstreamAppTimeout("idle while sending request body"))
+                            };
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        clt_to_ups.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_h2_response_body_chunked<W>(
+        &mut self,
+        clt_w: &mut W,
+        recv_stream: &mut RecvStream,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut ups_to_clt =
+            H2StreamToChunkedTransfer::new(recv_stream, clt_w, self.copy_config.yield_size());
+
+        let mut idle_interval = self.ctx.idle_wheel.register();
+        let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut ups_to_clt => {
+                    return match r {
+                        Ok(_) => {
+                            self.http_notes.mark_rsp_recv_all();
+                            Ok(())
+                        }
+                        Err(H2StreamToChunkedTransferError::WriteError(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
+                        Err(e) => Err(ServerTaskError::UpstreamAppError(anyhow!("h2 recv response body error: {e}"))),
+                    };
+                }
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
+                _ = log_interval.tick() => {
+                    if let Some(log_ctx) = self.get_log_context() {
+                        log_ctx.log_periodic();
+                    }
+                }
+                n = idle_interval.tick() => {
+                    if ups_to_clt.is_idle() {
+                        idle_count += n;
+
+                        if idle_count >= self.max_idle_count {
+                            return if ups_to_clt.no_cached_data() {
+                                Err(ServerTaskError::UpstreamAppTimeout("idle while reading response body"))
+                            } else {
+                                Err(ServerTaskError::ClientAppTimeout("idle while sending response with body"))
+                            };
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        ups_to_clt.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx()
+                        && user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+
     fn mark_relaying(&mut self) {
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -829,7 +1412,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         {
             match reqmod
                 .h1_adapter(
-                    self.ctx.server_config.tcp_copy,
+                    self.copy_config,
                     self.ctx.server_config.body_line_max_len,
                     true,
                     self.ctx.idle_checker(&self.task_notes),
@@ -839,10 +1422,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                 Ok(mut adapter) => {
                     let mut adaptation_state =
                         ReqmodAdaptationRunState::new(self.task_notes.task_created_instant());
-                    adapter.set_client_addr(self.ctx.client_addr());
+                    adapter.set_client_addr(self.task_notes.client_addr());
                     if let Some(name) = self.task_notes.raw_user_name() {
                         adapter.set_client_username(name.clone());
                     }
+                    if let Some(cert) = self.audit_ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     let r = self
                         .run_with_adaptation(clt_r, clt_w, ups_c, adapter, &mut adaptation_state)
                         .await;
@@ -902,9 +1488,11 @@ impl<'a> HttpProxyForwardTask<'a> {
             .boxed();
 
         let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
 
         let clt_read_size = self.task_stats.clt.read.get_bytes();
         let mut rsp_header: Option<HttpForwardRemoteResponse> = None;
+        let mut interim_count = 0;
         loop {
             tokio::select! {
                 biased;
@@ -917,6 +1505,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                             match hdr.code {
                                 100 | 103 => {
                                     // CONTINUE | Early Hints
+                                    interim_count += 1;
+                                    self.check_interim_response_count(interim_count)?;
                                     self.send_response_header(clt_w, &hdr).await?;
                                 }
                                 _ => {
@@ -963,6 +1553,9 @@ impl<'a> HttpProxyForwardTask<'a> {
                         }
                     }
                 }
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
                 _ = log_interval.tick() => {
                     if let Some(log_ctx) = self.get_log_context() {
                         log_ctx.log_periodic();
@@ -975,7 +1568,9 @@ impl<'a> HttpProxyForwardTask<'a> {
         let mut close_remote = false;
         let mut rsp_header = match rsp_header {
             Some(header) => {
-                if !adaptation_state.clt_read_finished {
+                if adaptation_state.clt_read_finished {
+                    self.clt_body_finished = true;
+                } else {
                     // not all client data read in, drop the client connection
                     self.should_close = true;
                 }
@@ -988,6 +1583,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                 header
             }
             None => {
+                // the request has been fully transferred by the adaptation
+                self.clt_body_finished = true;
                 match tokio::time::timeout(
                     self.rsp_hdr_recv_timeout(),
                     self.recv_final_response_header(ups_r, clt_w),
@@ -1048,8 +1645,7 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         if let Some(mut recv_body) = rsp_recv_body {
             let mut body_reader = recv_body.body_reader();
-            let copy_to_clt =
-                StreamCopy::new(&mut body_reader, clt_w, &self.ctx.server_config.tcp_copy);
+            let copy_to_clt = StreamCopy::new(&mut body_reader, clt_w, &self.copy_config);
             copy_to_clt.await.map_err(|e| match e {
                 StreamCopyError::ReadFailed(e) => ServerTaskError::InternalAdapterError(anyhow!(
                     "read http error response from adapter failed: {e:?}"
@@ -1095,7 +1691,7 @@ impl<'a> HttpProxyForwardTask<'a> {
                         .await;
                 }
 
-                let mut fast_read_buf = vec![0u8; self.ctx.server_config.tcp_copy.buffer_size()];
+                let mut fast_read_buf = vec![0u8; self.copy_config.buffer_size()];
                 let nr = clt_body_reader
                     .read_all_now(&mut fast_read_buf)
                     .await
@@ -1109,6 +1705,7 @@ impl<'a> HttpProxyForwardTask<'a> {
 
                 fast_read_buf.truncate(nr);
                 if clt_body_reader.finished() {
+                    self.clt_body_finished = true;
                     return self
                         .run_with_all_body(fwd_ctx, fast_read_buf, clt_w, ups_c)
                         .await;
@@ -1326,19 +1923,16 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.http_notes.retry_new_connection = false;
 
         let mut clt_to_ups = match fast_read_buf {
-            Some(buf) => StreamCopy::with_data(
-                clt_body_reader,
-                ups_w,
-                &self.ctx.server_config.tcp_copy,
-                buf,
-            ),
-            None => StreamCopy::new(clt_body_reader, ups_w, &self.ctx.server_config.tcp_copy),
+            Some(buf) => StreamCopy::with_data(clt_body_reader, ups_w, &self.copy_config, buf),
+            None => StreamCopy::new(clt_body_reader, ups_w, &self.copy_config),
         };
 
         let mut rsp_header: Option<HttpForwardRemoteResponse> = None;
+        let mut interim_count = 0;
 
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
         let mut idle_count = 0;
         loop {
             tokio::select! {
@@ -1352,6 +1946,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                             match hdr.code {
                                 100 | 103 => {
                                     // CONTINUE | Early Hints
+                                    interim_count += 1;
+                                    self.check_interim_response_count(interim_count)?;
                                     self.send_response_header(clt_w, &hdr).await?;
                                 }
                                 _ => {
@@ -1379,9 +1975,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                         StreamCopyError::ReadFailed(e) => ServerTaskError::ClientTcpReadFailed(e),
                         StreamCopyError::WriteFailed(e) => ServerTaskError::UpstreamWriteFailed(e),
                     })?;
+                    self.clt_body_finished = true;
                     self.http_notes.mark_req_send_all();
                     break;
                 }
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
                 _ = log_interval.tick() => {
                     if let Some(log_ctx) = self.get_log_context() {
                         log_ctx.log_periodic();
@@ -1428,7 +2028,9 @@ impl<'a> HttpProxyForwardTask<'a> {
         let copy_done = clt_to_ups.finished();
         let mut rsp_header = match rsp_header {
             Some(header) => {
-                if !clt_body_reader.finished() {
+                if clt_body_reader.finished() {
+                    self.clt_body_finished = true;
+                } else {
                     // not all client data read in, drop the client connection
                     self.should_close = true;
                 }
@@ -1479,6 +2081,7 @@ impl<'a> HttpProxyForwardTask<'a> {
     where
         W: AsyncWrite + Unpin,
     {
+        let mut interim_count = 0;
         loop {
             let hdr = self.recv_response_header(ups_r).await?;
             match hdr.code {
@@ -1490,6 +2093,8 @@ impl<'a> HttpProxyForwardTask<'a> {
                 }
                 103 => {
                     // HTTP Early Hints
+                    interim_count += 1;
+                    self.check_interim_response_count(interim_count)?;
                     self.send_response_header(clt_w, &hdr).await?;
                 }
                 _ => return Ok(hdr),
@@ -1497,6 +2102,16 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn check_interim_response_count(&self, count: usize) -> ServerTaskResult<()> {
+        if count > self.ctx.server_config.rsp_interim_max_count {
+            Err(ServerTaskError::InvalidUpstreamProtocol(
+                "too many interim responses",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     async fn recv_response_header(
         &mut self,
         ups_r: &mut BoxHttpForwardReader,
@@ -1532,6 +2147,15 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
         self.http_notes.origin_status = rsp_header.code;
         self.http_notes.rsp_status = 0;
+        if self
+            .ctx
+            .server_config
+            .http_forward_upstream_close_on_status
+            .matches(rsp_header.code)
+        {
+            // do not reuse the connection to a possibly unhealthy upstream
+            self.close_upstream = true;
+        }
         self.update_response_header(rsp_header);
 
         if audit_task
@@ -1540,7 +2164,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         {
             match respmod
                 .h1_adapter(
-                    self.ctx.server_config.tcp_copy,
+                    self.copy_config,
                     self.ctx.server_config.body_line_max_len,
                     self.ctx.idle_checker(&self.task_notes),
                 )
@@ -1551,10 +2175,13 @@ impl<'a> HttpProxyForwardTask<'a> {
                         self.task_notes.task_created_instant(),
                         self.http_notes.dur_rsp_recv_hdr,
                     );
-                    adapter.set_client_addr(self.ctx.client_addr());
+                    adapter.set_client_addr(self.task_notes.client_addr());
                     if let Some(name) = self.task_notes.raw_user_name() {
                         adapter.set_client_username(name.clone());
                     }
+                    if let Some(cert) = self.audit_ctx.client_cert() {
+                        adapter.set_client_cert(cert.clone());
+                    }
                     adapter.set_respond_shared_headers(adaptation_respond_shared_headers);
                     let r = self
                         .send_response_with_adaptation(
@@ -1599,6 +2226,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         W: AsyncWrite + Send + Unpin,
     {
         let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
         let mut adaptation_fut = icap_adapter
             .xfer(adaptation_state, self.req, rsp_header, ups_r, clt_w)
             .boxed();
@@ -1606,6 +2234,9 @@ impl<'a> HttpProxyForwardTask<'a> {
             tokio::select! {
                 biased;
 
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
                 _ = log_interval.tick() => {
                     if let Some(log_ctx) = self.get_log_context() {
                         log_ctx.log_periodic();
@@ -1641,7 +2272,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         self.send_error_response = false;
 
         if let Some(body_type) = rsp_header.body_type(&self.req.method) {
-            let mut buf = Vec::with_capacity(self.ctx.server_config.tcp_copy.buffer_size());
+            let mut buf = Vec::with_capacity(self.copy_config.buffer_size());
             rsp_header.serialize_to(&mut buf);
             self.http_notes.rsp_status = rsp_header.code; // the following function must send rsp header out
             self.send_response_body(buf, clt_w, ups_r, body_type).await
@@ -1667,16 +2298,14 @@ impl<'a> HttpProxyForwardTask<'a> {
         let header_len = header.len() as u64;
         let mut body_reader =
             HttpBodyReader::new(ups_r, body_type, self.ctx.server_config.body_line_max_len);
+        body_reader.set_trailer_max_size(self.ctx.server_config.rsp_trailer_max_size);
 
-        let mut ups_to_clt = StreamCopy::with_data(
-            &mut body_reader,
-            clt_w,
-            &self.ctx.server_config.tcp_copy,
-            header,
-        );
+        let mut ups_to_clt =
+            StreamCopy::with_data(&mut body_reader, clt_w, &self.copy_config, header);
 
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut log_interval = self.ctx.get_log_interval();
+        let deadline = self.deadline;
         let mut idle_count = 0;
         loop {
             tokio::select! {
@@ -1698,6 +2327,9 @@ impl<'a> HttpProxyForwardTask<'a> {
                         Err(StreamCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
                     };
                 }
+                _ = deadline.wait() => {
+                    return Err(ServerTaskError::DeadlineExceeded(self.task_notes.time_elapsed()));
+                }
                 _ = log_interval.tick() => {
                     if let Some(log_ctx) = self.get_log_context() {
                         log_ctx.log_periodic();
@@ -1775,6 +2407,12 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
         }
 
+        if let Some(via) = &self.ctx.server_config.via_header
+            && via.response
+        {
+            via.append_to(&mut rsp.end_to_end_headers, rsp.version);
+        }
+
         if self.ctx.server_config.echo_chained_info {
             if let Some(addr) = self.tcp_notes.chained.target_addr {
                 http_header::set_upstream_addr(&mut rsp.hop_by_hop_headers, addr);
@@ -1784,6 +2422,14 @@ impl<'a> HttpProxyForwardTask<'a> {
                 http_header::set_outgoing_ip(&mut rsp.hop_by_hop_headers, addr);
             }
         }
+
+        self.ctx
+            .server_config
+            .http_rsp_header_rules
+            .apply(&mut rsp.end_to_end_headers);
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            user_ctx.apply_http_rsp_header_rules(&mut rsp.end_to_end_headers);
+        }
     }
 
     async fn send_response_header<W>(
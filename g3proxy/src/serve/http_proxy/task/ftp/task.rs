@@ -133,10 +133,20 @@ impl<'a> FtpOverHttpTask<'a> {
             Err(e) => e,
         };
         if let Some(log_ctx) = self.get_log_context() {
+            if self.is_slow() {
+                log_ctx.log_slow(&e);
+            }
             log_ctx.log(e);
         }
     }
 
+    fn is_slow(&self) -> bool {
+        self.ctx
+            .server_config
+            .task_log_slow_threshold
+            .is_some_and(|threshold| self.task_notes.time_elapsed() >= threshold)
+    }
+
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_ftp_over_http.add_task();
         self.ctx.server_stats.task_ftp_over_http.inc_alive_task();
@@ -342,6 +352,10 @@ impl<'a> FtpOverHttpTask<'a> {
             if let Some(limiter) = user.tcp_all_download_speed_limit() {
                 clt_w.add_global_limiter(limiter.clone());
             }
+            if let Some(quota) = user.traffic_quota() {
+                clt_r.add_global_limiter(quota.clone());
+                clt_w.add_global_limiter(quota.clone());
+            }
 
             let user_config = user_ctx.user_config();
             if user_config
@@ -391,6 +405,13 @@ impl<'a> FtpOverHttpTask<'a> {
                 ));
             }
 
+            if user_ctx.check_traffic_quota().is_err() {
+                self.reply_too_many_requests(clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
@@ -12,12 +12,14 @@ pub(super) use common::CommonTaskContext;
 mod protocol;
 
 mod connect;
+mod connect_udp;
 mod forward;
 mod ftp;
 mod pipeline;
 mod untrusted;
 
 use connect::HttpProxyConnectTask;
+use connect_udp::HttpProxyConnectUdpTask;
 use forward::HttpProxyForwardTask;
 use ftp::FtpOverHttpTask;
 pub(super) use pipeline::{
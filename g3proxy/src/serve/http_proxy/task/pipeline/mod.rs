@@ -4,8 +4,8 @@
  */
 
 use super::{
-    CommonTaskContext, FtpOverHttpTask, HttpProxyConnectTask, HttpProxyForwardTask,
-    HttpProxyServerStats, HttpProxyUntrustedTask, protocol,
+    CommonTaskContext, FtpOverHttpTask, HttpProxyConnectTask, HttpProxyConnectUdpTask,
+    HttpProxyForwardTask, HttpProxyServerStats, HttpProxyUntrustedTask, protocol,
 };
 
 mod reader;
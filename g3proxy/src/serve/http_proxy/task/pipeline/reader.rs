@@ -5,6 +5,7 @@
 
 use std::sync::Arc;
 
+use http::{StatusCode, Version};
 use log::trace;
 use tokio::io::AsyncRead;
 use tokio::sync::mpsc;
@@ -16,6 +17,24 @@ use super::{CommonTaskContext, HttpProxyCltWrapperStats, HttpProxyPipelineStats}
 use crate::module::http_forward::HttpProxyClientResponse;
 use crate::serve::ServerStats;
 
+/// Get the error response if the next request is received before the response of the previous one
+/// and pipelining is not allowed
+fn check_pipelined_request(
+    allow_pipelined: bool,
+    buffered: &[u8],
+    version: Version,
+) -> Option<HttpProxyClientResponse> {
+    if allow_pipelined || buffered.is_empty() {
+        None
+    } else {
+        Some(HttpProxyClientResponse::from_standard(
+            StatusCode::BAD_REQUEST,
+            version,
+            true,
+        ))
+    }
+}
+
 pub(crate) struct HttpProxyPipelineReaderTask<CDR> {
     ctx: Arc<CommonTaskContext>,
     task_queue: mpsc::Sender<Result<HttpProxyRequest<CDR>, HttpProxyClientResponse>>,
@@ -65,25 +84,53 @@ where
 
     async fn run(&mut self) {
         let (stream_sender, mut stream_receiver) = mpsc::channel(1);
+        let mut last_version = Version::HTTP_11;
         loop {
             if let Some(mut reader) = self.stream_reader.take() {
                 let quit_after_timeout = self.pipeline_stats.get_alive_task() <= 0;
+                // only count the connection as idle if there is no request in processing
+                let idle_guard = quit_after_timeout.then(|| {
+                    self.ctx
+                        .server_stats
+                        .idle_clients
+                        .enter(self.ctx.server_config.client_idle_max_count)
+                });
 
-                match tokio::time::timeout(
+                let wait_data = async {
+                    match &idle_guard {
+                        Some(guard) => tokio::select! {
+                            biased;
+
+                            r = reader.fill_wait_data() => Some(r),
+                            _ = guard.evicted() => None,
+                        },
+                        None => Some(reader.fill_wait_data().await),
+                    }
+                };
+                let r = tokio::time::timeout(
                     self.ctx.server_config.pipeline_read_idle_timeout,
-                    reader.fill_wait_data(),
+                    wait_data,
                 )
-                .await
-                {
-                    Ok(Ok(true)) => {}
-                    Ok(Ok(false)) => {
+                .await;
+                drop(idle_guard);
+
+                match r {
+                    Ok(Some(Ok(true))) => {}
+                    Ok(Some(Ok(false))) => {
                         trace!("client {} closed", self.ctx.client_addr());
                         break;
                     }
-                    Ok(Err(e)) => {
+                    Ok(Some(Err(e))) => {
                         trace!("client {} closed with error {e:?}", self.ctx.client_addr());
                         break;
                     }
+                    Ok(None) => {
+                        trace!(
+                            "client {} closed as too many idle connections",
+                            self.ctx.client_addr()
+                        );
+                        break;
+                    }
                     Err(_) => {
                         // timeout
                         self.stream_reader = Some(reader);
@@ -108,7 +155,9 @@ where
                 .await
                 {
                     Ok(Ok((mut req, send_reader))) => {
-                        if send_reader {
+                        last_version = version;
+                        // hold the reader until the response is sent if pipelining is not allowed
+                        if send_reader || !self.ctx.server_config.allow_pipelined_request {
                             req.body_reader = Some(reader);
                         } else {
                             self.stream_reader = Some(reader);
@@ -158,6 +207,16 @@ where
             } else {
                 match stream_receiver.recv().await.flatten() {
                     Some(mut reader) => {
+                        if let Some(rsp) = check_pipelined_request(
+                            self.ctx.server_config.allow_pipelined_request,
+                            reader.buffer(),
+                            last_version,
+                        ) {
+                            trace!("pipelined request from client {}", self.ctx.client_addr());
+                            let _ = self.task_queue.send(Err(rsp)).await;
+                            break;
+                        }
+
                         // we can now read the next request
                         reader.reset_buffer_stats(Arc::new(NilLimitedReaderStats::default()));
                         let limit_config = &self.ctx.server_config.tcp_sock_speed_limit;
@@ -174,3 +233,29 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEXT_REQUEST: &[u8] = b"GET http://www.example.net/next HTTP/1.1\r\n\r\n";
+
+    #[test]
+    fn pipelined_not_allowed() {
+        let rsp = check_pipelined_request(false, NEXT_REQUEST, Version::HTTP_11).unwrap();
+        assert_eq!(rsp.status(), 400);
+        assert!(rsp.should_close());
+    }
+
+    #[test]
+    fn keep_alive_not_pipelined() {
+        // the next request is sent after the response of the previous one
+        assert!(check_pipelined_request(false, b"", Version::HTTP_11).is_none());
+        assert!(check_pipelined_request(true, b"", Version::HTTP_11).is_none());
+    }
+
+    #[test]
+    fn pipelined_allowed() {
+        assert!(check_pipelined_request(true, NEXT_REQUEST, Version::HTTP_11).is_none());
+    }
+}
@@ -12,6 +12,7 @@ use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::mpsc;
 
+use g3_daemon::server::ClientConnectionInfo;
 use g3_io_ext::{ArcLimitedWriterStats, LimitedWriter};
 use g3_types::auth::UserAuthError;
 use g3_types::net::{HttpAuth, HttpProxySubProtocol};
@@ -19,12 +20,13 @@ use g3_types::net::{HttpAuth, HttpProxySubProtocol};
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest};
 use super::{
     CommonTaskContext, FtpOverHttpTask, HttpProxyCltWrapperStats, HttpProxyConnectTask,
-    HttpProxyForwardTask, HttpProxyPipelineStats, HttpProxyUntrustedTask,
+    HttpProxyConnectUdpTask, HttpProxyForwardTask, HttpProxyPipelineStats, HttpProxyUntrustedTask,
 };
 use crate::audit::AuditContext;
 use crate::auth::{UserContext, UserGroup, UserRequestStats};
 use crate::config::server::ServerConfig;
 use crate::escape::EgressPathSelection;
+use crate::module::ext_authz::{ExtAuthzDecision, ExtAuthzRequest};
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
 use crate::serve::{ServerStats, ServerTaskNotes, UsernameParams};
 
@@ -117,9 +119,22 @@ where
         }
     }
 
+    /// Get the client connection info with the real client ip found in the forwarding chain
+    fn real_client_cc_info(&self, req: &HttpProxyRequest<CDR>) -> ClientConnectionInfo {
+        let mut cc_info = self.ctx.cc_info.clone();
+        if let Some(config) = &self.ctx.server_config.real_client_ip {
+            let ip = config.find(cc_info.client_ip(), &req.inner.end_to_end_headers);
+            if ip != cc_info.client_ip() {
+                cc_info.set_forwarded_client_ip(ip);
+            }
+        }
+        cc_info
+    }
+
     fn do_auth(
         &mut self,
         req: &HttpProxyRequest<CDR>,
+        cc_info: &ClientConnectionInfo,
     ) -> Result<Option<UserContext>, UserAuthError> {
         if let Some(user_group) = &self.user_group {
             let mut user_ctx = match &req.inner.auth_info {
@@ -152,7 +167,7 @@ where
                     )?
                 }
             };
-            user_ctx.check_client_addr(self.ctx.client_addr())?;
+            user_ctx.check_client_addr(cc_info.client_addr())?;
 
             user_ctx.check_in_site(
                 self.ctx.server_config.name(),
@@ -194,10 +209,11 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
+                    let cc_info = self.real_client_cc_info(&req);
+                    let res = match self.do_auth(&req, &cc_info) {
                         Ok(user_ctx) => {
                             self.req_count.consequent_auth_failed = 0;
-                            self.run(req, user_ctx).await
+                            self.run(req, user_ctx, cc_info).await
                         }
                         Err(e) => {
                             self.req_count.consequent_auth_failed += 1;
@@ -281,6 +297,7 @@ where
         &mut self,
         mut req: HttpProxyRequest<CDR>,
         user_ctx: Option<UserContext>,
+        cc_info: ClientConnectionInfo,
     ) -> LoopAction {
         let Ok(path_selection) = self.get_egress_path_selection(&mut req) else {
             self.req_count.invalid += 1;
@@ -294,7 +311,7 @@ where
         };
 
         let task_notes = ServerTaskNotes::with_path_selection(
-            self.ctx.cc_info.clone(),
+            cc_info,
             user_ctx,
             req.time_accepted.elapsed(),
             path_selection,
@@ -303,7 +320,7 @@ where
         // -vvv: log each incoming HTTP connection/request
         debug!(
             "new http request from {} to server {} (method={} uri={} escaper={})",
-            self.ctx.client_addr(),
+            task_notes.client_addr(),
             self.ctx.server_config.name(),
             req.inner.method,
             req.inner.uri,
@@ -313,6 +330,7 @@ where
         let mut audit_ctx = self.audit_ctx.clone();
         let remote_protocol = match req.client_protocol {
             HttpProxySubProtocol::TcpConnect => HttpProxySubProtocol::TcpConnect,
+            HttpProxySubProtocol::UdpConnect => HttpProxySubProtocol::UdpConnect,
             HttpProxySubProtocol::HttpForward => {
                 let _ = self
                     .forward_context
@@ -351,7 +369,9 @@ where
                 {
                     let mut connect_task =
                         HttpProxyConnectTask::new(&self.ctx, audit_ctx, &req, task_notes);
-                    connect_task.connect_to_upstream(&mut stream_w).await;
+                    connect_task
+                        .connect_to_upstream::<CDR, CDW>(&mut stream_w)
+                        .await;
                     if connect_task.back_to_http() {
                         // reopen write end
                         self.stream_writer = Some(stream_w);
@@ -372,6 +392,21 @@ where
                     unreachable!()
                 }
             }
+            HttpProxySubProtocol::UdpConnect => {
+                if let (Some(mut stream_w), Some(stream_r)) =
+                    (self.stream_writer.take(), req.body_reader.take())
+                {
+                    // close read end
+                    let _ = req.stream_sender.try_send(None);
+                    let mut connect_udp_task =
+                        HttpProxyConnectUdpTask::new(&self.ctx, &req, task_notes);
+                    connect_udp_task.setup_upstream(&mut stream_w).await;
+                    connect_udp_task.into_running(stream_r, stream_w);
+                    LoopAction::Break
+                } else {
+                    unreachable!()
+                }
+            }
             HttpProxySubProtocol::HttpForward | HttpProxySubProtocol::HttpsForward => {
                 if let Some(mut stream_w) = self.stream_writer.take() {
                     match self
@@ -533,17 +568,64 @@ where
             _ => unreachable!(),
         };
 
+        let ext_authz_decision = match &self.ctx.ext_authz_client {
+            Some(client) => {
+                let authz_req = ExtAuthzRequest {
+                    client_ip: task_notes.client_ip(),
+                    method: &req.inner.method,
+                    uri: &req.inner.uri,
+                    upstream: &req.upstream,
+                    is_https,
+                    user: task_notes.user_ctx().map(|c| c.user_name().as_ref()),
+                    headers: &req.inner.end_to_end_headers,
+                };
+                Some(client.check(&authz_req).await)
+            }
+            None => None,
+        };
+
+        if let Some(policy) = &self.ctx.server_config.forwarded_header {
+            policy.apply(
+                &mut req.inner.end_to_end_headers,
+                self.ctx.client_addr(),
+                self.ctx.server_addr(),
+            );
+        }
+        self.ctx
+            .server_config
+            .http_req_header_rules
+            .apply(&mut req.inner.end_to_end_headers);
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            user_ctx.apply_http_req_header_rules(&mut req.inner.end_to_end_headers);
+        }
+        let mut ext_authz_denied = false;
+        match ext_authz_decision {
+            Some(ExtAuthzDecision::Allow(headers)) => {
+                for (name, _) in &headers {
+                    req.inner.end_to_end_headers.remove(name);
+                }
+                for (name, value) in headers {
+                    req.inner.end_to_end_headers.append(name, value);
+                }
+            }
+            Some(ExtAuthzDecision::Deny) => ext_authz_denied = true,
+            None => {}
+        }
+
         match req.body_reader.take() {
             Some(stream_r) => {
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
                 let mut forward_task =
                     HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                if ext_authz_denied {
+                    forward_task.set_ext_authz_denied();
+                }
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
                     .await;
-                if forward_task.should_close() {
+                if forward_task.should_close() || !forward_task.clt_body_finished() {
                     // close read end
                     let _ = req.stream_sender.try_send(None);
                     LoopAction::Break
@@ -561,6 +643,9 @@ where
                 // no body, and the connection is expected to keep alive from the client side
                 let mut forward_task =
                     HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                if ext_authz_denied {
+                    forward_task.set_ext_authz_denied();
+                }
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
@@ -10,7 +10,7 @@ use tokio::time::Instant;
 
 use g3_http::server::{HttpProxyClientRequest, HttpRequestParseError, UriExt};
 use g3_http::uri::{HttpMasque, WellKnownUri};
-use g3_types::net::{HttpProxySubProtocol, UpstreamAddr};
+use g3_types::net::{HttpProxySubProtocol, HttpUpgradeToken, UpstreamAddr};
 
 use super::HttpClientReader;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
@@ -48,6 +48,9 @@ where
                         // proxy-connection is not standard, but at least curl use it
                         return req.parse_header_connection(header);
                     }
+                    "upgrade" if config.allow_connect_udp => {
+                        return req.parse_header_upgrade(name, header);
+                    }
                     "forwarded" | "x-forwarded-for" => {
                         if config.steal_forwarded_for {
                             return Ok(());
@@ -74,6 +77,18 @@ where
                     req.set_host(&addr);
                     (addr, protocol)
                 }
+                Some(WellKnownUri::Masque(HttpMasque::Udp(addr))) if config.allow_connect_udp => {
+                    // RFC 9298, with HTTP/1.1 Upgrade
+                    if req.method != Method::GET
+                        || !req.has_upgrade_token(&HttpUpgradeToken::ConnectUdp)
+                    {
+                        return Err(HttpRequestParseError::UnsupportedRequest(
+                            "connect-udp should be a GET request with upgrade token connect-udp"
+                                .to_string(),
+                        ));
+                    }
+                    (addr, HttpProxySubProtocol::UdpConnect)
+                }
                 Some(WellKnownUri::Masque(HttpMasque::Http(uri))) => {
                     req.uri = uri;
                     let (addr, protocol) = req.uri.get_upstream_and_protocol()?;
@@ -96,13 +111,30 @@ where
             req.uri.get_upstream_and_protocol()?
         };
 
+        if sub_protocol != HttpProxySubProtocol::UdpConnect
+            && req.hop_by_hop_headers.contains_key(header::UPGRADE)
+        {
+            return Err(HttpRequestParseError::UpgradeIsNotSupported);
+        }
+
         if !config.allow_custom_host
+            && sub_protocol != HttpProxySubProtocol::UdpConnect
             && let Some(host) = &req.host
             && !host.host_eq(&upstream)
         {
             return Err(HttpRequestParseError::UnmatchedHostAndAuthority);
         }
 
+        if let Some(via) = &config.via_header
+            && via.request
+            && matches!(
+                sub_protocol,
+                HttpProxySubProtocol::HttpForward | HttpProxySubProtocol::HttpsForward
+            )
+        {
+            via.append_to(&mut req.end_to_end_headers, req.version);
+        }
+
         let req = HttpProxyRequest {
             client_protocol: sub_protocol,
             inner: req,
@@ -114,7 +146,7 @@ where
         };
 
         match req.client_protocol {
-            HttpProxySubProtocol::TcpConnect => {
+            HttpProxySubProtocol::TcpConnect | HttpProxySubProtocol::UdpConnect => {
                 // just send to forward task, which will go into a connect task
                 // reader should be sent
                 return Ok((req, true));
@@ -142,3 +174,77 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use tokio::io::AsyncReadExt;
+    use tokio_test::io::Builder;
+    use yaml_rust::YamlLoader;
+
+    use g3_http::HttpBodyReader;
+    use g3_io_ext::{LimitedBufReader, NilLimitedReaderStats};
+    use g3_yaml::yaml_doc;
+
+    #[tokio::test]
+    async fn parse_pipelined() {
+        let doc = yaml_doc!(
+            r#"
+                type: http_proxy
+                name: s1
+                escaper: e1
+            "#
+        );
+        let config = HttpProxyServerConfig::parse(doc.as_hash().unwrap(), None).unwrap();
+
+        let content = b"GET http://www.example.net/ HTTP/1.1\r\n\
+                        Host: www.example.net\r\n\r\n\
+                        POST http://www.example.net/upload HTTP/1.1\r\n\
+                        Host: www.example.net\r\n\
+                        Content-Length: 4\r\n\r\n\
+                        test\
+                        GET http://www.example.net/next HTTP/1.1\r\n\
+                        Host: www.example.net\r\n\r\n";
+        let stream = Builder::new().read(content).build();
+        let mut reader = LimitedBufReader::new_unlimited(
+            stream,
+            Arc::new(NilLimitedReaderStats::default()),
+            Arc::new(NilLimitedReaderStats::default()),
+        );
+        let (sender, _receiver) = mpsc::channel(1);
+        let mut version = Version::HTTP_11;
+
+        let (req, send_reader) =
+            HttpProxyRequest::parse(&config, &mut reader, sender.clone(), &mut version)
+                .await
+                .unwrap();
+        assert_eq!(req.inner.uri.path(), "/");
+        // no body, the next request can be read in directly
+        assert!(!send_reader);
+        assert!(!reader.buffer().is_empty());
+
+        let (req, send_reader) =
+            HttpProxyRequest::parse(&config, &mut reader, sender.clone(), &mut version)
+                .await
+                .unwrap();
+        assert_eq!(req.inner.method, Method::POST);
+        // the body should be read by the task before parsing the next request
+        assert!(send_reader);
+        let mut body_reader =
+            HttpBodyReader::new(&mut reader, req.inner.body_type().unwrap(), 1024);
+        let mut body = Vec::new();
+        body_reader.read_to_end(&mut body).await.unwrap();
+        assert!(body_reader.finished());
+        assert_eq!(body, b"test");
+
+        let (req, send_reader) =
+            HttpProxyRequest::parse(&config, &mut reader, sender, &mut version)
+                .await
+                .unwrap();
+        assert_eq!(req.inner.uri.path(), "/next");
+        assert!(!send_reader);
+        assert!(reader.buffer().is_empty());
+    }
+}
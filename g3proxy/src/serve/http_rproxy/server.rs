@@ -314,6 +314,10 @@ impl ServerInternal for HttpRProxyServer {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
         self.server_stats.set_offline();
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for HttpRProxyServer {
@@ -13,7 +13,8 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
 use crate::serve::{
-    ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ServerForbiddenSnapshot, ServerForbiddenStats, ServerForwardConnectionSnapshot,
+    ServerForwardConnectionStats, ServerPerTaskStats, ServerStats,
 };
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -30,6 +31,7 @@ pub(crate) struct HttpRProxyServerStats {
 
     pub task_http_untrusted: ServerPerTaskStats,
     pub task_http_forward: ServerPerTaskStats,
+    pub forward_connection: ServerForwardConnectionStats,
 
     pub io_http: TcpIoStats,
     pub io_untrusted: TcpIoStats,
@@ -46,6 +48,7 @@ impl HttpRProxyServerStats {
             forbidden: Default::default(),
             task_http_untrusted: Default::default(),
             task_http_forward: Default::default(),
+            forward_connection: Default::default(),
             io_http: Default::default(),
             io_untrusted: Default::default(),
         }
@@ -118,6 +121,10 @@ impl ServerStats for HttpRProxyServerStats {
         self.forbidden.snapshot()
     }
 
+    fn forward_connection_snapshot(&self) -> Option<ServerForwardConnectionSnapshot> {
+        Some(self.forward_connection.snapshot())
+    }
+
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         Some(UntrustedTaskStatsSnapshot {
             task_total: self.task_http_untrusted.get_task_total(),
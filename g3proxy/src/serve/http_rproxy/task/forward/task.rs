@@ -210,6 +210,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
             remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
             remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+            client_cert: None,
         })
     }
 
@@ -229,9 +230,19 @@ impl<'a> HttpRProxyForwardTask<'a> {
         };
         if let Some(log_ctx) = self.get_log_context() {
             log_ctx.log(&e);
+            if self.is_slow() {
+                log_ctx.log_slow(&e);
+            }
         }
     }
 
+    fn is_slow(&self) -> bool {
+        self.ctx
+            .server_config
+            .task_log_slow_threshold
+            .is_some_and(|threshold| self.task_notes.time_elapsed() >= threshold)
+    }
+
     fn pre_start(&mut self) {
         self.ctx.server_stats.task_http_forward.add_task();
         self.ctx.server_stats.task_http_forward.inc_alive_task();
@@ -418,6 +429,10 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 if let Some(limiter) = user.tcp_all_download_speed_limit() {
                     clt_w.add_global_limiter(limiter.clone());
                 }
+                if let Some(quota) = user.traffic_quota() {
+                    br.add_global_limiter(quota.clone());
+                    clt_w.add_global_limiter(quota.clone());
+                }
             }
         } else {
             clt_w.reset_stats(clt_w_stats);
@@ -432,6 +447,9 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 if let Some(limiter) = user.tcp_all_download_speed_limit() {
                     clt_w.add_global_limiter(limiter.clone());
                 }
+                if let Some(quota) = user.traffic_quota() {
+                    clt_w.add_global_limiter(quota.clone());
+                }
             }
         }
     }
@@ -459,6 +477,13 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 ));
             }
 
+            if user_ctx.check_traffic_quota().is_err() {
+                self.reply_too_many_requests(clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
@@ -507,6 +532,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         {
             self.task_notes.stage = ServerTaskStage::Connected;
             self.http_notes.reused_connection = true;
+            self.ctx.server_stats.forward_connection.add_reused();
             fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
             self.http_notes.retry_new_connection = false;
             if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -604,6 +630,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
 
         match self.make_new_connection(fwd_ctx).await {
             Ok(mut connection) => {
+                self.ctx.server_stats.forward_connection.add_new();
                 self.task_notes.stage = ServerTaskStage::Connected;
                 fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
 
@@ -942,6 +969,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         };
 
         let mut rsp_header: Option<HttpForwardRemoteResponse> = None;
+        let mut interim_count = 0;
 
         let mut idle_interval = self.ctx.idle_wheel.register();
         let mut log_interval = self.ctx.get_log_interval();
@@ -958,6 +986,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
                             match hdr.code {
                                 100 | 103 => {
                                     // CONTINUE | Early Hints
+                                    interim_count += 1;
+                                    self.check_interim_response_count(interim_count)?;
                                     self.send_response_header(clt_w, &hdr).await?;
                                 }
                                 _ => {
@@ -1085,6 +1115,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
     where
         W: AsyncWrite + Unpin,
     {
+        let mut interim_count = 0;
         loop {
             let hdr = self.recv_response_header(ups_r).await?;
             match hdr.code {
@@ -1096,6 +1127,8 @@ impl<'a> HttpRProxyForwardTask<'a> {
                 }
                 103 => {
                     // HTTP Early Hints
+                    interim_count += 1;
+                    self.check_interim_response_count(interim_count)?;
                     self.send_response_header(clt_w, &hdr).await?;
                 }
                 _ => {
@@ -1105,6 +1138,16 @@ impl<'a> HttpRProxyForwardTask<'a> {
         }
     }
 
+    fn check_interim_response_count(&self, count: usize) -> ServerTaskResult<()> {
+        if count > self.ctx.server_config.rsp_interim_max_count {
+            Err(ServerTaskError::InvalidUpstreamProtocol(
+                "too many interim responses",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
     async fn recv_response_header(
         &mut self,
         ups_r: &mut BoxHttpForwardReader,
@@ -240,6 +240,10 @@ impl ServerInternal for IntelliProxy {
     fn _abort_runtime(&self) {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for IntelliProxy {
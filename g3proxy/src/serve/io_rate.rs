@@ -0,0 +1,60 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use g3_types::stats::{GlobalStatsMap, IoRateEstimator, StatId, TcpIoSnapshot};
+
+use super::ArcServerStats;
+
+/// the interval to sample the io counters of servers
+const IO_RATE_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+static SERVER_IO_RATE_MAP: Mutex<GlobalStatsMap<(ArcServerStats, IoRateEstimator)>> =
+    Mutex::new(GlobalStatsMap::new());
+
+fn io_snapshot(stats: &ArcServerStats) -> TcpIoSnapshot {
+    let mut snapshot = stats.tcp_io_snapshot().unwrap_or_default();
+    if let Some(udp) = stats.udp_io_snapshot() {
+        snapshot.in_bytes = snapshot.in_bytes.wrapping_add(udp.in_bytes);
+        snapshot.out_bytes = snapshot.out_bytes.wrapping_add(udp.out_bytes);
+    }
+    snapshot
+}
+
+fn sample() {
+    let now = Instant::now();
+    let mut map = SERVER_IO_RATE_MAP.lock().unwrap();
+    super::foreach_server(|_, server| {
+        if let Some(stats) = server.get_server_stats() {
+            map.get_or_insert_with(stats.stat_id(), || {
+                (stats, IoRateEstimator::new(IO_RATE_SAMPLE_INTERVAL))
+            });
+        }
+    });
+    map.retain(|(stats, estimator)| {
+        estimator.update(io_snapshot(stats), now);
+        Arc::strong_count(stats) > 1
+    });
+}
+
+/// Sample the server io counters every second to estimate the current io rate
+pub fn spawn_io_rate_sample() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(IO_RATE_SAMPLE_INTERVAL);
+        loop {
+            interval.tick().await;
+            sample();
+        }
+    });
+}
+
+/// Get the estimated (in, out) rate in bytes per second
+pub(crate) fn get_io_rate(stat_id: StatId) -> Option<(u64, u64)> {
+    let map = SERVER_IO_RATE_MAP.lock().unwrap();
+    map.get(&stat_id)
+        .map(|(_, estimator)| (estimator.in_rate(), estimator.out_rate()))
+}
@@ -12,7 +12,7 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats};
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, TcpAcceptHook};
 use g3_daemon::server::{
     BaseServer, ClientConnectionInfo, ReloadServer, ServerQuitPolicy, ServerReloadCommand,
 };
@@ -50,26 +50,36 @@ mod tcp_stream;
 mod tcp_tproxy;
 mod tls_stream;
 
+mod accept_hook;
+mod client_cert;
 mod error;
 mod task;
 
 mod username_params;
 use username_params::UsernameParams;
 
+use accept_hook::IngressNetworkFilterHook;
+use client_cert::ClientCertParser;
 pub(crate) use error::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
-pub(crate) use task::{ServerTaskNotes, ServerTaskStage};
+pub(crate) use task::{ServerTaskNotes, ServerTaskStage, upgraded_task_max_idle_count};
 
 mod ops;
 pub(crate) use ops::{
-    force_quit_offline_server, force_quit_offline_servers, foreach_server, get_server, reload,
-    stop_all, update_dependency_to_auditor, update_dependency_to_escaper,
+    force_quit_offline_server, force_quit_offline_servers, foreach_server, get_server, quiesce,
+    reload, stop_all, update_dependency_to_auditor, update_dependency_to_escaper,
     update_dependency_to_user_group, wait_all_tasks,
 };
 pub use ops::{spawn_all, spawn_offline_clean};
 
+mod io_rate;
+pub(crate) use io_rate::get_io_rate;
+pub use io_rate::spawn_io_rate_sample;
+
 mod stats;
 pub(crate) use stats::{
-    ArcServerStats, ServerForbiddenSnapshot, ServerForbiddenStats, ServerPerTaskStats, ServerStats,
+    ArcServerStats, ServerBufferGuard, ServerBufferStats, ServerForbiddenSnapshot,
+    ServerForbiddenStats, ServerForwardConnectionSnapshot, ServerForwardConnectionStats,
+    ServerMirrorSnapshot, ServerMirrorStats, ServerPerTaskStats, ServerStats,
 };
 
 #[async_trait]
@@ -117,6 +127,9 @@ trait ServerInternal: Server {
 
     fn _start_runtime(&self, server: ArcServer) -> anyhow::Result<()>;
     fn _abort_runtime(&self);
+    /// Stop the listen runtime only, the server will still be online as it's still registered,
+    /// and it will be set offline when aborted at the next reload or delete.
+    fn _quiesce_runtime(&self);
 }
 
 pub(crate) type ArcServer = Arc<dyn Server + Send + Sync>;
@@ -150,6 +163,10 @@ impl AcceptTcpServer for WrapArcServer {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
         self.0.run_tcp_task(stream, cc_info).await
     }
+
+    fn tcp_accept_hook(&self) -> Option<Arc<dyn TcpAcceptHook>> {
+        self.0.tcp_accept_hook()
+    }
 }
 
 #[async_trait]
@@ -3,7 +3,6 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::net::SocketAddr;
 use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
@@ -17,19 +16,20 @@ use tokio::net::TcpStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
+use g3_daemon::listen::{
+    AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime, TcpAcceptHook,
+};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::haproxy::{ProxyProtocolV1Reader, ProxyProtocolV2Reader};
 use g3_openssl::{SslAcceptor, SslStream};
-use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
 use g3_types::net::{OpensslServerConfig, OpensslTicketKey, ProxyProtocolVersion, RollingTicketer};
 
 use crate::config::server::native_tls_port::NativeTlsPortConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::serve::{
-    ArcServer, ArcServerInternal, Server, ServerInternal, ServerQuitPolicy, ServerRegistry,
-    WrapArcServer,
+    ArcServer, ArcServerInternal, IngressNetworkFilterHook, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, WrapArcServer,
 };
 
 pub(crate) struct NativeTlsPort {
@@ -37,7 +37,7 @@ pub(crate) struct NativeTlsPort {
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     tls_server_config: OpensslServerConfig,
-    ingress_net_filter: Option<AclNetworkRule>,
+    accept_hook: Option<Arc<dyn TcpAcceptHook>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
 
     next_server: ArcSwap<ArcServer>,
@@ -66,10 +66,9 @@ impl NativeTlsPort {
             return Err(anyhow!("no tls server config set"));
         };
 
-        let ingress_net_filter = config
-            .ingress_net_filter
-            .as_ref()
-            .map(|builder| builder.build());
+        let accept_hook = config.ingress_net_filter.as_ref().map(|builder| {
+            Arc::new(IngressNetworkFilterHook::new(builder.build())) as Arc<dyn TcpAcceptHook>
+        });
 
         let next_server = Arc::new(fetch_server(&config.server));
 
@@ -78,7 +77,7 @@ impl NativeTlsPort {
             listen_stats,
             tls_rolling_ticketer,
             tls_server_config,
-            ingress_net_filter,
+            accept_hook,
             reload_sender,
             next_server: ArcSwap::new(next_server),
             quit_policy: Arc::new(ServerQuitPolicy::default()),
@@ -145,23 +144,6 @@ impl NativeTlsPort {
         }
     }
 
-    fn drop_early(&self, client_addr: SocketAddr) -> bool {
-        if let Some(ingress_net_filter) = &self.ingress_net_filter {
-            let (_, action) = ingress_net_filter.check(client_addr.ip());
-            match action {
-                AclAction::Permit | AclAction::PermitAndLog => {}
-                AclAction::Forbid | AclAction::ForbidAndLog => {
-                    self.listen_stats.add_dropped();
-                    return true;
-                }
-            }
-        }
-
-        // TODO add cps limit
-
-        false
-    }
-
     async fn run_task(&self, mut stream: TcpStream, mut cc_info: ClientConnectionInfo) {
         let Ok(ssl) = Ssl::new(&self.tls_server_config.ssl_context) else {
             self.listen_stats.add_dropped();
@@ -280,6 +262,10 @@ impl ServerInternal for NativeTlsPort {
     fn _abort_runtime(&self) {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for NativeTlsPort {
@@ -302,13 +288,12 @@ impl BaseServer for NativeTlsPort {
 #[async_trait]
 impl AcceptTcpServer for NativeTlsPort {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
-        let client_addr = cc_info.client_addr();
-        if self.drop_early(client_addr) {
-            return;
-        }
-
         self.run_task(stream, cc_info).await
     }
+
+    fn tcp_accept_hook(&self) -> Option<Arc<dyn TcpAcceptHook>> {
+        self.accept_hook.clone()
+    }
 }
 
 #[async_trait]
@@ -8,7 +8,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
-use log::{debug, warn};
+use log::{debug, info, warn};
 use tokio::sync::Mutex;
 
 use g3_types::metrics::NodeName;
@@ -250,8 +250,44 @@ pub(crate) async fn update_dependency_to_auditor(auditor: &NodeName, status: &st
     }
 }
 
+/// Stop accepting new connections on the server, and wait for the alive tasks to be drained.
+///
+/// The server will be kept in the registry and stay online, and will be respawned at the next reload.
+pub(crate) async fn quiesce(name: &NodeName, wait_timeout: Duration) -> anyhow::Result<()> {
+    let server = {
+        let _guard = SERVER_OPS_LOCK.lock().await;
+        registry::quiesce(name)?
+    };
+    info!(
+        "server {name} quiesced, waiting for {} alive tasks to finish",
+        server.alive_count()
+    );
+
+    let loop_wait = async {
+        while server.alive_count() > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    };
+    match tokio::time::timeout(wait_timeout, loop_wait).await {
+        Ok(_) => {
+            info!("server {name} drained");
+            Ok(())
+        }
+        Err(_) => Err(anyhow!(
+            "server {name} still has {} alive tasks after {wait_timeout:?}",
+            server.alive_count()
+        )),
+    }
+}
+
 fn reload_old_unlocked(old: AnyServerConfig, new: AnyServerConfig) -> anyhow::Result<()> {
     let name = old.name();
+    if registry::is_quiesced(name) {
+        debug!("server {name} reload: will respawn as it has been quiesced");
+        registry::reload_and_respawn(name, new)?;
+        update_dependency_to_server_unlocked(name, "reloaded");
+        return Ok(());
+    }
     match old.diff_action(&new) {
         ServerConfigDiffAction::NoAction => {
             debug!("server {name} reload: no action is needed");
@@ -307,7 +343,7 @@ fn spawn_new_unlocked(config: AnyServerConfig) -> anyhow::Result<()> {
         AnyServerConfig::TlsStream(c) => TlsStreamServer::prepare_initial(c)?,
         AnyServerConfig::SniProxy(c) => SniProxyServer::prepare_initial(c)?,
         AnyServerConfig::SocksProxy(c) => SocksProxyServer::prepare_initial(c)?,
-        AnyServerConfig::HttpProxy(c) => HttpProxyServer::prepare_initial(c)?,
+        AnyServerConfig::HttpProxy(c) => HttpProxyServer::prepare_initial(*c)?,
         AnyServerConfig::HttpRProxy(c) => HttpRProxyServer::prepare_initial(c)?,
     };
     registry::add(name.clone(), server)?;
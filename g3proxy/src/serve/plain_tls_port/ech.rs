@@ -0,0 +1,190 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use g3_dpi::parser::tls::{ExtensionType, HandshakeCoalescer, Record};
+use g3_types::net::TlsServerName;
+
+use crate::config::server::plain_tls_port::TlsEchAction;
+
+const ECH_CLIENT_HELLO_TYPE_OUTER: u8 = 0;
+
+/// The fatal handshake_failure alert record sent when rejecting the ECH ClientHello
+pub(super) const HANDSHAKE_FAILURE_ALERT: [u8; 7] = [
+    0x15, // Alert
+    0x03, 0x03, 0x00, 0x02, 0x02, // fatal
+    0x28, // handshake_failure
+];
+
+#[derive(Debug, Eq, PartialEq)]
+pub(super) enum EchCheckResult {
+    /// Continue the handshake
+    Continue,
+    /// Log the outer SNI and continue the handshake without ECH
+    Log(String),
+    /// Reject the handshake, with the outer SNI
+    Reject(String),
+}
+
+/// Check the received ClientHello bytes with the configured ECH action
+pub(super) fn check(action: TlsEchAction, data: &[u8]) -> EchCheckResult {
+    if action == TlsEchAction::Ignore {
+        return EchCheckResult::Continue;
+    }
+    let Some(ech) = detect_outer(data) else {
+        return EchCheckResult::Continue;
+    };
+    let outer_sni = ech
+        .sni
+        .as_ref()
+        .map(|v| v.as_ref().to_string())
+        .unwrap_or_default();
+    match action {
+        TlsEchAction::Ignore => EchCheckResult::Continue,
+        TlsEchAction::Log => EchCheckResult::Log(outer_sni),
+        TlsEchAction::Reject => EchCheckResult::Reject(outer_sni),
+    }
+}
+
+pub(super) struct OuterEchClientHello {
+    pub(super) sni: Option<TlsServerName>,
+}
+
+/// Check if the ClientHello in the received bytes has the outer ECH extension.
+///
+/// The bytes should contain the full ClientHello message.
+pub(super) fn detect_outer(data: &[u8]) -> Option<OuterEchClientHello> {
+    let mut handshake_coalescer = HandshakeCoalescer::default();
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let mut record = Record::parse(&data[offset..]).ok()?;
+        offset += record.encoded_len();
+
+        let ch = match record.consume_handshake(&mut handshake_coalescer).ok()? {
+            Some(handshake_msg) => handshake_msg.parse_client_hello().ok()?,
+            None => match handshake_coalescer.parse_client_hello().ok()? {
+                Some(ch) => ch,
+                None => continue,
+            },
+        };
+
+        let ech = ch.get_ext(ExtensionType::EncryptedClientHello).ok()??;
+        if ech.first() != Some(&ECH_CLIENT_HELLO_TYPE_OUTER) {
+            return None;
+        }
+        let sni = ch
+            .get_ext(ExtensionType::ServerName)
+            .ok()
+            .flatten()
+            .and_then(|v| TlsServerName::from_extension_value(v).ok());
+        return Some(OuterEchClientHello { sni });
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client_hello(extensions: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut ext_data = Vec::new();
+        for (t, v) in extensions {
+            ext_data.extend_from_slice(&t.to_be_bytes());
+            ext_data.extend_from_slice(&(v.len() as u16).to_be_bytes());
+            ext_data.extend_from_slice(v);
+        }
+
+        let mut body = vec![0x03, 0x03]; // legacy version
+        body.extend_from_slice(&[0u8; 32]); // random
+        body.push(0x00); // session id
+        body.extend_from_slice(&[0x00, 0x02, 0x13, 0x01]); // cipher suites
+        body.extend_from_slice(&[0x01, 0x00]); // compression methods
+        body.extend_from_slice(&(ext_data.len() as u16).to_be_bytes());
+        body.extend_from_slice(&ext_data);
+
+        let mut handshake = vec![0x01]; // ClientHello
+        handshake.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        handshake.extend_from_slice(&body);
+
+        let mut record = vec![0x16, 0x03, 0x01];
+        record.extend_from_slice(&(handshake.len() as u16).to_be_bytes());
+        record.extend_from_slice(&handshake);
+        record
+    }
+
+    fn sni_ext(name: &str) -> Vec<u8> {
+        let mut v = Vec::new();
+        v.extend_from_slice(&((name.len() + 3) as u16).to_be_bytes());
+        v.push(0x00); // host_name
+        v.extend_from_slice(&(name.len() as u16).to_be_bytes());
+        v.extend_from_slice(name.as_bytes());
+        v
+    }
+
+    const ECH_OUTER: &[u8] = &[
+        0x00, // outer
+        0x00, 0x01, // kdf id
+        0x00, 0x01, // aead id
+        0x01, // config id
+        0x00, 0x01, 0xaa, // enc
+        0x00, 0x01, 0xbb, // payload
+    ];
+
+    #[test]
+    fn alert() {
+        assert_eq!(
+            HANDSHAKE_FAILURE_ALERT[6],
+            u8::from(rustls::AlertDescription::HandshakeFailure)
+        );
+    }
+
+    #[test]
+    fn outer() {
+        let sni = sni_ext("public.example.net");
+        let data = client_hello(&[(0x0000, &sni), (0xfe0d, ECH_OUTER)]);
+
+        assert_eq!(check(TlsEchAction::Ignore, &data), EchCheckResult::Continue);
+        assert_eq!(
+            check(TlsEchAction::Log, &data),
+            EchCheckResult::Log("public.example.net".to_string())
+        );
+        assert_eq!(
+            check(TlsEchAction::Reject, &data),
+            EchCheckResult::Reject("public.example.net".to_string())
+        );
+
+        // outer ECH without SNI
+        let data = client_hello(&[(0xfe0d, ECH_OUTER)]);
+        assert_eq!(
+            check(TlsEchAction::Reject, &data),
+            EchCheckResult::Reject(String::new())
+        );
+    }
+
+    #[test]
+    fn no_outer() {
+        let sni = sni_ext("www.example.net");
+        let data = client_hello(&[(0x0000, &sni)]);
+        for action in [
+            TlsEchAction::Ignore,
+            TlsEchAction::Log,
+            TlsEchAction::Reject,
+        ] {
+            assert_eq!(check(action, &data), EchCheckResult::Continue);
+        }
+
+        // inner ECH
+        let data = client_hello(&[(0x0000, &sni), (0xfe0d, &[0x01])]);
+        assert_eq!(check(TlsEchAction::Reject, &data), EchCheckResult::Continue);
+
+        // incomplete ClientHello
+        let data = client_hello(&[(0x0000, &sni), (0xfe0d, ECH_OUTER)]);
+        assert_eq!(
+            check(TlsEchAction::Reject, &data[..data.len() - 4]),
+            EchCheckResult::Continue
+        );
+    }
+}
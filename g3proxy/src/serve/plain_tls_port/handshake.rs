@@ -0,0 +1,142 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::{self, IoSlice};
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use super::TlsAcceptError;
+
+/// Stream wrapper used during the TLS handshake to limit the total bytes read from the client.
+pub(super) struct HandshakeStream<S> {
+    inner: S,
+    max_size: Option<usize>,
+    read_size: usize,
+}
+
+impl<S> HandshakeStream<S> {
+    pub(super) fn new(inner: S, max_size: Option<usize>) -> Self {
+        HandshakeStream {
+            inner,
+            max_size,
+            read_size: 0,
+        }
+    }
+
+    pub(super) fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn exceeded(&self) -> bool {
+        self.max_size.is_some_and(|max| self.read_size > max)
+    }
+
+    /// Convert the io error returned while reading from this stream
+    pub(super) fn accept_error(&self, e: io::Error) -> TlsAcceptError {
+        if self.exceeded() {
+            TlsAcceptError::HandshakeTooLarge(self.read_size)
+        } else {
+            TlsAcceptError::Io(e)
+        }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for HandshakeStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.exceeded() {
+            return Poll::Ready(Err(io::Error::other("tls handshake size limit exceeded")));
+        }
+        let filled = buf.filled().len();
+        ready!(Pin::new(&mut this.inner).poll_read(cx, buf))?;
+        this.read_size += buf.filled().len() - filled;
+        if this.exceeded() {
+            Poll::Ready(Err(io::Error::other("tls handshake size limit exceeded")))
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for HandshakeStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn limit() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut stream = HandshakeStream::new(server, Some(16));
+
+        client.write_all(&[0u8; 16]).await.unwrap();
+        let mut buf = [0u8; 64];
+        assert_eq!(stream.read(&mut buf).await.unwrap(), 16);
+        assert!(!stream.exceeded());
+
+        client.write_all(&[0u8; 1]).await.unwrap();
+        let e = stream.read(&mut buf).await.unwrap_err();
+        assert!(matches!(
+            stream.accept_error(e),
+            TlsAcceptError::HandshakeTooLarge(17)
+        ));
+
+        // no more read after the limit exceeded
+        client.write_all(&[0u8; 1]).await.unwrap();
+        assert!(stream.read(&mut buf).await.is_err());
+
+        stream.write_all(b"alert").await.unwrap();
+        client.read_exact(&mut buf[..5]).await.unwrap();
+        assert_eq!(&buf[..5], b"alert");
+    }
+
+    #[tokio::test]
+    async fn no_limit() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut stream = HandshakeStream::new(server, None);
+
+        client.write_all(&[0u8; 512]).await.unwrap();
+        drop(client);
+        let mut buf = Vec::new();
+        assert_eq!(stream.read_to_end(&mut buf).await.unwrap(), 512);
+        let e = io::Error::other("test");
+        assert!(matches!(stream.accept_error(e), TlsAcceptError::Io(_)));
+    }
+}
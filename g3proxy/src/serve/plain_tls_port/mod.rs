@@ -3,44 +3,65 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::net::SocketAddr;
+use std::io;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
-use log::debug;
+use bytes::BytesMut;
+use log::{debug, info};
 #[cfg(feature = "quic")]
 use quinn::Connection;
+use rustls::server::Acceptor;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::TcpStream;
 use tokio::sync::broadcast;
-use tokio_rustls::{TlsAcceptor, server::TlsStream};
+use tokio_rustls::{StartHandshake, TlsAcceptor, server::TlsStream};
 
-use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
+use g3_daemon::listen::{
+    AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime, TcpAcceptHook,
+};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::haproxy::{ProxyProtocolV1Reader, ProxyProtocolV2Reader};
 use g3_openssl::SslStream;
-use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
 use g3_types::net::{
     OpensslTicketKey, ProxyProtocolVersion, RollingTicketer, RustlsServerConnectionExt,
 };
 
-use crate::config::server::plain_tls_port::PlainTlsPortConfig;
+use crate::config::server::plain_tls_port::{PlainTlsPortConfig, TlsEchAction};
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::serve::{
-    ArcServer, ArcServerInternal, Server, ServerInternal, ServerQuitPolicy, ServerRegistry,
-    WrapArcServer,
+    ArcServer, ArcServerInternal, IngressNetworkFilterHook, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, WrapArcServer,
 };
 
+mod ech;
+mod handshake;
+
+use ech::EchCheckResult;
+use handshake::HandshakeStream;
+
+enum TlsAcceptError {
+    Io(io::Error),
+    HandshakeTooLarge(usize),
+}
+
+impl From<io::Error> for TlsAcceptError {
+    fn from(e: io::Error) -> Self {
+        TlsAcceptError::Io(e)
+    }
+}
+
 pub(crate) struct PlainTlsPort {
     config: PlainTlsPortConfig,
     listen_stats: Arc<ListenStats>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     tls_acceptor: TlsAcceptor,
     tls_accept_timeout: Duration,
-    ingress_net_filter: Option<AclNetworkRule>,
+    accept_hook: Option<Arc<dyn TcpAcceptHook>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
 
     next_server: ArcSwap<ArcServer>,
@@ -69,10 +90,9 @@ impl PlainTlsPort {
             return Err(anyhow!("no tls server config set"));
         };
 
-        let ingress_net_filter = config
-            .ingress_net_filter
-            .as_ref()
-            .map(|builder| builder.build());
+        let accept_hook = config.ingress_net_filter.as_ref().map(|builder| {
+            Arc::new(IngressNetworkFilterHook::new(builder.build())) as Arc<dyn TcpAcceptHook>
+        });
 
         let next_server = Arc::new(fetch_server(&config.server));
 
@@ -82,7 +102,7 @@ impl PlainTlsPort {
             tls_rolling_ticketer,
             tls_acceptor: TlsAcceptor::from(tls_server_config.driver),
             tls_accept_timeout: tls_server_config.accept_timeout,
-            ingress_net_filter,
+            accept_hook,
             reload_sender,
             next_server: ArcSwap::new(next_server),
             quit_policy: Arc::new(ServerQuitPolicy::default()),
@@ -147,23 +167,6 @@ impl PlainTlsPort {
         }
     }
 
-    fn drop_early(&self, client_addr: SocketAddr) -> bool {
-        if let Some(ingress_net_filter) = &self.ingress_net_filter {
-            let (_, action) = ingress_net_filter.check(client_addr.ip());
-            match action {
-                AclAction::Permit | AclAction::PermitAndLog => {}
-                AclAction::Forbid | AclAction::ForbidAndLog => {
-                    self.listen_stats.add_dropped();
-                    return true;
-                }
-            }
-        }
-
-        // TODO add cps limit
-
-        false
-    }
-
     async fn run_task(&self, mut stream: TcpStream, mut cc_info: ClientConnectionInfo) {
         match self.config.proxy_protocol {
             Some(ProxyProtocolVersion::V1) => {
@@ -193,8 +196,16 @@ impl PlainTlsPort {
             None => {}
         }
 
-        match tokio::time::timeout(self.tls_accept_timeout, self.tls_acceptor.accept(stream)).await
+        let accept_result = if self.config.tls_ech_action == TlsEchAction::Ignore
+            && self.config.tls_handshake_max_size.is_none()
         {
+            tokio::time::timeout(self.tls_accept_timeout, self.tls_acceptor.accept(stream))
+                .await
+                .map(|r| r.map_err(TlsAcceptError::Io))
+        } else {
+            tokio::time::timeout(self.tls_accept_timeout, self.accept_lazy(stream, &cc_info)).await
+        };
+        match accept_result {
             Ok(Ok(tls_stream)) => {
                 if tls_stream.get_ref().1.session_reused() {
                     // Quick ACK is needed with session resumption
@@ -203,7 +214,15 @@ impl PlainTlsPort {
                 let next_server = self.next_server.load().as_ref().clone();
                 next_server.run_rustls_task(tls_stream, cc_info).await
             }
-            Ok(Err(e)) => {
+            Ok(Err(TlsAcceptError::HandshakeTooLarge(size))) => {
+                self.listen_stats.add_oversized();
+                debug!(
+                    "{} - {} tls handshake too large: read {size} bytes",
+                    cc_info.sock_local_addr(),
+                    cc_info.sock_peer_addr()
+                );
+            }
+            Ok(Err(TlsAcceptError::Io(e))) => {
                 self.listen_stats.add_failed();
                 debug!(
                     "{} - {} tls error: {e:?}",
@@ -223,6 +242,89 @@ impl PlainTlsPort {
             }
         }
     }
+
+    /// Read the ClientHello by ourselves before continue the handshake
+    async fn accept_lazy(
+        &self,
+        stream: TcpStream,
+        cc_info: &ClientConnectionInfo,
+    ) -> Result<TlsStream<TcpStream>, TlsAcceptError> {
+        let mut stream = HandshakeStream::new(stream, self.config.tls_handshake_max_size);
+        let mut acceptor = Acceptor::default();
+        let mut buf = BytesMut::with_capacity(2048);
+        let accepted = loop {
+            let offset = buf.len();
+            match stream.read_buf(&mut buf).await {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "connection closed before tls client hello received",
+                    )
+                    .into());
+                }
+                Ok(_) => {}
+                Err(e) => return Err(stream.accept_error(e)),
+            }
+            let mut data = &buf[offset..];
+            while !data.is_empty() {
+                acceptor.read_tls(&mut data)?;
+            }
+            match acceptor.accept() {
+                Ok(Some(accepted)) => break accepted,
+                Ok(None) => {}
+                Err((e, mut alert)) => {
+                    let mut alert_buf = Vec::new();
+                    if alert.write_all(&mut alert_buf).is_ok() {
+                        let _ = stream.write_all(&alert_buf).await;
+                    }
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, e).into());
+                }
+            }
+        };
+
+        match ech::check(self.config.tls_ech_action, &buf) {
+            EchCheckResult::Continue => {}
+            EchCheckResult::Log(outer_sni) => {
+                info!(
+                    "{} - {} tls client hello with ECH, outer sni: {outer_sni}",
+                    cc_info.sock_local_addr(),
+                    cc_info.sock_peer_addr()
+                );
+            }
+            EchCheckResult::Reject(outer_sni) => {
+                let _ = stream.write_all(&ech::HANDSHAKE_FAILURE_ALERT).await;
+                return Err(io::Error::other(format!(
+                    "ECH is not supported, outer sni: {outer_sni}"
+                ))
+                .into());
+            }
+        }
+
+        let tls_config = self.tls_acceptor.config().clone();
+        if self.config.tls_handshake_max_size.is_none() {
+            let tls_stream = StartHandshake::from_parts(accepted, stream.into_inner())
+                .into_stream(tls_config)
+                .await?;
+            return Ok(tls_stream);
+        }
+
+        match StartHandshake::from_parts(accepted, stream)
+            .into_stream(tls_config)
+            .into_fallible()
+            .await
+        {
+            Ok(tls_stream) => {
+                // move the established session back to the raw tcp stream
+                let (stream, session) = tls_stream.into_inner();
+                let tls_stream = self
+                    .tls_acceptor
+                    .accept_with(stream.into_inner(), |s| *s = session)
+                    .await?;
+                Ok(tls_stream)
+            }
+            Err((e, stream)) => Err(stream.accept_error(e)),
+        }
+    }
 }
 
 impl ServerInternal for PlainTlsPort {
@@ -282,6 +384,10 @@ impl ServerInternal for PlainTlsPort {
     fn _abort_runtime(&self) {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for PlainTlsPort {
@@ -304,13 +410,12 @@ impl BaseServer for PlainTlsPort {
 #[async_trait]
 impl AcceptTcpServer for PlainTlsPort {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
-        let client_addr = cc_info.client_addr();
-        if self.drop_early(client_addr) {
-            return;
-        }
-
         self.run_task(stream, cc_info).await
     }
+
+    fn tcp_accept_hook(&self) -> Option<Arc<dyn TcpAcceptHook>> {
+        self.accept_hook.clone()
+    }
 }
 
 #[async_trait]
@@ -20,17 +20,20 @@ static OFFLINE_SERVER_SET: Mutex<Vec<ArcServerInternal>> = Mutex::new(Vec::new()
 
 pub(super) struct ServerRegistry {
     inner: HashMap<NodeName, ArcServerInternal, FixedState>,
+    quiesced: HashSet<NodeName, FixedState>,
 }
 
 impl ServerRegistry {
     const fn new() -> Self {
         ServerRegistry {
             inner: HashMap::with_hasher(FixedState::with_seed(0)),
+            quiesced: HashSet::with_hasher(FixedState::with_seed(0)),
         }
     }
 
     fn add(&mut self, name: NodeName, server: ArcServerInternal) -> anyhow::Result<()> {
         server._start_runtime(server.clone())?;
+        self.quiesced.remove(&name);
         if let Some(old_server) = self.inner.insert(name, server) {
             old_server._abort_runtime();
             add_offline(old_server);
@@ -39,6 +42,7 @@ impl ServerRegistry {
     }
 
     fn del(&mut self, name: &NodeName) {
+        self.quiesced.remove(name);
         if let Some(old_server) = self.inner.remove(name) {
             old_server._abort_runtime();
             add_offline(old_server);
@@ -57,6 +61,16 @@ impl ServerRegistry {
         self.inner.get(name).cloned()
     }
 
+    fn quiesce(&mut self, name: &NodeName) -> anyhow::Result<ArcServerInternal> {
+        let Some(server) = self.inner.get(name) else {
+            return Err(anyhow!("no server with name {name} found"));
+        };
+        if self.quiesced.insert(name.clone()) {
+            server._quiesce_runtime();
+        }
+        Ok(server.clone())
+    }
+
     fn reload_no_respawn(
         &mut self,
         name: &NodeName,
@@ -169,6 +183,18 @@ pub(super) fn get_server(name: &NodeName) -> Option<ArcServerInternal> {
     sr.get_server(name)
 }
 
+pub(super) fn quiesce(name: &NodeName) -> anyhow::Result<ArcServerInternal> {
+    let mut sr = RUNTIME_SERVER_REGISTRY
+        .lock()
+        .map_err(|e| anyhow!("failed to lock server registry: {e}"))?;
+    sr.quiesce(name)
+}
+
+pub(super) fn is_quiesced(name: &NodeName) -> bool {
+    let sr = RUNTIME_SERVER_REGISTRY.lock().unwrap();
+    sr.quiesced.contains(name)
+}
+
 fn check_get_server(name: &NodeName) -> anyhow::Result<ArcServerInternal> {
     get_server(name).ok_or_else(|| anyhow!("no server with name {name} found"))
 }
@@ -225,3 +251,66 @@ pub(crate) fn get_or_insert_default(name: &NodeName) -> ArcServer {
     let mut sr = RUNTIME_SERVER_REGISTRY.lock().unwrap();
     sr.get_or_insert_default(name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use crate::config::server::tcp_stream::TcpStreamServerConfig;
+    use crate::serve::tcp_stream::TcpStreamServer;
+
+    fn tcp_stream_config(port: u16) -> TcpStreamServerConfig {
+        let docs = YamlLoader::load_from_str(&format!(
+            "{{name: test, escaper: default, listen: '127.0.0.1:{port}', upstream: '127.0.0.1:80'}}"
+        ))
+        .unwrap();
+        let Yaml::Hash(map) = &docs[0] else {
+            unreachable!()
+        };
+        TcpStreamServerConfig::parse(map, None).unwrap()
+    }
+
+    fn is_online(sr: &ServerRegistry, name: &NodeName) -> bool {
+        let server = sr.get_server(name).unwrap();
+        server.get_server_stats().unwrap().is_online()
+    }
+
+    #[tokio::test]
+    async fn quiesce() {
+        let name = NodeName::from_str("test").unwrap();
+        let mut sr = ServerRegistry::new();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let server = TcpStreamServer::prepare_initial(tcp_stream_config(port)).unwrap();
+        sr.add(name.clone(), server).unwrap();
+        assert!(is_online(&sr, &name));
+
+        // still online as it's still registered
+        sr.quiesce(&name).unwrap();
+        assert!(sr.quiesced.contains(&name));
+        assert!(is_online(&sr, &name));
+        sr.quiesce(&name).unwrap();
+        assert!(is_online(&sr, &name));
+
+        // the old server will be aborted after respawn
+        let config = AnyServerConfig::TcpStream(tcp_stream_config(port));
+        sr.reload_and_respawn(&name, config).unwrap();
+        assert!(!sr.quiesced.contains(&name));
+        assert!(is_online(&sr, &name));
+
+        sr.quiesce(&name).unwrap();
+        let stats = sr.get_server(&name).unwrap().get_server_stats().unwrap();
+        sr.del(&name);
+        assert!(!sr.quiesced.contains(&name));
+        assert!(!stats.is_online());
+
+        assert!(sr.quiesce(&name).is_err());
+    }
+}
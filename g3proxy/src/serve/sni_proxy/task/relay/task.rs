@@ -72,6 +72,7 @@ impl TcpStreamTask {
                 client_wr_bytes: self.task_stats.clt.write.get_bytes(),
                 remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
                 remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+                client_cert: None,
             })
     }
 
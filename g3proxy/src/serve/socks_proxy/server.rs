@@ -24,6 +24,7 @@ use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::metrics::NodeName;
+use g3_types::net::TlsClientCertInfo;
 
 use super::SocksProxyServerStats;
 use super::task::{CommonTaskContext, SocksProxyNegotiationTask};
@@ -33,8 +34,8 @@ use crate::config::server::socks_proxy::SocksProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, ClientCertParser, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, WrapArcServer,
 };
 
 pub(crate) struct SocksProxyServer {
@@ -43,6 +44,7 @@ pub(crate) struct SocksProxyServer {
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
+    client_cert_parser: ClientCertParser,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Option<Logger>,
 
@@ -88,6 +90,7 @@ impl SocksProxyServer {
             listen_stats,
             ingress_net_filter,
             dst_host_filter,
+            client_cert_parser: ClientCertParser::default(),
             reload_sender,
             task_logger,
             escaper: ArcSwap::new(escaper),
@@ -151,8 +154,12 @@ impl SocksProxyServer {
         AuditContext::new(self.audit_handle.load_full())
     }
 
-    async fn run_task<S>(&self, stream: S, cc_info: ClientConnectionInfo)
-    where
+    async fn run_task<S>(
+        &self,
+        stream: S,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<TlsClientCertInfo>>,
+    ) where
         S: AsyncStream,
         S::R: AsyncRead + Send + Sync + Unpin + 'static,
         S::W: AsyncWrite + Send + Sync + Unpin + 'static,
@@ -174,7 +181,10 @@ impl SocksProxyServer {
             cc_info,
             task_logger: self.task_logger.clone(),
         };
-        SocksProxyNegotiationTask::new(ctx, self.audit_context(), self.user_group.load_full())
+        let mut audit_ctx = self.audit_context();
+        audit_ctx.set_client_cert(client_cert);
+
+        SocksProxyNegotiationTask::new(ctx, audit_ctx, self.user_group.load_full())
             .into_running(stream)
             .await;
     }
@@ -249,6 +259,10 @@ impl ServerInternal for SocksProxyServer {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
         self.server_stats.set_offline();
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for SocksProxyServer {
@@ -271,7 +285,7 @@ impl BaseServer for SocksProxyServer {
 #[async_trait]
 impl AcceptTcpServer for SocksProxyServer {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
-        self.run_task(stream, cc_info).await
+        self.run_task(stream, cc_info, None).await
     }
 }
 
@@ -313,10 +327,16 @@ impl Server for SocksProxyServer {
     }
 
     async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo) {
-        self.run_task(stream, cc_info).await
+        let client_cert = self
+            .client_cert_parser
+            .parse_rustls(stream.get_ref().1, &cc_info);
+        self.run_task(stream, cc_info, client_cert).await
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
-        self.run_task(stream, cc_info).await
+        let client_cert = self
+            .client_cert_parser
+            .parse_openssl(stream.ssl(), &cc_info);
+        self.run_task(stream, cc_info, client_cert).await
     }
 }
@@ -92,6 +92,12 @@ impl SocksProxyTcpConnectTask {
                 client_wr_bytes: self.task_stats.clt.write.get_bytes(),
                 remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
                 remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+                client_cert: self
+                    .ctx
+                    .server_config
+                    .log_client_cert
+                    .then(|| self.audit_ctx.client_cert().map(|c| c.as_ref()))
+                    .flatten(),
             })
     }
 
@@ -245,6 +251,13 @@ impl SocksProxyTcpConnectTask {
                 ));
             }
 
+            if user_ctx.check_traffic_quota().is_err() {
+                self.reply_forbidden(&mut clt_w).await;
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::QuotaExceeded,
+                ));
+            }
+
             match user_ctx.acquire_request_semaphore() {
                 Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
                 Err(_) => {
@@ -410,7 +423,7 @@ impl SocksProxyTcpConnectTask {
                 .unwrap_or_else(|| audit_handle.do_task_audit());
 
             if audit_task {
-                let ctx = StreamInspectContext::new(
+                let mut ctx = StreamInspectContext::new(
                     audit_handle.clone(),
                     self.ctx.server_config.clone(),
                     self.ctx.server_stats.clone(),
@@ -419,6 +432,7 @@ impl SocksProxyTcpConnectTask {
                     &self.task_notes,
                     &self.tcp_notes,
                 );
+                ctx.set_client_cert(self.audit_ctx.client_cert().cloned());
                 return crate::inspect::stream::transit_with_inspection(
                     clt_r,
                     clt_w,
@@ -468,6 +482,10 @@ impl SocksProxyTcpConnectTask {
             if let Some(limiter) = user.tcp_all_download_speed_limit() {
                 clt_w.add_global_limiter(limiter.clone());
             }
+            if let Some(quota) = user.traffic_quota() {
+                clt_r.add_global_limiter(quota.clone());
+                clt_w.add_global_limiter(quota.clone());
+            }
         }
         let wrapper_stats = Arc::new(wrapper_stats);
         clt_r.reset_stats(wrapper_stats.clone());
@@ -4,10 +4,11 @@
  */
 
 use std::sync::Arc;
-use std::sync::atomic::{AtomicI32, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicI32, AtomicU64, AtomicUsize, Ordering};
 
 use arc_swap::ArcSwapOption;
 
+use g3_io_ext::{StreamCopyConfig, StreamMirrorStats};
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
@@ -31,15 +32,50 @@ pub(crate) trait ServerStats {
     fn tcp_io_snapshot(&self) -> Option<TcpIoSnapshot> {
         None
     }
+    /// the part of tcp io that relayed by splice(2)
+    fn tcp_splice_io_snapshot(&self) -> Option<TcpIoSnapshot> {
+        None
+    }
     fn udp_io_snapshot(&self) -> Option<UdpIoSnapshot> {
         None
     }
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot;
 
+    /// count for tasks that have been upgraded, such as websocket
+    fn add_upgraded_task(&self) {}
+    fn get_upgraded_task_total(&self) -> Option<u64> {
+        None
+    }
+
+    /// count for tasks that have been aborted as the max task duration exceeded
+    fn add_deadline_exceeded_task(&self) {}
+    fn get_deadline_exceeded_task_total(&self) -> Option<u64> {
+        None
+    }
+
+    /// count for new and reused upstream connections of http forward tasks
+    fn forward_connection_snapshot(&self) -> Option<ServerForwardConnectionSnapshot> {
+        None
+    }
+
+    /// count of client connections that are idle waiting for the next request
+    fn get_idle_client_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// memory size of the copy buffers allocated by alive tasks
+    fn get_buffer_memory(&self) -> Option<usize> {
+        None
+    }
+
     // for tasks that we should not trust them but must drain them
     fn untrusted_snapshot(&self) -> Option<UntrustedTaskStatsSnapshot> {
         None
     }
+
+    fn mirror_snapshot(&self) -> Option<ServerMirrorSnapshot> {
+        None
+    }
 }
 
 pub(crate) type ArcServerStats = Arc<dyn ServerStats + Send + Sync>;
@@ -80,6 +116,88 @@ impl ServerForbiddenStats {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct ServerForwardConnectionSnapshot {
+    pub(crate) new: u64,
+    pub(crate) reused: u64,
+    pub(crate) h2_new: u64,
+    pub(crate) h2_multiplexed: u64,
+    pub(crate) policy_closed: u64,
+}
+
+#[derive(Default)]
+pub(crate) struct ServerForwardConnectionStats {
+    new: AtomicU64,
+    reused: AtomicU64,
+    h2_new: AtomicU64,
+    h2_multiplexed: AtomicU64,
+    policy_closed: AtomicU64,
+}
+
+impl ServerForwardConnectionStats {
+    pub(crate) fn add_new(&self) {
+        self.new.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_reused(&self) {
+        self.reused.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_h2_new(&self) {
+        self.h2_new.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_h2_multiplexed(&self) {
+        self.h2_multiplexed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_policy_closed(&self) {
+        self.policy_closed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> ServerForwardConnectionSnapshot {
+        ServerForwardConnectionSnapshot {
+            new: self.new.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            h2_new: self.h2_new.load(Ordering::Relaxed),
+            h2_multiplexed: self.h2_multiplexed.load(Ordering::Relaxed),
+            policy_closed: self.policy_closed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ServerMirrorSnapshot {
+    pub(crate) mirrored: u64,
+    pub(crate) dropped: u64,
+}
+
+/// Stats for the traffic mirrored to the shadow destination
+#[derive(Default)]
+pub(crate) struct ServerMirrorStats {
+    mirrored: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl ServerMirrorStats {
+    pub(crate) fn snapshot(&self) -> ServerMirrorSnapshot {
+        ServerMirrorSnapshot {
+            mirrored: self.mirrored.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl StreamMirrorStats for ServerMirrorStats {
+    fn add_mirrored_bytes(&self, size: usize) {
+        self.mirrored.fetch_add(size as u64, Ordering::Relaxed);
+    }
+
+    fn add_dropped_bytes(&self, size: usize) {
+        self.dropped.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct ServerPerTaskStats {
     task_total: AtomicU64,
@@ -107,3 +225,93 @@ impl ServerPerTaskStats {
         self.alive_count.load(Ordering::Relaxed)
     }
 }
+
+#[derive(Default)]
+pub(crate) struct ServerBufferStats {
+    used: AtomicUsize,
+}
+
+impl ServerBufferStats {
+    /// Reserve the copy buffers for both directions of a task.
+    ///
+    /// The minimal buffer size will be used if the budget has been exceeded.
+    pub(crate) fn reserve(
+        self: &Arc<Self>,
+        mut copy_config: StreamCopyConfig,
+        budget: Option<usize>,
+    ) -> (StreamCopyConfig, ServerBufferGuard) {
+        let mut size = copy_config.buffer_size() * 2;
+        if let Some(budget) = budget
+            && self.used.load(Ordering::Relaxed) + size > budget
+        {
+            // the minimal buffer size will be used
+            copy_config.set_buffer_size(0);
+            size = copy_config.buffer_size() * 2;
+        }
+        self.used.fetch_add(size, Ordering::Relaxed);
+        let guard = ServerBufferGuard {
+            stats: self.clone(),
+            size,
+        };
+        (copy_config, guard)
+    }
+
+    pub(crate) fn get_used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct ServerBufferGuard {
+    stats: Arc<ServerBufferStats>,
+    size: usize,
+}
+
+impl ServerBufferGuard {
+    /// Charge the real allocated size instead, such as the pipe size when relaying by splice
+    pub(crate) fn recharge(&mut self, size: usize) {
+        self.stats.used.fetch_add(size, Ordering::Relaxed);
+        self.stats.used.fetch_sub(self.size, Ordering::Relaxed);
+        self.size = size;
+    }
+}
+
+impl Drop for ServerBufferGuard {
+    fn drop(&mut self) {
+        self.stats.used.fetch_sub(self.size, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_budget() {
+        let stats = Arc::new(ServerBufferStats::default());
+        let mut copy_config = StreamCopyConfig::default();
+        copy_config.set_buffer_size(16 * 1024);
+
+        let (c1, g1) = stats.reserve(copy_config, Some(64 * 1024));
+        assert_eq!(c1.buffer_size(), 16 * 1024);
+        let (c2, g2) = stats.reserve(copy_config, Some(64 * 1024));
+        assert_eq!(c2.buffer_size(), 16 * 1024);
+        assert_eq!(stats.get_used(), 64 * 1024);
+
+        let (c3, g3) = stats.reserve(copy_config, Some(64 * 1024));
+        assert_eq!(c3.buffer_size(), 4 * 1024);
+        assert_eq!(stats.get_used(), 72 * 1024);
+
+        drop(g1);
+        drop(g3);
+        assert_eq!(stats.get_used(), 32 * 1024);
+        let (c4, mut g4) = stats.reserve(copy_config, None);
+        assert_eq!(c4.buffer_size(), 16 * 1024);
+        drop(g2);
+        assert_eq!(stats.get_used(), 32 * 1024);
+
+        g4.recharge(128 * 1024);
+        assert_eq!(stats.get_used(), 128 * 1024);
+        drop(g4);
+        assert_eq!(stats.get_used(), 0);
+    }
+}
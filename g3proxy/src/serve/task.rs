@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 use std::time::Duration;
@@ -46,6 +47,18 @@ impl ServerTaskStage {
     }
 }
 
+/// Get the max idle count to use after a task has been upgraded.
+///
+/// The user config takes precedence over the server config, and 0 means no idle limit.
+/// `None` will be returned if the current max idle count should be kept.
+pub(crate) fn upgraded_task_max_idle_count(
+    user: Option<usize>,
+    server: Option<usize>,
+) -> Option<usize> {
+    user.or(server)
+        .map(|count| if count == 0 { usize::MAX } else { count })
+}
+
 /// server task notes is bounded to a single client connection.
 /// it can be reset if the connection is consisted of many tasks.
 /// Do not share this struct between different client connections.
@@ -188,6 +201,15 @@ impl ServerTaskNotes {
         }
     }
 
+    /// Hash all the user info that may affect the egress path selection in escapers
+    pub(crate) fn hash_egress_selection<H: Hasher>(&self, state: &mut H) {
+        self.user_ctx
+            .as_ref()
+            .map(|ctx| (ctx.user_name(), &ctx.user_config().egress_path_selection))
+            .hash(state);
+        self.egress_path_selection.hash(state);
+    }
+
     #[inline]
     pub(crate) fn task_created_instant(&self) -> Instant {
         self.create_ins
@@ -206,3 +228,24 @@ impl ServerTaskNotes {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upgraded_max_idle_count() {
+        assert_eq!(upgraded_task_max_idle_count(None, None), None);
+        assert_eq!(upgraded_task_max_idle_count(None, Some(10)), Some(10));
+        assert_eq!(upgraded_task_max_idle_count(Some(5), Some(10)), Some(5));
+        assert_eq!(upgraded_task_max_idle_count(Some(5), None), Some(5));
+        assert_eq!(
+            upgraded_task_max_idle_count(Some(0), Some(10)),
+            Some(usize::MAX)
+        );
+        assert_eq!(
+            upgraded_task_max_idle_count(None, Some(0)),
+            Some(usize::MAX)
+        );
+    }
+}
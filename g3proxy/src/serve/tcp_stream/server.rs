@@ -24,7 +24,7 @@ use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::collection::{SelectiveVec, SelectiveVecBuilder};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslClientConfig, UpstreamAddr, WeightedUpstreamAddr};
+use g3_types::net::{OpensslClientConfig, TlsClientCertInfo, UpstreamAddr, WeightedUpstreamAddr};
 
 use super::common::CommonTaskContext;
 use super::stats::TcpStreamServerStats;
@@ -34,8 +34,8 @@ use crate::config::server::tcp_stream::TcpStreamServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, ClientCertParser, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, WrapArcServer,
 };
 
 pub(crate) struct TcpStreamServer {
@@ -44,6 +44,7 @@ pub(crate) struct TcpStreamServer {
     listen_stats: Arc<ListenStats>,
     upstream: SelectiveVec<WeightedUpstreamAddr>,
     tls_client_config: Option<Arc<OpensslClientConfig>>,
+    client_cert_parser: ClientCertParser,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Option<Logger>,
@@ -100,6 +101,7 @@ impl TcpStreamServer {
             listen_stats,
             upstream,
             tls_client_config,
+            client_cert_parser: ClientCertParser::default(),
             ingress_net_filter,
             reload_sender,
             task_logger,
@@ -184,16 +186,23 @@ impl TcpStreamServer {
         (ctx, upstream.inner())
     }
 
-    async fn run_task_with_stream<T>(&self, stream: T, cc_info: ClientConnectionInfo)
-    where
+    async fn run_task_with_stream<T>(
+        &self,
+        stream: T,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<TlsClientCertInfo>>,
+    ) where
         T: AsyncStream,
         T::R: AsyncRead + Send + Sync + Unpin + 'static,
         T::W: AsyncWrite + Send + Sync + Unpin + 'static,
     {
         let (ctx, upstream) = self.get_ctx_and_upstream(cc_info);
 
+        let mut audit_ctx = self.audit_context();
+        audit_ctx.set_client_cert(client_cert);
+
         let (clt_r, clt_w) = stream.into_split();
-        TcpStreamTask::new(ctx, upstream, self.audit_context())
+        TcpStreamTask::new(ctx, upstream, audit_ctx)
             .into_running(clt_r, clt_w)
             .await;
     }
@@ -281,6 +290,10 @@ impl ServerInternal for TcpStreamServer {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
         self.server_stats.set_offline();
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for TcpStreamServer {
@@ -311,7 +324,16 @@ impl AcceptTcpServer for TcpStreamServer {
             return;
         }
 
-        self.run_task_with_stream(stream, cc_info).await
+        #[cfg(target_os = "linux")]
+        if self.config.tcp_splice {
+            let (ctx, upstream) = self.get_ctx_and_upstream(cc_info);
+            TcpStreamTask::new(ctx, upstream, self.audit_context())
+                .into_running_splice(stream)
+                .await;
+            return;
+        }
+
+        self.run_task_with_stream(stream, cc_info, None).await
     }
 }
 
@@ -384,7 +406,11 @@ impl Server for TcpStreamServer {
             return;
         }
 
-        self.run_task_with_stream(stream, cc_info).await
+        let client_cert = self
+            .client_cert_parser
+            .parse_rustls(stream.get_ref().1, &cc_info);
+        self.run_task_with_stream(stream, cc_info, client_cert)
+            .await
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
@@ -394,6 +420,10 @@ impl Server for TcpStreamServer {
             return;
         }
 
-        self.run_task_with_stream(stream, cc_info).await
+        let client_cert = self
+            .client_cert_parser
+            .parse_openssl(stream.ssl(), &cc_info);
+        self.run_task_with_stream(stream, cc_info, client_cert)
+            .await
     }
 }
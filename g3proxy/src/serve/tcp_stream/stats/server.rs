@@ -12,7 +12,10 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats};
 
-use crate::serve::{ServerForbiddenSnapshot, ServerForbiddenStats, ServerStats};
+use crate::serve::{
+    ServerBufferStats, ServerForbiddenSnapshot, ServerForbiddenStats, ServerMirrorSnapshot,
+    ServerMirrorStats, ServerStats,
+};
 
 pub(crate) struct TcpStreamServerStats {
     name: NodeName,
@@ -27,7 +30,10 @@ pub(crate) struct TcpStreamServerStats {
     task_alive_count: AtomicI32,
 
     tcp: TcpIoStats,
+    tcp_splice: TcpIoStats,
     pub(crate) forbidden: ServerForbiddenStats,
+    pub(crate) buffer: Arc<ServerBufferStats>,
+    pub(crate) mirror: Arc<ServerMirrorStats>,
 }
 
 impl TcpStreamServerStats {
@@ -41,7 +47,10 @@ impl TcpStreamServerStats {
             task_total: AtomicU64::new(0),
             task_alive_count: AtomicI32::new(0),
             tcp: Default::default(),
+            tcp_splice: Default::default(),
             forbidden: Default::default(),
+            buffer: Default::default(),
+            mirror: Default::default(),
         }
     }
 
@@ -71,6 +80,18 @@ impl TcpStreamServerStats {
         self.tcp.add_out_bytes(size);
     }
 
+    #[inline]
+    pub(crate) fn add_splice_read(&self, size: u64) {
+        self.tcp.add_in_bytes(size);
+        self.tcp_splice.add_in_bytes(size);
+    }
+
+    #[inline]
+    pub(crate) fn add_splice_write(&self, size: u64) {
+        self.tcp.add_out_bytes(size);
+        self.tcp_splice.add_out_bytes(size);
+    }
+
     #[must_use]
     pub(crate) fn add_task(self: &Arc<Self>) -> TcpStreamServerAliveTaskGuard {
         self.task_total.fetch_add(1, Ordering::Relaxed);
@@ -128,8 +149,20 @@ impl ServerStats for TcpStreamServerStats {
         Some(self.tcp.snapshot())
     }
 
+    fn tcp_splice_io_snapshot(&self) -> Option<TcpIoSnapshot> {
+        Some(self.tcp_splice.snapshot())
+    }
+
+    fn mirror_snapshot(&self) -> Option<ServerMirrorSnapshot> {
+        Some(self.mirror.snapshot())
+    }
+
     #[inline]
     fn forbidden_stats(&self) -> ServerForbiddenSnapshot {
         self.forbidden.snapshot()
     }
+
+    fn get_buffer_memory(&self) -> Option<usize> {
+        Some(self.buffer.get_used())
+    }
 }
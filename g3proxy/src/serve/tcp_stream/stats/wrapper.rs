@@ -16,6 +16,7 @@ use super::TcpStreamServerStats;
 pub(crate) struct TcpStreamTaskCltWrapperStats {
     server: Arc<TcpStreamServerStats>,
     task: Arc<TcpStreamTaskStats>,
+    splice: bool,
 }
 
 impl TcpStreamTaskCltWrapperStats {
@@ -26,17 +27,35 @@ impl TcpStreamTaskCltWrapperStats {
         let s = TcpStreamTaskCltWrapperStats {
             server: Arc::clone(server),
             task: Arc::clone(task),
+            splice: false,
         };
         // Clone is OK as we only have smart pointer in s
         (Arc::new(s.clone()), Arc::new(s))
     }
+
+    /// The bytes will also be counted as splice bytes in server stats
+    pub(crate) fn new_splice_pair(
+        server: &Arc<TcpStreamServerStats>,
+        task: &Arc<TcpStreamTaskStats>,
+    ) -> (ArcLimitedReaderStats, ArcLimitedWriterStats) {
+        let s = TcpStreamTaskCltWrapperStats {
+            server: Arc::clone(server),
+            task: Arc::clone(task),
+            splice: true,
+        };
+        (Arc::new(s.clone()), Arc::new(s))
+    }
 }
 
 impl LimitedReaderStats for TcpStreamTaskCltWrapperStats {
     fn add_read_bytes(&self, size: usize) {
         let size = size as u64;
         self.task.clt.read.add_bytes(size);
-        self.server.add_read(size);
+        if self.splice {
+            self.server.add_splice_read(size);
+        } else {
+            self.server.add_read(size);
+        }
     }
 }
 
@@ -44,6 +63,10 @@ impl LimitedWriterStats for TcpStreamTaskCltWrapperStats {
     fn add_write_bytes(&self, size: usize) {
         let size = size as u64;
         self.task.clt.write.add_bytes(size);
-        self.server.add_write(size);
+        if self.splice {
+            self.server.add_splice_write(size);
+        } else {
+            self.server.add_write(size);
+        }
     }
 }
@@ -3,14 +3,22 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use log::debug;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
-use g3_io_ext::{IdleInterval, LimitedReader, LimitedWriter, StreamCopyConfig};
+#[cfg(target_os = "linux")]
+use g3_io_ext::SpliceCopy;
+use g3_io_ext::{
+    IdleInterval, LimitedReader, LimitedWriter, StreamCopyConfig, StreamMirrorReceiver,
+    StreamMirrorWriter,
+};
 use g3_types::net::UpstreamAddr;
 
 use super::common::CommonTaskContext;
@@ -19,8 +27,37 @@ use crate::audit::AuditContext;
 use crate::auth::User;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
+#[cfg(target_os = "linux")]
+use crate::module::tcp_connect::{TcpConnectError, TcpConnectRemoteWrapperStats};
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
-use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::serve::{
+    ServerBufferGuard, ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
+
+/// the max number of pending write chunks for the traffic mirror
+const MIRROR_QUEUE_SIZE: usize = 64;
+const MIRROR_CONNECT_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Send the mirrored client traffic to the mirror address, all errors will be ignored
+fn spawn_mirror(addr: SocketAddr, receiver: StreamMirrorReceiver) {
+    tokio::spawn(async move {
+        let stream =
+            match tokio::time::timeout(MIRROR_CONNECT_TIMEOUT, TcpStream::connect(addr)).await {
+                Ok(Ok(stream)) => stream,
+                Ok(Err(e)) => {
+                    debug!("failed to connect to traffic mirror {addr}: {e}");
+                    return;
+                }
+                Err(_) => {
+                    debug!("timed out to connect to traffic mirror {addr}");
+                    return;
+                }
+            };
+        if let Err(e) = receiver.run_to(stream).await {
+            debug!("failed to write to traffic mirror {addr}: {e}");
+        }
+    });
+}
 
 pub(super) struct TcpStreamTask {
     ctx: CommonTaskContext,
@@ -29,7 +66,9 @@ pub(super) struct TcpStreamTask {
     task_notes: ServerTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
     audit_ctx: AuditContext,
+    copy_config: StreamCopyConfig,
     _alive_guard: Option<TcpStreamServerAliveTaskGuard>,
+    buffer_guard: Option<ServerBufferGuard>,
 }
 
 impl TcpStreamTask {
@@ -39,6 +78,7 @@ impl TcpStreamTask {
         audit_ctx: AuditContext,
     ) -> Self {
         let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, Duration::ZERO);
+        let copy_config = ctx.server_config.tcp_copy;
         TcpStreamTask {
             ctx,
             upstream: upstream.clone(),
@@ -46,7 +86,9 @@ impl TcpStreamTask {
             task_notes,
             task_stats: Arc::new(TcpStreamTaskStats::default()),
             audit_ctx,
+            copy_config,
             _alive_guard: None,
+            buffer_guard: None,
         }
     }
 
@@ -63,6 +105,12 @@ impl TcpStreamTask {
                 client_wr_bytes: self.task_stats.clt.write.get_bytes(),
                 remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
                 remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+                client_cert: self
+                    .ctx
+                    .server_config
+                    .log_client_cert
+                    .then(|| self.audit_ctx.client_cert().map(|c| c.as_ref()))
+                    .flatten(),
             })
     }
 
@@ -82,8 +130,40 @@ impl TcpStreamTask {
         }
     }
 
+    /// Relay by splice(2) if possible, or fallback to the buffered copy
+    #[cfg(target_os = "linux")]
+    pub(super) async fn into_running_splice(mut self, stream: TcpStream) {
+        if !self.splice_allowed() {
+            let (clt_r, clt_w) = stream.into_split();
+            return self.into_running(clt_r, clt_w).await;
+        }
+
+        self.pre_start();
+        let e = match self.run_splice(stream).await {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => e,
+        };
+        if let Some(log_ctx) = self.get_log_context() {
+            log_ctx.log(e);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn splice_allowed(&self) -> bool {
+        self.ctx.tls_client_config.is_none()
+            && self.ctx.server_config.traffic_mirror.is_none()
+            && self.audit_ctx.handle().is_none()
+            && self.ctx.server_config.tcp_sock_speed_limit.shift_millis == 0
+    }
+
     fn pre_start(&mut self) {
         self._alive_guard = Some(self.ctx.server_stats.add_task());
+        let (copy_config, buffer_guard) = self.ctx.server_stats.buffer.reserve(
+            self.ctx.server_config.tcp_copy,
+            self.ctx.server_config.tcp_copy_buffer_budget,
+        );
+        self.copy_config = copy_config;
+        self.buffer_guard = Some(buffer_guard);
 
         if self.ctx.server_config.flush_task_log_on_created
             && let Some(log_ctx) = self.get_log_context()
@@ -147,7 +227,17 @@ impl TcpStreamTask {
         };
 
         self.task_notes.stage = ServerTaskStage::Connected;
-        self.run_connected(clt_r, clt_w, ups_r, ups_w).await
+        if let Some(mirror_addr) = self.ctx.server_config.traffic_mirror {
+            let (ups_w, receiver) = StreamMirrorWriter::new(
+                ups_w,
+                MIRROR_QUEUE_SIZE,
+                self.ctx.server_stats.mirror.clone(),
+            );
+            spawn_mirror(mirror_addr, receiver);
+            self.run_connected(clt_r, clt_w, ups_r, ups_w).await
+        } else {
+            self.run_connected(clt_r, clt_w, ups_r, ups_w).await
+        }
     }
 
     async fn run_connected<CR, CW, UR, UW>(
@@ -186,7 +276,7 @@ impl TcpStreamTask {
         UW: AsyncWrite + Send + Sync + Unpin + 'static,
     {
         if let Some(audit_handle) = self.audit_ctx.check_take_handle() {
-            let ctx = StreamInspectContext::new(
+            let mut ctx = StreamInspectContext::new(
                 audit_handle,
                 self.ctx.server_config.clone(),
                 self.ctx.server_stats.clone(),
@@ -195,6 +285,7 @@ impl TcpStreamTask {
                 &self.task_notes,
                 &self.tcp_notes,
             );
+            ctx.set_client_cert(self.audit_ctx.client_cert().cloned());
             crate::inspect::stream::transit_with_inspection(
                 clt_r,
                 clt_w,
@@ -210,6 +301,74 @@ impl TcpStreamTask {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    async fn run_splice(&mut self, stream: TcpStream) -> ServerTaskResult<()> {
+        // set client side socket options
+        self.ctx
+            .cc_info
+            .tcp_sock_set_raw_opts(&self.ctx.server_config.tcp_misc_opts, true)
+            .map_err(|_| {
+                ServerTaskError::InternalServerError("failed to set client socket options")
+            })?;
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = TcpConnectTaskConf {
+            upstream: &self.upstream,
+        };
+        let (ups, ups_stats) = match self
+            .ctx
+            .escaper
+            .tcp_setup_splice_connection(
+                &task_conf,
+                &mut self.tcp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+                &mut self.audit_ctx,
+            )
+            .await
+        {
+            Ok(c) => c,
+            Err(TcpConnectError::MethodUnavailable) => {
+                let (clt_r, clt_w) = stream.into_split();
+                let (clt_r, clt_w) = self.setup_limit_and_stats(clt_r, clt_w);
+                return self.run(clt_r, clt_w).await;
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        self.task_notes.stage = ServerTaskStage::Connected;
+        if self.ctx.server_config.flush_task_log_on_connected
+            && let Some(log_ctx) = self.get_log_context()
+        {
+            log_ctx.log_connected();
+        }
+        self.task_notes.mark_relaying();
+        self.splice_relay(&stream, &ups, ups_stats).await
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn splice_relay(
+        &mut self,
+        clt: &TcpStream,
+        ups: &TcpStream,
+        ups_stats: Arc<TcpConnectRemoteWrapperStats>,
+    ) -> ServerTaskResult<()> {
+        let (clt_r_stats, clt_w_stats) =
+            TcpStreamTaskCltWrapperStats::new_splice_pair(&self.ctx.server_stats, &self.task_stats);
+        let clt_to_ups =
+            SpliceCopy::new(clt, ups, &self.copy_config, clt_r_stats, ups_stats.clone()).map_err(
+                |_| ServerTaskError::InternalServerError("failed to create splice pipe"),
+            )?;
+        let ups_to_clt = SpliceCopy::new(ups, clt, &self.copy_config, ups_stats, clt_w_stats)
+            .map_err(|_| ServerTaskError::InternalServerError("failed to create splice pipe"))?;
+        if let Some(guard) = &mut self.buffer_guard {
+            // no userspace buffer will be allocated
+            guard.recharge(clt_to_ups.pipe_size() + ups_to_clt.pipe_size());
+        }
+
+        self.transit_splice(clt_to_ups, ups_to_clt).await
+    }
+
     fn setup_limit_and_stats<CR, CW>(
         &self,
         clt_r: CR,
@@ -242,7 +401,7 @@ impl TcpStreamTask {
 
 impl StreamTransitTask for TcpStreamTask {
     fn copy_config(&self) -> StreamCopyConfig {
-        self.ctx.server_config.tcp_copy
+        self.copy_config
     }
 
     fn idle_check_interval(&self) -> IdleInterval {
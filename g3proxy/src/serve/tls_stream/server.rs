@@ -38,8 +38,8 @@ use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::tcp_stream::TcpStreamServerStats;
 use crate::serve::{
-    ArcServer, ArcServerInternal, ArcServerStats, Server, ServerInternal, ServerQuitPolicy,
-    ServerRegistry, ServerStats, WrapArcServer,
+    ArcServer, ArcServerInternal, ArcServerStats, ClientCertParser, Server, ServerInternal,
+    ServerQuitPolicy, ServerRegistry, ServerStats, WrapArcServer,
 };
 
 pub(crate) struct TlsStreamServer {
@@ -51,6 +51,7 @@ pub(crate) struct TlsStreamServer {
     tls_acceptor: TlsAcceptor,
     tls_accept_timeout: Duration,
     tls_client_config: Option<Arc<OpensslClientConfig>>,
+    client_cert_parser: ClientCertParser,
     ingress_net_filter: Option<AclNetworkRule>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Option<Logger>,
@@ -117,6 +118,7 @@ impl TlsStreamServer {
             tls_acceptor: TlsAcceptor::from(tls_server_config.driver),
             tls_accept_timeout: tls_server_config.accept_timeout,
             tls_client_config,
+            client_cert_parser: ClientCertParser::default(),
             ingress_net_filter,
             reload_sender,
             task_logger,
@@ -221,7 +223,13 @@ impl TlsStreamServer {
             task_logger: self.task_logger.clone(),
         };
 
-        TlsStreamTask::new(ctx, upstream.inner(), self.audit_context())
+        let mut audit_ctx = self.audit_context();
+        audit_ctx.set_client_cert(
+            self.client_cert_parser
+                .parse_rustls(stream.get_ref().1, &ctx.cc_info),
+        );
+
+        TlsStreamTask::new(ctx, upstream.inner(), audit_ctx)
             .into_running(stream)
             .await;
     }
@@ -294,6 +302,10 @@ impl ServerInternal for TlsStreamServer {
         let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
         self.server_stats.set_offline();
     }
+
+    fn _quiesce_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::Quiesce);
+    }
 }
 
 impl BaseServer for TlsStreamServer {
@@ -22,7 +22,9 @@ use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
 use crate::serve::tcp_stream::{TcpStreamServerAliveTaskGuard, TcpStreamTaskCltWrapperStats};
-use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::serve::{
+    ServerBufferGuard, ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage,
+};
 
 pub(super) struct TlsStreamTask {
     ctx: CommonTaskContext,
@@ -31,7 +33,9 @@ pub(super) struct TlsStreamTask {
     task_notes: ServerTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
     audit_ctx: AuditContext,
+    copy_config: StreamCopyConfig,
     _alive_guard: Option<TcpStreamServerAliveTaskGuard>,
+    _buffer_guard: Option<ServerBufferGuard>,
 }
 
 impl TlsStreamTask {
@@ -41,6 +45,7 @@ impl TlsStreamTask {
         audit_ctx: AuditContext,
     ) -> Self {
         let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, Duration::ZERO);
+        let copy_config = ctx.server_config.tcp_copy;
         TlsStreamTask {
             ctx,
             upstream: upstream.clone(),
@@ -48,7 +53,9 @@ impl TlsStreamTask {
             task_notes,
             task_stats: Arc::new(TcpStreamTaskStats::default()),
             audit_ctx,
+            copy_config,
             _alive_guard: None,
+            _buffer_guard: None,
         }
     }
 
@@ -65,6 +72,12 @@ impl TlsStreamTask {
                 client_wr_bytes: self.task_stats.clt.write.get_bytes(),
                 remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
                 remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+                client_cert: self
+                    .ctx
+                    .server_config
+                    .log_client_cert
+                    .then(|| self.audit_ctx.client_cert().map(|c| c.as_ref()))
+                    .flatten(),
             })
     }
 
@@ -81,6 +94,12 @@ impl TlsStreamTask {
 
     fn pre_start(&mut self) {
         self._alive_guard = Some(self.ctx.server_stats.add_task());
+        let (copy_config, buffer_guard) = self.ctx.server_stats.buffer.reserve(
+            self.ctx.server_config.tcp_copy,
+            self.ctx.server_config.tcp_copy_buffer_budget,
+        );
+        self.copy_config = copy_config;
+        self._buffer_guard = Some(buffer_guard);
 
         if self.ctx.server_config.flush_task_log_on_created
             && let Some(log_ctx) = self.get_log_context()
@@ -175,7 +194,7 @@ impl TlsStreamTask {
         let (clt_r, clt_w) = self.split_clt(clt_stream);
 
         if let Some(audit_handle) = self.audit_ctx.check_take_handle() {
-            let ctx = StreamInspectContext::new(
+            let mut ctx = StreamInspectContext::new(
                 audit_handle,
                 self.ctx.server_config.clone(),
                 self.ctx.server_stats.clone(),
@@ -184,6 +203,7 @@ impl TlsStreamTask {
                 &self.task_notes,
                 &self.tcp_notes,
             );
+            ctx.set_client_cert(self.audit_ctx.client_cert().cloned());
             crate::inspect::stream::transit_with_inspection(
                 clt_r,
                 clt_w,
@@ -231,7 +251,7 @@ impl TlsStreamTask {
 
 impl StreamTransitTask for TlsStreamTask {
     fn copy_config(&self) -> StreamCopyConfig {
-        self.ctx.server_config.tcp_copy
+        self.copy_config
     }
 
     fn idle_check_interval(&self) -> IdleInterval {
@@ -0,0 +1,72 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::{Arc, Mutex};
+
+use g3_daemon::metrics::TAG_KEY_STAT_ID;
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::metrics::NodeName;
+use g3_types::stats::{GlobalStatsMap, StatId};
+
+use crate::audit::AuditorStats;
+
+const TAG_KEY_AUDITOR: &str = "auditor";
+
+const METRIC_NAME_TLS_SNI_MISMATCH: &str = "auditor.tls.sni_mismatch";
+
+#[derive(Default)]
+struct AuditorSnapshot {
+    tls_sni_mismatch: u64,
+}
+
+type AuditorStatsValue = (Arc<AuditorStats>, AuditorSnapshot);
+
+static AUDITOR_STATS_MAP: Mutex<GlobalStatsMap<AuditorStatsValue>> =
+    Mutex::new(GlobalStatsMap::new());
+
+trait AuditorMetricExt {
+    fn add_auditor_tags(&mut self, auditor: &NodeName, stat_id: StatId);
+}
+
+impl AuditorMetricExt for StatsdTagGroup {
+    fn add_auditor_tags(&mut self, auditor: &NodeName, stat_id: StatId) {
+        let mut buffer = itoa::Buffer::new();
+        let stat_id = buffer.format(stat_id.as_u64());
+        self.add_tag(TAG_KEY_AUDITOR, auditor);
+        self.add_tag(TAG_KEY_STAT_ID, stat_id);
+    }
+}
+
+pub(in crate::stat) fn sync_stats() {
+    let mut stats_map = AUDITOR_STATS_MAP.lock().unwrap();
+    crate::audit::foreach_stats(|stats| {
+        stats_map.get_or_insert_with(stats.stat_id(), || (stats, AuditorSnapshot::default()));
+    });
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut stats_map = AUDITOR_STATS_MAP.lock().unwrap();
+    stats_map.retain(|(stats, snap)| {
+        emit_to_statsd(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_to_statsd(client: &mut StatsdClient, stats: &AuditorStats, snap: &mut AuditorSnapshot) {
+    let new_value = stats.tls_sni_mismatch();
+    if new_value == 0 && snap.tls_sni_mismatch == 0 {
+        return;
+    }
+
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_auditor_tags(stats.name(), stats.stat_id());
+
+    let diff_value = new_value.wrapping_sub(snap.tls_sni_mismatch);
+    client
+        .count_with_tags(METRIC_NAME_TLS_SNI_MISMATCH, diff_value, &common_tags)
+        .send();
+    snap.tls_sni_mismatch = new_value;
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+pub(super) mod auditor;
 pub(super) mod escaper;
 pub(super) mod resolver;
 pub(super) mod server;
@@ -40,6 +41,7 @@ enum MetricUserRequestType {
     HttpForward,
     HttpsForward,
     HttpConnect,
+    HttpConnectUdp,
     FtpOverHttp,
     SocksTcpConnect,
     SocksUdpConnect,
@@ -52,6 +54,7 @@ impl MetricUserRequestType {
             MetricUserRequestType::HttpForward => "http_forward",
             MetricUserRequestType::HttpsForward => "https_forward",
             MetricUserRequestType::HttpConnect => "http_connect",
+            MetricUserRequestType::HttpConnectUdp => "http_connect_udp",
             MetricUserRequestType::FtpOverHttp => "ftp_over_http",
             MetricUserRequestType::SocksTcpConnect => "socks_tcp_connect",
             MetricUserRequestType::SocksUdpConnect => "socks_udp_connect",
@@ -21,6 +21,8 @@ const TAG_KEY_RR_TYPE: &str = "rr_type";
 const METRIC_NAME_QUERY_TOTAL: &str = "resolver.query.total";
 const METRIC_NAME_QUERY_CACHED: &str = "resolver.query.cached";
 const METRIC_NAME_QUERY_TRASHED: &str = "resolver.query.trashed";
+const METRIC_NAME_QUERY_NEG_CACHED: &str = "resolver.query.neg_cached";
+const METRIC_NAME_QUERY_PREFETCHED: &str = "resolver.query.prefetched";
 const METRIC_NAME_QUERY_DRIVER: &str = "resolver.query.driver.total";
 const METRIC_NAME_QUERY_DRIVER_TIMEOUT: &str = "resolver.query.driver.timeout";
 const METRIC_NAME_QUERY_DRIVER_REFUSED: &str = "resolver.query.driver.refused";
@@ -29,12 +31,17 @@ const METRIC_NAME_QUERY_SERVER_REFUSED: &str = "resolver.query.server.refused";
 const METRIC_NAME_QUERY_SERVER_MALFORMED: &str = "resolver.query.server.malformed";
 const METRIC_NAME_QUERY_SERVER_NOT_FOUND: &str = "resolver.query.server.not_found";
 const METRIC_NAME_QUERY_SERVER_SERV_FAIL: &str = "resolver.query.server.serv_fail";
+const METRIC_NAME_QUERY_DNSSEC_VALIDATED: &str = "resolver.query.dnssec.validated";
+const METRIC_NAME_QUERY_DNSSEC_UNVALIDATED: &str = "resolver.query.dnssec.unvalidated";
 const METRIC_NAME_MEMORY_CACHE_CAPACITY: &str = "resolver.memory.cache.capacity";
 const METRIC_NAME_MEMORY_CACHE_LENGTH: &str = "resolver.memory.cache.length";
 const METRIC_NAME_MEMORY_DOING_CAPACITY: &str = "resolver.memory.doing.capacity";
 const METRIC_NAME_MEMORY_DOING_LENGTH: &str = "resolver.memory.doing.length";
+const METRIC_NAME_MEMORY_DOING_MAX_AGE: &str = "resolver.memory.doing.max_age";
 const METRIC_NAME_MEMORY_TRASH_CAPACITY: &str = "resolver.memory.trash.capacity";
 const METRIC_NAME_MEMORY_TRASH_LENGTH: &str = "resolver.memory.trash.length";
+const METRIC_NAME_MEMORY_NEG_CACHE_CAPACITY: &str = "resolver.memory.neg_cache.capacity";
+const METRIC_NAME_MEMORY_NEG_CACHE_LENGTH: &str = "resolver.memory.neg_cache.length";
 
 type ResolverStatsValue = (Arc<ResolverStats>, ResolverSnapshot);
 
@@ -145,6 +152,8 @@ fn emit_query_stats_to_statsd(
 
     emit_query_stats_u64!(cached, METRIC_NAME_QUERY_CACHED);
     emit_query_stats_u64!(trashed, METRIC_NAME_QUERY_TRASHED);
+    emit_query_stats_u64!(neg_cached, METRIC_NAME_QUERY_NEG_CACHED);
+    emit_query_stats_u64!(prefetched, METRIC_NAME_QUERY_PREFETCHED);
     emit_query_stats_u64!(driver, METRIC_NAME_QUERY_DRIVER);
     emit_query_stats_u64!(driver_timeout, METRIC_NAME_QUERY_DRIVER_TIMEOUT);
     emit_query_stats_u64!(driver_refused, METRIC_NAME_QUERY_DRIVER_REFUSED);
@@ -153,6 +162,8 @@ fn emit_query_stats_to_statsd(
     emit_query_stats_u64!(server_malformed, METRIC_NAME_QUERY_SERVER_MALFORMED);
     emit_query_stats_u64!(server_not_found, METRIC_NAME_QUERY_SERVER_NOT_FOUND);
     emit_query_stats_u64!(server_serv_fail, METRIC_NAME_QUERY_SERVER_SERV_FAIL);
+    emit_query_stats_u64!(dnssec_validated, METRIC_NAME_QUERY_DNSSEC_VALIDATED);
+    emit_query_stats_u64!(dnssec_unvalidated, METRIC_NAME_QUERY_DNSSEC_UNVALIDATED);
 }
 
 fn emit_memory_stats_to_statsd(
@@ -174,6 +185,9 @@ fn emit_memory_stats_to_statsd(
     emit_field!(len_cache, METRIC_NAME_MEMORY_CACHE_LENGTH);
     emit_field!(cap_doing, METRIC_NAME_MEMORY_DOING_CAPACITY);
     emit_field!(len_doing, METRIC_NAME_MEMORY_DOING_LENGTH);
+    emit_field!(age_doing, METRIC_NAME_MEMORY_DOING_MAX_AGE);
     emit_field!(cap_trash, METRIC_NAME_MEMORY_TRASH_CAPACITY);
     emit_field!(len_trash, METRIC_NAME_MEMORY_TRASH_LENGTH);
+    emit_field!(cap_neg_cache, METRIC_NAME_MEMORY_NEG_CACHE_CAPACITY);
+    emit_field!(len_neg_cache, METRIC_NAME_MEMORY_NEG_CACHE_LENGTH);
 }
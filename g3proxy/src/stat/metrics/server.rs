@@ -5,19 +5,25 @@
 
 use std::sync::{Arc, Mutex};
 
+use hdrhistogram::Histogram;
+
 use g3_daemon::listen::{ListenSnapshot, ListenStats};
 use g3_daemon::metrics::{
-    ServerMetricExt, TAG_KEY_TRANSPORT, TRANSPORT_TYPE_TCP, TRANSPORT_TYPE_UDP,
+    ServerMetricExt, TAG_KEY_QUANTILE, TAG_KEY_TRANSPORT, TRANSPORT_TYPE_TCP, TRANSPORT_TYPE_UDP,
 };
+use g3_histogram::HistogramStats;
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::{GlobalStatsMap, TcpIoSnapshot, UdpIoSnapshot};
 
-use crate::serve::{ArcServerStats, ServerForbiddenSnapshot};
+use crate::serve::{
+    ArcServerStats, ServerForbiddenSnapshot, ServerForwardConnectionSnapshot, ServerMirrorSnapshot,
+};
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
 const METRIC_NAME_SERVER_CONN_TOTAL: &str = "server.connection.total";
 const METRIC_NAME_SERVER_TASK_TOTAL: &str = "server.task.total";
 const METRIC_NAME_SERVER_TASK_ALIVE: &str = "server.task.alive";
+const METRIC_NAME_SERVER_TASK_CONCURRENCY: &str = "server.task.concurrency";
 const METRIC_NAME_SERVER_FORBIDDEN_AUTH_FAILED: &str = "server.forbidden.auth_failed";
 const METRIC_NAME_SERVER_FORBIDDEN_DEST_DENIED: &str = "server.forbidden.dest_denied";
 const METRIC_NAME_SERVER_FORBIDDEN_USER_BLOCKED: &str = "server.forbidden.user_blocked";
@@ -25,8 +31,23 @@ const METRIC_NAME_SERVER_IO_IN_BYTES: &str = "server.traffic.in.bytes";
 const METRIC_NAME_SERVER_IO_IN_PACKETS: &str = "server.traffic.in.packets";
 const METRIC_NAME_SERVER_IO_OUT_BYTES: &str = "server.traffic.out.bytes";
 const METRIC_NAME_SERVER_IO_OUT_PACKETS: &str = "server.traffic.out.packets";
+const METRIC_NAME_SERVER_TASK_BUFFER_MEMORY: &str = "server.task.buffer_memory";
+const METRIC_NAME_SERVER_CONN_IDLE: &str = "server.connection.idle";
+const METRIC_NAME_SERVER_UPGRADED_TASK_TOTAL: &str = "server.task.upgraded_total";
+const METRIC_NAME_SERVER_DEADLINE_EXCEEDED_TASK_TOTAL: &str = "server.task.deadline_exceeded";
+const METRIC_NAME_SERVER_FORWARD_CONNECTION_NEW: &str = "server.forward.connection.new";
+const METRIC_NAME_SERVER_FORWARD_CONNECTION_REUSED: &str = "server.forward.connection.reused";
+const METRIC_NAME_SERVER_FORWARD_CONNECTION_H2_NEW: &str = "server.forward.connection.h2_new";
+const METRIC_NAME_SERVER_FORWARD_CONNECTION_H2_MULTIPLEXED: &str =
+    "server.forward.connection.h2_multiplexed";
+const METRIC_NAME_SERVER_FORWARD_CONNECTION_POLICY_CLOSED: &str =
+    "server.forward.connection.policy_closed";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_TOTAL: &str = "server.task.untrusted_total";
 const METRIC_NAME_SERVER_UNTRUSTED_TASK_ALIVE: &str = "server.task.untrusted_alive";
+const METRIC_NAME_SERVER_IO_SPLICE_IN_BYTES: &str = "server.traffic.splice_in.bytes";
+const METRIC_NAME_SERVER_IO_SPLICE_OUT_BYTES: &str = "server.traffic.splice_out.bytes";
+const METRIC_NAME_SERVER_IO_MIRROR_BYTES: &str = "server.traffic.mirror.bytes";
+const METRIC_NAME_SERVER_IO_MIRROR_DROPPED_BYTES: &str = "server.traffic.mirror_dropped.bytes";
 const METRIC_NAME_SERVER_IO_UNTRUSTED_IN_BYTES: &str = "server.traffic.untrusted_in.bytes";
 
 type ServerStatsValue = (ArcServerStats, ServerSnapshot);
@@ -41,10 +62,31 @@ static LISTEN_STATS_MAP: Mutex<GlobalStatsMap<ListenStatsValue>> =
 struct ServerSnapshot {
     conn_total: u64,
     task_total: u64,
+    upgraded_task_total: u64,
+    deadline_exceeded_task_total: u64,
+    forward_connection: ServerForwardConnectionSnapshot,
     forbidden: ServerForbiddenSnapshot,
     tcp: TcpIoSnapshot,
+    tcp_splice: TcpIoSnapshot,
+    mirror: ServerMirrorSnapshot,
     udp: UdpIoSnapshot,
     untrusted: UntrustedTaskStatsSnapshot,
+    concurrency: ConcurrencySnapshot,
+}
+
+/// The sampled alive task count between two emits
+struct ConcurrencySnapshot {
+    histogram: Histogram<u64>,
+    stats: HistogramStats,
+}
+
+impl Default for ConcurrencySnapshot {
+    fn default() -> Self {
+        ConcurrencySnapshot {
+            histogram: Histogram::new(3).unwrap(),
+            stats: HistogramStats::default(),
+        }
+    }
 }
 
 pub(in crate::stat) fn sync_stats() {
@@ -65,6 +107,14 @@ pub(in crate::stat) fn sync_stats() {
     drop(listen_stats_map);
 }
 
+pub(in crate::stat) fn sample_stats() {
+    let mut server_stats_map = SERVER_STATS_MAP.lock().unwrap();
+    for (stats, snap) in server_stats_map.values_mut() {
+        let alive = stats.get_alive_count().max(0) as u64;
+        let _ = snap.concurrency.histogram.record(alive);
+    }
+}
+
 pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
     let mut server_stats_map = SERVER_STATS_MAP.lock().unwrap();
     server_stats_map.retain(|(stats, snap)| {
@@ -111,6 +161,52 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
         )
         .send();
 
+    if let Some(idle_count) = stats.get_idle_client_count() {
+        client
+            .gauge_with_tags(METRIC_NAME_SERVER_CONN_IDLE, idle_count, &common_tags)
+            .send();
+    }
+
+    if let Some(buffer_memory) = stats.get_buffer_memory() {
+        client
+            .gauge_with_tags(
+                METRIC_NAME_SERVER_TASK_BUFFER_MEMORY,
+                buffer_memory,
+                &common_tags,
+            )
+            .send();
+    }
+
+    if let Some(new_value) = stats.get_upgraded_task_total() {
+        let diff_value = new_value.wrapping_sub(snap.upgraded_task_total);
+        client
+            .count_with_tags(
+                METRIC_NAME_SERVER_UPGRADED_TASK_TOTAL,
+                diff_value,
+                &common_tags,
+            )
+            .send();
+        snap.upgraded_task_total = new_value;
+    }
+
+    if let Some(new_value) = stats.get_deadline_exceeded_task_total() {
+        let diff_value = new_value.wrapping_sub(snap.deadline_exceeded_task_total);
+        client
+            .count_with_tags(
+                METRIC_NAME_SERVER_DEADLINE_EXCEEDED_TASK_TOTAL,
+                diff_value,
+                &common_tags,
+            )
+            .send();
+        snap.deadline_exceeded_task_total = new_value;
+    }
+
+    if let Some(new_snap) = stats.forward_connection_snapshot() {
+        emit_forward_connection_stats(client, new_snap, &mut snap.forward_connection, &common_tags);
+    }
+
+    emit_concurrency_stats(client, &mut snap.concurrency, &common_tags);
+
     emit_forbidden_stats(
         client,
         stats.forbidden_stats(),
@@ -122,6 +218,14 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
         emit_tcp_io_to_statsd(client, tcp_io_stats, &mut snap.tcp, &common_tags);
     }
 
+    if let Some(tcp_splice_stats) = stats.tcp_splice_io_snapshot() {
+        emit_tcp_splice_to_statsd(client, tcp_splice_stats, &mut snap.tcp_splice, &common_tags);
+    }
+
+    if let Some(mirror_stats) = stats.mirror_snapshot() {
+        emit_mirror_to_statsd(client, mirror_stats, &mut snap.mirror, &common_tags);
+    }
+
     if let Some(udp_io_stats) = stats.udp_io_snapshot() {
         emit_udp_io_to_statsd(client, udp_io_stats, &mut snap.udp, &common_tags);
     }
@@ -131,6 +235,25 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
     }
 }
 
+fn emit_concurrency_stats(
+    client: &mut StatsdClient,
+    snap: &mut ConcurrencySnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    if snap.histogram.is_empty() {
+        return;
+    }
+    snap.stats.update(&snap.histogram);
+    snap.histogram.reset();
+
+    snap.stats.foreach_stat(|_, quantile, v| {
+        client
+            .gauge_float_with_tags(METRIC_NAME_SERVER_TASK_CONCURRENCY, v, common_tags)
+            .with_tag(TAG_KEY_QUANTILE, quantile)
+            .send();
+    });
+}
+
 fn emit_forbidden_stats(
     client: &mut StatsdClient,
     stats: ServerForbiddenSnapshot,
@@ -155,6 +278,40 @@ fn emit_forbidden_stats(
     emit_forbid_stats_u64!(user_blocked, METRIC_NAME_SERVER_FORBIDDEN_USER_BLOCKED);
 }
 
+fn emit_forward_connection_stats(
+    client: &mut StatsdClient,
+    stats: ServerForwardConnectionSnapshot,
+    snap: &mut ServerForwardConnectionSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    if stats.new == 0 && stats.reused == 0 && stats.h2_new == 0 {
+        return;
+    }
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = stats.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, common_tags)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(new, METRIC_NAME_SERVER_FORWARD_CONNECTION_NEW);
+    emit_field!(reused, METRIC_NAME_SERVER_FORWARD_CONNECTION_REUSED);
+    emit_field!(h2_new, METRIC_NAME_SERVER_FORWARD_CONNECTION_H2_NEW);
+    emit_field!(
+        h2_multiplexed,
+        METRIC_NAME_SERVER_FORWARD_CONNECTION_H2_MULTIPLEXED
+    );
+    emit_field!(
+        policy_closed,
+        METRIC_NAME_SERVER_FORWARD_CONNECTION_POLICY_CLOSED
+    );
+}
+
 fn emit_tcp_io_to_statsd(
     client: &mut StatsdClient,
     stats: TcpIoSnapshot,
@@ -181,6 +338,56 @@ fn emit_tcp_io_to_statsd(
     emit_field!(out_bytes, METRIC_NAME_SERVER_IO_OUT_BYTES);
 }
 
+fn emit_tcp_splice_to_statsd(
+    client: &mut StatsdClient,
+    stats: TcpIoSnapshot,
+    snap: &mut TcpIoSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    if stats.in_bytes == 0 && snap.in_bytes == 0 {
+        return;
+    }
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = stats.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, common_tags)
+                .with_tag(TAG_KEY_TRANSPORT, TRANSPORT_TYPE_TCP)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(in_bytes, METRIC_NAME_SERVER_IO_SPLICE_IN_BYTES);
+    emit_field!(out_bytes, METRIC_NAME_SERVER_IO_SPLICE_OUT_BYTES);
+}
+
+fn emit_mirror_to_statsd(
+    client: &mut StatsdClient,
+    stats: ServerMirrorSnapshot,
+    snap: &mut ServerMirrorSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            if stats.$field != 0 || snap.$field != 0 {
+                let new_value = stats.$field;
+                let diff_value = new_value.wrapping_sub(snap.$field);
+                client
+                    .count_with_tags($name, diff_value, common_tags)
+                    .with_tag(TAG_KEY_TRANSPORT, TRANSPORT_TYPE_TCP)
+                    .send();
+                snap.$field = new_value;
+            }
+        };
+    }
+
+    emit_field!(mirrored, METRIC_NAME_SERVER_IO_MIRROR_BYTES);
+    emit_field!(dropped, METRIC_NAME_SERVER_IO_MIRROR_DROPPED_BYTES);
+}
+
 fn emit_udp_io_to_statsd(
     client: &mut StatsdClient,
     stats: UdpIoSnapshot,
@@ -40,6 +40,7 @@ const METRIC_NAME_FORBIDDEN_DEST_DENIED: &str = "user.forbidden.dest_denied";
 const METRIC_NAME_FORBIDDEN_IP_BLOCKED: &str = "user.forbidden.ip_blocked";
 const METRIC_NAME_FORBIDDEN_LOG_SKIPPED: &str = "user.forbidden.log_skipped";
 const METRIC_NAME_FORBIDDEN_UA_BLOCKED: &str = "user.forbidden.ua_blocked";
+const METRIC_NAME_FORBIDDEN_QUOTA_EXCEEDED: &str = "user.forbidden.quota_exceeded";
 
 pub(super) struct RequestStatsNamesRef<'a> {
     pub(super) connection_total: &'a str,
@@ -283,6 +284,7 @@ fn emit_user_forbidden_stats(
     emit_forbid_stats_u64!(dest_denied, METRIC_NAME_FORBIDDEN_DEST_DENIED);
     emit_forbid_stats_u64!(ip_blocked, METRIC_NAME_FORBIDDEN_IP_BLOCKED);
     emit_forbid_stats_u64!(ua_blocked, METRIC_NAME_FORBIDDEN_UA_BLOCKED);
+    emit_forbid_stats_u64!(quota_exceeded, METRIC_NAME_FORBIDDEN_QUOTA_EXCEEDED);
     emit_forbid_stats_u64!(log_skipped, METRIC_NAME_FORBIDDEN_LOG_SKIPPED);
 }
 
@@ -454,6 +456,7 @@ where
     emit_field!(http_forward, MetricUserRequestType::HttpForward);
     emit_field!(https_forward, MetricUserRequestType::HttpsForward);
     emit_field!(http_connect, MetricUserRequestType::HttpConnect);
+    emit_field!(http_connect_udp, MetricUserRequestType::HttpConnectUdp);
     emit_field!(ftp_over_http, MetricUserRequestType::FtpOverHttp);
     emit_field!(socks_tcp_connect, MetricUserRequestType::SocksTcpConnect);
     emit_field!(socks_udp_connect, MetricUserRequestType::SocksUdpConnect);
@@ -470,6 +473,10 @@ where
     emit(stats.http_forward(), MetricUserRequestType::HttpForward);
     emit(stats.https_forward(), MetricUserRequestType::HttpsForward);
     emit(stats.http_connect(), MetricUserRequestType::HttpConnect);
+    emit(
+        stats.http_connect_udp(),
+        MetricUserRequestType::HttpConnectUdp,
+    );
     emit(stats.ftp_over_http(), MetricUserRequestType::FtpOverHttp);
     emit(
         stats.socks_tcp_connect(),
@@ -530,6 +537,7 @@ fn find_io_stat<'a, F>(
     emit_tcp_field!(http_forward, MetricUserRequestType::HttpForward);
     emit_tcp_field!(https_forward, MetricUserRequestType::HttpsForward);
     emit_tcp_field!(http_connect, MetricUserRequestType::HttpConnect);
+    emit_tcp_field!(http_connect_udp, MetricUserRequestType::HttpConnectUdp);
     emit_tcp_field!(ftp_over_http, MetricUserRequestType::FtpOverHttp);
     emit_tcp_field!(socks_tcp_connect, MetricUserRequestType::SocksTcpConnect);
 
@@ -5,7 +5,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread::JoinHandle;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, anyhow};
 
@@ -18,6 +18,9 @@ pub(crate) use metrics::user_site;
 
 static QUIT_STAT_THREAD: AtomicBool = AtomicBool::new(false);
 
+/// the interval to sample the alive task count of servers
+const SERVER_CONCURRENCY_SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
 fn build_statsd_client(config: &StatsdClientConfig) -> anyhow::Result<StatsdClient> {
     let client = config
         .build()
@@ -41,14 +44,17 @@ fn spawn_main_thread(config: &StatsdClientConfig) -> anyhow::Result<JoinHandle<(
                 metrics::server::sync_stats();
                 metrics::escaper::sync_stats();
                 metrics::resolver::sync_stats();
+                metrics::auditor::sync_stats();
                 metrics::user::sync_stats();
                 g3_daemon::log::metrics::sync_stats();
 
                 metrics::server::emit_stats(&mut client);
                 metrics::escaper::emit_stats(&mut client);
                 metrics::resolver::emit_stats(&mut client);
+                metrics::auditor::emit_stats(&mut client);
                 metrics::user::emit_stats(&mut client);
                 g3_daemon::runtime::metrics::emit_stats(&mut client);
+                g3_daemon::metrics::reload::emit_stats(&mut client);
                 g3_daemon::log::metrics::emit_stats(&mut client);
 
                 client.flush_sink();
@@ -57,7 +63,12 @@ fn spawn_main_thread(config: &StatsdClientConfig) -> anyhow::Result<JoinHandle<(
                     break;
                 }
 
-                g3_daemon::stat::emit::wait_duration(emit_duration, instant_start);
+                g3_daemon::stat::emit::wait_duration_with_sample(
+                    emit_duration,
+                    instant_start,
+                    SERVER_CONCURRENCY_SAMPLE_INTERVAL,
+                    metrics::server::sample_stats,
+                );
             }
         })
         .map_err(|e| anyhow!("failed to spawn thread: {e:?}"))?;
@@ -10,6 +10,8 @@ pub(crate) struct TrafficStats {
     pub(crate) http_forward: TcpIoStats,
     pub(crate) https_forward: TcpIoStats,
     pub(crate) http_connect: TcpIoStats,
+    /// counted by the capsule stream bytes
+    pub(crate) http_connect_udp: TcpIoStats,
     pub(crate) ftp_over_http: TcpIoStats,
     pub(crate) socks_tcp_connect: TcpIoStats,
     pub(crate) socks_udp_connect: UdpIoStats,
@@ -21,6 +23,7 @@ pub(crate) struct TrafficSnapshot {
     pub(crate) http_forward: TcpIoSnapshot,
     pub(crate) https_forward: TcpIoSnapshot,
     pub(crate) http_connect: TcpIoSnapshot,
+    pub(crate) http_connect_udp: TcpIoSnapshot,
     pub(crate) ftp_over_http: TcpIoSnapshot,
     pub(crate) socks_tcp_connect: TcpIoSnapshot,
     pub(crate) socks_udp_connect: UdpIoSnapshot,
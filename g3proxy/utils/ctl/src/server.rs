@@ -3,7 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use clap::{Arg, ArgMatches, Command};
+use clap::{Arg, ArgMatches, Command, value_parser};
 use futures_util::future::TryFutureExt;
 
 use g3_ctl::CommandResult;
@@ -11,17 +11,31 @@ use g3_ctl::CommandResult;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::server_capnp::server_control;
 
+use crate::common::parse_operation_result;
+
 pub const COMMAND: &str = "server";
 
 const COMMAND_ARG_NAME: &str = "name";
 
 const SUBCOMMAND_STATUS: &str = "status";
+const SUBCOMMAND_QUIESCE: &str = "quiesce";
+const SUBCOMMAND_QUIESCE_ARG_WAIT: &str = "wait";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
         .subcommand_required(true)
         .subcommand(Command::new(SUBCOMMAND_STATUS))
+        .subcommand(
+            Command::new(SUBCOMMAND_QUIESCE).arg(
+                Arg::new(SUBCOMMAND_QUIESCE_ARG_WAIT)
+                    .value_name("TIMEOUT")
+                    .num_args(1)
+                    .value_parser(value_parser!(u32))
+                    .default_value("60")
+                    .long("wait"),
+            ),
+        )
 }
 
 async fn status(client: &server_control::Client) -> CommandResult<()> {
@@ -32,19 +46,37 @@ async fn status(client: &server_control::Client) -> CommandResult<()> {
     println!("alive tasks: {}", stats.get_alive_task_count());
     println!("total conn: {}", stats.get_total_conn_count());
     println!("total task: {}", stats.get_total_task_count());
+    println!("in rate: {} B/s", stats.get_in_rate());
+    println!("out rate: {} B/s", stats.get_out_rate());
     Ok(())
 }
 
+async fn quiesce(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let wait_timeout = args
+        .get_one::<u32>(SUBCOMMAND_QUIESCE_ARG_WAIT)
+        .copied()
+        .unwrap_or_default();
+    let mut req = client.quiesce_request();
+    req.get().set_wait_timeout(wait_timeout);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
-    let (subcommand, _) = args.subcommand().unwrap();
+    let (subcommand, args) = args.subcommand().unwrap();
     match subcommand {
         SUBCOMMAND_STATUS => {
             super::proc::get_server(client, name)
                 .and_then(|server| async move { status(&server).await })
                 .await
         }
+        SUBCOMMAND_QUIESCE => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { quiesce(&server, args).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
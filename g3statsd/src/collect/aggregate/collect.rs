@@ -13,7 +13,9 @@ use tokio::sync::broadcast;
 use g3_types::metrics::NodeName;
 
 use super::AggregateHandle;
-use crate::collect::{ArcCollectorInternal, Collector, CollectorInternal, CollectorRegistry};
+use crate::collect::{
+    ArcCollectorInternal, Collector, CollectorInternal, CollectorRegistry, CollectorStats,
+};
 use crate::config::collector::aggregate::AggregateCollectorConfig;
 use crate::config::collector::{AnyCollectorConfig, CollectorConfig};
 use crate::types::MetricRecord;
@@ -22,6 +24,7 @@ pub(crate) struct AggregateCollector {
     name: NodeName,
     config: ArcSwap<AggregateCollectorConfig>,
     handle: Arc<AggregateHandle>,
+    stats: Arc<CollectorStats>,
 
     reload_sender: broadcast::Sender<Arc<AggregateCollectorConfig>>,
 }
@@ -32,11 +35,14 @@ impl AggregateCollector {
     ) -> anyhow::Result<ArcCollectorInternal> {
         let config = Arc::new(config);
         let reload_sender = broadcast::Sender::new(4);
-        let handle = AggregateHandle::spawn_new(config.clone(), reload_sender.subscribe());
+        let stats = Arc::new(CollectorStats::default());
+        let handle =
+            AggregateHandle::spawn_new(config.clone(), reload_sender.subscribe(), stats.clone());
         let server = AggregateCollector {
             name: config.name().clone(),
             config: ArcSwap::new(config),
             handle,
+            stats,
             reload_sender,
         };
         Ok(Arc::new(server))
@@ -55,8 +61,13 @@ impl Collector for AggregateCollector {
     }
 
     fn add_metric(&self, _time: DateTime<Utc>, record: MetricRecord, worker_id: Option<usize>) {
+        self.stats.add_input();
         self.handle.add_metric(record, worker_id);
     }
+
+    fn stats(&self) -> Option<&CollectorStats> {
+        Some(&self.stats)
+    }
 }
 
 impl CollectorInternal for AggregateCollector {
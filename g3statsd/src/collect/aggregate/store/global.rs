@@ -4,15 +4,19 @@
  */
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use ahash::AHashMap;
 use chrono::Utc;
+use log::warn;
 use tokio::sync::{broadcast, mpsc};
 
 use g3_types::metrics::MetricTagMap;
 
-use super::Command;
-use crate::collect::ArcCollector;
+use super::histogram::{HistogramNames, HistogramStoreValue};
+use super::{Command, SeriesLimiter};
+use crate::collect::{ArcCollector, CollectorStats};
+use crate::config::collector::CollectorConfig;
 use crate::config::collector::aggregate::AggregateCollectorConfig;
 use crate::export::ArcExporter;
 use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
@@ -27,8 +31,15 @@ pub(super) struct GlobalStore {
     next: Option<ArcCollector>,
     exporters: Vec<ArcExporter>,
 
+    limiter: SeriesLimiter,
+    dropped_series: Arc<AtomicU64>,
+    stats: Arc<CollectorStats>,
+
     counter: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
     gauge: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
+    /// the gauge deltas that have no absolute value to apply to in the current interval
+    gauge_delta: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
+    histogram: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, HistogramStoreValue>>,
 }
 
 impl GlobalStore {
@@ -36,6 +47,8 @@ impl GlobalStore {
         config: Arc<AggregateCollectorConfig>,
         cfg_receiver: broadcast::Receiver<Arc<AggregateCollectorConfig>>,
         cmd_receiver: mpsc::UnboundedReceiver<Command>,
+        dropped_series: Arc<AtomicU64>,
+        stats: Arc<CollectorStats>,
     ) -> Self {
         let next = config
             .next
@@ -46,6 +59,7 @@ impl GlobalStore {
             .iter()
             .map(crate::export::get_or_insert_default)
             .collect();
+        let limiter = SeriesLimiter::new(config.max_series, dropped_series.clone());
 
         GlobalStore {
             config,
@@ -53,8 +67,13 @@ impl GlobalStore {
             cmd_receiver,
             next,
             exporters,
+            limiter,
+            dropped_series,
+            stats,
             counter: Default::default(),
             gauge: Default::default(),
+            gauge_delta: Default::default(),
+            histogram: Default::default(),
         }
     }
 
@@ -103,6 +122,7 @@ impl GlobalStore {
             .iter()
             .map(crate::export::get_or_insert_default)
             .collect();
+        self.limiter.set_max(config.max_series);
         self.config = config;
     }
 
@@ -117,42 +137,111 @@ impl GlobalStore {
     }
 
     fn add_record(&mut self, record: MetricRecord) {
-        match record.r#type {
+        let MetricRecord {
+            r#type,
+            name,
+            tag_map,
+            value,
+        } = record;
+
+        match r#type {
             MetricType::Counter => {
-                let MetricRecord {
-                    r#type: _,
-                    name,
-                    tag_map,
-                    value,
-                } = record;
-
-                self.counter
-                    .entry(name)
-                    .or_default()
-                    .entry(tag_map)
-                    .and_modify(|v| *v += value)
-                    .or_insert(value);
+                if let Some(v) = self
+                    .counter
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    *v += value;
+                    return;
+                }
+                if self.limiter.reserve() {
+                    self.counter.entry(name).or_default().insert(tag_map, value);
+                }
             }
             MetricType::Gauge => {
-                let MetricRecord {
-                    r#type: _,
-                    name,
-                    tag_map,
-                    value,
-                } = record;
-
-                self.gauge
-                    .entry(name)
-                    .or_default()
-                    .entry(tag_map)
-                    .and_modify(|v| *v = value)
-                    .or_insert(value);
+                let moved = self
+                    .gauge_delta
+                    .get_mut(&name)
+                    .and_then(|inner| inner.remove(&tag_map))
+                    .is_some();
+                if let Some(v) = self
+                    .gauge
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    *v = value;
+                    return;
+                }
+                // the series is already counted if moved from the gauge deltas
+                if moved || self.limiter.reserve() {
+                    self.gauge.entry(name).or_default().insert(tag_map, value);
+                }
+            }
+            MetricType::GaugeDelta => {
+                if let Some(v) = self
+                    .gauge
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    *v += value;
+                    return;
+                }
+                if let Some(v) = self
+                    .gauge_delta
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    *v += value;
+                    return;
+                }
+                if self.limiter.reserve() {
+                    self.gauge_delta
+                        .entry(name)
+                        .or_default()
+                        .insert(tag_map, value);
+                }
+            }
+            MetricType::Timer => {
+                if let Some(v) = self
+                    .histogram
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    v.add(value);
+                    return;
+                }
+                if self.limiter.reserve() {
+                    let mut v = HistogramStoreValue::new(self.config.histogram_buckets.clone());
+                    v.add(value);
+                    self.histogram.entry(name).or_default().insert(tag_map, v);
+                }
+            }
+        }
+    }
+
+    /// Convert the histograms to `_bucket`, `_count` and `_sum` counters
+    fn flush_histogram(&mut self) {
+        for (name, mut inner_map) in self.histogram.drain() {
+            let names = HistogramNames::new(&name);
+            for (tag_map, value) in inner_map.drain() {
+                value.add_to_counter(&names, &tag_map, &mut self.counter);
             }
         }
     }
 
     fn emit(&mut self) {
         let time = Utc::now();
+        self.flush_histogram();
+        self.limiter.reset();
+
+        let dropped = self.dropped_series.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            self.stats.add_dropped(dropped);
+            warn!(
+                "collector {}: {dropped} records of new series dropped as the max series limit reached",
+                self.config.name()
+            );
+        }
 
         macro_rules! emit_orig {
             ($map:ident, $metric_type:expr) => {
@@ -223,9 +312,11 @@ impl GlobalStore {
         if self.config.join_tags.is_empty() {
             emit_orig!(counter, MetricType::Counter);
             emit_orig!(gauge, MetricType::Gauge);
+            emit_orig!(gauge_delta, MetricType::GaugeDelta);
         } else {
             emit_join!(counter, MetricType::Counter);
             emit_join!(gauge, MetricType::Gauge);
+            emit_join!(gauge_delta, MetricType::GaugeDelta);
         }
     }
 }
@@ -0,0 +1,213 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use ahash::AHashMap;
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue};
+
+use crate::config::collector::aggregate::HistogramBuckets;
+use crate::types::{MetricName, MetricValue};
+
+const BUCKET_TAG_NAME: MetricTagName = unsafe { MetricTagName::new_static_unchecked("le") };
+static BUCKET_TAG_VALUE_INF: MetricTagValue =
+    unsafe { MetricTagValue::new_static_unchecked("inf") };
+
+type CounterMap = AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>;
+
+/// The names of the series that a histogram will be converted to
+pub(super) struct HistogramNames {
+    bucket: Arc<MetricName>,
+    count: Arc<MetricName>,
+    sum: Arc<MetricName>,
+}
+
+impl HistogramNames {
+    pub(super) fn new(name: &MetricName) -> Self {
+        let new_name = |suffix: &str| {
+            let mut name = name.clone();
+            name.add_suffix(suffix);
+            Arc::new(name)
+        };
+        HistogramNames {
+            bucket: new_name("_bucket"),
+            count: new_name("_count"),
+            sum: new_name("_sum"),
+        }
+    }
+}
+
+/// Aggregate the timer samples in an emit interval
+pub(super) struct HistogramStoreValue {
+    buckets: HistogramBuckets,
+    /// the sample count in each bucket, not cumulative, the last one is for the +Inf bucket
+    counts: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+impl HistogramStoreValue {
+    pub(super) fn new(buckets: HistogramBuckets) -> Self {
+        let counts = vec![0; buckets.bounds().len() + 1];
+        HistogramStoreValue {
+            buckets,
+            counts,
+            count: 0,
+            sum: 0.0,
+        }
+    }
+
+    pub(super) fn add(&mut self, value: MetricValue) {
+        let v = value.as_f64();
+        // the bucket with the smallest upper bound that is not less than the value
+        let i = self.buckets.bounds().partition_point(|bound| *bound < v);
+        self.counts[i] += 1;
+        self.count += 1;
+        self.sum += v;
+    }
+
+    /// Convert to cumulative counters, which will be added to the counter map
+    pub(super) fn add_to_counter(
+        self,
+        names: &HistogramNames,
+        tag_map: &Arc<MetricTagMap>,
+        counter: &mut CounterMap,
+    ) {
+        let mut add_counter =
+            |name: &Arc<MetricName>, tag_map: Arc<MetricTagMap>, value: MetricValue| {
+                counter
+                    .entry(name.clone())
+                    .or_default()
+                    .entry(tag_map)
+                    .and_modify(|v| *v += value)
+                    .or_insert(value);
+            };
+
+        if !self.buckets.bounds().is_empty() {
+            let tag_values = self
+                .buckets
+                .tag_values()
+                .iter()
+                .chain(std::iter::once(&BUCKET_TAG_VALUE_INF));
+            let mut cumulative = 0;
+            for (count, tag_value) in self.counts.iter().zip(tag_values) {
+                cumulative += *count;
+                let mut bucket_tag_map = tag_map.as_ref().clone();
+                bucket_tag_map.insert(BUCKET_TAG_NAME, tag_value.clone());
+                add_counter(
+                    &names.bucket,
+                    Arc::new(bucket_tag_map),
+                    MetricValue::Unsigned(cumulative),
+                );
+            }
+        }
+        add_counter(
+            &names.count,
+            tag_map.clone(),
+            MetricValue::Unsigned(self.count),
+        );
+        add_counter(&names.sum, tag_map.clone(), MetricValue::Double(self.sum));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    use crate::config::collector::aggregate::AggregateCollectorConfig;
+
+    fn bucket_value(counter: &CounterMap, name: &str, le: &str) -> MetricValue {
+        let name = MetricName::parse(name).unwrap();
+        let mut tag_map = MetricTagMap::default();
+        tag_map.insert(BUCKET_TAG_NAME, MetricTagValue::from_str(le).unwrap());
+        *counter.get(&name).unwrap().get(&tag_map).unwrap()
+    }
+
+    #[test]
+    fn cumulative_buckets() {
+        let yaml = yaml_doc!(
+            r#"
+                name: test
+                histogram_buckets: [10, 50, 100]
+            "#
+        );
+        let config = AggregateCollectorConfig::parse(yaml.as_hash().unwrap(), None).unwrap();
+
+        let mut v = HistogramStoreValue::new(config.histogram_buckets.clone());
+        for sample in [1, 10, 11, 30, 50, 70, 100, 101, 500] {
+            v.add(MetricValue::Unsigned(sample));
+        }
+        v.add(MetricValue::Double(0.5));
+
+        let names = HistogramNames::new(&MetricName::parse("req.latency").unwrap());
+        let tag_map = Arc::new(MetricTagMap::default());
+        let mut counter = CounterMap::default();
+        v.add_to_counter(&names, &tag_map, &mut counter);
+
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "10.0"),
+            MetricValue::Unsigned(3)
+        );
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "50.0"),
+            MetricValue::Unsigned(6)
+        );
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "100.0"),
+            MetricValue::Unsigned(8)
+        );
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "inf"),
+            MetricValue::Unsigned(10)
+        );
+
+        let count_name = MetricName::parse("req.latency_count").unwrap();
+        assert_eq!(
+            counter.get(&count_name).unwrap().get(&tag_map),
+            Some(&MetricValue::Unsigned(10))
+        );
+        let sum_name = MetricName::parse("req.latency_sum").unwrap();
+        assert_eq!(
+            counter.get(&sum_name).unwrap().get(&tag_map),
+            Some(&MetricValue::Double(873.5))
+        );
+
+        // the next interval will be added to the existing counters
+        let mut v = HistogramStoreValue::new(config.histogram_buckets.clone());
+        v.add(MetricValue::Unsigned(20));
+        v.add_to_counter(&names, &tag_map, &mut counter);
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "10.0"),
+            MetricValue::Unsigned(3)
+        );
+        assert_eq!(
+            bucket_value(&counter, "req.latency_bucket", "50.0"),
+            MetricValue::Unsigned(7)
+        );
+    }
+
+    #[test]
+    fn no_buckets() {
+        let mut v = HistogramStoreValue::new(HistogramBuckets::default());
+        v.add(MetricValue::Unsigned(1));
+        v.add(MetricValue::Unsigned(2));
+
+        let names = HistogramNames::new(&MetricName::parse("req.latency").unwrap());
+        let tag_map = Arc::new(MetricTagMap::default());
+        let mut counter = CounterMap::default();
+        v.add_to_counter(&names, &tag_map, &mut counter);
+        assert_eq!(counter.len(), 2);
+        let count_name = MetricName::parse("req.latency_count").unwrap();
+        assert_eq!(
+            counter.get(&count_name).unwrap().get(&tag_map),
+            Some(&MetricValue::Unsigned(2))
+        );
+    }
+}
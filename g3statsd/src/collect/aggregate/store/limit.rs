@@ -0,0 +1,72 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Limit the number of distinct series stored in an emit interval
+pub(super) struct SeriesLimiter {
+    max: Option<NonZeroUsize>,
+    count: usize,
+    /// the count of records dropped, shared by all stores of the same collector
+    dropped: Arc<AtomicU64>,
+}
+
+impl SeriesLimiter {
+    pub(super) fn new(max: Option<NonZeroUsize>, dropped: Arc<AtomicU64>) -> Self {
+        SeriesLimiter {
+            max,
+            count: 0,
+            dropped,
+        }
+    }
+
+    pub(super) fn set_max(&mut self, max: Option<NonZeroUsize>) {
+        self.max = max;
+    }
+
+    /// Reserve space for a new series, return false and count the drop if the limit is reached
+    pub(super) fn reserve(&mut self) -> bool {
+        if let Some(max) = self.max
+            && self.count >= max.get()
+        {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        self.count += 1;
+        true
+    }
+
+    /// Should be called after all the stored series have been emitted
+    pub(super) fn reset(&mut self) {
+        self.count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve() {
+        let dropped = Arc::new(AtomicU64::new(0));
+        let mut limiter = SeriesLimiter::new(NonZeroUsize::new(2), dropped.clone());
+        assert!(limiter.reserve());
+        assert!(limiter.reserve());
+        assert!(!limiter.reserve());
+        assert!(!limiter.reserve());
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+
+        limiter.reset();
+        assert!(limiter.reserve());
+
+        limiter.set_max(None);
+        for _ in 0..10 {
+            assert!(limiter.reserve());
+        }
+        assert_eq!(dropped.load(Ordering::Relaxed), 2);
+    }
+}
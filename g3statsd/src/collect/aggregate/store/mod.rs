@@ -4,15 +4,22 @@
  */
 
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 
 use tokio::sync::{Semaphore, broadcast, mpsc};
 
+use crate::collect::CollectorStats;
 use crate::config::collector::aggregate::AggregateCollectorConfig;
 use crate::types::{MetricRecord, MetricType};
 
 mod timer;
 use timer::EmitTimer;
 
+mod histogram;
+
+mod limit;
+use limit::SeriesLimiter;
+
 mod global;
 use global::GlobalStore;
 
@@ -28,19 +35,24 @@ enum Command {
 pub(super) struct AggregateHandle {
     worker: Vec<mpsc::UnboundedSender<Command>>,
     global: mpsc::UnboundedSender<Command>,
+    stats: Arc<CollectorStats>,
 }
 
 impl AggregateHandle {
     pub(super) fn spawn_new(
         config: Arc<AggregateCollectorConfig>,
         cfg_receiver: broadcast::Receiver<Arc<AggregateCollectorConfig>>,
+        stats: Arc<CollectorStats>,
     ) -> Arc<Self> {
         let (global_cmd_sender, global_cmd_receiver) = mpsc::unbounded_channel();
+        let dropped_series = Arc::new(AtomicU64::new(0));
 
         let global_store = GlobalStore::new(
             config.clone(),
             cfg_receiver.resubscribe(),
             global_cmd_receiver,
+            dropped_series.clone(),
+            stats.clone(),
         );
         tokio::spawn(global_store.into_running());
 
@@ -48,7 +60,13 @@ impl AggregateHandle {
         let _: Result<usize, ()> = g3_daemon::runtime::worker::foreach(|handle| {
             let (worker_sender, worker_receiver) = mpsc::unbounded_channel();
 
-            let worker_store = WorkerStore::new(worker_receiver, global_cmd_sender.clone());
+            let worker_store = WorkerStore::new(
+                &config,
+                cfg_receiver.resubscribe(),
+                worker_receiver,
+                global_cmd_sender.clone(),
+                dropped_series.clone(),
+            );
             handle.handle.spawn(worker_store.into_running());
             worker_senders.push(worker_sender);
             Ok(())
@@ -57,6 +75,7 @@ impl AggregateHandle {
         let handle = Arc::new(AggregateHandle {
             worker: worker_senders,
             global: global_cmd_sender,
+            stats,
         });
 
         let emit_timer = EmitTimer::new(config, handle.clone(), cfg_receiver);
@@ -72,16 +91,16 @@ impl AggregateHandle {
                     && let Some(sender) = self.worker.get(id)
                 {
                     if sender.send(Command::Add(record)).is_err() {
-                        // TODO add stats
+                        self.stats.add_dropped(1);
                     }
                     return;
                 }
             }
-            MetricType::Gauge => {}
+            MetricType::Gauge | MetricType::GaugeDelta | MetricType::Timer => {}
         }
 
         if self.global.send(Command::Add(record)).is_err() {
-            // TODO add stats
+            self.stats.add_dropped(1);
         }
     }
 }
@@ -4,32 +4,41 @@
  */
 
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 
 use ahash::AHashMap;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 
 use g3_types::metrics::MetricTagMap;
 
-use super::Command;
+use super::{Command, SeriesLimiter};
+use crate::config::collector::aggregate::AggregateCollectorConfig;
 use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
 
 const BATCH_SIZE: usize = 128;
 
 pub(super) struct WorkerStore {
+    cfg_receiver: broadcast::Receiver<Arc<AggregateCollectorConfig>>,
     receiver: mpsc::UnboundedReceiver<Command>,
     global_sender: mpsc::UnboundedSender<Command>,
+    limiter: SeriesLimiter,
 
     counter: AHashMap<Arc<MetricName>, AHashMap<Arc<MetricTagMap>, MetricValue>>,
 }
 
 impl WorkerStore {
     pub(super) fn new(
+        config: &AggregateCollectorConfig,
+        cfg_receiver: broadcast::Receiver<Arc<AggregateCollectorConfig>>,
         receiver: mpsc::UnboundedReceiver<Command>,
         global_sender: mpsc::UnboundedSender<Command>,
+        dropped_series: Arc<AtomicU64>,
     ) -> Self {
         WorkerStore {
+            cfg_receiver,
             receiver,
             global_sender,
+            limiter: SeriesLimiter::new(config.max_series, dropped_series),
             counter: Default::default(),
         }
     }
@@ -37,26 +46,50 @@ impl WorkerStore {
     pub(super) async fn into_running(mut self) {
         let mut buffer = Vec::with_capacity(BATCH_SIZE);
         loop {
-            let nr = self.receiver.recv_many(&mut buffer, BATCH_SIZE).await;
-            if nr == 0 {
-                break;
-            }
+            tokio::select! {
+                biased;
 
-            for cmd in buffer.drain(..) {
-                match cmd {
-                    Command::Add(record) => self.add_record(record),
-                    Command::Sync(semaphore) => {
+                r = self.cfg_receiver.recv() => {
+                    match r {
+                        Ok(config) => self.limiter.set_max(config.max_series),
+                        Err(broadcast::error::RecvError::Closed) => break,
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    }
+                }
+                nr = self.receiver.recv_many(&mut buffer, BATCH_SIZE) => {
+                    if nr == 0 {
                         self.emit();
-                        semaphore.add_permits(1);
+                        return;
                     }
-                    Command::Emit => unreachable!(),
+                    self.handle_cmd(&mut buffer);
                 }
             }
         }
 
+        loop {
+            let nr = self.receiver.recv_many(&mut buffer, BATCH_SIZE).await;
+            if nr == 0 {
+                break;
+            }
+            self.handle_cmd(&mut buffer);
+        }
+
         self.emit();
     }
 
+    fn handle_cmd(&mut self, buffer: &mut Vec<Command>) {
+        for cmd in buffer.drain(..) {
+            match cmd {
+                Command::Add(record) => self.add_record(record),
+                Command::Sync(semaphore) => {
+                    self.emit();
+                    semaphore.add_permits(1);
+                }
+                Command::Emit => unreachable!(),
+            }
+        }
+    }
+
     fn add_record(&mut self, record: MetricRecord) {
         match record.r#type {
             MetricType::Counter => {
@@ -67,20 +100,26 @@ impl WorkerStore {
                     value,
                 } = record;
 
-                self.counter
-                    .entry(name)
-                    .or_default()
-                    .entry(tag_map)
-                    .and_modify(|v| *v += value)
-                    .or_insert(value);
+                if let Some(v) = self
+                    .counter
+                    .get_mut(&name)
+                    .and_then(|inner| inner.get_mut(&tag_map))
+                {
+                    *v += value;
+                    return;
+                }
+                if self.limiter.reserve() {
+                    self.counter.entry(name).or_default().insert(tag_map, value);
+                }
             }
-            MetricType::Gauge => {
+            MetricType::Gauge | MetricType::GaugeDelta | MetricType::Timer => {
                 let _ = self.global_sender.send(Command::Add(record));
             }
         }
     }
 
     fn emit(&mut self) {
+        self.limiter.reset();
         for (name, mut inner_map) in self.counter.drain() {
             for (tag_map, value) in inner_map.drain() {
                 let record = MetricRecord {
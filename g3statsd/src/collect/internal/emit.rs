@@ -3,37 +3,160 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
 use tokio::sync::broadcast;
 use tokio::time::Instant;
 
+use g3_daemon::metrics::reload::{ReloadFailureType, ReloadStatsSnapshot};
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue, NodeName};
+
+use crate::collect::{ArcCollector, CollectorStatsSnapshot};
 use crate::config::collector::internal::InternalCollectorConfig;
+use crate::export::ArcExporter;
+use crate::runtime::export::AggregateExportStatsSnapshot;
+use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
+
+const TAG_COLLECTOR: MetricTagName = unsafe { MetricTagName::new_static_unchecked("collector") };
+const TAG_EXPORTER: MetricTagName = unsafe { MetricTagName::new_static_unchecked("exporter") };
+const TAG_FAILURE_TYPE: MetricTagName =
+    unsafe { MetricTagName::new_static_unchecked("failure_type") };
+
+struct MetricNames {
+    input_records: Arc<MetricName>,
+    input_rate: Arc<MetricName>,
+    parse_errors: Arc<MetricName>,
+    parse_error_rate: Arc<MetricName>,
+    dropped_records: Arc<MetricName>,
+    counter_resets: Arc<MetricName>,
+    evicted_series: Arc<MetricName>,
+    reload_success: Arc<MetricName>,
+    reload_failure: Arc<MetricName>,
+    reload_last_success_time: Arc<MetricName>,
+}
+
+impl MetricNames {
+    fn new() -> Self {
+        // all the names are valid
+        let name = |s: &str| Arc::new(MetricName::parse(s).unwrap());
+        MetricNames {
+            input_records: name("g3statsd.collector.input_records"),
+            input_rate: name("g3statsd.collector.input_rate"),
+            parse_errors: name("g3statsd.collector.parse_errors"),
+            parse_error_rate: name("g3statsd.collector.parse_error_rate"),
+            dropped_records: name("g3statsd.collector.dropped_records"),
+            counter_resets: name("g3statsd.exporter.counter_resets"),
+            evicted_series: name("g3statsd.exporter.evicted_series"),
+            reload_success: name("g3statsd.reload.success"),
+            reload_failure: name("g3statsd.reload.failure"),
+            reload_last_success_time: name("g3statsd.reload.last_success_time"),
+        }
+    }
+}
+
+struct NodeState<T> {
+    tag_map: Arc<MetricTagMap>,
+    last: T,
+}
+
+impl<T> NodeState<T> {
+    fn new(tag_name: MetricTagName, name: &NodeName, snapshot: T) -> Self {
+        let mut tag_map = MetricTagMap::default();
+        // the node name is a valid tag value
+        if let Ok(value) = MetricTagValue::from_str(name.as_str()) {
+            tag_map.insert(tag_name, value);
+        }
+        NodeState {
+            tag_map: Arc::new(tag_map),
+            last: snapshot,
+        }
+    }
+}
+
+struct ReloadState {
+    empty_tag_map: Arc<MetricTagMap>,
+    failure_tag_maps: Vec<(ReloadFailureType, Arc<MetricTagMap>)>,
+    last: ReloadStatsSnapshot,
+}
+
+impl ReloadState {
+    fn new() -> Self {
+        let failure_tag_maps = ReloadFailureType::ALL
+            .into_iter()
+            .map(|failure_type| {
+                let mut tag_map = MetricTagMap::default();
+                // the failure type is a valid tag value
+                if let Ok(value) = MetricTagValue::from_str(failure_type.as_str()) {
+                    tag_map.insert(TAG_FAILURE_TYPE, value);
+                }
+                (failure_type, Arc::new(tag_map))
+            })
+            .collect();
+        ReloadState {
+            empty_tag_map: Arc::new(MetricTagMap::default()),
+            failure_tag_maps,
+            last: ReloadStatsSnapshot::default(),
+        }
+    }
+}
 
 pub(super) struct InternalEmitter {
     reload_receiver: broadcast::Receiver<Arc<InternalCollectorConfig>>,
+    next: Option<ArcCollector>,
+    exporters: Vec<ArcExporter>,
+    names: MetricNames,
+    collectors: AHashMap<NodeName, NodeState<CollectorStatsSnapshot>>,
+    exporters_state: AHashMap<NodeName, NodeState<AggregateExportStatsSnapshot>>,
+    reload: ReloadState,
 }
 
 impl InternalEmitter {
     pub(super) fn new(reload_receiver: broadcast::Receiver<Arc<InternalCollectorConfig>>) -> Self {
-        InternalEmitter { reload_receiver }
+        InternalEmitter {
+            reload_receiver,
+            next: None,
+            exporters: Vec::new(),
+            names: MetricNames::new(),
+            collectors: AHashMap::default(),
+            exporters_state: AHashMap::default(),
+            reload: ReloadState::new(),
+        }
+    }
+
+    fn update_config(&mut self, config: &InternalCollectorConfig) {
+        self.next = config
+            .next
+            .as_ref()
+            .map(|name| crate::collect::get_or_insert_default(name));
+        self.exporters = config
+            .exporters
+            .iter()
+            .map(crate::export::get_or_insert_default)
+            .collect();
     }
 
     pub(super) async fn into_running(mut self, mut config: Arc<InternalCollectorConfig>) {
+        self.update_config(&config);
         let mut interval = tokio::time::interval(config.emit_interval);
 
         let mut last_instant = Instant::now();
         loop {
             tokio::select! {
                 i = interval.tick() => {
+                    let elapsed = i.saturating_duration_since(last_instant);
                     last_instant = i;
-                    // TODO emit stats
+                    self.emit(elapsed);
                 }
                 r = self.reload_receiver.recv() => {
                     match r {
                         Ok(c) => {
                             let next_tick = last_instant + interval.period();
                             config = c;
+                            self.update_config(&config);
                             interval = tokio::time::interval_at(next_tick, config.emit_interval);
                         }
                         Err(broadcast::error::RecvError::Closed) => {
@@ -45,4 +168,175 @@ impl InternalEmitter {
             }
         }
     }
+
+    fn emit(&mut self, elapsed: Duration) {
+        let mut snapshots = Vec::new();
+        crate::collect::foreach_stats(|name, snapshot| snapshots.push((name.clone(), snapshot)));
+
+        let time = Utc::now();
+        let mut collectors = AHashMap::with_capacity(snapshots.len());
+        for (name, snapshot) in snapshots {
+            let Some(mut state) = self.collectors.remove(&name) else {
+                // no increments to emit for the newly found collectors
+                collectors.insert(name.clone(), NodeState::new(TAG_COLLECTOR, &name, snapshot));
+                continue;
+            };
+            let delta = snapshot.delta(&state.last);
+            state.last = snapshot;
+            self.emit_collector(time, elapsed, &state.tag_map, &delta);
+            collectors.insert(name, state);
+        }
+        // the removed collectors will be dropped here
+        self.collectors = collectors;
+
+        let mut snapshots = Vec::new();
+        crate::export::foreach_aggregate_stats(|name, snapshot| {
+            snapshots.push((name.clone(), snapshot))
+        });
+
+        let mut exporters = AHashMap::with_capacity(snapshots.len());
+        for (name, snapshot) in snapshots {
+            let Some(mut state) = self.exporters_state.remove(&name) else {
+                exporters.insert(name.clone(), NodeState::new(TAG_EXPORTER, &name, snapshot));
+                continue;
+            };
+            let delta = snapshot.delta(&state.last);
+            state.last = snapshot;
+            self.emit_exporter(time, &state.tag_map, &delta);
+            exporters.insert(name, state);
+        }
+        self.exporters_state = exporters;
+
+        let snapshot = g3_daemon::metrics::reload::snapshot();
+        let delta = snapshot.delta(&self.reload.last);
+        self.reload.last = snapshot;
+        self.emit_reload(time, &delta);
+    }
+
+    fn emit_collector(
+        &self,
+        time: DateTime<Utc>,
+        elapsed: Duration,
+        tag_map: &Arc<MetricTagMap>,
+        delta: &CollectorStatsSnapshot,
+    ) {
+        let secs = elapsed.as_secs_f64();
+        let rate = |v: u64| {
+            if secs > 0.0 {
+                MetricValue::Double(v as f64 / secs)
+            } else {
+                MetricValue::Double(0.0)
+            }
+        };
+
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.input_records,
+            tag_map,
+            MetricValue::Unsigned(delta.input_records),
+        );
+        self.send(
+            time,
+            MetricType::Gauge,
+            &self.names.input_rate,
+            tag_map,
+            rate(delta.input_records),
+        );
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.parse_errors,
+            tag_map,
+            MetricValue::Unsigned(delta.parse_errors),
+        );
+        self.send(
+            time,
+            MetricType::Gauge,
+            &self.names.parse_error_rate,
+            tag_map,
+            rate(delta.parse_errors),
+        );
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.dropped_records,
+            tag_map,
+            MetricValue::Unsigned(delta.dropped_records),
+        );
+    }
+
+    fn emit_exporter(
+        &self,
+        time: DateTime<Utc>,
+        tag_map: &Arc<MetricTagMap>,
+        delta: &AggregateExportStatsSnapshot,
+    ) {
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.counter_resets,
+            tag_map,
+            MetricValue::Unsigned(delta.counter_resets),
+        );
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.evicted_series,
+            tag_map,
+            MetricValue::Unsigned(delta.evicted_series),
+        );
+    }
+
+    fn emit_reload(&self, time: DateTime<Utc>, delta: &ReloadStatsSnapshot) {
+        self.send(
+            time,
+            MetricType::Counter,
+            &self.names.reload_success,
+            &self.reload.empty_tag_map,
+            MetricValue::Unsigned(delta.success),
+        );
+        for (failure_type, tag_map) in &self.reload.failure_tag_maps {
+            self.send(
+                time,
+                MetricType::Counter,
+                &self.names.reload_failure,
+                tag_map,
+                MetricValue::Unsigned(delta.failure(*failure_type)),
+            );
+        }
+        if delta.last_success_time > 0 {
+            self.send(
+                time,
+                MetricType::Gauge,
+                &self.names.reload_last_success_time,
+                &self.reload.empty_tag_map,
+                MetricValue::Unsigned(delta.last_success_time),
+            );
+        }
+    }
+
+    /// The records sent to the next collector will also be counted in its input stats,
+    /// but the number of them is fixed in each emit interval, so there will be no amplification.
+    fn send(
+        &self,
+        time: DateTime<Utc>,
+        r#type: MetricType,
+        name: &Arc<MetricName>,
+        tag_map: &Arc<MetricTagMap>,
+        value: MetricValue,
+    ) {
+        let record = MetricRecord {
+            r#type,
+            name: name.clone(),
+            tag_map: tag_map.clone(),
+            value,
+        };
+        for exporter in &self.exporters {
+            exporter.add_metric(time, &record);
+        }
+        if let Some(next) = &self.next {
+            next.add_metric(time, record, None);
+        }
+    }
 }
@@ -14,11 +14,14 @@ use crate::types::MetricRecord;
 
 mod registry;
 use registry::CollectorRegistry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{foreach_stats, get_names, get_or_insert_default};
+
+mod stats;
+pub(crate) use stats::{CollectorStats, CollectorStatsSnapshot};
 
 mod ops;
 pub use ops::load_all;
-pub(crate) use ops::{reload, update_dependency_to_exporter};
+pub(crate) use ops::{diff_all, reload, update_dependency_to_exporter};
 
 mod aggregate;
 mod discard;
@@ -31,6 +34,11 @@ pub(crate) trait Collector {
     fn r#type(&self) -> &'static str;
 
     fn add_metric(&self, time: DateTime<Utc>, record: MetricRecord, worker_id: Option<usize>);
+
+    /// Get the input stats, only for the collectors that handle the records
+    fn stats(&self) -> Option<&CollectorStats> {
+        None
+    }
 }
 
 trait CollectorInternal: Collector {
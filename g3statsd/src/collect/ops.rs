@@ -3,7 +3,8 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use async_recursion::async_recursion;
@@ -52,6 +53,35 @@ pub async fn load_all() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Get the action for each collector if the loaded config is applied, without really applying it
+pub(crate) async fn diff_all(
+    all_config: &BTreeMap<NodeName, Arc<AnyCollectorConfig>>,
+) -> Vec<(NodeName, &'static str)> {
+    let _guard = COLLECTOR_OPS_LOCK.lock().await;
+
+    let mut new_names = HashSet::<NodeName>::new();
+    let mut actions = Vec::new();
+
+    for config in all_config.values() {
+        let name = config.name();
+        new_names.insert(name.clone());
+        let action = match registry::get_config(name) {
+            Some(old) => old.diff_action(config).as_str(),
+            None => "spawn_new",
+        };
+        actions.push((name.clone(), action));
+    }
+
+    for name in registry::get_names() {
+        if !new_names.contains(&name) {
+            actions.push((name, "delete"));
+        }
+    }
+
+    actions.sort_by(|a, b| a.0.cmp(&b.0));
+    actions
+}
+
 pub(crate) async fn reload(
     name: &NodeName,
     position: Option<YamlDocPosition>,
@@ -11,7 +11,7 @@ use foldhash::fast::FixedState;
 
 use g3_types::metrics::NodeName;
 
-use super::{ArcCollector, ArcCollectorInternal};
+use super::{ArcCollector, ArcCollectorInternal, CollectorStatsSnapshot};
 use crate::config::collector::AnyCollectorConfig;
 
 static RUNTIME_COLLECTOR_REGISTRY: Mutex<CollectorRegistry> = Mutex::new(CollectorRegistry::new());
@@ -102,6 +102,19 @@ where
     r.foreach(f);
 }
 
+/// Take a snapshot of the stats of all collectors that have them
+pub(crate) fn foreach_stats<F>(mut f: F)
+where
+    F: FnMut(&NodeName, CollectorStatsSnapshot),
+{
+    let r = RUNTIME_COLLECTOR_REGISTRY.lock().unwrap();
+    r.foreach(|name, collector| {
+        if let Some(stats) = collector.stats() {
+            f(name, stats.snapshot());
+        }
+    });
+}
+
 pub(crate) fn get_names() -> HashSet<NodeName> {
     let r = RUNTIME_COLLECTOR_REGISTRY.lock().unwrap();
     r.get_names()
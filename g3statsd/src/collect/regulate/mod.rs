@@ -10,7 +10,10 @@ use chrono::{DateTime, Utc};
 
 use g3_types::metrics::NodeName;
 
-use super::{ArcCollector, ArcCollectorInternal, Collector, CollectorInternal, CollectorRegistry};
+use super::{
+    ArcCollector, ArcCollectorInternal, Collector, CollectorInternal, CollectorRegistry,
+    CollectorStats,
+};
 use crate::config::collector::regulate::RegulateCollectorConfig;
 use crate::config::collector::{AnyCollectorConfig, CollectorConfig};
 use crate::export::ArcExporter;
@@ -20,10 +23,15 @@ pub(crate) struct RegulateCollector {
     config: RegulateCollectorConfig,
     next: Option<ArcCollector>,
     exporters: Vec<ArcExporter>,
+    stats: Arc<CollectorStats>,
 }
 
 impl RegulateCollector {
-    fn new<F>(config: RegulateCollectorConfig, fetch_collector: F) -> Self
+    fn new<F>(
+        config: RegulateCollectorConfig,
+        fetch_collector: F,
+        stats: Arc<CollectorStats>,
+    ) -> Self
     where
         F: FnMut(&NodeName) -> ArcCollector,
     {
@@ -38,13 +46,18 @@ impl RegulateCollector {
             config,
             next,
             exporters,
+            stats,
         }
     }
 
     pub(crate) fn prepare_initial(
         config: RegulateCollectorConfig,
     ) -> anyhow::Result<ArcCollectorInternal> {
-        let server = RegulateCollector::new(config, crate::collect::get_or_insert_default);
+        let server = RegulateCollector::new(
+            config,
+            crate::collect::get_or_insert_default,
+            Arc::new(CollectorStats::default()),
+        );
         Ok(Arc::new(server))
     }
 
@@ -54,9 +67,11 @@ impl RegulateCollector {
         registry: &mut CollectorRegistry,
     ) -> anyhow::Result<ArcCollectorInternal> {
         if let AnyCollectorConfig::Regulate(config) = config {
-            Ok(Arc::new(RegulateCollector::new(config, |name| {
-                registry.get_or_insert_default(name)
-            })))
+            Ok(Arc::new(RegulateCollector::new(
+                config,
+                |name| registry.get_or_insert_default(name),
+                self.stats.clone(),
+            )))
         } else {
             Err(anyhow!(
                 "config type mismatch: expect {}, actual {}",
@@ -79,6 +94,7 @@ impl Collector for RegulateCollector {
     }
 
     fn add_metric(&self, time: DateTime<Utc>, mut record: MetricRecord, worker_id: Option<usize>) {
+        self.stats.add_input();
         if let Some(prefix) = &self.config.prefix {
             let name = Arc::make_mut(&mut record.name);
             name.add_prefix(prefix);
@@ -98,6 +114,10 @@ impl Collector for RegulateCollector {
             next.add_metric(time, record, worker_id);
         }
     }
+
+    fn stats(&self) -> Option<&CollectorStats> {
+        Some(&self.stats)
+    }
 }
 
 impl CollectorInternal for RegulateCollector {
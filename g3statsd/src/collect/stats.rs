@@ -0,0 +1,83 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The input stats of a collector, which will be emitted by the internal collectors
+#[derive(Default)]
+pub(crate) struct CollectorStats {
+    input_records: AtomicU64,
+    parse_errors: AtomicU64,
+    dropped_records: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct CollectorStatsSnapshot {
+    pub(crate) input_records: u64,
+    pub(crate) parse_errors: u64,
+    pub(crate) dropped_records: u64,
+}
+
+impl CollectorStats {
+    #[inline]
+    pub(crate) fn add_input(&self) {
+        self.input_records.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn add_parse_error(&self) {
+        self.parse_errors.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn add_dropped(&self, count: u64) {
+        self.dropped_records.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> CollectorStatsSnapshot {
+        CollectorStatsSnapshot {
+            input_records: self.input_records.load(Ordering::Relaxed),
+            parse_errors: self.parse_errors.load(Ordering::Relaxed),
+            dropped_records: self.dropped_records.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl CollectorStatsSnapshot {
+    /// Get the increments since the last snapshot
+    pub(crate) fn delta(&self, last: &Self) -> Self {
+        CollectorStatsSnapshot {
+            input_records: self.input_records.wrapping_sub(last.input_records),
+            parse_errors: self.parse_errors.wrapping_sub(last.parse_errors),
+            dropped_records: self.dropped_records.wrapping_sub(last.dropped_records),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta() {
+        let stats = CollectorStats::default();
+        stats.add_input();
+        stats.add_input();
+        stats.add_parse_error();
+        let last = stats.snapshot();
+
+        stats.add_input();
+        stats.add_dropped(3);
+        let delta = stats.snapshot().delta(&last);
+        assert_eq!(
+            delta,
+            CollectorStatsSnapshot {
+                input_records: 1,
+                parse_errors: 0,
+                dropped_records: 3,
+            }
+        );
+    }
+}
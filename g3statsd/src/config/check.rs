@@ -0,0 +1,79 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::Context;
+
+use g3_daemon::opts::CheckReport;
+use g3_types::metrics::NodeName;
+
+use super::collector::AnyCollectorConfig;
+
+/// Check all the loaded config nodes and the references between them
+pub fn check() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    let exporters = check_exporter(&mut report);
+    let collectors = check_collector(&mut report, &exporters);
+    check_importer(&mut report, &collectors);
+
+    report
+}
+
+fn check_exporter(report: &mut CheckReport) -> BTreeSet<NodeName> {
+    let all_exporter = crate::config::exporter::get_all();
+    for c in &all_exporter {
+        report.add_node("exporter", c.name().as_str(), c.exporter_type(), Vec::new());
+    }
+    all_exporter.iter().map(|c| c.name().clone()).collect()
+}
+
+fn check_collector(report: &mut CheckReport, exporters: &BTreeSet<NodeName>) -> BTreeSet<NodeName> {
+    let all_collector = match crate::config::collector::get_all_sorted()
+        .context("failed to get all collector config")
+    {
+        Ok(v) => v,
+        Err(e) => {
+            report.add_error("collector", e);
+            return BTreeSet::new();
+        }
+    };
+
+    let names: BTreeSet<NodeName> = all_collector.iter().map(|c| c.name().clone()).collect();
+    for c in &all_collector {
+        let mut errors = Vec::new();
+        if let Some(d) = c.dependent_collector() {
+            for v in d {
+                if !names.contains(&v) {
+                    errors.push(format!("dependent collector {v} not found"));
+                }
+            }
+        }
+        let used_exporters: &[NodeName] = match c.as_ref() {
+            AnyCollectorConfig::Aggregate(c) => &c.exporters,
+            AnyCollectorConfig::Internal(c) => &c.exporters,
+            _ => &[],
+        };
+        for v in used_exporters {
+            if !exporters.contains(v) {
+                errors.push(format!("exporter {v} not found"));
+            }
+        }
+        report.add_node("collector", c.name().as_str(), c.collector_type(), errors);
+    }
+    names
+}
+
+fn check_importer(report: &mut CheckReport, collectors: &BTreeSet<NodeName>) {
+    for c in crate::config::importer::get_all() {
+        let mut errors = Vec::new();
+        let v = c.collector();
+        if !v.is_empty() && !collectors.contains(v) {
+            errors.push(format!("collector {v} not found"));
+        }
+        report.add_node("importer", c.name().as_str(), c.importer_type(), errors);
+    }
+}
@@ -4,18 +4,76 @@
  */
 
 use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
 use yaml_rust::{Yaml, yaml};
 
-use g3_types::metrics::{MetricTagName, NodeName};
+use g3_types::metrics::{MetricTagName, MetricTagValue, NodeName};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyCollectorConfig, CollectorConfig, CollectorConfigDiffAction};
 
 const COLLECTOR_CONFIG_TYPE: &str = "Aggregate";
 
+/// The upper bounds of the histogram buckets, which should be finite and strictly increasing
+#[derive(Clone, Debug, Default)]
+pub(crate) struct HistogramBuckets {
+    bounds: Arc<[f64]>,
+    /// the value of the `le` tag for each bound
+    tag_values: Arc<[MetricTagValue]>,
+}
+
+impl HistogramBuckets {
+    fn new(bounds: Vec<f64>) -> anyhow::Result<Self> {
+        let mut tag_values = Vec::with_capacity(bounds.len());
+        for (i, bound) in bounds.iter().enumerate() {
+            if !bound.is_finite() {
+                return Err(anyhow!("bucket bound {bound} is not finite"));
+            }
+            if i > 0 && *bound <= bounds[i - 1] {
+                return Err(anyhow!(
+                    "bucket bound {bound} should be greater than the previous one {}",
+                    bounds[i - 1]
+                ));
+            }
+            let s = ryu::Buffer::new().format_finite(*bound).to_string();
+            let value = MetricTagValue::from_str(&s)
+                .map_err(|e| anyhow!("unable to use bucket bound {bound} as tag value: {e}"))?;
+            tag_values.push(value);
+        }
+        Ok(HistogramBuckets {
+            bounds: Arc::from(bounds),
+            tag_values: Arc::from(tag_values),
+        })
+    }
+
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let bounds = g3_yaml::value::as_list(value, g3_yaml::value::as_f64)?;
+        HistogramBuckets::new(bounds)
+    }
+
+    pub(crate) fn bounds(&self) -> &[f64] {
+        &self.bounds
+    }
+
+    pub(crate) fn tag_values(&self) -> &[MetricTagValue] {
+        &self.tag_values
+    }
+}
+
+impl PartialEq for HistogramBuckets {
+    fn eq(&self, other: &Self) -> bool {
+        self.bounds == other.bounds
+    }
+}
+
+// the bounds are always finite
+impl Eq for HistogramBuckets {}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct AggregateCollectorConfig {
     name: NodeName,
@@ -24,6 +82,8 @@ pub(crate) struct AggregateCollectorConfig {
     pub(crate) join_tags: Vec<MetricTagName>,
     pub(crate) next: Option<NodeName>,
     pub(crate) exporters: Vec<NodeName>,
+    pub(crate) histogram_buckets: HistogramBuckets,
+    pub(crate) max_series: Option<NonZeroUsize>,
 }
 
 impl AggregateCollectorConfig {
@@ -35,6 +95,8 @@ impl AggregateCollectorConfig {
             join_tags: Vec::new(),
             next: None,
             exporters: Vec::new(),
+            histogram_buckets: HistogramBuckets::default(),
+            max_series: None,
         }
     }
 
@@ -77,6 +139,17 @@ impl AggregateCollectorConfig {
                     .context(format!("invalid list of exporter names for key {k}"))?;
                 Ok(())
             }
+            "histogram_buckets" => {
+                self.histogram_buckets = HistogramBuckets::parse_yaml(v)
+                    .context(format!("invalid histogram buckets value for key {k}"))?;
+                Ok(())
+            }
+            "max_series" => {
+                let max = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                self.max_series = Some(max);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -121,3 +194,28 @@ impl CollectorConfig for AggregateCollectorConfig {
         Some(set)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn histogram_buckets() {
+        let yaml = yaml_doc!("[0.005, 0.1, 1, 2.5, 100]");
+        let buckets = HistogramBuckets::parse_yaml(&yaml).unwrap();
+        assert_eq!(buckets.bounds(), &[0.005, 0.1, 1.0, 2.5, 100.0]);
+        let tag_values: Vec<&str> = buckets.tag_values().iter().map(|v| v.as_str()).collect();
+        assert_eq!(tag_values, ["0.005", "0.1", "1.0", "2.5", "100.0"]);
+
+        let yaml = yaml_doc!("[1, 1]");
+        assert!(HistogramBuckets::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!("[2, 1]");
+        assert!(HistogramBuckets::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!("[1, 1e400]");
+        assert!(HistogramBuckets::parse_yaml(&yaml).is_err());
+    }
+}
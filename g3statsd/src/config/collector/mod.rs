@@ -3,7 +3,8 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
-use std::collections::BTreeSet;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -33,6 +34,17 @@ pub(crate) enum CollectorConfigDiffAction {
     Update,
 }
 
+impl CollectorConfigDiffAction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            CollectorConfigDiffAction::NoAction => "no_action",
+            CollectorConfigDiffAction::SpawnNew => "spawn_new",
+            CollectorConfigDiffAction::Reload => "reload",
+            CollectorConfigDiffAction::Update => "update",
+        }
+    }
+}
+
 pub(crate) trait CollectorConfig {
     fn name(&self) -> &NodeName;
     fn position(&self) -> Option<YamlDocPosition>;
@@ -59,22 +71,30 @@ pub(crate) enum AnyCollectorConfig {
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
-    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
-    parser.foreach_map(v, |map, position| {
-        let collector = load_collector(map, position)?;
+    for collector in parse_all(v, conf_dir)? {
         if let Some(old_collector) = registry::add(collector) {
-            Err(anyhow!(
+            return Err(anyhow!(
                 "collector with name {} already exists",
                 old_collector.name()
-            ))
-        } else {
-            Ok(())
+            ));
         }
-    })?;
+    }
     build_topology_map()?;
     Ok(())
 }
 
+/// Parse all the collector config without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyCollectorConfig>> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let collector = load_collector(map, position)?;
+        all.borrow_mut().push(collector);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyCollectorConfig> {
     let doc = g3_yaml::load_doc(position)?;
     if let Yaml::Hash(map) = doc {
@@ -140,6 +160,22 @@ fn build_topology_map() -> anyhow::Result<TopoMap> {
     Ok(topo_map)
 }
 
+/// Check the dependency between the collectors which are not added to the registry
+pub(crate) fn check_topology(
+    all: &BTreeMap<NodeName, Arc<AnyCollectorConfig>>,
+) -> anyhow::Result<()> {
+    let mut topo_map = TopoMap::default();
+
+    for name in all.keys() {
+        topo_map.add_node(name, &|name| {
+            let conf = all.get(name)?;
+            conf.dependent_collector()
+        })?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn get_all_sorted() -> anyhow::Result<Vec<Arc<AnyCollectorConfig>>> {
     let topo_map = build_topology_map()?;
     let sorted_nodes = topo_map.sorted_nodes();
@@ -12,7 +12,10 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction};
-use crate::runtime::export::StreamExportConfig;
+use crate::runtime::export::{
+    AggregateEmitJitter, AggregateRollupConfig, AggregateTagTransform, CounterEmitMode,
+    GraphiteSanitizeRules, MetricSanitizeAction, MetricSanitizeRules, StreamExportConfig,
+};
 use crate::types::MetricName;
 
 const EXPORTER_CONFIG_TYPE: &str = "Graphite";
@@ -22,9 +25,16 @@ pub(crate) struct GraphiteExporterConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     pub(crate) emit_interval: Duration,
+    pub(crate) rollup: AggregateRollupConfig,
+    pub(crate) stale_timeout: Option<Duration>,
+    pub(crate) emit_jitter: AggregateEmitJitter,
+    pub(crate) tag_transform: AggregateTagTransform,
     pub(crate) stream_export: StreamExportConfig,
     pub(crate) prefix: Option<MetricName>,
     pub(crate) global_tags: MetricTagMap,
+    pub(crate) counter_mode: CounterEmitMode,
+    pub(crate) align_timestamp: bool,
+    pub(crate) sanitize_action: Option<MetricSanitizeAction>,
 }
 
 impl GraphiteExporterConfig {
@@ -33,9 +43,16 @@ impl GraphiteExporterConfig {
             name: NodeName::default(),
             position,
             emit_interval: Duration::from_secs(10),
+            rollup: AggregateRollupConfig::default(),
+            stale_timeout: None,
+            emit_jitter: AggregateEmitJitter::default(),
+            tag_transform: AggregateTagTransform::default(),
             stream_export: StreamExportConfig::new(2003),
             prefix: None,
             global_tags: MetricTagMap::default(),
+            counter_mode: CounterEmitMode::default(),
+            align_timestamp: false,
+            sanitize_action: None,
         }
     }
 
@@ -63,6 +80,27 @@ impl GraphiteExporterConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "rollup" => {
+                self.rollup = AggregateRollupConfig::parse_yaml(v)
+                    .context(format!("invalid aggregate rollup config value for key {k}"))?;
+                Ok(())
+            }
+            "stale_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.stale_timeout = Some(timeout);
+                Ok(())
+            }
+            "emit_jitter" => {
+                self.emit_jitter = AggregateEmitJitter::parse_yaml(v)
+                    .context(format!("invalid emit jitter value for key {k}"))?;
+                Ok(())
+            }
+            "tag_transform" => {
+                self.tag_transform = AggregateTagTransform::parse_yaml(v)
+                    .context(format!("invalid tag transform value for key {k}"))?;
+                Ok(())
+            }
             "prefix" => {
                 let prefix = MetricName::parse_yaml(v)
                     .context(format!("invalid metric name value for key {k}"))?;
@@ -74,6 +112,22 @@ impl GraphiteExporterConfig {
                     .context(format!("invalid static metrics tags value for key {k}"))?;
                 Ok(())
             }
+            "counter_mode" => {
+                self.counter_mode = CounterEmitMode::parse_yaml(v)
+                    .context(format!("invalid counter emit mode value for key {k}"))?;
+                Ok(())
+            }
+            "align_timestamp" => {
+                self.align_timestamp = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "sanitize" => {
+                let action = MetricSanitizeAction::parse_yaml(v)
+                    .context(format!("invalid metric sanitize action value for key {k}"))?;
+                GraphiteSanitizeRules::check_action(action)?;
+                self.sanitize_action = Some(action);
+                Ok(())
+            }
             _ => self.stream_export.set_by_yaml_kv(k, v),
         }
     }
@@ -82,6 +136,17 @@ impl GraphiteExporterConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        self.rollup
+            .check(self.emit_interval)
+            .context("invalid rollup config")?;
+        if let Some(timeout) = self.stale_timeout
+            && timeout < self.emit_interval
+        {
+            return Err(anyhow!(
+                "stale timeout {timeout:?} should not be less than the emit interval {:?}",
+                self.emit_interval
+            ));
+        }
         self.stream_export.check(self.name.clone())?;
         Ok(())
     }
@@ -101,10 +166,26 @@ impl ExporterConfig for GraphiteExporterConfig {
     }
 
     fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
-        let AnyExporterConfig::Graphite(_new) = new else {
+        let AnyExporterConfig::Graphite(new) = new else {
             return ExporterConfigDiffAction::SpawnNew;
         };
 
-        ExporterConfigDiffAction::Reload
+        if self.eq(new) {
+            return ExporterConfigDiffAction::NoAction;
+        }
+
+        if self.prefix == new.prefix && self.global_tags == new.global_tags {
+            return ExporterConfigDiffAction::Reload;
+        }
+
+        let mut check = new.clone();
+        check.prefix.clone_from(&self.prefix);
+        check.global_tags.clone_from(&self.global_tags);
+        check.position.clone_from(&self.position);
+        if check.eq(self) {
+            ExporterConfigDiffAction::UpdateInPlace
+        } else {
+            ExporterConfigDiffAction::Reload
+        }
     }
 }
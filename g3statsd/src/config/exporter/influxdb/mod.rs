@@ -3,6 +3,7 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use http::HeaderValue;
@@ -12,11 +13,18 @@ use g3_types::metrics::MetricTagMap;
 
 use super::{AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction};
 use super::{CONFIG_KEY_EXPORTER_NAME, CONFIG_KEY_EXPORTER_TYPE};
+use crate::runtime::export::{
+    AggregateEmitJitter, AggregateRollupConfig, AggregateTagTransform, CounterEmitMode,
+    MetricSanitizeAction,
+};
 use crate::types::MetricName;
 
 mod precision;
 pub(crate) use precision::TimestampPrecision;
 
+mod v1;
+pub(crate) use v1::InfluxdbV1ExporterConfig;
+
 mod v2;
 pub(crate) use v2::InfluxdbV2ExporterConfig;
 
@@ -27,8 +35,18 @@ pub(crate) trait InfluxdbExporterConfig {
     fn emit_interval(&self) -> Duration;
     fn precision(&self) -> TimestampPrecision;
     fn max_body_lines(&self) -> usize;
+    fn rollup(&self) -> AggregateRollupConfig;
+    fn stale_timeout(&self) -> Option<Duration>;
+    fn emit_jitter(&self) -> AggregateEmitJitter;
+    fn tag_transform(&self) -> AggregateTagTransform;
     fn prefix(&self) -> Option<MetricName>;
     fn global_tags(&self) -> MetricTagMap;
+    fn counter_mode(&self) -> CounterEmitMode;
+    fn align_timestamp(&self) -> bool;
+    fn sanitize_action(&self) -> Option<MetricSanitizeAction>;
     fn build_api_path(&self) -> anyhow::Result<PathAndQuery>;
+    fn auth_scheme(&self) -> &'static str;
     fn build_api_token(&self) -> Option<HeaderValue>;
+    fn token_file(&self) -> Option<PathBuf>;
+    fn token_refresh_interval(&self) -> Duration;
 }
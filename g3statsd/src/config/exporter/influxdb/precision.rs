@@ -17,6 +17,15 @@ pub(crate) enum TimestampPrecision {
 }
 
 impl TimestampPrecision {
+    pub(crate) fn v1_query_value(self) -> &'static str {
+        match self {
+            Self::Seconds => "s",
+            Self::MilliSeconds => "ms",
+            Self::MicroSeconds => "u",
+            Self::NanoSeconds => "ns",
+        }
+    }
+
     pub(crate) fn v2_query_value(self) -> &'static str {
         match self {
             Self::Seconds => "s",
@@ -0,0 +1,444 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{Context, anyhow};
+use http::HeaderValue;
+use http::uri::PathAndQuery;
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC, utf8_percent_encode};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::auth::{Password, Username};
+use g3_types::metrics::{MetricTagMap, NodeName};
+use g3_types::net::HttpBasicAuth;
+use g3_yaml::YamlDocPosition;
+
+use super::{
+    AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction, InfluxdbExporterConfig,
+    TimestampPrecision,
+};
+use crate::runtime::export::{
+    AggregateEmitJitter, AggregateRollupConfig, AggregateTagTransform, CounterEmitMode,
+    HttpExportConfig, InfluxdbSanitizeRules, MetricSanitizeAction, MetricSanitizeRules,
+};
+use crate::types::MetricName;
+
+const EXPORTER_CONFIG_TYPE: &str = "InfluxDB_V1";
+
+const QUERY_VALUE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum InfluxdbV1AuthMethod {
+    /// use the http basic auth header
+    #[default]
+    Basic,
+    /// use the u and p query parameters
+    Query,
+}
+
+impl InfluxdbV1AuthMethod {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = value {
+            match s.to_lowercase().as_str() {
+                "basic" => Ok(InfluxdbV1AuthMethod::Basic),
+                "query" => Ok(InfluxdbV1AuthMethod::Query),
+                _ => Err(anyhow!("invalid influxdb v1 auth method: {s}")),
+            }
+        } else {
+            Err(anyhow!(
+                "yaml value type for influxdb v1 auth method should be string"
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct InfluxdbV1ExporterConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    emit_interval: Duration,
+    rollup: AggregateRollupConfig,
+    stale_timeout: Option<Duration>,
+    emit_jitter: AggregateEmitJitter,
+    tag_transform: AggregateTagTransform,
+    max_body_lines: usize,
+    pub(crate) http_export: HttpExportConfig,
+    database: String,
+    retention_policy: Option<String>,
+    username: Option<Username>,
+    password: Password,
+    auth_method: InfluxdbV1AuthMethod,
+    precision: TimestampPrecision,
+    prefix: Option<MetricName>,
+    global_tags: MetricTagMap,
+    counter_mode: CounterEmitMode,
+    align_timestamp: bool,
+    sanitize_action: Option<MetricSanitizeAction>,
+}
+
+impl InfluxdbV1ExporterConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        InfluxdbV1ExporterConfig {
+            name: NodeName::default(),
+            position,
+            emit_interval: Duration::from_secs(10),
+            rollup: AggregateRollupConfig::default(),
+            stale_timeout: None,
+            emit_jitter: AggregateEmitJitter::default(),
+            tag_transform: AggregateTagTransform::default(),
+            max_body_lines: 10000,
+            http_export: HttpExportConfig::new(8086),
+            database: String::new(),
+            retention_policy: None,
+            username: None,
+            password: Password::empty(),
+            auth_method: InfluxdbV1AuthMethod::default(),
+            precision: TimestampPrecision::Seconds,
+            prefix: None,
+            global_tags: MetricTagMap::default(),
+            counter_mode: CounterEmitMode::default(),
+            align_timestamp: false,
+            sanitize_action: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut collector = InfluxdbV1ExporterConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| collector.set(k, v))?;
+
+        collector.check()?;
+        Ok(collector)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_EXPORTER_TYPE => Ok(()),
+            super::CONFIG_KEY_EXPORTER_NAME => {
+                self.name = g3_yaml::value::as_metric_node_name(v)?;
+                Ok(())
+            }
+            "database" | "db" => {
+                self.database = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "retention_policy" | "rp" => {
+                self.retention_policy = Some(g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "username" => {
+                let username = g3_yaml::value::as_username(v)
+                    .context(format!("invalid username value for key {k}"))?;
+                self.username = Some(username);
+                Ok(())
+            }
+            "password" => {
+                self.password = g3_yaml::value::as_password(v)
+                    .context(format!("invalid password value for key {k}"))?;
+                Ok(())
+            }
+            "auth_method" => {
+                self.auth_method = InfluxdbV1AuthMethod::parse_yaml(v)
+                    .context(format!("invalid auth method value for key {k}"))?;
+                Ok(())
+            }
+            "precision" => {
+                self.precision = TimestampPrecision::parse_yaml(v)
+                    .context(format!("invalid timestamp precision value for key {k}"))?;
+                Ok(())
+            }
+            "emit_interval" => {
+                self.emit_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "rollup" => {
+                self.rollup = AggregateRollupConfig::parse_yaml(v)
+                    .context(format!("invalid aggregate rollup config value for key {k}"))?;
+                Ok(())
+            }
+            "stale_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.stale_timeout = Some(timeout);
+                Ok(())
+            }
+            "emit_jitter" => {
+                self.emit_jitter = AggregateEmitJitter::parse_yaml(v)
+                    .context(format!("invalid emit jitter value for key {k}"))?;
+                Ok(())
+            }
+            "tag_transform" => {
+                self.tag_transform = AggregateTagTransform::parse_yaml(v)
+                    .context(format!("invalid tag transform value for key {k}"))?;
+                Ok(())
+            }
+            "max_body_lines" => {
+                self.max_body_lines = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            "prefix" => {
+                let prefix = MetricName::parse_yaml(v)
+                    .context(format!("invalid metric name value for key {k}"))?;
+                self.prefix = Some(prefix);
+                Ok(())
+            }
+            "global_tags" => {
+                self.global_tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                Ok(())
+            }
+            "counter_mode" => {
+                self.counter_mode = CounterEmitMode::parse_yaml(v)
+                    .context(format!("invalid counter emit mode value for key {k}"))?;
+                Ok(())
+            }
+            "align_timestamp" => {
+                self.align_timestamp = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "sanitize" => {
+                let action = MetricSanitizeAction::parse_yaml(v)
+                    .context(format!("invalid metric sanitize action value for key {k}"))?;
+                InfluxdbSanitizeRules::check_action(action)?;
+                self.sanitize_action = Some(action);
+                Ok(())
+            }
+            _ => self
+                .http_export
+                .set_by_yaml_kv(k, v, self.position.as_ref()),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        self.rollup
+            .check(self.emit_interval)
+            .context("invalid rollup config")?;
+        if let Some(timeout) = self.stale_timeout
+            && timeout < self.emit_interval
+        {
+            return Err(anyhow!(
+                "stale timeout {timeout:?} should not be less than the emit interval {:?}",
+                self.emit_interval
+            ));
+        }
+        if self.database.is_empty() {
+            return Err(anyhow!("database is not set"));
+        }
+        self.http_export.check(self.name.clone())?;
+        Ok(())
+    }
+}
+
+impl ExporterConfig for InfluxdbV1ExporterConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn exporter_type(&self) -> &'static str {
+        EXPORTER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
+        let AnyExporterConfig::InfluxdbV1(new) = new else {
+            return ExporterConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ExporterConfigDiffAction::NoAction;
+        }
+
+        let mut check = new.clone();
+        check.prefix.clone_from(&self.prefix);
+        check.global_tags.clone_from(&self.global_tags);
+        check.position.clone_from(&self.position);
+        check.http_export.copy_rate_limit(&self.http_export);
+        if check.eq(self) {
+            ExporterConfigDiffAction::UpdateInPlace
+        } else {
+            ExporterConfigDiffAction::Reload
+        }
+    }
+}
+
+impl InfluxdbExporterConfig for InfluxdbV1ExporterConfig {
+    fn emit_interval(&self) -> Duration {
+        self.emit_interval
+    }
+
+    fn precision(&self) -> TimestampPrecision {
+        self.precision
+    }
+
+    fn max_body_lines(&self) -> usize {
+        self.max_body_lines
+    }
+
+    fn rollup(&self) -> AggregateRollupConfig {
+        self.rollup.clone()
+    }
+
+    fn stale_timeout(&self) -> Option<Duration> {
+        self.stale_timeout
+    }
+
+    fn emit_jitter(&self) -> AggregateEmitJitter {
+        self.emit_jitter
+    }
+
+    fn tag_transform(&self) -> AggregateTagTransform {
+        self.tag_transform.clone()
+    }
+
+    fn prefix(&self) -> Option<MetricName> {
+        self.prefix.clone()
+    }
+
+    fn global_tags(&self) -> MetricTagMap {
+        self.global_tags.clone()
+    }
+
+    fn counter_mode(&self) -> CounterEmitMode {
+        self.counter_mode
+    }
+
+    fn align_timestamp(&self) -> bool {
+        self.align_timestamp
+    }
+
+    fn sanitize_action(&self) -> Option<MetricSanitizeAction> {
+        self.sanitize_action
+    }
+
+    fn build_api_path(&self) -> anyhow::Result<PathAndQuery> {
+        let mut path = format!(
+            "/write?db={}&precision={}",
+            utf8_percent_encode(&self.database, QUERY_VALUE_ENCODE_SET),
+            self.precision.v1_query_value()
+        );
+        if let Some(rp) = &self.retention_policy {
+            let _ = write!(
+                path,
+                "&rp={}",
+                utf8_percent_encode(rp, QUERY_VALUE_ENCODE_SET)
+            );
+        }
+        if self.auth_method == InfluxdbV1AuthMethod::Query
+            && let Some(username) = &self.username
+        {
+            let _ = write!(
+                path,
+                "&u={}&p={}",
+                utf8_percent_encode(username.as_original(), QUERY_VALUE_ENCODE_SET),
+                utf8_percent_encode(self.password.as_original(), QUERY_VALUE_ENCODE_SET)
+            );
+        }
+        PathAndQuery::from_str(&path).map_err(|e| anyhow!("invalid influxdb api path {path}: {e}"))
+    }
+
+    fn auth_scheme(&self) -> &'static str {
+        "Basic"
+    }
+
+    fn build_api_token(&self) -> Option<HeaderValue> {
+        if self.auth_method != InfluxdbV1AuthMethod::Basic {
+            return None;
+        }
+        let username = self.username.clone()?;
+        let auth = HttpBasicAuth::new(username, self.password.clone());
+        HeaderValue::try_from(&auth).ok()
+    }
+
+    fn token_file(&self) -> Option<PathBuf> {
+        None
+    }
+
+    fn token_refresh_interval(&self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn load(yaml: &str) -> InfluxdbV1ExporterConfig {
+        let Yaml::Hash(map) = YamlLoader::load_from_str(yaml).unwrap().remove(0) else {
+            panic!("yaml doc is not a map");
+        };
+        InfluxdbV1ExporterConfig::parse(&map, None).unwrap()
+    }
+
+    #[test]
+    fn api_path() {
+        let config = load(
+            r#"
+            name: v1
+            server: 127.0.0.1
+            database: my db
+            retention_policy: one_week
+            precision: ms
+        "#,
+        );
+        assert_eq!(
+            config.build_api_path().unwrap().as_str(),
+            "/write?db=my%20db&precision=ms&rp=one_week"
+        );
+        assert!(config.build_api_token().is_none());
+    }
+
+    #[test]
+    fn auth() {
+        let config = load(
+            r#"
+            name: v1
+            server: 127.0.0.1
+            database: db
+            username: user
+            password: pass
+        "#,
+        );
+        assert_eq!(
+            config.build_api_path().unwrap().as_str(),
+            "/write?db=db&precision=s"
+        );
+        assert_eq!(config.build_api_token().unwrap(), "Basic dXNlcjpwYXNz");
+
+        let config = load(
+            r#"
+            name: v1
+            server: 127.0.0.1
+            database: db
+            username: user
+            password: p&ss
+            auth_method: query
+        "#,
+        );
+        assert_eq!(
+            config.build_api_path().unwrap().as_str(),
+            "/write?db=db&precision=s&u=user&p=p%26ss"
+        );
+        assert!(config.build_api_token().is_none());
+    }
+}
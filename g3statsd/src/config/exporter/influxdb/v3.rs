@@ -3,6 +3,7 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::time::Duration;
 
@@ -18,7 +19,10 @@ use super::{
     AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction, InfluxdbExporterConfig,
     TimestampPrecision,
 };
-use crate::runtime::export::HttpExportConfig;
+use crate::runtime::export::{
+    AggregateEmitJitter, AggregateRollupConfig, AggregateTagTransform, CounterEmitMode,
+    HttpExportConfig, InfluxdbSanitizeRules, MetricSanitizeAction, MetricSanitizeRules,
+};
 use crate::types::MetricName;
 
 const EXPORTER_CONFIG_TYPE: &str = "InfluxDB_V3";
@@ -30,14 +34,23 @@ pub(crate) struct InfluxdbV3ExporterConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     emit_interval: Duration,
+    rollup: AggregateRollupConfig,
+    stale_timeout: Option<Duration>,
+    emit_jitter: AggregateEmitJitter,
+    tag_transform: AggregateTagTransform,
     max_body_lines: usize,
     pub(crate) http_export: HttpExportConfig,
     database: String,
     token: String,
+    token_file: Option<PathBuf>,
+    token_refresh_interval: Duration,
     precision: TimestampPrecision,
     no_sync: bool,
     prefix: Option<MetricName>,
     global_tags: MetricTagMap,
+    counter_mode: CounterEmitMode,
+    align_timestamp: bool,
+    sanitize_action: Option<MetricSanitizeAction>,
 }
 
 impl InfluxdbV3ExporterConfig {
@@ -46,14 +59,23 @@ impl InfluxdbV3ExporterConfig {
             name: NodeName::default(),
             position,
             emit_interval: Duration::from_secs(10),
+            rollup: AggregateRollupConfig::default(),
+            stale_timeout: None,
+            emit_jitter: AggregateEmitJitter::default(),
+            tag_transform: AggregateTagTransform::default(),
             max_body_lines: 10000,
             http_export: HttpExportConfig::new(8181),
             database: String::new(),
             token: String::new(),
+            token_file: None,
+            token_refresh_interval: Duration::from_secs(60),
             precision: TimestampPrecision::Seconds,
             no_sync: false,
             prefix: None,
             global_tags: MetricTagMap::default(),
+            counter_mode: CounterEmitMode::default(),
+            align_timestamp: false,
+            sanitize_action: None,
         }
     }
 
@@ -85,6 +107,18 @@ impl InfluxdbV3ExporterConfig {
                     .context(format!("invalid http header value string for key {k}"))?;
                 Ok(())
             }
+            "token_file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.token_file = Some(path);
+                Ok(())
+            }
+            "token_refresh_interval" => {
+                self.token_refresh_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
             "precision" => {
                 self.precision = TimestampPrecision::parse_yaml(v)
                     .context(format!("invalid timestamp precision value for key {k}"))?;
@@ -99,6 +133,27 @@ impl InfluxdbV3ExporterConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "rollup" => {
+                self.rollup = AggregateRollupConfig::parse_yaml(v)
+                    .context(format!("invalid aggregate rollup config value for key {k}"))?;
+                Ok(())
+            }
+            "stale_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.stale_timeout = Some(timeout);
+                Ok(())
+            }
+            "emit_jitter" => {
+                self.emit_jitter = AggregateEmitJitter::parse_yaml(v)
+                    .context(format!("invalid emit jitter value for key {k}"))?;
+                Ok(())
+            }
+            "tag_transform" => {
+                self.tag_transform = AggregateTagTransform::parse_yaml(v)
+                    .context(format!("invalid tag transform value for key {k}"))?;
+                Ok(())
+            }
             "max_body_lines" => {
                 self.max_body_lines = g3_yaml::value::as_usize(v)?;
                 Ok(())
@@ -114,7 +169,25 @@ impl InfluxdbV3ExporterConfig {
                     .context(format!("invalid static metrics tags value for key {k}"))?;
                 Ok(())
             }
-            _ => self.http_export.set_by_yaml_kv(k, v),
+            "counter_mode" => {
+                self.counter_mode = CounterEmitMode::parse_yaml(v)
+                    .context(format!("invalid counter emit mode value for key {k}"))?;
+                Ok(())
+            }
+            "align_timestamp" => {
+                self.align_timestamp = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "sanitize" => {
+                let action = MetricSanitizeAction::parse_yaml(v)
+                    .context(format!("invalid metric sanitize action value for key {k}"))?;
+                InfluxdbSanitizeRules::check_action(action)?;
+                self.sanitize_action = Some(action);
+                Ok(())
+            }
+            _ => self
+                .http_export
+                .set_by_yaml_kv(k, v, self.position.as_ref()),
         }
     }
 
@@ -122,10 +195,22 @@ impl InfluxdbV3ExporterConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        self.rollup
+            .check(self.emit_interval)
+            .context("invalid rollup config")?;
+        if let Some(timeout) = self.stale_timeout
+            && timeout < self.emit_interval
+        {
+            return Err(anyhow!(
+                "stale timeout {timeout:?} should not be less than the emit interval {:?}",
+                self.emit_interval
+            ));
+        }
         if self.database.is_empty() {
             return Err(anyhow!("database is not set"));
         }
         if self.token.is_empty()
+            && self.token_file.is_none()
             && let Ok(token) = std::env::var(AUTH_TOKEN_ENV_VAR)
         {
             self.token = token;
@@ -149,11 +234,24 @@ impl ExporterConfig for InfluxdbV3ExporterConfig {
     }
 
     fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
-        let AnyExporterConfig::InfluxdbV3(_new) = new else {
+        let AnyExporterConfig::InfluxdbV3(new) = new else {
             return ExporterConfigDiffAction::SpawnNew;
         };
 
-        ExporterConfigDiffAction::Reload
+        if self.eq(new) {
+            return ExporterConfigDiffAction::NoAction;
+        }
+
+        let mut check = new.clone();
+        check.prefix.clone_from(&self.prefix);
+        check.global_tags.clone_from(&self.global_tags);
+        check.position.clone_from(&self.position);
+        check.http_export.copy_rate_limit(&self.http_export);
+        if check.eq(self) {
+            ExporterConfigDiffAction::UpdateInPlace
+        } else {
+            ExporterConfigDiffAction::Reload
+        }
     }
 }
 
@@ -170,6 +268,22 @@ impl InfluxdbExporterConfig for InfluxdbV3ExporterConfig {
         self.max_body_lines
     }
 
+    fn rollup(&self) -> AggregateRollupConfig {
+        self.rollup.clone()
+    }
+
+    fn stale_timeout(&self) -> Option<Duration> {
+        self.stale_timeout
+    }
+
+    fn emit_jitter(&self) -> AggregateEmitJitter {
+        self.emit_jitter
+    }
+
+    fn tag_transform(&self) -> AggregateTagTransform {
+        self.tag_transform.clone()
+    }
+
     fn prefix(&self) -> Option<MetricName> {
         self.prefix.clone()
     }
@@ -178,6 +292,18 @@ impl InfluxdbExporterConfig for InfluxdbV3ExporterConfig {
         self.global_tags.clone()
     }
 
+    fn counter_mode(&self) -> CounterEmitMode {
+        self.counter_mode
+    }
+
+    fn align_timestamp(&self) -> bool {
+        self.align_timestamp
+    }
+
+    fn sanitize_action(&self) -> Option<MetricSanitizeAction> {
+        self.sanitize_action
+    }
+
     fn build_api_path(&self) -> anyhow::Result<PathAndQuery> {
         let path = if self.no_sync {
             format!(
@@ -195,11 +321,23 @@ impl InfluxdbExporterConfig for InfluxdbV3ExporterConfig {
         PathAndQuery::from_str(&path).map_err(|e| anyhow!("invalid influxdb api path {path}: {e}"))
     }
 
+    fn auth_scheme(&self) -> &'static str {
+        "Bearer"
+    }
+
     fn build_api_token(&self) -> Option<HeaderValue> {
         if self.token.is_empty() {
             return None;
         }
-        let s = format!("Bearer {}", self.token);
+        let s = format!("{} {}", self.auth_scheme(), self.token);
         HeaderValue::from_str(&s).ok()
     }
+
+    fn token_file(&self) -> Option<PathBuf> {
+        self.token_file.clone()
+    }
+
+    fn token_refresh_interval(&self) -> Duration {
+        self.token_refresh_interval
+    }
 }
@@ -3,6 +3,7 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::path::Path;
 
 use anyhow::{Context, anyhow};
@@ -25,10 +26,24 @@ pub(crate) mod opentsdb;
 const CONFIG_KEY_EXPORTER_TYPE: &str = "type";
 const CONFIG_KEY_EXPORTER_NAME: &str = "name";
 
+#[derive(Debug, PartialEq, Eq)]
 pub(crate) enum ExporterConfigDiffAction {
     NoAction,
     SpawnNew,
     Reload,
+    /// only the in-place updatable fields are changed
+    UpdateInPlace,
+}
+
+impl ExporterConfigDiffAction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ExporterConfigDiffAction::NoAction => "no_action",
+            ExporterConfigDiffAction::SpawnNew => "spawn_new",
+            ExporterConfigDiffAction::Reload => "reload",
+            ExporterConfigDiffAction::UpdateInPlace => "update_in_place",
+        }
+    }
 }
 
 pub(crate) trait ExporterConfig {
@@ -50,28 +65,42 @@ pub(crate) enum AnyExporterConfig {
     Memory(memory::MemoryExporterConfig),
     Graphite(graphite::GraphiteExporterConfig),
     Opentsdb(opentsdb::OpentsdbExporterConfig),
+    InfluxdbV1(influxdb::InfluxdbV1ExporterConfig),
     InfluxdbV2(influxdb::InfluxdbV2ExporterConfig),
     InfluxdbV3(influxdb::InfluxdbV3ExporterConfig),
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
-    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
-    parser.foreach_map(v, |map, position| {
-        let exporter = load_exporter(map, position)?;
+    for exporter in parse_all(v, conf_dir)? {
         if let Some(old_exporter) = registry::add(exporter) {
-            Err(anyhow!(
+            return Err(anyhow!(
                 "exporter with name {} already exists",
                 old_exporter.name()
-            ))
-        } else {
-            Ok(())
+            ));
         }
-    })?;
+    }
     Ok(())
 }
 
+/// Parse all the exporter config without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyExporterConfig>> {
+    let mut parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    parser.enable_json();
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let exporter = load_exporter(map, position)?;
+        all.borrow_mut().push(exporter);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyExporterConfig> {
-    let doc = g3_yaml::load_doc(position)?;
+    let doc = if g3_yaml::json::is_json_file(&position.path) {
+        g3_yaml::json::load_doc(&position.path)?
+    } else {
+        g3_yaml::load_doc(position)?
+    };
     if let Yaml::Hash(map) = doc {
         let exporter = load_exporter(&map, Some(position.clone()))?;
         registry::add(exporter.clone());
@@ -112,6 +141,11 @@ fn load_exporter(
                 .context("failed to load this OpenTSDB exporter")?;
             Ok(AnyExporterConfig::Opentsdb(exporter))
         }
+        "influxdb_v1" => {
+            let exporter = influxdb::InfluxdbV1ExporterConfig::parse(map, position)
+                .context("failed to load this InfluxDB v1 exporter")?;
+            Ok(AnyExporterConfig::InfluxdbV1(exporter))
+        }
         "influxdb_v2" => {
             let exporter = influxdb::InfluxdbV2ExporterConfig::parse(map, position)
                 .context("failed to load this InfluxDB v2 exporter")?;
@@ -125,3 +159,147 @@ fn load_exporter(
         _ => Err(anyhow!("unsupported exporter type {}", exporter_type)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn json_same_as_yaml() {
+        let yaml = r#"
+            name: graphite
+            type: graphite
+            emit_interval: 30s
+            server: 127.0.0.1
+            port: 2004
+            prefix: g3
+            global_tags:
+              dc: a
+        "#;
+        let json = r#"{
+            "name": "graphite",
+            "type": "graphite",
+            "emit_interval": "30s",
+            "server": "127.0.0.1",
+            "port": 2004,
+            "prefix": "g3",
+            "global_tags": {"dc": "a"}
+        }"#;
+
+        let Yaml::Hash(yaml_map) = YamlLoader::load_from_str(yaml).unwrap().remove(0) else {
+            panic!("yaml doc is not a map");
+        };
+        let from_yaml = load_exporter(&yaml_map, None).unwrap();
+
+        let value = serde_json::from_str(json).unwrap();
+        let Yaml::Hash(json_map) = g3_yaml::json::to_yaml(&value).unwrap() else {
+            panic!("json doc is not a map");
+        };
+        let from_json = load_exporter(&json_map, None).unwrap();
+
+        assert_eq!(format!("{from_yaml:?}"), format!("{from_json:?}"));
+    }
+
+    fn load_yaml(yaml: &str) -> AnyExporterConfig {
+        let Yaml::Hash(map) = YamlLoader::load_from_str(yaml).unwrap().remove(0) else {
+            panic!("yaml doc is not a map");
+        };
+        load_exporter(&map, None).unwrap()
+    }
+
+    #[test]
+    fn diff_action() {
+        let old = load_yaml(
+            r#"
+            name: graphite
+            type: graphite
+            server: 127.0.0.1
+            prefix: g3
+        "#,
+        );
+
+        let same = load_yaml(
+            r#"
+            name: graphite
+            type: graphite
+            server: 127.0.0.1
+            prefix: g3
+        "#,
+        );
+        assert_eq!(old.diff_action(&same), ExporterConfigDiffAction::NoAction);
+
+        let tags = load_yaml(
+            r#"
+            name: graphite
+            type: graphite
+            server: 127.0.0.1
+            prefix: g3.new
+            global_tags:
+              dc: a
+        "#,
+        );
+        assert_eq!(
+            old.diff_action(&tags),
+            ExporterConfigDiffAction::UpdateInPlace
+        );
+
+        let server = load_yaml(
+            r#"
+            name: graphite
+            type: graphite
+            server: 127.0.0.2
+            prefix: g3.new
+        "#,
+        );
+        assert_eq!(old.diff_action(&server), ExporterConfigDiffAction::Reload);
+
+        let other = load_yaml(
+            r#"
+            name: graphite
+            type: opentsdb
+            server: 127.0.0.1
+        "#,
+        );
+        assert_eq!(old.diff_action(&other), ExporterConfigDiffAction::SpawnNew);
+
+        let old = load_yaml(
+            r#"
+            name: opentsdb
+            type: opentsdb
+            server: 127.0.0.1
+        "#,
+        );
+        let rate_limit = load_yaml(
+            r#"
+            name: opentsdb
+            type: opentsdb
+            server: 127.0.0.1
+            max_requests_per_second: 10
+        "#,
+        );
+        assert_eq!(
+            old.diff_action(&rate_limit),
+            ExporterConfigDiffAction::UpdateInPlace
+        );
+        assert_eq!(
+            rate_limit.diff_action(&rate_limit.clone()),
+            ExporterConfigDiffAction::NoAction
+        );
+
+        for yaml in [
+            "{name: influxdb, type: influxdb_v1, server: 127.0.0.1, database: db}",
+            "{name: influxdb, type: influxdb_v2, server: 127.0.0.1, bucket: db, token: abc}",
+            "{name: influxdb, type: influxdb_v3, server: 127.0.0.1, database: db, token: abc}",
+            "{name: graphite, type: graphite, server: 127.0.0.1, global_tags: {dc: a}}",
+        ] {
+            let old = load_yaml(yaml);
+            let same = load_yaml(yaml);
+            assert_eq!(
+                old.diff_action(&same),
+                ExporterConfigDiffAction::NoAction,
+                "{yaml}"
+            );
+        }
+    }
+}
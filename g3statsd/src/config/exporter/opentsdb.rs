@@ -14,7 +14,10 @@ use g3_types::metrics::{MetricTagMap, NodeName};
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyExporterConfig, ExporterConfig, ExporterConfigDiffAction};
-use crate::runtime::export::HttpExportConfig;
+use crate::runtime::export::{
+    AggregateEmitJitter, AggregateRollupConfig, AggregateTagTransform, CounterEmitMode,
+    HttpExportConfig, MetricSanitizeAction, MetricSanitizeRules, OpentsdbSanitizeRules,
+};
 use crate::types::MetricName;
 
 const EXPORTER_CONFIG_TYPE: &str = "OpenTSDB";
@@ -24,11 +27,19 @@ pub(crate) struct OpentsdbExporterConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     pub(crate) emit_interval: Duration,
+    pub(crate) rollup: AggregateRollupConfig,
+    pub(crate) stale_timeout: Option<Duration>,
+    pub(crate) emit_jitter: AggregateEmitJitter,
+    pub(crate) tag_transform: AggregateTagTransform,
     pub(crate) max_data_points: usize,
+    pub(crate) chunked_transfer: bool,
     pub(crate) http_export: HttpExportConfig,
     sync_timeout: Option<Duration>,
     pub(crate) prefix: Option<MetricName>,
     pub(crate) global_tags: MetricTagMap,
+    pub(crate) counter_mode: CounterEmitMode,
+    pub(crate) align_timestamp: bool,
+    pub(crate) sanitize_action: Option<MetricSanitizeAction>,
 }
 
 impl OpentsdbExporterConfig {
@@ -37,11 +48,19 @@ impl OpentsdbExporterConfig {
             name: NodeName::default(),
             position,
             emit_interval: Duration::from_secs(10),
+            rollup: AggregateRollupConfig::default(),
+            stale_timeout: None,
+            emit_jitter: AggregateEmitJitter::default(),
+            tag_transform: AggregateTagTransform::default(),
             max_data_points: 50,
+            chunked_transfer: false,
             http_export: HttpExportConfig::new(4242),
             sync_timeout: None,
             prefix: None,
             global_tags: MetricTagMap::default(),
+            counter_mode: CounterEmitMode::default(),
+            align_timestamp: false,
+            sanitize_action: None,
         }
     }
 
@@ -88,10 +107,35 @@ impl OpentsdbExporterConfig {
                     .context(format!("invalid humanize duration value for key {k}"))?;
                 Ok(())
             }
+            "rollup" => {
+                self.rollup = AggregateRollupConfig::parse_yaml(v)
+                    .context(format!("invalid aggregate rollup config value for key {k}"))?;
+                Ok(())
+            }
+            "stale_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                self.stale_timeout = Some(timeout);
+                Ok(())
+            }
+            "emit_jitter" => {
+                self.emit_jitter = AggregateEmitJitter::parse_yaml(v)
+                    .context(format!("invalid emit jitter value for key {k}"))?;
+                Ok(())
+            }
+            "tag_transform" => {
+                self.tag_transform = AggregateTagTransform::parse_yaml(v)
+                    .context(format!("invalid tag transform value for key {k}"))?;
+                Ok(())
+            }
             "max_data_points" => {
                 self.max_data_points = g3_yaml::value::as_usize(v)?;
                 Ok(())
             }
+            "chunked_transfer" => {
+                self.chunked_transfer = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
             "prefix" => {
                 let prefix = MetricName::parse_yaml(v)
                     .context(format!("invalid metric name value for key {k}"))?;
@@ -103,7 +147,25 @@ impl OpentsdbExporterConfig {
                     .context(format!("invalid static metrics tags value for key {k}"))?;
                 Ok(())
             }
-            _ => self.http_export.set_by_yaml_kv(k, v),
+            "counter_mode" => {
+                self.counter_mode = CounterEmitMode::parse_yaml(v)
+                    .context(format!("invalid counter emit mode value for key {k}"))?;
+                Ok(())
+            }
+            "align_timestamp" => {
+                self.align_timestamp = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "sanitize" => {
+                let action = MetricSanitizeAction::parse_yaml(v)
+                    .context(format!("invalid metric sanitize action value for key {k}"))?;
+                OpentsdbSanitizeRules::check_action(action)?;
+                self.sanitize_action = Some(action);
+                Ok(())
+            }
+            _ => self
+                .http_export
+                .set_by_yaml_kv(k, v, self.position.as_ref()),
         }
     }
 
@@ -111,6 +173,17 @@ impl OpentsdbExporterConfig {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        self.rollup
+            .check(self.emit_interval)
+            .context("invalid rollup config")?;
+        if let Some(timeout) = self.stale_timeout
+            && timeout < self.emit_interval
+        {
+            return Err(anyhow!(
+                "stale timeout {timeout:?} should not be less than the emit interval {:?}",
+                self.emit_interval
+            ));
+        }
         self.http_export.check(self.name.clone())?;
         Ok(())
     }
@@ -130,10 +203,23 @@ impl ExporterConfig for OpentsdbExporterConfig {
     }
 
     fn diff_action(&self, new: &AnyExporterConfig) -> ExporterConfigDiffAction {
-        let AnyExporterConfig::Opentsdb(_new) = new else {
+        let AnyExporterConfig::Opentsdb(new) = new else {
             return ExporterConfigDiffAction::SpawnNew;
         };
 
-        ExporterConfigDiffAction::Reload
+        if self.eq(new) {
+            return ExporterConfigDiffAction::NoAction;
+        }
+
+        let mut check = new.clone();
+        check.prefix.clone_from(&self.prefix);
+        check.global_tags.clone_from(&self.global_tags);
+        check.position.clone_from(&self.position);
+        check.http_export.copy_rate_limit(&self.http_export);
+        if check.eq(self) {
+            ExporterConfigDiffAction::UpdateInPlace
+        } else {
+            ExporterConfigDiffAction::Reload
+        }
     }
 }
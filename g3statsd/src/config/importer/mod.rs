@@ -3,6 +3,7 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::path::Path;
 
 use anyhow::{Context, anyhow};
@@ -28,6 +29,17 @@ pub(crate) enum ImporterConfigDiffAction {
     ReloadAndRespawn,
 }
 
+impl ImporterConfigDiffAction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            ImporterConfigDiffAction::NoAction => "no_action",
+            ImporterConfigDiffAction::SpawnNew => "spawn_new",
+            ImporterConfigDiffAction::ReloadNoRespawn => "reload_no_respawn",
+            ImporterConfigDiffAction::ReloadAndRespawn => "reload_and_respawn",
+        }
+    }
+}
+
 pub(crate) trait ImporterConfig {
     fn name(&self) -> &NodeName;
     fn position(&self) -> Option<YamlDocPosition>;
@@ -53,23 +65,36 @@ pub(crate) enum AnyImporterConfig {
 }
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
-    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
-    parser.foreach_map(v, |map, position| {
-        let importer = load_importer(map, position)?;
-        if let Some(importer) = registry::add(importer) {
-            Err(anyhow!(
+    for importer in parse_all(v, conf_dir)? {
+        if let Some(old_importer) = registry::add(importer) {
+            return Err(anyhow!(
                 "importer with name {} already exists",
-                importer.name()
-            ))
-        } else {
-            Ok(())
+                old_importer.name()
+            ));
         }
-    })?;
+    }
     Ok(())
 }
 
+/// Parse all the importer config without adding them to the registry
+pub(crate) fn parse_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<Vec<AnyImporterConfig>> {
+    let mut parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    parser.enable_json();
+    let all = RefCell::new(Vec::new());
+    parser.foreach_map(v, |map, position| {
+        let importer = load_importer(map, position)?;
+        all.borrow_mut().push(importer);
+        Ok(())
+    })?;
+    Ok(all.into_inner())
+}
+
 pub(crate) fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<AnyImporterConfig> {
-    let doc = g3_yaml::load_doc(position)?;
+    let doc = if g3_yaml::json::is_json_file(&position.path) {
+        g3_yaml::json::load_doc(&position.path)?
+    } else {
+        g3_yaml::load_doc(position)?
+    };
     if let Yaml::Hash(map) = doc {
         let importer = load_importer(&map, Some(position.clone()))?;
         registry::add(importer.clone());
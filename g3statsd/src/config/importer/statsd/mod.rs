@@ -6,6 +6,12 @@
 use super::{AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction};
 use super::{CONFIG_KEY_IMPORTER_NAME, CONFIG_KEY_IMPORTER_TYPE};
 
+mod type_override;
+pub(crate) use type_override::StatsdTypeOverride;
+
+mod tee;
+pub(crate) use tee::StatsdTeeConfig;
+
 mod udp;
 pub(crate) use udp::StatsdUdpImporterConfig;
 
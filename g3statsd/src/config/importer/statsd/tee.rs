@@ -0,0 +1,111 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::{Context, anyhow};
+use rand::distr::Bernoulli;
+use yaml_rust::Yaml;
+
+/// Forward the received raw datagrams to secondary StatsD endpoints
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StatsdTeeConfig {
+    pub(crate) targets: Vec<SocketAddr>,
+    pub(crate) sample_ratio: Bernoulli,
+}
+
+// the sample ratio is always in range [0, 1]
+impl Eq for StatsdTeeConfig {}
+
+impl Default for StatsdTeeConfig {
+    fn default() -> Self {
+        StatsdTeeConfig {
+            targets: Vec::new(),
+            sample_ratio: Bernoulli::new(1.0).unwrap(),
+        }
+    }
+}
+
+impl StatsdTeeConfig {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut config = StatsdTeeConfig::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "target" | "targets" => {
+                        config.targets = g3_yaml::value::as_list(v, g3_yaml::value::as_sockaddr)
+                            .context(format!("invalid socket address list value for key {k}"))?;
+                        Ok(())
+                    }
+                    "sample_ratio" => {
+                        config.sample_ratio = g3_yaml::value::as_random_ratio(v)
+                            .context(format!("invalid random ratio value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                config.targets = g3_yaml::value::as_list(value, g3_yaml::value::as_sockaddr)
+                    .context("invalid socket address list value")?;
+            }
+        }
+
+        if config.targets.is_empty() {
+            return Err(anyhow!("no target set"));
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_map() {
+        let yaml = yaml_doc!(
+            r#"
+                targets:
+                  - 127.0.0.1:8125
+                  - "[::1]:8125"
+                sample_ratio: 10%
+            "#
+        );
+        let config = StatsdTeeConfig::parse_yaml(&yaml).unwrap();
+        assert_eq!(
+            config.targets,
+            [
+                SocketAddr::from(([127, 0, 0, 1], 8125)),
+                SocketAddr::from(([0, 0, 0, 0, 0, 0, 0, 1], 8125)),
+            ]
+        );
+        assert_eq!(config.sample_ratio, Bernoulli::from_ratio(10, 100).unwrap());
+    }
+
+    #[test]
+    fn parse_short() {
+        let yaml = Yaml::String("127.0.0.1:8125".to_string());
+        let config = StatsdTeeConfig::parse_yaml(&yaml).unwrap();
+        assert_eq!(config.targets, [SocketAddr::from(([127, 0, 0, 1], 8125))]);
+        assert_eq!(config.sample_ratio, Bernoulli::new(1.0).unwrap());
+    }
+
+    #[test]
+    fn parse_err() {
+        let yaml = yaml_doc!("sample_ratio: 0.5");
+        assert!(StatsdTeeConfig::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                target: 127.0.0.1:8125
+                sample_ratio: 2
+            "#
+        );
+        assert!(StatsdTeeConfig::parse_yaml(&yaml).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use ahash::AHashMap;
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use crate::types::{MetricName, MetricType};
+
+/// Force the type of the metrics that match the name patterns.
+///
+/// The pattern can be:
+///  - an exact metric name, e.g. `foo.bar`
+///  - all metrics under a parent name, e.g. `foo.*`
+///  - all metrics, i.e. `*`
+///
+/// The exact match takes precedence, then the longest parent name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct StatsdTypeOverride {
+    exact: AHashMap<MetricName, MetricType>,
+    /// sorted by the length of the parent name, longest first
+    child: Vec<(MetricName, usize, MetricType)>,
+}
+
+impl StatsdTypeOverride {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for statsd type override should be 'map'"
+            ));
+        };
+
+        let mut type_override = StatsdTypeOverride::default();
+        g3_yaml::foreach_kv(map, |k, v| {
+            let metric_type = match v {
+                Yaml::String(s) => MetricType::from_str(s)?,
+                _ => return Err(anyhow!("the metric type for {k} should be a string")),
+            };
+            type_override
+                .add_pattern(k, metric_type)
+                .context(format!("invalid metric name pattern {k}"))
+        })?;
+        Ok(type_override)
+    }
+
+    fn add_pattern(&mut self, pattern: &str, metric_type: MetricType) -> anyhow::Result<()> {
+        if pattern == "*" {
+            self.add_child(MetricName::from([]), 0, metric_type);
+        } else if let Some(parent) = pattern.strip_suffix(".*") {
+            let parent = MetricName::parse(parent)?;
+            let len = parent.display('.').to_string().len();
+            self.add_child(parent, len, metric_type);
+        } else {
+            let name = MetricName::parse(pattern)?;
+            self.exact.insert(name, metric_type);
+        }
+        Ok(())
+    }
+
+    fn add_child(&mut self, parent: MetricName, len: usize, metric_type: MetricType) {
+        self.child.retain(|(name, _, _)| *name != parent);
+        let pos = self.child.partition_point(|(_, l, _)| *l >= len);
+        self.child.insert(pos, (parent, len, metric_type));
+    }
+
+    pub(crate) fn get(&self, name: &MetricName) -> Option<MetricType> {
+        if let Some(t) = self.exact.get(name) {
+            return Some(*t);
+        }
+        self.child
+            .iter()
+            .find(|(parent, _, _)| name.is_child_of(parent))
+            .map(|(_, _, t)| *t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_and_get() {
+        let yaml = yaml_doc!(
+            r#"
+                "*": counter
+                foo.*: gauge
+                foo.bar.*: counter
+                foo.bar.baz: gauge
+                foo.bar: counter
+            "#
+        );
+        let type_override = StatsdTypeOverride::parse_yaml(&yaml).unwrap();
+
+        let get = |s: &str| type_override.get(&MetricName::parse(s).unwrap());
+        assert_eq!(get("foo.bar.baz"), Some(MetricType::Gauge));
+        assert_eq!(get("foo.bar.qux"), Some(MetricType::Counter));
+        assert_eq!(get("foo.bar"), Some(MetricType::Counter));
+        assert_eq!(get("foo.baz"), Some(MetricType::Gauge));
+        assert_eq!(get("foo"), Some(MetricType::Counter));
+        assert_eq!(get("bar"), Some(MetricType::Counter));
+    }
+
+    #[test]
+    fn no_fallback() {
+        let yaml = yaml_doc!(
+            r#"
+                foo.*: gauge
+                foo.bar.*: counter
+            "#
+        );
+        let type_override = StatsdTypeOverride::parse_yaml(&yaml).unwrap();
+
+        let get = |s: &str| type_override.get(&MetricName::parse(s).unwrap());
+        assert_eq!(get("foo.bar.baz"), Some(MetricType::Counter));
+        assert_eq!(get("foo.baz"), Some(MetricType::Gauge));
+        assert_eq!(get("foo"), None);
+        assert_eq!(get("foobar.baz"), None);
+    }
+
+    #[test]
+    fn parse_err() {
+        let yaml = yaml_doc!("foo: set");
+        assert!(StatsdTypeOverride::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!("foo..bar: gauge");
+        assert!(StatsdTypeOverride::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_doc!("- foo");
+        assert!(StatsdTypeOverride::parse_yaml(&yaml).is_err());
+    }
+}
@@ -11,7 +11,10 @@ use g3_types::metrics::NodeName;
 use g3_types::net::UdpListenConfig;
 use g3_yaml::YamlDocPosition;
 
-use super::{AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction};
+use super::{
+    AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction, StatsdTeeConfig,
+    StatsdTypeOverride,
+};
 
 const IMPORTER_CONFIG_TYPE: &str = "StatsD_UDP";
 
@@ -23,6 +26,8 @@ pub(crate) struct StatsdUdpImporterConfig {
     pub(crate) listen: UdpListenConfig,
     pub(crate) listen_in_worker: bool,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) type_override: Option<StatsdTypeOverride>,
+    pub(crate) tee: Option<StatsdTeeConfig>,
 }
 
 impl StatsdUdpImporterConfig {
@@ -34,6 +39,8 @@ impl StatsdUdpImporterConfig {
             listen: UdpListenConfig::default(),
             listen_in_worker: false,
             ingress_net_filter: None,
+            type_override: None,
+            tee: None,
         }
     }
 
@@ -76,6 +83,18 @@ impl StatsdUdpImporterConfig {
                 self.ingress_net_filter = Some(filter);
                 Ok(())
             }
+            "type_override" => {
+                let type_override = StatsdTypeOverride::parse_yaml(v)
+                    .context(format!("invalid statsd type override value for key {k}"))?;
+                self.type_override = Some(type_override);
+                Ok(())
+            }
+            "tee" => {
+                let tee = StatsdTeeConfig::parse_yaml(v)
+                    .context(format!("invalid statsd tee config value for key {k}"))?;
+                self.tee = Some(tee);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
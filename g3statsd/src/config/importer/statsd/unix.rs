@@ -11,7 +11,7 @@ use yaml_rust::{Yaml, yaml};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::{AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction};
+use super::{AnyImporterConfig, ImporterConfig, ImporterConfigDiffAction, StatsdTypeOverride};
 
 const IMPORTER_CONFIG_TYPE: &str = "StatsD_UNIX";
 
@@ -21,6 +21,7 @@ pub(crate) struct StatsdUnixImporterConfig {
     position: Option<YamlDocPosition>,
     pub(crate) collector: NodeName,
     pub(crate) listen: PathBuf,
+    pub(crate) type_override: Option<StatsdTypeOverride>,
 }
 
 impl StatsdUnixImporterConfig {
@@ -30,6 +31,7 @@ impl StatsdUnixImporterConfig {
             position,
             collector: Default::default(),
             listen: PathBuf::new(),
+            type_override: None,
         }
     }
 
@@ -61,6 +63,12 @@ impl StatsdUnixImporterConfig {
                     .context(format!("invalid unix listen path value for key {k}"))?;
                 Ok(())
             }
+            "type_override" => {
+                let type_override = StatsdTypeOverride::parse_yaml(v)
+                    .context(format!("invalid statsd type override value for key {k}"))?;
+                self.type_override = Some(type_override);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
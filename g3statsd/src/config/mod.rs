@@ -8,6 +8,12 @@ use std::path::Path;
 use anyhow::anyhow;
 use yaml_rust::{Yaml, yaml};
 
+mod check;
+pub use check::check;
+
+mod staging;
+pub(crate) use staging::StagingConfig;
+
 pub(crate) mod collector;
 pub(crate) mod exporter;
 pub(crate) mod importer;
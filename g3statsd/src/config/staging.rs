@@ -0,0 +1,174 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::NodeName;
+
+use super::collector::AnyCollectorConfig;
+use super::exporter::AnyExporterConfig;
+use super::importer::AnyImporterConfig;
+
+/// The config nodes loaded from the config file but not added to the registries,
+/// so the running config and the running objects will not be changed
+#[derive(Default)]
+pub(crate) struct StagingConfig {
+    pub(crate) importers: BTreeMap<NodeName, Arc<AnyImporterConfig>>,
+    pub(crate) collectors: BTreeMap<NodeName, Arc<AnyCollectorConfig>>,
+    pub(crate) exporters: BTreeMap<NodeName, Arc<AnyExporterConfig>>,
+}
+
+impl StagingConfig {
+    pub(crate) async fn load() -> anyhow::Result<Self> {
+        tokio::task::spawn_blocking(StagingConfig::load_blocking)
+            .await
+            .map_err(|e| anyhow!("failed to join staging config load task: {e}"))?
+    }
+
+    fn load_blocking() -> anyhow::Result<Self> {
+        let conf_file =
+            g3_daemon::opts::config_file().ok_or_else(|| anyhow!("no config file set"))?;
+        let conf_dir = g3_daemon::opts::config_dir()
+            .ok_or_else(|| anyhow!("no valid config dir has been set"))?;
+
+        let staging = RefCell::new(StagingConfig::default());
+        // allow multiple docs, and treat them as the same
+        g3_yaml::foreach_doc(conf_file, |_, doc| match doc {
+            Yaml::Hash(map) => staging.borrow_mut().load_doc(map, conf_dir),
+            _ => Err(anyhow!("yaml doc root should be hash")),
+        })?;
+
+        let staging = staging.into_inner();
+        staging.check()?;
+        Ok(staging)
+    }
+
+    fn load_doc(&mut self, map: &yaml::Hash, conf_dir: &Path) -> anyhow::Result<()> {
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "importer" => {
+                for c in super::importer::parse_all(v, conf_dir)? {
+                    let name = c.name().clone();
+                    if self.importers.insert(name.clone(), Arc::new(c)).is_some() {
+                        return Err(anyhow!("importer with name {name} already exists"));
+                    }
+                }
+                Ok(())
+            }
+            "collector" => {
+                for c in super::collector::parse_all(v, conf_dir)? {
+                    let name = c.name().clone();
+                    if self.collectors.insert(name.clone(), Arc::new(c)).is_some() {
+                        return Err(anyhow!("collector with name {name} already exists"));
+                    }
+                }
+                Ok(())
+            }
+            "exporter" => {
+                for c in super::exporter::parse_all(v, conf_dir)? {
+                    let name = c.name().clone();
+                    if self.exporters.insert(name.clone(), Arc::new(c)).is_some() {
+                        return Err(anyhow!("exporter with name {name} already exists"));
+                    }
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        })
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        super::collector::check_topology(&self.collectors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn load_str(s: &str) -> anyhow::Result<StagingConfig> {
+        let mut staging = StagingConfig::default();
+        for doc in YamlLoader::load_from_str(s).unwrap() {
+            let Yaml::Hash(map) = doc else {
+                panic!("yaml doc root should be hash");
+            };
+            staging.load_doc(&map, Path::new("."))?;
+        }
+        staging.check()?;
+        Ok(staging)
+    }
+
+    fn name(s: &'static str) -> NodeName {
+        NodeName::new_static(s)
+    }
+
+    #[test]
+    fn load_without_registry() {
+        let staging = load_str(
+            r#"
+            importer:
+              - name: staging_importer
+                type: dummy
+            collector:
+              - name: staging_regulate
+                type: regulate
+                next: staging_discard
+              - name: staging_discard
+                type: discard
+            exporter:
+              - name: staging_exporter
+                type: discard
+            "#,
+        )
+        .unwrap();
+        assert!(staging.importers.contains_key(&name("staging_importer")));
+        assert_eq!(staging.collectors.len(), 2);
+        assert!(staging.exporters.contains_key(&name("staging_exporter")));
+
+        // the running config should not be changed
+        assert!(
+            !crate::config::importer::get_all()
+                .iter()
+                .any(|c| c.name().as_str() == "staging_importer")
+        );
+        assert!(
+            !crate::config::exporter::get_all()
+                .iter()
+                .any(|c| c.name().as_str() == "staging_exporter")
+        );
+    }
+
+    #[test]
+    fn duplicate_name() {
+        // the docs are treated as the same
+        let r = load_str(
+            "exporter:\n  - name: staging_dup\n    type: discard\n---\n\
+             exporter:\n  - name: staging_dup\n    type: console\n",
+        );
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn loop_dependency() {
+        let r = load_str(
+            r#"
+            collector:
+              - name: staging_loop_a
+                type: regulate
+                next: staging_loop_b
+              - name: staging_loop_b
+                type: regulate
+                next: staging_loop_a
+            "#,
+        );
+        assert!(r.is_err());
+    }
+}
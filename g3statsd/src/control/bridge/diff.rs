@@ -0,0 +1,75 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+
+use crate::config::StagingConfig;
+
+/// Load the config file and get the action for each object if it's reloaded, one line per object.
+///
+/// The config is loaded into a staging set, so neither the running config nor the running objects
+/// will be changed.
+pub(in crate::control) async fn diff_config() -> anyhow::Result<Vec<String>> {
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(async move {
+            let staging = StagingConfig::load().await?;
+            Ok(diff_staging(&staging).await)
+        })
+        .await
+        .map_err(|e| anyhow!("failed to spawn diff task: {e}"))?
+}
+
+async fn diff_staging(staging: &StagingConfig) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (name, action) in crate::import::diff_all(&staging.importers).await {
+        lines.push(format!("importer {name}: {action}"));
+    }
+    for (name, action) in crate::collect::diff_all(&staging.collectors).await {
+        lines.push(format!("collector {name}: {action}"));
+    }
+    for (name, action) in crate::export::diff_all(&staging.exporters).await {
+        lines.push(format!("exporter {name}: {action}"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use yaml_rust::YamlLoader;
+
+    use g3_types::metrics::NodeName;
+
+    use crate::config::exporter::AnyExporterConfig;
+    use crate::config::exporter::discard::DiscardExporterConfig;
+
+    #[tokio::test]
+    async fn diff_new_exporter() {
+        let doc = YamlLoader::load_from_str("name: diff_new\ntype: discard").unwrap();
+        let yaml_rust::Yaml::Hash(map) = &doc[0] else {
+            panic!("yaml doc root should be hash");
+        };
+        let config = DiscardExporterConfig::parse(map, None).unwrap();
+
+        let mut staging = StagingConfig::default();
+        staging.exporters.insert(
+            NodeName::new_static("diff_new"),
+            Arc::new(AnyExporterConfig::Discard(config)),
+        );
+        let lines = diff_staging(&staging).await;
+        assert!(lines.contains(&"exporter diff_new: spawn_new".to_string()));
+
+        // the staging config should not be added to the running config
+        assert!(
+            !crate::config::exporter::get_all()
+                .iter()
+                .any(|c| c.name().as_str() == "diff_new")
+        );
+        assert!(!crate::export::get_names().contains(&NodeName::new_static("diff_new")));
+    }
+}
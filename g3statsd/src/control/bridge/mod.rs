@@ -5,3 +5,6 @@
 
 mod reload;
 pub(super) use reload::{reload_collector, reload_exporter, reload_importer};
+
+mod diff;
+pub(super) use diff::diff_config;
@@ -15,11 +15,16 @@ macro_rules! impl_reload {
             position: Option<YamlDocPosition>,
         ) -> anyhow::Result<()> {
             let name = unsafe { NodeName::new_unchecked(name) };
-            g3_daemon::runtime::main_handle()
-                .ok_or(anyhow!("unable to get main runtime handle"))?
-                .spawn(async move { crate::$m::reload(&name, position).await })
-                .await
-                .map_err(|e| anyhow!("failed to spawn reload task: {e}"))?
+            let r = async move {
+                g3_daemon::runtime::main_handle()
+                    .ok_or(anyhow!("unable to get main runtime handle"))?
+                    .spawn(async move { crate::$m::reload(&name, position).await })
+                    .await
+                    .map_err(|e| anyhow!("failed to spawn reload task: {e}"))?
+            }
+            .await;
+            g3_daemon::metrics::reload::add_object_result(&r);
+            r
         }
     };
 }
@@ -3,9 +3,13 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::str::FromStr;
+
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_types::metrics::NodeName;
+
 use g3statsd_proto::proc_capnp::proc_control;
 use g3statsd_proto::types_capnp::fetch_result;
 
@@ -136,6 +140,81 @@ impl proc_control::Server for ProcControlImpl {
             Ok(())
         })
     }
+
+    fn dump_exporter(
+        &mut self,
+        params: proc_control::DumpExporterParams,
+        mut results: proc_control::DumpExporterResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let name = pry!(pry!(params.get_name()).to_string());
+        let prefix = pry!(pry!(params.get_prefix()).to_string());
+        let limit = params.get_limit() as usize;
+        Promise::from_future(async move {
+            let r = dump_exporter(name, prefix, limit).await;
+            let mut builder = results.get().init_result();
+            match r {
+                Ok(lines) => builder.set_data(lines.as_slice())?,
+                Err(e) => {
+                    let mut ev = builder.init_err();
+                    ev.set_code(-1);
+                    ev.set_reason(format!("{e:?}").as_str());
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn rate_limit_exporter(
+        &mut self,
+        params: proc_control::RateLimitExporterParams,
+        mut results: proc_control::RateLimitExporterResults,
+    ) -> Promise<(), capnp::Error> {
+        let name = pry!(pry!(pry!(params.get()).get_name()).to_string());
+        let r = rate_limit_exporter(name);
+        let mut builder = results.get().init_result();
+        match r {
+            Ok(lines) => pry!(builder.set_data(lines.as_slice())),
+            Err(e) => {
+                let mut ev = builder.init_err();
+                ev.set_code(-1);
+                ev.set_reason(format!("{e:?}").as_str());
+            }
+        }
+        Promise::ok(())
+    }
+
+    fn diff_config(
+        &mut self,
+        _params: proc_control::DiffConfigParams,
+        mut results: proc_control::DiffConfigResults,
+    ) -> Promise<(), capnp::Error> {
+        Promise::from_future(async move {
+            let r = crate::control::bridge::diff_config().await;
+            let mut builder = results.get().init_result();
+            match r {
+                Ok(lines) => builder.set_data(lines.as_slice())?,
+                Err(e) => {
+                    let mut ev = builder.init_err();
+                    ev.set_code(-1);
+                    ev.set_reason(format!("{e:?}").as_str());
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
+async fn dump_exporter(name: String, prefix: String, limit: usize) -> anyhow::Result<Vec<String>> {
+    let name = NodeName::from_str(&name)?;
+    let handle = crate::export::get_aggregate_dump_handle(&name)?;
+    handle.dump(prefix, limit).await
+}
+
+fn rate_limit_exporter(name: String) -> anyhow::Result<Vec<String>> {
+    let name = NodeName::from_str(&name)?;
+    let handle = crate::export::get_http_export_handle(&name)?;
+    Ok(handle.status())
 }
 
 #[allow(unused)]
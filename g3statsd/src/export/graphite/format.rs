@@ -15,13 +15,19 @@ use tokio::sync::mpsc;
 use g3_types::metrics::MetricTagMap;
 
 use crate::config::exporter::graphite::GraphiteExporterConfig;
-use crate::runtime::export::{AggregateExport, CounterStoreValue, GaugeStoreValue, StreamExport};
+use crate::runtime::export::{
+    AggregateExport, AggregateExportUpdate, CounterEmitMode, CounterStoreValue, GaugeStoreValue,
+    GraphiteSanitizeRules, MetricSanitizer, StreamExport, align_timestamp,
+};
 use crate::types::{MetricName, MetricValue};
 
 pub(super) struct GraphitePlaintextAggregateExport {
     emit_interval: Duration,
     prefix: Option<MetricName>,
     global_tags: MetricTagMap,
+    counter_mode: CounterEmitMode,
+    timestamp_align: Option<Duration>,
+    sanitizer: MetricSanitizer<GraphiteSanitizeRules>,
     data_sender: mpsc::UnboundedSender<Vec<u8>>,
 
     buf: Vec<u8>,
@@ -36,28 +42,40 @@ impl GraphitePlaintextAggregateExport {
             emit_interval: config.emit_interval,
             prefix: config.prefix.clone(),
             global_tags: config.global_tags.clone(),
+            counter_mode: config.counter_mode,
+            timestamp_align: config.align_timestamp.then_some(config.emit_interval),
+            sanitizer: MetricSanitizer::new(config.sanitize_action),
             data_sender,
             buf: Vec::with_capacity(2048),
         }
     }
 
+    fn emit_time(&self) -> DateTime<Utc> {
+        let now = Utc::now();
+        match self.timestamp_align {
+            Some(interval) => align_timestamp(&now, interval),
+            None => now,
+        }
+    }
+
     fn serialize(
         &mut self,
         time: &DateTime<Utc>,
-        name: &MetricName,
+        name: &str,
         tags: &MetricTagMap,
         value: &MetricValue,
     ) {
-        if let Some(prefix) = &self.prefix {
-            let _ = write!(self.buf, "{}.{}", prefix.display('.'), name.display('.'));
-        } else {
-            let _ = write!(self.buf, "{}", name.display('.'));
-        }
-        if !self.global_tags.is_empty() {
-            let _ = write!(self.buf, ";{}", self.global_tags.display_graphite());
-        }
-        if !tags.is_empty() {
-            let _ = write!(self.buf, ";{}", tags.display_graphite());
+        let start = self.buf.len();
+        self.buf.extend_from_slice(name.as_bytes());
+        for (k, v) in self.global_tags.iter().chain(tags.iter()) {
+            let (Some(k), Some(v)) = (
+                self.sanitizer.tag_name(k.as_str()),
+                self.sanitizer.tag_value(v.as_str()),
+            ) else {
+                self.buf.truncate(start);
+                return;
+            };
+            let _ = write!(self.buf, ";{k}={v}");
         }
         let _ = write!(self.buf, " {value}");
         let mut ts_buffer = Buffer::new();
@@ -73,30 +91,46 @@ impl AggregateExport for GraphitePlaintextAggregateExport {
         self.emit_interval
     }
 
+    fn update(&mut self, update: AggregateExportUpdate) {
+        self.prefix = update.prefix;
+        self.global_tags = update.global_tags;
+    }
+
     fn emit_gauge(
         &mut self,
         name: &MetricName,
         values: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         self.buf.clear();
-        let now = Utc::now();
+        let now = self.emit_time();
         for (tags, v) in values {
-            self.serialize(&now, name, tags, &v.value);
+            self.serialize(&now, &name, tags, &v.value);
+        }
+        if !self.buf.is_empty() {
+            let _ = self.data_sender.send(self.buf.clone());
         }
-        let _ = self.data_sender.send(self.buf.clone());
     }
 
     fn emit_counter(
         &mut self,
         name: &MetricName,
+        _interval: Duration,
         values: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         self.buf.clear();
-        let now = Utc::now();
+        let now = self.emit_time();
         for (tags, v) in values {
-            self.serialize(&now, name, tags, &v.sum);
+            self.serialize(&now, &name, tags, &v.emit_value(self.counter_mode));
+        }
+        if !self.buf.is_empty() {
+            let _ = self.data_sender.send(self.buf.clone());
         }
-        let _ = self.data_sender.send(self.buf.clone());
     }
 }
 
@@ -14,7 +14,10 @@ use g3_types::metrics::NodeName;
 use super::{ArcExporterInternal, Exporter, ExporterInternal};
 use crate::config::exporter::graphite::GraphiteExporterConfig;
 use crate::config::exporter::{AnyExporterConfig, ExporterConfig};
-use crate::runtime::export::{AggregateExportRuntime, StreamExportRuntime};
+use crate::runtime::export::{
+    AggregateDumpHandle, AggregateExportRuntime, AggregateExportUpdate, AggregateUpdateHandle,
+    StreamExportRuntime,
+};
 use crate::types::MetricRecord;
 
 mod format;
@@ -23,6 +26,8 @@ use format::{GraphitePlaintextAggregateExport, GraphitePlaintextStreamExport};
 pub(crate) struct GraphiteExporter {
     config: GraphiteExporterConfig,
     sender: mpsc::UnboundedSender<(DateTime<Utc>, MetricRecord)>,
+    dump_handle: AggregateDumpHandle,
+    update_handle: AggregateUpdateHandle,
 }
 
 impl GraphiteExporter {
@@ -30,7 +35,17 @@ impl GraphiteExporter {
         let (sender, receiver) = mpsc::unbounded_channel();
         let (agg_sender, agg_receiver) = mpsc::unbounded_channel();
         let aggregate_export = GraphitePlaintextAggregateExport::new(&config, agg_sender);
-        let aggregate_runtime = AggregateExportRuntime::new(aggregate_export, receiver);
+        let aggregate_runtime = AggregateExportRuntime::new(
+            config.name().clone(),
+            aggregate_export,
+            receiver,
+            &config.rollup,
+            config.stale_timeout,
+            config.emit_jitter,
+            config.tag_transform.clone(),
+        );
+        let dump_handle = aggregate_runtime.dump_handle();
+        let update_handle = aggregate_runtime.update_handle();
 
         let http_export = GraphitePlaintextStreamExport::default();
         let http_runtime =
@@ -38,7 +53,12 @@ impl GraphiteExporter {
 
         tokio::spawn(async move { aggregate_runtime.into_running().await });
         tokio::spawn(http_runtime.into_running());
-        GraphiteExporter { config, sender }
+        GraphiteExporter {
+            config,
+            sender,
+            dump_handle,
+            update_handle,
+        }
     }
 
     pub(crate) fn prepare_initial(config: GraphiteExporterConfig) -> ArcExporterInternal {
@@ -57,6 +77,31 @@ impl GraphiteExporter {
             ))
         }
     }
+
+    /// Keep the running runtimes, and only update the in-place updatable fields
+    fn prepare_update_in_place(
+        &self,
+        config: AnyExporterConfig,
+    ) -> anyhow::Result<GraphiteExporter> {
+        if let AnyExporterConfig::Graphite(config) = config {
+            self.update_handle.update(AggregateExportUpdate {
+                prefix: config.prefix.clone(),
+                global_tags: config.global_tags.clone(),
+            })?;
+            Ok(GraphiteExporter {
+                config,
+                sender: self.sender.clone(),
+                dump_handle: self.dump_handle.clone(),
+                update_handle: self.update_handle.clone(),
+            })
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.exporter_type(),
+                config.exporter_type()
+            ))
+        }
+    }
 }
 
 impl Exporter for GraphiteExporter {
@@ -73,6 +118,10 @@ impl Exporter for GraphiteExporter {
     fn add_metric(&self, time: DateTime<Utc>, record: &MetricRecord) {
         let _ = self.sender.send((time, record.clone())); // TODO record drop
     }
+
+    fn aggregate_dump_handle(&self) -> Option<AggregateDumpHandle> {
+        Some(self.dump_handle.clone())
+    }
 }
 
 impl ExporterInternal for GraphiteExporter {
@@ -84,4 +133,9 @@ impl ExporterInternal for GraphiteExporter {
         let exporter = self.prepare_reload(config)?;
         Ok(Arc::new(exporter))
     }
+
+    fn _update_in_place(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal> {
+        let exporter = self.prepare_update_in_place(config)?;
+        Ok(Arc::new(exporter))
+    }
 }
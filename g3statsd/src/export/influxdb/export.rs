@@ -13,15 +13,20 @@ use chrono::{DateTime, Utc};
 use http::uri::PathAndQuery;
 use http::{HeaderMap, HeaderValue, header};
 use itoa::Buffer;
-use tokio::sync::mpsc;
 
 use g3_http::client::HttpForwardRemoteResponse;
 use g3_types::metrics::MetricTagMap;
 
+use super::token::InfluxdbTokenFile;
+use crate::config::exporter::ExporterConfig;
 use crate::config::exporter::influxdb::{InfluxdbExporterConfig, TimestampPrecision};
-use crate::runtime::export::{AggregateExport, CounterStoreValue, GaugeStoreValue, HttpExport};
+use crate::runtime::export::{
+    AggregateExport, AggregateExportUpdate, CounterEmitMode, CounterStoreValue, GaugeStoreValue,
+    HttpExport, HttpExportSender, InfluxdbSanitizeRules, MetricSanitizer, align_timestamp,
+};
 use crate::types::{MetricName, MetricValue};
 
+#[derive(Clone)]
 pub(super) struct InfluxdbEncodedLines {
     len: usize,
     buf: Vec<u8>,
@@ -33,7 +38,10 @@ pub(super) struct InfluxdbAggregateExport {
     max_body_lines: usize,
     prefix: Option<MetricName>,
     global_tags: MetricTagMap,
-    lines_sender: mpsc::UnboundedSender<InfluxdbEncodedLines>,
+    counter_mode: CounterEmitMode,
+    timestamp_align: Option<Duration>,
+    sanitizer: MetricSanitizer<InfluxdbSanitizeRules>,
+    lines_sender: HttpExportSender<InfluxdbEncodedLines>,
 
     buf: Vec<u8>,
 }
@@ -41,7 +49,7 @@ pub(super) struct InfluxdbAggregateExport {
 impl InfluxdbAggregateExport {
     pub(super) fn new<T: InfluxdbExporterConfig>(
         config: &T,
-        lines_sender: mpsc::UnboundedSender<InfluxdbEncodedLines>,
+        lines_sender: HttpExportSender<InfluxdbEncodedLines>,
     ) -> Self {
         InfluxdbAggregateExport {
             emit_interval: config.emit_interval(),
@@ -49,31 +57,40 @@ impl InfluxdbAggregateExport {
             max_body_lines: config.max_body_lines(),
             prefix: config.prefix(),
             global_tags: config.global_tags(),
+            counter_mode: config.counter_mode(),
+            timestamp_align: config.align_timestamp().then_some(config.emit_interval()),
+            sanitizer: MetricSanitizer::new(config.sanitize_action()),
             lines_sender,
             buf: Vec::new(),
         }
     }
 
-    fn serialize_name_tags(&mut self, name: &MetricName, tag_map: &MetricTagMap) {
-        if let Some(prefix) = &self.prefix {
-            let _ = write!(
-                &mut self.buf,
-                "{}.{}",
-                prefix.display('.'),
-                name.display('.')
-            );
-        } else {
-            let _ = write!(&mut self.buf, "{}", name.display('.'));
-        }
-        if !self.global_tags.is_empty() {
-            let _ = write!(&mut self.buf, ",{}", self.global_tags.display_influxdb());
-        }
-        if !tag_map.is_empty() {
-            let _ = write!(&mut self.buf, ",{}", tag_map.display_influxdb());
+    /// Return false and restore the buf if the series is rejected by the sanitizer
+    fn serialize_name_tags(&mut self, name: &str, tag_map: &MetricTagMap) -> bool {
+        let start = self.buf.len();
+        self.buf.extend_from_slice(name.as_bytes());
+        for (k, v) in self.global_tags.iter().chain(tag_map.iter()) {
+            let (Some(k), Some(v)) = (
+                self.sanitizer.tag_name(k.as_str()),
+                self.sanitizer.tag_value(v.as_str()),
+            ) else {
+                self.buf.truncate(start);
+                return false;
+            };
+            let _ = write!(&mut self.buf, ",{k}={v}");
         }
+        true
     }
 
     fn serialize_timestamp(&mut self, time: &DateTime<Utc>) {
+        let aligned;
+        let time = match self.timestamp_align {
+            Some(interval) => {
+                aligned = align_timestamp(time, interval);
+                &aligned
+            }
+            None => time,
+        };
         let mut ts_buffer = Buffer::new();
         match self.precision {
             TimestampPrecision::Seconds => {
@@ -105,7 +122,7 @@ impl InfluxdbAggregateExport {
         if line_number == 0 || self.buf.is_empty() {
             return;
         }
-        let _ = self.lines_sender.send(InfluxdbEncodedLines {
+        self.lines_sender.send(InfluxdbEncodedLines {
             len: line_number,
             buf: self.buf.clone(),
         });
@@ -118,16 +135,26 @@ impl AggregateExport for InfluxdbAggregateExport {
         self.emit_interval
     }
 
+    fn update(&mut self, update: AggregateExportUpdate) {
+        self.prefix = update.prefix;
+        self.global_tags = update.global_tags;
+    }
+
     fn emit_gauge(
         &mut self,
         name: &MetricName,
         values: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         let mut line_number = 0;
         self.buf.clear();
 
         for (tag_map, gauge) in values {
-            self.serialize_name_tags(name, tag_map);
+            if !self.serialize_name_tags(&name, tag_map) {
+                continue;
+            }
 
             let _ = write!(&mut self.buf, " value={}", gauge.value.display_influxdb());
 
@@ -147,23 +174,40 @@ impl AggregateExport for InfluxdbAggregateExport {
     fn emit_counter(
         &mut self,
         name: &MetricName,
+        interval: Duration,
         values: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         let mut line_number = 0;
         self.buf.clear();
 
         for (tag_map, counter) in values {
-            self.serialize_name_tags(name, tag_map);
-
-            let rate =
-                MetricValue::Double(counter.diff.as_f64() / self.emit_interval.as_secs_f64());
-            let _ = write!(
-                &mut self.buf,
-                " count={},diff={},rate={}",
-                counter.sum.display_influxdb(),
-                counter.diff.display_influxdb(),
-                rate.display_influxdb(),
-            );
+            if !self.serialize_name_tags(&name, tag_map) {
+                continue;
+            }
+
+            let rate = MetricValue::Double(counter.diff.as_f64() / interval.as_secs_f64());
+            match self.counter_mode {
+                CounterEmitMode::Absolute => {
+                    let _ = write!(
+                        &mut self.buf,
+                        " count={},diff={},rate={}",
+                        counter.sum.display_influxdb(),
+                        counter.diff.display_influxdb(),
+                        rate.display_influxdb(),
+                    );
+                }
+                CounterEmitMode::Delta => {
+                    let _ = write!(
+                        &mut self.buf,
+                        " diff={},rate={}",
+                        counter.diff.display_influxdb(),
+                        rate.display_influxdb(),
+                    );
+                }
+            }
 
             self.serialize_timestamp(&counter.time);
             self.buf.push(b'\n');
@@ -183,10 +227,14 @@ pub(super) struct InfluxdbHttpExport {
     api_path: PathAndQuery,
     static_headers: HeaderMap,
     max_body_lines: usize,
+    token_file: Option<InfluxdbTokenFile>,
 }
 
 impl InfluxdbHttpExport {
-    pub(super) fn new<T: InfluxdbExporterConfig>(config: &T) -> anyhow::Result<Self> {
+    pub(super) fn new<T>(config: &T) -> anyhow::Result<Self>
+    where
+        T: InfluxdbExporterConfig + ExporterConfig,
+    {
         let api_path = config.build_api_path()?;
         let mut static_headers = HeaderMap::new();
         static_headers.insert(
@@ -194,13 +242,24 @@ impl InfluxdbHttpExport {
             HeaderValue::from_static("text/plain; charset=utf-8"),
         );
         static_headers.insert(header::ACCEPT, HeaderValue::from_static("application/json"));
-        if let Some(v) = config.build_api_token() {
-            static_headers.insert(header::AUTHORIZATION, v);
-        }
+        let token_file = if let Some(path) = config.token_file() {
+            Some(InfluxdbTokenFile::new(
+                config.name().clone(),
+                path,
+                config.auth_scheme(),
+                config.token_refresh_interval(),
+            ))
+        } else {
+            if let Some(v) = config.build_api_token() {
+                static_headers.insert(header::AUTHORIZATION, v);
+            }
+            None
+        };
         Ok(InfluxdbHttpExport {
             api_path,
             static_headers,
             max_body_lines: config.max_body_lines(),
+            token_file,
         })
     }
 }
@@ -232,7 +291,26 @@ impl HttpExport for InfluxdbHttpExport {
         handled_pieces
     }
 
-    fn check_response(&self, rsp: HttpForwardRemoteResponse, body: &[u8]) -> anyhow::Result<()> {
+    fn write_dynamic_headers(&mut self, header_buf: &mut Vec<u8>) {
+        if let Some(token_file) = &mut self.token_file
+            && let Some(value) = token_file.header()
+        {
+            header_buf.extend_from_slice(b"Authorization: ");
+            header_buf.extend_from_slice(value.as_bytes());
+            header_buf.extend_from_slice(b"\r\n");
+        }
+    }
+
+    fn check_response(
+        &mut self,
+        rsp: HttpForwardRemoteResponse,
+        body: &[u8],
+    ) -> anyhow::Result<()> {
+        if rsp.code == 401
+            && let Some(token_file) = &mut self.token_file
+        {
+            token_file.set_unauthorized();
+        }
         if rsp.code != 200 && rsp.code != 204 {
             if let Ok(detail) = std::str::from_utf8(body) {
                 Err(anyhow!("error response: {} {detail}", rsp.code))
@@ -244,3 +322,66 @@ impl HttpExport for InfluxdbHttpExport {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::mpsc;
+
+    fn serialize(precision: TimestampPrecision, align: Option<Duration>) -> String {
+        let (lines_sender, _) = mpsc::channel(1);
+        let lines_sender = HttpExportSender::from(lines_sender);
+        let mut export = InfluxdbAggregateExport {
+            emit_interval: Duration::from_secs(10),
+            precision,
+            max_body_lines: 1000,
+            prefix: None,
+            global_tags: MetricTagMap::default(),
+            counter_mode: CounterEmitMode::default(),
+            timestamp_align: align,
+            sanitizer: MetricSanitizer::new(None),
+            lines_sender,
+            buf: Vec::new(),
+        };
+        let time = DateTime::from_timestamp(1_700_000_006, 123_456_789).unwrap();
+        export.serialize_timestamp(&time);
+        String::from_utf8(export.buf).unwrap()
+    }
+
+    #[test]
+    fn timestamp_align() {
+        let align = Some(Duration::from_secs(10));
+        assert_eq!(serialize(TimestampPrecision::Seconds, None), " 1700000006");
+        assert_eq!(serialize(TimestampPrecision::Seconds, align), " 1700000010");
+        assert_eq!(
+            serialize(TimestampPrecision::MilliSeconds, None),
+            " 1700000006123"
+        );
+        assert_eq!(
+            serialize(TimestampPrecision::MilliSeconds, align),
+            " 1700000010000"
+        );
+        assert_eq!(
+            serialize(TimestampPrecision::MicroSeconds, align),
+            " 1700000010000000"
+        );
+        assert_eq!(
+            serialize(TimestampPrecision::NanoSeconds, None),
+            " 1700000006123456789"
+        );
+        assert_eq!(
+            serialize(TimestampPrecision::NanoSeconds, align),
+            " 1700000010000000000"
+        );
+
+        let align = Some(Duration::from_millis(100));
+        assert_eq!(
+            serialize(TimestampPrecision::MilliSeconds, align),
+            " 1700000006100"
+        );
+        assert_eq!(
+            serialize(TimestampPrecision::NanoSeconds, align),
+            " 1700000006100000000"
+        );
+    }
+}
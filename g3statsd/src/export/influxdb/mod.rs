@@ -8,6 +8,11 @@ use super::{ArcExporterInternal, Exporter, ExporterInternal};
 mod export;
 use export::{InfluxdbAggregateExport, InfluxdbHttpExport};
 
+mod token;
+
+mod v1;
+pub(super) use v1::InfluxdbV1Exporter;
+
 mod v2;
 pub(super) use v2::InfluxdbV2Exporter;
 
@@ -0,0 +1,128 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+use http::HeaderValue;
+use log::{info, warn};
+
+use g3_types::metrics::NodeName;
+
+/// Load the auth token from a file, and reload it after the refresh interval or on auth failure
+pub(super) struct InfluxdbTokenFile {
+    exporter: NodeName,
+    path: PathBuf,
+    scheme: &'static str,
+    refresh_interval: Duration,
+    header: Option<HeaderValue>,
+    expire: Option<Instant>,
+    refresh_count: u64,
+    unauthorized_count: u64,
+}
+
+impl InfluxdbTokenFile {
+    pub(super) fn new(
+        exporter: NodeName,
+        path: PathBuf,
+        scheme: &'static str,
+        refresh_interval: Duration,
+    ) -> Self {
+        InfluxdbTokenFile {
+            exporter,
+            path,
+            scheme,
+            refresh_interval,
+            header: None,
+            expire: None,
+            refresh_count: 0,
+            unauthorized_count: 0,
+        }
+    }
+
+    fn load(&self) -> anyhow::Result<HeaderValue> {
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| anyhow!("failed to read file {}: {e}", self.path.display()))?;
+        let token = content.trim();
+        if token.is_empty() {
+            return Err(anyhow!("no token found in file {}", self.path.display()));
+        }
+        let mut value = HeaderValue::from_str(&format!("{} {token}", self.scheme))
+            .context("invalid token value")?;
+        value.set_sensitive(true);
+        Ok(value)
+    }
+
+    /// Get the current auth header value, the token file will be reloaded if expired
+    pub(super) fn header(&mut self) -> Option<&HeaderValue> {
+        let now = Instant::now();
+        if self.expire.map(|expire| now >= expire).unwrap_or(true) {
+            // retry after the refresh interval even if failed, and keep the old token
+            self.expire = Some(now + self.refresh_interval);
+            match self.load() {
+                Ok(value) => {
+                    if self.header.as_ref().is_some_and(|old| *old != value) {
+                        self.refresh_count += 1;
+                        info!(
+                            "exporter {}: auth token refreshed, total {} times",
+                            self.exporter, self.refresh_count
+                        );
+                    }
+                    self.header = Some(value);
+                }
+                Err(e) => warn!(
+                    "exporter {}: failed to load auth token: {e:?}",
+                    self.exporter
+                ),
+            }
+        }
+        self.header.as_ref()
+    }
+
+    /// Mark the token as invalid after an unauthorized response, so it will be reloaded at the next request
+    pub(super) fn set_unauthorized(&mut self) {
+        self.unauthorized_count += 1;
+        warn!(
+            "exporter {}: unauthorized response received, total {} times",
+            self.exporter, self.unauthorized_count
+        );
+        self.expire = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn reload() {
+        let path = std::env::temp_dir().join(format!("g3statsd-token-{}", std::process::id()));
+        std::fs::write(&path, "abc\n").unwrap();
+
+        let mut token = InfluxdbTokenFile::new(
+            NodeName::from_str("test").unwrap(),
+            path.clone(),
+            "Bearer",
+            Duration::from_secs(60),
+        );
+        assert_eq!(token.header().unwrap(), "Bearer abc");
+
+        // cached before expire
+        std::fs::write(&path, "def").unwrap();
+        assert_eq!(token.header().unwrap(), "Bearer abc");
+
+        token.set_unauthorized();
+        assert_eq!(token.header().unwrap(), "Bearer def");
+        assert_eq!(token.refresh_count, 1);
+        assert_eq!(token.unauthorized_count, 1);
+
+        // keep the old token if failed to reload
+        std::fs::remove_file(&path).unwrap();
+        token.set_unauthorized();
+        assert_eq!(token.header().unwrap(), "Bearer def");
+    }
+}
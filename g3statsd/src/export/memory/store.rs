@@ -64,6 +64,22 @@ impl InnerMap<GaugeStoreValue> {
         queue.push_front(store_v);
         queue.truncate(store_count);
     }
+
+    fn add_delta(
+        &mut self,
+        time: DateTime<Utc>,
+        store_count: usize,
+        tag_map: Arc<MetricTagMap>,
+        delta: MetricValue,
+    ) {
+        let mut store_v = GaugeStoreValue { time, value: delta };
+        let queue = self.inner.entry(tag_map).or_default();
+        if let Some(last_v) = queue.front() {
+            store_v.value += last_v.value;
+        }
+        queue.push_front(store_v);
+        queue.truncate(store_count);
+    }
 }
 
 type CounterInnerMap = Arc<Mutex<InnerMap<CounterStoreValue>>>;
@@ -107,6 +123,17 @@ impl MemoryStore {
                 let mut inner = slot.lock().unwrap();
                 inner.add(time, store_count, record.tag_map.clone(), record.value);
             }
+            MetricType::GaugeDelta => {
+                let mut map = self.gauge.lock().unwrap();
+                let slot = map.entry(record.name.clone()).or_default().clone();
+                drop(map);
+
+                let mut inner = slot.lock().unwrap();
+                inner.add_delta(time, store_count, record.tag_map.clone(), record.value);
+            }
+            MetricType::Timer => {
+                // timer samples should be aggregated to histograms by aggregate collectors
+            }
         };
     }
 }
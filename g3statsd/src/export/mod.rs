@@ -10,14 +10,18 @@ use chrono::{DateTime, Utc};
 use g3_types::metrics::NodeName;
 
 use crate::config::exporter::AnyExporterConfig;
+use crate::runtime::export::{AggregateDumpHandle, HttpExportHandle};
 use crate::types::MetricRecord;
 
 mod registry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{
+    foreach_aggregate_stats, get_aggregate_dump_handle, get_http_export_handle, get_names,
+    get_or_insert_default,
+};
 
 mod ops;
 pub use ops::load_all;
-pub(crate) use ops::reload;
+pub(crate) use ops::{diff_all, reload};
 
 mod console;
 mod discard;
@@ -32,6 +36,16 @@ pub(crate) trait Exporter {
     fn r#type(&self) -> &str;
 
     fn add_metric(&self, time: DateTime<Utc>, record: &MetricRecord);
+
+    /// Get the handle to dump the aggregation state, only for aggregate exporters
+    fn aggregate_dump_handle(&self) -> Option<AggregateDumpHandle> {
+        None
+    }
+
+    /// Get the handle to the http export runtimes, only for http exporters
+    fn http_export_handle(&self) -> Option<Arc<HttpExportHandle>> {
+        None
+    }
 }
 
 trait ExporterInternal: Exporter {
@@ -39,6 +53,11 @@ trait ExporterInternal: Exporter {
 
     fn _reload(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal>;
 
+    /// Update the in-place updatable fields without respawning the runtimes, fallback to reload
+    fn _update_in_place(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal> {
+        self._reload(config)
+    }
+
     fn _clean_to_offline(&self) {}
 }
 
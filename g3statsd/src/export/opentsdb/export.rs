@@ -13,13 +13,16 @@ use chrono::{DateTime, Utc};
 use http::uri::PathAndQuery;
 use http::{HeaderMap, HeaderValue, header};
 use serde_json::{Map, Number, Value};
-use tokio::sync::mpsc;
 
 use g3_http::client::HttpForwardRemoteResponse;
 use g3_types::metrics::MetricTagMap;
 
 use crate::config::exporter::opentsdb::OpentsdbExporterConfig;
-use crate::runtime::export::{AggregateExport, CounterStoreValue, GaugeStoreValue, HttpExport};
+use crate::runtime::export::{
+    AggregateExport, AggregateExportUpdate, CounterEmitMode, CounterStoreValue, GaugeStoreValue,
+    HttpExport, HttpExportSender, HttpStreamingBody, MetricSanitizer, OpentsdbSanitizeRules,
+    align_timestamp,
+};
 use crate::types::{MetricName, MetricValue};
 
 pub(super) struct OpentsdbAggregateExport {
@@ -27,7 +30,10 @@ pub(super) struct OpentsdbAggregateExport {
     max_data_points: usize,
     prefix: Option<MetricName>,
     global_tags: MetricTagMap,
-    values_sender: mpsc::UnboundedSender<Vec<Value>>,
+    counter_mode: CounterEmitMode,
+    timestamp_align: Option<Duration>,
+    sanitizer: MetricSanitizer<OpentsdbSanitizeRules>,
+    values_sender: HttpExportSender<Vec<Value>>,
 
     value_buf: Vec<Value>,
 }
@@ -35,13 +41,16 @@ pub(super) struct OpentsdbAggregateExport {
 impl OpentsdbAggregateExport {
     pub(super) fn new(
         config: &OpentsdbExporterConfig,
-        values_sender: mpsc::UnboundedSender<Vec<Value>>,
+        values_sender: HttpExportSender<Vec<Value>>,
     ) -> Self {
         OpentsdbAggregateExport {
             emit_interval: config.emit_interval,
             max_data_points: config.max_data_points,
             prefix: config.prefix.clone(),
             global_tags: config.global_tags.clone(),
+            counter_mode: config.counter_mode,
+            timestamp_align: config.align_timestamp.then_some(config.emit_interval),
+            sanitizer: MetricSanitizer::new(config.sanitize_action),
             values_sender,
             value_buf: Vec::with_capacity(32),
         }
@@ -49,32 +58,31 @@ impl OpentsdbAggregateExport {
 
     fn build_data_point(
         &self,
-        name: &MetricName,
+        name: &str,
         time: &DateTime<Utc>,
         tags: &MetricTagMap,
         value: &MetricValue,
-    ) -> Value {
+    ) -> Option<Value> {
+        let mut tag_map = Map::with_capacity(self.global_tags.len() + tags.len());
+        for (k, v) in self.global_tags.iter().chain(tags.iter()) {
+            let k = self.sanitizer.tag_name(k.as_str())?;
+            let v = self.sanitizer.tag_value(v.as_str())?;
+            tag_map.insert(k.into_owned(), Value::String(v.into_owned()));
+        }
+
+        let timestamp = match self.timestamp_align {
+            Some(interval) => align_timestamp(time, interval),
+            None => *time,
+        };
         let mut map = Map::with_capacity(4);
-        let name = self
-            .prefix
-            .as_ref()
-            .map(|p| format!("{}.{}", p.display('.'), name.display('.')))
-            .unwrap_or_else(|| name.display('.').to_string());
-        map.insert("metric".to_string(), Value::String(name));
+        map.insert("metric".to_string(), Value::String(name.to_string()));
         map.insert(
             "timestamp".to_string(),
-            Value::Number(Number::from(time.timestamp())),
+            Value::Number(Number::from(timestamp.timestamp())),
         );
         map.insert("value".to_string(), Value::Number(value.as_json_number()));
-        let mut tag_map = Map::with_capacity(tags.len());
-        for (name, value) in self.global_tags.iter() {
-            tag_map.insert(name.to_string(), Value::String(value.to_string()));
-        }
-        for (name, value) in tags.iter() {
-            tag_map.insert(name.to_string(), Value::String(value.to_string()));
-        }
         map.insert("tags".to_string(), Value::Object(tag_map));
-        Value::Object(map)
+        Some(Value::Object(map))
     }
 
     fn send_data_points(&mut self) {
@@ -83,7 +91,7 @@ impl OpentsdbAggregateExport {
         }
         let new_buf = Vec::with_capacity(self.value_buf.capacity());
         let data_points = std::mem::replace(&mut self.value_buf, new_buf);
-        let _ = self.values_sender.send(data_points);
+        self.values_sender.send(data_points);
     }
 }
 
@@ -92,18 +100,27 @@ impl AggregateExport for OpentsdbAggregateExport {
         self.emit_interval
     }
 
+    fn update(&mut self, update: AggregateExportUpdate) {
+        self.prefix = update.prefix;
+        self.global_tags = update.global_tags;
+    }
+
     fn emit_gauge(
         &mut self,
         name: &MetricName,
         values: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         self.value_buf.clear();
         for (tag_map, v) in values {
             if self.value_buf.len() >= self.max_data_points {
                 self.send_data_points();
             }
-            let data = self.build_data_point(name, &v.time, tag_map, &v.value);
-            self.value_buf.push(data);
+            if let Some(data) = self.build_data_point(&name, &v.time, tag_map, &v.value) {
+                self.value_buf.push(data);
+            }
         }
         self.send_data_points();
     }
@@ -111,15 +128,22 @@ impl AggregateExport for OpentsdbAggregateExport {
     fn emit_counter(
         &mut self,
         name: &MetricName,
+        _interval: Duration,
         values: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
     ) {
+        let Some(name) = self.sanitizer.full_name(self.prefix.as_ref(), name) else {
+            return;
+        };
         self.value_buf.clear();
         for (tag_map, v) in values {
             if self.value_buf.len() >= self.max_data_points {
                 self.send_data_points();
             }
-            let data = self.build_data_point(name, &v.time, tag_map, &v.sum);
-            self.value_buf.push(data);
+            if let Some(data) =
+                self.build_data_point(&name, &v.time, tag_map, &v.emit_value(self.counter_mode))
+            {
+                self.value_buf.push(data);
+            }
         }
         self.send_data_points();
     }
@@ -129,6 +153,9 @@ pub(super) struct OpentsdbHttpExport {
     api_path: PathAndQuery,
     static_headers: HeaderMap,
     max_data_points: usize,
+    chunked_transfer: bool,
+    /// the number of data points already sent in the current streaming body
+    streamed_data_points: usize,
 }
 
 impl OpentsdbHttpExport {
@@ -145,6 +172,8 @@ impl OpentsdbHttpExport {
             api_path,
             static_headers,
             max_data_points: config.max_data_points,
+            chunked_transfer: config.chunked_transfer,
+            streamed_data_points: 0,
         })
     }
 }
@@ -191,7 +220,19 @@ impl HttpExport for OpentsdbHttpExport {
         handled_pieces
     }
 
-    fn check_response(&self, rsp: HttpForwardRemoteResponse, body: &[u8]) -> anyhow::Result<()> {
+    fn streaming_body(&mut self) -> Option<&mut (dyn HttpStreamingBody<Vec<Value>> + Send)> {
+        if self.chunked_transfer {
+            Some(self)
+        } else {
+            None
+        }
+    }
+
+    fn check_response(
+        &mut self,
+        rsp: HttpForwardRemoteResponse,
+        body: &[u8],
+    ) -> anyhow::Result<()> {
         if rsp.code != 204 {
             if let Ok(detail) = std::str::from_utf8(body) {
                 Err(anyhow!("error response: {} {detail}", rsp.code))
@@ -203,3 +244,85 @@ impl HttpExport for OpentsdbHttpExport {
         }
     }
 }
+
+impl HttpStreamingBody<Vec<Value>> for OpentsdbHttpExport {
+    fn begin_body(&mut self, chunk_buf: &mut Vec<u8>) {
+        self.streamed_data_points = 0;
+        chunk_buf.push(b'[');
+    }
+
+    fn fill_chunk(&mut self, pieces: &[Vec<Value>], chunk_buf: &mut Vec<u8>) -> usize {
+        let mut added_data_points = 0;
+        let mut handled_pieces = 0;
+
+        for piece in pieces {
+            if handled_pieces > 0 && added_data_points + piece.len() > self.max_data_points {
+                break;
+            }
+            handled_pieces += 1;
+
+            for v in piece {
+                if self.streamed_data_points > 0 {
+                    chunk_buf.push(b',');
+                }
+                let _ = write!(chunk_buf, "{v}");
+                self.streamed_data_points += 1;
+                added_data_points += 1;
+            }
+        }
+
+        handled_pieces
+    }
+
+    fn end_body(&mut self, chunk_buf: &mut Vec<u8>) {
+        chunk_buf.push(b']');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn streaming_body() {
+        let mut export = OpentsdbHttpExport {
+            api_path: PathAndQuery::from_static("/api/put"),
+            static_headers: HeaderMap::new(),
+            max_data_points: 2,
+            chunked_transfer: true,
+            streamed_data_points: 0,
+        };
+        let pieces = [
+            vec![json!(1), json!(2)],
+            vec![],
+            vec![json!(3)],
+            vec![json!(4), json!(5), json!(6)],
+        ];
+
+        let mut body = Vec::new();
+        let mut chunks = Vec::new();
+        let mut chunk_buf = Vec::new();
+        let streaming = export.streaming_body().unwrap();
+        streaming.begin_body(&mut chunk_buf);
+        let mut handled = 0;
+        while handled < pieces.len() {
+            handled += streaming.fill_chunk(&pieces[handled..], &mut chunk_buf);
+            chunks.push(String::from_utf8(chunk_buf.clone()).unwrap());
+            body.append(&mut chunk_buf);
+        }
+        streaming.end_body(&mut chunk_buf);
+        body.append(&mut chunk_buf);
+
+        assert_eq!(chunks, ["[1,2", ",3", ",4,5,6"]);
+        assert_eq!(body, b"[1,2,3,4,5,6]");
+
+        // a new body
+        let streaming = export.streaming_body().unwrap();
+        streaming.begin_body(&mut chunk_buf);
+        let handled = streaming.fill_chunk(&pieces[2..3], &mut chunk_buf);
+        assert_eq!(handled, 1);
+        streaming.end_body(&mut chunk_buf);
+        assert_eq!(chunk_buf, b"[3]");
+    }
+}
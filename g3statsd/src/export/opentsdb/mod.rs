@@ -14,7 +14,10 @@ use g3_types::metrics::NodeName;
 use super::{ArcExporterInternal, Exporter, ExporterInternal};
 use crate::config::exporter::opentsdb::OpentsdbExporterConfig;
 use crate::config::exporter::{AnyExporterConfig, ExporterConfig};
-use crate::runtime::export::{AggregateExportRuntime, HttpExportRuntime};
+use crate::runtime::export::{
+    AggregateDumpHandle, AggregateExportRuntime, AggregateExportUpdate, AggregateUpdateHandle,
+    HttpExportHandle, spawn_http_export,
+};
 use crate::types::MetricRecord;
 
 mod export;
@@ -23,22 +26,37 @@ use export::{OpentsdbAggregateExport, OpentsdbHttpExport};
 pub(crate) struct OpentsdbExporter {
     config: OpentsdbExporterConfig,
     sender: mpsc::UnboundedSender<(DateTime<Utc>, MetricRecord)>,
+    dump_handle: AggregateDumpHandle,
+    update_handle: AggregateUpdateHandle,
+    http_handle: Arc<HttpExportHandle>,
 }
 
 impl OpentsdbExporter {
     fn new(config: OpentsdbExporterConfig) -> anyhow::Result<Self> {
         let (sender, receiver) = mpsc::unbounded_channel();
-        let (agg_sender, agg_receiver) = mpsc::unbounded_channel();
+        let (agg_sender, http_handle) =
+            spawn_http_export(&config.http_export, || OpentsdbHttpExport::new(&config))?;
         let aggregate_export = OpentsdbAggregateExport::new(&config, agg_sender);
-        let aggregate_runtime = AggregateExportRuntime::new(aggregate_export, receiver);
-
-        let http_export = OpentsdbHttpExport::new(&config)?;
-        let http_runtime =
-            HttpExportRuntime::new(config.http_export.clone(), http_export, agg_receiver);
+        let aggregate_runtime = AggregateExportRuntime::new(
+            config.name().clone(),
+            aggregate_export,
+            receiver,
+            &config.rollup,
+            config.stale_timeout,
+            config.emit_jitter,
+            config.tag_transform.clone(),
+        );
+        let dump_handle = aggregate_runtime.dump_handle();
+        let update_handle = aggregate_runtime.update_handle();
 
         tokio::spawn(async move { aggregate_runtime.into_running().await });
-        tokio::spawn(http_runtime.into_running());
-        Ok(OpentsdbExporter { config, sender })
+        Ok(OpentsdbExporter {
+            config,
+            sender,
+            dump_handle,
+            update_handle,
+            http_handle,
+        })
     }
 
     pub(crate) fn prepare_initial(
@@ -59,6 +77,33 @@ impl OpentsdbExporter {
             ))
         }
     }
+
+    /// Keep the running runtimes, and only update the in-place updatable fields
+    fn prepare_update_in_place(
+        &self,
+        config: AnyExporterConfig,
+    ) -> anyhow::Result<OpentsdbExporter> {
+        if let AnyExporterConfig::Opentsdb(config) = config {
+            self.update_handle.update(AggregateExportUpdate {
+                prefix: config.prefix.clone(),
+                global_tags: config.global_tags.clone(),
+            })?;
+            self.http_handle.update(&config.http_export);
+            Ok(OpentsdbExporter {
+                config,
+                sender: self.sender.clone(),
+                dump_handle: self.dump_handle.clone(),
+                update_handle: self.update_handle.clone(),
+                http_handle: self.http_handle.clone(),
+            })
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.exporter_type(),
+                config.exporter_type()
+            ))
+        }
+    }
 }
 
 impl Exporter for OpentsdbExporter {
@@ -75,6 +120,14 @@ impl Exporter for OpentsdbExporter {
     fn add_metric(&self, time: DateTime<Utc>, record: &MetricRecord) {
         let _ = self.sender.send((time, record.clone())); // TODO record drop
     }
+
+    fn aggregate_dump_handle(&self) -> Option<AggregateDumpHandle> {
+        Some(self.dump_handle.clone())
+    }
+
+    fn http_export_handle(&self) -> Option<Arc<HttpExportHandle>> {
+        Some(self.http_handle.clone())
+    }
 }
 
 impl ExporterInternal for OpentsdbExporter {
@@ -86,4 +139,9 @@ impl ExporterInternal for OpentsdbExporter {
         let exporter = self.prepare_reload(config)?;
         Ok(Arc::new(exporter))
     }
+
+    fn _update_in_place(&self, config: AnyExporterConfig) -> anyhow::Result<ArcExporterInternal> {
+        let exporter = self.prepare_update_in_place(config)?;
+        Ok(Arc::new(exporter))
+    }
 }
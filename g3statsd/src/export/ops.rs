@@ -3,7 +3,8 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use log::debug;
@@ -51,6 +52,35 @@ pub async fn load_all() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Get the action for each exporter if the loaded config is applied, without really applying it
+pub(crate) async fn diff_all(
+    all_config: &BTreeMap<NodeName, Arc<AnyExporterConfig>>,
+) -> Vec<(NodeName, &'static str)> {
+    let _guard = EXPORTER_OPS_LOCK.lock().await;
+
+    let mut new_names = HashSet::<NodeName>::new();
+    let mut actions = Vec::new();
+
+    for config in all_config.values() {
+        let name = config.name();
+        new_names.insert(name.clone());
+        let action = match registry::get_config(name) {
+            Some(old) => old.diff_action(config).as_str(),
+            None => "spawn_new",
+        };
+        actions.push((name.clone(), action));
+    }
+
+    for name in registry::get_names() {
+        if !new_names.contains(&name) {
+            actions.push((name, "delete"));
+        }
+    }
+
+    actions.sort_by(|a, b| a.0.cmp(&b.0));
+    actions
+}
+
 pub(crate) async fn reload(
     name: &NodeName,
     position: Option<YamlDocPosition>,
@@ -108,6 +138,10 @@ async fn reload_unlocked(old: AnyExporterConfig, new: AnyExporterConfig) -> anyh
             debug!("exporter {name} reload: will reload from existed");
             reload_existed_unlocked(name, Some(new)).await
         }
+        ExporterConfigDiffAction::UpdateInPlace => {
+            debug!("exporter {name} reload: will update the existed in place");
+            update_existed_in_place_unlocked(name, new).await
+        }
     }
 }
 
@@ -129,6 +163,17 @@ async fn reload_existed_unlocked(
     Ok(())
 }
 
+async fn update_existed_in_place_unlocked(
+    name: &NodeName,
+    new: AnyExporterConfig,
+) -> anyhow::Result<()> {
+    const STATUS: &str = "updated";
+
+    registry::update_in_place(name, new)?;
+    crate::collect::update_dependency_to_exporter(name, STATUS).await;
+    Ok(())
+}
+
 // use async fn to allow tokio schedule
 async fn spawn_new_unlocked(config: AnyExporterConfig) -> anyhow::Result<()> {
     const STATUS: &str = "spawned";
@@ -147,6 +192,9 @@ async fn spawn_new_unlocked(config: AnyExporterConfig) -> anyhow::Result<()> {
         AnyExporterConfig::Opentsdb(config) => {
             super::opentsdb::OpentsdbExporter::prepare_initial(config)?
         }
+        AnyExporterConfig::InfluxdbV1(config) => {
+            super::influxdb::InfluxdbV1Exporter::prepare_initial(config)?
+        }
         AnyExporterConfig::InfluxdbV2(config) => {
             super::influxdb::InfluxdbV2Exporter::prepare_initial(config)?
         }
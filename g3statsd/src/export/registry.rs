@@ -4,7 +4,7 @@
  */
 
 use std::collections::{HashMap, HashSet};
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
 use foldhash::fast::FixedState;
@@ -13,6 +13,7 @@ use g3_types::metrics::NodeName;
 
 use super::{ArcExporter, ArcExporterInternal};
 use crate::config::exporter::AnyExporterConfig;
+use crate::runtime::export::{AggregateDumpHandle, AggregateExportStatsSnapshot, HttpExportHandle};
 
 static RUNTIME_EXPORTER_REGISTRY: Mutex<HashMap<NodeName, ArcExporterInternal, FixedState>> =
     Mutex::new(HashMap::with_hasher(FixedState::with_seed(0)));
@@ -42,6 +43,38 @@ pub(super) fn get_config(name: &NodeName) -> Option<AnyExporterConfig> {
     ht.get(name).map(|exporter| exporter._clone_config())
 }
 
+pub(crate) fn get_aggregate_dump_handle(name: &NodeName) -> anyhow::Result<AggregateDumpHandle> {
+    let ht = RUNTIME_EXPORTER_REGISTRY.lock().unwrap();
+    let Some(exporter) = ht.get(name) else {
+        return Err(anyhow!("no exporter with name {name} found"));
+    };
+    exporter
+        .aggregate_dump_handle()
+        .ok_or_else(|| anyhow!("exporter {name} is not an aggregate exporter"))
+}
+
+pub(crate) fn foreach_aggregate_stats<F>(mut f: F)
+where
+    F: FnMut(&NodeName, AggregateExportStatsSnapshot),
+{
+    let ht = RUNTIME_EXPORTER_REGISTRY.lock().unwrap();
+    for (name, exporter) in ht.iter() {
+        if let Some(handle) = exporter.aggregate_dump_handle() {
+            f(name, handle.stats().snapshot());
+        }
+    }
+}
+
+pub(crate) fn get_http_export_handle(name: &NodeName) -> anyhow::Result<Arc<HttpExportHandle>> {
+    let ht = RUNTIME_EXPORTER_REGISTRY.lock().unwrap();
+    let Some(exporter) = ht.get(name) else {
+        return Err(anyhow!("no exporter with name {name} found"));
+    };
+    exporter
+        .http_export_handle()
+        .ok_or_else(|| anyhow!("exporter {name} is not an http exporter"))
+}
+
 pub(super) fn reload_existed(
     name: &NodeName,
     config: Option<AnyExporterConfig>,
@@ -61,6 +94,20 @@ pub(super) fn reload_existed(
     Ok(())
 }
 
+pub(super) fn update_in_place(name: &NodeName, config: AnyExporterConfig) -> anyhow::Result<()> {
+    let mut ht = RUNTIME_EXPORTER_REGISTRY
+        .lock()
+        .map_err(|e| anyhow!("failed to lock exporter registry: {e}"))?;
+    let Some(old_exporter) = ht.get(name) else {
+        return Err(anyhow!("no exporter with name {name} found"));
+    };
+
+    let exporter = old_exporter._update_in_place(config)?;
+    // the runtimes are shared with the new one, so no offline cleaning here
+    ht.insert(name.clone(), exporter);
+    Ok(())
+}
+
 pub(crate) fn get_or_insert_default(name: &NodeName) -> ArcExporter {
     let mut ht = RUNTIME_EXPORTER_REGISTRY.lock().unwrap();
     ht.entry(name.clone())
@@ -23,7 +23,7 @@ use registry::ImporterRegistry;
 pub(crate) use registry::get_names;
 
 mod ops;
-pub(crate) use ops::{reload, update_dependency_to_collector};
+pub(crate) use ops::{diff_all, reload, update_dependency_to_collector};
 pub use ops::{spawn_all, stop_all};
 
 mod dummy;
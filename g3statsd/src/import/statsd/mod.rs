@@ -3,9 +3,15 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use crate::config::importer::statsd::StatsdTypeOverride;
+use crate::types::{MetricRecord, MetricType};
+
 mod parser;
 use parser::StatsdRecordVisitor;
 
+mod tee;
+use tee::StatsdTee;
+
 mod udp;
 pub(super) use udp::StatsdUdpImporter;
 
@@ -13,3 +19,15 @@ pub(super) use udp::StatsdUdpImporter;
 mod unix;
 #[cfg(unix)]
 pub(super) use unix::StatsdUnixImporter;
+
+fn override_type(record: &mut MetricRecord, type_override: Option<&StatsdTypeOverride>) {
+    if let Some(type_override) = type_override
+        && let Some(metric_type) = type_override.get(&record.name)
+    {
+        record.r#type = match (record.r#type, metric_type) {
+            // keep the relative semantics
+            (MetricType::GaugeDelta, MetricType::Gauge) => MetricType::GaugeDelta,
+            (_, t) => t,
+        };
+    }
+}
@@ -141,10 +141,17 @@ impl Iterator for LineValueIter<'_> {
                 continue;
             }
 
+            // a gauge value with explicit sign is a delta to the current value
+            let r#type = if self.r#type == MetricType::Gauge && matches!(value[0], b'+' | b'-') {
+                MetricType::GaugeDelta
+            } else {
+                self.r#type
+            };
+
             return match std::str::from_utf8(value) {
                 Ok(s) => match MetricValue::from_str(s) {
                     Ok(value) => Some(Ok(MetricRecord {
-                        r#type: self.r#type,
+                        r#type,
                         name: self.name.clone(),
                         tag_map: self.tag_map.clone(),
                         value,
@@ -163,6 +170,11 @@ fn parse_type(part: &[u8]) -> Result<MetricType, StatsdParseError> {
         1 => match part[0] {
             b'c' => Ok(MetricType::Counter),
             b'g' => Ok(MetricType::Gauge),
+            b'h' => Ok(MetricType::Timer),
+            _ => Err(StatsdParseError::UnsupportedType),
+        },
+        2 => match part {
+            b"ms" => Ok(MetricType::Timer),
             _ => Err(StatsdParseError::UnsupportedType),
         },
         _ => Err(StatsdParseError::UnsupportedType),
@@ -204,9 +216,57 @@ mod tests {
         let parser = LineParser::new(gauge);
         let mut iter = parser.parse().unwrap();
         let r = iter.next().unwrap().unwrap();
-        assert_eq!(r.r#type, MetricType::Gauge);
+        assert_eq!(r.r#type, MetricType::GaugeDelta);
         assert_eq!(r.value, MetricValue::Signed(-10));
         assert!(r.name.display('.').to_string().as_bytes().eq(b"gaugor"));
+
+        let gauge = b"gaugor:+4|g";
+        let parser = LineParser::new(gauge);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::GaugeDelta);
+        assert_eq!(r.value, MetricValue::Unsigned(4));
+        assert!(r.name.display('.').to_string().as_bytes().eq(b"gaugor"));
+
+        let timer = b"glork:320|ms";
+        let parser = LineParser::new(timer);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Timer);
+        assert_eq!(r.value, MetricValue::Unsigned(320));
+        assert!(r.name.display('.').to_string().as_bytes().eq(b"glork"));
+
+        let histogram = b"glork:2.5|h";
+        let parser = LineParser::new(histogram);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Timer);
+        assert_eq!(r.value, MetricValue::Double(2.5));
+    }
+
+    #[test]
+    fn gauge_delta() {
+        let gauge = b"gaugor:1:+2.5:-3|g";
+        let parser = LineParser::new(gauge);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Gauge);
+        assert_eq!(r.value, MetricValue::Unsigned(1));
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::GaugeDelta);
+        assert_eq!(r.value, MetricValue::Double(2.5));
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::GaugeDelta);
+        assert_eq!(r.value, MetricValue::Signed(-3));
+        assert!(iter.next().is_none());
+
+        // only for gauges
+        let counter = b"gorets:+1|c";
+        let parser = LineParser::new(counter);
+        let mut iter = parser.parse().unwrap();
+        let r = iter.next().unwrap().unwrap();
+        assert_eq!(r.r#type, MetricType::Counter);
+        assert_eq!(r.value, MetricValue::Unsigned(1));
     }
 
     #[test]
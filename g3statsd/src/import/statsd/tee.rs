@@ -0,0 +1,55 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::UdpSocket;
+
+use anyhow::Context;
+use rand::Rng;
+use rand::distr::Bernoulli;
+
+use g3_socket::BindAddr;
+
+use crate::config::importer::statsd::StatsdTeeConfig;
+
+pub(super) struct StatsdTee {
+    sockets: Vec<UdpSocket>,
+    sample_ratio: Bernoulli,
+}
+
+impl StatsdTee {
+    pub(super) fn new(config: &StatsdTeeConfig) -> anyhow::Result<Self> {
+        let mut sockets = Vec::with_capacity(config.targets.len());
+        for target in &config.targets {
+            let socket = g3_socket::udp::new_std_socket_to(
+                *target,
+                &BindAddr::None,
+                Default::default(),
+                Default::default(),
+            )
+            .context(format!(
+                "failed to create udp socket to tee target {target}"
+            ))?;
+            socket
+                .connect(target)
+                .context(format!("failed to connect to tee target {target}"))?;
+            sockets.push(socket);
+        }
+        Ok(StatsdTee {
+            sockets,
+            sample_ratio: config.sample_ratio,
+        })
+    }
+
+    /// Forward the raw packet to all the targets, the errors will be ignored
+    pub(super) fn send(&self, packet: &[u8]) {
+        if !rand::rng().sample(self.sample_ratio) {
+            return;
+        }
+        for socket in &self.sockets {
+            // the sockets are non-blocking, so this won't block the receive runtime
+            let _ = socket.send(packet);
+        }
+    }
+}
@@ -21,7 +21,7 @@ use g3_daemon::server::{BaseServer, ServerReloadCommand};
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
 
-use super::StatsdRecordVisitor;
+use super::{StatsdRecordVisitor, StatsdTee};
 use crate::collect::ArcCollector;
 use crate::config::importer::statsd::StatsdUdpImporterConfig;
 use crate::config::importer::{AnyImporterConfig, ImporterConfig};
@@ -32,6 +32,7 @@ use crate::import::{
 pub(crate) struct StatsdUdpImporter {
     config: StatsdUdpImporterConfig,
     ingress_net_filter: Option<AclNetworkRule>,
+    tee: Option<StatsdTee>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
 
     collector: ArcSwap<ArcCollector>,
@@ -39,7 +40,7 @@ pub(crate) struct StatsdUdpImporter {
 }
 
 impl StatsdUdpImporter {
-    fn new(config: StatsdUdpImporterConfig, reload_version: usize) -> Self {
+    fn new(config: StatsdUdpImporterConfig, reload_version: usize) -> anyhow::Result<Self> {
         let reload_sender = crate::import::new_reload_notify_channel();
 
         let ingress_net_filter = config
@@ -47,27 +48,30 @@ impl StatsdUdpImporter {
             .as_ref()
             .map(|builder| builder.build());
 
+        let tee = config.tee.as_ref().map(StatsdTee::new).transpose()?;
+
         let collector = Arc::new(crate::collect::get_or_insert_default(config.collector()));
 
-        StatsdUdpImporter {
+        Ok(StatsdUdpImporter {
             config,
             ingress_net_filter,
+            tee,
             reload_sender,
             collector: ArcSwap::new(collector),
             reload_version,
-        }
+        })
     }
 
     pub(crate) fn prepare_initial(
         config: StatsdUdpImporterConfig,
     ) -> anyhow::Result<ArcImporterInternal> {
-        let server = StatsdUdpImporter::new(config, 1);
+        let server = StatsdUdpImporter::new(config, 1)?;
         Ok(Arc::new(server))
     }
 
     fn prepare_reload(&self, config: AnyImporterConfig) -> anyhow::Result<StatsdUdpImporter> {
         if let AnyImporterConfig::StatsDUdp(config) = config {
-            Ok(StatsdUdpImporter::new(config, self.reload_version + 1))
+            StatsdUdpImporter::new(config, self.reload_version + 1)
         } else {
             Err(anyhow!(
                 "config type mismatch: expect {}, actual {}",
@@ -170,12 +174,23 @@ impl ReceiveUdpServer for StatsdUdpImporter {
             return;
         }
 
+        if let Some(tee) = &self.tee {
+            tee.send(packet);
+        }
+
         let time = Utc::now();
+        let collector = self.collector.load();
         let iter = StatsdRecordVisitor::new(packet);
         for r in iter {
             match r {
-                Ok(r) => self.collector.load().add_metric(time, r, worker_id),
+                Ok(mut r) => {
+                    super::override_type(&mut r, self.config.type_override.as_ref());
+                    collector.add_metric(time, r, worker_id)
+                }
                 Err(e) => {
+                    if let Some(stats) = collector.stats() {
+                        stats.add_parse_error();
+                    }
                     debug!("invalid StatsD record from {client_addr}: {e}");
                 }
             }
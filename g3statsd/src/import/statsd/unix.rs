@@ -144,11 +144,18 @@ impl ReceiveUdpServer for StatsdUnixImporter {
 impl ReceiveUnixDatagramServer for StatsdUnixImporter {
     fn receive_unix_packet(&self, packet: &[u8], client_addr: SocketAddr) {
         let time = Utc::now();
+        let collector = self.collector.load();
         let iter = StatsdRecordVisitor::new(packet);
         for r in iter {
             match r {
-                Ok(r) => self.collector.load().add_metric(time, r, None),
+                Ok(mut r) => {
+                    super::override_type(&mut r, self.config.type_override.as_ref());
+                    collector.add_metric(time, r, None)
+                }
                 Err(e) => {
+                    if let Some(stats) = collector.stats() {
+                        stats.add_parse_error();
+                    }
                     debug!("invalid StatsD record from {client_addr:?}: {e}");
                 }
             }
@@ -19,6 +19,8 @@ fn main() -> anyhow::Result<()> {
 
     // set up process logger early, only proc args is used inside
     g3_daemon::log::process::setup(&proc_args.daemon_config);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_CONFIG);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_LISTEN);
     if proc_args.daemon_config.need_daemon_controller() {
         g3statsd::control::UpgradeActor::connect_to_old_daemon();
     }
@@ -27,11 +29,18 @@ fn main() -> anyhow::Result<()> {
         Ok(c) => c,
         Err(e) => {
             g3_daemon::control::upgrade::cancel_old_shutdown();
+            if proc_args.daemon_config.check_config {
+                g3_daemon::opts::CheckReport::print_load_error(&e);
+            }
             return Err(e.context(format!("failed to load config, opts: {:?}", &proc_args)));
         }
     };
     debug!("loaded config from {}", config_file.display());
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_CONFIG);
 
+    if proc_args.daemon_config.check_config {
+        return g3statsd::config::check().print();
+    }
     if proc_args.daemon_config.test_config {
         info!("the format of the config file is ok");
         return Ok(());
@@ -105,5 +114,6 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3statsd::import::spawn_all()
         .await
         .context("failed to spawn all importers")?;
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_LISTEN);
     Ok(())
 }
@@ -0,0 +1,85 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Round the time to the nearest boundary of the interval since the unix epoch
+pub(crate) fn align_timestamp(time: &DateTime<Utc>, interval: Duration) -> DateTime<Utc> {
+    let interval = interval.as_nanos() as i128;
+    if interval == 0 {
+        return *time;
+    }
+
+    let nanos = time.timestamp() as i128 * NANOS_PER_SECOND + time.timestamp_subsec_nanos() as i128;
+    let aligned = (nanos + interval / 2).div_euclid(interval) * interval;
+    let secs = aligned.div_euclid(NANOS_PER_SECOND) as i64;
+    let subsec_nanos = aligned.rem_euclid(NANOS_PER_SECOND) as u32;
+    DateTime::from_timestamp(secs, subsec_nanos).unwrap_or(*time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(secs: i64, nanos: u32) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, nanos).unwrap()
+    }
+
+    #[test]
+    fn seconds() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(align_timestamp(&time(1000, 0), interval), time(1000, 0));
+        assert_eq!(
+            align_timestamp(&time(1004, 999_999_999), interval),
+            time(1000, 0)
+        );
+        assert_eq!(align_timestamp(&time(1005, 0), interval), time(1010, 0));
+        assert_eq!(align_timestamp(&time(1009, 1), interval), time(1010, 0));
+
+        let interval = Duration::from_secs(60);
+        assert_eq!(align_timestamp(&time(1029, 0), interval), time(1020, 0));
+        assert_eq!(align_timestamp(&time(1051, 0), interval), time(1080, 0));
+    }
+
+    #[test]
+    fn sub_seconds() {
+        let interval = Duration::from_millis(500);
+        assert_eq!(
+            align_timestamp(&time(1000, 249_000_000), interval),
+            time(1000, 0)
+        );
+        assert_eq!(
+            align_timestamp(&time(1000, 251_000_000), interval),
+            time(1000, 500_000_000)
+        );
+        assert_eq!(
+            align_timestamp(&time(1000, 800_000_000), interval),
+            time(1001, 0)
+        );
+
+        let interval = Duration::from_micros(100);
+        assert_eq!(
+            align_timestamp(&time(1000, 123_456_789), interval),
+            time(1000, 123_500_000)
+        );
+
+        let interval = Duration::from_nanos(1);
+        assert_eq!(
+            align_timestamp(&time(1000, 123_456_789), interval),
+            time(1000, 123_456_789)
+        );
+    }
+
+    #[test]
+    fn before_epoch() {
+        let interval = Duration::from_secs(10);
+        assert_eq!(align_timestamp(&time(-14, 0), interval), time(-10, 0));
+        assert_eq!(align_timestamp(&time(-16, 0), interval), time(-20, 0));
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use tokio::sync::{mpsc, oneshot};
+
+use g3_types::metrics::MetricTagMap;
+
+use super::{AggregateExportStats, CounterStoreValue, GaugeStoreValue, InnerMap};
+use crate::types::{MetricName, MetricValue};
+
+/// The max number of series that can be dumped in a single request
+pub(crate) const MAX_DUMP_LIMIT: usize = 10000;
+
+pub(super) struct AggregateDumpRequest {
+    prefix: String,
+    limit: usize,
+    result_sender: oneshot::Sender<Vec<String>>,
+}
+
+/// Dump the current aggregation state of an aggregate export runtime
+#[derive(Clone)]
+pub(crate) struct AggregateDumpHandle {
+    sender: mpsc::Sender<AggregateDumpRequest>,
+    stats: Arc<AggregateExportStats>,
+}
+
+impl AggregateDumpHandle {
+    pub(super) fn new(
+        sender: mpsc::Sender<AggregateDumpRequest>,
+        stats: Arc<AggregateExportStats>,
+    ) -> Self {
+        AggregateDumpHandle { sender, stats }
+    }
+
+    pub(crate) fn stats(&self) -> &Arc<AggregateExportStats> {
+        &self.stats
+    }
+
+    /// Get at most `limit` series whose dotted name starts with `prefix`, one json object per series.
+    /// The limit will be clamped to [`MAX_DUMP_LIMIT`].
+    pub(crate) async fn dump(&self, prefix: String, limit: usize) -> anyhow::Result<Vec<String>> {
+        let (result_sender, result_receiver) = oneshot::channel();
+        self.sender
+            .try_send(AggregateDumpRequest {
+                prefix,
+                limit: limit.min(MAX_DUMP_LIMIT),
+                result_sender,
+            })
+            .map_err(|e| anyhow!("failed to send dump request: {e}"))?;
+        result_receiver
+            .await
+            .map_err(|_| anyhow!("the aggregate runtime is offline"))
+    }
+}
+
+impl AggregateDumpRequest {
+    pub(super) fn response(
+        self,
+        gauge: &AHashMap<Arc<MetricName>, InnerMap<GaugeStoreValue>>,
+        counter: &AHashMap<Arc<MetricName>, InnerMap<CounterStoreValue>>,
+    ) {
+        let mut dumper = AggregateDumper::new(self.prefix, self.limit);
+        for (name, inner) in gauge {
+            dumper.dump_map(name, inner, |v, map| {
+                map.insert("type".to_string(), Value::from("gauge"));
+                map.insert("value".to_string(), dump_value(&v.value));
+                v.time
+            });
+        }
+        for (name, inner) in counter {
+            dumper.dump_map(name, inner, |v, map| {
+                map.insert("type".to_string(), Value::from("counter"));
+                map.insert("sum".to_string(), dump_value(&v.sum));
+                map.insert("diff".to_string(), dump_value(&v.diff));
+                v.time
+            });
+        }
+        let _ = self.result_sender.send(dumper.result);
+    }
+}
+
+fn dump_value(v: &MetricValue) -> Value {
+    Value::Number(v.as_json_number())
+}
+
+struct AggregateDumper {
+    prefix: String,
+    limit: usize,
+    result: Vec<String>,
+}
+
+impl AggregateDumper {
+    fn new(prefix: String, limit: usize) -> Self {
+        AggregateDumper {
+            prefix,
+            limit,
+            result: Vec::new(),
+        }
+    }
+
+    fn dump_map<T, F>(&mut self, name: &MetricName, inner: &InnerMap<T>, fill_value: F)
+    where
+        F: Fn(&T, &mut Map<String, Value>) -> DateTime<Utc>,
+    {
+        if self.result.len() >= self.limit {
+            return;
+        }
+        let name = name.display('.').to_string();
+        if !name.starts_with(&self.prefix) {
+            return;
+        }
+
+        for (tag_map, v) in &inner.inner {
+            if self.result.len() >= self.limit {
+                return;
+            }
+
+            let mut map = Map::with_capacity(6);
+            map.insert("name".to_string(), Value::String(name.clone()));
+            map.insert("tags".to_string(), dump_tag_map(tag_map));
+            let last_seen = fill_value(v, &mut map);
+            map.insert(
+                "last_seen".to_string(),
+                Value::String(last_seen.to_rfc3339()),
+            );
+            self.result.push(Value::Object(map).to_string());
+        }
+    }
+}
+
+fn dump_tag_map(tag_map: &MetricTagMap) -> Value {
+    let mut map = Map::with_capacity(tag_map.len());
+    for (k, v) in tag_map.iter() {
+        map.insert(
+            k.as_str().to_string(),
+            Value::String(v.as_str().to_string()),
+        );
+    }
+    Value::Object(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    use g3_types::metrics::{MetricTagName, MetricTagValue};
+
+    #[test]
+    fn dump() {
+        let time = DateTime::from_timestamp(1700000000, 0).unwrap();
+        let mut tag_map = MetricTagMap::default();
+        tag_map.insert(
+            MetricTagName::from_str("k").unwrap(),
+            MetricTagValue::from_str("v").unwrap(),
+        );
+        let tag_map = Arc::new(tag_map);
+
+        let mut gauge = AHashMap::new();
+        for name in ["foo.gauge", "bar.gauge"] {
+            let mut inner = InnerMap::default();
+            inner.inner.insert(
+                tag_map.clone(),
+                GaugeStoreValue {
+                    time,
+                    value: MetricValue::Signed(-1),
+                },
+            );
+            gauge.insert(Arc::new(MetricName::parse(name).unwrap()), inner);
+        }
+        let mut counter = AHashMap::new();
+        let mut inner = InnerMap::default();
+        inner.inner.insert(
+            Arc::new(MetricTagMap::default()),
+            CounterStoreValue {
+                time,
+                sum: MetricValue::Unsigned(10),
+                diff: MetricValue::Unsigned(2),
+            },
+        );
+        counter.insert(Arc::new(MetricName::parse("foo.counter").unwrap()), inner);
+
+        let (result_sender, mut result_receiver) = oneshot::channel();
+        let req = AggregateDumpRequest {
+            prefix: "foo.".to_string(),
+            limit: 10,
+            result_sender,
+        };
+        req.response(&gauge, &counter);
+        let result = result_receiver.try_recv().unwrap();
+        assert_eq!(result.len(), 2);
+        let v: Value = serde_json::from_str(&result[0]).unwrap();
+        assert_eq!(v["name"], "foo.gauge");
+        assert_eq!(v["type"], "gauge");
+        assert_eq!(v["value"], -1);
+        assert_eq!(v["tags"]["k"], "v");
+        assert_eq!(v["last_seen"], "2023-11-14T22:13:20+00:00");
+        let v: Value = serde_json::from_str(&result[1]).unwrap();
+        assert_eq!(v["name"], "foo.counter");
+        assert_eq!(v["type"], "counter");
+        assert_eq!(v["sum"], 10);
+        assert_eq!(v["diff"], 2);
+        assert!(v["tags"].as_object().unwrap().is_empty());
+
+        let (result_sender, mut result_receiver) = oneshot::channel();
+        let req = AggregateDumpRequest {
+            prefix: String::new(),
+            limit: 1,
+            result_sender,
+        };
+        req.response(&gauge, &counter);
+        assert_eq!(result_receiver.try_recv().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn clamp_limit() {
+        let (sender, mut receiver) = mpsc::channel(1);
+        let handle = AggregateDumpHandle::new(sender, Arc::default());
+        let task = tokio::spawn(async move { handle.dump(String::new(), usize::MAX).await });
+        let req = receiver.recv().await.unwrap();
+        assert_eq!(req.limit, MAX_DUMP_LIMIT);
+        req.response(&AHashMap::new(), &AHashMap::new());
+        assert!(task.await.unwrap().unwrap().is_empty());
+    }
+}
@@ -3,17 +3,46 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::collections::hash_map;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 
 use ahash::AHashMap;
-use chrono::{DateTime, Utc};
+use anyhow::anyhow;
+use chrono::{DateTime, TimeDelta, Utc};
+use log::debug;
 use tokio::sync::mpsc;
+use yaml_rust::Yaml;
 
-use g3_types::metrics::MetricTagMap;
+use g3_types::metrics::{MetricTagMap, NodeName};
 
 use crate::types::{MetricName, MetricRecord, MetricType, MetricValue};
 
+mod align;
+pub(crate) use align::align_timestamp;
+
+mod dump;
+pub(crate) use dump::AggregateDumpHandle;
+use dump::AggregateDumpRequest;
+
+mod stats;
+pub(crate) use stats::{AggregateExportStats, AggregateExportStatsSnapshot};
+
+mod rollup;
+pub(crate) use rollup::AggregateRollupConfig;
+use rollup::RollupStore;
+
+mod timer;
+pub(crate) use timer::AggregateEmitJitter;
+use timer::EmitTimer;
+
+mod transform;
+pub(crate) use transform::AggregateTagTransform;
+
+mod update;
+pub(crate) use update::{AggregateExportUpdate, AggregateUpdateHandle};
+
 struct InnerMap<T> {
     inner: AHashMap<Arc<MetricTagMap>, T>,
 }
@@ -34,20 +63,70 @@ pub(crate) trait AggregateExport {
         name: &MetricName,
         values: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>,
     );
+    /// The `interval` is the time range covered by the counter diff values
     fn emit_counter(
         &mut self,
         name: &MetricName,
+        interval: Duration,
         values: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
     );
+
+    fn update(&mut self, update: AggregateExportUpdate);
 }
 
 pub(crate) struct AggregateExportRuntime<T: AggregateExport> {
+    name: NodeName,
     exporter: T,
     receiver: mpsc::UnboundedReceiver<(DateTime<Utc>, MetricRecord)>,
+    dump_sender: mpsc::Sender<AggregateDumpRequest>,
+    dump_receiver: mpsc::Receiver<AggregateDumpRequest>,
+    update_sender: mpsc::Sender<AggregateExportUpdate>,
+    update_receiver: mpsc::Receiver<AggregateExportUpdate>,
     store_time: DateTime<Utc>,
+    emit_interval: Duration,
+    emit_jitter: AggregateEmitJitter,
+    tag_transform: AggregateTagTransform,
+    emit_count: u64,
+    /// the extra time to keep the idle series after the current emit interval
+    stale_delay: TimeDelta,
+    stats: Arc<AggregateExportStats>,
 
     counter: AHashMap<Arc<MetricName>, InnerMap<CounterStoreValue>>,
     gauge: AHashMap<Arc<MetricName>, InnerMap<GaugeStoreValue>>,
+    rollups: Vec<RollupStore>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum CounterEmitMode {
+    /// emit the total value since the series is added
+    #[default]
+    Absolute,
+    /// emit the increments since the last emit
+    Delta,
+}
+
+impl CounterEmitMode {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::String(s) = value {
+            CounterEmitMode::from_str(s)
+        } else {
+            Err(anyhow!(
+                "yaml value type for counter emit mode should be string"
+            ))
+        }
+    }
+}
+
+impl FromStr for CounterEmitMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "absolute" | "total" | "sum" => Ok(CounterEmitMode::Absolute),
+            "delta" | "diff" => Ok(CounterEmitMode::Delta),
+            _ => Err(anyhow!("invalid counter emit mode: {s}")),
+        }
+    }
 }
 
 pub(crate) struct CounterStoreValue {
@@ -56,6 +135,46 @@ pub(crate) struct CounterStoreValue {
     pub(crate) diff: MetricValue,
 }
 
+impl CounterStoreValue {
+    fn new(time: DateTime<Utc>, value: MetricValue) -> Self {
+        CounterStoreValue {
+            time,
+            sum: value,
+            diff: value,
+        }
+    }
+
+    /// Add a new increment, the diff will be restarted if this is the first one in the current emit interval.
+    ///
+    /// The increment may be negative. Only the wraparound of an unsigned total value will be treated
+    /// as a counter reset, and the total value will restart from the new increment.
+    /// Return the increment actually applied and whether there is a reset.
+    fn add(&mut self, time: DateTime<Utc>, value: MetricValue) -> (MetricValue, bool) {
+        if self.time != time {
+            self.time = time;
+            self.diff = MetricValue::Unsigned(0);
+        }
+        let reset = match (self.sum, value) {
+            (MetricValue::Unsigned(sum), MetricValue::Unsigned(v)) => sum.checked_add(v).is_none(),
+            _ => false,
+        };
+        if reset {
+            self.sum = value;
+        } else {
+            self.sum += value;
+        }
+        self.diff += value;
+        (value, reset)
+    }
+
+    pub(crate) fn emit_value(&self, mode: CounterEmitMode) -> MetricValue {
+        match mode {
+            CounterEmitMode::Absolute => self.sum,
+            CounterEmitMode::Delta => self.diff,
+        }
+    }
+}
+
 pub(crate) struct GaugeStoreValue {
     pub(crate) time: DateTime<Utc>,
     pub(crate) value: MetricValue,
@@ -63,25 +182,61 @@ pub(crate) struct GaugeStoreValue {
 
 impl<T: AggregateExport> AggregateExportRuntime<T> {
     pub(crate) fn new(
+        name: NodeName,
         exporter: T,
         receiver: mpsc::UnboundedReceiver<(DateTime<Utc>, MetricRecord)>,
+        rollup: &AggregateRollupConfig,
+        stale_timeout: Option<Duration>,
+        emit_jitter: AggregateEmitJitter,
+        tag_transform: AggregateTagTransform,
     ) -> Self {
+        let emit_interval = exporter.emit_interval();
+        let stale_delay = stale_timeout
+            .and_then(|timeout| timeout.checked_sub(emit_interval))
+            .and_then(|delay| TimeDelta::from_std(delay).ok())
+            .unwrap_or_default();
+        let (dump_sender, dump_receiver) = mpsc::channel(4);
+        let (update_sender, update_receiver) = mpsc::channel(4);
+        let rollups = rollup
+            .windows()
+            .iter()
+            .map(|w| RollupStore::new(*w, emit_interval))
+            .collect();
         AggregateExportRuntime {
+            name,
             exporter,
             receiver,
+            dump_sender,
+            dump_receiver,
+            update_sender,
+            update_receiver,
             store_time: Utc::now(),
+            emit_interval,
+            emit_jitter,
+            tag_transform,
+            emit_count: 0,
+            stale_delay,
+            stats: Arc::new(AggregateExportStats::default()),
             counter: AHashMap::default(),
             gauge: AHashMap::default(),
+            rollups,
         }
     }
 
+    pub(crate) fn dump_handle(&self) -> AggregateDumpHandle {
+        AggregateDumpHandle::new(self.dump_sender.clone(), self.stats.clone())
+    }
+
+    pub(crate) fn update_handle(&self) -> AggregateUpdateHandle {
+        AggregateUpdateHandle::new(self.update_sender.clone())
+    }
+
     pub(crate) async fn into_running(mut self) {
         const BATCH_SIZE: usize = 128;
 
         let mut buf = Vec::with_capacity(BATCH_SIZE);
 
-        let emit_interval = self.exporter.emit_interval();
-        let mut emit_interval = tokio::time::interval(emit_interval);
+        let mut emit_timer = EmitTimer::new(self.emit_interval, self.emit_jitter);
 
         loop {
             buf.clear();
@@ -89,13 +244,26 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
             tokio::select! {
                 biased;
 
-                _ = emit_interval.tick() => {
+                _ = emit_timer.tick() => {
                     self.retain();
                     self.emit();
+                    self.emit_rollups(false);
+                }
+                r = self.dump_receiver.recv() => {
+                    if let Some(req) = r {
+                        req.response(&self.gauge, &self.counter);
+                    }
+                }
+                r = self.update_receiver.recv() => {
+                    if let Some(update) = r {
+                        debug!("exporter {}: export config updated in place", self.name);
+                        self.exporter.update(update);
+                    }
                 }
                 n = self.receiver.recv_many(&mut buf, BATCH_SIZE) => {
                     if n == 0 {
                         self.emit();
+                        self.emit_rollups(true);
                         break;
                     }
 
@@ -107,15 +275,40 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
         }
     }
 
+    /// Evict the series that have not been updated within the stale timeout
     fn retain(&mut self) {
+        let stale_time = self.store_time - self.stale_delay;
+        let mut evicted = 0;
         self.gauge.retain(|_, inner| {
-            inner.inner.retain(|_, v| v.time >= self.store_time);
+            inner.inner.retain(|_, v| {
+                if v.time < stale_time {
+                    evicted += 1;
+                    false
+                } else {
+                    true
+                }
+            });
             !inner.inner.is_empty()
         });
         self.counter.retain(|_, inner| {
-            inner.inner.retain(|_, v| v.time >= self.store_time);
+            inner.inner.retain(|_, v| {
+                if v.time < stale_time {
+                    evicted += 1;
+                    false
+                } else {
+                    if v.time < self.store_time {
+                        // no increment in the last emit interval
+                        v.diff = MetricValue::Unsigned(0);
+                    }
+                    true
+                }
+            });
             !inner.inner.is_empty()
         });
+        if evicted > 0 {
+            debug!("exporter {}: {evicted} stale series evicted", self.name);
+            self.stats.add_evicted_series(evicted);
+        }
         self.store_time = Utc::now();
     }
 
@@ -124,39 +317,352 @@ impl<T: AggregateExport> AggregateExportRuntime<T> {
             self.exporter.emit_gauge(name, &inner.inner);
         }
         for (name, inner) in &self.counter {
-            self.exporter.emit_counter(name, &inner.inner);
+            self.exporter
+                .emit_counter(name, self.emit_interval, &inner.inner);
         }
     }
 
-    fn add_record(&mut self, record: MetricRecord) {
+    /// Emit the rollups whose window ends, or all of them if `force` is set
+    fn emit_rollups(&mut self, force: bool) {
+        let emit_count = self.emit_count;
+        self.emit_count = self.emit_count.wrapping_add(1);
+
+        for rollup in &mut self.rollups {
+            if !force && !rollup.should_emit(emit_count) {
+                continue;
+            }
+
+            for (name, inner) in std::mem::take(&mut rollup.gauge) {
+                let values: AHashMap<_, _> = inner
+                    .inner
+                    .into_iter()
+                    .map(|(tag_map, v)| (rollup.tag_map(&tag_map), v))
+                    .collect();
+                self.exporter.emit_gauge(&name, &values);
+            }
+            for (name, inner) in std::mem::take(&mut rollup.counter) {
+                let values: AHashMap<_, _> = inner
+                    .inner
+                    .into_iter()
+                    .map(|(tag_map, v)| (rollup.tag_map(&tag_map), v))
+                    .collect();
+                self.exporter.emit_counter(&name, rollup.window, &values);
+            }
+        }
+    }
+
+    fn add_record(&mut self, mut record: MetricRecord) {
+        record.tag_map = self.tag_transform.apply(&record.tag_map);
         match record.r#type {
             MetricType::Counter => {
-                self.counter
-                    .entry(record.name.clone())
-                    .or_default()
-                    .inner
-                    .entry(record.tag_map.clone())
-                    .and_modify(|v| {
+                let inner = &mut self.counter.entry(record.name.clone()).or_default().inner;
+                let (sum, increment) = match inner.entry(record.tag_map.clone()) {
+                    hash_map::Entry::Occupied(mut o) => {
+                        let v = o.get_mut();
+                        let (increment, reset) = v.add(self.store_time, record.value);
+                        if reset {
+                            self.stats.add_counter_reset();
+                        }
+                        (v.sum, increment)
+                    }
+                    hash_map::Entry::Vacant(o) => {
+                        let v = o.insert(CounterStoreValue::new(self.store_time, record.value));
+                        (v.sum, record.value)
+                    }
+                };
+                for rollup in &mut self.rollups {
+                    rollup
+                        .counter
+                        .entry(record.name.clone())
+                        .or_default()
+                        .inner
+                        .entry(record.tag_map.clone())
+                        .and_modify(|v| {
+                            v.time = self.store_time;
+                            v.sum = sum;
+                            v.diff += increment;
+                        })
+                        .or_insert(CounterStoreValue {
+                            time: self.store_time,
+                            sum,
+                            diff: increment,
+                        });
+                }
+            }
+            MetricType::Gauge | MetricType::GaugeDelta => {
+                let inner = &mut self.gauge.entry(record.name.clone()).or_default().inner;
+                let value = match inner.entry(record.tag_map.clone()) {
+                    hash_map::Entry::Occupied(mut o) => {
+                        let v = o.get_mut();
                         v.time = self.store_time;
-                        v.sum += record.value;
-                        v.diff = record.value;
-                    })
-                    .or_insert(CounterStoreValue {
-                        time: self.store_time,
-                        sum: record.value,
-                        diff: record.value,
-                    });
+                        if record.r#type == MetricType::GaugeDelta {
+                            v.value += record.value;
+                        } else {
+                            v.value = record.value;
+                        }
+                        v.value
+                    }
+                    hash_map::Entry::Vacant(o) => {
+                        // the delta will be applied to 0 if there is no previous value
+                        o.insert(GaugeStoreValue {
+                            time: self.store_time,
+                            value: record.value,
+                        });
+                        record.value
+                    }
+                };
+                for rollup in &mut self.rollups {
+                    rollup
+                        .gauge
+                        .entry(record.name.clone())
+                        .or_default()
+                        .inner
+                        .insert(
+                            record.tag_map.clone(),
+                            GaugeStoreValue {
+                                time: self.store_time,
+                                value,
+                            },
+                        );
+                }
             }
-            MetricType::Gauge => {
-                let inner = self.gauge.entry(record.name.clone()).or_default();
-                inner.inner.insert(
-                    record.tag_map,
-                    GaugeStoreValue {
-                        time: self.store_time,
-                        value: record.value,
-                    },
-                );
+            MetricType::Timer => {
+                // timer samples should be aggregated to histograms by aggregate collectors
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeDelta;
+
+    #[test]
+    fn counter_emit_mode() {
+        let t1 = Utc::now();
+        let t2 = t1 + TimeDelta::seconds(10);
+        let t3 = t2 + TimeDelta::seconds(10);
+
+        let mut v = CounterStoreValue::new(t1, MetricValue::Unsigned(1));
+        v.add(t1, MetricValue::Unsigned(2));
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Absolute),
+            MetricValue::Unsigned(3)
+        );
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Delta),
+            MetricValue::Unsigned(3)
+        );
+
+        v.add(t2, MetricValue::Unsigned(4));
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Absolute),
+            MetricValue::Unsigned(7)
+        );
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Delta),
+            MetricValue::Unsigned(4)
+        );
+
+        // the series is evicted as no update in an interval, and then re-added
+        let v = CounterStoreValue::new(t3, MetricValue::Unsigned(5));
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Absolute),
+            MetricValue::Unsigned(5)
+        );
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Delta),
+            MetricValue::Unsigned(5)
+        );
+    }
+
+    #[test]
+    fn counter_reset() {
+        let t1 = Utc::now();
+        let t2 = t1 + TimeDelta::seconds(10);
+
+        // overflow
+        let mut v = CounterStoreValue::new(t1, MetricValue::Unsigned(u64::MAX - 1));
+        let (increment, reset) = v.add(t2, MetricValue::Unsigned(3));
+        assert!(reset);
+        assert_eq!(increment, MetricValue::Unsigned(3));
+        assert_eq!(v.sum, MetricValue::Unsigned(3));
+        assert_eq!(v.diff, MetricValue::Unsigned(3));
+
+        let (increment, reset) = v.add(t2, MetricValue::Unsigned(2));
+        assert!(!reset);
+        assert_eq!(increment, MetricValue::Unsigned(2));
+        assert_eq!(v.sum, MetricValue::Unsigned(5));
+        assert_eq!(v.diff, MetricValue::Unsigned(5));
+
+        // negative increment
+        let mut v = CounterStoreValue::new(t1, MetricValue::Unsigned(10));
+        let (increment, reset) = v.add(t2, MetricValue::Signed(-4));
+        assert!(!reset);
+        assert_eq!(increment, MetricValue::Signed(-4));
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Absolute),
+            MetricValue::Signed(6)
+        );
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Delta),
+            MetricValue::Signed(-4)
+        );
+
+        let (_, reset) = v.add(t2, MetricValue::Unsigned(1));
+        assert!(!reset);
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Absolute),
+            MetricValue::Signed(7)
+        );
+        assert_eq!(
+            v.emit_value(CounterEmitMode::Delta),
+            MetricValue::Signed(-3)
+        );
+    }
+
+    struct DummyExport;
+
+    impl AggregateExport for DummyExport {
+        fn emit_interval(&self) -> Duration {
+            Duration::from_secs(10)
+        }
+
+        fn emit_gauge(&mut self, _: &MetricName, _: &AHashMap<Arc<MetricTagMap>, GaugeStoreValue>) {
+        }
+
+        fn emit_counter(
+            &mut self,
+            _: &MetricName,
+            _: Duration,
+            _: &AHashMap<Arc<MetricTagMap>, CounterStoreValue>,
+        ) {
+        }
+
+        fn update(&mut self, _: AggregateExportUpdate) {}
+    }
+
+    #[test]
+    fn stale_eviction() {
+        let (_sender, receiver) = mpsc::unbounded_channel();
+        let mut runtime = AggregateExportRuntime::new(
+            NodeName::from_str("test").unwrap(),
+            DummyExport,
+            receiver,
+            &AggregateRollupConfig::default(),
+            Some(Duration::from_secs(30)),
+            AggregateEmitJitter::default(),
+            AggregateTagTransform::default(),
+        );
+        let t0 = Utc::now();
+        runtime.store_time = t0;
+
+        let name = Arc::new(MetricName::parse("foo").unwrap());
+        let tag_map = Arc::new(MetricTagMap::default());
+        runtime.add_record(MetricRecord {
+            r#type: MetricType::Counter,
+            name: name.clone(),
+            tag_map: tag_map.clone(),
+            value: MetricValue::Unsigned(1),
+        });
+        runtime.add_record(MetricRecord {
+            r#type: MetricType::Gauge,
+            name: name.clone(),
+            tag_map: tag_map.clone(),
+            value: MetricValue::Unsigned(1),
+        });
+
+        for i in 1..=3 {
+            runtime.retain();
+            runtime.store_time = t0 + TimeDelta::seconds(10 * i);
+            assert_eq!(runtime.stats.snapshot().evicted_series, 0);
+            let counter = runtime
+                .counter
+                .get(&name)
+                .unwrap()
+                .inner
+                .get(&tag_map)
+                .unwrap();
+            assert_eq!(counter.sum, MetricValue::Unsigned(1));
+            if i == 1 {
+                assert_eq!(counter.diff, MetricValue::Unsigned(1));
+            } else {
+                assert_eq!(counter.diff, MetricValue::Unsigned(0));
+            }
+            assert!(runtime.gauge.contains_key(&name));
+        }
+
+        runtime.retain();
+        assert_eq!(runtime.stats.snapshot().evicted_series, 2);
+        assert!(runtime.counter.is_empty());
+        assert!(runtime.gauge.is_empty());
+    }
+
+    #[test]
+    fn gauge_delta() {
+        let (_sender, receiver) = mpsc::unbounded_channel();
+        let mut runtime = AggregateExportRuntime::new(
+            NodeName::from_str("test").unwrap(),
+            DummyExport,
+            receiver,
+            &AggregateRollupConfig::default(),
+            None,
+            AggregateEmitJitter::default(),
+            AggregateTagTransform::default(),
+        );
+
+        let name = Arc::new(MetricName::parse("foo").unwrap());
+        let tag_map = Arc::new(MetricTagMap::default());
+        let mut add_gauge = |r#type, value| {
+            runtime.add_record(MetricRecord {
+                r#type,
+                name: name.clone(),
+                tag_map: tag_map.clone(),
+                value,
+            });
+            runtime
+                .gauge
+                .get(&name)
+                .unwrap()
+                .inner
+                .get(&tag_map)
+                .unwrap()
+                .value
+        };
+
+        // delta before any absolute value
+        assert_eq!(
+            add_gauge(MetricType::GaugeDelta, MetricValue::Unsigned(5)),
+            MetricValue::Unsigned(5)
+        );
+        assert_eq!(
+            add_gauge(MetricType::GaugeDelta, MetricValue::Signed(-3)),
+            MetricValue::Signed(2)
+        );
+        assert_eq!(
+            add_gauge(MetricType::Gauge, MetricValue::Unsigned(10)),
+            MetricValue::Unsigned(10)
+        );
+        assert_eq!(
+            add_gauge(MetricType::GaugeDelta, MetricValue::Double(0.5)),
+            MetricValue::Double(10.5)
+        );
+    }
+
+    #[test]
+    fn parse_counter_emit_mode() {
+        let yaml = Yaml::String("delta".to_string());
+        assert_eq!(
+            CounterEmitMode::parse_yaml(&yaml).unwrap(),
+            CounterEmitMode::Delta
+        );
+        let yaml = Yaml::String("Absolute".to_string());
+        assert_eq!(
+            CounterEmitMode::parse_yaml(&yaml).unwrap(),
+            CounterEmitMode::Absolute
+        );
+        let yaml = Yaml::String("rate".to_string());
+        assert!(CounterEmitMode::parse_yaml(&yaml).is_err());
+    }
+}
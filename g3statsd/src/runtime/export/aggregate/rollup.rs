@@ -0,0 +1,140 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue};
+
+use super::{CounterStoreValue, GaugeStoreValue, InnerMap};
+use crate::types::MetricName;
+
+const ROLLUP_TAG_NAME: MetricTagName = unsafe { MetricTagName::new_static_unchecked("rollup") };
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AggregateRollupConfig {
+    windows: Vec<Duration>,
+}
+
+impl AggregateRollupConfig {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let windows = match value {
+            Yaml::Array(_) => g3_yaml::value::as_list(value, g3_yaml::humanize::as_duration)?,
+            _ => vec![g3_yaml::humanize::as_duration(value)?],
+        };
+        Ok(AggregateRollupConfig { windows })
+    }
+
+    /// Each window should be a multiple of the emit interval.
+    pub(crate) fn check(&mut self, emit_interval: Duration) -> anyhow::Result<()> {
+        if emit_interval.is_zero() {
+            return Err(anyhow!("the emit interval should not be zero"));
+        }
+        for window in &self.windows {
+            if *window <= emit_interval {
+                return Err(anyhow!(
+                    "rollup window {window:?} should be larger than the emit interval {emit_interval:?}"
+                ));
+            }
+            if window.as_nanos() % emit_interval.as_nanos() != 0 {
+                return Err(anyhow!(
+                    "rollup window {window:?} should be a multiple of the emit interval {emit_interval:?}"
+                ));
+            }
+            if window.subsec_nanos() != 0 {
+                return Err(anyhow!(
+                    "rollup window {window:?} should be in whole seconds"
+                ));
+            }
+        }
+        self.windows.sort();
+        self.windows.dedup();
+        Ok(())
+    }
+
+    pub(crate) fn windows(&self) -> &[Duration] {
+        &self.windows
+    }
+}
+
+/// Accumulate the records for a rollup window.
+///
+/// - counter: the sum is the latest total value, and the diff is the sum of all increments in the window
+/// - gauge: the last value in the window
+pub(super) struct RollupStore {
+    pub(super) window: Duration,
+    emit_ticks: u64,
+    tag_value: MetricTagValue,
+    pub(super) counter: AHashMap<Arc<MetricName>, InnerMap<CounterStoreValue>>,
+    pub(super) gauge: AHashMap<Arc<MetricName>, InnerMap<GaugeStoreValue>>,
+}
+
+impl RollupStore {
+    pub(super) fn new(window: Duration, emit_interval: Duration) -> Self {
+        let emit_ticks = (window.as_nanos() / emit_interval.as_nanos()).max(1) as u64;
+        let tag_value = format!("{}s", window.as_secs());
+        RollupStore {
+            window,
+            emit_ticks,
+            // the value only contains digits and 's'
+            tag_value: MetricTagValue::from_str(&tag_value).unwrap(),
+            counter: AHashMap::default(),
+            gauge: AHashMap::default(),
+        }
+    }
+
+    pub(super) fn should_emit(&self, emit_count: u64) -> bool {
+        emit_count.is_multiple_of(self.emit_ticks)
+    }
+
+    /// Add the rollup tag to the original tag map
+    pub(super) fn tag_map(&self, tag_map: &MetricTagMap) -> Arc<MetricTagMap> {
+        let mut tag_map = tag_map.clone();
+        tag_map.insert(ROLLUP_TAG_NAME, self.tag_value.clone());
+        Arc::new(tag_map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let yaml = Yaml::String("60s".to_string());
+        let mut config = AggregateRollupConfig::parse_yaml(&yaml).unwrap();
+        assert!(config.check(Duration::from_secs(10)).is_ok());
+        assert!(config.check(Duration::from_secs(60)).is_err());
+        assert!(config.check(Duration::from_secs(25)).is_err());
+
+        let yaml = Yaml::Array(vec![
+            Yaml::String("5m".to_string()),
+            Yaml::String("1m".to_string()),
+            Yaml::String("60s".to_string()),
+        ]);
+        let mut config = AggregateRollupConfig::parse_yaml(&yaml).unwrap();
+        config.check(Duration::from_secs(10)).unwrap();
+        assert_eq!(
+            config.windows(),
+            &[Duration::from_secs(60), Duration::from_secs(300)]
+        );
+    }
+
+    #[test]
+    fn rollup_store() {
+        let store = RollupStore::new(Duration::from_secs(60), Duration::from_secs(10));
+        assert!(store.should_emit(0));
+        assert!(!store.should_emit(5));
+        assert!(store.should_emit(6));
+
+        let tag_map = store.tag_map(&MetricTagMap::default());
+        assert_eq!(tag_map.display_influxdb().to_string(), "rollup=60s");
+    }
+}
@@ -0,0 +1,88 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The stats of an aggregate export runtime, which will be emitted by the internal collectors
+#[derive(Default)]
+pub(crate) struct AggregateExportStats {
+    counter_resets: AtomicU64,
+    evicted_series: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AggregateExportStatsSnapshot {
+    pub(crate) counter_resets: u64,
+    pub(crate) evicted_series: u64,
+}
+
+impl AggregateExportStats {
+    #[inline]
+    pub(super) fn add_counter_reset(&self) {
+        self.counter_resets.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(super) fn add_evicted_series(&self, count: u64) {
+        self.evicted_series.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> AggregateExportStatsSnapshot {
+        AggregateExportStatsSnapshot {
+            counter_resets: self.counter_resets.load(Ordering::Relaxed),
+            evicted_series: self.evicted_series.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl AggregateExportStatsSnapshot {
+    /// Get the increments since the last snapshot
+    ///
+    /// The stats will be restarted from 0 if the exporter is reloaded,
+    /// so a decreased value means all of it is new.
+    pub(crate) fn delta(&self, last: &Self) -> Self {
+        let delta = |new: u64, old: u64| new.checked_sub(old).unwrap_or(new);
+        AggregateExportStatsSnapshot {
+            counter_resets: delta(self.counter_resets, last.counter_resets),
+            evicted_series: delta(self.evicted_series, last.evicted_series),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta() {
+        let stats = AggregateExportStats::default();
+        stats.add_counter_reset();
+        stats.add_evicted_series(2);
+        let last = stats.snapshot();
+
+        stats.add_counter_reset();
+        stats.add_evicted_series(3);
+        let delta = stats.snapshot().delta(&last);
+        assert_eq!(
+            delta,
+            AggregateExportStatsSnapshot {
+                counter_resets: 1,
+                evicted_series: 3,
+            }
+        );
+
+        // the exporter is reloaded with new stats
+        let stats = AggregateExportStats::default();
+        stats.add_evicted_series(1);
+        let delta = stats.snapshot().delta(&last);
+        assert_eq!(
+            delta,
+            AggregateExportStatsSnapshot {
+                counter_resets: 0,
+                evicted_series: 1,
+            }
+        );
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use tokio::time::Instant;
+use yaml_rust::Yaml;
+
+const JITTER_MAX_PERMILLE: u16 = 500;
+
+/// The max random delay of each emit, in per mille of the emit interval
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AggregateEmitJitter(u16);
+
+impl AggregateEmitJitter {
+    /// The value can be a fraction like 0.1 or a percentage like "10%"
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let fraction = match value {
+            Yaml::String(s) => {
+                if let Some(p) = s.strip_suffix('%') {
+                    f64::from_str(p.trim())? / 100.0
+                } else {
+                    f64::from_str(s)?
+                }
+            }
+            Yaml::Real(_) | Yaml::Integer(_) => g3_yaml::value::as_f64(value)?,
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for emit jitter should be 'string', 'integer' or 'real'"
+                ));
+            }
+        };
+        AggregateEmitJitter::from_fraction(fraction)
+    }
+
+    fn from_fraction(fraction: f64) -> anyhow::Result<Self> {
+        let permille = (fraction * 1000.0).round();
+        if !(0.0..=JITTER_MAX_PERMILLE as f64).contains(&permille) {
+            return Err(anyhow!(
+                "emit jitter {fraction} should be in range [0, {}]",
+                JITTER_MAX_PERMILLE as f64 / 1000.0
+            ));
+        }
+        Ok(AggregateEmitJitter(permille as u16))
+    }
+
+    fn band(&self) -> f64 {
+        self.0 as f64 / 1000.0
+    }
+}
+
+/// The emit timer with each tick randomly delayed within the jitter band.
+///
+/// The random delay is added to each tick of a fixed interval schedule,
+/// so the average interval is not changed.
+pub(super) struct EmitTimer {
+    interval: Duration,
+    band: f64,
+    rng: fastrand::Rng,
+    next_tick: Instant,
+    deadline: Instant,
+}
+
+impl EmitTimer {
+    pub(super) fn new(interval: Duration, jitter: AggregateEmitJitter) -> Self {
+        EmitTimer::with_rng(Instant::now(), interval, jitter, fastrand::Rng::new())
+    }
+
+    fn with_rng(
+        start: Instant,
+        interval: Duration,
+        jitter: AggregateEmitJitter,
+        rng: fastrand::Rng,
+    ) -> Self {
+        let mut timer = EmitTimer {
+            interval,
+            band: jitter.band(),
+            rng,
+            next_tick: start,
+            deadline: start,
+        };
+        timer.schedule();
+        timer
+    }
+
+    fn schedule(&mut self) {
+        // a delay in [0, band] makes each interval vary within [-band, +band]
+        let delay = self.interval.mul_f64(self.band * self.rng.f64());
+        self.deadline = self.next_tick + delay;
+        self.next_tick += self.interval;
+    }
+
+    pub(super) async fn tick(&mut self) {
+        tokio::time::sleep_until(self.deadline).await;
+        self.schedule();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_jitter() {
+        let v = Yaml::Real("0.1".to_string());
+        assert_eq!(
+            AggregateEmitJitter::parse_yaml(&v).unwrap(),
+            AggregateEmitJitter(100)
+        );
+
+        let v = Yaml::String("25%".to_string());
+        assert_eq!(
+            AggregateEmitJitter::parse_yaml(&v).unwrap(),
+            AggregateEmitJitter(250)
+        );
+
+        let v = Yaml::Integer(0);
+        assert_eq!(
+            AggregateEmitJitter::parse_yaml(&v).unwrap(),
+            AggregateEmitJitter(0)
+        );
+
+        let v = Yaml::Real("0.6".to_string());
+        assert!(AggregateEmitJitter::parse_yaml(&v).is_err());
+        let v = Yaml::String("-10%".to_string());
+        assert!(AggregateEmitJitter::parse_yaml(&v).is_err());
+    }
+
+    #[test]
+    fn no_jitter() {
+        let start = Instant::now();
+        let interval = Duration::from_secs(10);
+        let mut timer = EmitTimer::with_rng(
+            start,
+            interval,
+            AggregateEmitJitter::default(),
+            fastrand::Rng::with_seed(1),
+        );
+        assert_eq!(timer.deadline, start);
+        timer.schedule();
+        assert_eq!(timer.deadline, start + interval);
+    }
+
+    #[test]
+    fn jitter_band() {
+        const TICKS: u32 = 1000;
+
+        let start = Instant::now();
+        let interval = Duration::from_secs(10);
+        let jitter = AggregateEmitJitter(100);
+        let max_delay = Duration::from_secs(1);
+        let mut timer = EmitTimer::with_rng(start, interval, jitter, fastrand::Rng::with_seed(1));
+
+        let first = timer.deadline;
+        assert!(first <= start + max_delay);
+        let mut last = first;
+        for i in 1..=TICKS {
+            timer.schedule();
+            let nominal = start + interval * i;
+            assert!(timer.deadline >= nominal);
+            assert!(timer.deadline <= nominal + max_delay);
+            let gap = timer.deadline - last;
+            assert!(gap >= interval - max_delay);
+            assert!(gap <= interval + max_delay);
+            last = timer.deadline;
+        }
+
+        let average = (last - first) / TICKS;
+        assert!(average >= interval - Duration::from_millis(10));
+        assert!(average <= interval + Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,310 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::{Yaml, yaml};
+
+use g3_types::metrics::{MetricTagMap, MetricTagName, MetricTagValue};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum TagTransformOp {
+    /// rename the tag, the existing tag with the new name will be overwritten
+    Rename {
+        from: MetricTagName,
+        to: MetricTagName,
+    },
+    /// drop the tags
+    Drop(Vec<MetricTagName>),
+    /// convert the values of the tags to lowercase
+    Lowercase(Vec<MetricTagName>),
+    /// replace the value of the tag if it matches a key of the map
+    MapValue {
+        tag: MetricTagName,
+        map: BTreeMap<MetricTagValue, MetricTagValue>,
+    },
+}
+
+impl TagTransformOp {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for tag transform operation should be 'map'"
+            ));
+        };
+        if map.len() != 1 {
+            return Err(anyhow!(
+                "there should be exactly one operation in each tag transform step"
+            ));
+        }
+        let Some((Yaml::String(k), v)) = map.front() else {
+            return Err(anyhow!("invalid tag transform operation"));
+        };
+        match g3_yaml::key::normalize(k).as_str() {
+            "rename" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("yaml value type for 'rename' should be 'map'"));
+                };
+                let from = g3_yaml::hash_get_required(map, "from")?;
+                let from = g3_yaml::value::as_metric_tag_name(from)
+                    .context("invalid metric tag name value for key from")?;
+                let to = g3_yaml::hash_get_required(map, "to")?;
+                let to = g3_yaml::value::as_metric_tag_name(to)
+                    .context("invalid metric tag name value for key to")?;
+                Ok(TagTransformOp::Rename { from, to })
+            }
+            "drop" => {
+                let tags = parse_tag_names(v).context(format!("invalid value for key {k}"))?;
+                Ok(TagTransformOp::Drop(tags))
+            }
+            "lowercase" => {
+                let tags = parse_tag_names(v).context(format!("invalid value for key {k}"))?;
+                Ok(TagTransformOp::Lowercase(tags))
+            }
+            "map_value" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("yaml value type for 'map_value' should be 'map'"));
+                };
+                parse_map_value(map).context(format!("invalid value for key {k}"))
+            }
+            _ => Err(anyhow!("invalid tag transform operation {k}")),
+        }
+    }
+
+    fn apply(&self, tag_map: &mut MetricTagMap) {
+        match self {
+            TagTransformOp::Rename { from, to } => {
+                if let Some(value) = tag_map.get(from).cloned() {
+                    tag_map.drop(from);
+                    tag_map.insert(to.clone(), value);
+                }
+            }
+            TagTransformOp::Drop(tags) => {
+                for tag in tags {
+                    tag_map.drop(tag);
+                }
+            }
+            TagTransformOp::Lowercase(tags) => {
+                for tag in tags {
+                    let Some(value) = tag_map.get(tag) else {
+                        continue;
+                    };
+                    if !value.as_str().chars().any(|c| c.is_uppercase()) {
+                        continue;
+                    }
+                    // lowercase of the allowed chars are still allowed
+                    if let Ok(value) = MetricTagValue::from_str(&value.as_str().to_lowercase()) {
+                        tag_map.insert(tag.clone(), value);
+                    }
+                }
+            }
+            TagTransformOp::MapValue { tag, map } => {
+                if let Some(new) = tag_map.get(tag).and_then(|v| map.get(v)) {
+                    tag_map.insert(tag.clone(), new.clone());
+                }
+            }
+        }
+    }
+}
+
+fn parse_tag_names(value: &Yaml) -> anyhow::Result<Vec<MetricTagName>> {
+    let tags = match value {
+        Yaml::Array(_) => g3_yaml::value::as_list(value, g3_yaml::value::as_metric_tag_name)?,
+        _ => vec![g3_yaml::value::as_metric_tag_name(value)?],
+    };
+    if tags.is_empty() {
+        return Err(anyhow!("no tag name set"));
+    }
+    Ok(tags)
+}
+
+fn parse_map_value(map: &yaml::Hash) -> anyhow::Result<TagTransformOp> {
+    let tag = g3_yaml::hash_get_required(map, "tag")?;
+    let tag = g3_yaml::value::as_metric_tag_name(tag)
+        .context("invalid metric tag name value for key tag")?;
+    let values = g3_yaml::hash_get_required(map, "map")?;
+    let Yaml::Hash(values) = values else {
+        return Err(anyhow!("yaml value type for key map should be 'map'"));
+    };
+    let mut value_map = BTreeMap::new();
+    g3_yaml::foreach_kv(values, |k, v| {
+        let from = MetricTagValue::from_str(k)
+            .map_err(|e| anyhow!("invalid metric tag value {k}: {e}"))?;
+        let to = g3_yaml::value::as_metric_tag_value(v)?;
+        value_map.insert(from, to);
+        Ok(())
+    })?;
+    Ok(TagTransformOp::MapValue {
+        tag,
+        map: value_map,
+    })
+}
+
+/// The tag transform pipeline, the operations will be applied in order
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AggregateTagTransform {
+    ops: Vec<TagTransformOp>,
+}
+
+impl AggregateTagTransform {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let ops = match value {
+            Yaml::Array(seq) => {
+                let mut ops = Vec::with_capacity(seq.len());
+                for (i, v) in seq.iter().enumerate() {
+                    let op = TagTransformOp::parse_yaml(v)
+                        .context(format!("invalid tag transform operation #{i}"))?;
+                    ops.push(op);
+                }
+                ops
+            }
+            Yaml::Hash(_) => vec![TagTransformOp::parse_yaml(value)?],
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for tag transform should be 'seq' or 'map'"
+                ));
+            }
+        };
+        Ok(AggregateTagTransform { ops })
+    }
+
+    /// Apply the transform to the tag map, the original one will be returned if nothing changed
+    pub(crate) fn apply(&self, tag_map: &Arc<MetricTagMap>) -> Arc<MetricTagMap> {
+        if self.ops.is_empty() {
+            return tag_map.clone();
+        }
+
+        let mut new = tag_map.as_ref().clone();
+        for op in &self.ops {
+            op.apply(&mut new);
+        }
+        if new.eq(tag_map.as_ref()) {
+            tag_map.clone()
+        } else {
+            Arc::new(new)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn load_transform(s: &str) -> AggregateTagTransform {
+        let docs = YamlLoader::load_from_str(s).unwrap();
+        AggregateTagTransform::parse_yaml(&docs[0]).unwrap()
+    }
+
+    fn tag_map(tags: &[(&str, &str)]) -> Arc<MetricTagMap> {
+        let mut map = MetricTagMap::default();
+        for (name, value) in tags {
+            map.insert(
+                MetricTagName::from_str(name).unwrap(),
+                MetricTagValue::from_str(value).unwrap(),
+            );
+        }
+        Arc::new(map)
+    }
+
+    #[test]
+    fn rename() {
+        let transform = load_transform("rename: {from: host, to: hostname}");
+        let r = transform.apply(&tag_map(&[("host", "a"), ("env", "prod")]));
+        assert_eq!(r, tag_map(&[("hostname", "a"), ("env", "prod")]));
+
+        let r = transform.apply(&tag_map(&[("host", "a"), ("hostname", "b")]));
+        assert_eq!(r, tag_map(&[("hostname", "a")]));
+
+        let origin = tag_map(&[("env", "prod")]);
+        let r = transform.apply(&origin);
+        assert!(Arc::ptr_eq(&r, &origin));
+    }
+
+    #[test]
+    fn drop() {
+        let transform = load_transform("drop: [pid, request_id]");
+        let r = transform.apply(&tag_map(&[("pid", "1"), ("request_id", "x"), ("env", "a")]));
+        assert_eq!(r, tag_map(&[("env", "a")]));
+
+        let transform = load_transform("drop: pid");
+        let r = transform.apply(&tag_map(&[("pid", "1")]));
+        assert!(r.is_empty());
+    }
+
+    #[test]
+    fn lowercase() {
+        let transform = load_transform("lowercase: [method]");
+        let r = transform.apply(&tag_map(&[("method", "GET"), ("Env", "PROD")]));
+        assert_eq!(r, tag_map(&[("method", "get"), ("Env", "PROD")]));
+
+        let origin = tag_map(&[("method", "get")]);
+        let r = transform.apply(&origin);
+        assert!(Arc::ptr_eq(&r, &origin));
+    }
+
+    #[test]
+    fn map_value() {
+        let transform =
+            load_transform("map_value: {tag: env, map: {production: prod, staging: stage}}");
+        let r = transform.apply(&tag_map(&[("env", "production")]));
+        assert_eq!(r, tag_map(&[("env", "prod")]));
+        let r = transform.apply(&tag_map(&[("env", "staging")]));
+        assert_eq!(r, tag_map(&[("env", "stage")]));
+        let r = transform.apply(&tag_map(&[("env", "dev")]));
+        assert_eq!(r, tag_map(&[("env", "dev")]));
+    }
+
+    #[test]
+    fn ordering() {
+        let transform = load_transform(
+            r#"
+            - rename: {from: Method, to: method}
+            - lowercase: method
+            - map_value: {tag: method, map: {get: read, post: write}}
+            - drop: method
+            "#,
+        );
+        let r = transform.apply(&tag_map(&[("Method", "GET"), ("env", "a")]));
+        assert_eq!(r, tag_map(&[("env", "a")]));
+
+        let transform = load_transform(
+            r#"
+            - rename: {from: Method, to: method}
+            - lowercase: method
+            - map_value: {tag: method, map: {get: read, post: write}}
+            "#,
+        );
+        let r = transform.apply(&tag_map(&[("Method", "GET")]));
+        assert_eq!(r, tag_map(&[("method", "read")]));
+
+        // the map is applied before lowercase, so the upper case value is not matched
+        let transform = load_transform(
+            r#"
+            - map_value: {tag: method, map: {get: read}}
+            - lowercase: method
+            "#,
+        );
+        let r = transform.apply(&tag_map(&[("method", "GET")]));
+        assert_eq!(r, tag_map(&[("method", "get")]));
+    }
+
+    #[test]
+    fn parse_error() {
+        let parse = |s: &str| {
+            let docs = YamlLoader::load_from_str(s).unwrap();
+            AggregateTagTransform::parse_yaml(&docs[0])
+        };
+        assert!(parse("upper: [a]").is_err());
+        assert!(parse("drop: []").is_err());
+        assert!(parse("rename: {from: a}").is_err());
+        assert!(parse("- {drop: a, lowercase: b}").is_err());
+        assert!(parse("map_value: {tag: a, map: [b]}").is_err());
+        assert!(parse("drop").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use anyhow::anyhow;
+use tokio::sync::mpsc;
+
+use g3_types::metrics::MetricTagMap;
+
+use crate::types::MetricName;
+
+/// The export config fields that can be updated in place, without respawning the runtimes
+pub(crate) struct AggregateExportUpdate {
+    pub(crate) prefix: Option<MetricName>,
+    pub(crate) global_tags: MetricTagMap,
+}
+
+/// Update the export config of a running aggregate export runtime
+#[derive(Clone)]
+pub(crate) struct AggregateUpdateHandle {
+    sender: mpsc::Sender<AggregateExportUpdate>,
+}
+
+impl AggregateUpdateHandle {
+    pub(super) fn new(sender: mpsc::Sender<AggregateExportUpdate>) -> Self {
+        AggregateUpdateHandle { sender }
+    }
+
+    /// The update will take effect since the next emit
+    pub(crate) fn update(&self, update: AggregateExportUpdate) -> anyhow::Result<()> {
+        self.sender
+            .try_send(update)
+            .map_err(|e| anyhow!("failed to send update request: {e}"))
+    }
+}
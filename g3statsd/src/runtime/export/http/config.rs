@@ -17,18 +17,27 @@ use yaml_rust::Yaml;
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
+use g3_yaml::YamlDocPosition;
+
+use super::AwsSigV4Config;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct HttpExportConfig {
     pub(super) exporter: NodeName,
     pub(super) host: Host,
     port: u16,
+    default_port: u16,
     resolve_retry_wait: Duration,
     connect_retry_wait: Duration,
     pub(super) rsp_head_max_size: usize,
     pub(super) body_line_max_len: usize,
+    pub(super) max_requests_per_second: u32,
+    pub(super) rate_limit_queue_size: usize,
+    pub(super) send_queue_size: usize,
+    pub(super) fanout: Vec<HttpExportConfig>,
+    pub(super) sigv4: Option<AwsSigV4Config>,
 
-    peer_s: String,
+    pub(super) peer_s: String,
     peer_addrs: Vec<SocketAddr>,
 }
 
@@ -42,10 +51,16 @@ impl HttpExportConfig {
             exporter: NodeName::default(),
             host,
             port,
+            default_port: port,
             resolve_retry_wait: Duration::from_secs(30),
             connect_retry_wait: Duration::from_secs(10),
             rsp_head_max_size: 8192,
             body_line_max_len: 512,
+            max_requests_per_second: 0,
+            rate_limit_queue_size: 1024,
+            send_queue_size: 1024,
+            fanout: Vec::new(),
+            sigv4: None,
             peer_s: String::new(),
             peer_addrs: Vec::new(),
         }
@@ -56,13 +71,58 @@ impl HttpExportConfig {
             return Err(anyhow!("peer address is not set"));
         }
 
-        self.exporter = exporter;
         let peer = UpstreamAddr::new(self.host.clone(), self.port);
         self.peer_s = peer.to_string();
+        for (i, child) in self.fanout.iter_mut().enumerate() {
+            child
+                .check(exporter.clone())
+                .context(format!("invalid fanout child #{i}"))?;
+        }
+        self.exporter = exporter;
         Ok(())
     }
 
-    pub(crate) fn set_by_yaml_kv(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+    /// Copy the rate limit config, which can be updated in place
+    pub(crate) fn copy_rate_limit(&mut self, other: &Self) {
+        self.max_requests_per_second = other.max_requests_per_second;
+        self.rate_limit_queue_size = other.rate_limit_queue_size;
+        for (child, other) in self.fanout.iter_mut().zip(other.fanout.iter()) {
+            child.copy_rate_limit(other);
+        }
+    }
+
+    /// Iterate over the primary endpoint and all the fan-out children
+    pub(super) fn endpoints(&self) -> impl Iterator<Item = &HttpExportConfig> {
+        std::iter::once(self).chain(self.fanout.iter())
+    }
+
+    fn parse_fanout(&mut self, v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<()> {
+        let Yaml::Array(seq) = v else {
+            return Err(anyhow!("yaml value type for fanout should be 'seq'"));
+        };
+        for (i, v) in seq.iter().enumerate() {
+            let Yaml::Hash(map) = v else {
+                return Err(anyhow!(
+                    "yaml value type for fanout child #{i} should be 'map'"
+                ));
+            };
+            let mut child = HttpExportConfig::new(self.default_port);
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "fanout" => Err(anyhow!("nested fanout is not allowed")),
+                _ => child.set_by_yaml_kv(k, v, position),
+            })
+            .context(format!("invalid fanout child #{i}"))?;
+            self.fanout.push(child);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_by_yaml_kv(
+        &mut self,
+        k: &str,
+        v: &Yaml,
+        position: Option<&YamlDocPosition>,
+    ) -> anyhow::Result<()> {
         match g3_yaml::key::normalize(k).as_str() {
             "host" | "server" => {
                 self.host = g3_yaml::value::as_host(v)?;
@@ -92,6 +152,29 @@ impl HttpExportConfig {
                     .context(format!("invalid usize value for key {k}"))?;
                 Ok(())
             }
+            "max_requests_per_second" => {
+                self.max_requests_per_second =
+                    g3_yaml::value::as_u32(v).context(format!("invalid u32 value for key {k}"))?;
+                Ok(())
+            }
+            "rate_limit_queue_size" => {
+                self.rate_limit_queue_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "send_queue_size" => {
+                let size = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                self.send_queue_size = size.get();
+                Ok(())
+            }
+            "fanout" => self.parse_fanout(v, position),
+            "sigv4" => {
+                let config = AwsSigV4Config::parse_yaml(v, position)
+                    .context(format!("invalid aws sigv4 config value for key {k}"))?;
+                self.sigv4 = Some(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -0,0 +1,244 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+
+use super::{
+    HttpExport, HttpExportConfig, HttpExportRateLimit, HttpExportRuntime, HttpExportStats,
+};
+
+struct HttpExportPieceSender<P> {
+    sender: mpsc::Sender<P>,
+    stats: Arc<HttpExportStats>,
+}
+
+impl<P> HttpExportPieceSender<P> {
+    fn send(&self, piece: P) {
+        match self.sender.try_send(piece) {
+            Ok(_) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => self.stats.add_dropped_batch(),
+            Err(mpsc::error::TrySendError::Closed(_)) => {}
+        }
+    }
+}
+
+/// Send each body piece to the primary endpoint and all the fan-out children
+pub(crate) struct HttpExportSender<P> {
+    senders: Vec<HttpExportPieceSender<P>>,
+}
+
+impl<P: Clone> HttpExportSender<P> {
+    pub(crate) fn send(&self, piece: P) {
+        let Some((last, others)) = self.senders.split_last() else {
+            return;
+        };
+        for sender in others {
+            // the failure of a single child should not affect the others
+            sender.send(piece.clone());
+        }
+        last.send(piece);
+    }
+}
+
+impl<P> From<mpsc::Sender<P>> for HttpExportSender<P> {
+    fn from(sender: mpsc::Sender<P>) -> Self {
+        HttpExportSender {
+            senders: vec![HttpExportPieceSender {
+                sender,
+                stats: Arc::new(HttpExportStats::default()),
+            }],
+        }
+    }
+}
+
+struct HttpExportEndpoint {
+    peer: String,
+    rate_limit: Arc<HttpExportRateLimit>,
+    stats: Arc<HttpExportStats>,
+}
+
+impl HttpExportEndpoint {
+    fn status(&self) -> Vec<String> {
+        let mut lines = self.rate_limit.status();
+        lines.extend(self.stats.status());
+        lines
+    }
+}
+
+/// The handle to the http export runtimes of all the endpoints
+pub(crate) struct HttpExportHandle {
+    endpoints: Vec<HttpExportEndpoint>,
+}
+
+impl HttpExportHandle {
+    /// Apply the new rate limit config to all the endpoints
+    pub(crate) fn update(&self, config: &HttpExportConfig) {
+        for (endpoint, config) in self.endpoints.iter().zip(config.endpoints()) {
+            endpoint.rate_limit.update(config);
+        }
+    }
+
+    pub(crate) fn status(&self) -> Vec<String> {
+        if let [endpoint] = self.endpoints.as_slice() {
+            return endpoint.status();
+        }
+
+        let mut lines = Vec::new();
+        for endpoint in &self.endpoints {
+            lines.push(format!("{}:", endpoint.peer));
+            for line in endpoint.status() {
+                lines.push(format!("  {line}"));
+            }
+        }
+        lines
+    }
+}
+
+/// Spawn a http export runtime for each endpoint, a new `HttpExport` will be created for each of them
+pub(crate) fn spawn_http_export<T, F>(
+    config: &HttpExportConfig,
+    mut new_export: F,
+) -> anyhow::Result<(HttpExportSender<T::BodyPiece>, Arc<HttpExportHandle>)>
+where
+    T: HttpExport + Send + 'static,
+    T::BodyPiece: Send,
+    F: FnMut() -> anyhow::Result<T>,
+{
+    let mut runtimes = Vec::with_capacity(config.fanout.len() + 1);
+    let mut senders = Vec::with_capacity(runtimes.capacity());
+    let mut endpoints = Vec::with_capacity(runtimes.capacity());
+    for config in config.endpoints() {
+        let mut config = config.clone();
+        config.fanout.clear();
+        let (sender, receiver) = mpsc::channel(config.send_queue_size);
+        let export = new_export()?;
+        let stats = Arc::new(HttpExportStats::default());
+        endpoints.push(HttpExportEndpoint {
+            peer: config.peer_s.clone(),
+            rate_limit: Arc::new(HttpExportRateLimit::new(&config)),
+            stats: stats.clone(),
+        });
+        senders.push(HttpExportPieceSender { sender, stats });
+        runtimes.push((config, export, receiver));
+    }
+
+    for ((config, export, receiver), endpoint) in runtimes.into_iter().zip(endpoints.iter()) {
+        let runtime = HttpExportRuntime::new(
+            config,
+            export,
+            receiver,
+            endpoint.rate_limit.clone(),
+            endpoint.stats.clone(),
+        );
+        tokio::spawn(runtime.into_running());
+    }
+    Ok((
+        HttpExportSender { senders },
+        Arc::new(HttpExportHandle { endpoints }),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use yaml_rust::{Yaml, YamlLoader};
+
+    use g3_types::metrics::NodeName;
+
+    fn piece_sender(size: usize) -> (HttpExportPieceSender<i32>, mpsc::Receiver<i32>) {
+        let (sender, receiver) = mpsc::channel(size);
+        let sender = HttpExportPieceSender {
+            sender,
+            stats: Arc::new(HttpExportStats::default()),
+        };
+        (sender, receiver)
+    }
+
+    #[test]
+    fn send() {
+        let (s1, mut r1) = piece_sender(4);
+        let (s2, r2) = piece_sender(4);
+        let (s3, mut r3) = piece_sender(4);
+        let sender = HttpExportSender {
+            senders: vec![s1, s2, s3],
+        };
+
+        sender.send(1);
+        // a closed child should not affect the others
+        drop(r2);
+        sender.send(2);
+
+        assert_eq!(r1.try_recv().unwrap(), 1);
+        assert_eq!(r1.try_recv().unwrap(), 2);
+        assert_eq!(r3.try_recv().unwrap(), 1);
+        assert_eq!(r3.try_recv().unwrap(), 2);
+        assert!(r3.try_recv().is_err());
+        assert_eq!(sender.senders[1].stats.dropped_batches(), 0);
+    }
+
+    #[test]
+    fn send_full() {
+        let (s1, mut r1) = piece_sender(1);
+        let (s2, mut r2) = piece_sender(4);
+        let sender = HttpExportSender {
+            senders: vec![s1, s2],
+        };
+
+        sender.send(1);
+        // the full child should not block or affect the others
+        sender.send(2);
+        sender.send(3);
+
+        assert_eq!(r1.try_recv().unwrap(), 1);
+        assert!(r1.try_recv().is_err());
+        assert_eq!(sender.senders[0].stats.dropped_batches(), 2);
+
+        assert_eq!(r2.try_recv().unwrap(), 1);
+        assert_eq!(r2.try_recv().unwrap(), 2);
+        assert_eq!(r2.try_recv().unwrap(), 3);
+        assert_eq!(sender.senders[1].stats.dropped_batches(), 0);
+
+        // there is room again after the receiver catches up
+        sender.send(4);
+        assert_eq!(r1.try_recv().unwrap(), 4);
+    }
+
+    #[test]
+    fn fanout_config() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+            - host: 127.0.0.2
+              max_requests_per_second: 10
+            - host: 127.0.0.3
+              port: 9000
+            "#,
+        )
+        .unwrap();
+        let mut config = HttpExportConfig::new(8086);
+        config.set_by_yaml_kv("fanout", &yaml[0], None).unwrap();
+        config
+            .set_by_yaml_kv("host", &Yaml::String("127.0.0.1".to_string()), None)
+            .unwrap();
+        config
+            .set_by_yaml_kv("port", &Yaml::Integer(8087), None)
+            .unwrap();
+        config.check(NodeName::from_str("test").unwrap()).unwrap();
+
+        let peers: Vec<_> = config.endpoints().map(|c| c.peer_s.as_str()).collect();
+        assert_eq!(
+            peers,
+            ["127.0.0.1:8087", "127.0.0.2:8086", "127.0.0.3:9000"]
+        );
+        assert_eq!(config.fanout[0].max_requests_per_second, 10);
+        assert_eq!(config.fanout[1].max_requests_per_second, 0);
+
+        let yaml = YamlLoader::load_from_str("[{host: 127.0.0.2, fanout: []}]").unwrap();
+        let mut config = HttpExportConfig::new(8086);
+        assert!(config.set_by_yaml_kv("fanout", &yaml[0], None).is_err());
+    }
+}
@@ -4,6 +4,7 @@
  */
 
 use std::io::{self, IoSlice};
+use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
@@ -21,6 +22,18 @@ use g3_io_ext::{AsyncStream, LimitedWriteExt};
 mod config;
 pub(crate) use config::HttpExportConfig;
 
+mod rate_limit;
+use rate_limit::{HttpExportRateLimit, TokenBucket};
+
+mod stats;
+use stats::HttpExportStats;
+
+mod sigv4;
+use sigv4::{AwsSigV4Config, AwsSigV4Signer};
+
+mod fanout;
+pub(crate) use fanout::{HttpExportHandle, HttpExportSender, spawn_http_export};
+
 const BATCH_SIZE: usize = 128;
 
 pub(crate) trait HttpExport {
@@ -29,13 +42,35 @@ pub(crate) trait HttpExport {
     fn api_path(&self) -> &PathAndQuery;
     fn static_headers(&self) -> &HeaderMap;
     fn fill_body(&mut self, piece: &[Self::BodyPiece], body_buf: &mut Vec<u8>) -> usize;
-    fn check_response(&self, rsp: HttpForwardRemoteResponse, body: &[u8]) -> anyhow::Result<()>;
+    /// Write the headers that may change between requests, each line should end with CRLF
+    fn write_dynamic_headers(&mut self, _header_buf: &mut Vec<u8>) {}
+    fn check_response(&mut self, rsp: HttpForwardRemoteResponse, body: &[u8])
+    -> anyhow::Result<()>;
+
+    /// Get the streaming body filler if the body should be sent in chunked transfer encoding
+    fn streaming_body(&mut self) -> Option<&mut (dyn HttpStreamingBody<Self::BodyPiece> + Send)> {
+        None
+    }
+}
+
+/// The streaming variant of `HttpExport::fill_body`.
+///
+/// All the pending pieces will be sent in a single request, and only one chunk will be buffered at a time.
+pub(crate) trait HttpStreamingBody<P> {
+    fn begin_body(&mut self, chunk_buf: &mut Vec<u8>);
+    /// Fill the next chunk and return the number of pieces handled, which should not be 0
+    fn fill_chunk(&mut self, pieces: &[P], chunk_buf: &mut Vec<u8>) -> usize;
+    fn end_body(&mut self, chunk_buf: &mut Vec<u8>);
 }
 
 pub(crate) struct HttpExportRuntime<T: HttpExport> {
     config: HttpExportConfig,
     exporter: T,
-    receiver: mpsc::UnboundedReceiver<T::BodyPiece>,
+    receiver: mpsc::Receiver<T::BodyPiece>,
+    rate_limit: Arc<HttpExportRateLimit>,
+    stats: Arc<HttpExportStats>,
+    token_bucket: TokenBucket,
+    signer: Option<AwsSigV4Signer>,
 
     recv_buf: Vec<T::BodyPiece>,
     recv_handled: usize,
@@ -51,7 +86,9 @@ impl<T: HttpExport> HttpExportRuntime<T> {
     pub(crate) fn new(
         config: HttpExportConfig,
         exporter: T,
-        receiver: mpsc::UnboundedReceiver<T::BodyPiece>,
+        receiver: mpsc::Receiver<T::BodyPiece>,
+        rate_limit: Arc<HttpExportRateLimit>,
+        stats: Arc<HttpExportStats>,
     ) -> Self {
         let mut header_buf = Vec::with_capacity(1024);
         config.write_fixed_header(
@@ -60,10 +97,17 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             exporter.static_headers(),
         );
         let fixed_header_len = header_buf.len();
+        let signer = config.sigv4.clone().map(|sigv4| {
+            AwsSigV4Signer::new(config.exporter.clone(), sigv4, config.host.to_string())
+        });
         HttpExportRuntime {
             config,
             exporter,
             receiver,
+            rate_limit,
+            stats,
+            token_bucket: TokenBucket::new(),
+            signer,
             recv_buf: Vec::with_capacity(BATCH_SIZE),
             recv_handled: 0,
             header_buf,
@@ -79,7 +123,10 @@ impl<T: HttpExport> HttpExportRuntime<T> {
         loop {
             match self.config.connect().await {
                 Ok(stream) => self.run_with_stream(stream).await,
-                Err(wait) => self.drop_wait(wait).await,
+                Err(wait) => {
+                    self.stats.add_connect_failed();
+                    self.drop_wait(wait).await
+                }
             }
             if self.quit {
                 break;
@@ -90,7 +137,7 @@ impl<T: HttpExport> HttpExportRuntime<T> {
     async fn drop_wait(&mut self, wait: Duration) {
         if tokio::time::timeout(wait, async {
             while self.receiver.recv().await.is_some() {
-                // TODO add metrics
+                self.stats.add_dropped(1);
             }
         })
         .await
@@ -113,13 +160,43 @@ impl<T: HttpExport> HttpExportRuntime<T> {
 
         loop {
             if self.recv_handled < self.recv_buf.len() {
+                if let Err(wait) = self.token_bucket.acquire(&self.rate_limit) {
+                    tokio::select! {
+                        biased;
+
+                        r = buf_reader.read(&mut read_buf) => {
+                            match r {
+                                Ok(_) => {
+                                    debug!("exporter {}: connection closed by peer", self.config.exporter);
+                                }
+                                Err(e) => {
+                                    debug!("exporter {}: connection closed by peer: {e}", self.config.exporter);
+                                }
+                            }
+                            break;
+                        }
+                        _ = tokio::time::sleep(wait) => {}
+                        n = self.receiver.recv_many(&mut self.recv_buf, BATCH_SIZE), if !self.quit => {
+                            if n == 0 {
+                                self.quit = true;
+                            } else {
+                                self.drop_over_queued();
+                            }
+                        }
+                    }
+                    continue;
+                }
+
                 if let Err(e) = self.send_records(&mut buf_reader, &mut writer).await {
+                    self.stats.add_request_failed();
                     warn!(
                         "exporter {}: failed to send records: {e:?}",
                         self.config.exporter
                     );
                     break;
                 }
+                self.rate_limit
+                    .set_queued(self.recv_buf.len() - self.recv_handled);
                 if self.close_connection {
                     break;
                 }
@@ -127,6 +204,10 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             } else {
                 self.recv_buf.clear();
                 self.recv_handled = 0;
+                if self.quit {
+                    // the receiver is closed while waiting for the rate limit
+                    break;
+                }
             }
 
             tokio::select! {
@@ -153,6 +234,24 @@ impl<T: HttpExport> HttpExportRuntime<T> {
         }
     }
 
+    /// Drop the oldest pieces if the queue is full while waiting for the rate limit
+    fn drop_over_queued(&mut self) {
+        let queued = self.recv_buf.len() - self.recv_handled;
+        let max = self.rate_limit.queue_max_size();
+        if queued > max {
+            let dropped = queued - max;
+            self.recv_buf
+                .drain(self.recv_handled..self.recv_handled + dropped);
+            self.rate_limit.add_dropped(dropped);
+            warn!(
+                "exporter {}: dropped {dropped} oldest records as the rate limit queue is full",
+                self.config.exporter
+            );
+        }
+        self.rate_limit
+            .set_queued(self.recv_buf.len() - self.recv_handled);
+    }
+
     async fn send_records<R, W>(&mut self, reader: &mut R, writer: &mut W) -> anyhow::Result<()>
     where
         R: AsyncBufRead + Unpin,
@@ -163,8 +262,19 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             .map_err(|e| anyhow!("failed to send request: {e}"))?;
         let rsp = self.recv_response(reader).await?;
         self.close_connection = !rsp.keep_alive();
+        if (rsp.code == 401 || rsp.code == 403)
+            && let Some(signer) = &mut self.signer
+        {
+            signer.set_unauthorized();
+        }
         if let Err(e) = self.exporter.check_response(rsp, &self.rsp_body_buf) {
-            warn!("exporter {}: error response: {e:?}", self.config.exporter);
+            self.stats.add_request_failed();
+            warn!(
+                "exporter {}: error response from {}: {e:?}",
+                self.config.exporter, self.config.peer_s
+            );
+        } else {
+            self.stats.add_request_ok();
         }
         Ok(())
     }
@@ -174,8 +284,32 @@ impl<T: HttpExport> HttpExportRuntime<T> {
         W: AsyncWrite + Unpin,
     {
         self.header_buf.truncate(self.fixed_header_len);
+        self.exporter.write_dynamic_headers(&mut self.header_buf);
         self.req_body_buf.clear();
 
+        // the whole body is needed to calculate the payload hash for signing
+        if self.signer.is_none()
+            && let Some(streaming) = self.exporter.streaming_body()
+        {
+            self.header_buf
+                .extend_from_slice(b"Transfer-Encoding: chunked\r\n\r\n");
+            writer.write_all(&self.header_buf).await?;
+
+            streaming.begin_body(&mut self.req_body_buf);
+            while self.recv_handled < self.recv_buf.len() {
+                let records = &self.recv_buf[self.recv_handled..];
+                let handled = streaming.fill_chunk(records, &mut self.req_body_buf);
+                self.recv_handled += handled.max(1);
+                write_chunk(writer, &self.req_body_buf).await?;
+                self.req_body_buf.clear();
+            }
+            streaming.end_body(&mut self.req_body_buf);
+            write_chunk(writer, &self.req_body_buf).await?;
+            writer.write_all(b"0\r\n\r\n").await?;
+            writer.flush().await?;
+            return Ok(());
+        }
+
         let records = &self.recv_buf[self.recv_handled..];
         let handled = self.exporter.fill_body(records, &mut self.req_body_buf);
         if handled == 0 {
@@ -189,6 +323,18 @@ impl<T: HttpExport> HttpExportRuntime<T> {
             self.recv_handled += handled;
         }
 
+        if let Some(signer) = &mut self.signer {
+            signer
+                .write_headers(
+                    self.exporter.api_path().as_str(),
+                    self.exporter.static_headers(),
+                    &self.req_body_buf,
+                    &mut self.header_buf,
+                )
+                .await
+                .map_err(io::Error::other)?;
+        }
+
         // set content-length
         self.header_buf.extend_from_slice(b"Content-Length: ");
         let mut usize_buf = Buffer::new();
@@ -250,3 +396,22 @@ impl<T: HttpExport> HttpExportRuntime<T> {
         Ok(rsp)
     }
 }
+
+async fn write_chunk<W>(writer: &mut W, data: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    if data.is_empty() {
+        // an empty chunk means the end of the body
+        return Ok(());
+    }
+    let size = format!("{:x}\r\n", data.len());
+    writer
+        .write_all_vectored([
+            IoSlice::new(size.as_bytes()),
+            IoSlice::new(data),
+            IoSlice::new(b"\r\n"),
+        ])
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,169 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use super::HttpExportConfig;
+
+/// The rate limit state shared between the http export runtime and the exporter
+pub(crate) struct HttpExportRateLimit {
+    max_requests_per_second: AtomicU32,
+    queue_max_size: AtomicUsize,
+    last_second_requests: AtomicU32,
+    queued: AtomicUsize,
+    dropped: AtomicU64,
+}
+
+impl HttpExportRateLimit {
+    pub(super) fn new(config: &HttpExportConfig) -> Self {
+        HttpExportRateLimit {
+            max_requests_per_second: AtomicU32::new(config.max_requests_per_second),
+            queue_max_size: AtomicUsize::new(config.rate_limit_queue_size),
+            last_second_requests: AtomicU32::new(0),
+            queued: AtomicUsize::new(0),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Apply the new rate limit config, which will take effect at the next request
+    pub(crate) fn update(&self, config: &HttpExportConfig) {
+        self.max_requests_per_second
+            .store(config.max_requests_per_second, Ordering::Relaxed);
+        self.queue_max_size
+            .store(config.rate_limit_queue_size, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(super) fn max_requests_per_second(&self) -> u32 {
+        self.max_requests_per_second.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(super) fn queue_max_size(&self) -> usize {
+        self.queue_max_size.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(super) fn set_queued(&self, queued: usize) {
+        self.queued.store(queued, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(super) fn add_dropped(&self, count: usize) {
+        self.dropped.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn status(&self) -> Vec<String> {
+        let max = self.max_requests_per_second();
+        let used = self.last_second_requests.load(Ordering::Relaxed);
+        let mut lines = Vec::with_capacity(5);
+        if max == 0 {
+            lines.push("max_requests_per_second: unlimited".to_string());
+        } else {
+            lines.push(format!("max_requests_per_second: {max}"));
+            let utilization = used as f64 * 100.0 / max as f64;
+            lines.push(format!("utilization: {utilization:.1}%"));
+        }
+        lines.push(format!("last_second_requests: {used}"));
+        lines.push(format!("queued: {}", self.queued.load(Ordering::Relaxed)));
+        lines.push(format!("dropped: {}", self.dropped.load(Ordering::Relaxed)));
+        lines
+    }
+}
+
+/// Token bucket with the capacity of one second's requests
+pub(super) struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    window_start: Instant,
+    window_requests: u32,
+}
+
+impl TokenBucket {
+    pub(super) fn new() -> Self {
+        let now = Instant::now();
+        TokenBucket {
+            tokens: 0.0,
+            last_refill: now,
+            window_start: now,
+            window_requests: 0,
+        }
+    }
+
+    fn record(&mut self, now: Instant, limit: &HttpExportRateLimit) {
+        let elapsed = now.duration_since(self.window_start);
+        if elapsed >= Duration::from_secs(1) {
+            // report 0 if there is no request in the last whole second
+            let last = if elapsed >= Duration::from_secs(2) {
+                0
+            } else {
+                self.window_requests
+            };
+            limit.last_second_requests.store(last, Ordering::Relaxed);
+            self.window_start = now;
+            self.window_requests = 0;
+        }
+        self.window_requests = self.window_requests.saturating_add(1);
+    }
+
+    /// Take a token for the next request, or return the time to wait before retry
+    pub(super) fn acquire(&mut self, limit: &HttpExportRateLimit) -> Result<(), Duration> {
+        self.acquire_at(Instant::now(), limit)
+    }
+
+    fn acquire_at(&mut self, now: Instant, limit: &HttpExportRateLimit) -> Result<(), Duration> {
+        let max = limit.max_requests_per_second();
+        if max == 0 {
+            self.record(now, limit);
+            return Ok(());
+        }
+
+        let rate = max as f64;
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * rate).min(rate);
+        self.last_refill = now;
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            self.record(now, limit);
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_bucket() {
+        let mut config = HttpExportConfig::new(80);
+        config.max_requests_per_second = 2;
+        let limit = HttpExportRateLimit::new(&config);
+
+        let mut bucket = TokenBucket::new();
+        let start = bucket.last_refill;
+        let wait = bucket.acquire_at(start, &limit).unwrap_err();
+        assert_eq!(wait, Duration::from_millis(500));
+
+        let now = start + Duration::from_secs(1);
+        assert!(bucket.acquire_at(now, &limit).is_ok());
+        assert!(bucket.acquire_at(now, &limit).is_ok());
+        assert!(bucket.acquire_at(now, &limit).is_err());
+
+        // the bucket capacity is one second's requests
+        let now = start + Duration::from_secs(10);
+        assert!(bucket.acquire_at(now, &limit).is_ok());
+        assert!(bucket.acquire_at(now, &limit).is_ok());
+        assert!(bucket.acquire_at(now, &limit).is_err());
+        assert_eq!(limit.last_second_requests.load(Ordering::Relaxed), 0);
+
+        config.max_requests_per_second = 0;
+        limit.update(&config);
+        assert!(bucket.acquire_at(now, &limit).is_ok());
+    }
+}
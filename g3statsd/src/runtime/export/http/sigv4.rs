@@ -0,0 +1,631 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+use log::{info, warn};
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use yaml_rust::Yaml;
+
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+const SIGNING_ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+const ENV_ACCESS_KEY_ID: &str = "AWS_ACCESS_KEY_ID";
+const ENV_SECRET_ACCESS_KEY: &str = "AWS_SECRET_ACCESS_KEY";
+const ENV_SESSION_TOKEN: &str = "AWS_SESSION_TOKEN";
+
+#[derive(Clone, PartialEq, Eq)]
+struct AwsCredential {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl fmt::Debug for AwsCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AwsCredential")
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AwsCredential {
+    fn load_env() -> anyhow::Result<Self> {
+        let access_key_id = std::env::var(ENV_ACCESS_KEY_ID)
+            .map_err(|e| anyhow!("failed to get env var {ENV_ACCESS_KEY_ID}: {e}"))?;
+        let secret_access_key = std::env::var(ENV_SECRET_ACCESS_KEY)
+            .map_err(|e| anyhow!("failed to get env var {ENV_SECRET_ACCESS_KEY}: {e}"))?;
+        let session_token = std::env::var(ENV_SESSION_TOKEN)
+            .ok()
+            .filter(|s| !s.is_empty());
+        Ok(AwsCredential {
+            access_key_id,
+            secret_access_key,
+            session_token,
+        })
+    }
+
+    async fn load_file(path: &PathBuf, profile: &str) -> anyhow::Result<Self> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| anyhow!("failed to read file {}: {e}", path.display()))?;
+        AwsCredential::parse_profile(&content, profile)
+            .context(format!("invalid credential file {}", path.display()))
+    }
+
+    /// Parse the profile in the AWS shared credentials file format
+    fn parse_profile(content: &str, profile: &str) -> anyhow::Result<Self> {
+        let mut in_profile = false;
+        let mut access_key_id = None;
+        let mut secret_access_key = None;
+        let mut session_token = None;
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                in_profile = section.trim() == profile;
+                continue;
+            }
+            if !in_profile {
+                continue;
+            }
+            let Some((k, v)) = line.split_once('=') else {
+                continue;
+            };
+            let v = v.trim().to_string();
+            match k.trim() {
+                "aws_access_key_id" => access_key_id = Some(v),
+                "aws_secret_access_key" => secret_access_key = Some(v),
+                "aws_session_token" => session_token = Some(v),
+                _ => {}
+            }
+        }
+        Ok(AwsCredential {
+            access_key_id: access_key_id
+                .ok_or_else(|| anyhow!("no aws_access_key_id found in profile {profile}"))?,
+            secret_access_key: secret_access_key
+                .ok_or_else(|| anyhow!("no aws_secret_access_key found in profile {profile}"))?,
+            session_token,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum AwsCredentialSource {
+    Static(AwsCredential),
+    Env,
+    File { path: PathBuf, profile: String },
+}
+
+impl AwsCredentialSource {
+    async fn load(&self) -> anyhow::Result<AwsCredential> {
+        match self {
+            AwsCredentialSource::Static(c) => Ok(c.clone()),
+            AwsCredentialSource::Env => AwsCredential::load_env(),
+            AwsCredentialSource::File { path, profile } => {
+                AwsCredential::load_file(path, profile).await
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct AwsSigV4Config {
+    region: String,
+    service: String,
+    credential: AwsCredentialSource,
+    credential_refresh_interval: Duration,
+}
+
+impl AwsSigV4Config {
+    pub(super) fn parse_yaml(
+        value: &Yaml,
+        position: Option<&YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!("yaml value type for sigv4 config should be 'map'"));
+        };
+
+        let mut region = String::new();
+        let mut service = String::new();
+        let mut access_key_id = None;
+        let mut secret_access_key = None;
+        let mut session_token = None;
+        let mut credential_file = None;
+        let mut credential_profile = "default".to_string();
+        let mut credential_refresh_interval = Duration::from_secs(300);
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "region" => {
+                region = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "service" => {
+                service = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "access_key_id" => {
+                access_key_id = Some(g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "secret_access_key" => {
+                secret_access_key = Some(g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "session_token" => {
+                session_token = Some(g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "credential_file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                credential_file = Some(path);
+                Ok(())
+            }
+            "credential_profile" => {
+                credential_profile = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "credential_refresh_interval" => {
+                credential_refresh_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if region.is_empty() {
+            return Err(anyhow!("no region set"));
+        }
+        if service.is_empty() {
+            return Err(anyhow!("no service set"));
+        }
+        let credential = match (access_key_id, secret_access_key, credential_file) {
+            (Some(access_key_id), Some(secret_access_key), None) => {
+                AwsCredentialSource::Static(AwsCredential {
+                    access_key_id,
+                    secret_access_key,
+                    session_token,
+                })
+            }
+            (None, None, Some(path)) => AwsCredentialSource::File {
+                path,
+                profile: credential_profile,
+            },
+            (None, None, None) => AwsCredentialSource::Env,
+            (Some(_), Some(_), Some(_)) => {
+                return Err(anyhow!(
+                    "static credential and credential file should not be set at the same time"
+                ));
+            }
+            _ => {
+                return Err(anyhow!(
+                    "access_key_id and secret_access_key should be set together"
+                ));
+            }
+        };
+        Ok(AwsSigV4Config {
+            region,
+            service,
+            credential,
+            credential_refresh_interval,
+        })
+    }
+}
+
+/// Sign the http export requests by AWS Signature Version 4
+pub(super) struct AwsSigV4Signer {
+    exporter: NodeName,
+    config: AwsSigV4Config,
+    host: String,
+    credential: Option<AwsCredential>,
+    expire: Option<Instant>,
+    hex_buf: String,
+}
+
+impl AwsSigV4Signer {
+    pub(super) fn new(exporter: NodeName, config: AwsSigV4Config, host: String) -> Self {
+        AwsSigV4Signer {
+            exporter,
+            config,
+            host,
+            credential: None,
+            expire: None,
+            hex_buf: String::with_capacity(64),
+        }
+    }
+
+    /// Get the current credential, which will be reloaded if expired
+    async fn credential(&mut self) -> Option<&AwsCredential> {
+        let now = Instant::now();
+        if self.expire.map(|expire| now >= expire).unwrap_or(true) {
+            // retry after the refresh interval even if failed, and keep the old credential
+            self.expire = Some(now + self.config.credential_refresh_interval);
+            match self.config.credential.load().await {
+                Ok(credential) => {
+                    if self
+                        .credential
+                        .as_ref()
+                        .is_some_and(|old| *old != credential)
+                    {
+                        info!("exporter {}: aws credential refreshed", self.exporter);
+                    }
+                    self.credential = Some(credential);
+                }
+                Err(e) => warn!(
+                    "exporter {}: failed to load aws credential: {e:?}",
+                    self.exporter
+                ),
+            }
+        }
+        self.credential.as_ref()
+    }
+
+    /// Force to reload the credential at the next request
+    pub(super) fn set_unauthorized(&mut self) {
+        if !matches!(self.config.credential, AwsCredentialSource::Static(_)) {
+            self.expire = None;
+        }
+    }
+
+    /// Write the signing headers, each line will end with CRLF
+    pub(super) async fn write_headers(
+        &mut self,
+        path_and_query: &str,
+        static_headers: &HeaderMap,
+        body: &[u8],
+        header_buf: &mut Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let time = Utc::now();
+        let payload_hash = hex::encode(openssl::sha::sha256(body));
+        let Some(credential) = self.credential().await.cloned() else {
+            return Err(anyhow!("no aws credential available"));
+        };
+
+        let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+        let mut headers: Vec<(String, String)> = Vec::with_capacity(static_headers.len() + 4);
+        headers.push(("host".to_string(), self.host.clone()));
+        headers.push(("x-amz-content-sha256".to_string(), payload_hash.clone()));
+        headers.push(("x-amz-date".to_string(), amz_date.clone()));
+        if let Some(token) = &credential.session_token {
+            headers.push(("x-amz-security-token".to_string(), token.clone()));
+        }
+        for (name, value) in static_headers {
+            if name == http::header::AUTHORIZATION {
+                continue;
+            }
+            if let Ok(value) = value.to_str() {
+                headers.push((name.as_str().to_string(), value.to_string()));
+            }
+        }
+
+        let authorization = sign_request(
+            &credential,
+            &self.config.region,
+            &self.config.service,
+            "POST",
+            path_and_query,
+            &mut headers,
+            &payload_hash,
+            &time,
+            &mut self.hex_buf,
+        )?;
+
+        let _ = write!(header_buf, "X-Amz-Date: {amz_date}\r\n");
+        let _ = write!(header_buf, "X-Amz-Content-Sha256: {payload_hash}\r\n");
+        if let Some(token) = &credential.session_token {
+            let _ = write!(header_buf, "X-Amz-Security-Token: {token}\r\n");
+        }
+        let _ = write!(header_buf, "Authorization: {authorization}\r\n");
+        Ok(())
+    }
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let key = PKey::hmac(key).map_err(|e| anyhow!("failed to create hmac key: {e}"))?;
+    let mut signer = Signer::new(MessageDigest::sha256(), &key)
+        .map_err(|e| anyhow!("failed to create hmac signer: {e}"))?;
+    signer
+        .update(data)
+        .map_err(|e| anyhow!("failed to update hmac signer: {e}"))?;
+    signer
+        .sign_to_vec()
+        .map_err(|e| anyhow!("failed to sign hmac: {e}"))
+}
+
+/// Encode all chars except the unreserved ones, and keep '/' if `path` is set
+fn uri_encode(s: &str, path: bool, buf: &mut String) {
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                buf.push(b as char)
+            }
+            b'/' if path => buf.push('/'),
+            _ => {
+                let _ = fmt::Write::write_fmt(buf, format_args!("%{b:02X}"));
+            }
+        }
+    }
+}
+
+/// Trim the value and convert sequential spaces to a single space
+fn push_canonical_header_value(value: &str, buf: &mut String) {
+    for (i, part) in value.split_ascii_whitespace().enumerate() {
+        if i > 0 {
+            buf.push(' ');
+        }
+        buf.push_str(part);
+    }
+}
+
+fn canonical_query(query: &str) -> String {
+    let mut pairs: Vec<(&str, &str)> = query
+        .split('&')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.split_once('=').unwrap_or((s, "")))
+        .collect();
+    pairs.sort();
+    let mut buf = String::with_capacity(query.len() + 8);
+    for (i, (k, v)) in pairs.into_iter().enumerate() {
+        if i > 0 {
+            buf.push('&');
+        }
+        // the values in the query should have already been encoded
+        buf.push_str(k);
+        buf.push('=');
+        buf.push_str(v);
+    }
+    buf
+}
+
+/// Sign the request and return the value of the Authorization header.
+///
+/// The `headers` should contain all the headers to be signed, with lowercase names.
+#[allow(clippy::too_many_arguments)]
+fn sign_request(
+    credential: &AwsCredential,
+    region: &str,
+    service: &str,
+    method: &str,
+    path_and_query: &str,
+    headers: &mut [(String, String)],
+    payload_hash: &str,
+    time: &DateTime<Utc>,
+    hex_buf: &mut String,
+) -> anyhow::Result<String> {
+    let date = time.format("%Y%m%d").to_string();
+    let amz_date = time.format("%Y%m%dT%H%M%SZ").to_string();
+    let (path, query) = path_and_query
+        .split_once('?')
+        .unwrap_or((path_and_query, ""));
+
+    headers.sort();
+    let mut canonical_request = String::with_capacity(512);
+    canonical_request.push_str(method);
+    canonical_request.push('\n');
+    // the path sent has already been encoded once, encode it again as required by non-S3 services
+    uri_encode(
+        if path.is_empty() { "/" } else { path },
+        true,
+        &mut canonical_request,
+    );
+    canonical_request.push('\n');
+    canonical_request.push_str(&canonical_query(query));
+    canonical_request.push('\n');
+    for (name, value) in headers.iter() {
+        canonical_request.push_str(name);
+        canonical_request.push(':');
+        push_canonical_header_value(value, &mut canonical_request);
+        canonical_request.push('\n');
+    }
+    canonical_request.push('\n');
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    canonical_request.push_str(&signed_headers);
+    canonical_request.push('\n');
+    canonical_request.push_str(payload_hash);
+
+    let scope = format!("{date}/{region}/{service}/aws4_request");
+    hex_buf.clear();
+    hex_buf.push_str(&hex::encode(openssl::sha::sha256(
+        canonical_request.as_bytes(),
+    )));
+    let string_to_sign = format!("{SIGNING_ALGORITHM}\n{amz_date}\n{scope}\n{hex_buf}");
+
+    let secret = format!("AWS4{}", credential.secret_access_key);
+    let k_date = hmac_sha256(secret.as_bytes(), date.as_bytes())?;
+    let k_region = hmac_sha256(&k_date, region.as_bytes())?;
+    let k_service = hmac_sha256(&k_region, service.as_bytes())?;
+    let k_signing = hmac_sha256(&k_service, b"aws4_request")?;
+    let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes())?);
+
+    Ok(format!(
+        "{SIGNING_ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        credential.access_key_id
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn example_credential() -> AwsCredential {
+        AwsCredential {
+            access_key_id: "AKIDEXAMPLE".to_string(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn sign_example() {
+        // the example from the AWS Signature Version 4 documentation
+        let time = DateTime::from_timestamp(1440938160, 0).unwrap();
+        let mut headers = vec![
+            (
+                "content-type".to_string(),
+                "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+            ),
+            ("host".to_string(), "iam.amazonaws.com".to_string()),
+            ("x-amz-date".to_string(), "20150830T123600Z".to_string()),
+        ];
+        let payload_hash = hex::encode(openssl::sha::sha256(b""));
+        let mut hex_buf = String::new();
+        let authorization = sign_request(
+            &example_credential(),
+            "us-east-1",
+            "iam",
+            "GET",
+            "/?Version=2010-05-08&Action=ListUsers",
+            &mut headers,
+            &payload_hash,
+            &time,
+            &mut hex_buf,
+        )
+        .unwrap();
+        assert_eq!(
+            hex_buf,
+            "f536975d06c0309214f805bb90ccff089219ecd68b2577efef23edd43b7e1a59"
+        );
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/iam/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date, \
+             Signature=5d672d79c15b13162d9279b0855cfba6789a8edb4c82c400e06b5924a6f2b5d7"
+        );
+    }
+
+    #[test]
+    fn encode() {
+        let mut buf = String::new();
+        uri_encode("/api/v1/remote write", true, &mut buf);
+        assert_eq!(buf, "/api/v1/remote%20write");
+
+        buf.clear();
+        uri_encode("/a%2Fb", true, &mut buf);
+        assert_eq!(buf, "/a%252Fb");
+
+        assert_eq!(
+            canonical_query("db=a&rp=&precision=s"),
+            "db=a&precision=s&rp="
+        );
+        assert_eq!(canonical_query("b&a=1"), "a=1&b=");
+        assert_eq!(canonical_query(""), "");
+
+        buf.clear();
+        push_canonical_header_value("  text/plain;   charset=utf-8 ", &mut buf);
+        assert_eq!(buf, "text/plain; charset=utf-8");
+    }
+
+    #[test]
+    fn parse_profile() {
+        let content = r#"
+[default]
+aws_access_key_id = AKID1
+aws_secret_access_key = SECRET1
+
+[other]
+aws_access_key_id=AKID2
+aws_secret_access_key=SECRET2
+aws_session_token=TOKEN2
+"#;
+        let c = AwsCredential::parse_profile(content, "default").unwrap();
+        assert_eq!(c.access_key_id, "AKID1");
+        assert_eq!(c.secret_access_key, "SECRET1");
+        assert!(c.session_token.is_none());
+
+        let c = AwsCredential::parse_profile(content, "other").unwrap();
+        assert_eq!(c.access_key_id, "AKID2");
+        assert_eq!(c.session_token.as_deref(), Some("TOKEN2"));
+
+        assert!(AwsCredential::parse_profile(content, "none").is_err());
+    }
+
+    #[test]
+    fn parse_config() {
+        let position = YamlDocPosition {
+            path: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("exporter.yaml"),
+            index: 0,
+        };
+        let parse = |s: &str| {
+            let docs = YamlLoader::load_from_str(s).unwrap();
+            AwsSigV4Config::parse_yaml(&docs[0], Some(&position))
+        };
+
+        let config = parse(
+            "{region: us-east-1, service: aps, access_key_id: AKID, secret_access_key: SECRET}",
+        )
+        .unwrap();
+        assert!(matches!(config.credential, AwsCredentialSource::Static(_)));
+
+        let config = parse("{region: us-east-1, service: aps}").unwrap();
+        assert_eq!(config.credential, AwsCredentialSource::Env);
+        assert_eq!(config.credential_refresh_interval, Duration::from_secs(300));
+
+        // relative to the dir of the config file
+        let config =
+            parse("{region: us-east-1, service: aps, credential_file: Cargo.toml}").unwrap();
+        assert_eq!(
+            config.credential,
+            AwsCredentialSource::File {
+                path: PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"),
+                profile: "default".to_string(),
+            }
+        );
+
+        assert!(parse("{service: aps}").is_err());
+        assert!(parse("{region: us-east-1, service: aps, access_key_id: AKID}").is_err());
+    }
+
+    #[tokio::test]
+    async fn write_headers() {
+        let config = AwsSigV4Config {
+            region: "us-east-1".to_string(),
+            service: "aps".to_string(),
+            credential: AwsCredentialSource::Static(AwsCredential {
+                session_token: Some("TOKEN".to_string()),
+                ..example_credential()
+            }),
+            credential_refresh_interval: Duration::from_secs(300),
+        };
+        let mut signer =
+            AwsSigV4Signer::new(NodeName::default(), config, "aps.example.net".to_string());
+        let mut static_headers = HeaderMap::new();
+        static_headers.insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("text/plain"),
+        );
+        let mut header_buf = Vec::new();
+        signer
+            .write_headers("/api/v1/write", &static_headers, b"", &mut header_buf)
+            .await
+            .unwrap();
+        let headers = String::from_utf8(header_buf).unwrap();
+        assert!(headers.contains(
+            "X-Amz-Content-Sha256: e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855\r\n"
+        ));
+        assert!(headers.contains("X-Amz-Security-Token: TOKEN\r\n"));
+        assert!(headers.contains(
+            "SignedHeaders=content-type;host;x-amz-content-sha256;x-amz-date;x-amz-security-token, "
+        ));
+        assert!(headers.ends_with("\r\n"));
+    }
+}
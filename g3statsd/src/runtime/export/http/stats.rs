@@ -0,0 +1,76 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The request stats of a single http export endpoint
+#[derive(Default)]
+pub(crate) struct HttpExportStats {
+    requests: AtomicU64,
+    failed_requests: AtomicU64,
+    connect_failures: AtomicU64,
+    dropped: AtomicU64,
+    dropped_batches: AtomicU64,
+    /// the count of failures since the last successful request
+    continuous_failures: AtomicU64,
+}
+
+impl HttpExportStats {
+    pub(super) fn add_request_ok(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.continuous_failures.store(0, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_request_failed(&self) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        self.continuous_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_connect_failed(&self) {
+        self.connect_failures.fetch_add(1, Ordering::Relaxed);
+        self.continuous_failures.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_dropped(&self, count: usize) {
+        self.dropped.fetch_add(count as u64, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_dropped_batch(&self) {
+        self.dropped_batches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn dropped_batches(&self) -> u64 {
+        self.dropped_batches.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn healthy(&self) -> bool {
+        self.continuous_failures.load(Ordering::Relaxed) == 0
+    }
+
+    pub(crate) fn status(&self) -> Vec<String> {
+        vec![
+            format!("healthy: {}", self.healthy()),
+            format!("requests: {}", self.requests.load(Ordering::Relaxed)),
+            format!(
+                "failed_requests: {}",
+                self.failed_requests.load(Ordering::Relaxed)
+            ),
+            format!(
+                "connect_failures: {}",
+                self.connect_failures.load(Ordering::Relaxed)
+            ),
+            format!(
+                "continuous_failures: {}",
+                self.continuous_failures.load(Ordering::Relaxed)
+            ),
+            format!(
+                "dropped_while_disconnected: {}",
+                self.dropped.load(Ordering::Relaxed)
+            ),
+            format!("dropped_batches: {}", self.dropped_batches()),
+        ]
+    }
+}
@@ -5,11 +5,23 @@
 
 mod aggregate;
 pub(crate) use aggregate::{
-    AggregateExport, AggregateExportRuntime, CounterStoreValue, GaugeStoreValue,
+    AggregateDumpHandle, AggregateEmitJitter, AggregateExport, AggregateExportRuntime,
+    AggregateExportStatsSnapshot, AggregateExportUpdate, AggregateRollupConfig,
+    AggregateTagTransform, AggregateUpdateHandle, CounterEmitMode, CounterStoreValue,
+    GaugeStoreValue, align_timestamp,
 };
 
 mod stream;
 pub(crate) use stream::{StreamExport, StreamExportConfig, StreamExportRuntime};
 
 mod http;
-pub(crate) use http::{HttpExport, HttpExportConfig, HttpExportRuntime};
+pub(crate) use http::{
+    HttpExport, HttpExportConfig, HttpExportHandle, HttpExportSender, HttpStreamingBody,
+    spawn_http_export,
+};
+
+mod sanitize;
+pub(crate) use sanitize::{
+    GraphiteSanitizeRules, InfluxdbSanitizeRules, MetricSanitizeAction, MetricSanitizeRules,
+    MetricSanitizer, OpentsdbSanitizeRules,
+};
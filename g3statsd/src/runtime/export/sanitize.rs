@@ -0,0 +1,369 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+use crate::types::MetricName;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum MetricSanitizeAction {
+    /// escape each invalid char if the backend supports it, or replace it with '_'
+    Escape,
+    /// replace each invalid char with the specified char
+    Replace(char),
+    /// strip all invalid chars
+    Strip,
+    /// drop the whole series
+    Reject,
+}
+
+impl MetricSanitizeAction {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::String(s) => MetricSanitizeAction::from_str(s),
+            Yaml::Hash(map) => {
+                let mut action = None;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "replace" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        let mut chars = s.chars();
+                        let (Some(c), None) = (chars.next(), chars.next()) else {
+                            return Err(anyhow!("the replacement should be a single char"));
+                        };
+                        action = Some(MetricSanitizeAction::Replace(c));
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                action.ok_or_else(|| anyhow!("no valid sanitize action found"))
+            }
+            _ => Err(anyhow!(
+                "yaml value type for metric sanitize action should be 'string' or 'map'"
+            )),
+        }
+    }
+}
+
+impl FromStr for MetricSanitizeAction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "escape" => Ok(MetricSanitizeAction::Escape),
+            "replace" => Ok(MetricSanitizeAction::Replace('_')),
+            "strip" => Ok(MetricSanitizeAction::Strip),
+            "reject" => Ok(MetricSanitizeAction::Reject),
+            _ => Err(anyhow!("invalid metric sanitize action: {s}")),
+        }
+    }
+}
+
+/// The chars allowed by the backend in metric names and tags.
+pub(crate) trait MetricSanitizeRules {
+    const DEFAULT_ACTION: MetricSanitizeAction = MetricSanitizeAction::Replace('_');
+
+    fn valid_name_char(c: char) -> bool;
+    fn valid_tag_name_char(c: char) -> bool;
+    fn valid_tag_value_char(c: char) -> bool;
+
+    /// Whether the invalid char can be escaped with a backslash
+    fn escape_name_char(_c: char) -> bool {
+        false
+    }
+    fn escape_tag_char(_c: char) -> bool {
+        false
+    }
+
+    fn check_action(action: MetricSanitizeAction) -> anyhow::Result<()> {
+        match action {
+            MetricSanitizeAction::Escape
+                if Self::DEFAULT_ACTION != MetricSanitizeAction::Escape =>
+            {
+                Err(anyhow!("escape is not supported by this backend"))
+            }
+            MetricSanitizeAction::Replace(c)
+                if !(Self::valid_name_char(c)
+                    && Self::valid_tag_name_char(c)
+                    && Self::valid_tag_value_char(c)) =>
+            {
+                Err(anyhow!("the replacement char '{c}' is not valid itself"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// See <https://graphite.readthedocs.io/en/latest/tags.html>
+pub(crate) struct GraphiteSanitizeRules;
+
+impl MetricSanitizeRules for GraphiteSanitizeRules {
+    fn valid_name_char(c: char) -> bool {
+        matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.')
+    }
+
+    fn valid_tag_name_char(c: char) -> bool {
+        c.is_ascii_graphic() && !matches!(c, ';' | '!' | '^' | '=')
+    }
+
+    fn valid_tag_value_char(c: char) -> bool {
+        // '~' is only invalid at the beginning, but we disallow it totally
+        c.is_ascii_graphic() && !matches!(c, ';' | '~')
+    }
+}
+
+/// The special chars that need to be escaped in the line protocol are not allowed,
+/// see <https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/>
+pub(crate) struct InfluxdbSanitizeRules;
+
+impl MetricSanitizeRules for InfluxdbSanitizeRules {
+    const DEFAULT_ACTION: MetricSanitizeAction = MetricSanitizeAction::Escape;
+
+    fn valid_name_char(c: char) -> bool {
+        !(c.is_control() || matches!(c, ',' | ' ' | '\\'))
+    }
+
+    fn valid_tag_name_char(c: char) -> bool {
+        !(c.is_control() || matches!(c, ',' | '=' | ' ' | '\\' | '"'))
+    }
+
+    fn valid_tag_value_char(c: char) -> bool {
+        !(c.is_control() || matches!(c, ',' | '=' | ' ' | '\\' | '"'))
+    }
+
+    fn escape_name_char(c: char) -> bool {
+        matches!(c, ',' | ' ')
+    }
+
+    fn escape_tag_char(c: char) -> bool {
+        matches!(c, ',' | '=' | ' ')
+    }
+}
+
+/// See <http://opentsdb.net/docs/build/html/user_guide/writing/index.html#metrics-and-tags>
+pub(crate) struct OpentsdbSanitizeRules;
+
+impl OpentsdbSanitizeRules {
+    fn valid_char(c: char) -> bool {
+        if c.is_ascii() {
+            matches!(c, 'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '/')
+        } else {
+            c.is_alphanumeric()
+        }
+    }
+}
+
+impl MetricSanitizeRules for OpentsdbSanitizeRules {
+    fn valid_name_char(c: char) -> bool {
+        Self::valid_char(c)
+    }
+
+    fn valid_tag_name_char(c: char) -> bool {
+        Self::valid_char(c)
+    }
+
+    fn valid_tag_value_char(c: char) -> bool {
+        Self::valid_char(c)
+    }
+}
+
+/// Sanitize metric names and tags before serialization.
+///
+/// `None` will be returned if the series should be rejected.
+pub(crate) struct MetricSanitizer<R> {
+    action: MetricSanitizeAction,
+    _rules: PhantomData<R>,
+}
+
+impl<R: MetricSanitizeRules> MetricSanitizer<R> {
+    pub(crate) fn new(action: Option<MetricSanitizeAction>) -> Self {
+        MetricSanitizer {
+            action: action.unwrap_or(R::DEFAULT_ACTION),
+            _rules: PhantomData,
+        }
+    }
+
+    /// Get the sanitized metric name with the prefix added
+    pub(crate) fn full_name(
+        &self,
+        prefix: Option<&MetricName>,
+        name: &MetricName,
+    ) -> Option<String> {
+        let name = if let Some(prefix) = prefix {
+            format!("{}.{}", prefix.display('.'), name.display('.'))
+        } else {
+            name.display('.').to_string()
+        };
+        self.name(&name).map(|s| s.into_owned())
+    }
+
+    pub(crate) fn name<'a>(&self, s: &'a str) -> Option<Cow<'a, str>> {
+        self.sanitize(s, R::valid_name_char, R::escape_name_char)
+    }
+
+    pub(crate) fn tag_name<'a>(&self, s: &'a str) -> Option<Cow<'a, str>> {
+        self.sanitize(s, R::valid_tag_name_char, R::escape_tag_char)
+    }
+
+    pub(crate) fn tag_value<'a>(&self, s: &'a str) -> Option<Cow<'a, str>> {
+        self.sanitize(s, R::valid_tag_value_char, R::escape_tag_char)
+    }
+
+    fn sanitize<'a>(
+        &self,
+        s: &'a str,
+        valid: fn(char) -> bool,
+        escape: fn(char) -> bool,
+    ) -> Option<Cow<'a, str>> {
+        let Some(pos) = s.find(|c| !valid(c)) else {
+            return Some(Cow::Borrowed(s));
+        };
+        let mut r = String::with_capacity(s.len() + 4);
+        r.push_str(&s[..pos]);
+        match self.action {
+            MetricSanitizeAction::Escape => {
+                for c in s[pos..].chars() {
+                    if valid(c) {
+                        r.push(c);
+                    } else if escape(c) {
+                        r.push('\\');
+                        r.push(c);
+                    } else {
+                        r.push('_');
+                    }
+                }
+            }
+            MetricSanitizeAction::Replace(to) => {
+                r.extend(s[pos..].chars().map(|c| if valid(c) { c } else { to }));
+            }
+            MetricSanitizeAction::Strip => {
+                r.extend(s[pos..].chars().filter(|c| valid(*c)));
+                if r.is_empty() {
+                    return None;
+                }
+            }
+            MetricSanitizeAction::Reject => return None,
+        }
+        Some(Cow::Owned(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::{yaml_doc, yaml_str};
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_action() {
+        let yaml = yaml_str!("strip");
+        assert_eq!(
+            MetricSanitizeAction::parse_yaml(&yaml).unwrap(),
+            MetricSanitizeAction::Strip
+        );
+
+        let yaml = yaml_doc!("replace: '-'");
+        assert_eq!(
+            MetricSanitizeAction::parse_yaml(&yaml).unwrap(),
+            MetricSanitizeAction::Replace('-')
+        );
+
+        let yaml = yaml_doc!("replace: '--'");
+        assert!(MetricSanitizeAction::parse_yaml(&yaml).is_err());
+
+        let yaml = yaml_str!("escape");
+        assert_eq!(
+            MetricSanitizeAction::parse_yaml(&yaml).unwrap(),
+            MetricSanitizeAction::Escape
+        );
+
+        assert!(GraphiteSanitizeRules::check_action(MetricSanitizeAction::Replace(';')).is_err());
+        assert!(GraphiteSanitizeRules::check_action(MetricSanitizeAction::Escape).is_err());
+        assert!(OpentsdbSanitizeRules::check_action(MetricSanitizeAction::Escape).is_err());
+        assert!(InfluxdbSanitizeRules::check_action(MetricSanitizeAction::Escape).is_ok());
+        assert!(OpentsdbSanitizeRules::check_action(MetricSanitizeAction::Replace('/')).is_ok());
+    }
+
+    #[test]
+    fn graphite() {
+        let sanitizer = MetricSanitizer::<GraphiteSanitizeRules>::new(None);
+        assert_eq!(sanitizer.name("foo.bar").unwrap(), "foo.bar");
+        assert_eq!(sanitizer.name("foo/bar.中").unwrap(), "foo_bar._");
+        assert_eq!(sanitizer.tag_name("a/b").unwrap(), "a/b");
+        assert_eq!(sanitizer.tag_name("a=b").unwrap(), "a_b");
+        assert_eq!(sanitizer.tag_value("~a;b").unwrap(), "_a_b");
+
+        let sanitizer =
+            MetricSanitizer::<GraphiteSanitizeRules>::new(Some(MetricSanitizeAction::Strip));
+        assert_eq!(sanitizer.name("foo/bar").unwrap(), "foobar");
+        assert!(sanitizer.tag_value("中文").is_none());
+
+        let sanitizer =
+            MetricSanitizer::<GraphiteSanitizeRules>::new(Some(MetricSanitizeAction::Reject));
+        assert_eq!(sanitizer.name("foo.bar").unwrap(), "foo.bar");
+        assert!(sanitizer.name("foo/bar").is_none());
+    }
+
+    #[test]
+    fn influxdb() {
+        let sanitizer = MetricSanitizer::<InfluxdbSanitizeRules>::new(None);
+        assert_eq!(sanitizer.name("foo/bar.中").unwrap(), "foo/bar.中");
+        assert_eq!(sanitizer.name("foo=bar").unwrap(), "foo=bar");
+        assert_eq!(sanitizer.name("foo,bar baz").unwrap(), "foo\\,bar\\ baz");
+        assert_eq!(sanitizer.tag_name("a=b").unwrap(), "a\\=b");
+        assert_eq!(sanitizer.tag_value("a,b=c d").unwrap(), "a\\,b\\=c\\ d");
+        assert_eq!(sanitizer.tag_value("a\nb\\").unwrap(), "a_b_");
+
+        let sanitizer =
+            MetricSanitizer::<InfluxdbSanitizeRules>::new(Some(MetricSanitizeAction::Replace('_')));
+        assert_eq!(sanitizer.name("foo,bar").unwrap(), "foo_bar");
+        assert_eq!(sanitizer.tag_value("a,b=c d").unwrap(), "a_b_c_d");
+
+        let sanitizer =
+            MetricSanitizer::<InfluxdbSanitizeRules>::new(Some(MetricSanitizeAction::Strip));
+        assert_eq!(sanitizer.tag_name("a\tb").unwrap(), "ab");
+
+        let sanitizer =
+            MetricSanitizer::<InfluxdbSanitizeRules>::new(Some(MetricSanitizeAction::Reject));
+        assert!(sanitizer.tag_name("a\"b").is_none());
+    }
+
+    #[test]
+    fn full_name() {
+        let sanitizer = MetricSanitizer::<GraphiteSanitizeRules>::new(None);
+        let prefix = MetricName::parse("g3").unwrap();
+        let name = MetricName::parse("foo/bar").unwrap();
+        assert_eq!(sanitizer.full_name(None, &name).unwrap(), "foo_bar");
+        assert_eq!(
+            sanitizer.full_name(Some(&prefix), &name).unwrap(),
+            "g3.foo_bar"
+        );
+
+        let sanitizer =
+            MetricSanitizer::<GraphiteSanitizeRules>::new(Some(MetricSanitizeAction::Reject));
+        assert!(sanitizer.full_name(Some(&prefix), &name).is_none());
+    }
+
+    #[test]
+    fn opentsdb() {
+        let sanitizer = MetricSanitizer::<OpentsdbSanitizeRules>::new(None);
+        assert_eq!(sanitizer.name("foo/bar.中").unwrap(), "foo/bar.中");
+        assert_eq!(sanitizer.tag_name("a:b").unwrap(), "a_b");
+        assert_eq!(sanitizer.tag_value("a b").unwrap(), "a_b");
+
+        let sanitizer =
+            MetricSanitizer::<OpentsdbSanitizeRules>::new(Some(MetricSanitizeAction::Strip));
+        assert_eq!(sanitizer.tag_value("a=b").unwrap(), "ab");
+        assert!(sanitizer.tag_value("==").is_none());
+
+        let sanitizer =
+            MetricSanitizer::<OpentsdbSanitizeRules>::new(Some(MetricSanitizeAction::Reject));
+        assert!(sanitizer.name("foo bar").is_none());
+    }
+}
@@ -3,8 +3,11 @@
  * Copyright 2025 ByteDance and/or its affiliates.
  */
 
+use std::str::FromStr;
 use std::sync::Arc;
 
+use anyhow::anyhow;
+
 use g3_types::metrics::MetricTagMap;
 
 mod name;
@@ -17,6 +20,23 @@ pub(crate) use value::MetricValue;
 pub(crate) enum MetricType {
     Counter,
     Gauge,
+    /// a signed delta to the current gauge value
+    GaugeDelta,
+    /// a sample value, which will be aggregated to histogram in aggregate collectors
+    Timer,
+}
+
+impl FromStr for MetricType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "counter" | "c" => Ok(MetricType::Counter),
+            "gauge" | "g" => Ok(MetricType::Gauge),
+            "timer" | "ms" | "histogram" | "h" => Ok(MetricType::Timer),
+            _ => Err(anyhow!("unsupported metric type {s}")),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -38,6 +38,30 @@ impl MetricName {
         self.nodes = new_nodes;
     }
 
+    /// Append the suffix to the last node, the suffix should only contain chars valid in node names
+    pub(crate) fn add_suffix(&mut self, suffix: &str) {
+        match self.nodes.pop_back() {
+            Some(last) => {
+                let node = unsafe { NodeName::new_unchecked(format!("{last}{suffix}")) };
+                self.nodes.push_back(node);
+            }
+            None => {
+                let node = unsafe { NodeName::new_unchecked(suffix) };
+                self.nodes.push_back(node);
+            }
+        }
+    }
+
+    /// Check if this name is under the `parent` name, an empty parent name matches all
+    pub(crate) fn is_child_of(&self, parent: &MetricName) -> bool {
+        self.nodes.len() > parent.nodes.len()
+            && self
+                .nodes
+                .iter()
+                .zip(parent.nodes.iter())
+                .all(|(a, b)| a == b)
+    }
+
     pub(crate) fn display(&self, delimiter: char) -> MetricNameDisplay<'_> {
         MetricNameDisplay {
             nodes: &self.nodes,
@@ -106,4 +130,21 @@ mod tests {
         name.add_prefix(&prefix);
         assert_eq!(name.display('.').to_string().as_str(), "g3.bar.foo.counter");
     }
+
+    #[test]
+    fn add_suffix() {
+        let mut name = MetricName::parse("foo.latency").unwrap();
+        name.add_suffix("_bucket");
+        assert_eq!(name.display('.').to_string().as_str(), "foo.latency_bucket");
+    }
+
+    #[test]
+    fn is_child_of() {
+        let name = MetricName::parse("foo.bar.counter").unwrap();
+        assert!(name.is_child_of(&MetricName::parse("foo").unwrap()));
+        assert!(name.is_child_of(&MetricName::parse("foo.bar").unwrap()));
+        assert!(!name.is_child_of(&MetricName::parse("foo.bar.counter").unwrap()));
+        assert!(!name.is_child_of(&MetricName::parse("bar").unwrap()));
+        assert!(name.is_child_of(&MetricName::from([])));
+    }
 }
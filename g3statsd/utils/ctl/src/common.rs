@@ -5,7 +5,7 @@
 
 use g3_ctl::{CommandError, CommandResult};
 
-use g3statsd_proto::types_capnp::operation_result;
+use g3statsd_proto::types_capnp::{fetch_result, operation_result};
 
 pub(crate) fn parse_operation_result(r: operation_result::Reader<'_>) -> CommandResult<()> {
     match r.which().unwrap() {
@@ -16,3 +16,18 @@ pub(crate) fn parse_operation_result(r: operation_result::Reader<'_>) -> Command
         }
     }
 }
+
+pub(crate) fn parse_fetch_result<T>(
+    r: fetch_result::Reader<'_, T>,
+) -> CommandResult<<T as capnp::traits::Owned>::Reader<'_>>
+where
+    T: capnp::traits::Owned,
+{
+    match r.which().unwrap() {
+        fetch_result::Which::Data(data) => Ok(data?),
+        fetch_result::Which::Err(err) => {
+            let e = err?;
+            Err(CommandError::api_error(e.get_code(), e.get_reason()?))
+        }
+    }
+}
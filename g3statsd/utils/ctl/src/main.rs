@@ -23,6 +23,9 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::reload_importer())
         .subcommand(proc::commands::reload_collector())
         .subcommand(proc::commands::reload_exporter())
+        .subcommand(proc::commands::dump_exporter())
+        .subcommand(proc::commands::rate_limit_exporter())
+        .subcommand(proc::commands::diff_config())
 }
 
 #[tokio::main(flavor = "current_thread")]
@@ -55,6 +58,11 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_RELOAD_IMPORTER => proc::reload_importer(&proc_control, args).await,
                 proc::COMMAND_RELOAD_COLLECTOR => proc::reload_collector(&proc_control, args).await,
                 proc::COMMAND_RELOAD_EXPORTER => proc::reload_exporter(&proc_control, args).await,
+                proc::COMMAND_DUMP_EXPORTER => proc::dump_exporter(&proc_control, args).await,
+                proc::COMMAND_RATE_LIMIT_EXPORTER => {
+                    proc::rate_limit_exporter(&proc_control, args).await
+                }
+                proc::COMMAND_DIFF_CONFIG => proc::diff_config(&proc_control).await,
                 _ => Err(CommandError::Cli(anyhow!(
                     "unsupported command {subcommand}"
                 ))),
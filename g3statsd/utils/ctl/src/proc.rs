@@ -9,7 +9,7 @@ use g3_ctl::CommandResult;
 
 use g3statsd_proto::proc_capnp::proc_control;
 
-use crate::common::parse_operation_result;
+use crate::common::{parse_fetch_result, parse_operation_result};
 
 pub const COMMAND_VERSION: &str = "version";
 pub const COMMAND_OFFLINE: &str = "offline";
@@ -26,11 +26,18 @@ pub const COMMAND_RELOAD_IMPORTER: &str = "reload-importer";
 pub const COMMAND_RELOAD_COLLECTOR: &str = "reload-collector";
 pub const COMMAND_RELOAD_EXPORTER: &str = "reload-exporter";
 
+pub const COMMAND_DUMP_EXPORTER: &str = "dump-exporter";
+pub const COMMAND_RATE_LIMIT_EXPORTER: &str = "rate-limit-exporter";
+
+pub const COMMAND_DIFF_CONFIG: &str = "diff-config";
+
 const SUBCOMMAND_ARG_NAME: &str = "name";
+const COMMAND_DUMP_ARG_PREFIX: &str = "prefix";
+const COMMAND_DUMP_ARG_LIMIT: &str = "limit";
 
 pub mod commands {
     use super::*;
-    use clap::{Arg, Command};
+    use clap::{Arg, Command, value_parser};
 
     pub fn version() -> Command {
         Command::new(COMMAND_VERSION)
@@ -73,6 +80,37 @@ pub mod commands {
         Command::new(COMMAND_RELOAD_EXPORTER)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
     }
+
+    pub fn dump_exporter() -> Command {
+        Command::new(COMMAND_DUMP_EXPORTER)
+            .about("Dump the current aggregation state of an aggregate exporter")
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+            .arg(
+                Arg::new(COMMAND_DUMP_ARG_PREFIX)
+                    .help("Only dump the metrics whose name starts with this prefix")
+                    .long(COMMAND_DUMP_ARG_PREFIX)
+                    .num_args(1),
+            )
+            .arg(
+                Arg::new(COMMAND_DUMP_ARG_LIMIT)
+                    .help("Max number of series to dump, at most 10000")
+                    .long(COMMAND_DUMP_ARG_LIMIT)
+                    .num_args(1)
+                    .value_parser(value_parser!(u32))
+                    .default_value("1000"),
+            )
+    }
+
+    pub fn rate_limit_exporter() -> Command {
+        Command::new(COMMAND_RATE_LIMIT_EXPORTER)
+            .about("Show the request rate limit and stats of each peer of an http exporter")
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+    }
+
+    pub fn diff_config() -> Command {
+        Command::new(COMMAND_DIFF_CONFIG)
+            .about("Load the config file and show the action for each object if reloaded")
+    }
 }
 
 pub async fn version(client: &proc_control::Client) -> CommandResult<()> {
@@ -156,3 +194,37 @@ pub async fn reload_exporter(
     let rsp = req.send().promise.await?;
     parse_operation_result(rsp.get()?.get_result()?)
 }
+
+pub async fn dump_exporter(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let mut req = client.dump_exporter_request();
+    req.get().set_name(name);
+    if let Some(prefix) = args.get_one::<String>(COMMAND_DUMP_ARG_PREFIX) {
+        req.get().set_prefix(prefix);
+    }
+    if let Some(limit) = args.get_one::<u32>(COMMAND_DUMP_ARG_LIMIT) {
+        req.get().set_limit(*limit);
+    }
+    let rsp = req.send().promise.await?;
+    let lines = parse_fetch_result(rsp.get()?.get_result()?)?;
+    g3_ctl::print_result_list(lines)
+}
+
+pub async fn rate_limit_exporter(
+    client: &proc_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let mut req = client.rate_limit_exporter_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    let lines = parse_fetch_result(rsp.get()?.get_result()?)?;
+    g3_ctl::print_result_list(lines)
+}
+
+pub async fn diff_config(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.diff_config_request();
+    let rsp = req.send().promise.await?;
+    let lines = parse_fetch_result(rsp.get()?.get_result()?)?;
+    g3_ctl::print_result_list(lines)
+}
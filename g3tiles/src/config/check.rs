@@ -0,0 +1,46 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::Context;
+
+use g3_daemon::opts::CheckReport;
+use g3_types::metrics::NodeName;
+
+/// Check all the loaded config nodes and the references between them
+pub fn check() -> CheckReport {
+    let mut report = CheckReport::default();
+
+    for c in crate::config::discover::get_all() {
+        report.add_node("discover", c.name().as_str(), c.r#type(), Vec::new());
+    }
+    for c in crate::config::backend::get_all() {
+        report.add_node("backend", c.name().as_str(), c.r#type(), Vec::new());
+    }
+
+    let all_server =
+        match crate::config::server::get_all_sorted().context("failed to get all server config") {
+            Ok(v) => v,
+            Err(e) => {
+                report.add_error("server", e);
+                return report;
+            }
+        };
+    let names: BTreeSet<NodeName> = all_server.iter().map(|c| c.name().clone()).collect();
+    for c in &all_server {
+        let mut errors = Vec::new();
+        if let Some(d) = c.dependent_server() {
+            for v in d {
+                if !names.contains(&v) {
+                    errors.push(format!("dependent server {v} not found"));
+                }
+            }
+        }
+        report.add_node("server", c.name().as_str(), c.r#type(), errors);
+    }
+
+    report
+}
@@ -289,6 +289,30 @@ impl YamlMapCallback for OpensslHostConfig {
         value: &Yaml,
         doc: Option<&YamlDocPosition>,
     ) -> anyhow::Result<()> {
+        const KEYS: &[&str] = &[
+            "name",
+            "cert_pairs",
+            "tlcp_cert_pairs",
+            "enable_client_auth",
+            "session_id_context",
+            "no_session_ticket",
+            "disable_session_ticket",
+            "no_session_cache",
+            "disable_session_cache",
+            "ca_certificate",
+            "ca_cert",
+            "client_auth_certificate",
+            "client_auth_cert",
+            "request_rate_limit",
+            "request_limit_quota",
+            "request_max_alive",
+            "request_alive_max",
+            "tcp_sock_speed_limit",
+            "tcp_conn_speed_limit",
+            "task_idle_max_count",
+            "backends",
+        ];
+
         match g3_yaml::key::normalize(key).as_str() {
             "name" => {
                 self.name = g3_yaml::value::as_string(value)?;
@@ -367,7 +391,7 @@ impl YamlMapCallback for OpensslHostConfig {
                 self.backends = g3_yaml::value::as_alpn_matched_backends(value)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {key}")),
+            _ => Err(g3_yaml::key::invalid_at(key, KEYS, doc)),
         }
     }
 
@@ -108,6 +108,12 @@ impl RustlsHostConfig {
                 .push_cert_pair(pair)
                 .context(format!("failed to add cert pair {i}"))?;
         }
+        // keep using the first cert pair if no one matches the SNI, as this host may be the default one
+        if let Some(pair) = self.cert_pairs.first() {
+            cert_resolver
+                .set_fallback_cert_pair(pair)
+                .context("failed to set fallback cert pair")?;
+        }
         let mut config = config_builder.with_cert_resolver(Arc::new(cert_resolver));
 
         config.set_session_cache(self.no_session_cache);
@@ -134,6 +140,32 @@ impl YamlMapCallback for RustlsHostConfig {
         value: &Yaml,
         doc: Option<&YamlDocPosition>,
     ) -> anyhow::Result<()> {
+        const KEYS: &[&str] = &[
+            "name",
+            "cert_pairs",
+            "enable_client_auth",
+            "use_session_ticket",
+            "no_session_ticket",
+            "disable_session_ticket",
+            "no_session_cache",
+            "disable_session_cache",
+            "ca_certificate",
+            "ca_cert",
+            "client_auth_certificate",
+            "client_auth_cert",
+            "accept_timeout",
+            "handshake_timeout",
+            "negotiation_timeout",
+            "request_rate_limit",
+            "request_limit_quota",
+            "request_max_alive",
+            "request_alive_max",
+            "tcp_sock_speed_limit",
+            "tcp_conn_speed_limit",
+            "task_idle_max_count",
+            "backends",
+        ];
+
         match g3_yaml::key::normalize(key).as_str() {
             "name" => {
                 self.name = g3_yaml::value::as_string(value)?;
@@ -207,7 +239,7 @@ impl YamlMapCallback for RustlsHostConfig {
                 self.backends = g3_yaml::value::as_alpn_matched_backends(value)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {key}")),
+            _ => Err(g3_yaml::key::invalid_at(key, KEYS, doc)),
         }
     }
 
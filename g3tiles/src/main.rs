@@ -11,6 +11,10 @@ use g3_daemon::control::{QuitAction, UpgradeAction};
 use g3tiles::opts::ProcArgs;
 
 fn main() -> anyhow::Result<()> {
+    // load before any other threads are spawned, as the environment variables will be changed
+    #[cfg(unix)]
+    let inherited_sockets = g3_daemon::listen::load_inherited_sockets();
+
     #[cfg(feature = "openssl-probe")]
     unsafe {
         openssl_probe::init_openssl_env_vars();
@@ -40,6 +44,10 @@ fn main() -> anyhow::Result<()> {
 
     // set up process logger early, only proc args is used inside
     g3_daemon::log::process::setup(&proc_args.daemon_config);
+    #[cfg(unix)]
+    g3_daemon::listen::log_inherited_sockets(inherited_sockets);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_CONFIG);
+    g3_daemon::health::register(g3_daemon::health::COMPONENT_LISTEN);
     if proc_args.daemon_config.need_daemon_controller() {
         g3tiles::control::UpgradeActor::connect_to_old_daemon();
     }
@@ -48,11 +56,18 @@ fn main() -> anyhow::Result<()> {
         Ok(c) => c,
         Err(e) => {
             g3_daemon::control::upgrade::cancel_old_shutdown();
+            if proc_args.daemon_config.check_config {
+                g3_daemon::opts::CheckReport::print_load_error(&e);
+            }
             return Err(e.context(format!("failed to load config, opts: {:?}", &proc_args)));
         }
     };
     debug!("loaded config from {}", config_file.display());
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_CONFIG);
 
+    if proc_args.daemon_config.check_config {
+        return g3tiles::config::check().print();
+    }
     if proc_args.daemon_config.test_config {
         info!("the format of the config file is ok");
         return Ok(());
@@ -143,5 +158,6 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3tiles::serve::spawn_all()
         .await
         .context("failed to spawn all servers")?;
+    g3_daemon::health::set_ready(g3_daemon::health::COMPONENT_LISTEN);
     Ok(())
 }
@@ -6,6 +6,7 @@
 use log::{error, info, warn};
 use tokio::sync::Mutex;
 
+use g3_daemon::metrics::reload::ReloadFailureType;
 use g3_daemon::signal::AsyncSignalAction;
 
 static RELOAD_MUTEX: Mutex<()> = Mutex::const_new(());
@@ -14,19 +15,30 @@ async fn do_reload() {
     let _guard = RELOAD_MUTEX.lock().await;
     info!("reloading config");
 
+    let mut failure_type = None;
+
     if let Err(e) = crate::config::reload().await {
         warn!("error reloading config: {e:?}");
         warn!("reload aborted");
+        failure_type = Some(ReloadFailureType::Config);
     }
 
     if let Err(e) = crate::discover::load_all().await {
         error!("failed to reload all discovers: {e}");
+        failure_type.get_or_insert(ReloadFailureType::Apply);
     }
     if let Err(e) = crate::backend::load_all().await {
         error!("failed to reload all backends: {e}");
+        failure_type.get_or_insert(ReloadFailureType::Apply);
     }
     if let Err(e) = crate::serve::spawn_all().await {
         error!("failed to reload all servers: {e:?}");
+        failure_type.get_or_insert(ReloadFailureType::Apply);
+    }
+
+    match failure_type {
+        Some(failure_type) => g3_daemon::metrics::reload::add_failure(failure_type),
+        None => g3_daemon::metrics::reload::add_success(),
     }
 
     info!("reload finished");
@@ -41,7 +53,6 @@ impl AsyncSignalAction for QuitAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct OfflineAction {}
 
@@ -51,7 +62,6 @@ impl AsyncSignalAction for OfflineAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct ReloadAction {}
 
@@ -62,9 +72,7 @@ impl AsyncSignalAction for ReloadAction {
 }
 
 pub fn register() -> anyhow::Result<()> {
-    #[cfg(unix)]
     g3_daemon::signal::register_reload(ReloadAction {})?;
-    #[cfg(unix)]
     g3_daemon::signal::register_offline(OfflineAction {})?;
     g3_daemon::signal::register_quit(QuitAction {})
 }
@@ -5,6 +5,8 @@
 
 mod sched;
 pub use sched::CpuAffinity;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub use sched::allowed_cpu_id_list;
 
 mod hostname;
 pub use hostname::hostname;
@@ -24,6 +24,24 @@ pub const fn max_cpu_id() -> usize {
     (bytes << 3) - 1
 }
 
+pub(super) fn allowed_cpu_id_list() -> io::Result<Vec<usize>> {
+    let mut cpu_set: libc::cpu_set_t = unsafe { mem::zeroed() };
+    let r = unsafe {
+        libc::sched_getaffinity(
+            0,
+            size_of::<libc::cpu_set_t>() as libc::size_t,
+            &mut cpu_set,
+        )
+    };
+    if r != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let list = (0..=max_cpu_id())
+        .filter(|id| unsafe { libc::CPU_ISSET(*id, &cpu_set) })
+        .collect();
+    Ok(list)
+}
+
 impl CpuAffinityImpl {
     pub const fn max_cpu_id(&self) -> usize {
         max_cpu_id()
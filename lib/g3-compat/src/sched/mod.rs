@@ -16,6 +16,12 @@ use std::str::FromStr;
 mod os;
 use os::CpuAffinityImpl;
 
+/// Get the list of CPU IDs that the current process is allowed to run on
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub fn allowed_cpu_id_list() -> io::Result<Vec<usize>> {
+    os::allowed_cpu_id_list()
+}
+
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
 struct CpuId(usize);
 
@@ -138,6 +144,13 @@ mod tests {
         assert_eq!(affinity.cpu_id_list(), &[2, 0]);
     }
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[test]
+    fn allowed() {
+        let list = allowed_cpu_id_list().unwrap();
+        assert!(!list.is_empty());
+    }
+
     #[test]
     fn range() {
         let mut affinity = CpuAffinity::default();
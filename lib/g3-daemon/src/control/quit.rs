@@ -195,12 +195,14 @@ impl<T: QuitAction> QuitLoop<T> {
 
         self.release_controller().await;
         info!("start force shutdown");
+        crate::health::set_shutting_down();
         self.action.do_force_shutdown().await;
         clear_daemon_quit_channel();
     }
 
     async fn graceful_shutdown(&self) {
         info!("start graceful shutdown now");
+        crate::health::set_shutting_down();
         self.action.do_graceful_shutdown().await;
     }
 
@@ -127,6 +127,9 @@ where
             }
             Some("set") => self.set(iter),
             Some("pid") => Ok(std::process::id().to_string()),
+            Some("alive") => Ok("ok".to_string()),
+            Some("ready") => Ok(crate::health::readiness_report()),
+            Some("workers") => Ok(crate::runtime::worker::sched_affinity_report()),
             Some(k) => Err(anyhow!("unknown command {k}")),
             None => Ok(String::new()),
         };
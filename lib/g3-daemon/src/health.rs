@@ -0,0 +1,165 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! Liveness and readiness state of the daemon.
+//!
+//! Each daemon should register all the components it depends on at startup,
+//! and mark them ready after they are loaded or spawned. The daemon is ready
+//! only if all registered components are ready and it's not shutting down.
+
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub const COMPONENT_CONFIG: &str = "config";
+pub const COMPONENT_LISTEN: &str = "listen";
+pub const COMPONENT_RESOLVER: &str = "resolver";
+pub const COMPONENT_ESCAPER: &str = "escaper";
+pub const COMPONENT_GEOIP_DB: &str = "geoip_db";
+
+struct HealthState {
+    components: Mutex<Vec<(&'static str, bool)>>,
+    shutting_down: AtomicBool,
+}
+
+impl HealthState {
+    const fn new() -> Self {
+        HealthState {
+            components: Mutex::new(Vec::new()),
+            shutting_down: AtomicBool::new(false),
+        }
+    }
+
+    fn register(&self, component: &'static str) {
+        let mut components = self.components.lock().unwrap();
+        if !components.iter().any(|(name, _)| *name == component) {
+            components.push((component, false));
+        }
+    }
+
+    fn set_state(&self, component: &'static str, ready: bool) {
+        let mut components = self.components.lock().unwrap();
+        if let Some((_, state)) = components.iter_mut().find(|(name, _)| *name == component) {
+            *state = ready;
+        } else {
+            components.push((component, ready));
+        }
+    }
+
+    fn set_shutting_down(&self) {
+        self.shutting_down.store(true, Ordering::Relaxed);
+    }
+
+    fn is_ready(&self) -> bool {
+        if self.shutting_down.load(Ordering::Relaxed) {
+            return false;
+        }
+        let components = self.components.lock().unwrap();
+        components.iter().all(|(_, ready)| *ready)
+    }
+
+    fn readiness_report(&self) -> String {
+        let mut s = String::with_capacity(128);
+        if self.is_ready() {
+            s.push_str("ready");
+        } else {
+            s.push_str("not ready");
+        }
+        if self.shutting_down.load(Ordering::Relaxed) {
+            s.push_str("\nshutting down");
+        }
+        let components = self.components.lock().unwrap();
+        for (name, ready) in components.iter() {
+            let state = if *ready { "ready" } else { "not ready" };
+            let _ = write!(s, "\n{name}: {state}");
+        }
+        s
+    }
+}
+
+static HEALTH_STATE: HealthState = HealthState::new();
+
+/// Register a component that should be ready before the daemon is ready
+pub fn register(component: &'static str) {
+    HEALTH_STATE.register(component);
+}
+
+pub fn set_ready(component: &'static str) {
+    HEALTH_STATE.set_state(component, true);
+}
+
+pub fn set_not_ready(component: &'static str) {
+    HEALTH_STATE.set_state(component, false);
+}
+
+pub(crate) fn set_shutting_down() {
+    HEALTH_STATE.set_shutting_down();
+}
+
+pub fn is_ready() -> bool {
+    HEALTH_STATE.is_ready()
+}
+
+/// Get the readiness report, the first line will be `ready` or `not ready`,
+/// and each of the following lines shows the state of a registered component
+pub fn readiness_report() -> String {
+    HEALTH_STATE.readiness_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transition() {
+        let state = HealthState::new();
+        assert!(state.is_ready());
+        assert_eq!(state.readiness_report(), "ready");
+
+        state.register(COMPONENT_CONFIG);
+        state.register(COMPONENT_LISTEN);
+        state.register(COMPONENT_CONFIG);
+        assert!(!state.is_ready());
+        assert_eq!(
+            state.readiness_report(),
+            "not ready\nconfig: not ready\nlisten: not ready"
+        );
+
+        state.set_state(COMPONENT_CONFIG, true);
+        assert!(!state.is_ready());
+        state.set_state(COMPONENT_LISTEN, true);
+        assert!(state.is_ready());
+        assert_eq!(
+            state.readiness_report(),
+            "ready\nconfig: ready\nlisten: ready"
+        );
+
+        // register again won't reset the state
+        state.register(COMPONENT_LISTEN);
+        assert!(state.is_ready());
+
+        state.set_state(COMPONENT_LISTEN, false);
+        assert!(!state.is_ready());
+        state.set_state(COMPONENT_LISTEN, true);
+        assert!(state.is_ready());
+
+        // unregistered component will be added
+        state.set_state(COMPONENT_RESOLVER, false);
+        assert!(!state.is_ready());
+        assert_eq!(
+            state.readiness_report(),
+            "not ready\nconfig: ready\nlisten: ready\nresolver: not ready"
+        );
+        state.set_state(COMPONENT_RESOLVER, true);
+        assert!(state.is_ready());
+
+        state.set_shutting_down();
+        assert!(!state.is_ready());
+        assert_eq!(
+            state.readiness_report(),
+            "not ready\nshutting down\nconfig: ready\nlisten: ready\nresolver: ready"
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod config;
 pub mod control;
+pub mod health;
 pub mod listen;
 pub mod log;
 pub mod metrics;
@@ -3,11 +3,20 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+#[cfg(unix)]
+use std::io;
+
+#[cfg(unix)]
+use log::{info, warn};
+
 mod stats;
 pub use stats::{ListenAliveGuard, ListenSnapshot, ListenStats};
 
+mod source_limit;
+use source_limit::{SourceConnectionGuard, SourceConnectionLimiter};
+
 mod tcp;
-pub use tcp::{AcceptTcpServer, ListenTcpRuntime};
+pub use tcp::{AcceptTcpServer, ListenTcpRuntime, TcpAcceptHook};
 
 mod udp;
 pub use udp::{ReceiveUdpRuntime, ReceiveUdpServer};
@@ -21,3 +30,23 @@ pub use quic::{AcceptQuicServer, ListenQuicConf, ListenQuicRuntime};
 mod unix;
 #[cfg(unix)]
 pub use unix::*;
+
+/// Load the listen sockets inherited from the previous process,
+/// which will be reused when spawning listeners with the same address.
+///
+/// This should be called at the very beginning of the process, before the process logger is set up,
+/// as the environment variables will be removed. Use [`log_inherited_sockets`] to log the result.
+#[cfg(unix)]
+pub fn load_inherited_sockets() -> io::Result<usize> {
+    g3_socket::inherit::load_from_env()
+}
+
+/// Log the result of [`load_inherited_sockets`] after the process logger is set up
+#[cfg(unix)]
+pub fn log_inherited_sockets(r: io::Result<usize>) {
+    match r {
+        Ok(0) => {}
+        Ok(n) => info!("loaded {n} inherited listen sockets"),
+        Err(e) => warn!("failed to load inherited listen sockets: {e}"),
+    }
+}
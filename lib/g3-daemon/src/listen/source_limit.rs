@@ -0,0 +1,155 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+
+use foldhash::fast::FixedState;
+
+use g3_types::net::TcpListenConfig;
+
+/// Limit the concurrent connections from the same source network.
+///
+/// Entries will be removed when all connections from the source are closed,
+/// so the map size is bounded by the total alive connection count.
+pub(super) struct SourceConnectionLimiter {
+    max_per_source: usize,
+    ipv4_prefix: u8,
+    ipv6_prefix: u8,
+    alive: Mutex<HashMap<IpAddr, usize, FixedState>>,
+}
+
+impl SourceConnectionLimiter {
+    pub(super) fn new(config: &TcpListenConfig) -> Option<Arc<Self>> {
+        let max_per_source = config.max_conn_per_source();
+        if max_per_source == 0 {
+            return None;
+        }
+        Some(Arc::new(SourceConnectionLimiter {
+            max_per_source,
+            ipv4_prefix: config.source_ipv4_prefix(),
+            ipv6_prefix: config.source_ipv6_prefix(),
+            alive: Mutex::new(HashMap::with_hasher(FixedState::with_seed(0))),
+        }))
+    }
+
+    fn source_network(&self, ip: IpAddr) -> IpAddr {
+        match ip {
+            IpAddr::V4(v4) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.ipv4_prefix as u32)
+                    .unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from_bits(v4.to_bits() & mask))
+            }
+            IpAddr::V6(v6) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.ipv6_prefix as u32)
+                    .unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from_bits(v6.to_bits() & mask))
+            }
+        }
+    }
+
+    /// Return None if the limit has been reached for this source
+    pub(super) fn acquire(self: &Arc<Self>, ip: IpAddr) -> Option<SourceConnectionGuard> {
+        let network = self.source_network(ip);
+        let mut alive = self.alive.lock().unwrap();
+        let count = alive.entry(network).or_insert(0);
+        if *count >= self.max_per_source {
+            return None;
+        }
+        *count += 1;
+        Some(SourceConnectionGuard {
+            limiter: self.clone(),
+            network,
+        })
+    }
+
+    fn release(&self, network: IpAddr) {
+        let mut alive = self.alive.lock().unwrap();
+        if let Some(count) = alive.get_mut(&network) {
+            *count -= 1;
+            if *count == 0 {
+                alive.remove(&network);
+            }
+        }
+    }
+}
+
+pub(super) struct SourceConnectionGuard {
+    limiter: Arc<SourceConnectionLimiter>,
+    network: IpAddr,
+}
+
+impl Drop for SourceConnectionGuard {
+    fn drop(&mut self) {
+        self.limiter.release(self.network);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_limiter(max: usize, ipv4_prefix: u8, ipv6_prefix: u8) -> Arc<SourceConnectionLimiter> {
+        let mut config = TcpListenConfig::default();
+        config.set_max_conn_per_source(max);
+        config.set_source_ipv4_prefix(ipv4_prefix).unwrap();
+        config.set_source_ipv6_prefix(ipv6_prefix).unwrap();
+        SourceConnectionLimiter::new(&config).unwrap()
+    }
+
+    #[test]
+    fn disabled() {
+        let config = TcpListenConfig::default();
+        assert!(SourceConnectionLimiter::new(&config).is_none());
+    }
+
+    #[test]
+    fn per_ip() {
+        let limiter = new_limiter(2, 32, 128);
+        let ip1 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+        let ip2 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+
+        let g1 = limiter.acquire(ip1).unwrap();
+        let _g2 = limiter.acquire(ip1).unwrap();
+        assert!(limiter.acquire(ip1).is_none());
+        let _g3 = limiter.acquire(ip2).unwrap();
+
+        drop(g1);
+        let _g4 = limiter.acquire(ip1).unwrap();
+        assert!(limiter.acquire(ip1).is_none());
+    }
+
+    #[test]
+    fn per_network() {
+        let limiter = new_limiter(1, 24, 64);
+        let _g1 = limiter
+            .acquire(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)))
+            .unwrap();
+        assert!(
+            limiter
+                .acquire(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 200)))
+                .is_none()
+        );
+        let _g2 = limiter
+            .acquire(IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1)))
+            .unwrap();
+
+        let _g3 = limiter.acquire("2001:db8::1".parse().unwrap()).unwrap();
+        assert!(limiter.acquire("2001:db8::2".parse().unwrap()).is_none());
+        let _g4 = limiter.acquire("2001:db8:0:1::1".parse().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn release_all() {
+        let limiter = new_limiter(1, 32, 128);
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        let g = limiter.acquire(ip).unwrap();
+        drop(g);
+        assert!(limiter.alive.lock().unwrap().is_empty());
+    }
+}
@@ -16,6 +16,8 @@ pub struct ListenSnapshot {
     pub dropped: u64,
     pub timeout: u64,
     pub failed: u64,
+    pub oversized: u64,
+    pub vetoed: u64,
 }
 
 #[derive(Debug)]
@@ -28,6 +30,8 @@ pub struct ListenStats {
     dropped: AtomicU64,
     timeout: AtomicU64,
     failed: AtomicU64,
+    oversized: AtomicU64,
+    vetoed: AtomicU64,
 }
 
 impl ListenStats {
@@ -40,6 +44,8 @@ impl ListenStats {
             dropped: AtomicU64::new(0),
             timeout: AtomicU64::new(0),
             failed: AtomicU64::new(0),
+            oversized: AtomicU64::new(0),
+            vetoed: AtomicU64::new(0),
         }
     }
 
@@ -95,6 +101,22 @@ impl ListenStats {
         self.failed.load(Ordering::Relaxed)
     }
 
+    /// the early protocol negotiation data (such as TLS handshake) exceeds the size limit
+    pub fn add_oversized(&self) {
+        self.oversized.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn oversized(&self) -> u64 {
+        self.oversized.load(Ordering::Relaxed)
+    }
+
+    /// the connection is vetoed by the tcp accept hook of the server
+    pub fn add_vetoed(&self) {
+        self.vetoed.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn vetoed(&self) -> u64 {
+        self.vetoed.load(Ordering::Relaxed)
+    }
+
     pub fn add_by_proxy_protocol_error(&self, e: ProxyProtocolReadError) {
         match e {
             ProxyProtocolReadError::ReadTimeout => self.add_timeout(),
@@ -18,12 +18,27 @@ use g3_socket::RawSocket;
 use g3_std_ext::net::SocketAddrExt;
 use g3_types::net::TcpListenConfig;
 
-use crate::listen::{ListenAliveGuard, ListenStats};
+use crate::listen::{
+    ListenAliveGuard, ListenStats, SourceConnectionGuard, SourceConnectionLimiter,
+};
 use crate::server::{BaseServer, ClientConnectionInfo, ReloadServer, ServerReloadCommand};
 
+/// Hook to preprocess the accepted tcp connection before the protocol handler.
+///
+/// It will be called in the worker runtime where the task will be run.
+#[async_trait]
+pub trait TcpAcceptHook: Send + Sync {
+    /// Return false to veto the connection, which will then be closed and counted as vetoed.
+    async fn check(&self, stream: &TcpStream, cc_info: &ClientConnectionInfo) -> bool;
+}
+
 #[async_trait]
 pub trait AcceptTcpServer: BaseServer {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo);
+
+    fn tcp_accept_hook(&self) -> Option<Arc<dyn TcpAcceptHook>> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -43,7 +58,10 @@ where
         }
     }
 
-    fn create_instance(&self) -> ListenTcpRuntimeInstance<S> {
+    fn create_instance(
+        &self,
+        source_limiter: Option<Arc<SourceConnectionLimiter>>,
+    ) -> ListenTcpRuntimeInstance<S> {
         let server_type = self.server.r#type();
         let server_version = self.server.version();
         ListenTcpRuntimeInstance {
@@ -54,6 +72,7 @@ where
             #[cfg(target_os = "linux")]
             follow_incoming_cpu: false,
             listen_stats: self.listen_stats.clone(),
+            source_limiter,
             instance_id: 0,
             _alive_guard: None,
         }
@@ -73,8 +92,13 @@ where
             }
         }
 
+        #[cfg(unix)]
+        g3_socket::inherit::check_tcp_listeners(listen_config.address(), instance_count)?;
+
+        // shared by all instances
+        let source_limiter = SourceConnectionLimiter::new(listen_config);
         for i in 0..instance_count {
-            let mut runtime = self.create_instance();
+            let mut runtime = self.create_instance(source_limiter.clone());
             runtime.instance_id = i;
 
             let listener = g3_socket::tcp::new_std_listener(listen_config)?;
@@ -97,6 +121,7 @@ pub struct ListenTcpRuntimeInstance<S> {
     #[cfg(target_os = "linux")]
     follow_incoming_cpu: bool,
     listen_stats: Arc<ListenStats>,
+    source_limiter: Option<Arc<SourceConnectionLimiter>>,
     instance_id: usize,
     _alive_guard: Option<ListenAliveGuard>,
 }
@@ -157,7 +182,7 @@ where
                             self.server = new_server;
                             continue;
                         }
-                        Ok(ServerReloadCommand::QuitRuntime) => {},
+                        Ok(ServerReloadCommand::QuitRuntime | ServerReloadCommand::Quiesce) => {},
                         Err(RecvError::Closed) => {},
                         Err(RecvError::Lagged(dropped)) => {
                             warn!("SRT[{}_v{}#{}] server {} reload notify channel overflowed, {dropped} msg dropped",
@@ -212,15 +237,30 @@ where
     }
 
     fn run_task(&self, stream: TcpStream, peer_addr: SocketAddr, local_addr: SocketAddr) {
+        let source_guard = match &self.source_limiter {
+            Some(limiter) => match limiter.acquire(peer_addr.ip()) {
+                Some(guard) => Some(guard),
+                None => {
+                    self.listen_stats.add_dropped();
+                    return;
+                }
+            },
+            None => None,
+        };
         let server = self.server.clone();
+        let listen_stats = self.listen_stats.clone();
 
         let mut cc_info = ClientConnectionInfo::new(peer_addr, local_addr);
         cc_info.set_tcp_raw_socket(RawSocket::from(&stream));
         if let Some(worker_id) = self.worker_id {
             cc_info.set_worker_id(Some(worker_id));
-            tokio::spawn(async move {
-                server.run_tcp_task(stream, cc_info).await;
-            });
+            tokio::spawn(Self::spawned_tcp_task(
+                server,
+                stream,
+                cc_info,
+                listen_stats,
+                source_guard,
+            ));
             return;
         }
         #[cfg(target_os = "linux")]
@@ -229,21 +269,50 @@ where
             && let Some(rt) = crate::runtime::worker::select_handle_by_cpu_id(cpu_id)
         {
             cc_info.set_worker_id(Some(rt.id));
-            rt.handle.spawn(async move {
-                server.run_tcp_task(stream, cc_info).await;
-            });
+            rt.handle.spawn(Self::spawned_tcp_task(
+                server,
+                stream,
+                cc_info,
+                listen_stats,
+                source_guard,
+            ));
             return;
         }
         if let Some(rt) = crate::runtime::worker::select_handle() {
             cc_info.set_worker_id(Some(rt.id));
-            rt.handle.spawn(async move {
-                server.run_tcp_task(stream, cc_info).await;
-            });
+            rt.handle.spawn(Self::spawned_tcp_task(
+                server,
+                stream,
+                cc_info,
+                listen_stats,
+                source_guard,
+            ));
         } else {
-            tokio::spawn(async move {
-                server.run_tcp_task(stream, cc_info).await;
-            });
+            tokio::spawn(Self::spawned_tcp_task(
+                server,
+                stream,
+                cc_info,
+                listen_stats,
+                source_guard,
+            ));
+        }
+    }
+
+    async fn spawned_tcp_task(
+        server: S,
+        stream: TcpStream,
+        cc_info: ClientConnectionInfo,
+        listen_stats: Arc<ListenStats>,
+        source_guard: Option<SourceConnectionGuard>,
+    ) {
+        if let Some(hook) = server.tcp_accept_hook()
+            && !hook.check(&stream, &cc_info).await
+        {
+            listen_stats.add_vetoed();
+            return;
         }
+        server.run_tcp_task(stream, cc_info).await;
+        drop(source_guard);
     }
 
     fn get_rt_handle(&mut self, listen_in_worker: bool) -> (Handle, Option<CpuAffinity>) {
@@ -270,12 +339,20 @@ where
                     self.follow_incoming_cpu = true;
                 }
 
-                if let Some(cpu_affinity) = cpu_affinity
-                    && let Err(e) =
+                if let Some(cpu_affinity) = cpu_affinity {
+                    if let Err(e) =
                         g3_socket::tcp::try_listen_on_local_cpu(&listener, &cpu_affinity)
-                {
+                    {
+                        warn!(
+                            "SRT[{}_v{}#{}] failed to set cpu affinity for listen socket: {e}",
+                            self.server.name(),
+                            self.server_version,
+                            self.instance_id
+                        );
+                    }
+                } else {
                     warn!(
-                        "SRT[{}_v{}#{}] failed to set cpu affinity for listen socket: {e}",
+                        "SRT[{}_v{}#{}] no cpu affinity found for the listen runtime, follow_cpu_affinity will not take effect",
                         self.server.name(),
                         self.server_version,
                         self.instance_id
@@ -301,3 +378,101 @@ where
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use g3_types::metrics::NodeName;
+
+    struct FixedHook(bool);
+
+    #[async_trait]
+    impl TcpAcceptHook for FixedHook {
+        async fn check(&self, _stream: &TcpStream, _cc_info: &ClientConnectionInfo) -> bool {
+            self.0
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestServer {
+        name: NodeName,
+        hook: Option<Arc<dyn TcpAcceptHook>>,
+        run_count: Arc<AtomicUsize>,
+    }
+
+    impl BaseServer for TestServer {
+        fn name(&self) -> &NodeName {
+            &self.name
+        }
+
+        fn r#type(&self) -> &'static str {
+            "test"
+        }
+
+        fn version(&self) -> usize {
+            1
+        }
+    }
+
+    impl ReloadServer for TestServer {
+        fn reload(&self) -> Self {
+            self.clone()
+        }
+    }
+
+    #[async_trait]
+    impl AcceptTcpServer for TestServer {
+        async fn run_tcp_task(&self, _stream: TcpStream, _cc_info: ClientConnectionInfo) {
+            self.run_count.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn tcp_accept_hook(&self) -> Option<Arc<dyn TcpAcceptHook>> {
+            self.hook.clone()
+        }
+    }
+
+    async fn run_task(server: &TestServer, listen_stats: &Arc<ListenStats>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let _client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (stream, peer_addr) = listener.accept().await.unwrap();
+        let cc_info = ClientConnectionInfo::new(peer_addr, stream.local_addr().unwrap());
+        ListenTcpRuntimeInstance::spawned_tcp_task(
+            server.clone(),
+            stream,
+            cc_info,
+            listen_stats.clone(),
+            None,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn accept_hook() {
+        let name = NodeName::new_static("test");
+        let listen_stats = Arc::new(ListenStats::new(&name));
+        let mut server = TestServer {
+            name,
+            hook: None,
+            run_count: Arc::new(AtomicUsize::new(0)),
+        };
+
+        run_task(&server, &listen_stats).await;
+        assert_eq!(server.run_count.load(Ordering::Relaxed), 1);
+        assert_eq!(listen_stats.vetoed(), 0);
+
+        server.hook = Some(Arc::new(FixedHook(true)));
+        run_task(&server, &listen_stats).await;
+        assert_eq!(server.run_count.load(Ordering::Relaxed), 2);
+        assert_eq!(listen_stats.vetoed(), 0);
+
+        server.hook = Some(Arc::new(FixedHook(false)));
+        run_task(&server, &listen_stats).await;
+        assert_eq!(server.run_count.load(Ordering::Relaxed), 2);
+        assert_eq!(listen_stats.vetoed(), 1);
+        assert_eq!(listen_stats.dropped(), 0);
+    }
+}
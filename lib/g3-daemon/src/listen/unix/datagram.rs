@@ -91,7 +91,7 @@ where
                             self.server = new_server;
                             continue;
                         }
-                        Ok(ServerReloadCommand::QuitRuntime) => {},
+                        Ok(ServerReloadCommand::QuitRuntime | ServerReloadCommand::Quiesce) => {},
                         Err(RecvError::Closed) => {},
                         Err(RecvError::Lagged(dropped)) => {
                             warn!("SRT[{}_v{}] server {} reload notify channel overflowed, {dropped} msg dropped",
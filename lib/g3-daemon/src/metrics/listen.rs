@@ -15,6 +15,8 @@ const METRIC_NAME_LISTEN_ACCEPTED: &str = "listen.accepted";
 const METRIC_NAME_LISTEN_DROPPED: &str = "listen.dropped";
 const METRIC_NAME_LISTEN_TIMEOUT: &str = "listen.timeout";
 const METRIC_NAME_LISTEN_FAILED: &str = "listen.failed";
+const METRIC_NAME_LISTEN_OVERSIZED: &str = "listen.oversized";
+const METRIC_NAME_LISTEN_VETOED: &str = "listen.vetoed";
 
 pub fn emit_listen_stats(
     client: &mut StatsdClient,
@@ -49,4 +51,6 @@ pub fn emit_listen_stats(
     emit_field!(dropped, METRIC_NAME_LISTEN_DROPPED);
     emit_field!(timeout, METRIC_NAME_LISTEN_TIMEOUT);
     emit_field!(failed, METRIC_NAME_LISTEN_FAILED);
+    emit_field!(oversized, METRIC_NAME_LISTEN_OVERSIZED);
+    emit_field!(vetoed, METRIC_NAME_LISTEN_VETOED);
 }
@@ -15,6 +15,7 @@ mod server;
 pub use server::{ServerMetricExt, TAG_KEY_ONLINE, TAG_KEY_SERVER};
 
 pub mod helper;
+pub mod reload;
 
 pub const TAG_KEY_DAEMON_GROUP: &str = "daemon_group";
 
@@ -0,0 +1,219 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use g3_statsd_client::StatsdClient;
+
+const TAG_KEY_FAILURE_TYPE: &str = "failure_type";
+
+const METRIC_NAME_RELOAD_SUCCESS: &str = "daemon.reload.success";
+const METRIC_NAME_RELOAD_FAILURE: &str = "daemon.reload.failure";
+const METRIC_NAME_RELOAD_LAST_SUCCESS_TIME: &str = "daemon.reload.last_success_time";
+
+#[derive(Clone, Copy)]
+pub enum ReloadFailureType {
+    /// failed to load the config files
+    Config,
+    /// failed to apply the new config to some of the objects
+    Apply,
+    /// failed to reload a single object by the ctl command
+    Object,
+}
+
+impl ReloadFailureType {
+    pub const ALL: [ReloadFailureType; 3] = [
+        ReloadFailureType::Config,
+        ReloadFailureType::Apply,
+        ReloadFailureType::Object,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ReloadFailureType::Config => "config",
+            ReloadFailureType::Apply => "apply",
+            ReloadFailureType::Object => "object",
+        }
+    }
+}
+
+struct ReloadStats {
+    success: AtomicU64,
+    config_failure: AtomicU64,
+    apply_failure: AtomicU64,
+    object_failure: AtomicU64,
+    last_success_time: AtomicU64,
+}
+
+impl ReloadStats {
+    const fn new() -> Self {
+        ReloadStats {
+            success: AtomicU64::new(0),
+            config_failure: AtomicU64::new(0),
+            apply_failure: AtomicU64::new(0),
+            object_failure: AtomicU64::new(0),
+            last_success_time: AtomicU64::new(0),
+        }
+    }
+
+    fn add_success(&self, time: u64) {
+        self.success.fetch_add(1, Ordering::Relaxed);
+        self.last_success_time.store(time, Ordering::Relaxed);
+    }
+
+    fn add_failure(&self, failure_type: ReloadFailureType) {
+        let stats = match failure_type {
+            ReloadFailureType::Config => &self.config_failure,
+            ReloadFailureType::Apply => &self.apply_failure,
+            ReloadFailureType::Object => &self.object_failure,
+        };
+        stats.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> ReloadStatsSnapshot {
+        ReloadStatsSnapshot {
+            success: self.success.load(Ordering::Relaxed),
+            config_failure: self.config_failure.load(Ordering::Relaxed),
+            apply_failure: self.apply_failure.load(Ordering::Relaxed),
+            object_failure: self.object_failure.load(Ordering::Relaxed),
+            last_success_time: self.last_success_time.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReloadStatsSnapshot {
+    pub success: u64,
+    config_failure: u64,
+    apply_failure: u64,
+    object_failure: u64,
+    /// the unix timestamp of the last successful reload, 0 if no reload succeeded
+    pub last_success_time: u64,
+}
+
+impl ReloadStatsSnapshot {
+    pub fn failure(&self, failure_type: ReloadFailureType) -> u64 {
+        match failure_type {
+            ReloadFailureType::Config => self.config_failure,
+            ReloadFailureType::Apply => self.apply_failure,
+            ReloadFailureType::Object => self.object_failure,
+        }
+    }
+
+    /// Get the increments since the old snapshot, the last success time will be kept
+    pub fn delta(&self, old: &Self) -> Self {
+        ReloadStatsSnapshot {
+            success: self.success.wrapping_sub(old.success),
+            config_failure: self.config_failure.wrapping_sub(old.config_failure),
+            apply_failure: self.apply_failure.wrapping_sub(old.apply_failure),
+            object_failure: self.object_failure.wrapping_sub(old.object_failure),
+            last_success_time: self.last_success_time,
+        }
+    }
+}
+
+static RELOAD_STATS: ReloadStats = ReloadStats::new();
+static RELOAD_SNAPSHOT: Mutex<ReloadStatsSnapshot> = Mutex::new(ReloadStatsSnapshot {
+    success: 0,
+    config_failure: 0,
+    apply_failure: 0,
+    object_failure: 0,
+    last_success_time: 0,
+});
+
+pub fn add_success() {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    RELOAD_STATS.add_success(now);
+}
+
+pub fn add_failure(failure_type: ReloadFailureType) {
+    RELOAD_STATS.add_failure(failure_type);
+}
+
+/// Record the result of a single object reload triggered by the ctl command
+pub fn add_object_result<T, E>(r: &Result<T, E>) {
+    match r {
+        Ok(_) => add_success(),
+        Err(_) => add_failure(ReloadFailureType::Object),
+    }
+}
+
+/// Get the current stats, for daemons that emit the metrics in their own way
+pub fn snapshot() -> ReloadStatsSnapshot {
+    RELOAD_STATS.snapshot()
+}
+
+pub fn emit_stats(client: &mut StatsdClient) {
+    let delta = {
+        let mut snap = RELOAD_SNAPSHOT.lock().unwrap();
+        let new = RELOAD_STATS.snapshot();
+        let delta = new.delta(&snap);
+        *snap = new;
+        delta
+    };
+
+    client
+        .count(METRIC_NAME_RELOAD_SUCCESS, delta.success)
+        .send();
+    for failure_type in ReloadFailureType::ALL {
+        client
+            .count(METRIC_NAME_RELOAD_FAILURE, delta.failure(failure_type))
+            .with_tag(TAG_KEY_FAILURE_TYPE, failure_type.as_str())
+            .send();
+    }
+
+    if delta.last_success_time > 0 {
+        client
+            .gauge(
+                METRIC_NAME_RELOAD_LAST_SUCCESS_TIME,
+                delta.last_success_time,
+            )
+            .send();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delta() {
+        let stats = ReloadStats::new();
+        let snap0 = stats.snapshot();
+        assert_eq!(snap0, ReloadStatsSnapshot::default());
+
+        stats.add_success(100);
+        stats.add_failure(ReloadFailureType::Config);
+        stats.add_failure(ReloadFailureType::Object);
+        stats.add_failure(ReloadFailureType::Object);
+        let snap1 = stats.snapshot();
+        let delta = snap1.delta(&snap0);
+        assert_eq!(delta.success, 1);
+        assert_eq!(delta.failure(ReloadFailureType::Config), 1);
+        assert_eq!(delta.failure(ReloadFailureType::Apply), 0);
+        assert_eq!(delta.failure(ReloadFailureType::Object), 2);
+        assert_eq!(delta.last_success_time, 100);
+
+        stats.add_failure(ReloadFailureType::Apply);
+        let snap2 = stats.snapshot();
+        let delta = snap2.delta(&snap1);
+        assert_eq!(delta.success, 0);
+        assert_eq!(delta.failure(ReloadFailureType::Config), 0);
+        assert_eq!(delta.failure(ReloadFailureType::Apply), 1);
+        assert_eq!(delta.failure(ReloadFailureType::Object), 0);
+        assert_eq!(delta.last_success_time, 100);
+
+        stats.add_success(200);
+        stats.add_success(300);
+        let delta = stats.snapshot().delta(&snap2);
+        assert_eq!(delta.success, 2);
+        assert_eq!(delta.last_success_time, 300);
+    }
+}
@@ -0,0 +1,163 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt::Write;
+
+use anyhow::anyhow;
+
+/// The report of the config check, which will be printed in logfmt style.
+///
+/// Each line describes a config node, and the last line shows the final result, like:
+///
+/// ```text
+/// kind=escaper name=default type=DirectFixed result=ok
+/// kind=server name=http type=HttpProxy result=error error="escaper foo not found"
+/// result=error nodes=2 errors=1
+/// ```
+#[derive(Default)]
+pub struct CheckReport {
+    lines: Vec<String>,
+    node_count: usize,
+    error_count: usize,
+}
+
+impl CheckReport {
+    pub fn add_node(&mut self, kind: &str, name: &str, r#type: &str, errors: Vec<String>) {
+        self.node_count += 1;
+        if errors.is_empty() {
+            self.lines
+                .push(format!("kind={kind} name={name} type={type} result=ok"));
+        } else {
+            for e in errors {
+                self.error_count += 1;
+                self.lines.push(format!(
+                    "kind={kind} name={name} type={type} result=error error={e:?}"
+                ));
+            }
+        }
+    }
+
+    pub fn add_error(&mut self, kind: &str, e: anyhow::Error) {
+        self.error_count += 1;
+        self.lines.push(format!(
+            "kind={kind} result=error error={:?}",
+            format!("{e:#}")
+        ));
+    }
+
+    pub fn has_error(&self) -> bool {
+        self.error_count > 0
+    }
+
+    pub fn format(&self) -> String {
+        let mut s = String::with_capacity(self.lines.len() * 64 + 64);
+        for line in &self.lines {
+            s.push_str(line);
+            s.push('\n');
+        }
+        let result = if self.has_error() { "error" } else { "ok" };
+        let _ = writeln!(
+            s,
+            "result={result} nodes={} errors={}",
+            self.node_count, self.error_count
+        );
+        s
+    }
+
+    /// Print the report to stdout, and return error if any check failed
+    pub fn print(&self) -> anyhow::Result<()> {
+        print!("{}", self.format());
+        if self.has_error() {
+            Err(anyhow!(
+                "config check failed with {} errors",
+                self.error_count
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn format_load_error(e: &anyhow::Error) -> String {
+        format!(
+            "kind=config result=error error={:?}\nresult=error nodes=0 errors=1\n",
+            format!("{e:#}")
+        )
+    }
+
+    /// Print the config load error to stdout
+    pub fn print_load_error(e: &anyhow::Error) {
+        print!("{}", Self::format_load_error(e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn ok() {
+        let report = CheckReport::default();
+        assert!(!report.has_error());
+        assert_eq!(report.format(), "result=ok nodes=0 errors=0\n");
+        assert!(report.print().is_ok());
+
+        let mut report = CheckReport::default();
+        report.add_node("escaper", "default", "DirectFixed", Vec::new());
+        report.add_node("server", "http", "HttpProxy", Vec::new());
+        assert!(!report.has_error());
+        assert_eq!(
+            report.format(),
+            "kind=escaper name=default type=DirectFixed result=ok\n\
+             kind=server name=http type=HttpProxy result=ok\n\
+             result=ok nodes=2 errors=0\n"
+        );
+        assert!(report.print().is_ok());
+    }
+
+    #[test]
+    fn error() {
+        let mut report = CheckReport::default();
+        report.add_node("escaper", "default", "DirectFixed", Vec::new());
+        report.add_node(
+            "server",
+            "http",
+            "HttpProxy",
+            vec![
+                "escaper foo not found".to_string(),
+                "user group \"bar\" not found".to_string(),
+            ],
+        );
+        report.add_error(
+            "auditor",
+            Err::<(), _>(anyhow!("no such file"))
+                .context("failed to load auditor")
+                .unwrap_err(),
+        );
+        assert!(report.has_error());
+        assert_eq!(
+            report.format(),
+            "kind=escaper name=default type=DirectFixed result=ok\n\
+             kind=server name=http type=HttpProxy result=error error=\"escaper foo not found\"\n\
+             kind=server name=http type=HttpProxy result=error error=\"user group \\\"bar\\\" not found\"\n\
+             kind=auditor result=error error=\"failed to load auditor: no such file\"\n\
+             result=error nodes=2 errors=3\n"
+        );
+        let e = report.print().unwrap_err();
+        assert_eq!(e.to_string(), "config check failed with 3 errors");
+    }
+
+    #[test]
+    fn load_error() {
+        let e = Err::<(), _>(anyhow!("invalid key foo"))
+            .context("failed to load main conf")
+            .unwrap_err();
+        assert_eq!(
+            CheckReport::format_load_error(&e),
+            "kind=config result=error error=\"failed to load main conf: invalid key foo\"\n\
+             result=error nodes=0 errors=1\n"
+        );
+    }
+}
@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
 use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint, value_parser};
@@ -13,7 +14,9 @@ const ARGS_SYSTEMD: &str = "systemd";
 const ARGS_MONITORED: &str = "monitored";
 const ARGS_PID_FILE: &str = "pid-file";
 const ARGS_TEST_CONFIG: &str = "test-config";
+const ARGS_CHECK: &str = "check";
 const ARGS_PANIC_QUIT: &str = "panic-quit";
+const ARGS_WORKER_THREADS: &str = "worker-threads";
 
 pub trait DaemonArgsExt {
     fn append_daemon_args(self) -> Self;
@@ -28,6 +31,7 @@ pub struct DaemonArgs {
     pub process_name: &'static str,
     pub pid_file: Option<PathBuf>,
     pub test_config: bool,
+    pub check_config: bool,
     pub(crate) panic_quit: bool,
 }
 
@@ -41,6 +45,7 @@ impl DaemonArgs {
             process_name,
             pid_file: None,
             test_config: false,
+            check_config: false,
             panic_quit: false,
         }
     }
@@ -76,6 +81,10 @@ impl DaemonArgs {
         if args.get_flag(ARGS_TEST_CONFIG) {
             self.test_config = true;
         }
+        if args.get_flag(ARGS_CHECK) {
+            self.test_config = true;
+            self.check_config = true;
+        }
         if args.get_flag(ARGS_DAEMON) {
             self.enable_daemon_mode();
         }
@@ -91,6 +100,9 @@ impl DaemonArgs {
         if args.get_flag(ARGS_PANIC_QUIT) {
             self.panic_quit = true;
         }
+        if let Some(n) = args.get_one::<NonZeroUsize>(ARGS_WORKER_THREADS) {
+            crate::runtime::config::set_worker_thread_number_override(*n);
+        }
         Ok(())
     }
 }
@@ -144,11 +156,27 @@ impl DaemonArgsExt for Command {
                 .short('t')
                 .long(ARGS_TEST_CONFIG),
         )
+        .arg(
+            Arg::new(ARGS_CHECK)
+                .help(
+                    "Check the config file and all nodes in it, print a structured report and exit",
+                )
+                .action(ArgAction::SetTrue)
+                .long(ARGS_CHECK),
+        )
         .arg(
             Arg::new(ARGS_PANIC_QUIT)
                 .help("Quit the process if panic")
                 .action(ArgAction::SetTrue)
                 .long(ARGS_PANIC_QUIT),
         )
+        .arg(
+            Arg::new(ARGS_WORKER_THREADS)
+                .help("Override the total thread number of the worker runtime")
+                .num_args(1)
+                .value_name("NUMBER")
+                .value_parser(value_parser!(NonZeroUsize))
+                .long(ARGS_WORKER_THREADS),
+        )
     }
 }
@@ -6,6 +6,9 @@
 mod daemon;
 pub use daemon::{DaemonArgs, DaemonArgsExt};
 
+mod check;
+pub use check::CheckReport;
+
 mod control;
 pub use control::{DEFAULT_CONTROL_DIR, control_dir, validate_and_set_control_dir};
 
@@ -3,9 +3,12 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::num::NonZeroUsize;
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use anyhow::{Context, anyhow};
+use log::warn;
 use yaml_rust::Yaml;
 
 use g3_runtime::blended::BlendedRuntimeConfig;
@@ -15,6 +18,7 @@ use g3_types::sync::GlobalInit;
 static RUNTIME_CONFIG: GlobalInit<BlendedRuntimeConfig> =
     GlobalInit::new(BlendedRuntimeConfig::new());
 static WORKER_CONFIG: GlobalInit<Option<UnaidedRuntimeConfig>> = GlobalInit::new(None);
+static WORKER_THREAD_NUMBER_OVERRIDE: OnceLock<NonZeroUsize> = OnceLock::new();
 static GRACEFUL_WAIT_CONFIG: GlobalInit<GracefulWaitConfig> =
     GlobalInit::new(GracefulWaitConfig::new());
 
@@ -75,11 +79,35 @@ pub fn load(v: &Yaml) -> anyhow::Result<()> {
 }
 
 pub fn load_worker(v: &Yaml) -> anyhow::Result<()> {
-    let config = UnaidedRuntimeConfig::parse_yaml(v)?;
+    let mut config = UnaidedRuntimeConfig::parse_yaml(v)?;
+    if let Some(n) = WORKER_THREAD_NUMBER_OVERRIDE.get() {
+        config.set_thread_number_total(*n);
+        config
+            .check()
+            .context(format!("invalid worker thread number override {n}"))?;
+    }
+    let available = std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN);
+    if config.thread_number_total() > available {
+        warn!(
+            "the worker thread number {} exceeds the number of available CPUs {available}",
+            config.thread_number_total()
+        );
+    }
     WORKER_CONFIG.with_mut(|v| v.replace(config));
     Ok(())
 }
 
+/// Override the total thread number of the worker runtime, this should be called before config load.
+///
+/// The CPU detection may over-report in cgroup limited containers, so this can be used to pin it.
+pub fn set_worker_thread_number_override(num: NonZeroUsize) {
+    let _ = WORKER_THREAD_NUMBER_OVERRIDE.set(num);
+}
+
+pub(crate) fn worker_thread_number_override() -> Option<NonZeroUsize> {
+    WORKER_THREAD_NUMBER_OVERRIDE.get().copied()
+}
+
 pub fn set_default_thread_number(num: usize) {
     RUNTIME_CONFIG.with_mut(|config| config.set_default_thread_number(num));
 }
@@ -4,14 +4,16 @@
  */
 
 use std::cell::RefCell;
+use std::fmt::Write;
 use std::sync::OnceLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use log::warn;
 use rustc_hash::{FxBuildHasher, FxHashMap};
 use tokio::runtime::Handle;
 
 use g3_compat::CpuAffinity;
-use g3_runtime::unaided::WorkersGuard;
+use g3_runtime::unaided::{UnaidedRuntimeConfig, WorkersGuard};
 use g3_types::sync::GlobalInit;
 
 #[derive(Clone)]
@@ -42,8 +44,55 @@ fn build_cpu_core_worker_map() {
     let _ = CPU_CORE_WORKER_MAP.set(map);
 }
 
+fn check_sched_affinity(config: &UnaidedRuntimeConfig) {
+    let runtime_number = config.runtime_number();
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    let allowed_cpu_list = match g3_compat::allowed_cpu_id_list() {
+        Ok(list) => Some(list),
+        Err(e) => {
+            warn!("failed to get the allowed cpu list of current process: {e}");
+            None
+        }
+    };
+
+    for (id, _affinity) in config.sched_affinity() {
+        if *id >= runtime_number {
+            warn!(
+                "sched affinity set for worker {id}, but there are only {runtime_number} workers"
+            );
+            continue;
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if let Some(allowed) = &allowed_cpu_list {
+            for cpu_id in _affinity.cpu_id_list() {
+                if !allowed.contains(cpu_id) {
+                    warn!(
+                        "cpu {cpu_id} in sched affinity of worker {id} is not online or not allowed"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Get the effective cpu affinity description for all workers
+pub fn sched_affinity_report() -> String {
+    let mut s = String::new();
+    for h in handles() {
+        if !s.is_empty() {
+            s.push('\n');
+        }
+        let _ = match &h.cpu_affinity {
+            Some(affinity) => write!(s, "worker-{}: {:?}", h.id, affinity.cpu_id_list()),
+            None => write!(s, "worker-{}: none", h.id),
+        };
+    }
+    s
+}
+
 pub fn spawn_workers() -> anyhow::Result<Option<WorkersGuard>> {
     if let Some(config) = crate::runtime::config::get_worker_config() {
+        check_sched_affinity(config);
         let guard = config.start(|id, handle, cpu_affinity| {
             super::metrics::add_tokio_stats(handle.metrics(), format!("worker-{id}"));
             let worker_handle = WorkerHandle {
@@ -56,6 +105,11 @@ pub fn spawn_workers() -> anyhow::Result<Option<WorkersGuard>> {
         build_cpu_core_worker_map();
         Ok(Some(guard))
     } else {
+        if let Some(n) = crate::runtime::config::worker_thread_number_override() {
+            warn!(
+                "worker runtime is not enabled, the worker thread number override {n} is ignored"
+            );
+        }
         Ok(None)
     }
 }
@@ -45,6 +45,12 @@ impl ClientConnectionInfo {
         self.server_addr = addr.dst_addr;
     }
 
+    /// Set the real client ip found in the forwarding chain, the port is unknown and will be 0
+    #[inline]
+    pub fn set_forwarded_client_ip(&mut self, ip: IpAddr) {
+        self.client_addr = SocketAddr::new(ip, 0);
+    }
+
     #[inline]
     pub fn set_worker_id(&mut self, worker_id: Option<usize>) {
         self.worker_id = worker_id;
@@ -97,7 +103,7 @@ impl ClientConnectionInfo {
     ) -> io::Result<()> {
         if let Some(raw_socket) = &self.tcp_raw_socket {
             raw_socket.set_tcp_misc_opts(
-                AddressFamily::from(&self.client_addr),
+                AddressFamily::from(&self.sock_peer_addr),
                 opts,
                 default_set_nodelay,
             )
@@ -13,6 +13,8 @@ use super::ClientConnectionInfo;
 #[derive(Clone)]
 pub enum ServerReloadCommand {
     QuitRuntime,
+    /// Stop accepting new connections but keep the server, the alive tasks will run to end
+    Quiesce,
     ReloadVersion(usize),
 }
 
@@ -11,7 +11,7 @@ pub use unix::{register_offline, register_quit, register_reload};
 #[cfg(windows)]
 mod windows;
 #[cfg(windows)]
-pub use windows::register_quit;
+pub use windows::{register_offline, register_quit, register_reload};
 
 pub trait AsyncSignalAction: Copy {
     fn run(&self) -> impl Future<Output = ()> + Send;
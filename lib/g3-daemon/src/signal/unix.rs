@@ -52,6 +52,9 @@ where
     Ok(())
 }
 
+/// Call the reload action each time SIGHUP is received.
+///
+/// This is opt-in, each binary should call this in its signal setup if it supports reload.
 pub fn register_reload<RELOAD>(call_reload: RELOAD) -> anyhow::Result<()>
 where
     RELOAD: AsyncSignalAction + Send + 'static,
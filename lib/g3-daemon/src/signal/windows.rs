@@ -23,3 +23,19 @@ where
 
     Ok(())
 }
+
+/// There is no SIGTERM on windows, so this is a no-op
+pub fn register_offline<OFFLINE>(_go_offline: OFFLINE) -> anyhow::Result<()>
+where
+    OFFLINE: AsyncSignalAction + Send + 'static,
+{
+    Ok(())
+}
+
+/// There is no SIGHUP on windows, so this is a no-op
+pub fn register_reload<RELOAD>(_call_reload: RELOAD) -> anyhow::Result<()>
+where
+    RELOAD: AsyncSignalAction + Send + 'static,
+{
+    Ok(())
+}
@@ -16,3 +16,28 @@ pub fn wait_duration(emit_duration: Duration, instant_start: Instant) {
         std::thread::sleep(emit_duration);
     }
 }
+
+/// Wait until the next emit time, and call `sample` every `sample_interval` during the wait
+pub fn wait_duration_with_sample<F>(
+    emit_duration: Duration,
+    instant_start: Instant,
+    sample_interval: Duration,
+    mut sample: F,
+) where
+    F: FnMut(),
+{
+    let Some(instant_next) = instant_start.checked_add(emit_duration) else {
+        std::thread::sleep(emit_duration);
+        return;
+    };
+    loop {
+        let Some(left) = instant_next.checked_duration_since(Instant::now()) else {
+            return;
+        };
+        if left.is_zero() {
+            return;
+        }
+        std::thread::sleep(left.min(sample_interval));
+        sample();
+    }
+}
@@ -14,6 +14,8 @@ pub struct H1InterceptionConfig {
     pub rsp_head_recv_timeout: Duration,
     pub req_head_max_size: usize,
     pub rsp_head_max_size: usize,
+    pub rsp_head_allow_obs_fold: bool,
+    pub rsp_reason_max_len: usize,
     pub body_line_max_len: usize,
     pub steal_forwarded_for: bool,
 }
@@ -27,6 +29,8 @@ impl Default for H1InterceptionConfig {
             rsp_head_recv_timeout: Duration::from_secs(60),
             req_head_max_size: 65536,
             rsp_head_max_size: 65536,
+            rsp_head_allow_obs_fold: false,
+            rsp_reason_max_len: 1024,
             body_line_max_len: 8192,
             steal_forwarded_for: false,
         }
@@ -29,6 +29,7 @@ pub enum ExtensionType {
     PostHandshakeAuth,                   // rfc8446(TLS1.3)
     SignatureAlgorithmsCert,             // rfc8446(TLS1.3)
     KeyShare,                            // rfc8446(TLS1.3)
+    EncryptedClientHello,                // draft-ietf-tls-esni
     Unknown(u16),
 }
 
@@ -57,6 +58,7 @@ impl From<u16> for ExtensionType {
             49 => ExtensionType::PostHandshakeAuth,
             50 => ExtensionType::SignatureAlgorithmsCert,
             51 => ExtensionType::KeyShare,
+            0xfe0d => ExtensionType::EncryptedClientHello,
             n => ExtensionType::Unknown(n),
         }
     }
@@ -144,6 +144,12 @@ fn sni() {
         .unwrap()
         .unwrap();
     let sni = TlsServerName::from_extension_value(sni_bytes).unwrap();
+    // the GREASE ECH extension, which is of outer type
+    let ech_bytes = client_hello
+        .get_ext(ExtensionType::EncryptedClientHello)
+        .unwrap()
+        .unwrap();
+    assert_eq!(ech_bytes[0], 0x00);
     assert!(record.consume_done());
     assert_eq!(sni.as_ref(), "accounts.google.com");
 }
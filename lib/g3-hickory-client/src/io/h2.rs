@@ -27,6 +27,7 @@ pub async fn connect(
     connect_info: TcpConnectInfo,
     tls_config: ClientConfig,
     tls_name: ServerName<'static>,
+    path: Option<String>,
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<HttpsClientStream, ProtoError> {
@@ -59,7 +60,7 @@ pub async fn connect(
         let _ = connection.await;
     });
 
-    HttpsClientStream::new(&server_name, send_request, request_timeout)
+    HttpsClientStream::new(&server_name, send_request, path.as_deref(), request_timeout)
 }
 
 /// A DNS client connection for DNS-over-HTTPS
@@ -76,9 +77,10 @@ impl HttpsClientStream {
     pub fn new(
         name_server_name: &str,
         h2: SendRequest<Bytes>,
+        path: Option<&str>,
         request_timeout: Duration,
     ) -> Result<Self, ProtoError> {
-        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_2, name_server_name)?;
+        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_2, name_server_name, path)?;
         Ok(HttpsClientStream {
             request_builder: Arc::new(request_builder),
             request_timeout,
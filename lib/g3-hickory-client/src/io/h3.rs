@@ -25,6 +25,7 @@ pub async fn connect(
     connect_info: UdpConnectInfo,
     tls_config: ClientConfig,
     tls_name: String,
+    path: Option<String>,
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<H3ClientStream, ProtoError> {
@@ -40,7 +41,13 @@ pub async fn connect(
         .await
         .map_err(|e| format!("h3 connection failed: {e}"))?;
 
-    H3ClientStream::new(&tls_name, driver, send_request, request_timeout)
+    H3ClientStream::new(
+        &tls_name,
+        driver,
+        send_request,
+        path.as_deref(),
+        request_timeout,
+    )
 }
 
 /// A DNS client connection for DNS-over-HTTP/3
@@ -59,9 +66,10 @@ impl H3ClientStream {
         name_server_name: &str,
         connection: Connection<h3_quinn::Connection, Bytes>,
         send_request: SendRequest<h3_quinn::OpenStreams, Bytes>,
+        path: Option<&str>,
         request_timeout: Duration,
     ) -> Result<Self, ProtoError> {
-        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_3, name_server_name)?;
+        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_3, name_server_name, path)?;
         Ok(H3ClientStream {
             request_builder: Arc::new(request_builder),
             request_timeout,
@@ -14,14 +14,20 @@ pub struct HttpDnsRequestBuilder {
 }
 
 impl HttpDnsRequestBuilder {
-    pub fn new(version: Version, host: &str) -> Result<Self, ProtoError> {
+    pub fn new(version: Version, host: &str, path: Option<&str>) -> Result<Self, ProtoError> {
         let mut parts = Parts::default();
         parts.scheme = Some(Scheme::HTTPS);
         parts.authority = Some(
             Authority::from_str(host)
                 .map_err(|e| ProtoError::from(format!("invalid authority: {e}")))?,
         );
-        parts.path_and_query = Some(PathAndQuery::from_static(super::DNS_QUERY_PATH));
+        parts.path_and_query = match path {
+            Some(path) => Some(
+                PathAndQuery::from_str(path)
+                    .map_err(|e| ProtoError::from(format!("invalid path: {e}")))?,
+            ),
+            None => Some(PathAndQuery::from_static(super::DNS_QUERY_PATH)),
+        };
 
         let url = Uri::from_parts(parts)
             .map_err(|e| ProtoError::from(format!("uri parse error: {e}")))?;
@@ -47,3 +53,37 @@ impl HttpDnsRequestBuilder {
         req
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn post() {
+        let builder = HttpDnsRequestBuilder::new(Version::HTTP_2, "dns.example.net", None).unwrap();
+        let req = builder.post(33);
+        assert_eq!(req.method(), Method::POST);
+        assert_eq!(req.version(), Version::HTTP_2);
+        assert_eq!(req.uri().to_string(), "https://dns.example.net/dns-query");
+        assert_eq!(
+            req.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/dns-message"
+        );
+        assert_eq!(
+            req.headers().get(header::ACCEPT).unwrap(),
+            "application/dns-message"
+        );
+        assert_eq!(req.headers().get(header::CONTENT_LENGTH).unwrap(), "33");
+
+        let builder =
+            HttpDnsRequestBuilder::new(Version::HTTP_3, "192.0.2.1:8443", Some("/resolve"))
+                .unwrap();
+        let req = builder.post(48);
+        assert_eq!(req.uri().to_string(), "https://192.0.2.1:8443/resolve");
+        assert_eq!(req.headers().get(header::CONTENT_LENGTH).unwrap(), "48");
+
+        assert!(
+            HttpDnsRequestBuilder::new(Version::HTTP_2, "dns.example.net", Some("a b")).is_err()
+        );
+    }
+}
@@ -13,6 +13,7 @@ use hickory_proto::xfer::{DnsRequest, DnsRequestSender, DnsResponse, DnsResponse
 use hickory_proto::{ProtoError, ProtoErrorKind};
 use quinn::{Connection, RecvStream, VarInt};
 use rustls::ClientConfig;
+use tokio::sync::Mutex;
 
 use g3_socket::UdpConnectInfo;
 
@@ -23,13 +24,78 @@ pub async fn connect(
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<QuicClientStream, ProtoError> {
-    let connection = tokio::time::timeout(
+    let connection =
+        timed_quic_connect(connect_info, tls_config, &tls_name, connect_timeout).await?;
+    Ok(QuicClientStream::new(connection, request_timeout))
+}
+
+async fn timed_quic_connect(
+    connect_info: UdpConnectInfo,
+    tls_config: ClientConfig,
+    tls_name: &str,
+    connect_timeout: Duration,
+) -> Result<Connection, ProtoError> {
+    tokio::time::timeout(
         connect_timeout,
-        crate::connect::quinn::quic_connect(connect_info, tls_config, &tls_name, b"doq"),
+        crate::connect::quinn::quic_connect(connect_info, tls_config, tls_name, b"doq"),
     )
     .await
-    .map_err(|_| ProtoError::from("quic connect timed out"))??;
-    Ok(QuicClientStream::new(connection, request_timeout))
+    .map_err(|_| ProtoError::from("quic connect timed out"))?
+}
+
+/// A shared DNS-over-QUIC connection to a specific server
+///
+/// The connection will be reused by all streams got from this pool, and a new one will be
+/// established if it has been closed.
+pub struct QuicClientStreamPool {
+    connect_info: UdpConnectInfo,
+    tls_config: ClientConfig,
+    tls_name: String,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl QuicClientStreamPool {
+    pub fn new(
+        connect_info: UdpConnectInfo,
+        tls_config: ClientConfig,
+        tls_name: String,
+        connect_timeout: Duration,
+        request_timeout: Duration,
+    ) -> Self {
+        QuicClientStreamPool {
+            connect_info,
+            tls_config,
+            tls_name,
+            connect_timeout,
+            request_timeout,
+            connection: Mutex::new(None),
+        }
+    }
+
+    /// Get a client stream on the shared connection, reconnect if the connection has been closed
+    pub async fn get_stream(&self) -> Result<QuicClientStream, ProtoError> {
+        let mut connection = self.connection.lock().await;
+        if let Some(c) = connection.as_ref()
+            && c.close_reason().is_none()
+        {
+            return Ok(QuicClientStream::new_shared(
+                c.clone(),
+                self.request_timeout,
+            ));
+        }
+
+        let c = timed_quic_connect(
+            self.connect_info.clone(),
+            self.tls_config.clone(),
+            &self.tls_name,
+            self.connect_timeout,
+        )
+        .await?;
+        *connection = Some(c.clone());
+        Ok(QuicClientStream::new_shared(c, self.request_timeout))
+    }
 }
 
 /// A DNS client connection for DNS-over-QUIC
@@ -37,6 +103,8 @@ pub async fn connect(
 pub struct QuicClientStream {
     quic_connection: Connection,
     request_timeout: Duration,
+    /// the connection is shared with other streams and should not be closed on shutdown
+    shared: bool,
     is_shutdown: bool,
 }
 
@@ -45,6 +113,16 @@ impl QuicClientStream {
         QuicClientStream {
             quic_connection: connection,
             request_timeout,
+            shared: false,
+            is_shutdown: false,
+        }
+    }
+
+    fn new_shared(connection: Connection, request_timeout: Duration) -> Self {
+        QuicClientStream {
+            quic_connection: connection,
+            request_timeout,
+            shared: true,
             is_shutdown: false,
         }
     }
@@ -72,8 +150,10 @@ impl DnsRequestSender for QuicClientStream {
 
     fn shutdown(&mut self) {
         self.is_shutdown = true;
-        // no error
-        self.quic_connection.close(VarInt::from_u32(0), b"Shutdown");
+        if !self.shared {
+            // no error
+            self.quic_connection.close(VarInt::from_u32(0), b"Shutdown");
+        }
     }
 
     fn is_shutdown(&self) -> bool {
@@ -152,3 +232,109 @@ async fn quic_recv(mut recv_stream: RecvStream) -> Result<DnsResponse, ProtoErro
 
     Ok(rsp)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+
+    use quinn::crypto::rustls::QuicServerConfig;
+    use quinn::{Endpoint, ServerConfig};
+    use rustls::RootCertStore;
+    use rustls_pki_types::pem::PemObject;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+    use tokio::sync::mpsc;
+
+    use g3_socket::BindAddr;
+
+    fn server_endpoint() -> Endpoint {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let cert = CertificateDer::from_pem_slice(include_bytes!("test_data/server.pem")).unwrap();
+        let key = PrivateKeyDer::from_pem_slice(include_bytes!("test_data/server.key")).unwrap();
+        let mut tls_config = rustls::ServerConfig::builder_with_provider(provider)
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .unwrap()
+            .with_no_client_auth()
+            .with_single_cert(vec![cert], key)
+            .unwrap();
+        tls_config.alpn_protocols = vec![b"doq".to_vec()];
+        let quic_config = QuicServerConfig::try_from(tls_config).unwrap();
+        let server_config = ServerConfig::with_crypto(Arc::new(quic_config));
+        Endpoint::server(
+            server_config,
+            SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0),
+        )
+        .unwrap()
+    }
+
+    fn client_pool(server: SocketAddr) -> QuicClientStreamPool {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let ca_cert = CertificateDer::from_pem_slice(include_bytes!("test_data/ca.pem")).unwrap();
+        let mut roots = RootCertStore::empty();
+        roots.add(ca_cert).unwrap();
+        let tls_config = ClientConfig::builder_with_provider(provider)
+            .with_protocol_versions(&[&rustls::version::TLS13])
+            .unwrap()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        let connect_info = UdpConnectInfo {
+            server,
+            bind: BindAddr::None,
+            buf_conf: Default::default(),
+            misc_opts: Default::default(),
+        };
+        QuicClientStreamPool::new(
+            connect_info,
+            tls_config,
+            "dns.example.net".to_string(),
+            Duration::from_secs(5),
+            Duration::from_secs(5),
+        )
+    }
+
+    #[tokio::test]
+    async fn pool_reconnect() {
+        let server = server_endpoint();
+        let server_addr = server.local_addr().unwrap();
+        let (conn_sender, mut conn_receiver) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(incoming) = server.accept().await {
+                let Ok(connection) = incoming.await else {
+                    continue;
+                };
+                if conn_sender.send(connection).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let pool = client_pool(server_addr);
+
+        let mut stream1 = pool.get_stream().await.unwrap();
+        let server_conn = conn_receiver.recv().await.unwrap();
+
+        // the connection should be reused, even if the previous stream is shutdown
+        stream1.shutdown();
+        let stream2 = pool.get_stream().await.unwrap();
+        assert_eq!(
+            stream1.quic_connection.stable_id(),
+            stream2.quic_connection.stable_id()
+        );
+        assert!(stream2.quic_connection.close_reason().is_none());
+        assert!(conn_receiver.try_recv().is_err());
+
+        // simulate the connection loss
+        server_conn.close(VarInt::from_u32(0), b"lost");
+        stream2.quic_connection.closed().await;
+
+        let stream3 = pool.get_stream().await.unwrap();
+        assert_ne!(
+            stream2.quic_connection.stable_id(),
+            stream3.quic_connection.stable_id()
+        );
+        assert!(stream3.quic_connection.close_reason().is_none());
+        // a new handshake should be done
+        let _ = conn_receiver.recv().await.unwrap();
+    }
+}
@@ -171,23 +171,21 @@ impl ChunkedDataDecodeReaderInternal {
                         )));
                     }
 
-                    match memchr::memchr(b'\n', r_buf) {
-                        Some(p) => {
-                            self.chunk_header.put_slice(&r_buf[0..=p]);
-                            reader.as_mut().consume(p + 1);
-                            break;
-                        }
-                        None => {
-                            let len = r_buf.len();
-                            if self.chunk_header.len() + len > self.body_line_max_size {
-                                return Poll::Ready(Err(io::Error::other(format!(
-                                    "chunk header line too long (> {})",
-                                    self.body_line_max_size
-                                ))));
-                            }
-                            self.chunk_header.put_slice(r_buf);
-                            reader.as_mut().consume(len);
-                        }
+                    let (len, found) = match memchr::memchr(b'\n', r_buf) {
+                        Some(p) => (p + 1, true),
+                        None => (r_buf.len(), false),
+                    };
+                    // the chunk extensions are also counted in
+                    if self.chunk_header.len() + len > self.body_line_max_size {
+                        return Poll::Ready(Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("chunk header line too long (> {})", self.body_line_max_size),
+                        )));
+                    }
+                    self.chunk_header.put_slice(&r_buf[0..len]);
+                    reader.as_mut().consume(len);
+                    if found {
+                        break;
                     }
                 }
 
@@ -304,4 +302,23 @@ mod test {
         assert_eq!(&buf[0..len], b"test\nbody");
         assert!(body_deocder.finished());
     }
+
+    #[tokio::test]
+    async fn read_long_chunk_extension() {
+        let content = b"5;ext=abcdefghijklmnopqrstuvwxyz\r\ntest\n\r\n0\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_deocder = ChunkedDataDecodeReader::new(&mut buf_stream, 64);
+
+        let mut buf = [0u8; 32];
+        let len = body_deocder.read(&mut buf).await.unwrap();
+        assert_eq!(&buf[0..len], b"test\n");
+
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_deocder = ChunkedDataDecodeReader::new(&mut buf_stream, 16);
+
+        let e = body_deocder.read(&mut buf).await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
 }
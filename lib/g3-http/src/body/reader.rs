@@ -10,7 +10,9 @@ use std::task::{Context, Poll, ready};
 use bytes::BufMut;
 use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
 
-use super::HttpBodyType;
+use g3_types::net::HttpHeaderMap;
+
+use super::{HttpBodyType, TrailerReadError};
 use crate::HttpChunkedLine;
 
 enum NextReadType {
@@ -35,6 +37,9 @@ pub struct HttpBodyReader<'a, R> {
 
     trailer_line_length: usize,
     trailer_last_char: u8,
+    trailer_total_size: usize,
+    trailer_max_size: usize,
+    trailer_cache: Option<Vec<u8>>,
 
     finished: bool,
     read_content_length: u64,
@@ -66,6 +71,9 @@ where
             chunk_size_line_cache: Vec::new(),
             trailer_line_length: 0,
             trailer_last_char: 0,
+            trailer_total_size: 0,
+            trailer_max_size: usize::MAX,
+            trailer_cache: None,
             finished: false,
             read_content_length: 0,
             current_chunk_size: 0,
@@ -85,6 +93,9 @@ where
             chunk_size_line_cache: Vec::new(),
             trailer_line_length: 0,
             trailer_last_char: 0,
+            trailer_total_size: 0,
+            trailer_max_size: usize::MAX,
+            trailer_cache: None,
             finished: false,
             read_content_length: 0,
             current_chunk_size: 0,
@@ -104,6 +115,9 @@ where
             chunk_size_line_cache: Vec::<u8>::with_capacity(Self::DEFAULT_LINE_SIZE),
             trailer_line_length: 0,
             trailer_last_char: 0,
+            trailer_total_size: 0,
+            trailer_max_size: usize::MAX,
+            trailer_cache: None,
             finished: false,
             read_content_length: 0,
             current_chunk_size: 0,
@@ -123,6 +137,9 @@ where
             chunk_size_line_cache: Vec::<u8>::with_capacity(Self::DEFAULT_LINE_SIZE),
             trailer_line_length: 0,
             trailer_last_char: 0,
+            trailer_total_size: 0,
+            trailer_max_size: usize::MAX,
+            trailer_cache: None,
             finished: false,
             read_content_length: 0,
             current_chunk_size: 0,
@@ -144,6 +161,9 @@ where
             chunk_size_line_cache: Vec::<u8>::with_capacity(Self::DEFAULT_LINE_SIZE),
             trailer_line_length: 0,
             trailer_last_char: 0,
+            trailer_total_size: 0,
+            trailer_max_size: usize::MAX,
+            trailer_cache: None,
             finished: false,
             read_content_length: 0,
             current_chunk_size: next_chunk_size,
@@ -156,6 +176,41 @@ where
         self.finished
     }
 
+    /// Set the max total size of all the trailer lines, including the final empty line
+    pub fn set_trailer_max_size(&mut self, max_size: usize) {
+        self.trailer_max_size = max_size;
+    }
+
+    /// Keep a copy of the trailer fields while they are passed through
+    pub fn capture_trailer(&mut self) {
+        self.trailer_cache = Some(Vec::with_capacity(Self::DEFAULT_LINE_SIZE));
+    }
+
+    /// Get the captured trailer fields, should be called after all data has been read out
+    pub fn trailer(&self) -> Result<Option<HttpHeaderMap>, TrailerReadError> {
+        let Some(cache) = &self.trailer_cache else {
+            return Ok(None);
+        };
+        if !self.finished {
+            return Err(TrailerReadError::ReadError(io::Error::other(
+                "data has not been read out yet",
+            )));
+        }
+
+        let mut headers = HttpHeaderMap::default();
+        for line in cache.split_inclusive(|c| *c == b'\n') {
+            if line == b"\r\n" || line == b"\n" {
+                break;
+            }
+            super::trailer_reader::parse_trailer_line(line, &mut headers)?;
+        }
+        if headers.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(headers))
+        }
+    }
+
     fn update_next_read_size(&mut self) {
         const MAX_USIZE: usize = usize::MAX;
         debug_assert_eq!(self.next_read_size, 0);
@@ -384,7 +439,22 @@ where
         self.next_read_type = NextReadType::ChunkSize;
     }
 
-    fn poll_trailer(
+    fn poll_trailer(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let nw = ready!(self.poll_trailer_data(cx, buf))?;
+        self.trailer_total_size += nw;
+        if self.trailer_total_size > self.trailer_max_size {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "trailer too large",
+            )));
+        }
+        if let Some(cache) = &mut self.trailer_cache {
+            cache.extend_from_slice(&buf[..nw]);
+        }
+        Poll::Ready(Ok(nw))
+    }
+
+    fn poll_trailer_data(
         &mut self,
         cx: &mut Context<'_>,
         mut buf: &mut [u8],
@@ -773,4 +843,70 @@ mod tests {
         assert_eq!(&buf[..len], b"\r\n");
         assert!(body_reader.finished);
     }
+
+    #[tokio::test]
+    async fn capture_trailer() {
+        let content = b"4\r\nbody\r\n0\r\ngrpc-status: 0\r\ngrpc-message: OK\r\n\r\nXX";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_reader = HttpBodyReader::new(&mut buf_stream, HttpBodyType::Chunked, 1024);
+        body_reader.set_trailer_max_size(64);
+        body_reader.capture_trailer();
+
+        let mut buf = Vec::new();
+        body_reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), &content[..content.len() - 2]);
+        assert!(body_reader.finished());
+
+        let trailer = body_reader.trailer().unwrap().unwrap();
+        assert_eq!(trailer.get("grpc-status").unwrap().as_bytes(), b"0");
+        assert_eq!(trailer.get("grpc-message").unwrap().as_bytes(), b"OK");
+    }
+
+    #[tokio::test]
+    async fn capture_empty_trailer() {
+        let content = b"4\r\nbody\r\n0\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_reader = HttpBodyReader::new(&mut buf_stream, HttpBodyType::Chunked, 1024);
+        body_reader.capture_trailer();
+
+        let mut buf = Vec::new();
+        body_reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), content.as_slice());
+        assert!(body_reader.trailer().unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn too_large_trailer() {
+        let content = b"4\r\nbody\r\n0\r\ngrpc-status: 0\r\ngrpc-message: OK\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_reader = HttpBodyReader::new(&mut buf_stream, HttpBodyType::Chunked, 1024);
+        body_reader.set_trailer_max_size(24);
+
+        let mut buf = Vec::new();
+        let e = body_reader.read_to_end(&mut buf).await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn read_long_chunk_extension() {
+        let content = b"5;ext=abcdefghijklmnopqrstuvwxyz\r\ntest\n\r\n0\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_reader = HttpBodyReader::new(&mut buf_stream, HttpBodyType::Chunked, 64);
+
+        let mut buf = Vec::new();
+        body_reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), content.as_slice());
+
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut body_reader = HttpBodyReader::new(&mut buf_stream, HttpBodyType::Chunked, 16);
+
+        let mut buf = Vec::new();
+        let e = body_reader.read_to_end(&mut buf).await.unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
 }
@@ -95,19 +95,25 @@ impl TrailerReaderInternal {
                 return Poll::Ready(Ok(headers));
             }
 
-            let header = HttpHeaderLine::parse(&self.cached_line)?;
-            let name = HeaderName::from_str(header.name).map_err(|_| {
-                TrailerReadError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderName)
-            })?;
-            let value = HttpHeaderValue::from_str(header.value).map_err(|_| {
-                TrailerReadError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderValue)
-            })?;
+            parse_trailer_line(&self.cached_line, &mut self.headers)?;
             self.cached_line.clear();
-            self.headers.append(name, value);
         }
     }
 }
 
+pub(super) fn parse_trailer_line(
+    line: &[u8],
+    headers: &mut HttpHeaderMap,
+) -> Result<(), TrailerReadError> {
+    let header = HttpHeaderLine::parse(line)?;
+    let name = HeaderName::from_str(header.name)
+        .map_err(|_| TrailerReadError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderName))?;
+    let value = HttpHeaderValue::from_str(header.value)
+        .map_err(|_| TrailerReadError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderValue))?;
+    headers.append(name, value);
+    Ok(())
+}
+
 pub struct TrailerReader<'a, R> {
     reader: &'a mut R,
     internal: TrailerReaderInternal,
@@ -0,0 +1,189 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! The HTTP Capsule Protocol, see RFC 9297.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use thiserror::Error;
+
+pub const CAPSULE_TYPE_DATAGRAM: u64 = 0x00;
+
+const VARINT_MAX: u64 = (1 << 62) - 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum HttpCapsuleParseError {
+    #[error("too large capsule length {0}")]
+    TooLargeCapsule(u64),
+}
+
+/// Get the encoded length of a QUIC variable-length integer, see RFC 9000 Section 16.
+pub fn varint_len(v: u64) -> usize {
+    if v < (1 << 6) {
+        1
+    } else if v < (1 << 14) {
+        2
+    } else if v < (1 << 30) {
+        4
+    } else {
+        8
+    }
+}
+
+/// Encode a QUIC variable-length integer.
+///
+/// The value should not be greater than 2^62 - 1.
+pub fn put_varint<B: BufMut>(buf: &mut B, v: u64) {
+    debug_assert!(v <= VARINT_MAX);
+    match varint_len(v) {
+        1 => buf.put_u8(v as u8),
+        2 => buf.put_u16(0x4000 | v as u16),
+        4 => buf.put_u32(0x8000_0000 | v as u32),
+        _ => buf.put_u64(0xc000_0000_0000_0000 | v),
+    }
+}
+
+/// Decode a QUIC variable-length integer, return `(value, encoded_len)`.
+///
+/// `None` will be returned if there is no enough data.
+pub fn get_varint(buf: &[u8]) -> Option<(u64, usize)> {
+    let first = *buf.first()?;
+    let len = 1usize << (first >> 6);
+    if buf.len() < len {
+        return None;
+    }
+    let mut v = (first & 0x3f) as u64;
+    for b in &buf[1..len] {
+        v = (v << 8) | *b as u64;
+    }
+    Some((v, len))
+}
+
+#[derive(Debug)]
+pub struct HttpCapsule {
+    pub capsule_type: u64,
+    pub payload: Bytes,
+}
+
+impl HttpCapsule {
+    /// Decode the next capsule in `buf`, and advance `buf` if found.
+    ///
+    /// `Ok(None)` will be returned if more data is needed.
+    pub fn decode(
+        buf: &mut BytesMut,
+        max_size: usize,
+    ) -> Result<Option<Self>, HttpCapsuleParseError> {
+        let Some((capsule_type, type_len)) = get_varint(buf) else {
+            return Ok(None);
+        };
+        let Some((length, length_len)) = get_varint(&buf[type_len..]) else {
+            return Ok(None);
+        };
+        if length > max_size as u64 {
+            return Err(HttpCapsuleParseError::TooLargeCapsule(length));
+        }
+        let hdr_len = type_len + length_len;
+        let total_len = hdr_len + length as usize;
+        if buf.len() < total_len {
+            buf.reserve(total_len - buf.len());
+            return Ok(None);
+        }
+        buf.advance(hdr_len);
+        let payload = buf.split_to(length as usize).freeze();
+        Ok(Some(HttpCapsule {
+            capsule_type,
+            payload,
+        }))
+    }
+
+    /// Get the UDP payload if this is a DATAGRAM capsule for connect-udp, see RFC 9298.
+    ///
+    /// Datagrams with unknown context id should be dropped silently.
+    pub fn udp_payload(&self) -> Option<&[u8]> {
+        if self.capsule_type != CAPSULE_TYPE_DATAGRAM {
+            return None;
+        }
+        let (context_id, len) = get_varint(&self.payload)?;
+        if context_id != 0 {
+            return None;
+        }
+        Some(&self.payload[len..])
+    }
+
+    /// Encode an UDP payload as a DATAGRAM capsule for connect-udp, see RFC 9298.
+    pub fn encode_udp_datagram<B: BufMut>(buf: &mut B, payload: &[u8]) {
+        put_varint(buf, CAPSULE_TYPE_DATAGRAM);
+        // with a context id of 0
+        put_varint(buf, payload.len() as u64 + 1);
+        put_varint(buf, 0);
+        buf.put_slice(payload);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint() {
+        // examples from RFC 9000 Appendix A.1
+        let cases: &[(&[u8], u64)] = &[
+            (
+                &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c],
+                151288809941952652,
+            ),
+            (&[0x9d, 0x7f, 0x3e, 0x7d], 494878333),
+            (&[0x7b, 0xbd], 15293),
+            (&[0x25], 37),
+        ];
+        for (encoded, v) in cases {
+            assert_eq!(get_varint(encoded), Some((*v, encoded.len())));
+            assert_eq!(varint_len(*v), encoded.len());
+
+            let mut buf = Vec::new();
+            put_varint(&mut buf, *v);
+            assert_eq!(&buf, encoded);
+        }
+
+        assert_eq!(get_varint(&[0x40, 0x25]), Some((37, 2)));
+        assert_eq!(get_varint(&[]), None);
+        assert_eq!(get_varint(&[0x9d, 0x7f]), None);
+    }
+
+    #[test]
+    fn udp_datagram() {
+        let mut buf = BytesMut::new();
+        HttpCapsule::encode_udp_datagram(&mut buf, b"hello");
+        assert_eq!(buf.as_ref(), b"\x00\x06\x00hello");
+
+        let mut partial = BytesMut::from(&buf[..4]);
+        assert!(HttpCapsule::decode(&mut partial, 1024).unwrap().is_none());
+        assert_eq!(partial.len(), 4);
+
+        buf.extend_from_slice(b"\x00\x02\x01a");
+        let capsule = HttpCapsule::decode(&mut buf, 1024).unwrap().unwrap();
+        assert_eq!(capsule.udp_payload(), Some(b"hello".as_slice()));
+        // unknown context id
+        let capsule = HttpCapsule::decode(&mut buf, 1024).unwrap().unwrap();
+        assert_eq!(capsule.udp_payload(), None);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn unknown_type() {
+        let mut buf = BytesMut::from(b"\x40\x41\x01a".as_slice());
+        let capsule = HttpCapsule::decode(&mut buf, 1024).unwrap().unwrap();
+        assert_eq!(capsule.capsule_type, 0x41);
+        assert_eq!(capsule.udp_payload(), None);
+    }
+
+    #[test]
+    fn too_large() {
+        let mut buf = BytesMut::from(b"\x00\x44\x01".as_slice());
+        assert_eq!(
+            HttpCapsule::decode(&mut buf, 1024).unwrap_err(),
+            HttpCapsuleParseError::TooLargeCapsule(1025)
+        );
+    }
+}
@@ -5,7 +5,7 @@
 
 use std::io;
 
-use http::Version;
+use http::{HeaderName, Version};
 use thiserror::Error;
 
 use g3_types::net::HttpUpgradeTokenParseError;
@@ -18,16 +18,22 @@ pub enum HttpResponseParseError {
     RemoteClosed,
     #[error("too large header, should be less than {0}")]
     TooLargeHeader(usize),
+    #[error("too long reason phrase, should be no more than {0}")]
+    TooLongReason(usize),
     #[error("invalid version {0:?}")]
     InvalidVersion(Version),
     #[error("invalid status line: {0}")]
     InvalidStatusLine(HttpLineParseError),
     #[error("invalid header line: {0}")]
     InvalidHeaderLine(HttpLineParseError),
+    #[error("obsolete line folding found in header")]
+    ObsFoldedHeader,
     #[error("invalid chunked transfer-encoding")]
     InvalidChunkedTransferEncoding,
     #[error("invalid content length")]
     InvalidContentLength,
+    #[error("connection specific header {0} is not allowed")]
+    ConnectionSpecificHeader(HeaderName),
     #[error("invalid upgrade protocol: {0}")]
     InvalidUpgradeProtocol(#[from] HttpUpgradeTokenParseError),
     #[error("io failed: {0:?}")]
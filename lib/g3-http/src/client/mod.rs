@@ -12,5 +12,8 @@ pub use response::HttpForwardRemoteResponse;
 mod transparent;
 pub use transparent::HttpTransparentResponse;
 
+mod transparent_h2;
+pub use transparent_h2::HttpTransparentH2Response;
+
 mod adaptation;
 pub use adaptation::HttpAdaptedResponse;
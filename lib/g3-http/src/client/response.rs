@@ -13,7 +13,7 @@ use tokio::io::AsyncBufRead;
 use g3_io_ext::LimitedBufReadExt;
 use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
 
-use super::{HttpAdaptedResponse, HttpResponseParseError};
+use super::{HttpAdaptedResponse, HttpResponseParseError, HttpTransparentH2Response};
 use crate::header::Connection;
 use crate::{HttpBodyType, HttpHeaderLine, HttpLineParseError, HttpStatusLine};
 
@@ -54,6 +54,37 @@ impl HttpForwardRemoteResponse {
         }
     }
 
+    /// Build the HTTP/1.1 response head to send to the client from a HTTP/2 upstream response.
+    ///
+    /// The body will be sent in chunked encoding if it is expected but no content-length is set.
+    pub fn from_h2(rsp: &HttpTransparentH2Response, method: &Method, keep_alive: bool) -> Self {
+        let reason = rsp
+            .status
+            .canonical_reason()
+            .unwrap_or("NOT STANDARD STATUS CODE")
+            .to_string();
+        let mut r = HttpForwardRemoteResponse::new(Version::HTTP_11, rsp.status.as_u16(), reason);
+        r.end_to_end_headers = rsp.headers.clone();
+        r.keep_alive = keep_alive;
+        if !r.expect_no_body(method) {
+            match rsp.content_length() {
+                Some(content_length) => {
+                    r.has_content_length = true;
+                    r.content_length = content_length;
+                }
+                None => {
+                    r.has_transfer_encoding = true;
+                    r.chunked_transfer = true;
+                    r.hop_by_hop_headers.insert(
+                        header::TRANSFER_ENCODING,
+                        HttpHeaderValue::from_static("chunked"),
+                    );
+                }
+            }
+        }
+        r
+    }
+
     pub fn adapt_with_body(&self, adapted: HttpAdaptedResponse) -> Self {
         let mut hop_by_hop_headers = self.hop_by_hop_headers.clone();
         match adapted.content_length {
@@ -456,4 +487,33 @@ mod tests {
         assert!(!rsp.keep_alive());
         assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ReadUntilEnd));
     }
+
+    #[test]
+    fn from_h2() {
+        let parts = http::Response::builder()
+            .version(Version::HTTP_2)
+            .status(200)
+            .header("content-type", "text/plain")
+            .body(())
+            .unwrap()
+            .into_parts()
+            .0;
+        let h2_rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+
+        let method = Method::GET;
+        let rsp = HttpForwardRemoteResponse::from_h2(&h2_rsp, &method, true);
+        assert_eq!(rsp.code, 200);
+        assert!(rsp.keep_alive());
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::Chunked));
+        let buf = rsp.serialize();
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert!(s.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(s.contains("transfer-encoding: chunked\r\n"));
+
+        let method = Method::HEAD;
+        let rsp = HttpForwardRemoteResponse::from_h2(&h2_rsp, &method, false);
+        assert!(!rsp.keep_alive());
+        assert_eq!(rsp.body_type(&method), None);
+        assert!(!rsp.serialize().windows(8).any(|w| w == b"chunked\r"));
+    }
 }
@@ -187,6 +187,8 @@ impl HttpTransparentResponse {
         method: &Method,
         keep_alive: bool,
         max_header_size: usize,
+        max_reason_len: usize,
+        allow_obs_fold: bool,
     ) -> Result<(Self, Bytes), HttpResponseParseError>
     where
         R: AsyncBufRead + Unpin,
@@ -207,9 +209,12 @@ impl HttpTransparentResponse {
             };
         }
 
-        let mut rsp = HttpTransparentResponse::build_from_status_line(head_bytes.as_ref())?;
+        let mut rsp =
+            HttpTransparentResponse::build_from_status_line(head_bytes.as_ref(), max_reason_len)?;
         rsp.keep_alive = keep_alive;
 
+        // the last header line, buffered to be unfolded if obs-fold is allowed
+        let mut unfolded_line: Option<Vec<u8>> = None;
         loop {
             let header_size = head_bytes.len();
             if header_size >= max_header_size {
@@ -237,7 +242,28 @@ impl HttpTransparentResponse {
                 // header end line
                 break;
             }
-            rsp.parse_header_line(line_buf)?;
+            if matches!(line_buf[0], b' ' | b'\t') {
+                // obs-fold, see RFC 9112 Section 5.2
+                if !allow_obs_fold {
+                    return Err(HttpResponseParseError::ObsFoldedHeader);
+                }
+                let Some(line) = unfolded_line.as_mut() else {
+                    return Err(HttpResponseParseError::ObsFoldedHeader);
+                };
+                line.push(b' ');
+                line.extend_from_slice(line_buf.trim_ascii());
+                continue;
+            }
+            if allow_obs_fold {
+                if let Some(line) = unfolded_line.replace(line_buf.trim_ascii_end().to_vec()) {
+                    rsp.parse_header_line(&line)?;
+                }
+            } else {
+                rsp.parse_header_line(line_buf)?;
+            }
+        }
+        if let Some(line) = unfolded_line {
+            rsp.parse_header_line(&line)?;
         }
 
         rsp.origin_header_size = head_bytes.len();
@@ -264,9 +290,15 @@ impl HttpTransparentResponse {
         // Don't move non-standard connection headers to hop-by-hop headers, as we don't support them
     }
 
-    fn build_from_status_line(line_buf: &[u8]) -> Result<Self, HttpResponseParseError> {
+    fn build_from_status_line(
+        line_buf: &[u8],
+        max_reason_len: usize,
+    ) -> Result<Self, HttpResponseParseError> {
         let rsp =
             HttpStatusLine::parse(line_buf).map_err(HttpResponseParseError::InvalidStatusLine)?;
+        if rsp.reason.len() > max_reason_len {
+            return Err(HttpResponseParseError::TooLongReason(max_reason_len));
+        }
         let version = match rsp.version {
             0 => Version::HTTP_10,
             1 => Version::HTTP_11,
@@ -430,9 +462,10 @@ mod tests {
         let stream = tokio_test::io::Builder::new().read(content).build();
         let mut buf_stream = BufReader::new(stream);
         let method = Method::GET;
-        let (rsp, data) = HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096)
-            .await
-            .unwrap();
+        let (rsp, data) =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, false)
+                .await
+                .unwrap();
         assert_eq!(data.as_ref(), content.as_slice());
         assert_eq!(rsp.code, 200);
         assert!(rsp.keep_alive());
@@ -448,12 +481,92 @@ mod tests {
         let stream = tokio_test::io::Builder::new().read(content).build();
         let mut buf_stream = BufReader::new(stream);
         let method = Method::GET;
-        let (rsp, data) = HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096)
-            .await
-            .unwrap();
+        let (rsp, data) =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, false)
+                .await
+                .unwrap();
         assert_eq!(data.as_ref(), content.as_slice());
         assert_eq!(rsp.code, 200);
         assert!(!rsp.keep_alive());
         assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ReadUntilEnd));
     }
+
+    #[tokio::test]
+    async fn read_obs_fold_rejected() {
+        let content = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: text/plain;\r\n charset=utf-8\r\n\
+            Content-Length: 4\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let method = Method::GET;
+        let r =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, false).await;
+        assert!(matches!(r, Err(HttpResponseParseError::ObsFoldedHeader)));
+
+        let content = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: text/plain\r\n\
+            \tContent-Length: 4\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let r =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, false).await;
+        assert!(matches!(r, Err(HttpResponseParseError::ObsFoldedHeader)));
+    }
+
+    #[tokio::test]
+    async fn read_obs_fold_unfolded() {
+        let content = b"HTTP/1.1 200 OK\r\n\
+            Content-Type: text/plain;\r\n  \t charset=utf-8\r\n\
+            X-Test: a\r\n\tb\r\n c\r\n\
+            Content-Length: 4\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let method = Method::GET;
+        let (rsp, data) =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, true)
+                .await
+                .unwrap();
+        assert_eq!(data.as_ref(), content.as_slice());
+        assert_eq!(
+            rsp.end_to_end_headers
+                .get(header::CONTENT_TYPE)
+                .unwrap()
+                .to_str(),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            rsp.end_to_end_headers.get("x-test").unwrap().to_str(),
+            "a b c"
+        );
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ContentLength(4)));
+
+        let content = b"HTTP/1.1 200 OK\r\n\
+            \tContent-Length: 4\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let r =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 256, true).await;
+        assert!(matches!(r, Err(HttpResponseParseError::ObsFoldedHeader)));
+    }
+
+    #[tokio::test]
+    async fn read_long_reason() {
+        let content = b"HTTP/1.1 200 This Is A Long Reason\r\n\
+            Content-Length: 4\r\n\r\n";
+        let method = Method::GET;
+
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let (rsp, _) =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 24, false)
+                .await
+                .unwrap();
+        assert_eq!(rsp.reason, "This Is A Long Reason");
+
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let r =
+            HttpTransparentResponse::parse(&mut buf_stream, &method, true, 4096, 16, false).await;
+        assert!(matches!(r, Err(HttpResponseParseError::TooLongReason(16))));
+    }
 }
@@ -0,0 +1,335 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io::Write;
+use std::str::FromStr;
+
+use bytes::BufMut;
+use http::response::Parts;
+use http::{HeaderMap, HeaderName, Method, Response, StatusCode, Version, header};
+
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+use super::{HttpAdaptedResponse, HttpResponseParseError};
+use crate::HttpBodyType;
+
+/// The transparent response head received from a HTTP/2 upstream.
+///
+/// The `:status` pseudo-header is stored in `status`, and it's the only pseudo-header
+/// allowed in HTTP/2 responses. Pseudo-headers will never be stored in the header maps.
+///
+/// The trailers are received after the body, and should be set by `set_trailers`.
+/// The adapted responses will have no trailers, as the body will be replaced.
+pub struct HttpTransparentH2Response {
+    pub status: StatusCode,
+    pub headers: HttpHeaderMap,
+    pub trailers: Option<HttpHeaderMap>,
+    content_length: Option<u64>,
+}
+
+fn is_connection_specific_header(name: &HeaderName) -> bool {
+    // see https://datatracker.ietf.org/doc/html/rfc9113#section-8.2.2
+    matches!(
+        name.as_str(),
+        "connection" | "proxy-connection" | "keep-alive" | "transfer-encoding" | "upgrade"
+    )
+}
+
+fn convert_header_map(map: &HeaderMap) -> Result<HttpHeaderMap, HttpResponseParseError> {
+    let mut headers = HttpHeaderMap::default();
+    for (name, value) in map {
+        if is_connection_specific_header(name) {
+            return Err(HttpResponseParseError::ConnectionSpecificHeader(
+                name.clone(),
+            ));
+        }
+        headers.append(name.clone(), HttpHeaderValue::from(value.clone()));
+    }
+    Ok(headers)
+}
+
+impl HttpTransparentH2Response {
+    pub fn parse(parts: &Parts) -> Result<Self, HttpResponseParseError> {
+        if parts.version != Version::HTTP_2 {
+            return Err(HttpResponseParseError::InvalidVersion(parts.version));
+        }
+
+        let mut content_length: Option<u64> = None;
+        for value in parts.headers.get_all(header::CONTENT_LENGTH) {
+            let len = value
+                .to_str()
+                .ok()
+                .and_then(|s| u64::from_str(s).ok())
+                .ok_or(HttpResponseParseError::InvalidContentLength)?;
+            if content_length.is_some_and(|v| v != len) {
+                return Err(HttpResponseParseError::InvalidContentLength);
+            }
+            content_length = Some(len);
+        }
+
+        let headers = convert_header_map(&parts.headers)?;
+        Ok(HttpTransparentH2Response {
+            status: parts.status,
+            headers,
+            trailers: None,
+            content_length,
+        })
+    }
+
+    pub fn set_trailers(&mut self, trailers: &HeaderMap) -> Result<(), HttpResponseParseError> {
+        let trailers = convert_header_map(trailers)?;
+        self.trailers = Some(trailers);
+        Ok(())
+    }
+
+    fn adapted_headers(adapted: HttpAdaptedResponse) -> HttpHeaderMap {
+        let mut headers = adapted.headers;
+        // the adapted response is in HTTP/1.x format
+        headers.remove(header::UPGRADE);
+        headers.remove(HeaderName::from_static("proxy-connection"));
+        headers
+    }
+
+    pub fn adapt_with_body(&self, adapted: HttpAdaptedResponse) -> Self {
+        let status = adapted.status;
+        let content_length = adapted.content_length;
+        HttpTransparentH2Response {
+            status,
+            headers: Self::adapted_headers(adapted),
+            trailers: None,
+            content_length,
+        }
+    }
+
+    pub fn adapt_without_body(&self, adapted: HttpAdaptedResponse) -> Self {
+        let status = adapted.status;
+        let mut headers = Self::adapted_headers(adapted);
+        if let Some(mut v) = headers.remove(header::CONTENT_LENGTH) {
+            v.set_static_value("0");
+            headers.insert(header::CONTENT_LENGTH, v);
+        } else {
+            headers.insert(header::CONTENT_LENGTH, HttpHeaderValue::from_static("0"));
+        }
+        HttpTransparentH2Response {
+            status,
+            headers,
+            trailers: None,
+            content_length: Some(0),
+        }
+    }
+
+    #[inline]
+    pub fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    fn expect_no_body(&self, method: &Method) -> bool {
+        self.status.is_informational()
+            || self.status == StatusCode::NO_CONTENT
+            || self.status == StatusCode::NOT_MODIFIED
+            || method.eq(&Method::HEAD)
+    }
+
+    /// Get the body type, `ReadUntilEnd` means the body ends with the stream
+    pub fn body_type(&self, method: &Method) -> Option<HttpBodyType> {
+        if self.expect_no_body(method) {
+            return None;
+        }
+        match self.content_length {
+            Some(0) => None,
+            Some(len) => Some(HttpBodyType::ContentLength(len)),
+            None => Some(HttpBodyType::ReadUntilEnd),
+        }
+    }
+
+    /// Serialize the head in HTTP/1.1 format, which will be sent to the ICAP server
+    pub fn serialize_for_adapter(&self) -> Vec<u8> {
+        let mut buf = Vec::<u8>::with_capacity(1024);
+
+        let reason = self
+            .status
+            .canonical_reason()
+            .unwrap_or("NOT STANDARD STATUS CODE");
+        let _ = write!(buf, "HTTP/1.1 {} {}\r\n", self.status.as_u16(), reason);
+
+        self.headers
+            .for_each(|name, value| value.write_to_buf(name, &mut buf));
+        buf.put_slice(b"\r\n");
+        buf
+    }
+
+    /// Build the response head to send to the HTTP/2 client
+    pub fn build_response(&self) -> Response<()> {
+        let mut rsp = Response::new(());
+        *rsp.version_mut() = Version::HTTP_2;
+        *rsp.status_mut() = self.status;
+        *rsp.headers_mut() = HeaderMap::from(&self.headers);
+        rsp
+    }
+
+    pub fn build_trailers(&self) -> Option<HeaderMap> {
+        self.trailers.as_ref().map(HeaderMap::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use tokio::io::BufReader;
+
+    fn h2_parts(status: StatusCode, headers: &[(&'static str, &'static str)]) -> Parts {
+        let mut builder = Response::builder().version(Version::HTTP_2).status(status);
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    async fn adapted(data: &[u8]) -> HttpAdaptedResponse {
+        let stream = tokio_test::io::Builder::new().read(data).build();
+        let mut reader = BufReader::new(stream);
+        HttpAdaptedResponse::parse(&mut reader, 4096).await.unwrap()
+    }
+
+    #[test]
+    fn parse_ok() {
+        let parts = h2_parts(
+            StatusCode::OK,
+            &[("content-type", "text/plain"), ("content-length", "4")],
+        );
+        let rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        assert_eq!(rsp.status, StatusCode::OK);
+        assert_eq!(rsp.content_length(), Some(4));
+        assert_eq!(
+            rsp.headers.get("content-type").unwrap().to_str(),
+            "text/plain"
+        );
+        assert!(rsp.trailers.is_none());
+        assert_eq!(
+            rsp.body_type(&Method::GET),
+            Some(HttpBodyType::ContentLength(4))
+        );
+        assert_eq!(rsp.body_type(&Method::HEAD), None);
+
+        let parts = h2_parts(StatusCode::OK, &[]);
+        let rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        assert_eq!(
+            rsp.body_type(&Method::GET),
+            Some(HttpBodyType::ReadUntilEnd)
+        );
+
+        let parts = h2_parts(StatusCode::NO_CONTENT, &[]);
+        let rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        assert_eq!(rsp.body_type(&Method::GET), None);
+    }
+
+    #[test]
+    fn parse_err() {
+        let mut parts = h2_parts(StatusCode::OK, &[]);
+        parts.version = Version::HTTP_11;
+        assert!(matches!(
+            HttpTransparentH2Response::parse(&parts),
+            Err(HttpResponseParseError::InvalidVersion(Version::HTTP_11))
+        ));
+
+        let parts = h2_parts(StatusCode::OK, &[("connection", "keep-alive")]);
+        assert!(matches!(
+            HttpTransparentH2Response::parse(&parts),
+            Err(HttpResponseParseError::ConnectionSpecificHeader(_))
+        ));
+
+        let parts = h2_parts(StatusCode::OK, &[("transfer-encoding", "chunked")]);
+        assert!(HttpTransparentH2Response::parse(&parts).is_err());
+
+        let parts = h2_parts(
+            StatusCode::OK,
+            &[("content-length", "4"), ("content-length", "5")],
+        );
+        assert!(matches!(
+            HttpTransparentH2Response::parse(&parts),
+            Err(HttpResponseParseError::InvalidContentLength)
+        ));
+
+        let parts = h2_parts(StatusCode::OK, &[("content-length", "abc")]);
+        assert!(HttpTransparentH2Response::parse(&parts).is_err());
+    }
+
+    #[test]
+    fn trailers() {
+        let parts = h2_parts(StatusCode::OK, &[("trailer", "grpc-status")]);
+        let mut rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        assert!(rsp.build_trailers().is_none());
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("grpc-status", HeaderValue::from_static("0"));
+        rsp.set_trailers(&trailers).unwrap();
+        let built = rsp.build_trailers().unwrap();
+        assert_eq!(built.get("grpc-status").unwrap(), "0");
+
+        let mut trailers = HeaderMap::new();
+        trailers.insert("keep-alive", HeaderValue::from_static("timeout=5"));
+        assert!(rsp.set_trailers(&trailers).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let parts = h2_parts(
+            StatusCode::NOT_FOUND,
+            &[("content-type", "text/plain"), ("content-length", "4")],
+        );
+        let rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        assert_eq!(
+            rsp.serialize_for_adapter(),
+            b"HTTP/1.1 404 Not Found\r\ncontent-type: text/plain\r\ncontent-length: 4\r\n\r\n"
+        );
+
+        let built = rsp.build_response();
+        assert_eq!(built.version(), Version::HTTP_2);
+        assert_eq!(built.status(), StatusCode::NOT_FOUND);
+        assert_eq!(built.headers().get("content-length").unwrap(), "4");
+    }
+
+    #[tokio::test]
+    async fn adapt() {
+        let parts = h2_parts(StatusCode::OK, &[("content-length", "4")]);
+        let mut rsp = HttpTransparentH2Response::parse(&parts).unwrap();
+        let mut trailers = HeaderMap::new();
+        trailers.insert("x-checksum", HeaderValue::from_static("abc"));
+        rsp.set_trailers(&trailers).unwrap();
+
+        let adapted_rsp = adapted(
+            b"HTTP/1.1 403 Forbidden\r\nContent-Length: 9\r\nConnection: close\r\nUpgrade: h2c\r\n\r\n",
+        )
+        .await;
+        let new_rsp = rsp.adapt_with_body(adapted_rsp);
+        assert_eq!(new_rsp.status, StatusCode::FORBIDDEN);
+        assert_eq!(new_rsp.content_length(), Some(9));
+        assert!(!new_rsp.headers.contains_key(header::CONNECTION));
+        assert!(!new_rsp.headers.contains_key(header::UPGRADE));
+        assert!(new_rsp.trailers.is_none());
+
+        let adapted_rsp = adapted(b"HTTP/1.1 200 OK\r\nX-Custom: value\r\n\r\n").await;
+        let new_rsp = rsp.adapt_with_body(adapted_rsp);
+        assert_eq!(new_rsp.content_length(), None);
+        assert_eq!(
+            new_rsp.body_type(&Method::GET),
+            Some(HttpBodyType::ReadUntilEnd)
+        );
+
+        let adapted_rsp = adapted(b"HTTP/1.1 200 OK\r\nContent-Length: 9\r\n\r\n").await;
+        let new_rsp = rsp.adapt_without_body(adapted_rsp);
+        assert_eq!(new_rsp.content_length(), Some(0));
+        assert_eq!(
+            new_rsp
+                .headers
+                .get(header::CONTENT_LENGTH)
+                .unwrap()
+                .to_str(),
+            "0"
+        );
+        assert_eq!(new_rsp.body_type(&Method::GET), None);
+    }
+}
@@ -12,7 +12,9 @@ use http::{HeaderName, Method, Uri, Version, header};
 use tokio::io::AsyncBufRead;
 
 use g3_io_ext::LimitedBufReadExt;
-use g3_types::net::{Host, HttpAuth, HttpHeaderMap, HttpHeaderValue, UpstreamAddr};
+use g3_types::net::{
+    Host, HttpAuth, HttpHeaderMap, HttpHeaderValue, HttpUpgradeToken, UpstreamAddr,
+};
 
 use super::{HttpAdaptedRequest, HttpRequestParseError};
 use crate::header::Connection;
@@ -359,11 +361,33 @@ impl HttpProxyClientRequest {
         Ok(())
     }
 
+    /// Save the upgrade header, which is not allowed by default
+    pub fn parse_header_upgrade(
+        &mut self,
+        name: HeaderName,
+        header: &HttpHeaderLine,
+    ) -> Result<(), HttpRequestParseError> {
+        self.insert_hop_by_hop_header(name, header)
+    }
+
+    pub fn has_upgrade_token(&self, token: &HttpUpgradeToken) -> bool {
+        self.hop_by_hop_headers
+            .get_all(header::UPGRADE)
+            .iter()
+            .flat_map(|v| v.to_str().split(','))
+            .filter_map(|s| HttpUpgradeToken::from_str(s.trim()).ok())
+            .any(|p| p.eq(token))
+    }
+
     pub fn append_parsed_header(
         &mut self,
         name: HeaderName,
         header: &HttpHeaderLine,
     ) -> Result<(), HttpRequestParseError> {
+        if name == header::UPGRADE {
+            // should be handled by the caller if supported
+            return Err(HttpRequestParseError::UpgradeIsNotSupported);
+        }
         let mut value = HttpHeaderValue::from_str(header.value).map_err(|_| {
             HttpRequestParseError::InvalidHeaderLine(HttpLineParseError::InvalidHeaderValue)
         })?;
@@ -418,10 +442,6 @@ impl HttpProxyClientRequest {
                 // hop-by-hop option, but let's pass it
                 return self.insert_hop_by_hop_header(name, &header);
             }
-            "upgrade" => {
-                // TODO we have no support for it right now
-                return Err(HttpRequestParseError::UpgradeIsNotSupported);
-            }
             "transfer-encoding" => {
                 // it's a hop-by-hop option, but we just pass it
                 self.has_transfer_encoding = true;
@@ -589,4 +609,42 @@ mod tests {
                 .unwrap();
         assert!(!request.keep_alive());
     }
+
+    #[tokio::test]
+    async fn upgrade_connect_udp() {
+        let content = b"GET /.well-known/masque/udp/192.0.2.6/443/ HTTP/1.1\r\n\
+            Host: proxy.example.org\r\n\
+            Connection: Upgrade\r\n\
+            Upgrade: connect-udp\r\n\
+            Capsule-Protocol: ?1\r\n\r\n";
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let mut version = Version::HTTP_11;
+        let request = HttpProxyClientRequest::parse(
+            &mut buf_stream,
+            4096,
+            &mut version,
+            |req, name, value| {
+                if name == header::UPGRADE {
+                    req.parse_header_upgrade(name, value)
+                } else {
+                    req.append_parsed_header(name, value)
+                }
+            },
+        )
+        .await
+        .unwrap();
+        assert!(request.has_upgrade_token(&HttpUpgradeToken::ConnectUdp));
+        assert!(!request.has_upgrade_token(&HttpUpgradeToken::Websocket));
+
+        let stream = tokio_test::io::Builder::new().read(content).build();
+        let mut buf_stream = BufReader::new(stream);
+        let result =
+            HttpProxyClientRequest::parse(&mut buf_stream, 4096, &mut version, parse_more_header)
+                .await;
+        assert!(matches!(
+            result,
+            Err(HttpRequestParseError::UpgradeIsNotSupported)
+        ));
+    }
 }
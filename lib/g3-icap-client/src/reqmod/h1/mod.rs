@@ -15,7 +15,7 @@ use tokio::time::Instant;
 use g3_http::server::HttpAdaptedRequest;
 use g3_http::{HttpBodyReader, HttpBodyType};
 use g3_io_ext::{IdleCheck, StreamCopyConfig};
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, TlsClientCertInfo};
 
 use super::IcapReqmodClient;
 use crate::{IcapClientConnection, IcapServiceClient, IcapServiceOptions};
@@ -72,6 +72,7 @@ impl IcapReqmodClient {
             idle_checker,
             client_addr: None,
             client_username: None,
+            client_cert: None,
         })
     }
 }
@@ -86,6 +87,7 @@ pub struct HttpRequestAdapter<I: IdleCheck> {
     idle_checker: I,
     client_addr: Option<SocketAddr>,
     client_username: Option<Arc<str>>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
 }
 
 pub struct ReqmodAdaptationRunState {
@@ -137,6 +139,10 @@ impl<I: IdleCheck> HttpRequestAdapter<I> {
         self.client_username = Some(user);
     }
 
+    pub fn set_client_cert(&mut self, cert: Arc<TlsClientCertInfo>) {
+        self.client_cert = Some(cert);
+    }
+
     fn push_extended_headers(&self, data: &mut Vec<u8>) {
         if let Some(addr) = self.client_addr {
             crate::serialize::add_client_addr(data, addr);
@@ -144,6 +150,9 @@ impl<I: IdleCheck> HttpRequestAdapter<I> {
         if let Some(user) = &self.client_username {
             crate::serialize::add_client_username(data, user);
         }
+        if let Some(cert) = &self.client_cert {
+            crate::serialize::add_client_cert(data, cert);
+        }
     }
 
     fn preview_size(&self) -> Option<usize> {
@@ -17,7 +17,7 @@ use tokio::time::Instant;
 use g3_h2::H2StreamFromChunkedTransfer;
 use g3_http::server::HttpAdaptedRequest;
 use g3_io_ext::{IdleCheck, StreamCopyConfig};
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, TlsClientCertInfo};
 
 use super::IcapReqmodClient;
 use crate::{IcapClientConnection, IcapClientReader, IcapServiceClient, IcapServiceOptions};
@@ -62,6 +62,7 @@ impl IcapReqmodClient {
             idle_checker,
             client_addr: None,
             client_username: None,
+            client_cert: None,
         })
     }
 }
@@ -78,6 +79,7 @@ pub struct H2RequestAdapter<I: IdleCheck> {
     idle_checker: I,
     client_addr: Option<SocketAddr>,
     client_username: Option<Arc<str>>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
 }
 
 pub struct ReqmodAdaptationRunState {
@@ -129,6 +131,10 @@ impl<I: IdleCheck> H2RequestAdapter<I> {
         self.client_username = Some(user);
     }
 
+    pub fn set_client_cert(&mut self, cert: Arc<TlsClientCertInfo>) {
+        self.client_cert = Some(cert);
+    }
+
     fn push_extended_headers(&self, data: &mut Vec<u8>, extensions: Option<&Extensions>) {
         data.put_slice(b"X-Transformed-From: HTTP/2.0\r\n");
         if let Some(addr) = self.client_addr {
@@ -137,6 +143,9 @@ impl<I: IdleCheck> H2RequestAdapter<I> {
         if let Some(user) = &self.client_username {
             crate::serialize::add_client_username(data, user);
         }
+        if let Some(cert) = &self.client_cert {
+            crate::serialize::add_client_cert(data, cert);
+        }
         if let Some(ext) = extensions
             && let Some(p) = ext.get::<Protocol>()
         {
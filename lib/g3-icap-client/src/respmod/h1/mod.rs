@@ -15,7 +15,7 @@ use tokio::time::Instant;
 use g3_http::HttpBodyType;
 use g3_http::client::HttpAdaptedResponse;
 use g3_io_ext::{IdleCheck, StreamCopyConfig};
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, TlsClientCertInfo};
 
 use super::IcapRespmodClient;
 use crate::reqmod::h1::HttpRequestForAdaptation;
@@ -66,6 +66,7 @@ impl IcapRespmodClient {
             idle_checker,
             client_addr: None,
             client_username: None,
+            client_cert: None,
             respond_shared_headers: None,
         })
     }
@@ -80,6 +81,7 @@ pub struct HttpResponseAdapter<I: IdleCheck> {
     idle_checker: I,
     client_addr: Option<SocketAddr>,
     client_username: Option<Arc<str>>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
     respond_shared_headers: Option<HttpHeaderMap>,
 }
 
@@ -146,6 +148,10 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
         self.client_username = Some(user);
     }
 
+    pub fn set_client_cert(&mut self, cert: Arc<TlsClientCertInfo>) {
+        self.client_cert = Some(cert);
+    }
+
     pub fn set_respond_shared_headers(&mut self, shared_headers: Option<HttpHeaderMap>) {
         self.respond_shared_headers = shared_headers;
     }
@@ -157,6 +163,9 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
         if let Some(user) = &self.client_username {
             crate::serialize::add_client_username(data, user);
         }
+        if let Some(cert) = &self.client_cert {
+            crate::serialize::add_client_cert(data, cert);
+        }
         if let Some(map) = &self.respond_shared_headers {
             crate::serialize::add_shared(data, map);
         }
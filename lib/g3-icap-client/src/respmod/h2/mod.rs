@@ -14,7 +14,7 @@ use tokio::time::Instant;
 
 use g3_http::client::HttpAdaptedResponse;
 use g3_io_ext::{IdleCheck, StreamCopyConfig};
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, TlsClientCertInfo};
 
 use super::IcapRespmodClient;
 use crate::{IcapClientConnection, IcapServiceClient, IcapServiceOptions};
@@ -61,6 +61,7 @@ impl IcapRespmodClient {
             idle_checker,
             client_addr: None,
             client_username: None,
+            client_cert: None,
             respond_shared_headers: None,
         })
     }
@@ -76,6 +77,7 @@ pub struct H2ResponseAdapter<I: IdleCheck> {
     idle_checker: I,
     client_addr: Option<SocketAddr>,
     client_username: Option<String>,
+    client_cert: Option<Arc<TlsClientCertInfo>>,
     respond_shared_headers: Option<HttpHeaderMap>,
 }
 
@@ -134,6 +136,10 @@ impl<I: IdleCheck> H2ResponseAdapter<I> {
         self.client_username = Some(user.to_string());
     }
 
+    pub fn set_client_cert(&mut self, cert: Arc<TlsClientCertInfo>) {
+        self.client_cert = Some(cert);
+    }
+
     pub fn set_respond_shared_headers(&mut self, shared_headers: Option<HttpHeaderMap>) {
         self.respond_shared_headers = shared_headers;
     }
@@ -146,6 +152,9 @@ impl<I: IdleCheck> H2ResponseAdapter<I> {
         if let Some(user) = &self.client_username {
             crate::serialize::add_client_username(data, user);
         }
+        if let Some(cert) = &self.client_cert {
+            crate::serialize::add_client_cert(data, cert);
+        }
         if let Some(map) = &self.respond_shared_headers {
             crate::serialize::add_shared(data, map);
         }
@@ -9,7 +9,7 @@ use std::net::SocketAddr;
 use base64::prelude::*;
 use bytes::BufMut;
 
-use g3_types::net::HttpHeaderMap;
+use g3_types::net::{HttpHeaderMap, TlsClientCertInfo};
 
 pub(crate) fn add_client_addr(buf: &mut Vec<u8>, addr: SocketAddr) {
     let _ = write!(buf, "X-Client-IP: {}\r\n", addr.ip());
@@ -27,6 +27,12 @@ pub(crate) fn add_client_username(buf: &mut Vec<u8>, user: &str) {
     buf.put_slice(b"\r\n");
 }
 
+pub(crate) fn add_client_cert(buf: &mut Vec<u8>, cert: &TlsClientCertInfo) {
+    let _ = write!(buf, "X-Client-Cert-Subject: {}\r\n", cert.subject());
+    let _ = write!(buf, "X-Client-Cert-Issuer: {}\r\n", cert.issuer());
+    let _ = write!(buf, "X-Client-Cert-Fingerprint: {}\r\n", cert.fingerprint());
+}
+
 pub(crate) fn add_shared(buf: &mut Vec<u8>, headers: &HttpHeaderMap) {
     headers.for_each(|name, value| {
         buf.put_slice(name.as_str().as_bytes());
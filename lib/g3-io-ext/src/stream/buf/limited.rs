@@ -146,6 +146,11 @@ where
         self.inner.into_inner()
     }
 
+    /// Returns a reference to the internally buffered data
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+
     fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut LimitedReader<R>> {
         self.project().inner
     }
@@ -0,0 +1,192 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, ready};
+
+use bytes::Bytes;
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+use tokio::sync::mpsc;
+
+pub trait StreamMirrorStats {
+    /// bytes that have been written to the mirror destination
+    fn add_mirrored_bytes(&self, size: usize);
+    /// bytes that have been dropped as the mirror is slow or failed
+    fn add_dropped_bytes(&self, size: usize);
+}
+pub type ArcStreamMirrorStats = Arc<dyn StreamMirrorStats + Send + Sync>;
+
+pub struct StreamMirrorReceiver {
+    receiver: mpsc::Receiver<Bytes>,
+    stats: ArcStreamMirrorStats,
+}
+
+impl StreamMirrorReceiver {
+    /// Write all received data to the mirror destination.
+    ///
+    /// The remaining data will be dropped if write failed.
+    pub async fn run_to<W>(mut self, mut writer: W) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        while let Some(data) = self.receiver.recv().await {
+            writer.write_all(&data).await?;
+            self.stats.add_mirrored_bytes(data.len());
+        }
+        writer.shutdown().await
+    }
+}
+
+impl Drop for StreamMirrorReceiver {
+    fn drop(&mut self) {
+        self.receiver.close();
+        while let Ok(data) = self.receiver.try_recv() {
+            self.stats.add_dropped_bytes(data.len());
+        }
+    }
+}
+
+pin_project! {
+    /// Send a copy of all data written to the inner writer to the mirror receiver.
+    ///
+    /// The mirror will be stopped if the mirror queue is full or the receiver is gone,
+    /// so the inner writer will never be blocked by the mirror. All data written after
+    /// that will be dropped, as the mirror stream would be corrupted if it has a gap.
+    pub struct StreamMirrorWriter<W> {
+        #[pin]
+        inner: W,
+        sender: Option<mpsc::Sender<Bytes>>,
+        stats: ArcStreamMirrorStats,
+    }
+}
+
+impl<W> StreamMirrorWriter<W> {
+    /// Create a new mirror writer, the queue size is the max number of pending write chunks
+    pub fn new(
+        inner: W,
+        queue_size: usize,
+        stats: ArcStreamMirrorStats,
+    ) -> (Self, StreamMirrorReceiver) {
+        let (sender, receiver) = mpsc::channel(queue_size.max(1));
+        let writer = StreamMirrorWriter {
+            inner,
+            sender: Some(sender),
+            stats: stats.clone(),
+        };
+        (writer, StreamMirrorReceiver { receiver, stats })
+    }
+}
+
+impl<W: AsyncWrite> AsyncWrite for StreamMirrorWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let nw = ready!(this.inner.poll_write(cx, buf))?;
+        if nw > 0 {
+            if let Some(sender) = this.sender {
+                if sender.try_send(Bytes::copy_from_slice(&buf[..nw])).is_err() {
+                    // stop the mirror on both full and closed
+                    this.stats.add_dropped_bytes(nw);
+                    *this.sender = None;
+                }
+            } else {
+                this.stats.add_dropped_bytes(nw);
+            }
+        }
+        Poll::Ready(Ok(nw))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        ready!(this.inner.poll_shutdown(cx))?;
+        // let the mirror receiver finish
+        *this.sender = None;
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountStats {
+        mirrored: AtomicUsize,
+        dropped: AtomicUsize,
+    }
+
+    impl StreamMirrorStats for CountStats {
+        fn add_mirrored_bytes(&self, size: usize) {
+            self.mirrored.fetch_add(size, Ordering::Relaxed);
+        }
+
+        fn add_dropped_bytes(&self, size: usize) {
+            self.dropped.fetch_add(size, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn mirror_all() {
+        let stats = Arc::new(CountStats::default());
+        let (mut writer, receiver) = StreamMirrorWriter::new(Vec::new(), 16, stats.clone());
+        writer.write_all(b"hello ").await.unwrap();
+        writer.write_all(b"world").await.unwrap();
+        writer.shutdown().await.unwrap();
+        assert_eq!(writer.inner.as_slice(), b"hello world");
+
+        let mut mirror = Vec::new();
+        receiver.run_to(&mut mirror).await.unwrap();
+        assert_eq!(mirror.as_slice(), b"hello world");
+        assert_eq!(stats.mirrored.load(Ordering::Relaxed), 11);
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn drop_on_full() {
+        let stats = Arc::new(CountStats::default());
+        let (mut writer, receiver) = StreamMirrorWriter::new(Vec::new(), 1, stats.clone());
+        writer.write_all(b"hello").await.unwrap();
+        writer.write_all(b"world").await.unwrap();
+        assert_eq!(writer.inner.as_slice(), b"helloworld");
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 5);
+        assert!(writer.sender.is_none());
+
+        // the mirror has been stopped, no more data even if the queue is drained
+        writer.write_all(b"!").await.unwrap();
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 6);
+
+        let mut mirror = Vec::new();
+        receiver.run_to(&mut mirror).await.unwrap();
+        assert_eq!(mirror.as_slice(), b"hello");
+        assert_eq!(stats.mirrored.load(Ordering::Relaxed), 5);
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 6);
+    }
+
+    #[tokio::test]
+    async fn drop_on_closed() {
+        let stats = Arc::new(CountStats::default());
+        let (mut writer, receiver) = StreamMirrorWriter::new(Vec::new(), 16, stats.clone());
+        writer.write_all(b"hello").await.unwrap();
+        drop(receiver);
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 5);
+
+        writer.write_all(b"world").await.unwrap();
+        assert_eq!(writer.inner.as_slice(), b"helloworld");
+        assert_eq!(stats.dropped.load(Ordering::Relaxed), 10);
+        assert_eq!(stats.mirrored.load(Ordering::Relaxed), 0);
+        assert!(writer.sender.is_none());
+    }
+}
@@ -12,6 +12,16 @@ pub use limited::*;
 mod copy;
 pub use copy::{ROwnedStreamCopy, StreamCopy, StreamCopyConfig, StreamCopyError};
 
+mod mirror;
+pub use mirror::{
+    ArcStreamMirrorStats, StreamMirrorReceiver, StreamMirrorStats, StreamMirrorWriter,
+};
+
+#[cfg(target_os = "linux")]
+mod splice;
+#[cfg(target_os = "linux")]
+pub use splice::SpliceCopy;
+
 mod buf;
 pub use buf::{BufReadCopy, FlexBufReader, LimitedBufReader, OnceBufReader};
 
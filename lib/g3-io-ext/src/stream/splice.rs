@@ -0,0 +1,342 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use tokio::io::Interest;
+use tokio::net::TcpStream;
+
+use g3_io_sys::splice::SplicePipe;
+
+use crate::{ArcLimitedReaderStats, ArcLimitedWriterStats, StreamCopyConfig, StreamCopyError};
+
+/// Zero-copy data transfer between two tcp sockets by using splice(2).
+///
+/// The write direction of the writer socket will be shutdown when EOF is received from the reader.
+pub struct SpliceCopy<'a> {
+    reader: &'a TcpStream,
+    writer: &'a TcpStream,
+    reader_stats: ArcLimitedReaderStats,
+    writer_stats: ArcLimitedWriterStats,
+    pipe: SplicePipe,
+    pipe_size: usize,
+    yield_size: usize,
+    in_pipe: usize,
+    read_done: bool,
+    total_read: u64,
+    total_write: u64,
+    active: bool,
+}
+
+impl<'a> SpliceCopy<'a> {
+    pub fn new(
+        reader: &'a TcpStream,
+        writer: &'a TcpStream,
+        config: &StreamCopyConfig,
+        reader_stats: ArcLimitedReaderStats,
+        writer_stats: ArcLimitedWriterStats,
+    ) -> io::Result<Self> {
+        let pipe = SplicePipe::new()?;
+        // the kernel may round up or refuse the size, use the real one if failed
+        let pipe_size = match pipe.set_size(config.buffer_size()) {
+            Ok(size) => size,
+            Err(_) => pipe.size()?,
+        };
+        Ok(SpliceCopy {
+            reader,
+            writer,
+            reader_stats,
+            writer_stats,
+            pipe,
+            pipe_size,
+            yield_size: config.yield_size(),
+            in_pipe: 0,
+            read_done: false,
+            total_read: 0,
+            total_write: 0,
+            active: false,
+        })
+    }
+
+    #[inline]
+    pub fn finished(&self) -> bool {
+        self.read_done && self.in_pipe == 0
+    }
+
+    #[inline]
+    pub fn read_size(&self) -> u64 {
+        self.total_read
+    }
+
+    #[inline]
+    pub fn copied_size(&self) -> u64 {
+        self.total_write
+    }
+
+    /// The real size of the pipe buffer allocated in kernel
+    #[inline]
+    pub fn pipe_size(&self) -> usize {
+        self.pipe_size
+    }
+
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    #[inline]
+    pub fn is_idle(&self) -> bool {
+        !self.active
+    }
+
+    #[inline]
+    pub fn reset_active(&mut self) {
+        self.active = false;
+    }
+
+    fn poll_splice_in(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        loop {
+            ready!(self.reader.poll_read_ready(cx))?;
+            let len = self.pipe_size - self.in_pipe;
+            if self.in_pipe > 0 {
+                // EAGAIN will also be returned if the pipe is full, which may happen before
+                // reaching the pipe size as the pipe buffers may not be fully filled, so we
+                // should keep the read readiness and drain the pipe first
+                return match self.pipe.splice_in(self.reader, len) {
+                    Ok(n) => Poll::Ready(Ok(n)),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => Poll::Pending,
+                    Err(e) => Poll::Ready(Err(e)),
+                };
+            }
+            match self
+                .reader
+                .try_io(Interest::READABLE, || self.pipe.splice_in(self.reader, len))
+            {
+                Ok(n) => return Poll::Ready(Ok(n)),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+
+    fn poll_splice_out(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        loop {
+            ready!(self.writer.poll_write_ready(cx))?;
+            let len = self.in_pipe;
+            match self.writer.try_io(Interest::WRITABLE, || {
+                self.pipe.splice_out(self.writer, len)
+            }) {
+                Ok(0) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "write zero byte into writer",
+                    )));
+                }
+                Ok(n) => return Poll::Ready(Ok(n)),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+        }
+    }
+
+    fn poll_copy(&mut self, cx: &mut Context<'_>) -> Poll<Result<u64, StreamCopyError>> {
+        let mut copy_this_round = 0usize;
+        loop {
+            let mut progressed = false;
+
+            if !self.read_done && self.in_pipe < self.pipe_size {
+                match self.poll_splice_in(cx) {
+                    Poll::Ready(Ok(0)) => {
+                        self.read_done = true;
+                        progressed = true;
+                    }
+                    Poll::Ready(Ok(n)) => {
+                        self.in_pipe += n;
+                        self.total_read += n as u64;
+                        self.reader_stats.add_read_bytes(n);
+                        self.active = true;
+                        progressed = true;
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(StreamCopyError::ReadFailed(e))),
+                    Poll::Pending => {}
+                }
+            }
+
+            if self.in_pipe > 0 {
+                match self.poll_splice_out(cx) {
+                    Poll::Ready(Ok(n)) => {
+                        self.in_pipe -= n;
+                        self.total_write += n as u64;
+                        self.writer_stats.add_write_bytes(n);
+                        self.active = true;
+                        progressed = true;
+                        copy_this_round += n;
+                    }
+                    Poll::Ready(Err(e)) => {
+                        return Poll::Ready(Err(StreamCopyError::WriteFailed(e)));
+                    }
+                    Poll::Pending => {}
+                }
+            } else if self.read_done {
+                g3_io_sys::splice::shutdown_write(self.writer)
+                    .map_err(StreamCopyError::WriteFailed)?;
+                return Poll::Ready(Ok(self.total_write));
+            }
+
+            if !progressed {
+                return Poll::Pending;
+            }
+
+            // yield if we have copy too much
+            if copy_this_round >= self.yield_size {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl Future for SpliceCopy<'_> {
+    type Output = Result<u64, StreamCopyError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.poll_copy(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpSocket};
+
+    use crate::{LimitedReaderStats, LimitedWriterStats};
+
+    #[derive(Default)]
+    struct CountStats {
+        read: AtomicUsize,
+        write: AtomicUsize,
+    }
+
+    impl LimitedReaderStats for CountStats {
+        fn add_read_bytes(&self, size: usize) {
+            self.read.fetch_add(size, Ordering::Relaxed);
+        }
+    }
+
+    impl LimitedWriterStats for CountStats {
+        fn add_write_bytes(&self, size: usize) {
+            self.write.fetch_add(size, Ordering::Relaxed);
+        }
+    }
+
+    async fn tcp_pair() -> (TcpStream, TcpStream) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn splice_copy() {
+        let (mut client, server) = tcp_pair().await;
+        let (upstream, mut peer) = tcp_pair().await;
+
+        let stats = Arc::new(CountStats::default());
+        let copy = SpliceCopy::new(
+            &server,
+            &upstream,
+            &StreamCopyConfig::default(),
+            stats.clone(),
+            stats.clone(),
+        )
+        .unwrap();
+
+        let data = vec![0x5au8; 100 * 1024];
+        let send_data = data.clone();
+        let send = tokio::spawn(async move {
+            client.write_all(&send_data).await.unwrap();
+            client.shutdown().await.unwrap();
+        });
+
+        let nw = copy.await.unwrap();
+        assert_eq!(nw, data.len() as u64);
+        send.await.unwrap();
+
+        let mut buf = Vec::new();
+        peer.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf, data);
+        assert_eq!(stats.read.load(Ordering::Relaxed), data.len());
+        assert_eq!(stats.write.load(Ordering::Relaxed), data.len());
+    }
+
+    /// the client side has a small send buffer, and the server side has a small recv buffer
+    async fn small_buffer_tcp_pair() -> (TcpStream, TcpStream) {
+        let listen_socket = TcpSocket::new_v4().unwrap();
+        listen_socket.set_recv_buffer_size(4096).unwrap();
+        listen_socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+        let listener = listen_socket.listen(1).unwrap();
+
+        let socket = TcpSocket::new_v4().unwrap();
+        socket.set_send_buffer_size(4096).unwrap();
+        let client = socket
+            .connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        let (server, _) = listener.accept().await.unwrap();
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn splice_copy_pipe_full() {
+        let (mut client, server) = tcp_pair().await;
+        let (upstream, mut peer) = small_buffer_tcp_pair().await;
+
+        let stats = Arc::new(CountStats::default());
+        let mut copy = SpliceCopy::new(
+            &server,
+            &upstream,
+            &StreamCopyConfig::default(),
+            stats.clone(),
+            stats.clone(),
+        )
+        .unwrap();
+        // the pipe will be full before reaching the recorded pipe size
+        copy.pipe.set_size(4096).unwrap();
+        copy.pipe_size = 1 << 20;
+
+        // all data and the FIN will be received before the copy, so no more read readiness event
+        let data = (0..128 * 1024).map(|i| i as u8).collect::<Vec<u8>>();
+        client.write_all(&data).await.unwrap();
+        client.shutdown().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // the writer will be blocked until the peer starts reading
+        let recv = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let mut buf = Vec::new();
+            peer.read_to_end(&mut buf).await.unwrap();
+            buf
+        });
+
+        let nw = tokio::time::timeout(Duration::from_secs(10), copy)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(nw, data.len() as u64);
+        let buf = recv.await.unwrap();
+        assert_eq!(buf, data);
+        assert_eq!(stats.read.load(Ordering::Relaxed), data.len());
+        assert_eq!(stats.write.load(Ordering::Relaxed), data.len());
+    }
+}
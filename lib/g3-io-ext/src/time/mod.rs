@@ -6,5 +6,8 @@
 mod optional_interval;
 pub use optional_interval::OptionalInterval;
 
+mod optional_deadline;
+pub use optional_deadline::OptionalDeadline;
+
 mod idle;
 pub use idle::{IdleCheck, IdleForceQuitReason, IdleInterval, IdleWheel};
@@ -0,0 +1,50 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+#[derive(Clone, Copy, Default)]
+pub struct OptionalDeadline {
+    inner: Option<Instant>,
+}
+
+impl OptionalDeadline {
+    pub fn new(start: Instant, max_duration: Option<Duration>) -> Self {
+        OptionalDeadline {
+            inner: max_duration.and_then(|d| start.checked_add(d)),
+        }
+    }
+
+    pub async fn wait(self) {
+        match self.inner {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn never() {
+        let f = OptionalDeadline::new(Instant::now(), None);
+        let r = tokio::time::timeout(Duration::from_millis(10), f.wait()).await;
+        assert!(r.is_err());
+    }
+
+    #[tokio::test]
+    async fn normal() {
+        let f = OptionalDeadline::new(Instant::now(), Some(Duration::from_millis(8)));
+        let r = tokio::time::timeout(Duration::from_millis(10), f.wait()).await;
+        assert!(r.is_ok());
+
+        let r = tokio::time::timeout(Duration::from_millis(1), f.wait()).await;
+        assert!(r.is_ok());
+    }
+}
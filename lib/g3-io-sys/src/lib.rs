@@ -9,3 +9,6 @@ use addr::RawSocketAddr;
 pub mod ffi;
 
 pub mod udp;
+
+#[cfg(target_os = "linux")]
+pub mod splice;
@@ -0,0 +1,92 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::ptr;
+
+/// A nonblocking pipe used as the intermediate buffer for splice(2).
+pub struct SplicePipe {
+    read: OwnedFd,
+    write: OwnedFd,
+}
+
+impl SplicePipe {
+    pub fn new() -> io::Result<Self> {
+        let mut fds: [RawFd; 2] = [-1, -1];
+        let r = unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) };
+        if r < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(SplicePipe {
+            read: unsafe { OwnedFd::from_raw_fd(fds[0]) },
+            write: unsafe { OwnedFd::from_raw_fd(fds[1]) },
+        })
+    }
+
+    /// Set the pipe capacity, the real capacity will be returned
+    pub fn set_size(&self, size: usize) -> io::Result<usize> {
+        let r = unsafe {
+            libc::fcntl(
+                self.write.as_raw_fd(),
+                libc::F_SETPIPE_SZ,
+                size as libc::c_int,
+            )
+        };
+        if r < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(r as usize)
+        }
+    }
+
+    /// Get the pipe capacity
+    pub fn size(&self) -> io::Result<usize> {
+        let r = unsafe { libc::fcntl(self.write.as_raw_fd(), libc::F_GETPIPE_SZ) };
+        if r < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(r as usize)
+        }
+    }
+
+    /// Move data from the socket into the pipe
+    pub fn splice_in<T: AsRawFd>(&self, fd: &T, len: usize) -> io::Result<usize> {
+        splice(fd.as_raw_fd(), self.write.as_raw_fd(), len)
+    }
+
+    /// Move data from the pipe to the socket
+    pub fn splice_out<T: AsRawFd>(&self, fd: &T, len: usize) -> io::Result<usize> {
+        splice(self.read.as_raw_fd(), fd.as_raw_fd(), len)
+    }
+}
+
+fn splice(fd_in: RawFd, fd_out: RawFd, len: usize) -> io::Result<usize> {
+    let r = unsafe {
+        libc::splice(
+            fd_in,
+            ptr::null_mut(),
+            fd_out,
+            ptr::null_mut(),
+            len,
+            libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK,
+        )
+    };
+    if r < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(r as usize)
+    }
+}
+
+/// Shutdown the write direction of the socket
+pub fn shutdown_write<T: AsRawFd>(fd: &T) -> io::Result<()> {
+    let r = unsafe { libc::shutdown(fd.as_raw_fd(), libc::SHUT_WR) };
+    if r < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
@@ -46,7 +46,8 @@ pub use resolve::{as_resolve_redirection_builder, as_resolve_strategy};
 mod rustls;
 #[cfg(feature = "rustls")]
 pub use self::rustls::{
-    as_rustls_client_config_builder, as_rustls_server_config_builder, as_rustls_server_name,
+    as_rustls_client_auth_crl_config, as_rustls_client_config_builder, as_rustls_public_keys,
+    as_rustls_server_config_builder, as_rustls_server_name,
 };
 
 #[cfg(feature = "openssl")]
@@ -3,10 +3,13 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::str::FromStr;
+
 use anyhow::{Context, anyhow};
+use http::{HeaderName, HeaderValue};
 use serde_json::Value;
 
-use g3_types::net::HttpKeepAliveConfig;
+use g3_types::net::{HttpHeaderRule, HttpHeaderRuleAction, HttpHeaderRuleSet, HttpKeepAliveConfig};
 
 pub fn as_http_keepalive_config(v: &Value) -> anyhow::Result<HttpKeepAliveConfig> {
     let mut config = HttpKeepAliveConfig::default();
@@ -43,6 +46,60 @@ pub fn as_http_keepalive_config(v: &Value) -> anyhow::Result<HttpKeepAliveConfig
     Ok(config)
 }
 
+fn as_http_header_rule(v: &Value) -> anyhow::Result<HttpHeaderRule> {
+    let Value::Object(map) = v else {
+        return Err(anyhow!(
+            "json value type for 'HttpHeaderRule' should be 'map'"
+        ));
+    };
+
+    let mut action = None;
+    let mut name = None;
+    let mut value = None;
+    for (k, v) in map {
+        match crate::key::normalize(k).as_str() {
+            "action" => {
+                let s = crate::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                let a = HttpHeaderRuleAction::from_str(&s)
+                    .map_err(|_| anyhow!("invalid http header rule action {s}"))?;
+                action = Some(a);
+            }
+            "name" => {
+                let s = crate::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                let n = HeaderName::from_str(&s)
+                    .map_err(|e| anyhow!("invalid http header name {s}: {e}"))?;
+                name = Some(n);
+            }
+            "value" => {
+                let s = crate::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                let hv = HeaderValue::from_str(&s)
+                    .map_err(|e| anyhow!("invalid http header value string {s}: {e}"))?;
+                value = Some(hv);
+            }
+            _ => return Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    let Some(action) = action else {
+        return Err(anyhow!("no action set"));
+    };
+    let Some(name) = name else {
+        return Err(anyhow!("no header name set"));
+    };
+    HttpHeaderRule::new(action, name, value)
+}
+
+pub fn as_http_header_rule_set(v: &Value) -> anyhow::Result<HttpHeaderRuleSet> {
+    let mut set = HttpHeaderRuleSet::default();
+    for rule in crate::value::as_list(v, as_http_header_rule)? {
+        set.push(rule);
+    }
+    Ok(set)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -120,4 +177,37 @@ mod tests {
         let v = json!([1, 2, 3]);
         assert!(as_http_keepalive_config(&v).is_err());
     }
+
+    #[test]
+    fn as_http_header_rule_set_ok() {
+        let v = json!([
+            {"action": "remove", "name": "X-Tracking-Id"},
+            {"action": "add_if_absent", "name": "X-Forwarded-Proto", "value": "https"},
+        ]);
+        let set = as_http_header_rule_set(&v).unwrap();
+        let rules = set.rules();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].action(), HttpHeaderRuleAction::Remove);
+        assert_eq!(rules[1].action(), HttpHeaderRuleAction::Add);
+        assert_eq!(rules[1].name().as_str(), "x-forwarded-proto");
+
+        let v = json!({"action": "override", "name": "User-Agent", "value": "g3proxy"});
+        let set = as_http_header_rule_set(&v).unwrap();
+        assert_eq!(set.rules()[0].action(), HttpHeaderRuleAction::Set);
+    }
+
+    #[test]
+    fn as_http_header_rule_set_err() {
+        let v = json!({"action": "replace", "name": "X-A", "value": "a"});
+        assert!(as_http_header_rule_set(&v).is_err());
+
+        let v = json!({"action": "set", "name": "X-A"});
+        assert!(as_http_header_rule_set(&v).is_err());
+
+        let v = json!({"action": "remove", "name": "Transfer-Encoding"});
+        assert!(as_http_header_rule_set(&v).is_err());
+
+        let v = json!("X-A");
+        assert!(as_http_header_rule_set(&v).is_err());
+    }
 }
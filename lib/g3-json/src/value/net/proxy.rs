@@ -42,6 +42,9 @@ mod tests {
             ("httpconnect", ProxyRequestType::HttpConnect),
             ("HTTPConnect", ProxyRequestType::HttpConnect),
             ("http_connect", ProxyRequestType::HttpConnect),
+            ("httpconnectudp", ProxyRequestType::HttpConnectUdp),
+            ("HTTPConnectUDP", ProxyRequestType::HttpConnectUdp),
+            ("http_connect_udp", ProxyRequestType::HttpConnectUdp),
             ("sockstcpconnect", ProxyRequestType::SocksTcpConnect),
             ("SocksTCPConnect", ProxyRequestType::SocksTcpConnect),
             ("socks_tcp_connect", ProxyRequestType::SocksTcpConnect),
@@ -8,7 +8,7 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use serde_json::Value;
 
-use g3_types::net::TlsVersion;
+use g3_types::net::{AlpnProtocol, TlsVersion};
 
 pub fn as_tls_version(value: &Value) -> anyhow::Result<TlsVersion> {
     match value {
@@ -25,6 +25,16 @@ pub fn as_tls_version(value: &Value) -> anyhow::Result<TlsVersion> {
     }
 }
 
+pub fn as_alpn_protocol(value: &Value) -> anyhow::Result<AlpnProtocol> {
+    if let Value::String(s) = value {
+        AlpnProtocol::from_str(s)
+    } else {
+        Err(anyhow!(
+            "json value type for alpn protocol should be 'string'"
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,4 +110,20 @@ mod tests {
         assert!(as_tls_version(&json!({})).is_err());
         assert!(as_tls_version(&json!(null)).is_err());
     }
+
+    #[test]
+    fn as_alpn_protocol_ok() {
+        assert_eq!(
+            as_alpn_protocol(&json!("http/1.1")).unwrap(),
+            AlpnProtocol::Http11
+        );
+        assert_eq!(as_alpn_protocol(&json!("h2")).unwrap(), AlpnProtocol::Http2);
+    }
+
+    #[test]
+    fn as_alpn_protocol_err() {
+        assert!(as_alpn_protocol(&json!("h2c")).is_err());
+        assert!(as_alpn_protocol(&json!("")).is_err());
+        assert!(as_alpn_protocol(&json!(2)).is_err());
+    }
 }
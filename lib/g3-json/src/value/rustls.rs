@@ -3,14 +3,16 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::path::PathBuf;
+
 use anyhow::{Context, anyhow};
 use rustls_pki_types::pem::PemObject;
-use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName, SubjectPublicKeyInfoDer};
 use serde_json::Value;
 
 use g3_types::net::{
-    RustlsCertificatePair, RustlsCertificatePairBuilder, RustlsClientConfigBuilder,
-    RustlsServerConfigBuilder,
+    RustlsCertificatePair, RustlsCertificatePairBuilder, RustlsClientAuthCrlConfig,
+    RustlsClientConfigBuilder, RustlsRawPublicKey, RustlsServerConfigBuilder,
 };
 
 pub fn as_rustls_server_name(value: &Value) -> anyhow::Result<ServerName<'static>> {
@@ -69,6 +71,42 @@ pub fn as_rustls_private_key(value: &Value) -> anyhow::Result<PrivateKeyDer<'sta
     }
 }
 
+fn as_public_keys_from_single_element(
+    value: &Value,
+) -> anyhow::Result<Vec<SubjectPublicKeyInfoDer<'static>>> {
+    if let Value::String(s) = value {
+        let mut keys = Vec::new();
+        for (i, r) in SubjectPublicKeyInfoDer::pem_slice_iter(s.as_bytes()).enumerate() {
+            let key = r.map_err(|e| anyhow!("invalid public key #{i}: {e:?}"))?;
+            keys.push(key);
+        }
+        if keys.is_empty() {
+            Err(anyhow!("no valid public key found"))
+        } else {
+            Ok(keys)
+        }
+    } else {
+        Err(anyhow!("json value type 'public keys' should be 'string'"))
+    }
+}
+
+/// Parse the PEM encoded RFC 7250 raw public keys
+pub fn as_rustls_public_keys(
+    value: &Value,
+) -> anyhow::Result<Vec<SubjectPublicKeyInfoDer<'static>>> {
+    if let Value::Array(seq) = value {
+        let mut keys = Vec::new();
+        for (i, v) in seq.iter().enumerate() {
+            let this_keys = as_public_keys_from_single_element(v)
+                .context(format!("invalid public keys value for element #{i}"))?;
+            keys.extend(this_keys);
+        }
+        Ok(keys)
+    } else {
+        as_public_keys_from_single_element(value)
+    }
+}
+
 pub fn as_rustls_certificate_pair(value: &Value) -> anyhow::Result<RustlsCertificatePair> {
     if let Value::Object(map) = value {
         let mut pair_builder = RustlsCertificatePairBuilder::default();
@@ -95,6 +133,56 @@ pub fn as_rustls_certificate_pair(value: &Value) -> anyhow::Result<RustlsCertifi
     }
 }
 
+fn as_crl_file_path(value: &Value) -> anyhow::Result<PathBuf> {
+    if let Value::String(s) = value {
+        let path = PathBuf::from(s);
+        if path.is_relative() {
+            return Err(anyhow!("{s} is not an absolute path"));
+        }
+        Ok(path)
+    } else {
+        Err(anyhow!(
+            "json value type for 'file path' should be 'string'"
+        ))
+    }
+}
+
+pub fn as_rustls_client_auth_crl_config(
+    value: &Value,
+) -> anyhow::Result<RustlsClientAuthCrlConfig> {
+    let config = if let Value::Object(map) = value {
+        let mut files = Vec::new();
+        let mut refresh_interval = None;
+        for (k, v) in map {
+            match crate::key::normalize(k).as_str() {
+                "file" | "files" => {
+                    files = crate::value::as_list(v, as_crl_file_path)
+                        .context(format!("invalid file path list value for key {k}"))?;
+                }
+                "refresh_interval" => {
+                    let interval = crate::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    refresh_interval = Some(interval);
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        let mut config = RustlsClientAuthCrlConfig::new(files);
+        if let Some(interval) = refresh_interval {
+            config.set_refresh_interval(interval);
+        }
+        config
+    } else {
+        let files = crate::value::as_list(value, as_crl_file_path)
+            .context("invalid file path list value")?;
+        RustlsClientAuthCrlConfig::new(files)
+    };
+    config.check()?;
+    // make sure the files are valid at config load time
+    config.load()?;
+    Ok(config)
+}
+
 pub fn as_rustls_client_config_builder(value: &Value) -> anyhow::Result<RustlsClientConfigBuilder> {
     if let Value::Object(map) = value {
         let mut builder = RustlsClientConfigBuilder::default();
@@ -136,6 +224,16 @@ pub fn as_rustls_client_config_builder(value: &Value) -> anyhow::Result<RustlsCl
                         .context(format!("invalid cert pair value for key {k}"))?;
                     builder.set_cert_pair(pair);
                 }
+                "raw_public_key" => {
+                    let key = as_rustls_private_key(v)
+                        .context(format!("invalid private key value for key {k}"))?;
+                    builder.set_raw_public_key(RustlsRawPublicKey::new(key));
+                }
+                "server_raw_public_key" | "server_raw_public_keys" => {
+                    let keys = as_rustls_public_keys(v)
+                        .context(format!("invalid public keys value for key {k}"))?;
+                    builder.set_server_raw_public_keys(keys);
+                }
                 "ca_certificate" | "ca_cert" | "server_auth_certificate" | "server_auth_cert" => {
                     let certs = as_rustls_certificates(v)
                         .context(format!("invalid certificates value for key {k}"))?;
@@ -216,6 +314,13 @@ pub fn as_rustls_server_config_builder(value: &Value) -> anyhow::Result<RustlsSe
                         builder.enable_client_auth();
                     }
                 }
+                "enable_optional_client_auth" => {
+                    let enable =
+                        crate::value::as_bool(v).context(format!("invalid value for key {k}"))?;
+                    if enable {
+                        builder.enable_optional_client_auth();
+                    }
+                }
                 "use_session_ticket" => {
                     let enable =
                         crate::value::as_bool(v).context(format!("invalid value for key {k}"))?;
@@ -231,11 +336,51 @@ pub fn as_rustls_server_config_builder(value: &Value) -> anyhow::Result<RustlsSe
                         crate::value::as_bool(v).context(format!("invalid value for key {k}"))?;
                     builder.set_disable_session_cache(disable);
                 }
+                "session_ticket_only" => {
+                    let enable =
+                        crate::value::as_bool(v).context(format!("invalid value for key {k}"))?;
+                    builder.set_session_ticket_only(enable);
+                }
+                "shared_session_cache" => {
+                    let name =
+                        crate::value::as_string(v).context(format!("invalid value for key {k}"))?;
+                    builder.set_shared_session_cache(name);
+                }
+                "alpn" | "alpn_protocols" => {
+                    let protocols = crate::value::as_list(v, crate::value::as_alpn_protocol)
+                        .context(format!("invalid alpn protocol list value for key {k}"))?;
+                    builder.set_alpn_protocols(protocols);
+                }
                 "ca_certificate" | "ca_cert" | "client_auth_certificate" | "client_auth_cert" => {
                     let certs =
                         as_rustls_certificates(v).context(format!("invalid value for key {k}"))?;
                     builder.set_client_auth_certificates(certs);
                 }
+                "raw_public_key" => {
+                    let key = as_rustls_private_key(v)
+                        .context(format!("invalid private key value for key {k}"))?;
+                    builder.set_raw_public_key(RustlsRawPublicKey::new(key));
+                }
+                "client_auth_raw_public_key" | "client_auth_raw_public_keys" => {
+                    let keys = as_rustls_public_keys(v)
+                        .context(format!("invalid public keys value for key {k}"))?;
+                    builder.set_client_auth_raw_public_keys(keys);
+                }
+                "fallback_cert_pair" => {
+                    let pair = as_rustls_certificate_pair(v)
+                        .context(format!("invalid rustls cert pair value for key {k}"))?;
+                    builder.set_fallback_cert_pair(pair);
+                }
+                "log_cert_selection" => {
+                    let enable =
+                        crate::value::as_bool(v).context(format!("invalid value for key {k}"))?;
+                    builder.set_log_cert_selection(enable);
+                }
+                "client_auth_crl" | "ca_crl" => {
+                    let crl = as_rustls_client_auth_crl_config(v)
+                        .context(format!("invalid client auth crl config value for key {k}"))?;
+                    builder.set_client_auth_crl(crl);
+                }
                 "handshake_timeout" | "negotiation_timeout" | "accept_timeout" => {
                     let timeout = crate::humanize::as_duration(v)
                         .context(format!("invalid humanize duration value for key {k}"))?;
@@ -261,12 +406,16 @@ pub fn as_rustls_server_config_builder(value: &Value) -> anyhow::Result<RustlsSe
 #[cfg(test)]
 mod tests {
     use super::*;
+    use g3_types::net::AlpnProtocol;
     use serde_json::json;
     use std::time::Duration;
 
     const TEST_CERT1_PEM: &str = include_str!("test_data/test_cert1.pem");
     const TEST_CERT2_PEM: &str = include_str!("test_data/test_cert2.pem");
     const TEST_KEY1_PEM: &str = include_str!("test_data/test_key1.pem");
+    const TEST_CRL_PEM: &str = include_str!("test_data/test_crl.pem");
+    const TEST_PUBKEY1_PEM: &str = include_str!("test_data/test_pubkey1.pem");
+    const TEST_RPK_KEY1_PEM: &str = include_str!("test_data/test_rpk_key1.pem");
 
     #[test]
     fn as_rustls_server_name_ok() {
@@ -488,6 +637,86 @@ mod tests {
         expected.push_cert_pair(pair_builder.build().unwrap());
         expected.set_disable_session_ticket(true);
         assert_eq!(builder, expected);
+
+        // Session resumption fields
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "shared_session_cache": "site",
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        let mut pair_builder = RustlsCertificatePairBuilder::default();
+        pair_builder.set_certs(as_rustls_certificates(&json!(TEST_CERT1_PEM)).unwrap());
+        pair_builder.set_key(as_rustls_private_key(&json!(TEST_KEY1_PEM)).unwrap());
+        expected.push_cert_pair(pair_builder.build().unwrap());
+        expected.set_shared_session_cache("site".to_string());
+        assert_eq!(builder, expected);
+
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "session_ticket_only": true,
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        let mut pair_builder = RustlsCertificatePairBuilder::default();
+        pair_builder.set_certs(as_rustls_certificates(&json!(TEST_CERT1_PEM)).unwrap());
+        pair_builder.set_key(as_rustls_private_key(&json!(TEST_KEY1_PEM)).unwrap());
+        expected.push_cert_pair(pair_builder.build().unwrap());
+        expected.set_session_ticket_only(true);
+        assert_eq!(builder, expected);
+
+        // ALPN protocols
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "alpn": ["h2", "http/1.1"],
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        let mut pair_builder = RustlsCertificatePairBuilder::default();
+        pair_builder.set_certs(as_rustls_certificates(&json!(TEST_CERT1_PEM)).unwrap());
+        pair_builder.set_key(as_rustls_private_key(&json!(TEST_KEY1_PEM)).unwrap());
+        expected.push_cert_pair(pair_builder.build().unwrap());
+        expected.set_alpn_protocols(vec![AlpnProtocol::Http2, AlpnProtocol::Http11]);
+        assert_eq!(builder, expected);
+
+        // Optional client auth
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "enable_optional_client_auth": true,
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        let mut pair_builder = RustlsCertificatePairBuilder::default();
+        pair_builder.set_certs(as_rustls_certificates(&json!(TEST_CERT1_PEM)).unwrap());
+        pair_builder.set_key(as_rustls_private_key(&json!(TEST_KEY1_PEM)).unwrap());
+        expected.push_cert_pair(pair_builder.build().unwrap());
+        expected.enable_optional_client_auth();
+        assert_eq!(builder, expected);
+
+        // Fallback cert pair
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "fallback_cert_pair": {
+                "certificate": TEST_CERT1_PEM,
+                "private_key": TEST_KEY1_PEM,
+            },
+            "log_cert_selection": true,
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        let mut pair_builder = RustlsCertificatePairBuilder::default();
+        pair_builder.set_certs(as_rustls_certificates(&json!(TEST_CERT1_PEM)).unwrap());
+        pair_builder.set_key(as_rustls_private_key(&json!(TEST_KEY1_PEM)).unwrap());
+        let pair = pair_builder.build().unwrap();
+        expected.push_cert_pair(pair.clone());
+        expected.set_fallback_cert_pair(pair);
+        expected.set_log_cert_selection(true);
+        assert_eq!(builder, expected);
     }
 
     #[test]
@@ -501,5 +730,146 @@ mod tests {
         // Invalid value type
         let value = json!("invalid");
         assert!(as_rustls_server_config_builder(&value).is_err());
+
+        // Conflict session resumption fields
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "session_ticket_only": true,
+            "no_session_ticket": true,
+        });
+        assert!(as_rustls_server_config_builder(&value).is_err());
+
+        let value = json!({
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+            "shared_session_cache": "site",
+            "no_session_cache": true,
+        });
+        assert!(as_rustls_server_config_builder(&value).is_err());
+
+        // Invalid ALPN protocols
+        for alpn in [json!(["h2", "spdy/3"]), json!([]), json!(["h2", "h2"])] {
+            let value = json!({
+                "certificate": TEST_CERT1_PEM,
+                "private_key": TEST_KEY1_PEM,
+                "alpn": alpn,
+            });
+            assert!(as_rustls_server_config_builder(&value).is_err());
+        }
+    }
+
+    #[test]
+    fn as_rustls_client_auth_crl_config_ok() {
+        let dir = std::env::temp_dir().join(format!("g3-json-crl-ok-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let crl_path = dir.join("test_crl.pem");
+        std::fs::write(&crl_path, TEST_CRL_PEM).unwrap();
+
+        let value = json!(crl_path);
+        let config = as_rustls_client_auth_crl_config(&value).unwrap();
+        assert_eq!(
+            config,
+            RustlsClientAuthCrlConfig::new(vec![crl_path.clone()])
+        );
+
+        let value = json!({
+            "files": [crl_path],
+            "refresh_interval": "10m",
+        });
+        let config = as_rustls_client_auth_crl_config(&value).unwrap();
+        let mut expected = RustlsClientAuthCrlConfig::new(vec![crl_path]);
+        expected.set_refresh_interval(Duration::from_secs(600));
+        assert_eq!(config, expected);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn as_rustls_client_auth_crl_config_err() {
+        // relative path
+        let value = json!("test_crl.pem");
+        assert!(as_rustls_client_auth_crl_config(&value).is_err());
+
+        // not existed file
+        let value = json!("/not/existed/test_crl.pem");
+        assert!(as_rustls_client_auth_crl_config(&value).is_err());
+
+        // no file
+        let value = json!({"refresh_interval": "10m"});
+        assert!(as_rustls_client_auth_crl_config(&value).is_err());
+
+        // invalid type
+        let value = json!(1);
+        assert!(as_rustls_client_auth_crl_config(&value).is_err());
+    }
+
+    #[test]
+    fn as_rustls_public_keys_ok() {
+        let expected =
+            SubjectPublicKeyInfoDer::from_pem_slice(TEST_PUBKEY1_PEM.as_bytes()).unwrap();
+
+        let keys = as_rustls_public_keys(&json!(TEST_PUBKEY1_PEM)).unwrap();
+        assert_eq!(keys, vec![expected.clone()]);
+
+        let keys = as_rustls_public_keys(&json!([TEST_PUBKEY1_PEM, TEST_PUBKEY1_PEM])).unwrap();
+        assert_eq!(keys, vec![expected.clone(), expected]);
+    }
+
+    #[test]
+    fn as_rustls_public_keys_err() {
+        assert!(as_rustls_public_keys(&json!("invalid key")).is_err());
+        assert!(as_rustls_public_keys(&json!(TEST_CERT1_PEM)).is_err());
+        assert!(as_rustls_public_keys(&json!(1)).is_err());
+    }
+
+    #[test]
+    fn as_rustls_raw_public_key_config() {
+        let public_key =
+            SubjectPublicKeyInfoDer::from_pem_slice(TEST_PUBKEY1_PEM.as_bytes()).unwrap();
+        let raw_public_key = RustlsRawPublicKey::new(
+            PrivateKeyDer::from_pem_slice(TEST_RPK_KEY1_PEM.as_bytes()).unwrap(),
+        );
+
+        let value = json!({
+            "raw_public_key": TEST_RPK_KEY1_PEM,
+            "client_auth_raw_public_keys": TEST_PUBKEY1_PEM,
+        });
+        let builder = as_rustls_server_config_builder(&value).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.set_raw_public_key(raw_public_key.clone());
+        expected.set_client_auth_raw_public_keys(vec![public_key.clone()]);
+        assert_eq!(builder, expected);
+
+        let value = json!({
+            "raw_public_key": TEST_RPK_KEY1_PEM,
+            "server_raw_public_key": TEST_PUBKEY1_PEM,
+        });
+        let builder = as_rustls_client_config_builder(&value).unwrap();
+        let mut expected = RustlsClientConfigBuilder::default();
+        expected.set_raw_public_key(raw_public_key);
+        expected.set_server_raw_public_keys(vec![public_key]);
+        assert_eq!(builder, expected);
+
+        // can not be mixed with x509 config
+        let value = json!({
+            "raw_public_key": TEST_RPK_KEY1_PEM,
+            "certificate": TEST_CERT1_PEM,
+            "private_key": TEST_KEY1_PEM,
+        });
+        assert!(as_rustls_server_config_builder(&value).is_err());
+
+        let value = json!({
+            "raw_public_key": TEST_RPK_KEY1_PEM,
+            "enable_client_auth": true,
+            "client_auth_raw_public_keys": TEST_PUBKEY1_PEM,
+        });
+        assert!(as_rustls_server_config_builder(&value).is_err());
+
+        let value = json!({
+            "server_raw_public_keys": TEST_PUBKEY1_PEM,
+            "ca_certificate": TEST_CERT1_PEM,
+        });
+        assert!(as_rustls_client_config_builder(&value).is_err());
     }
 }
@@ -3,9 +3,10 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::sync::Arc;
 use std::time::Duration;
 
-use super::AnyResolveDriverConfig;
+use super::{AnyResolveDriverConfig, ClientSubnet};
 
 pub(crate) const RESOLVER_MINIMUM_CACHE_TTL: u32 = 30;
 #[cfg(any(feature = "c-ares", feature = "hickory"))]
@@ -14,14 +15,33 @@ pub(crate) const RESOLVER_MAXIMUM_CACHE_TTL: u32 = 3600;
 const RESOLVER_CACHE_INITIAL_CAPACITY: usize = 10;
 const RESOLVER_BATCH_REQUEST_COUNT: usize = 10;
 const RESOLVER_PROTECTIVE_QUERY_TIMEOUT: Duration = Duration::from_secs(60);
+const RESOLVER_INFLIGHT_QUERY_TIMEOUT: Duration = Duration::from_secs(90);
 const RESOLVER_GRACEFUL_STOP_WAIT: Duration = Duration::from_secs(30);
+const RESOLVER_WARM_UP_BATCH_SIZE: usize = 16;
+const RESOLVER_NEGATIVE_CACHE_MAX_SIZE: usize = 16384;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ResolverRuntimeConfig {
     pub initial_cache_capacity: usize,
     pub batch_request_count: usize,
     pub protective_query_timeout: Duration,
+    /// max time to wait for the driver response, the waiting queries will fail after this
+    pub inflight_query_timeout: Duration,
     pub graceful_stop_wait: Duration,
+    /// domains to be resolved (both A and AAAA) at startup, before the real requests arrive
+    pub warm_up_domains: Vec<Arc<str>>,
+    /// interval to resolve the warm up domains again, or only resolve at startup if not set
+    pub warm_up_refresh_interval: Option<Duration>,
+    /// max number of warm up domains to be sent to the driver in every 100ms
+    pub warm_up_batch_size: usize,
+    /// the default client subnet to be sent in EDNS Client Subnet option,
+    /// if there is no one set in the request
+    pub client_subnet: Option<ClientSubnet>,
+    /// refresh the cached record in background if it's served after this ratio of its cache ttl
+    pub prefetch_ratio: Option<f64>,
+    /// max number of negative records to be cached for each of A and AAAA,
+    /// new negative records won't be cached if reached
+    pub negative_cache_max_size: usize,
 }
 
 impl Default for ResolverRuntimeConfig {
@@ -30,7 +50,14 @@ impl Default for ResolverRuntimeConfig {
             initial_cache_capacity: RESOLVER_CACHE_INITIAL_CAPACITY,
             batch_request_count: RESOLVER_BATCH_REQUEST_COUNT,
             protective_query_timeout: RESOLVER_PROTECTIVE_QUERY_TIMEOUT,
+            inflight_query_timeout: RESOLVER_INFLIGHT_QUERY_TIMEOUT,
             graceful_stop_wait: RESOLVER_GRACEFUL_STOP_WAIT,
+            warm_up_domains: Vec::new(),
+            warm_up_refresh_interval: None,
+            warm_up_batch_size: RESOLVER_WARM_UP_BATCH_SIZE,
+            client_subnet: None,
+            prefetch_ratio: None,
+            negative_cache_max_size: RESOLVER_NEGATIVE_CACHE_MAX_SIZE,
         }
     }
 }
@@ -38,6 +65,9 @@ impl Default for ResolverRuntimeConfig {
 #[derive(Clone, Debug, PartialEq)]
 pub struct ResolverConfig {
     pub name: String,
+    /// the default driver
     pub driver: AnyResolveDriverConfig,
+    /// drivers for specific domain suffixes, the longest matched suffix wins
+    pub suffix_drivers: Vec<(String, AnyResolveDriverConfig)>,
     pub runtime: ResolverRuntimeConfig,
 }
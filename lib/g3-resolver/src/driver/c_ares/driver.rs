@@ -13,7 +13,7 @@ use tokio::sync::mpsc;
 
 use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
-use crate::{ResolveDriver, ResolveError, ResolvedRecord};
+use crate::{ClientSubnet, ResolveDriver, ResolveError, ResolvedRecord};
 
 pub(super) struct CAresResolver {
     pub(super) inner: FutureResolver,
@@ -122,6 +122,7 @@ impl ResolveDriver for CAresResolver {
     fn query_v4(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
@@ -130,13 +131,14 @@ impl ResolveDriver for CAresResolver {
         tokio::spawn(async move {
             let record = resolve_protective(query, domain, job_config).await;
 
-            let _ = sender.send(ResolveDriverResponse::V4(record)); // TODO log error
+            let _ = sender.send(ResolveDriverResponse::V4(record, subnet)); // TODO log error
         });
     }
 
     fn query_v6(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
@@ -145,7 +147,7 @@ impl ResolveDriver for CAresResolver {
         tokio::spawn(async move {
             let record = resolve_protective(query, domain, job_config).await;
 
-            let _ = sender.send(ResolveDriverResponse::V6(record)); // TODO log error
+            let _ = sender.send(ResolveDriverResponse::V6(record, subnet)); // TODO log error
         });
     }
 }
@@ -12,8 +12,8 @@ use super::FailOverDriverStaticConfig;
 use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
 use crate::{
-    ResolveDriver, ResolveJob, ResolveJobRecvResult, ResolveLocalError, ResolvedRecord,
-    ResolvedRecordSource, ResolverHandle,
+    ClientSubnet, ResolveDriver, ResolveJob, ResolveJobRecvResult, ResolveLocalError,
+    ResolvedRecord, ResolvedRecordSource, ResolverHandle,
 };
 
 pub(super) struct FailOverResolver {
@@ -144,18 +144,29 @@ impl ResolveDriver for FailOverResolver {
     fn query_v4(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
         let job_primary = self
             .primary
             .as_ref()
-            .map(|handle| handle.get_v4(domain.clone()).map(Some).unwrap_or(None))
+            .map(|handle| {
+                handle
+                    .get_v4_with_subnet(domain.clone(), subnet)
+                    .map(Some)
+                    .unwrap_or(None)
+            })
             .unwrap_or(None);
         let job_standby = self
             .standby
             .as_ref()
-            .map(|handle| handle.get_v4(domain.clone()).map(Some).unwrap_or(None))
+            .map(|handle| {
+                handle
+                    .get_v4_with_subnet(domain.clone(), subnet)
+                    .map(Some)
+                    .unwrap_or(None)
+            })
             .unwrap_or(None);
         let job = FailOverResolverJob {
             primary: job_primary,
@@ -165,25 +176,36 @@ impl ResolveDriver for FailOverResolver {
         };
         tokio::spawn(async move {
             let record = job.resolve_protective(domain).await;
-            let _ = sender.send(ResolveDriverResponse::V4(record)); // TODO log error
+            let _ = sender.send(ResolveDriverResponse::V4(record, subnet)); // TODO log error
         });
     }
 
     fn query_v6(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
         let job_primary = self
             .primary
             .as_ref()
-            .map(|handle| handle.get_v6(domain.clone()).map(Some).unwrap_or(None))
+            .map(|handle| {
+                handle
+                    .get_v6_with_subnet(domain.clone(), subnet)
+                    .map(Some)
+                    .unwrap_or(None)
+            })
             .unwrap_or(None);
         let job_standby = self
             .standby
             .as_ref()
-            .map(|handle| handle.get_v6(domain.clone()).map(Some).unwrap_or(None))
+            .map(|handle| {
+                handle
+                    .get_v6_with_subnet(domain.clone(), subnet)
+                    .map(Some)
+                    .unwrap_or(None)
+            })
             .unwrap_or(None);
         let job = FailOverResolverJob {
             primary: job_primary,
@@ -193,7 +215,7 @@ impl ResolveDriver for FailOverResolver {
         };
         tokio::spawn(async move {
             let record = job.resolve_protective(domain).await;
-            let _ = sender.send(ResolveDriverResponse::V6(record)); // TODO log error
+            let _ = sender.send(ResolveDriverResponse::V6(record, subnet)); // TODO log error
         });
     }
 }
@@ -10,9 +10,13 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use async_recursion::async_recursion;
+use hickory_client::ClientError;
 use hickory_client::client::{Client, ClientHandle};
 use hickory_proto::BufDnsStreamHandle;
+use hickory_proto::op::{Edns, Message, MessageType, OpCode, Query};
+use hickory_proto::rr::rdata::opt::EdnsOption;
 use hickory_proto::rr::{DNSClass, Name, RData, RecordType};
+use hickory_proto::xfer::{DnsHandle, DnsRequestOptions, FirstAnswer};
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
 use tokio::sync::mpsc;
@@ -20,27 +24,64 @@ use tokio::sync::mpsc;
 use g3_socket::{BindAddr, TcpConnectInfo, UdpConnectInfo};
 use g3_types::net::{DnsEncryptionConfig, DnsEncryptionProtocol, TcpMiscSockOpts, UdpMiscSockOpts};
 
-use crate::{ResolveDriverError, ResolveError, ResolvedRecord};
+use super::HickoryDnssecMode;
+use crate::{ClientSubnet, ResolveDriverError, ResolveError, ResolveServerError, ResolvedRecord};
+
+const EDNS_MAX_PAYLOAD_LEN: u16 = 1232;
 
 #[derive(Clone)]
 pub(super) struct DnsRequest {
     domain: Arc<str>,
     rtype: RecordType,
+    subnet: Option<ClientSubnet>,
 }
 
 impl DnsRequest {
-    pub(super) fn query_ipv6(domain: Arc<str>) -> Self {
+    pub(super) fn query_ipv6(domain: Arc<str>, subnet: Option<ClientSubnet>) -> Self {
         DnsRequest {
             domain,
             rtype: RecordType::AAAA,
+            subnet,
         }
     }
 
-    pub(super) fn query_ipv4(domain: Arc<str>) -> Self {
+    pub(super) fn query_ipv4(domain: Arc<str>, subnet: Option<ClientSubnet>) -> Self {
         DnsRequest {
             domain,
             rtype: RecordType::A,
+            subnet,
+        }
+    }
+
+    fn build_message(&self, name: Name, dnssec: bool) -> Message {
+        let mut query = Query::query(name, self.rtype);
+        query.set_query_class(DNSClass::IN);
+
+        let mut message = Message::new();
+        message
+            .add_query(query)
+            // the real ID will be set by the client stream
+            .set_id(0)
+            .set_message_type(MessageType::Query)
+            .set_op_code(OpCode::Query)
+            .set_recursion_desired(true);
+
+        if dnssec || self.subnet.is_some() {
+            let edns = message.extensions_mut().get_or_insert_with(Edns::new);
+            edns.set_max_payload(EDNS_MAX_PAYLOAD_LEN)
+                .set_version(0)
+                .set_dnssec_ok(dnssec);
+            if let Some(subnet) = self.subnet {
+                edns.options_mut().insert(EdnsOption::Subnet(
+                    hickory_proto::rr::rdata::opt::ClientSubnet::new(
+                        subnet.addr(),
+                        subnet.prefix(),
+                        0,
+                    ),
+                ));
+            }
         }
+        message
     }
 }
 
@@ -144,19 +185,43 @@ impl HickoryClientJob {
         // always use FQDN format such like "www.example.com."
         name.set_fqdn(true);
 
+        let mut validated = true;
         loop {
-            match async_client
-                .query(name.clone(), DNSClass::IN, req.rtype)
-                .await
-            {
+            let dnssec = self.config.dnssec != HickoryDnssecMode::Disabled;
+            let r = if !dnssec && req.subnet.is_none() {
+                async_client
+                    .query(name.clone(), DNSClass::IN, req.rtype)
+                    .await
+            } else {
+                let message = req.build_message(name.clone(), dnssec);
+                let mut options = DnsRequestOptions::default();
+                options.use_edns = true;
+                options.edns_set_dnssec_ok = dnssec;
+                async_client
+                    .send(hickory_proto::xfer::DnsRequest::new(message, options))
+                    .first_answer()
+                    .await
+                    .map_err(ClientError::from)
+            };
+            match r {
                 Ok(rsp) => {
                     let (mut msg, _) = rsp.into_parts();
 
                     let response_code = msg.response_code();
                     if let Some(e) = ResolveError::from_response_code(response_code) {
-                        return ResolvedRecord::failed(req.domain, self.config.negative_ttl, e);
+                        let ttl = if matches!(
+                            e,
+                            ResolveError::FromServer(ResolveServerError::NotFound)
+                        ) {
+                            self.negative_ttl(&msg)
+                        } else {
+                            self.config.negative_ttl
+                        };
+                        return ResolvedRecord::failed(req.domain, ttl, e);
                     }
 
+                    validated &= msg.authentic_data();
+
                     if msg.truncated() && self.try_truncated {
                         self.try_truncated = false;
                         if let Ok(client) = self.config.new_dns_over_tcp_client().await {
@@ -189,21 +254,31 @@ impl HickoryClientJob {
                             _ => {}
                         }
                     }
-                    return if ips.is_empty() {
-                        if has_cname {
-                            self.try_truncated = true;
-                            continue;
+                    if ips.is_empty() && has_cname {
+                        self.try_truncated = true;
+                        continue;
+                    }
+                    let negative_ttl = self.negative_ttl(&msg);
+                    let record = match self.config.dnssec {
+                        HickoryDnssecMode::Disabled => {
+                            return self.build_record(req, ttl, negative_ttl, ips);
+                        }
+                        HickoryDnssecMode::Permissive => {
+                            self.build_record(req, ttl, negative_ttl, ips)
+                        }
+                        HickoryDnssecMode::Strict => {
+                            if validated {
+                                self.build_record(req, ttl, negative_ttl, ips)
+                            } else {
+                                ResolvedRecord::failed(
+                                    req.domain,
+                                    self.config.negative_ttl,
+                                    ResolveServerError::ServFail.into(),
+                                )
+                            }
                         }
-                        ResolvedRecord::empty(req.domain, self.config.negative_ttl)
-                    } else {
-                        ResolvedRecord::resolved(
-                            req.domain,
-                            ttl,
-                            self.config.positive_min_ttl,
-                            self.config.positive_max_ttl,
-                            ips,
-                        )
                     };
+                    return record.with_dnssec_validated(validated);
                 }
                 Err(e) => {
                     self.state.add_failed();
@@ -220,6 +295,46 @@ impl HickoryClientJob {
     }
 }
 
+impl HickoryClientJob {
+    /// Get the negative cache TTL from the SOA record in the authority section (RFC 2308),
+    /// the value will be no less than the configured negative min ttl
+    fn negative_ttl(&self, msg: &Message) -> u32 {
+        msg.name_servers()
+            .iter()
+            .find_map(|r| match r.data() {
+                RData::SOA(soa) => Some(r.ttl().min(soa.minimum())),
+                _ => None,
+            })
+            .map(|ttl| {
+                ttl.clamp(
+                    self.config.negative_ttl,
+                    self.config.positive_max_ttl.max(self.config.negative_ttl),
+                )
+            })
+            .unwrap_or(self.config.negative_ttl)
+    }
+
+    fn build_record(
+        &self,
+        req: DnsRequest,
+        ttl: u32,
+        negative_ttl: u32,
+        ips: Vec<IpAddr>,
+    ) -> ResolvedRecord {
+        if ips.is_empty() {
+            ResolvedRecord::empty(req.domain, negative_ttl)
+        } else {
+            ResolvedRecord::resolved(
+                req.domain,
+                ttl,
+                self.config.positive_min_ttl,
+                self.config.positive_max_ttl,
+                ips,
+            )
+        }
+    }
+}
+
 #[derive(Clone)]
 pub(super) struct HickoryClientConfig {
     pub(super) target: SocketAddr,
@@ -233,6 +348,8 @@ pub(super) struct HickoryClientConfig {
     pub(super) negative_ttl: u32,
     pub(super) tcp_misc_opts: TcpMiscSockOpts,
     pub(super) udp_misc_opts: UdpMiscSockOpts,
+    pub(super) dnssec: HickoryDnssecMode,
+    pub(super) doh_path: Option<String>,
 }
 
 impl HickoryClientConfig {
@@ -355,6 +472,7 @@ impl HickoryClientConfig {
             self.tcp_connect_info(),
             tls_client,
             tls_name,
+            self.doh_path.clone(),
             self.connect_timeout,
             self.request_timeout,
         );
@@ -409,6 +527,7 @@ impl HickoryClientConfig {
             self.udp_connect_info(),
             tls_client,
             tls_name,
+            self.doh_path.clone(),
             self.connect_timeout,
             self.request_timeout,
         );
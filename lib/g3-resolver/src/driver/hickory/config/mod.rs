@@ -19,6 +19,35 @@ use crate::driver::BoxResolverDriver;
 #[cfg(feature = "yaml")]
 mod yaml;
 
+/// How to handle DNSSEC for the queries sent to the upstream servers.
+///
+/// The DO bit will be set in the EDNS0 OPT record if enabled, and the answer is considered to be
+/// validated if the AD bit is set by the upstream validating resolver. So make sure the upstream
+/// servers are trusted and the transport is secure, like DoT / DoH / DoQ.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum HickoryDnssecMode {
+    /// do not request DNSSEC
+    #[default]
+    Disabled,
+    /// request DNSSEC and mark the record as validated or not, unvalidated answers will be served
+    Permissive,
+    /// request DNSSEC and fail the query with SERVFAIL if the answer is not validated
+    Strict,
+}
+
+impl FromStr for HickoryDnssecMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "disabled" | "disable" | "off" | "none" => Ok(HickoryDnssecMode::Disabled),
+            "permissive" | "allow_unvalidated" => Ok(HickoryDnssecMode::Permissive),
+            "strict" | "require_validated" => Ok(HickoryDnssecMode::Strict),
+            _ => Err(()),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct HickoryDriverConfig {
     connect_timeout: Duration,
@@ -35,6 +64,8 @@ pub struct HickoryDriverConfig {
     encryption: Option<DnsEncryptionConfigBuilder>,
     tcp_misc_opts: TcpMiscSockOpts,
     udp_misc_opts: UdpMiscSockOpts,
+    dnssec: HickoryDnssecMode,
+    doh_path: Option<String>,
 }
 
 impl Default for HickoryDriverConfig {
@@ -54,6 +85,8 @@ impl Default for HickoryDriverConfig {
             encryption: None,
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
+            dnssec: HickoryDnssecMode::Disabled,
+            doh_path: None,
         }
     }
 }
@@ -117,6 +150,11 @@ impl HickoryDriverConfig {
         self.bind_addr
     }
 
+    #[inline]
+    pub fn get_dnssec_mode(&self) -> HickoryDnssecMode {
+        self.dnssec
+    }
+
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
         let mut driver =
             HickoryResolver::new(self.each_timeout, self.retry_interval, self.negative_ttl);
@@ -145,6 +183,8 @@ impl HickoryDriverConfig {
                 negative_ttl: self.negative_ttl,
                 tcp_misc_opts: self.tcp_misc_opts.clone(),
                 udp_misc_opts: self.udp_misc_opts,
+                dnssec: self.dnssec,
+                doh_path: self.doh_path.clone(),
             };
             let (req_sender, req_receiver) = kanal::unbounded_async();
             driver.push_client(req_sender);
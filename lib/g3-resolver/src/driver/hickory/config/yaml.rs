@@ -4,13 +4,14 @@
  */
 
 use std::path::Path;
+use std::str::FromStr;
 
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
 use g3_socket::BindAddr;
 
-use super::HickoryDriverConfig;
+use super::{HickoryDnssecMode, HickoryDriverConfig};
 
 impl HickoryDriverConfig {
     pub fn set_by_yaml_kv(
@@ -96,6 +97,24 @@ impl HickoryDriverConfig {
                 Ok(())
             }
             "negative_max_ttl" => Ok(()),
+            "dnssec" => {
+                self.dnssec = match v {
+                    Yaml::Boolean(false) => HickoryDnssecMode::Disabled,
+                    Yaml::Boolean(true) => HickoryDnssecMode::Strict,
+                    Yaml::String(s) => HickoryDnssecMode::from_str(s)
+                        .map_err(|_| anyhow!("invalid dnssec mode {s}"))?,
+                    _ => return Err(anyhow!("invalid yaml value type, expect boolean / string")),
+                };
+                Ok(())
+            }
+            "doh_path" => {
+                let path = g3_yaml::value::as_string(v)?;
+                if !path.starts_with('/') {
+                    return Err(anyhow!("the doh path should start with '/'"));
+                }
+                self.doh_path = Some(path);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -135,6 +154,7 @@ mod tests {
                 positive_max_ttl: 7200
                 negative_min_ttl: 600
                 negative_max_ttl: 1200
+                doh_path: "/resolve"
             "#
         );
         for (k, v) in yaml.as_hash().unwrap().iter() {
@@ -163,6 +183,7 @@ mod tests {
         assert_eq!(config.positive_min_ttl, 300);
         assert_eq!(config.positive_max_ttl, 7200);
         assert_eq!(config.negative_ttl, 600);
+        assert_eq!(config.doh_path.as_deref(), Some("/resolve"));
 
         // server as array
         let mut config = HickoryDriverConfig::default();
@@ -202,6 +223,7 @@ mod tests {
                 positive_min_ttl: -300
                 positive_max_ttl: "not_a_number"
                 negative_ttl: false
+                doh_path: "dns-query"
                 invalid_key: value
             "#
         );
@@ -272,6 +294,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_by_yaml_kv_dnssec() {
+        use g3_yaml::yaml_str;
+
+        let mut config = HickoryDriverConfig::default();
+        assert_eq!(config.get_dnssec_mode(), HickoryDnssecMode::Disabled);
+
+        config
+            .set_by_yaml_kv("dnssec", &yaml_str!("permissive"), None)
+            .unwrap();
+        assert_eq!(config.get_dnssec_mode(), HickoryDnssecMode::Permissive);
+
+        config
+            .set_by_yaml_kv("dnssec", &Yaml::Boolean(false), None)
+            .unwrap();
+        assert_eq!(config.get_dnssec_mode(), HickoryDnssecMode::Disabled);
+
+        config
+            .set_by_yaml_kv("dnssec", &Yaml::Boolean(true), None)
+            .unwrap();
+        assert_eq!(config.get_dnssec_mode(), HickoryDnssecMode::Strict);
+
+        assert!(
+            config
+                .set_by_yaml_kv("dnssec", &yaml_str!("invalid"), None)
+                .is_err()
+        );
+        assert!(
+            config
+                .set_by_yaml_kv("dnssec", &Yaml::Integer(1), None)
+                .is_err()
+        );
+    }
+
     #[test]
     fn check_config() {
         let mut config = HickoryDriverConfig::default();
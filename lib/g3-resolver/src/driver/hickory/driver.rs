@@ -12,7 +12,7 @@ use tokio::time::Instant;
 use super::DnsRequest;
 use crate::config::ResolverRuntimeConfig;
 use crate::message::ResolveDriverResponse;
-use crate::{ResolveDriver, ResolveDriverError, ResolveLocalError, ResolvedRecord};
+use crate::{ClientSubnet, ResolveDriver, ResolveDriverError, ResolveLocalError, ResolvedRecord};
 
 #[derive(Clone)]
 pub struct HickoryResolver {
@@ -26,32 +26,34 @@ impl ResolveDriver for HickoryResolver {
     fn query_v4(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
-        let request = DnsRequest::query_ipv4(domain.clone());
+        let request = DnsRequest::query_ipv4(domain.clone(), subnet);
 
         let job = self.clone();
         let timeout = config.protective_query_timeout;
         tokio::spawn(async move {
             let r = run_timed(job, timeout, domain, request).await;
-            let _ = sender.send(ResolveDriverResponse::V4(r));
+            let _ = sender.send(ResolveDriverResponse::V4(r, subnet));
         });
     }
 
     fn query_v6(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     ) {
-        let request = DnsRequest::query_ipv6(domain.clone());
+        let request = DnsRequest::query_ipv6(domain.clone(), subnet);
 
         let job = self.clone();
         let timeout = config.protective_query_timeout;
         tokio::spawn(async move {
             let r = run_timed(job, timeout, domain, request).await;
-            let _ = sender.send(ResolveDriverResponse::V6(r));
+            let _ = sender.send(ResolveDriverResponse::V6(r, subnet));
         });
     }
 }
@@ -4,7 +4,7 @@
  */
 
 mod config;
-pub use config::HickoryDriverConfig;
+pub use config::{HickoryDnssecMode, HickoryDriverConfig};
 
 mod client;
 use client::{DnsRequest, HickoryClient, HickoryClientConfig};
@@ -0,0 +1,198 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use anyhow::{Context, anyhow};
+
+use super::HostsResolver;
+use crate::BoxResolverDriver;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+const HOSTS_DEFAULT_TTL: u32 = 300;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HostsDriverConfig {
+    records: BTreeMap<String, Vec<IpAddr>>,
+    hosts_file: Option<PathBuf>,
+    file_records: BTreeMap<String, Vec<IpAddr>>,
+    positive_ttl: u32,
+    negative_ttl: u32,
+}
+
+impl Default for HostsDriverConfig {
+    fn default() -> Self {
+        HostsDriverConfig {
+            records: BTreeMap::new(),
+            hosts_file: None,
+            file_records: BTreeMap::new(),
+            positive_ttl: HOSTS_DEFAULT_TTL,
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+        }
+    }
+}
+
+fn normalize_name(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+impl HostsDriverConfig {
+    pub fn check(&mut self) -> anyhow::Result<()> {
+        if self.records.is_empty() && self.hosts_file.is_none() {
+            return Err(anyhow!("neither records nor hosts file is set"));
+        }
+        Ok(())
+    }
+
+    /// Add a static record, which takes precedence over the one in hosts file
+    pub fn add_record(&mut self, name: &str, ips: Vec<IpAddr>) -> anyhow::Result<()> {
+        let name = normalize_name(name);
+        if name.is_empty() {
+            return Err(anyhow!("empty domain name"));
+        }
+        self.records.entry(name).or_default().extend(ips);
+        Ok(())
+    }
+
+    /// Load all records from a hosts-style file, replacing the ones loaded before
+    pub fn load_hosts_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("failed to read hosts file {}: {e}", path.display()))?;
+        self.file_records =
+            parse_hosts(&content).context(format!("invalid hosts file {}", path.display()))?;
+        self.hosts_file = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    #[inline]
+    pub fn get_hosts_file(&self) -> Option<&Path> {
+        self.hosts_file.as_deref()
+    }
+
+    #[inline]
+    pub fn set_positive_ttl(&mut self, ttl: u32) {
+        self.positive_ttl = ttl;
+    }
+
+    #[inline]
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    fn merged_records(&self) -> AHashMap<String, Vec<IpAddr>> {
+        let mut records: AHashMap<String, Vec<IpAddr>> = self
+            .file_records
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        for (name, ips) in &self.records {
+            records.insert(name.clone(), ips.clone());
+        }
+        records
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> BoxResolverDriver {
+        Box::new(HostsResolver {
+            records: Arc::new(self.merged_records()),
+            positive_ttl: self.positive_ttl,
+            negative_ttl: self.negative_ttl,
+        })
+    }
+}
+
+fn parse_hosts(content: &str) -> anyhow::Result<BTreeMap<String, Vec<IpAddr>>> {
+    let mut records: BTreeMap<String, Vec<IpAddr>> = BTreeMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = match line.split_once('#') {
+            Some((data, _comment)) => data,
+            None => line,
+        };
+        let mut iter = line.split_whitespace();
+        let Some(ip) = iter.next() else {
+            continue;
+        };
+        let ip =
+            IpAddr::from_str(ip).map_err(|e| anyhow!("line {}: invalid ip {ip}: {e}", i + 1))?;
+        for name in iter {
+            let name = normalize_name(name);
+            if name.is_empty() {
+                continue;
+            }
+            let ips = records.entry(name).or_default();
+            if !ips.contains(&ip) {
+                ips.push(ip);
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    #[test]
+    fn parse_hosts_content() {
+        let content = r#"
+# comment line
+127.0.0.1   localhost localhost.localdomain
+::1         localhost  # trailing comment
+192.0.2.10  www.Example.net. example.net
+192.0.2.11  www.example.net
+"#;
+        let records = parse_hosts(content).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(
+            records.get("localhost").unwrap(),
+            &vec![
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                IpAddr::V6(Ipv6Addr::LOCALHOST)
+            ]
+        );
+        assert_eq!(
+            records.get("www.example.net").unwrap(),
+            &vec![
+                IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10)),
+                IpAddr::V4(Ipv4Addr::new(192, 0, 2, 11))
+            ]
+        );
+        assert!(records.contains_key("example.net"));
+
+        assert!(parse_hosts("192.0.2.300 bad.example.net").is_err());
+    }
+
+    #[test]
+    fn static_record_override() {
+        let mut config = HostsDriverConfig::default();
+        assert!(config.check().is_err());
+        config.file_records = parse_hosts("192.0.2.1 a.example.net b.example.net").unwrap();
+        config
+            .add_record(
+                "A.example.net.",
+                vec![IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2))],
+            )
+            .unwrap();
+        assert!(config.check().is_ok());
+        assert!(config.add_record(".", vec![]).is_err());
+
+        let records = config.merged_records();
+        assert_eq!(
+            records.get("a.example.net").unwrap(),
+            &vec![IpAddr::V4(Ipv4Addr::new(192, 0, 2, 2))]
+        );
+        assert_eq!(
+            records.get("b.example.net").unwrap(),
+            &vec![IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1))]
+        );
+    }
+}
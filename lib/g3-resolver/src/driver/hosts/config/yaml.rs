@@ -0,0 +1,109 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::Path;
+
+use anyhow::{Context, anyhow};
+use yaml_rust::Yaml;
+
+use super::HostsDriverConfig;
+
+impl HostsDriverConfig {
+    pub fn set_by_yaml_kv(
+        &mut self,
+        k: &str,
+        v: &Yaml,
+        lookup_dir: Option<&Path>,
+    ) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            "records" | "hosts" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid yaml value type for key {k}, expect map"));
+                };
+                self.records.clear();
+                g3_yaml::foreach_kv(map, |name, v| {
+                    let ips = g3_yaml::value::as_list(v, g3_yaml::value::as_ipaddr)
+                        .context(format!("invalid ip address value for domain {name}"))?;
+                    self.add_record(name, ips)
+                })
+            }
+            "hosts_file" | "file" => {
+                let (_, path) = g3_yaml::value::as_file(v, lookup_dir)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.load_hosts_file(&path)
+            }
+            "positive_ttl" | "ttl" => {
+                self.positive_ttl = g3_yaml::value::as_u32(v)?;
+                Ok(())
+            }
+            "negative_ttl" => {
+                self.negative_ttl = g3_yaml::value::as_u32(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_yaml::yaml_doc;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn set_by_yaml_kv_ok() {
+        let mut config = HostsDriverConfig::default();
+        let yaml = yaml_doc!(
+            r#"
+                records:
+                  www.example.net: 192.0.2.1
+                  Dual.example.net: ["192.0.2.2", "2001:db8::2"]
+                ttl: 60
+                negative_ttl: 10
+            "#
+        );
+        for (k, v) in yaml.as_hash().unwrap().iter() {
+            config.set_by_yaml_kv(k.as_str().unwrap(), v, None).unwrap();
+        }
+        config.check().unwrap();
+
+        assert_eq!(
+            config.records.get("www.example.net").unwrap(),
+            &vec![IpAddr::from_str("192.0.2.1").unwrap()]
+        );
+        assert_eq!(config.records.get("dual.example.net").unwrap().len(), 2);
+        assert_eq!(config.positive_ttl, 60);
+        assert_eq!(config.negative_ttl, 10);
+    }
+
+    #[test]
+    fn set_by_yaml_kv_err() {
+        let mut config = HostsDriverConfig::default();
+        let yaml = yaml_doc!(
+            r#"
+                records: "192.0.2.1"
+                hosts_file: "/non-existed/hosts"
+                ttl: -1
+                invalid_key: value
+            "#
+        );
+        for (k, v) in yaml.as_hash().unwrap().iter() {
+            assert!(config.set_by_yaml_kv(k.as_str().unwrap(), v, None).is_err());
+        }
+
+        let yaml = yaml_doc!(
+            r#"
+                records:
+                  www.example.net: "not.an.ip"
+            "#
+        );
+        for (k, v) in yaml.as_hash().unwrap().iter() {
+            assert!(config.set_by_yaml_kv(k.as_str().unwrap(), v, None).is_err());
+        }
+    }
+}
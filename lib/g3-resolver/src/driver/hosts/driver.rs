@@ -0,0 +1,145 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use tokio::sync::mpsc;
+
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{ClientSubnet, ResolveDriver, ResolveServerError, ResolvedRecord};
+
+pub(super) struct HostsResolver {
+    pub(super) records: Arc<AHashMap<String, Vec<IpAddr>>>,
+    pub(super) positive_ttl: u32,
+    pub(super) negative_ttl: u32,
+}
+
+impl HostsResolver {
+    fn resolve<F>(&self, domain: Arc<str>, filter: F) -> ResolvedRecord
+    where
+        F: Fn(&IpAddr) -> bool,
+    {
+        let name = domain.trim_end_matches('.').to_ascii_lowercase();
+        let Some(all_ips) = self.records.get(&name) else {
+            return ResolvedRecord::failed(
+                domain,
+                self.negative_ttl,
+                ResolveServerError::NotFound.into(),
+            );
+        };
+        let ips: Vec<IpAddr> = all_ips.iter().filter(|ip| filter(ip)).copied().collect();
+        if ips.is_empty() {
+            ResolvedRecord::empty(domain, self.positive_ttl)
+        } else {
+            ResolvedRecord::resolved(
+                domain,
+                self.positive_ttl,
+                self.positive_ttl,
+                self.positive_ttl,
+                ips,
+            )
+        }
+    }
+}
+
+impl ResolveDriver for HostsResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
+        _config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let record = self.resolve(domain, IpAddr::is_ipv4);
+        let _ = sender.send(ResolveDriverResponse::V4(record, subnet));
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
+        _config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        let record = self.resolve(domain, IpAddr::is_ipv6);
+        let _ = sender.send(ResolveDriverResponse::V6(record, subnet));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ResolveError, ResolveServerError};
+    use std::str::FromStr;
+
+    fn build() -> HostsResolver {
+        let mut records = AHashMap::new();
+        records.insert(
+            "dual.example.net".to_string(),
+            vec![
+                IpAddr::from_str("192.0.2.1").unwrap(),
+                IpAddr::from_str("2001:db8::1").unwrap(),
+            ],
+        );
+        records.insert(
+            "v4.example.net".to_string(),
+            vec![IpAddr::from_str("192.0.2.2").unwrap()],
+        );
+        HostsResolver {
+            records: Arc::new(records),
+            positive_ttl: 60,
+            negative_ttl: 10,
+        }
+    }
+
+    #[test]
+    fn query() {
+        let driver = build();
+        let config = ResolverRuntimeConfig::default();
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        driver.query_v4(
+            Arc::from("Dual.example.net."),
+            None,
+            &config,
+            sender.clone(),
+        );
+        let Ok(ResolveDriverResponse::V4(r, None)) = receiver.try_recv() else {
+            panic!("no v4 response");
+        };
+        assert_eq!(
+            r.result.unwrap(),
+            vec![IpAddr::from_str("192.0.2.1").unwrap()]
+        );
+
+        driver.query_v6(Arc::from("dual.example.net"), None, &config, sender.clone());
+        let Ok(ResolveDriverResponse::V6(r, None)) = receiver.try_recv() else {
+            panic!("no v6 response");
+        };
+        assert_eq!(
+            r.result.unwrap(),
+            vec![IpAddr::from_str("2001:db8::1").unwrap()]
+        );
+
+        driver.query_v6(Arc::from("v4.example.net"), None, &config, sender.clone());
+        let Ok(ResolveDriverResponse::V6(r, None)) = receiver.try_recv() else {
+            panic!("no v6 response");
+        };
+        assert!(r.is_ok());
+        assert!(!r.is_usable());
+
+        driver.query_v4(Arc::from("none.example.net"), None, &config, sender);
+        let Ok(ResolveDriverResponse::V4(r, None)) = receiver.try_recv() else {
+            panic!("no v4 response");
+        };
+        assert!(matches!(
+            r.result,
+            Err(ResolveError::FromServer(ResolveServerError::NotFound))
+        ));
+    }
+}
@@ -0,0 +1,10 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+mod config;
+pub use config::HostsDriverConfig;
+
+mod driver;
+use driver::HostsResolver;
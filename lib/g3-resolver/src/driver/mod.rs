@@ -7,10 +7,12 @@ use std::sync::Arc;
 
 use tokio::sync::mpsc;
 
-use crate::config::ResolverRuntimeConfig;
+use crate::ClientSubnet;
+use crate::config::{ResolverConfig, ResolverRuntimeConfig};
 use crate::message::ResolveDriverResponse;
 
 pub mod fail_over;
+pub mod hosts;
 
 #[cfg(feature = "c-ares")]
 pub mod c_ares;
@@ -21,6 +23,7 @@ pub mod hickory;
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyResolveDriverConfig {
     FailOver(fail_over::FailOverDriverConfig),
+    Hosts(hosts::HostsDriverConfig),
     #[cfg(feature = "c-ares")]
     CAres(c_ares::CAresDriverConfig),
     #[cfg(feature = "hickory")]
@@ -31,6 +34,7 @@ impl AnyResolveDriverConfig {
     pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<Box<dyn ResolveDriver>> {
         match self {
             AnyResolveDriverConfig::FailOver(c) => Ok(c.spawn_resolver_driver()),
+            AnyResolveDriverConfig::Hosts(c) => Ok(c.spawn_resolver_driver()),
             #[cfg(feature = "c-ares")]
             AnyResolveDriverConfig::CAres(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "hickory")]
@@ -39,19 +43,114 @@ impl AnyResolveDriverConfig {
     }
 }
 
-pub(crate) trait ResolveDriver {
+pub(crate) trait ResolveDriver: Send {
     fn query_v4(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     );
     fn query_v6(
         &self,
         domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
         config: &ResolverRuntimeConfig,
         sender: mpsc::UnboundedSender<ResolveDriverResponse>,
     );
 }
 
 pub(crate) type BoxResolverDriver = Box<dyn ResolveDriver>;
+
+/// The default driver and the drivers for specific domain suffixes
+pub(crate) struct ResolveDrivers {
+    default: BoxResolverDriver,
+    /// sorted by suffix length in descending order
+    suffix: Vec<(String, BoxResolverDriver)>,
+}
+
+impl ResolveDrivers {
+    pub(crate) fn spawn(config: &ResolverConfig) -> anyhow::Result<Self> {
+        let default = config.driver.spawn_resolver_driver()?;
+        let mut suffix = Vec::with_capacity(config.suffix_drivers.len());
+        for (s, c) in &config.suffix_drivers {
+            let s = s.trim_start_matches('.').to_ascii_lowercase();
+            suffix.push((s, c.spawn_resolver_driver()?));
+        }
+        // stable sort, so the first one wins if there are duplicated suffixes
+        suffix.sort_by_key(|(s, _)| std::cmp::Reverse(s.len()));
+        Ok(ResolveDrivers { default, suffix })
+    }
+
+    pub(crate) fn select(&self, domain: &str) -> &BoxResolverDriver {
+        self.suffix
+            .iter()
+            .find(|(s, _)| match_suffix(domain, s))
+            .map(|(_, d)| d)
+            .unwrap_or(&self.default)
+    }
+}
+
+fn match_suffix(domain: &str, suffix: &str) -> bool {
+    let domain = domain.trim_end_matches('.');
+    if domain.len() == suffix.len() {
+        domain.eq_ignore_ascii_case(suffix)
+    } else if domain.len() > suffix.len() {
+        let (prefix, tail) = domain.split_at(domain.len() - suffix.len());
+        prefix.ends_with('.') && tail.eq_ignore_ascii_case(suffix)
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(suffix_list: &[&str]) -> ResolveDrivers {
+        let config = ResolverConfig {
+            name: "test".to_string(),
+            driver: AnyResolveDriverConfig::FailOver(Default::default()),
+            suffix_drivers: suffix_list
+                .iter()
+                .map(|s| {
+                    (
+                        s.to_string(),
+                        AnyResolveDriverConfig::FailOver(Default::default()),
+                    )
+                })
+                .collect(),
+            runtime: Default::default(),
+        };
+        ResolveDrivers::spawn(&config).unwrap()
+    }
+
+    fn selected<'a>(drivers: &'a ResolveDrivers, domain: &str) -> Option<&'a str> {
+        let d = drivers.select(domain);
+        drivers
+            .suffix
+            .iter()
+            .find(|(_, v)| std::ptr::eq(v, d))
+            .map(|(s, _)| s.as_str())
+    }
+
+    #[test]
+    fn match_domain_suffix() {
+        assert!(match_suffix("internal", "internal"));
+        assert!(match_suffix("a.internal", "internal"));
+        assert!(match_suffix("a.Internal.", "internal"));
+        assert!(!match_suffix("ainternal", "internal"));
+        assert!(!match_suffix("nal", "internal"));
+    }
+
+    #[test]
+    fn select_longest() {
+        let drivers = build(&[".internal", "corp.internal", "example.net"]);
+        assert_eq!(selected(&drivers, "a.internal"), Some("internal"));
+        assert_eq!(selected(&drivers, "a.corp.internal"), Some("corp.internal"));
+        assert_eq!(selected(&drivers, "corp.internal"), Some("corp.internal"));
+        assert_eq!(selected(&drivers, "www.example.net"), Some("example.net"));
+        assert_eq!(selected(&drivers, "www.example.com"), None);
+        assert_eq!(selected(&drivers, "internal.com"), None);
+    }
+}
@@ -3,19 +3,44 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::future::poll_fn;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use ahash::AHashMap;
 use tokio::sync::{mpsc, oneshot};
 
-use super::{ArcResolvedRecord, ResolveLocalError, ResolvedRecordSource};
+use super::{ArcResolvedRecord, ClientSubnet, ResolveLocalError, ResolvedRecordSource};
 use crate::message::ResolveDriverRequest;
 
+thread_local! {
+    static WORKER_REQ_SENDERS: RefCell<AHashMap<usize, mpsc::UnboundedSender<ResolveDriverRequest>>> =
+        RefCell::new(AHashMap::new());
+}
+
+/// Register the request sender of the per worker runtime running on the current thread
+pub(crate) fn register_worker_sender(
+    resolver_id: usize,
+    sender: mpsc::UnboundedSender<ResolveDriverRequest>,
+) {
+    WORKER_REQ_SENDERS.with_borrow_mut(|map| map.insert(resolver_id, sender));
+}
+
+pub(crate) fn unregister_worker_sender(resolver_id: usize) {
+    WORKER_REQ_SENDERS.with_borrow_mut(|map| map.remove(&resolver_id));
+}
+
+/// Check if there is a per worker runtime running on the current thread
+pub(crate) fn has_worker_sender(resolver_id: usize) -> bool {
+    WORKER_REQ_SENDERS.with_borrow(|map| map.contains_key(&resolver_id))
+}
+
 #[derive(Clone, Debug)]
 pub struct ResolverHandle {
     req_sender: mpsc::UnboundedSender<ResolveDriverRequest>,
+    worker_resolver_id: Option<usize>,
 }
 
 impl PartialEq for ResolverHandle {
@@ -25,32 +50,70 @@ impl PartialEq for ResolverHandle {
 }
 
 impl ResolverHandle {
-    pub(crate) fn new(req_sender: mpsc::UnboundedSender<ResolveDriverRequest>) -> Self {
-        ResolverHandle { req_sender }
+    pub(crate) fn new(
+        req_sender: mpsc::UnboundedSender<ResolveDriverRequest>,
+        worker_resolver_id: Option<usize>,
+    ) -> Self {
+        ResolverHandle {
+            req_sender,
+            worker_resolver_id,
+        }
     }
 
     pub fn is_closed(&self) -> bool {
         self.req_sender.is_closed()
     }
 
+    fn send_req(&self, req: ResolveDriverRequest) -> Result<(), ResolveLocalError> {
+        let req = match self.worker_resolver_id {
+            Some(id) => {
+                // prefer the runtime on the current worker thread, or fallback to the shared one
+                let r = WORKER_REQ_SENDERS.with_borrow(|map| match map.get(&id) {
+                    Some(sender) => sender.send(req).map_err(|e| e.0),
+                    None => Err(req),
+                });
+                match r {
+                    Ok(_) => return Ok(()),
+                    Err(req) => req,
+                }
+            }
+            None => req,
+        };
+        self.req_sender
+            .send(req)
+            .map_err(|_| ResolveLocalError::NoResolverRunning)
+    }
+
     pub fn get_v4(&self, domain: Arc<str>) -> Result<ResolveJob, ResolveLocalError> {
+        self.get_v4_with_subnet(domain, None)
+    }
+
+    /// Resolve with the client subnet, which will override the one in the runtime config
+    pub fn get_v4_with_subnet(
+        &self,
+        domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
+    ) -> Result<ResolveJob, ResolveLocalError> {
         let (sender, receiver) = oneshot::channel();
-        let req = ResolveDriverRequest::GetV4(domain, sender);
-        let sender = self.req_sender.clone();
-        match sender.send(req) {
-            Ok(_) => Ok(ResolveJob { receiver }),
-            Err(_) => Err(ResolveLocalError::NoResolverRunning),
-        }
+        let req = ResolveDriverRequest::GetV4(domain, subnet, sender);
+        self.send_req(req)?;
+        Ok(ResolveJob { receiver })
     }
 
     pub fn get_v6(&self, domain: Arc<str>) -> Result<ResolveJob, ResolveLocalError> {
+        self.get_v6_with_subnet(domain, None)
+    }
+
+    /// Resolve with the client subnet, which will override the one in the runtime config
+    pub fn get_v6_with_subnet(
+        &self,
+        domain: Arc<str>,
+        subnet: Option<ClientSubnet>,
+    ) -> Result<ResolveJob, ResolveLocalError> {
         let (sender, receiver) = oneshot::channel();
-        let req = ResolveDriverRequest::GetV6(domain, sender);
-        let sender = self.req_sender.clone();
-        match sender.send(req) {
-            Ok(_) => Ok(ResolveJob { receiver }),
-            Err(_) => Err(ResolveLocalError::NoResolverRunning),
-        }
+        let req = ResolveDriverRequest::GetV6(domain, subnet, sender);
+        self.send_req(req)?;
+        Ok(ResolveJob { receiver })
     }
 }
 
@@ -73,3 +136,67 @@ impl ResolveJob {
         poll_fn(|cx| self.poll_recv(cx)).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recv_domain(receiver: &mut mpsc::UnboundedReceiver<ResolveDriverRequest>) -> Arc<str> {
+        match receiver.try_recv().unwrap() {
+            ResolveDriverRequest::GetV4(domain, _, _) => domain,
+            ResolveDriverRequest::GetV6(domain, _, _) => domain,
+        }
+    }
+
+    #[test]
+    fn worker_routing() {
+        const RESOLVER_ID: usize = usize::MAX;
+
+        let (shared_sender, mut shared_receiver) = mpsc::unbounded_channel();
+        let (worker_sender, mut worker_receiver) = mpsc::unbounded_channel();
+        let handle = ResolverHandle::new(shared_sender, Some(RESOLVER_ID));
+
+        // no worker runtime on this thread
+        let _job = handle.get_v4(Arc::from("a.example.net")).unwrap();
+        assert_eq!(&*recv_domain(&mut shared_receiver), "a.example.net");
+
+        register_worker_sender(RESOLVER_ID, worker_sender);
+        assert!(has_worker_sender(RESOLVER_ID));
+        let _job = handle.get_v6(Arc::from("b.example.net")).unwrap();
+        assert_eq!(&*recv_domain(&mut worker_receiver), "b.example.net");
+        assert!(shared_receiver.try_recv().is_err());
+
+        // the worker sender is only visible on the registered thread
+        let other_handle = handle.clone();
+        std::thread::spawn(move || {
+            assert!(!has_worker_sender(RESOLVER_ID));
+            let _job = other_handle.get_v4(Arc::from("c.example.net")).unwrap();
+        })
+        .join()
+        .unwrap();
+        assert_eq!(&*recv_domain(&mut shared_receiver), "c.example.net");
+        assert!(worker_receiver.try_recv().is_err());
+
+        // fallback to the shared runtime if the worker runtime has quit
+        drop(worker_receiver);
+        let _job = handle.get_v4(Arc::from("d.example.net")).unwrap();
+        assert_eq!(&*recv_domain(&mut shared_receiver), "d.example.net");
+
+        unregister_worker_sender(RESOLVER_ID);
+        assert!(!has_worker_sender(RESOLVER_ID));
+        let _job = handle.get_v4(Arc::from("e.example.net")).unwrap();
+        assert_eq!(&*recv_domain(&mut shared_receiver), "e.example.net");
+    }
+
+    #[test]
+    fn no_worker() {
+        let (shared_sender, shared_receiver) = mpsc::unbounded_channel();
+        let handle = ResolverHandle::new(shared_sender, None);
+        drop(shared_receiver);
+        assert!(handle.is_closed());
+        assert!(matches!(
+            handle.get_v4(Arc::from("a.example.net")),
+            Err(ResolveLocalError::NoResolverRunning)
+        ));
+    }
+}
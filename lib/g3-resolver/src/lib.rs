@@ -6,7 +6,7 @@
 pub mod driver;
 pub use driver::AnyResolveDriverConfig;
 
-pub(crate) use driver::{BoxResolverDriver, ResolveDriver};
+pub(crate) use driver::{BoxResolverDriver, ResolveDriver, ResolveDrivers};
 
 mod config;
 mod error;
@@ -17,6 +17,7 @@ mod record;
 mod resolver;
 mod runtime;
 mod stats;
+mod subnet;
 
 pub use config::{ResolverConfig, ResolverRuntimeConfig};
 pub use error::{ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError};
@@ -25,3 +26,4 @@ pub use query::ResolveQueryType;
 pub use record::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource};
 pub use resolver::{Resolver, ResolverBuilder};
 pub use stats::{ResolverMemorySnapshot, ResolverQuerySnapshot, ResolverSnapshot, ResolverStats};
+pub use subnet::ClientSubnet;
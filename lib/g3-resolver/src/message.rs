@@ -7,26 +7,31 @@ use std::sync::Arc;
 
 use tokio::sync::oneshot;
 
-use super::{ArcResolvedRecord, ResolvedRecord, ResolvedRecordSource, ResolverConfig};
+use super::{
+    ArcResolvedRecord, ClientSubnet, ResolvedRecord, ResolvedRecordSource, ResolverConfig,
+};
 
 #[derive(Clone, Debug)]
 pub(crate) enum ResolverCommand {
     Quit,
     Update(Box<ResolverConfig>),
+    WarmUp(Vec<Arc<str>>),
 }
 
 pub(crate) enum ResolveDriverRequest {
     GetV4(
         Arc<str>,
+        Option<ClientSubnet>,
         oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>,
     ),
     GetV6(
         Arc<str>,
+        Option<ClientSubnet>,
         oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>,
     ),
 }
 
 pub(crate) enum ResolveDriverResponse {
-    V4(ResolvedRecord),
-    V6(ResolvedRecord),
+    V4(ResolvedRecord, Option<ClientSubnet>),
+    V6(ResolvedRecord, Option<ClientSubnet>),
 }
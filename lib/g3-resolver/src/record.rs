@@ -43,6 +43,8 @@ pub struct ResolvedRecord {
     pub expire: Option<Instant>,
     pub vanish: Option<Instant>,
     pub result: Result<Vec<IpAddr>, ResolveError>,
+    /// DNSSEC validation state, or None if DNSSEC is not requested
+    pub dnssec_validated: Option<bool>,
 }
 
 pub type ArcResolvedRecord = Arc<ResolvedRecord>;
@@ -64,6 +66,14 @@ impl ResolvedRecord {
         self.expire.map(|expire| now >= expire).unwrap_or(true)
     }
 
+    /// NXDOMAIN or NODATA responses
+    pub fn is_negative(&self) -> bool {
+        match self.result.as_ref() {
+            Ok(ips) => ips.is_empty(),
+            Err(e) => matches!(e, ResolveError::FromServer(ResolveServerError::NotFound)),
+        }
+    }
+
     pub fn is_acceptable(&self) -> bool {
         let Err(e) = self.result.as_ref() else {
             return true;
@@ -105,6 +115,7 @@ impl ResolvedRecord {
             expire,
             vanish,
             result: Ok(ips),
+            dnssec_validated: None,
         }
     }
 
@@ -117,6 +128,7 @@ impl ResolvedRecord {
             expire,
             vanish: None,
             result: Ok(Vec::new()),
+            dnssec_validated: None,
         }
     }
 
@@ -129,6 +141,12 @@ impl ResolvedRecord {
             expire,
             vanish: None,
             result: Err(err),
+            dnssec_validated: None,
         }
     }
+
+    pub fn with_dnssec_validated(mut self, validated: bool) -> Self {
+        self.dnssec_validated = Some(validated);
+        self
+    }
 }
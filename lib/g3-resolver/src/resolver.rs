@@ -5,9 +5,11 @@
 
 use std::io;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread::JoinHandle;
 
 use log::warn;
+use tokio::runtime::Handle;
 use tokio::sync::mpsc;
 
 use super::ResolverStats;
@@ -16,17 +18,29 @@ use crate::handle::ResolverHandle;
 use crate::message::{ResolveDriverRequest, ResolverCommand};
 use crate::runtime::ResolverRuntime;
 
+static RESOLVER_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub struct ResolverBuilder {
     resolver_config: ResolverConfig,
     thread_name: Option<String>,
+    worker_handles: Vec<Handle>,
+}
+
+struct WorkerRuntime {
+    ctl_sender: mpsc::UnboundedSender<ResolverCommand>,
+    join_handle: tokio::task::JoinHandle<()>,
 }
 
 pub struct Resolver {
+    id: usize,
     config: ResolverConfig,
     stats: Arc<ResolverStats>,
     thread_handle: Option<JoinHandle<()>>,
     req_sender: mpsc::UnboundedSender<ResolveDriverRequest>,
     ctl_sender: mpsc::UnboundedSender<ResolverCommand>,
+    worker_runtimes: Vec<WorkerRuntime>,
+    /// will be disconnected after all the worker runtimes have quit
+    worker_stopped: Option<std::sync::mpsc::Receiver<()>>,
 }
 
 impl ResolverBuilder {
@@ -34,6 +48,7 @@ impl ResolverBuilder {
         ResolverBuilder {
             resolver_config: config,
             thread_name: None,
+            worker_handles: Vec::new(),
         }
     }
 
@@ -41,6 +56,14 @@ impl ResolverBuilder {
         self.thread_name = Some(name);
     }
 
+    /// Also run a resolver runtime on each of the worker runtimes.
+    ///
+    /// Queries from the worker threads will be handled on the same thread, and each runtime
+    /// will have its own cache. The shared runtime will still be used by other threads.
+    pub fn worker_handles(&mut self, handles: Vec<Handle>) {
+        self.worker_handles = handles;
+    }
+
     pub fn build(mut self) -> io::Result<Resolver> {
         let (req_sender, req_receiver) = mpsc::unbounded_channel();
         let (ctl_sender, ctl_receiver) = mpsc::unbounded_channel();
@@ -67,12 +90,47 @@ impl ResolverBuilder {
             });
         })?;
 
+        let id = RESOLVER_ID.fetch_add(1, Ordering::Relaxed);
+        let (stopped_sender, stopped_receiver) = std::sync::mpsc::channel::<()>();
+        let worker_runtimes = self
+            .worker_handles
+            .iter()
+            .map(|handle| {
+                let (req_sender, req_receiver) = mpsc::unbounded_channel();
+                let (ctl_sender, ctl_receiver) = mpsc::unbounded_channel();
+                let config = self.resolver_config.clone();
+                let stats = Arc::clone(&stats);
+                let stopped_sender = stopped_sender.clone();
+                let join_handle = handle.spawn(async move {
+                    let _stopped_sender = stopped_sender;
+                    let resolver_name = config.name.to_owned();
+                    crate::handle::register_worker_sender(id, req_sender);
+                    let mut resolver_runtime =
+                        ResolverRuntime::new(config, req_receiver, ctl_receiver, stats);
+                    resolver_runtime.disable_mem_stats();
+                    resolver_runtime.disable_warm_up();
+                    if let Err(e) = resolver_runtime.await {
+                        warn!("resolver {resolver_name} worker runtime exited with error: {e}");
+                    }
+                    crate::handle::unregister_worker_sender(id);
+                });
+                WorkerRuntime {
+                    ctl_sender,
+                    join_handle,
+                }
+            })
+            .collect();
+        drop(stopped_sender);
+
         Ok(Resolver {
+            id,
             config: self.resolver_config,
             stats,
             thread_handle: Some(thread_handle),
             req_sender,
             ctl_sender,
+            worker_runtimes,
+            worker_stopped: Some(stopped_receiver),
         })
     }
 }
@@ -83,7 +141,12 @@ impl Resolver {
     }
 
     pub fn get_handle(&self) -> ResolverHandle {
-        ResolverHandle::new(self.req_sender.clone())
+        let worker_resolver_id = if self.worker_runtimes.is_empty() {
+            None
+        } else {
+            Some(self.id)
+        };
+        ResolverHandle::new(self.req_sender.clone(), worker_resolver_id)
     }
 
     pub fn get_config(&self) -> ResolverConfig {
@@ -105,12 +168,40 @@ impl Resolver {
         self.ctl_sender
             .send(ResolverCommand::Update(Box::new(config.clone())))
             .map_err(io::Error::other)?;
+        for rt in &self.worker_runtimes {
+            let _ = rt
+                .ctl_sender
+                .send(ResolverCommand::Update(Box::new(config.clone())));
+        }
         self.config = config;
         Ok(())
     }
 
+    /// Resolve the domains in background to populate the cache of the shared runtime
+    pub fn warm_up(&self, domains: Vec<Arc<str>>) -> io::Result<()> {
+        self.ctl_sender
+            .send(ResolverCommand::WarmUp(domains))
+            .map_err(io::Error::other)
+    }
+
     fn stop(&self) {
         let _ = self.ctl_sender.send(ResolverCommand::Quit);
+        for rt in &self.worker_runtimes {
+            let _ = rt.ctl_sender.send(ResolverCommand::Quit);
+        }
+    }
+
+    fn join_worker_runtimes(&mut self) {
+        self.worker_runtimes.clear();
+        let Some(stopped) = self.worker_stopped.take() else {
+            return;
+        };
+        if crate::handle::has_worker_sender(self.id) {
+            // the worker runtime on this thread can only quit after we return
+            return;
+        }
+        // all the senders will be dropped after the worker runtimes quit
+        let _ = stopped.recv();
     }
 
     pub async fn shutdown(&mut self) {
@@ -118,6 +209,9 @@ impl Resolver {
             tokio::time::sleep(self.config.runtime.graceful_stop_wait).await;
 
             self.stop();
+            for rt in self.worker_runtimes.drain(..) {
+                let _ = rt.join_handle.await;
+            }
             let resolver_name = self.config.name.to_owned();
             if let Err(e) = tokio::task::spawn_blocking(move || {
                 let thread_id = join.thread().id();
@@ -142,6 +236,7 @@ impl Drop for Resolver {
     fn drop(&mut self) {
         if let Some(join) = self.thread_handle.take() {
             self.stop();
+            self.join_worker_runtimes();
             let thread_id = join.thread().id();
             if let Err(e) = join.join() {
                 warn!(
@@ -152,3 +247,50 @@ impl Drop for Resolver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnyResolveDriverConfig;
+
+    #[test]
+    fn drop_join_workers() {
+        let worker_rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let worker_handle = worker_rt.handle().clone();
+        let (quit_sender, quit_receiver) = tokio::sync::oneshot::channel::<()>();
+        let worker_thread = std::thread::spawn(move || {
+            worker_rt.block_on(async move {
+                let _ = quit_receiver.await;
+            });
+        });
+
+        let config = ResolverConfig {
+            name: "test".to_string(),
+            driver: AnyResolveDriverConfig::FailOver(Default::default()),
+            suffix_drivers: Vec::new(),
+            runtime: Default::default(),
+        };
+        let mut builder = ResolverBuilder::new(config);
+        builder.worker_handles(vec![worker_handle.clone()]);
+        let resolver = builder.build().unwrap();
+
+        // keep the worker runtime busy, so it will quit later than the shared one
+        let (busy_sender, busy_receiver) = std::sync::mpsc::channel();
+        worker_handle.spawn(async move {
+            let _ = busy_sender.send(());
+            std::thread::sleep(std::time::Duration::from_millis(200));
+        });
+        busy_receiver.recv().unwrap();
+
+        let stats = resolver.get_stats();
+        drop(resolver);
+        // the shared and the worker runtimes should have all quit
+        assert_eq!(Arc::strong_count(&stats), 1);
+
+        let _ = quit_sender.send(());
+        worker_thread.join().unwrap();
+    }
+}
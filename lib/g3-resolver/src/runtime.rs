@@ -3,25 +3,113 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::collections::hash_map;
+use std::collections::{VecDeque, hash_map};
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
-use ahash::AHashMap;
+use ahash::{AHashMap, AHashSet};
 use log::{trace, warn};
 use tokio::sync::{mpsc, oneshot};
-use tokio::time::Instant;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
 use tokio_util::time::{DelayQueue, delay_queue};
 
-use super::stats::{ResolverMemoryStats, ResolverStats};
-use super::{ArcResolvedRecord, BoxResolverDriver, ResolvedRecordSource, ResolverConfig};
+use super::stats::{ResolverMemoryStats, ResolverQueryStats, ResolverStats};
+use super::{
+    ArcResolvedRecord, ClientSubnet, ResolveDrivers, ResolvedRecord, ResolvedRecordSource,
+    ResolverConfig,
+};
 use crate::message::{ResolveDriverRequest, ResolveDriverResponse, ResolverCommand};
 
+const WARM_UP_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+const INFLIGHT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+type ResolvedRecordSender = oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>;
+
+/// Records for different client subnets should be cached separately
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct CacheKey {
+    domain: Arc<str>,
+    subnet: Option<ClientSubnet>,
+}
+
+impl CacheKey {
+    fn new(domain: Arc<str>, subnet: Option<ClientSubnet>) -> Self {
+        CacheKey { domain, subnet }
+    }
+}
+
 struct CachedRecord {
     inner: ArcResolvedRecord,
     expire_at: Instant,
     expire_key: Option<delay_queue::Key>,
+    prefetched: bool,
+}
+
+impl CachedRecord {
+    /// Check if the record has been cached for more than `ratio` of its cache ttl,
+    /// only one prefetch will be tried for each cached record
+    fn need_prefetch(&self, ratio: Option<f64>, now: Instant) -> bool {
+        if self.prefetched {
+            return false;
+        }
+        let Some(ratio) = ratio else {
+            return false;
+        };
+        let ttl = self.expire_at.saturating_duration_since(self.inner.created);
+        now.saturating_duration_since(self.inner.created) >= ttl.mul_f64(ratio)
+    }
+}
+
+/// NXDOMAIN or NODATA record, which will be dropped instead of moved to trash after expired
+struct CachedNegative {
+    inner: ArcResolvedRecord,
+    expire_key: delay_queue::Key,
+}
+
+struct DoingQuery {
+    created: Instant,
+    senders: Vec<ResolvedRecordSender>,
+}
+
+impl DoingQuery {
+    fn new(senders: Vec<ResolvedRecordSender>) -> Self {
+        DoingQuery {
+            created: Instant::now(),
+            senders,
+        }
+    }
+}
+
+/// Domains to be warmed up, each domain will be queued only once
+#[derive(Default)]
+struct WarmUpQueue {
+    queue: VecDeque<Arc<str>>,
+    queued: AHashSet<Arc<str>>,
+}
+
+impl WarmUpQueue {
+    fn push(&mut self, domain: Arc<str>) {
+        if self.queued.insert(domain.clone()) {
+            self.queue.push_back(domain);
+        }
+    }
+
+    fn pop(&mut self) -> Option<Arc<str>> {
+        let domain = self.queue.pop_front()?;
+        self.queued.remove(&domain);
+        Some(domain)
+    }
+
+    fn clear(&mut self) {
+        self.queue.clear();
+        self.queued.clear();
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
 }
 
 struct TrashedRecord {
@@ -36,15 +124,25 @@ pub(crate) struct ResolverRuntime {
     ctl_receiver: mpsc::UnboundedReceiver<ResolverCommand>,
     rsp_receiver: mpsc::UnboundedReceiver<ResolveDriverResponse>,
     rsp_sender: mpsc::UnboundedSender<ResolveDriverResponse>,
-    expired_v4: DelayQueue<Arc<str>>,
-    expired_v6: DelayQueue<Arc<str>>,
-    cache_v4: AHashMap<Arc<str>, CachedRecord>,
-    cache_v6: AHashMap<Arc<str>, CachedRecord>,
-    doing_v4: AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>>,
-    doing_v6: AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>>,
-    trash_v4: AHashMap<Arc<str>, TrashedRecord>,
-    trash_v6: AHashMap<Arc<str>, TrashedRecord>,
-    driver: Option<BoxResolverDriver>,
+    expired_v4: DelayQueue<CacheKey>,
+    expired_v6: DelayQueue<CacheKey>,
+    cache_v4: AHashMap<CacheKey, CachedRecord>,
+    cache_v6: AHashMap<CacheKey, CachedRecord>,
+    neg_expired_v4: DelayQueue<CacheKey>,
+    neg_expired_v6: DelayQueue<CacheKey>,
+    neg_cache_v4: AHashMap<CacheKey, CachedNegative>,
+    neg_cache_v6: AHashMap<CacheKey, CachedNegative>,
+    doing_v4: AHashMap<CacheKey, DoingQuery>,
+    doing_v6: AHashMap<CacheKey, DoingQuery>,
+    trash_v4: AHashMap<CacheKey, TrashedRecord>,
+    trash_v6: AHashMap<CacheKey, TrashedRecord>,
+    driver: Option<ResolveDrivers>,
+    report_mem_stats: bool,
+    warm_up_enabled: bool,
+    warm_up_queue: WarmUpQueue,
+    warm_up_batch_interval: Option<Interval>,
+    warm_up_refresh_interval: Option<Interval>,
+    inflight_check_interval: Interval,
 }
 
 impl Drop for ResolverRuntime {
@@ -63,6 +161,8 @@ impl ResolverRuntime {
     ) -> Self {
         let initial_cache_capacity = config.runtime.initial_cache_capacity;
         let (rsp_sender, rsp_receiver) = mpsc::unbounded_channel();
+        let mut inflight_check_interval = tokio::time::interval(INFLIGHT_CHECK_INTERVAL);
+        inflight_check_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
         ResolverRuntime {
             config,
             stats,
@@ -74,67 +174,236 @@ impl ResolverRuntime {
             expired_v6: DelayQueue::with_capacity(initial_cache_capacity),
             cache_v4: AHashMap::with_capacity(initial_cache_capacity),
             cache_v6: AHashMap::with_capacity(initial_cache_capacity),
+            neg_expired_v4: DelayQueue::new(),
+            neg_expired_v6: DelayQueue::new(),
+            neg_cache_v4: AHashMap::new(),
+            neg_cache_v6: AHashMap::new(),
             doing_v4: AHashMap::with_capacity(initial_cache_capacity),
             doing_v6: AHashMap::with_capacity(initial_cache_capacity),
             trash_v4: AHashMap::with_capacity(initial_cache_capacity),
             trash_v6: AHashMap::with_capacity(initial_cache_capacity),
             driver: None,
+            report_mem_stats: true,
+            warm_up_enabled: true,
+            warm_up_queue: WarmUpQueue::default(),
+            warm_up_batch_interval: None,
+            warm_up_refresh_interval: None,
+            inflight_check_interval,
         }
     }
 
+    /// Do not report cache memory stats, which should be reported by the shared runtime only
+    pub(crate) fn disable_mem_stats(&mut self) {
+        self.report_mem_stats = false;
+    }
+
+    /// Do not warm up the cache, which should be done by the shared runtime only
+    pub(crate) fn disable_warm_up(&mut self) {
+        self.warm_up_enabled = false;
+    }
+
     fn handle_cmd(&mut self, cmd: ResolverCommand) {
         match cmd {
-            ResolverCommand::Update(config) => match config.driver.spawn_resolver_driver() {
+            ResolverCommand::Update(config) => match ResolveDrivers::spawn(&config) {
                 Ok(driver) => {
                     self.driver = Some(driver);
+                    let warm_up_changed = self.config.runtime.warm_up_domains
+                        != config.runtime.warm_up_domains
+                        || self.config.runtime.warm_up_refresh_interval
+                            != config.runtime.warm_up_refresh_interval;
                     self.config = *config;
+                    if warm_up_changed {
+                        self.reset_warm_up();
+                    }
                 }
                 Err(e) => {
                     warn!("invalid resolver config {config:?} : {e}");
                 }
             },
+            ResolverCommand::WarmUp(domains) => {
+                if self.warm_up_enabled {
+                    domains
+                        .into_iter()
+                        .for_each(|domain| self.warm_up_queue.push(domain));
+                }
+            }
             ResolverCommand::Quit => {} // should be handled outside
         }
     }
 
+    fn reset_warm_up(&mut self) {
+        self.warm_up_queue.clear();
+        if !self.warm_up_enabled {
+            return;
+        }
+        for domain in &self.config.runtime.warm_up_domains {
+            self.warm_up_queue.push(domain.clone());
+        }
+        self.warm_up_refresh_interval = self.config.runtime.warm_up_refresh_interval.map(|d| {
+            let mut interval = tokio::time::interval_at(Instant::now() + d, d);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+    }
+
+    fn poll_warm_up(&mut self, cx: &mut Context<'_>) {
+        if let Some(interval) = &mut self.warm_up_refresh_interval
+            && interval.poll_tick(cx).is_ready()
+        {
+            for domain in &self.config.runtime.warm_up_domains {
+                self.warm_up_queue.push(domain.clone());
+            }
+        }
+
+        if self.warm_up_queue.is_empty() {
+            return;
+        }
+        loop {
+            let interval = self.warm_up_batch_interval.get_or_insert_with(|| {
+                let mut interval = tokio::time::interval(WARM_UP_BATCH_INTERVAL);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                interval
+            });
+            if interval.poll_tick(cx).is_pending() {
+                return;
+            }
+            for _ in 0..self.config.runtime.warm_up_batch_size {
+                let Some(domain) = self.warm_up_queue.pop() else {
+                    return;
+                };
+                self.warm_up(domain);
+            }
+            if self.warm_up_queue.is_empty() {
+                return;
+            }
+        }
+    }
+
+    fn warm_up(&mut self, domain: Arc<str>) {
+        let Some(drivers) = &self.driver else {
+            return;
+        };
+        let driver = drivers.select(&domain);
+        trace!("warm up domain {domain}");
+        let subnet = self.config.runtime.client_subnet;
+        let key = CacheKey::new(domain.clone(), subnet);
+        if let hash_map::Entry::Vacant(v) = self.doing_v4.entry(key.clone()) {
+            v.insert(DoingQuery::new(vec![]));
+            self.stats.query_a.add_query_driver();
+            driver.query_v4(
+                domain.clone(),
+                subnet,
+                &self.config.runtime,
+                self.rsp_sender.clone(),
+            );
+        }
+        if let hash_map::Entry::Vacant(v) = self.doing_v6.entry(key) {
+            v.insert(DoingQuery::new(vec![]));
+            self.stats.query_aaaa.add_query_driver();
+            driver.query_v6(
+                domain,
+                subnet,
+                &self.config.runtime,
+                self.rsp_sender.clone(),
+            );
+        }
+    }
+
     fn update_cache(
-        cache: &mut AHashMap<Arc<str>, CachedRecord>,
-        expire_queue: &mut DelayQueue<Arc<str>>,
+        cache: &mut AHashMap<CacheKey, CachedRecord>,
+        expire_queue: &mut DelayQueue<CacheKey>,
+        key: CacheKey,
         record: ArcResolvedRecord,
         expire_at: Instant,
     ) {
-        match cache.entry(record.domain.clone()) {
+        match cache.entry(key) {
             hash_map::Entry::Occupied(mut o) => {
+                let key = o.key().clone();
                 let v = o.get_mut();
                 let expire_key = match v.expire_key.take() {
                     Some(expire_key) => {
                         expire_queue.reset_at(&expire_key, expire_at);
                         expire_key
                     }
-                    None => expire_queue.insert_at(record.domain.clone(), expire_at),
+                    None => expire_queue.insert_at(key, expire_at),
                 };
                 v.inner = record;
                 v.expire_at = expire_at;
                 v.expire_key = Some(expire_key);
+                v.prefetched = false;
             }
             hash_map::Entry::Vacant(v) => {
-                let expire_key = expire_queue.insert_at(record.domain.to_owned(), expire_at);
+                let expire_key = expire_queue.insert_at(v.key().clone(), expire_at);
                 v.insert(CachedRecord {
                     inner: record,
                     expire_at,
                     expire_key: Some(expire_key),
+                    prefetched: false,
                 });
             }
         }
     }
 
+    fn remove_cache(
+        cache: &mut AHashMap<CacheKey, CachedRecord>,
+        expire_queue: &mut DelayQueue<CacheKey>,
+        key: &CacheKey,
+    ) {
+        if let Some(v) = cache.remove(key)
+            && let Some(expire_key) = v.expire_key
+        {
+            expire_queue.remove(&expire_key);
+        }
+    }
+
+    fn update_neg_cache(
+        cache: &mut AHashMap<CacheKey, CachedNegative>,
+        expire_queue: &mut DelayQueue<CacheKey>,
+        max_size: usize,
+        key: CacheKey,
+        record: ArcResolvedRecord,
+        expire_at: Instant,
+    ) {
+        let len = cache.len();
+        match cache.entry(key) {
+            hash_map::Entry::Occupied(mut o) => {
+                let v = o.get_mut();
+                expire_queue.reset_at(&v.expire_key, expire_at);
+                v.inner = record;
+            }
+            hash_map::Entry::Vacant(v) => {
+                if len >= max_size {
+                    trace!("negative cache is full, skip domain {}", v.key().domain);
+                    return;
+                }
+                let expire_key = expire_queue.insert_at(v.key().clone(), expire_at);
+                v.insert(CachedNegative {
+                    inner: record,
+                    expire_key,
+                });
+            }
+        }
+    }
+
+    fn remove_neg_cache(
+        cache: &mut AHashMap<CacheKey, CachedNegative>,
+        expire_queue: &mut DelayQueue<CacheKey>,
+        key: &CacheKey,
+    ) {
+        if let Some(v) = cache.remove(key) {
+            expire_queue.remove(&v.expire_key);
+        }
+    }
+
     fn handle_rsp(&mut self, rsp: ResolveDriverResponse) {
         match rsp {
-            ResolveDriverResponse::V4(record) => {
+            ResolveDriverResponse::V4(record, subnet) => {
                 self.stats.query_a.add_record(&record);
+                let key = CacheKey::new(record.domain.clone(), subnet);
                 if !record.is_acceptable() {
-                    if let Some(v) = self.trash_v4.get(&record.domain) {
-                        if let Some(vec) = self.doing_v4.remove(&record.domain) {
+                    if let Some(v) = self.trash_v4.get(&key) {
+                        if let Some(q) = self.doing_v4.remove(&key) {
+                            let vec = q.senders;
                             self.stats.query_a.add_query_trashed_n(vec.len());
                             for sender in vec.into_iter() {
                                 let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Trash));
@@ -142,28 +411,69 @@ impl ResolverRuntime {
                         }
                         return;
                     }
+                    if let Some(v) = self.cache_v4.get(&key) {
+                        // the prefetch query failed, keep using the cached one
+                        if let Some(q) = self.doing_v4.remove(&key) {
+                            let vec = q.senders;
+                            self.stats.query_a.add_query_cached_n(vec.len());
+                            for sender in vec.into_iter() {
+                                let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Cache));
+                            }
+                        }
+                        return;
+                    }
                 } else {
-                    self.trash_v4.remove(&record.domain);
+                    self.trash_v4.remove(&key);
                 }
                 let record = Arc::new(record);
-                if let Some(mut vec) = self.doing_v4.remove(&record.domain)
-                    && let Some(sender) = vec.pop()
+                let negative = record.is_negative();
+                if let Some(mut q) = self.doing_v4.remove(&key)
+                    && let Some(sender) = q.senders.pop()
                 {
                     let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
-                    self.stats.query_a.add_query_cached_n(vec.len());
-                    for sender in vec.into_iter() {
+                    if negative {
+                        self.stats.query_a.add_query_neg_cached_n(q.senders.len());
+                    } else {
+                        self.stats.query_a.add_query_cached_n(q.senders.len());
+                    }
+                    for sender in q.senders.into_iter() {
                         let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Cache));
                     }
                 }
                 if let Some(expire_at) = record.expire {
-                    Self::update_cache(&mut self.cache_v4, &mut self.expired_v4, record, expire_at);
+                    if negative {
+                        Self::remove_cache(&mut self.cache_v4, &mut self.expired_v4, &key);
+                        Self::update_neg_cache(
+                            &mut self.neg_cache_v4,
+                            &mut self.neg_expired_v4,
+                            self.config.runtime.negative_cache_max_size,
+                            key,
+                            record,
+                            expire_at,
+                        );
+                    } else {
+                        Self::remove_neg_cache(
+                            &mut self.neg_cache_v4,
+                            &mut self.neg_expired_v4,
+                            &key,
+                        );
+                        Self::update_cache(
+                            &mut self.cache_v4,
+                            &mut self.expired_v4,
+                            key,
+                            record,
+                            expire_at,
+                        );
+                    }
                 }
             }
-            ResolveDriverResponse::V6(record) => {
+            ResolveDriverResponse::V6(record, subnet) => {
                 self.stats.query_aaaa.add_record(&record);
+                let key = CacheKey::new(record.domain.clone(), subnet);
                 if !record.is_acceptable() {
-                    if let Some(v) = self.trash_v6.get(&record.domain) {
-                        if let Some(vec) = self.doing_v6.remove(&record.domain) {
+                    if let Some(v) = self.trash_v6.get(&key) {
+                        if let Some(q) = self.doing_v6.remove(&key) {
+                            let vec = q.senders;
                             self.stats.query_aaaa.add_query_trashed_n(vec.len());
                             for sender in vec.into_iter() {
                                 let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Trash));
@@ -171,33 +481,74 @@ impl ResolverRuntime {
                         }
                         return;
                     }
+                    if let Some(v) = self.cache_v6.get(&key) {
+                        // the prefetch query failed, keep using the cached one
+                        if let Some(q) = self.doing_v6.remove(&key) {
+                            let vec = q.senders;
+                            self.stats.query_aaaa.add_query_cached_n(vec.len());
+                            for sender in vec.into_iter() {
+                                let _ = sender.send((v.inner.clone(), ResolvedRecordSource::Cache));
+                            }
+                        }
+                        return;
+                    }
                 } else {
-                    self.trash_v6.remove(&record.domain);
+                    self.trash_v6.remove(&key);
                 }
                 let record = Arc::new(record);
-                if let Some(mut vec) = self.doing_v6.remove(&record.domain)
-                    && let Some(sender) = vec.pop()
+                let negative = record.is_negative();
+                if let Some(mut q) = self.doing_v6.remove(&key)
+                    && let Some(sender) = q.senders.pop()
                 {
                     let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
-                    self.stats.query_aaaa.add_query_cached_n(vec.len());
-                    for sender in vec.into_iter() {
+                    if negative {
+                        self.stats
+                            .query_aaaa
+                            .add_query_neg_cached_n(q.senders.len());
+                    } else {
+                        self.stats.query_aaaa.add_query_cached_n(q.senders.len());
+                    }
+                    for sender in q.senders.into_iter() {
                         let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Cache));
                     }
                 }
                 if let Some(expire_at) = record.expire {
-                    Self::update_cache(&mut self.cache_v6, &mut self.expired_v6, record, expire_at);
+                    if negative {
+                        Self::remove_cache(&mut self.cache_v6, &mut self.expired_v6, &key);
+                        Self::update_neg_cache(
+                            &mut self.neg_cache_v6,
+                            &mut self.neg_expired_v6,
+                            self.config.runtime.negative_cache_max_size,
+                            key,
+                            record,
+                            expire_at,
+                        );
+                    } else {
+                        Self::remove_neg_cache(
+                            &mut self.neg_cache_v6,
+                            &mut self.neg_expired_v6,
+                            &key,
+                        );
+                        Self::update_cache(
+                            &mut self.cache_v6,
+                            &mut self.expired_v6,
+                            key,
+                            record,
+                            expire_at,
+                        );
+                    }
                 }
             }
         }
     }
 
-    fn handle_expired_v4(&mut self, domain: &str) {
-        trace!("clean expired v4 for domain {domain}");
-        if let Some(r) = self.cache_v4.remove(domain)
+    fn handle_expired_v4(&mut self, key: CacheKey) {
+        trace!("clean expired v4 for domain {}", key.domain);
+        if let Some(r) = self.cache_v4.remove(&key)
             && let Some(vanish_at) = r.inner.vanish
         {
             self.trash_v4.insert(
-                r.inner.domain.clone(),
+                key,
                 TrashedRecord {
                     inner: r.inner,
                     vanish_at,
@@ -205,13 +556,13 @@ impl ResolverRuntime {
             );
         }
     }
-    fn handle_expired_v6(&mut self, domain: &str) {
-        trace!("clean expired v6 for domain {domain}");
-        if let Some(r) = self.cache_v6.remove(domain)
+    fn handle_expired_v6(&mut self, key: CacheKey) {
+        trace!("clean expired v6 for domain {}", key.domain);
+        if let Some(r) = self.cache_v6.remove(&key)
             && let Some(vanish_at) = r.inner.vanish
         {
             self.trash_v6.insert(
-                r.inner.domain.clone(),
+                key,
                 TrashedRecord {
                     inner: r.inner,
                     vanish_at,
@@ -222,70 +573,138 @@ impl ResolverRuntime {
 
     fn handle_req(&mut self, req: ResolveDriverRequest) {
         match req {
-            ResolveDriverRequest::GetV4(domain, sender) => {
+            ResolveDriverRequest::GetV4(domain, subnet, sender) => {
                 self.stats.query_a.add_query_total();
-                if let Some(r) = self.cache_v4.get(&domain) {
+                let subnet = subnet.or(self.config.runtime.client_subnet);
+                let key = CacheKey::new(domain.clone(), subnet);
+                if let Some(r) = self.cache_v4.get_mut(&key) {
                     self.stats.query_a.add_query_cached();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
+                    if r.need_prefetch(self.config.runtime.prefetch_ratio, Instant::now())
+                        && let hash_map::Entry::Vacant(v) = self.doing_v4.entry(key)
+                        && let Some(drivers) = &self.driver
+                    {
+                        trace!("prefetch v4 for domain {domain}");
+                        // don't retry if failed, the record will be queried again after expired
+                        r.prefetched = true;
+                        v.insert(DoingQuery::new(vec![]));
+                        self.stats.query_a.add_query_prefetched();
+                        self.stats.query_a.add_query_driver();
+                        drivers.select(&domain).query_v4(
+                            domain.clone(),
+                            subnet,
+                            &self.config.runtime,
+                            self.rsp_sender.clone(),
+                        );
+                    }
+                    return;
+                }
+                if let Some(r) = self.neg_cache_v4.get(&key) {
+                    self.stats.query_a.add_query_neg_cached();
+                    let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
                     return;
                 }
-                if let Some(r) = self.trash_v4.get(&domain) {
+                if let Some(r) = self.trash_v4.get(&key) {
                     self.stats.query_a.add_query_trashed();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
-                    self.doing_v4.entry(domain.clone()).or_insert_with(|| {
-                        if let Some(driver) = &self.driver {
+                    self.doing_v4.entry(key).or_insert_with(|| {
+                        if let Some(drivers) = &self.driver {
                             self.stats.query_a.add_query_driver();
-                            driver.query_v4(domain, &self.config.runtime, self.rsp_sender.clone());
+                            drivers.select(&domain).query_v4(
+                                domain.clone(),
+                                subnet,
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
                         }
-                        vec![]
+                        DoingQuery::new(vec![])
                     });
                     return;
                 }
-                match self.doing_v4.entry(domain.clone()) {
+                match self.doing_v4.entry(key) {
                     hash_map::Entry::Occupied(mut o) => {
                         // there is a query already
-                        o.get_mut().push(sender);
+                        o.get_mut().senders.push(sender);
                     }
                     hash_map::Entry::Vacant(v) => {
-                        v.insert(vec![sender]);
-                        if let Some(driver) = &self.driver {
+                        v.insert(DoingQuery::new(vec![sender]));
+                        if let Some(drivers) = &self.driver {
                             self.stats.query_a.add_query_driver();
-                            driver.query_v4(domain, &self.config.runtime, self.rsp_sender.clone());
+                            drivers.select(&domain).query_v4(
+                                domain.clone(),
+                                subnet,
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
                         } else {
                             unreachable!()
                         }
                     }
                 }
             }
-            ResolveDriverRequest::GetV6(domain, sender) => {
+            ResolveDriverRequest::GetV6(domain, subnet, sender) => {
                 self.stats.query_aaaa.add_query_total();
-                if let Some(r) = self.cache_v6.get(&domain) {
+                let subnet = subnet.or(self.config.runtime.client_subnet);
+                let key = CacheKey::new(domain.clone(), subnet);
+                if let Some(r) = self.cache_v6.get_mut(&key) {
                     self.stats.query_aaaa.add_query_cached();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
+                    if r.need_prefetch(self.config.runtime.prefetch_ratio, Instant::now())
+                        && let hash_map::Entry::Vacant(v) = self.doing_v6.entry(key)
+                        && let Some(drivers) = &self.driver
+                    {
+                        trace!("prefetch v6 for domain {domain}");
+                        // don't retry if failed, the record will be queried again after expired
+                        r.prefetched = true;
+                        v.insert(DoingQuery::new(vec![]));
+                        self.stats.query_aaaa.add_query_prefetched();
+                        self.stats.query_aaaa.add_query_driver();
+                        drivers.select(&domain).query_v6(
+                            domain.clone(),
+                            subnet,
+                            &self.config.runtime,
+                            self.rsp_sender.clone(),
+                        );
+                    }
+                    return;
+                }
+                if let Some(r) = self.neg_cache_v6.get(&key) {
+                    self.stats.query_aaaa.add_query_neg_cached();
+                    let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Cache));
                     return;
                 }
-                if let Some(r) = self.trash_v6.get(&domain) {
+                if let Some(r) = self.trash_v6.get(&key) {
                     self.stats.query_aaaa.add_query_trashed();
                     let _ = sender.send((Arc::clone(&r.inner), ResolvedRecordSource::Trash));
-                    self.doing_v6.entry(domain.clone()).or_insert_with(|| {
-                        if let Some(driver) = &self.driver {
+                    self.doing_v6.entry(key).or_insert_with(|| {
+                        if let Some(drivers) = &self.driver {
                             self.stats.query_aaaa.add_query_driver();
-                            driver.query_v6(domain, &self.config.runtime, self.rsp_sender.clone());
+                            drivers.select(&domain).query_v6(
+                                domain.clone(),
+                                subnet,
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
                         }
-                        vec![]
+                        DoingQuery::new(vec![])
                     });
                     return;
                 }
-                match self.doing_v6.entry(domain.clone()) {
+                match self.doing_v6.entry(key) {
                     hash_map::Entry::Occupied(mut o) => {
                         // there is a query already
-                        o.get_mut().push(sender);
+                        o.get_mut().senders.push(sender);
                     }
                     hash_map::Entry::Vacant(v) => {
-                        v.insert(vec![sender]);
-                        if let Some(driver) = &self.driver {
+                        v.insert(DoingQuery::new(vec![sender]));
+                        if let Some(drivers) = &self.driver {
                             self.stats.query_aaaa.add_query_driver();
-                            driver.query_v6(domain, &self.config.runtime, self.rsp_sender.clone());
+                            drivers.select(&domain).query_v6(
+                                domain.clone(),
+                                subnet,
+                                &self.config.runtime,
+                                self.rsp_sender.clone(),
+                            );
                         } else {
                             unreachable!()
                         }
@@ -296,9 +715,10 @@ impl ResolverRuntime {
     }
 
     fn update_mem_stats(&self) {
-        fn update<K, VC, VD, VT>(
+        fn update<K, VC, VN, VD, VT>(
             stats: &ResolverMemoryStats,
             cache_ht: &AHashMap<K, VC>,
+            neg_cache_ht: &AHashMap<K, VN>,
             doing_ht: &AHashMap<K, VD>,
             trash_ht: &AHashMap<K, VT>,
         ) {
@@ -308,22 +728,71 @@ impl ResolverRuntime {
             stats.set_doing_length(doing_ht.len());
             stats.set_trash_capacity(trash_ht.capacity());
             stats.set_trash_length(trash_ht.len());
+            stats.set_neg_cache_capacity(neg_cache_ht.capacity());
+            stats.set_neg_cache_length(neg_cache_ht.len());
         }
 
         update(
             &self.stats.memory_a,
             &self.cache_v4,
+            &self.neg_cache_v4,
             &self.doing_v4,
             &self.trash_v4,
         );
         update(
             &self.stats.memory_aaaa,
             &self.cache_v6,
+            &self.neg_cache_v6,
             &self.doing_v6,
             &self.trash_v6,
         );
     }
 
+    /// Fail the queries that have been waiting for the driver response for too long,
+    /// and return the age of the oldest one that is still in-flight
+    fn check_inflight(
+        doing: &mut AHashMap<CacheKey, DoingQuery>,
+        stats: &ResolverQueryStats,
+        timeout: Duration,
+        now: Instant,
+    ) -> Duration {
+        let mut max_age = Duration::ZERO;
+        doing.retain(|key, q| {
+            let age = now.saturating_duration_since(q.created);
+            if age < timeout {
+                max_age = max_age.max(age);
+                return true;
+            }
+
+            warn!(
+                "in-flight query for domain {} timed out after {age:?}",
+                key.domain
+            );
+            let record = Arc::new(ResolvedRecord::timed_out(key.domain.clone(), 0));
+            stats.add_record(&record);
+            for sender in q.senders.drain(..) {
+                let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
+            }
+            false
+        });
+        max_age
+    }
+
+    fn poll_inflight_check(&mut self, cx: &mut Context<'_>) {
+        if self.inflight_check_interval.poll_tick(cx).is_pending() {
+            return;
+        }
+
+        let now = Instant::now();
+        let timeout = self.config.runtime.inflight_query_timeout;
+        let age_v4 = Self::check_inflight(&mut self.doing_v4, &self.stats.query_a, timeout, now);
+        let age_v6 = Self::check_inflight(&mut self.doing_v6, &self.stats.query_aaaa, timeout, now);
+        if self.report_mem_stats {
+            self.stats.memory_a.set_doing_max_age(age_v4);
+            self.stats.memory_aaaa.set_doing_max_age(age_v6);
+        }
+    }
+
     fn clean_trash(&mut self) {
         let now = Instant::now();
         self.trash_v4.retain(|_, v| v.vanish_at > now);
@@ -332,7 +801,8 @@ impl ResolverRuntime {
 
     fn poll_loop(&mut self, cx: &mut Context<'_>) -> Poll<anyhow::Result<()>> {
         if self.driver.is_none() {
-            self.driver = Some(self.config.driver.spawn_resolver_driver()?);
+            self.driver = Some(ResolveDrivers::spawn(&self.config)?);
+            self.reset_warm_up();
         }
 
         self.clean_trash();
@@ -372,7 +842,7 @@ impl ResolverRuntime {
                     Poll::Ready(None) => break, // all items fetched
                     Poll::Ready(Some(t)) => {
                         update_mem_stats = true;
-                        self.handle_expired_v4(t.get_ref());
+                        self.handle_expired_v4(t.into_inner());
                     }
                 }
             }
@@ -382,15 +852,50 @@ impl ResolverRuntime {
                     Poll::Ready(None) => break, // all items fetched
                     Poll::Ready(Some(t)) => {
                         update_mem_stats = true;
-                        self.handle_expired_v6(t.get_ref());
+                        self.handle_expired_v6(t.into_inner());
                     }
                 }
             }
 
-            if update_mem_stats {
+            loop {
+                match self.neg_expired_v4.poll_expired(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => break, // all items fetched
+                    Poll::Ready(Some(t)) => {
+                        trace!(
+                            "clean expired negative v4 for domain {}",
+                            t.get_ref().domain
+                        );
+                        update_mem_stats = true;
+                        self.neg_cache_v4.remove(t.get_ref());
+                    }
+                }
+            }
+            loop {
+                match self.neg_expired_v6.poll_expired(cx) {
+                    Poll::Pending => break,
+                    Poll::Ready(None) => break, // all items fetched
+                    Poll::Ready(Some(t)) => {
+                        trace!(
+                            "clean expired negative v6 for domain {}",
+                            t.get_ref().domain
+                        );
+                        update_mem_stats = true;
+                        self.neg_cache_v6.remove(t.get_ref());
+                    }
+                }
+            }
+
+            if update_mem_stats && self.report_mem_stats {
                 self.update_mem_stats();
             }
 
+            // handle in-flight timeout
+            self.poll_inflight_check(cx);
+
+            // handle warm up
+            self.poll_warm_up(cx);
+
             // handle request
             for _ in 1..self.config.runtime.batch_request_count {
                 let req = match self.req_receiver.poll_recv(cx) {
@@ -416,3 +921,308 @@ impl Future for ResolverRuntime {
         (*self).poll_loop(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AnyResolveDriverConfig, ResolveError, ResolveLocalError, ResolveServerError};
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn inflight_timeout() {
+        let stats = ResolverStats::default();
+        let now = Instant::now();
+        let mut doing = AHashMap::new();
+
+        let (old_sender, mut old_receiver) = oneshot::channel();
+        let mut old = DoingQuery::new(vec![old_sender]);
+        old.created = now - Duration::from_secs(10);
+        doing.insert(CacheKey::new(Arc::from("old.example.net"), None), old);
+
+        let (new_sender, mut new_receiver) = oneshot::channel();
+        let mut new = DoingQuery::new(vec![new_sender]);
+        new.created = now - Duration::from_secs(2);
+        doing.insert(CacheKey::new(Arc::from("new.example.net"), None), new);
+
+        let age = ResolverRuntime::check_inflight(
+            &mut doing,
+            &stats.query_a,
+            Duration::from_secs(5),
+            now,
+        );
+        assert_eq!(age, Duration::from_secs(2));
+        assert_eq!(doing.len(), 1);
+        assert!(doing.contains_key(&CacheKey::new(Arc::from("new.example.net"), None)));
+
+        let (record, _) = old_receiver.try_recv().unwrap();
+        assert!(matches!(
+            record.result,
+            Err(ResolveError::FromLocal(ResolveLocalError::DriverTimedOut))
+        ));
+        assert!(new_receiver.try_recv().is_err());
+        assert_eq!(stats.snapshot().query_a.driver_timeout, 1);
+    }
+
+    fn new_runtime() -> ResolverRuntime {
+        let config = ResolverConfig {
+            name: "test".to_string(),
+            driver: AnyResolveDriverConfig::FailOver(Default::default()),
+            suffix_drivers: Vec::new(),
+            runtime: Default::default(),
+        };
+        let (_req_sender, req_receiver) = mpsc::unbounded_channel();
+        let (_ctl_sender, ctl_receiver) = mpsc::unbounded_channel();
+        ResolverRuntime::new(
+            config,
+            req_receiver,
+            ctl_receiver,
+            Arc::new(ResolverStats::default()),
+        )
+    }
+
+    fn get_v4_cached(runtime: &mut ResolverRuntime, domain: &Arc<str>) -> ArcResolvedRecord {
+        let (sender, mut receiver) = oneshot::channel();
+        runtime.handle_req(ResolveDriverRequest::GetV4(domain.clone(), None, sender));
+        let (record, source) = receiver.try_recv().unwrap();
+        assert!(matches!(source, ResolvedRecordSource::Cache));
+        record
+    }
+
+    #[tokio::test]
+    async fn cache_by_subnet() {
+        let mut runtime = new_runtime();
+
+        let domain: Arc<str> = Arc::from("www.example.net");
+        let subnet1 = ClientSubnet::from_str("192.0.2.0/24").unwrap();
+        let subnet2 = ClientSubnet::from_str("198.51.100.0/24").unwrap();
+        let ip1 = IpAddr::from_str("203.0.113.1").unwrap();
+        let ip2 = IpAddr::from_str("203.0.113.2").unwrap();
+
+        for (subnet, ip) in [(subnet1, ip1), (subnet2, ip2)] {
+            let record = ResolvedRecord::resolved(domain.clone(), 300, 30, 3600, vec![ip]);
+            runtime.handle_rsp(ResolveDriverResponse::V4(record, Some(subnet)));
+        }
+        assert_eq!(runtime.cache_v4.len(), 2);
+
+        for (subnet, ip) in [(subnet1, ip1), (subnet2, ip2)] {
+            let (sender, mut receiver) = oneshot::channel();
+            runtime.handle_req(ResolveDriverRequest::GetV4(
+                domain.clone(),
+                Some(subnet),
+                sender,
+            ));
+            let (record, source) = receiver.try_recv().unwrap();
+            assert!(matches!(source, ResolvedRecordSource::Cache));
+            assert_eq!(record.result.as_ref().unwrap(), &vec![ip]);
+        }
+    }
+
+    #[tokio::test]
+    async fn negative_cache() {
+        let mut runtime = new_runtime();
+        let domain: Arc<str> = Arc::from("none.example.net");
+
+        let record = ResolvedRecord::failed(
+            domain.clone(),
+            300,
+            ResolveError::FromServer(ResolveServerError::NotFound),
+        );
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert!(runtime.cache_v4.is_empty());
+        assert_eq!(runtime.neg_cache_v4.len(), 1);
+
+        let record = get_v4_cached(&mut runtime, &domain);
+        assert!(record.is_negative());
+        let snap = runtime.stats.snapshot();
+        assert_eq!(snap.query_a.neg_cached, 1);
+        assert_eq!(snap.query_a.cached, 0);
+
+        // a positive record replaces the negative one
+        let ip = IpAddr::from_str("203.0.113.1").unwrap();
+        let record = ResolvedRecord::resolved(domain.clone(), 300, 30, 3600, vec![ip]);
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert!(runtime.neg_cache_v4.is_empty());
+        assert_eq!(runtime.cache_v4.len(), 1);
+
+        // and a NODATA record replaces the positive one
+        let record = ResolvedRecord::empty(domain.clone(), 300);
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert!(runtime.cache_v4.is_empty());
+        assert_eq!(runtime.neg_cache_v4.len(), 1);
+        assert!(runtime.expired_v4.is_empty());
+
+        let record = get_v4_cached(&mut runtime, &domain);
+        assert!(record.is_ok());
+        assert!(!record.is_usable());
+        assert_eq!(runtime.stats.snapshot().query_a.neg_cached, 2);
+    }
+
+    #[test]
+    fn warm_up_queue() {
+        let mut queue = WarmUpQueue::default();
+        queue.push(Arc::from("a.example.net"));
+        queue.push(Arc::from("b.example.net"));
+        queue.push(Arc::from("a.example.net"));
+        assert_eq!(queue.queue.len(), 2);
+
+        assert_eq!(queue.pop().as_deref(), Some("a.example.net"));
+        // can be queued again after popped
+        queue.push(Arc::from("a.example.net"));
+        assert_eq!(queue.pop().as_deref(), Some("b.example.net"));
+        assert_eq!(queue.pop().as_deref(), Some("a.example.net"));
+        assert!(queue.pop().is_none());
+        assert!(queue.is_empty());
+        assert!(queue.queued.is_empty());
+    }
+
+    #[tokio::test]
+    async fn warm_up() {
+        let mut runtime = new_runtime();
+        runtime.config.runtime.warm_up_domains = vec![
+            Arc::from("a.example.net"),
+            Arc::from("b.example.net"),
+            Arc::from("a.example.net"),
+        ];
+        runtime.config.runtime.warm_up_batch_size = 1;
+        runtime.driver = Some(ResolveDrivers::spawn(&runtime.config).unwrap());
+        runtime.reset_warm_up();
+        assert_eq!(runtime.warm_up_queue.queue.len(), 2);
+
+        runtime.handle_cmd(ResolverCommand::WarmUp(vec![
+            Arc::from("b.example.net"),
+            Arc::from("c.example.net"),
+        ]));
+        assert_eq!(runtime.warm_up_queue.queue.len(), 3);
+
+        // only one batch is sent at each tick
+        std::future::poll_fn(|cx| {
+            runtime.poll_warm_up(cx);
+            if runtime.warm_up_queue.queue.len() < 3 {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        })
+        .await;
+        assert_eq!(runtime.warm_up_queue.queue.len(), 2);
+        assert!(
+            runtime
+                .doing_v4
+                .contains_key(&CacheKey::new(Arc::from("a.example.net"), None))
+        );
+        assert_eq!(runtime.doing_v6.len(), 1);
+        let snap = runtime.stats.snapshot();
+        assert_eq!(snap.query_a.driver, 1);
+        assert_eq!(snap.query_aaaa.driver, 1);
+    }
+
+    #[tokio::test]
+    async fn warm_up_disabled() {
+        let mut runtime = new_runtime();
+        runtime.config.runtime.warm_up_domains = vec![Arc::from("a.example.net")];
+        runtime.disable_warm_up();
+        runtime.reset_warm_up();
+        assert!(runtime.warm_up_queue.is_empty());
+
+        runtime.handle_cmd(ResolverCommand::WarmUp(vec![Arc::from("b.example.net")]));
+        assert!(runtime.warm_up_queue.is_empty());
+    }
+
+    #[tokio::test]
+    async fn negative_cache_max_size() {
+        let mut runtime = new_runtime();
+        runtime.config.runtime.negative_cache_max_size = 2;
+
+        for name in ["a.example.net", "b.example.net", "c.example.net"] {
+            let record = ResolvedRecord::empty(Arc::from(name), 300);
+            runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        }
+        assert_eq!(runtime.neg_cache_v4.len(), 2);
+        assert_eq!(runtime.neg_expired_v4.len(), 2);
+        assert!(
+            !runtime
+                .neg_cache_v4
+                .contains_key(&CacheKey::new(Arc::from("c.example.net"), None))
+        );
+
+        // existed ones can still be updated
+        let domain: Arc<str> = Arc::from("a.example.net");
+        let record = ResolvedRecord::failed(
+            domain.clone(),
+            300,
+            ResolveError::FromServer(ResolveServerError::NotFound),
+        );
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert_eq!(runtime.neg_cache_v4.len(), 2);
+        let record = get_v4_cached(&mut runtime, &domain);
+        assert!(record.is_err());
+
+        runtime.update_mem_stats();
+        let snap = runtime.stats.snapshot();
+        assert_eq!(snap.memory_a.len_neg_cache, 2);
+        assert!(snap.memory_a.cap_neg_cache >= 2);
+        assert_eq!(snap.memory_aaaa.len_neg_cache, 0);
+    }
+
+    #[tokio::test]
+    async fn prefetch() {
+        let mut runtime = new_runtime();
+        runtime.config.runtime.prefetch_ratio = Some(0.8);
+        runtime.driver = Some(ResolveDrivers::spawn(&runtime.config).unwrap());
+
+        let domain: Arc<str> = Arc::from("hot.example.net");
+        let key = CacheKey::new(domain.clone(), None);
+        let ip = IpAddr::from_str("203.0.113.1").unwrap();
+        let mut record = ResolvedRecord::resolved(domain.clone(), 300, 30, 3600, vec![ip]);
+        runtime.handle_rsp(ResolveDriverResponse::V4(record.clone(), None));
+
+        // not yet reached the prefetch point
+        get_v4_cached(&mut runtime, &domain);
+        assert!(runtime.doing_v4.is_empty());
+        assert_eq!(runtime.stats.snapshot().query_a.prefetched, 0);
+
+        // 90% of the cache ttl has passed
+        let now = Instant::now();
+        record.created = now - Duration::from_secs(243);
+        record.expire = Some(now + Duration::from_secs(27));
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+
+        for _ in 0..3 {
+            let record = get_v4_cached(&mut runtime, &domain);
+            assert_eq!(record.result.as_ref().unwrap(), &vec![ip]);
+        }
+        assert!(runtime.doing_v4.contains_key(&key));
+        let snap = runtime.stats.snapshot();
+        assert_eq!(snap.query_a.prefetched, 1);
+        assert_eq!(snap.query_a.driver, 1);
+        assert_eq!(snap.query_a.cached, 4);
+
+        // a failed prefetch should not replace the cached record
+        let record = ResolvedRecord::timed_out(domain.clone(), 30);
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert!(runtime.doing_v4.is_empty());
+        assert!(runtime.cache_v4.get(&key).unwrap().prefetched);
+
+        // and no more prefetch for the same cached record
+        for _ in 0..3 {
+            let record = get_v4_cached(&mut runtime, &domain);
+            assert_eq!(record.result.as_ref().unwrap(), &vec![ip]);
+        }
+        assert!(runtime.doing_v4.is_empty());
+        let snap = runtime.stats.snapshot();
+        assert_eq!(snap.query_a.prefetched, 1);
+        assert_eq!(snap.query_a.driver, 1);
+        assert_eq!(snap.query_a.cached, 7);
+
+        // a new record can be prefetched again
+        let mut record = ResolvedRecord::resolved(domain.clone(), 300, 30, 3600, vec![ip]);
+        record.created = now - Duration::from_secs(243);
+        record.expire = Some(now + Duration::from_secs(27));
+        runtime.handle_rsp(ResolveDriverResponse::V4(record, None));
+        assert!(!runtime.cache_v4.get(&key).unwrap().prefetched);
+        get_v4_cached(&mut runtime, &domain);
+        assert!(runtime.doing_v4.contains_key(&key));
+        assert_eq!(runtime.stats.snapshot().query_a.prefetched, 2);
+    }
+}
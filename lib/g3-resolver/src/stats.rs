@@ -4,6 +4,7 @@
  */
 
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
 
 use super::{
     ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError, ResolvedRecord,
@@ -13,8 +14,10 @@ use super::{
 pub struct ResolverQueryStats {
     query_total: AtomicU64,
     query_cached: AtomicU64,
+    query_neg_cached: AtomicU64,
     query_driver: AtomicU64,
     query_trashed: AtomicU64,
+    query_prefetched: AtomicU64,
     driver_timeout: AtomicU64,
     driver_refused: AtomicU64,
     driver_malformed: AtomicU64,
@@ -22,14 +25,18 @@ pub struct ResolverQueryStats {
     server_malformed: AtomicU64,
     server_not_found: AtomicU64,
     server_serv_fail: AtomicU64,
+    dnssec_validated: AtomicU64,
+    dnssec_unvalidated: AtomicU64,
 }
 
 #[derive(Default)]
 pub struct ResolverQuerySnapshot {
     pub total: u64,
     pub cached: u64,
+    pub neg_cached: u64,
     pub driver: u64,
     pub trashed: u64,
+    pub prefetched: u64,
     pub driver_timeout: u64,
     pub driver_refused: u64,
     pub driver_malformed: u64,
@@ -37,6 +44,8 @@ pub struct ResolverQuerySnapshot {
     pub server_malformed: u64,
     pub server_not_found: u64,
     pub server_serv_fail: u64,
+    pub dnssec_validated: u64,
+    pub dnssec_unvalidated: u64,
 }
 
 impl ResolverQueryStats {
@@ -44,8 +53,10 @@ impl ResolverQueryStats {
         ResolverQuerySnapshot {
             total: self.query_total.load(Ordering::Relaxed),
             cached: self.query_cached.load(Ordering::Relaxed),
+            neg_cached: self.query_neg_cached.load(Ordering::Relaxed),
             driver: self.query_driver.load(Ordering::Relaxed),
             trashed: self.query_trashed.load(Ordering::Relaxed),
+            prefetched: self.query_prefetched.load(Ordering::Relaxed),
             driver_timeout: self.driver_timeout.load(Ordering::Relaxed),
             driver_refused: self.driver_refused.load(Ordering::Relaxed),
             driver_malformed: self.driver_malformed.load(Ordering::Relaxed),
@@ -53,6 +64,8 @@ impl ResolverQueryStats {
             server_malformed: self.server_malformed.load(Ordering::Relaxed),
             server_not_found: self.server_not_found.load(Ordering::Relaxed),
             server_serv_fail: self.server_serv_fail.load(Ordering::Relaxed),
+            dnssec_validated: self.dnssec_validated.load(Ordering::Relaxed),
+            dnssec_unvalidated: self.dnssec_unvalidated.load(Ordering::Relaxed),
         }
     }
 
@@ -70,6 +83,16 @@ impl ResolverQueryStats {
         }
     }
 
+    pub(crate) fn add_query_neg_cached(&self) {
+        self.add_query_neg_cached_n(1);
+    }
+
+    pub(crate) fn add_query_neg_cached_n(&self, n: usize) {
+        if n > 0 {
+            self.query_neg_cached.fetch_add(n as u64, Ordering::Relaxed);
+        }
+    }
+
     pub(crate) fn add_query_driver(&self) {
         self.query_driver.fetch_add(1, Ordering::Relaxed);
     }
@@ -84,6 +107,10 @@ impl ResolverQueryStats {
         }
     }
 
+    pub(crate) fn add_query_prefetched(&self) {
+        self.query_prefetched.fetch_add(1, Ordering::Relaxed);
+    }
+
     #[inline]
     fn add_driver_timeout(&self) {
         self.driver_timeout.fetch_add(1, Ordering::Relaxed);
@@ -120,6 +147,15 @@ impl ResolverQueryStats {
     }
 
     pub(crate) fn add_record(&self, record: &ResolvedRecord) {
+        match record.dnssec_validated {
+            Some(true) => {
+                self.dnssec_validated.fetch_add(1, Ordering::Relaxed);
+            }
+            Some(false) => {
+                self.dnssec_unvalidated.fetch_add(1, Ordering::Relaxed);
+            }
+            None => {}
+        }
         if let Err(e) = &record.result {
             self.add_error(e);
         }
@@ -162,8 +198,11 @@ pub(crate) struct ResolverMemoryStats {
     len_cache: AtomicUsize,
     cap_doing: AtomicUsize,
     len_doing: AtomicUsize,
+    age_doing: AtomicU64,
     cap_trash: AtomicUsize,
     len_trash: AtomicUsize,
+    cap_neg_cache: AtomicUsize,
+    len_neg_cache: AtomicUsize,
 }
 
 #[derive(Default)]
@@ -172,8 +211,12 @@ pub struct ResolverMemorySnapshot {
     pub len_cache: usize,
     pub cap_doing: usize,
     pub len_doing: usize,
+    /// age of the oldest in-flight query, in milliseconds
+    pub age_doing: u64,
     pub cap_trash: usize,
     pub len_trash: usize,
+    pub cap_neg_cache: usize,
+    pub len_neg_cache: usize,
 }
 
 impl ResolverMemoryStats {
@@ -183,8 +226,11 @@ impl ResolverMemoryStats {
             len_cache: self.len_cache.load(Ordering::Relaxed),
             cap_doing: self.cap_doing.load(Ordering::Relaxed),
             len_doing: self.len_doing.load(Ordering::Relaxed),
+            age_doing: self.age_doing.load(Ordering::Relaxed),
             cap_trash: self.cap_trash.load(Ordering::Relaxed),
             len_trash: self.len_trash.load(Ordering::Relaxed),
+            cap_neg_cache: self.cap_neg_cache.load(Ordering::Relaxed),
+            len_neg_cache: self.len_neg_cache.load(Ordering::Relaxed),
         }
     }
 
@@ -204,6 +250,11 @@ impl ResolverMemoryStats {
         self.len_doing.store(value, Ordering::Relaxed);
     }
 
+    pub(crate) fn set_doing_max_age(&self, value: Duration) {
+        let millis = u64::try_from(value.as_millis()).unwrap_or(u64::MAX);
+        self.age_doing.store(millis, Ordering::Relaxed);
+    }
+
     pub(crate) fn set_trash_capacity(&self, value: usize) {
         self.cap_trash.store(value, Ordering::Relaxed);
     }
@@ -211,6 +262,14 @@ impl ResolverMemoryStats {
     pub(crate) fn set_trash_length(&self, value: usize) {
         self.len_trash.store(value, Ordering::Relaxed);
     }
+
+    pub(crate) fn set_neg_cache_capacity(&self, value: usize) {
+        self.cap_neg_cache.store(value, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_neg_cache_length(&self, value: usize) {
+        self.len_neg_cache.store(value, Ordering::Relaxed);
+    }
 }
 
 #[derive(Default)]
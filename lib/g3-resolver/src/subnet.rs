@@ -0,0 +1,104 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+/// The client subnet to be sent in the EDNS Client Subnet (RFC 7871) option
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ClientSubnet {
+    addr: IpAddr,
+    prefix: u8,
+}
+
+impl ClientSubnet {
+    /// Create a new client subnet, the host bits of the address will be cleared
+    pub fn new(addr: IpAddr, prefix: u8) -> anyhow::Result<Self> {
+        let addr = match addr {
+            IpAddr::V4(ip) => {
+                if prefix > 32 {
+                    return Err(anyhow!("too large prefix length {prefix} for ipv4"));
+                }
+                let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from_bits(ip.to_bits() & mask))
+            }
+            IpAddr::V6(ip) => {
+                if prefix > 128 {
+                    return Err(anyhow!("too large prefix length {prefix} for ipv6"));
+                }
+                let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from_bits(ip.to_bits() & mask))
+            }
+        };
+        Ok(ClientSubnet { addr, prefix })
+    }
+
+    #[inline]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    #[inline]
+    pub fn prefix(&self) -> u8 {
+        self.prefix
+    }
+}
+
+impl FromStr for ClientSubnet {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, prefix) = match s.split_once('/') {
+            Some((addr, prefix)) => {
+                let prefix = u8::from_str(prefix)
+                    .map_err(|e| anyhow!("invalid prefix length {prefix}: {e}"))?;
+                (addr, Some(prefix))
+            }
+            None => (s, None),
+        };
+        let addr = IpAddr::from_str(addr).map_err(|e| anyhow!("invalid ip address {addr}: {e}"))?;
+        let prefix = prefix.unwrap_or(match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        });
+        ClientSubnet::new(addr, prefix)
+    }
+}
+
+impl fmt::Display for ClientSubnet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let subnet = ClientSubnet::from_str("192.168.1.100/24").unwrap();
+        assert_eq!(subnet.addr(), IpAddr::from_str("192.168.1.0").unwrap());
+        assert_eq!(subnet.prefix(), 24);
+        assert_eq!(subnet.to_string(), "192.168.1.0/24");
+        assert_eq!(subnet, ClientSubnet::from_str("192.168.1.1/24").unwrap());
+
+        let subnet = ClientSubnet::from_str("2001:db8:1:2::1/56").unwrap();
+        assert_eq!(subnet.to_string(), "2001:db8:1::/56");
+
+        let subnet = ClientSubnet::from_str("10.0.0.1").unwrap();
+        assert_eq!(subnet.to_string(), "10.0.0.1/32");
+
+        let subnet = ClientSubnet::from_str("10.0.0.1/0").unwrap();
+        assert_eq!(subnet.to_string(), "0.0.0.0/0");
+
+        assert!(ClientSubnet::from_str("10.0.0.1/33").is_err());
+        assert!(ClientSubnet::from_str("2001:db8::/129").is_err());
+        assert!(ClientSubnet::from_str("example.net/24").is_err());
+    }
+}
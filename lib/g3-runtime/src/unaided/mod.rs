@@ -74,6 +74,11 @@ impl UnaidedRuntimeConfig {
         self.thread_number_total = num;
     }
 
+    #[inline]
+    pub fn thread_number_total(&self) -> NonZeroUsize {
+        self.thread_number_total
+    }
+
     pub fn set_thread_stack_size(&mut self, size: usize) {
         self.thread_stack_size = Some(size);
     }
@@ -82,6 +87,17 @@ impl UnaidedRuntimeConfig {
         self.sched_affinity.insert(id, cpus);
     }
 
+    #[inline]
+    pub fn sched_affinity(&self) -> &HashMap<usize, CpuAffinity> {
+        &self.sched_affinity
+    }
+
+    /// Get the number of the worker runtimes that will be started
+    #[inline]
+    pub fn runtime_number(&self) -> usize {
+        self.thread_number_total.get() / self.thread_number_per_rt.get()
+    }
+
     #[cfg(any(
         target_os = "linux",
         target_os = "android",
@@ -0,0 +1,246 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+//! Listen sockets inherited from the parent process.
+//!
+//! The protocol is the same as systemd socket activation:
+//!  - `LISTEN_PID`: the pid of the process that should take the sockets
+//!  - `LISTEN_FDS`: the number of the passed fds, which start from fd 3
+//!
+//! The inherited sockets will be matched against the listen address when creating
+//! new listeners, and fresh sockets will be created if no one matches. If a listen
+//! config needs multiple instances, the same number of sockets should be inherited.
+//!
+//! Only the receiving side is implemented. The daemons will not pass their listen sockets
+//! to the new process, it should be done by systemd or other process managers.
+
+use std::env;
+use std::io;
+use std::net::SocketAddr;
+use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+use std::sync::Mutex;
+
+use socket2::{Socket, Type};
+
+const ENV_LISTEN_PID: &str = "LISTEN_PID";
+const ENV_LISTEN_FDS: &str = "LISTEN_FDS";
+const ENV_LISTEN_FDNAMES: &str = "LISTEN_FDNAMES";
+const LISTEN_FDS_START: RawFd = 3;
+
+static INHERITED_SOCKETS: Mutex<Vec<Socket>> = Mutex::new(Vec::new());
+
+/// Load the inherited sockets from environment variables.
+///
+/// This should be called at the very beginning of the process, before spawning any threads
+/// (including the logger threads) or forking into background, as the environment variables
+/// will be removed after loaded. The ownership of the fds will be taken.
+/// Returns the number of loaded sockets.
+pub fn load_from_env() -> io::Result<usize> {
+    let pid = env::var(ENV_LISTEN_PID).ok();
+    let fds = env::var(ENV_LISTEN_FDS).ok();
+    let Some(fds) = parse_env(pid.as_deref(), fds.as_deref(), std::process::id())? else {
+        // not set, or passed to another process
+        return Ok(0);
+    };
+
+    // SAFETY: called at startup before any other threads are spawned
+    unsafe {
+        env::remove_var(ENV_LISTEN_PID);
+        env::remove_var(ENV_LISTEN_FDS);
+        env::remove_var(ENV_LISTEN_FDNAMES);
+    }
+
+    let mut sockets = INHERITED_SOCKETS.lock().unwrap();
+    for i in 0..fds {
+        let fd = LISTEN_FDS_START + i as RawFd;
+        // do not leak the fd to child processes
+        let flags = unsafe { libc::fcntl(fd, libc::F_GETFD) };
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, flags | libc::FD_CLOEXEC) } < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        // SAFETY: the fd is passed to this process and is valid as checked above
+        let socket = Socket::from(unsafe { OwnedFd::from_raw_fd(fd) });
+        socket.local_addr()?;
+        sockets.push(socket);
+    }
+    Ok(sockets.len())
+}
+
+/// Get the number of fds passed to the process with id `self_pid`
+fn parse_env(pid: Option<&str>, fds: Option<&str>, self_pid: u32) -> io::Result<Option<usize>> {
+    let Some(pid) = pid else {
+        return Ok(None);
+    };
+    let pid = pid.parse::<u32>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid {ENV_LISTEN_PID} value {pid}: {e}"),
+        )
+    })?;
+    if pid != self_pid {
+        return Ok(None);
+    }
+    let fds = fds.unwrap_or_default();
+    let fds = fds.parse::<usize>().map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid {ENV_LISTEN_FDS} value {fds}: {e}"),
+        )
+    })?;
+    Ok(Some(fds))
+}
+
+fn is_tcp_listener_at(socket: &Socket, addr: SocketAddr) -> io::Result<bool> {
+    if socket.r#type()? != Type::STREAM {
+        return Ok(false);
+    }
+    Ok(socket.local_addr()?.as_socket() == Some(addr))
+}
+
+/// Check that there are enough inherited tcp listen sockets for `instance` listeners at `addr`.
+///
+/// It's ok if no socket is inherited, then fresh sockets will be created. But if only part
+/// of the instances can take an inherited one, the fresh bind of the others will fail if
+/// the parent process didn't set SO_REUSEPORT, so an error will be returned instead.
+pub fn check_tcp_listeners(addr: SocketAddr, instance: usize) -> io::Result<()> {
+    if addr.port() == 0 {
+        return Ok(());
+    }
+    let sockets = INHERITED_SOCKETS.lock().unwrap();
+    let mut count = 0;
+    for socket in sockets.iter() {
+        if is_tcp_listener_at(socket, addr)? {
+            count += 1;
+        }
+    }
+    if count != 0 && count != instance {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{count} listen sockets inherited for {addr}, but {instance} instances needed"),
+        ));
+    }
+    Ok(())
+}
+
+/// Take the inherited tcp listen socket that bound to `addr`.
+///
+/// The socket will be removed from the inherited ones, so it will be closed
+/// when the listener that takes it is closed.
+pub(crate) fn take_tcp_listener(addr: SocketAddr) -> io::Result<Option<Socket>> {
+    let mut sockets = INHERITED_SOCKETS.lock().unwrap();
+    let mut found = None;
+    for (i, socket) in sockets.iter().enumerate() {
+        if is_tcp_listener_at(socket, addr)? {
+            found = Some(i);
+            break;
+        }
+    }
+    Ok(found.map(|i| sockets.swap_remove(i)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::os::fd::AsRawFd;
+
+    use socket2::{Domain, SockAddr};
+
+    use g3_types::net::TcpListenConfig;
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_env(None, Some("2"), 100).unwrap(), None);
+        assert_eq!(parse_env(Some("101"), Some("2"), 100).unwrap(), None);
+        assert_eq!(parse_env(Some("100"), Some("2"), 100).unwrap(), Some(2));
+        assert!(parse_env(Some("100"), None, 100).is_err());
+        assert!(parse_env(Some("100"), Some("x"), 100).is_err());
+        assert!(parse_env(Some("x"), Some("2"), 100).is_err());
+    }
+
+    #[test]
+    fn take() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        INHERITED_SOCKETS
+            .lock()
+            .unwrap()
+            .push(Socket::from(OwnedFd::from(listener)));
+
+        let socket = take_tcp_listener(addr).unwrap().unwrap();
+        assert_eq!(socket.local_addr().unwrap().as_socket(), Some(addr));
+        // the ownership is taken
+        assert!(take_tcp_listener(addr).unwrap().is_none());
+    }
+
+    #[test]
+    fn listen_inherited() {
+        let socket = Socket::new(Domain::IPV4, Type::STREAM, None).unwrap();
+        let bind_addr = SockAddr::from(SocketAddr::from(([127, 0, 0, 1], 0)));
+        socket.bind(&bind_addr).unwrap();
+        let addr = socket.local_addr().unwrap().as_socket().unwrap();
+        let fd = socket.as_raw_fd();
+        INHERITED_SOCKETS.lock().unwrap().push(socket);
+
+        let listener = crate::tcp::new_std_listener(&TcpListenConfig::new(addr)).unwrap();
+        assert_eq!(listener.local_addr().unwrap(), addr);
+        assert_eq!(listener.as_raw_fd(), fd);
+        assert!(take_tcp_listener(addr).unwrap().is_none());
+    }
+
+    #[test]
+    fn check_instances() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // nothing inherited, fresh sockets will be created
+        assert!(check_tcp_listeners(addr, 2).is_ok());
+
+        INHERITED_SOCKETS
+            .lock()
+            .unwrap()
+            .push(Socket::from(OwnedFd::from(listener)));
+        assert!(check_tcp_listeners(addr, 1).is_ok());
+        assert!(check_tcp_listeners(addr, 2).is_err());
+
+        assert!(take_tcp_listener(addr).unwrap().is_some());
+        assert!(check_tcp_listeners(addr, 2).is_ok());
+    }
+
+    #[test]
+    fn ipv6only_mismatch() {
+        let Ok(socket) = Socket::new(Domain::IPV6, Type::STREAM, None) else {
+            return;
+        };
+        socket.set_only_v6(true).unwrap();
+        let bind_addr = SockAddr::from(SocketAddr::from(([0u16; 8], 0)));
+        if socket.bind(&bind_addr).is_err() {
+            return;
+        }
+        let addr = socket.local_addr().unwrap().as_socket().unwrap();
+        INHERITED_SOCKETS.lock().unwrap().push(socket);
+
+        let mut config = TcpListenConfig::new(addr);
+        config.set_ipv6_only(false);
+        assert!(crate::tcp::new_std_listener(&config).is_err());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn transparent_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        INHERITED_SOCKETS
+            .lock()
+            .unwrap()
+            .push(Socket::from(OwnedFd::from(listener)));
+
+        let mut config = TcpListenConfig::new(addr);
+        config.set_transparent();
+        assert!(crate::tcp::new_std_listener(&config).is_err());
+    }
+}
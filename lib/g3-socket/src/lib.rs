@@ -10,6 +10,9 @@ pub use raw::RawSocket;
 
 mod listen;
 
+#[cfg(unix)]
+pub mod inherit;
+
 pub mod tcp;
 pub mod udp;
 pub mod util;
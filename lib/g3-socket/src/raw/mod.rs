@@ -40,6 +40,16 @@ impl RawSocket {
         Ok(())
     }
 
+    /// Get the socket buffer size in use, which may be different from the value we set,
+    /// as the kernel may double or limit it.
+    pub fn get_buf_opts(&self) -> io::Result<SocketBufferConfig> {
+        let socket = self.get_inner()?;
+        let mut buf_conf = SocketBufferConfig::default();
+        buf_conf.set_recv_size(socket.recv_buffer_size()?);
+        buf_conf.set_send_size(socket.send_buffer_size()?);
+        Ok(buf_conf)
+    }
+
     pub fn set_tcp_misc_opts(
         &self,
         family: AddressFamily,
@@ -52,6 +62,12 @@ impl RawSocket {
         } else if default_set_nodelay {
             socket.set_tcp_nodelay(true)?;
         }
+        if let Some(size) = misc_opts.send_buffer_size {
+            socket.set_send_buffer_size(size)?;
+        }
+        if let Some(size) = misc_opts.recv_buffer_size {
+            socket.set_recv_buffer_size(size)?;
+        }
         #[cfg(unix)]
         if let Some(mss) = misc_opts.max_segment_size {
             socket.set_tcp_mss(mss)?;
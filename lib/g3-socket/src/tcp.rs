@@ -17,6 +17,13 @@ use super::{BindAddr, RawSocket};
 
 pub fn new_std_listener(config: &TcpListenConfig) -> io::Result<std::net::TcpListener> {
     let addr = config.address();
+    #[cfg(unix)]
+    if addr.port() != 0
+        && let Some(socket) = super::inherit::take_tcp_listener(addr)?
+    {
+        return setup_inherited_listener(socket, config);
+    }
+
     let family = AddressFamily::from(&addr);
     let socket = new_tcp_socket(family)?;
     super::listen::set_addr_reuse(&socket, addr)?;
@@ -64,6 +71,58 @@ pub fn new_std_listener(config: &TcpListenConfig) -> io::Result<std::net::TcpLis
     Ok(std::net::TcpListener::from(socket))
 }
 
+#[cfg(unix)]
+fn setup_inherited_listener(
+    socket: Socket,
+    config: &TcpListenConfig,
+) -> io::Result<std::net::TcpListener> {
+    let addr = config.address();
+    // the following options can only be set before bind, which is done by the parent process
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("transparent can not be set on the inherited listen socket for {addr}"),
+        ));
+    }
+    #[cfg(not(target_os = "openbsd"))]
+    if let Some(enable) = config.is_ipv6only()
+        && addr.is_ipv6()
+        && addr.ip().is_unspecified()
+        && socket.only_v6()? != enable
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("ipv6only mismatch for the inherited listen socket for {addr}"),
+        ));
+    }
+
+    socket.set_nonblocking(true)?;
+    #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+    if let Some(mark) = config.mark() {
+        socket.set_mark(mark)?;
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    if let Some(iface) = config.interface() {
+        socket.bind_device(Some(iface.c_bytes()))?;
+    }
+    if let Some(keepalive_config) = config.keepalive()
+        && let Some(setting) = enable_tcp_keepalive(keepalive_config)
+    {
+        socket.set_tcp_keepalive(&setting)?;
+    }
+    #[cfg(any(target_os = "macos", target_os = "illumos", target_os = "solaris"))]
+    if let Some(iface) = config.interface() {
+        match AddressFamily::from(&addr) {
+            AddressFamily::Ipv4 => socket.bind_device_by_index_v4(Some(iface.id()))?,
+            AddressFamily::Ipv6 => socket.bind_device_by_index_v6(Some(iface.id()))?,
+        }
+    }
+    // update the backlog
+    socket.listen(config.backlog() as i32)?;
+    Ok(std::net::TcpListener::from(socket))
+}
+
 pub fn new_std_socket_to(
     peer_ip: IpAddr,
     bind: &BindAddr,
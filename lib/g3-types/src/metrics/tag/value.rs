@@ -16,6 +16,12 @@ pub struct MetricTagValue(SmolStr);
 impl MetricTagValue {
     pub const EMPTY: MetricTagValue = MetricTagValue(SmolStr::new_static(""));
 
+    /// # Safety
+    /// The characters in `s` is not checked
+    pub const unsafe fn new_static_unchecked(s: &'static str) -> Self {
+        MetricTagValue(SmolStr::new_static(s))
+    }
+
     #[inline]
     pub fn as_str(&self) -> &str {
         self.0.as_str()
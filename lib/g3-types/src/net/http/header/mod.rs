@@ -12,9 +12,13 @@ pub use name::HttpOriginalHeaderName;
 pub use value::HttpHeaderValue;
 
 mod forwarded;
+mod rule;
 mod server_id;
+mod via;
 
 pub use forwarded::{
     HttpForwardedHeaderType, HttpForwardedHeaderValue, HttpStandardForwardedHeaderValue,
 };
+pub use rule::{HttpHeaderRule, HttpHeaderRuleAction, HttpHeaderRuleSet};
 pub use server_id::HttpServerId;
+pub use via::HttpViaHeaderConfig;
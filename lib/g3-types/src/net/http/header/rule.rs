@@ -0,0 +1,305 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use http::{HeaderName, HeaderValue, header};
+
+use super::HttpHeaderMap;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HttpHeaderRuleAction {
+    /// Add the header only if it's not present
+    Add,
+    /// Replace all existing values of the header
+    Set,
+    /// Append the header after all existing values
+    Append,
+    /// Remove all values of the header
+    Remove,
+}
+
+impl FromStr for HttpHeaderRuleAction {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" | "add_if_absent" => Ok(HttpHeaderRuleAction::Add),
+            "set" | "override" => Ok(HttpHeaderRuleAction::Set),
+            "append" => Ok(HttpHeaderRuleAction::Append),
+            "remove" | "delete" | "strip" => Ok(HttpHeaderRuleAction::Remove),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpHeaderRule {
+    action: HttpHeaderRuleAction,
+    name: HeaderName,
+    value: Option<HeaderValue>,
+}
+
+impl HttpHeaderRule {
+    pub fn new(
+        action: HttpHeaderRuleAction,
+        name: HeaderName,
+        value: Option<HeaderValue>,
+    ) -> anyhow::Result<Self> {
+        if is_reserved_header(&name) {
+            return Err(anyhow!("header {name} is not allowed to be changed"));
+        }
+        match action {
+            HttpHeaderRuleAction::Remove => {
+                if value.is_some() {
+                    return Err(anyhow!("no value should be set for the remove action"));
+                }
+            }
+            _ => {
+                if value.is_none() {
+                    return Err(anyhow!("value is required for action {action:?}"));
+                }
+            }
+        }
+        Ok(HttpHeaderRule {
+            action,
+            name,
+            value,
+        })
+    }
+
+    #[inline]
+    pub fn action(&self) -> HttpHeaderRuleAction {
+        self.action
+    }
+
+    #[inline]
+    pub fn name(&self) -> &HeaderName {
+        &self.name
+    }
+
+    fn apply(&self, headers: &mut HttpHeaderMap) {
+        match self.action {
+            HttpHeaderRuleAction::Add => {
+                if !headers.contains_key(&self.name)
+                    && let Some(value) = &self.value
+                {
+                    headers.insert(self.name.clone(), value.clone().into());
+                }
+            }
+            HttpHeaderRuleAction::Set => {
+                if let Some(value) = &self.value {
+                    headers.insert(self.name.clone(), value.clone().into());
+                }
+            }
+            HttpHeaderRuleAction::Append => {
+                if let Some(value) = &self.value {
+                    headers.append(self.name.clone(), value.clone().into());
+                }
+            }
+            HttpHeaderRuleAction::Remove => {
+                headers.remove(&self.name);
+            }
+        }
+    }
+}
+
+/// Headers that are used for message framing or routing, which should not be changed by rules
+fn is_reserved_header(name: &HeaderName) -> bool {
+    matches!(
+        *name,
+        header::HOST
+            | header::CONTENT_LENGTH
+            | header::TRANSFER_ENCODING
+            | header::CONNECTION
+            | header::TE
+            | header::TRAILER
+            | header::UPGRADE
+    ) || matches!(name.as_str(), "keep-alive" | "proxy-connection")
+}
+
+/// An ordered list of header rules, applied one by one
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HttpHeaderRuleSet {
+    rules: Vec<HttpHeaderRule>,
+}
+
+impl HttpHeaderRuleSet {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn push(&mut self, rule: HttpHeaderRule) {
+        self.rules.push(rule);
+    }
+
+    #[inline]
+    pub fn rules(&self) -> &[HttpHeaderRule] {
+        &self.rules
+    }
+
+    pub fn apply(&self, headers: &mut HttpHeaderMap) {
+        for rule in &self.rules {
+            rule.apply(headers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::HttpHeaderValue;
+
+    fn values(headers: &HttpHeaderMap, name: &str) -> Vec<String> {
+        headers
+            .get_all(name)
+            .iter()
+            .map(|v| v.to_str().to_string())
+            .collect()
+    }
+
+    fn rule(
+        action: HttpHeaderRuleAction,
+        name: &'static str,
+        value: &'static str,
+    ) -> HttpHeaderRule {
+        HttpHeaderRule::new(
+            action,
+            HeaderName::from_static(name),
+            Some(HeaderValue::from_static(value)),
+        )
+        .unwrap()
+    }
+
+    fn headers() -> HttpHeaderMap {
+        let mut headers = HttpHeaderMap::default();
+        headers.append(
+            HeaderName::from_static("x-a"),
+            HttpHeaderValue::from_static("a1"),
+        );
+        headers.append(
+            HeaderName::from_static("x-a"),
+            HttpHeaderValue::from_static("a2"),
+        );
+        headers
+    }
+
+    #[test]
+    fn parse_action() {
+        assert_eq!(
+            HttpHeaderRuleAction::from_str("add").unwrap(),
+            HttpHeaderRuleAction::Add
+        );
+        assert_eq!(
+            HttpHeaderRuleAction::from_str("override").unwrap(),
+            HttpHeaderRuleAction::Set
+        );
+        assert_eq!(
+            HttpHeaderRuleAction::from_str("append").unwrap(),
+            HttpHeaderRuleAction::Append
+        );
+        assert_eq!(
+            HttpHeaderRuleAction::from_str("strip").unwrap(),
+            HttpHeaderRuleAction::Remove
+        );
+        assert!(HttpHeaderRuleAction::from_str("replace").is_err());
+    }
+
+    #[test]
+    fn new_rule() {
+        assert!(
+            HttpHeaderRule::new(
+                HttpHeaderRuleAction::Set,
+                header::CONTENT_LENGTH,
+                Some(HeaderValue::from_static("0"))
+            )
+            .is_err()
+        );
+        assert!(
+            HttpHeaderRule::new(
+                HttpHeaderRuleAction::Remove,
+                HeaderName::from_static("keep-alive"),
+                None
+            )
+            .is_err()
+        );
+        assert!(HttpHeaderRule::new(HttpHeaderRuleAction::Add, header::USER_AGENT, None).is_err());
+        assert!(
+            HttpHeaderRule::new(
+                HttpHeaderRuleAction::Remove,
+                header::USER_AGENT,
+                Some(HeaderValue::from_static("curl"))
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn apply_add() {
+        let mut headers = headers();
+        let mut set = HttpHeaderRuleSet::default();
+        set.push(rule(HttpHeaderRuleAction::Add, "x-a", "a3"));
+        set.push(rule(HttpHeaderRuleAction::Add, "x-b", "b1"));
+        set.apply(&mut headers);
+        assert_eq!(values(&headers, "x-a"), ["a1", "a2"]);
+        assert_eq!(values(&headers, "x-b"), ["b1"]);
+    }
+
+    #[test]
+    fn apply_set() {
+        let mut headers = headers();
+        let mut set = HttpHeaderRuleSet::default();
+        set.push(rule(HttpHeaderRuleAction::Set, "x-a", "a3"));
+        set.push(rule(HttpHeaderRuleAction::Set, "x-b", "b1"));
+        set.apply(&mut headers);
+        assert_eq!(values(&headers, "x-a"), ["a3"]);
+        assert_eq!(values(&headers, "x-b"), ["b1"]);
+    }
+
+    #[test]
+    fn apply_append() {
+        let mut headers = headers();
+        let mut set = HttpHeaderRuleSet::default();
+        set.push(rule(HttpHeaderRuleAction::Append, "x-a", "a3"));
+        set.apply(&mut headers);
+        assert_eq!(values(&headers, "x-a"), ["a1", "a2", "a3"]);
+    }
+
+    #[test]
+    fn apply_remove() {
+        let mut headers = headers();
+        let mut set = HttpHeaderRuleSet::default();
+        set.push(
+            HttpHeaderRule::new(
+                HttpHeaderRuleAction::Remove,
+                HeaderName::from_static("x-a"),
+                None,
+            )
+            .unwrap(),
+        );
+        set.apply(&mut headers);
+        assert!(!headers.contains_key("x-a"));
+    }
+
+    #[test]
+    fn apply_in_order() {
+        let mut headers = headers();
+        let mut set = HttpHeaderRuleSet::default();
+        set.push(
+            HttpHeaderRule::new(
+                HttpHeaderRuleAction::Remove,
+                HeaderName::from_static("x-a"),
+                None,
+            )
+            .unwrap(),
+        );
+        set.push(rule(HttpHeaderRuleAction::Add, "x-a", "a3"));
+        set.push(rule(HttpHeaderRuleAction::Add, "x-a", "a4"));
+        set.apply(&mut headers);
+        assert_eq!(values(&headers, "x-a"), ["a3"]);
+    }
+}
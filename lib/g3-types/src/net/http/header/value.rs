@@ -104,6 +104,15 @@ impl FromStr for HttpHeaderValue {
     }
 }
 
+impl From<HeaderValue> for HttpHeaderValue {
+    fn from(inner: HeaderValue) -> Self {
+        HttpHeaderValue {
+            inner,
+            original_name: None,
+        }
+    }
+}
+
 impl From<HttpHeaderValue> for HeaderValue {
     fn from(value: HttpHeaderValue) -> Self {
         value.into_inner()
@@ -0,0 +1,91 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use http::{Version, header};
+
+use super::{HttpHeaderMap, HttpHeaderValue, HttpServerId};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HttpViaHeaderConfig {
+    pub pseudonym: Option<HttpServerId>,
+    pub request: bool,
+    pub response: bool,
+    /// Replace all received Via entries with ours, so the intermediaries before us will be hidden
+    pub collapse: bool,
+}
+
+impl Default for HttpViaHeaderConfig {
+    fn default() -> Self {
+        HttpViaHeaderConfig {
+            pseudonym: None,
+            request: true,
+            response: true,
+            collapse: false,
+        }
+    }
+}
+
+impl HttpViaHeaderConfig {
+    /// Get the Via header value for a message received in `version`, see RFC 9110 Section 7.6.3.
+    pub fn header_value(&self, version: Version) -> HttpHeaderValue {
+        let protocol = match version {
+            Version::HTTP_09 => "0.9",
+            Version::HTTP_10 => "1.0",
+            Version::HTTP_11 => "1.1",
+            Version::HTTP_2 => "2",
+            Version::HTTP_3 => "3",
+            _ => "1.1",
+        };
+        let pseudonym = self
+            .pseudonym
+            .as_ref()
+            .map(|v| v.as_str())
+            .unwrap_or("unknown");
+        let value = format!("{protocol} {pseudonym}");
+        // the pseudonym has been checked to be valid in header value
+        unsafe { HttpHeaderValue::from_string_unchecked(value) }
+    }
+
+    /// Append our Via entry to `headers`, after all the received entries.
+    pub fn append_to(&self, headers: &mut HttpHeaderMap, version: Version) {
+        if self.collapse {
+            headers.remove(header::VIA);
+        }
+        headers.append(header::VIA, self.header_value(version));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn append() {
+        let mut config = HttpViaHeaderConfig {
+            pseudonym: Some(HttpServerId::from_str("proxy-a").unwrap()),
+            ..Default::default()
+        };
+
+        let mut headers = HttpHeaderMap::default();
+        headers.append(header::VIA, HttpHeaderValue::from_static("1.0 fred"));
+        config.append_to(&mut headers, Version::HTTP_11);
+        let values = headers
+            .get_all(header::VIA)
+            .iter()
+            .map(|v| v.to_str())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["1.0 fred", "1.1 proxy-a"]);
+
+        config.collapse = true;
+        config.append_to(&mut headers, Version::HTTP_2);
+        let values = headers
+            .get_all(header::VIA)
+            .iter()
+            .map(|v| v.to_str())
+            .collect::<Vec<_>>();
+        assert_eq!(values, ["2 proxy-a"]);
+    }
+}
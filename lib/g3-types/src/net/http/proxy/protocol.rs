@@ -9,4 +9,5 @@ pub enum HttpProxySubProtocol {
     HttpForward,
     HttpsForward,
     FtpOverHttp,
+    UdpConnect,
 }
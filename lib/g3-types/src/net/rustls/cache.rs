@@ -4,11 +4,16 @@
  */
 
 use std::num::NonZeroUsize;
-use std::sync::Mutex;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use ahash::AHashMap;
 
 use lru::LruCache;
 use rustls::server::StoresServerSessions;
 
+static SHARED_SESSION_CACHES: LazyLock<Mutex<AHashMap<String, Arc<RustlsServerSessionCache>>>> =
+    LazyLock::new(|| Mutex::new(AHashMap::new()));
+
 #[derive(Debug)]
 struct CacheSlot {
     local: Mutex<LruCache<Vec<u8>, Vec<u8>, ahash::RandomState>>,
@@ -58,6 +63,20 @@ impl RustlsServerSessionCache {
             ],
         }
     }
+
+    /// Get the process wide session cache with the given name.
+    ///
+    /// The same cache will be returned for the same name, so it will be kept across config rebuilds,
+    /// and it can be shared by all the servers that use the same name.
+    pub fn shared(name: &str) -> Arc<Self> {
+        let mut map = SHARED_SESSION_CACHES.lock().unwrap();
+        if let Some(cache) = map.get(name) {
+            return cache.clone();
+        }
+        let cache = Arc::new(RustlsServerSessionCache::default());
+        map.insert(name.to_string(), cache.clone());
+        cache
+    }
 }
 
 impl StoresServerSessions for RustlsServerSessionCache {
@@ -95,3 +114,23 @@ impl StoresServerSessions for RustlsServerSessionCache {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared() {
+        let c1 = RustlsServerSessionCache::shared("test-shared-a");
+        let c2 = RustlsServerSessionCache::shared("test-shared-a");
+        let c3 = RustlsServerSessionCache::shared("test-shared-b");
+        assert!(Arc::ptr_eq(&c1, &c2));
+        assert!(!Arc::ptr_eq(&c1, &c3));
+
+        assert!(c1.put(vec![1, 2, 3], vec![4, 5, 6]));
+        assert_eq!(c2.get(&[1, 2, 3]), Some(vec![4, 5, 6]));
+        assert_eq!(c3.get(&[1, 2, 3]), None);
+        assert_eq!(c2.take(&[1, 2, 3]), Some(vec![4, 5, 6]));
+        assert_eq!(c1.get(&[1, 2, 3]), None);
+    }
+}
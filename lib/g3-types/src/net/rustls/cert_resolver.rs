@@ -4,45 +4,224 @@
  */
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use anyhow::anyhow;
+use log::{info, warn};
+use rustls::SignatureScheme;
 use rustls::crypto::CryptoProvider;
-use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::server::{ClientHello, ParsedCertificate, ResolvesServerCert};
 use rustls::sign::CertifiedKey;
+use rustls_pki_types::ServerName;
 
 use super::RustlsCertificatePair;
 
+#[derive(Debug, Default)]
+pub struct MultipleCertResolverStats {
+    sni_fallback: AtomicU64,
+}
+
+impl MultipleCertResolverStats {
+    fn add_sni_fallback(&self) {
+        self.sni_fallback.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get the number of handshakes that used the fallback cert as no cert matched the SNI
+    pub fn sni_fallback(&self) -> u64 {
+        self.sni_fallback.load(Ordering::Relaxed)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct MultipleCertResolver {
     keys: Vec<Arc<CertifiedKey>>,
+    fallback: Option<Arc<CertifiedKey>>,
+    log_selection: bool,
+    stats: Arc<MultipleCertResolverStats>,
+}
+
+fn load_certified_key(pair: &RustlsCertificatePair) -> anyhow::Result<Arc<CertifiedKey>> {
+    let Some(provider) = CryptoProvider::get_default() else {
+        return Err(anyhow!("no rustls provider registered"));
+    };
+    let ck = CertifiedKey::from_der(pair.certs_owned(), pair.key_owned(), provider)
+        .map_err(|e| anyhow!("failed to load cert pair: {e}"))?;
+    Ok(Arc::new(ck))
+}
+
+fn cert_match_server_name(ck: &CertifiedKey, server_name: &ServerName<'_>) -> bool {
+    let Some(cert) = ck.cert.first() else {
+        return false;
+    };
+    let Ok(parsed) = ParsedCertificate::try_from(cert) else {
+        return false;
+    };
+    rustls::client::verify_server_name(&parsed, server_name).is_ok()
 }
 
 impl MultipleCertResolver {
     pub fn with_capacity(cap: usize) -> Self {
         MultipleCertResolver {
             keys: Vec::with_capacity(cap),
+            ..Default::default()
         }
     }
 
     pub fn push_cert_pair(&mut self, pair: &RustlsCertificatePair) -> anyhow::Result<()> {
-        let Some(provider) = CryptoProvider::get_default() else {
-            return Err(anyhow!("no rustls provider registered"));
-        };
-        let ck = CertifiedKey::from_der(pair.certs_owned(), pair.key_owned(), provider)
-            .map_err(|e| anyhow!("failed to load cert pair: {e}"))?;
-        self.keys.push(Arc::new(ck));
+        let ck = load_certified_key(pair)?;
+        self.keys.push(ck);
+        Ok(())
+    }
+
+    /// Set the fallback cert pair, which will be used if no cert matches the SNI.
+    ///
+    /// If not set, the handshake will fail if SNI is present but no cert matches it.
+    pub fn set_fallback_cert_pair(&mut self, pair: &RustlsCertificatePair) -> anyhow::Result<()> {
+        let ck = load_certified_key(pair)?;
+        self.fallback = Some(ck);
         Ok(())
     }
+
+    /// Log the selected cert for each handshake
+    pub fn set_log_selection(&mut self, enable: bool) {
+        self.log_selection = enable;
+    }
+
+    pub fn stats(&self) -> Arc<MultipleCertResolverStats> {
+        self.stats.clone()
+    }
+
+    fn select(
+        &self,
+        server_name: Option<&str>,
+        schemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        let Some(sni) = server_name else {
+            // no SNI, select the first one that supports the signature schemes
+            let selected = self
+                .keys
+                .iter()
+                .position(|ck| ck.key.choose_scheme(schemes).is_some());
+            if self.log_selection {
+                match selected {
+                    Some(i) => info!("rustls server: no sni, selected cert #{i}"),
+                    None => info!("rustls server: no sni, no cert selected"),
+                }
+            }
+            return selected.map(|i| self.keys[i].clone());
+        };
+
+        if let Ok(server_name) = ServerName::try_from(sni) {
+            for (i, ck) in self.keys.iter().enumerate() {
+                if ck.key.choose_scheme(schemes).is_some()
+                    && cert_match_server_name(ck, &server_name)
+                {
+                    if self.log_selection {
+                        info!("rustls server: sni {sni}, selected cert #{i}");
+                    }
+                    return Some(ck.clone());
+                }
+            }
+        }
+
+        match &self.fallback {
+            Some(ck) if ck.key.choose_scheme(schemes).is_some() => {
+                self.stats.add_sni_fallback();
+                if self.log_selection {
+                    info!("rustls server: sni {sni}, selected the fallback cert");
+                }
+                Some(ck.clone())
+            }
+            _ => {
+                if self.log_selection {
+                    warn!("rustls server: sni {sni}, no cert selected");
+                }
+                None
+            }
+        }
+    }
 }
 
 impl ResolvesServerCert for MultipleCertResolver {
     fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
-        let schemes = client_hello.signature_schemes();
-        for ck in &self.keys {
-            if ck.key.choose_scheme(schemes).is_some() {
-                return Some(ck.clone());
-            }
-        }
-        None
+        self.select(client_hello.server_name(), client_hello.signature_schemes())
+    }
+}
+
+#[cfg(all(test, feature = "rustls-ring"))]
+mod tests {
+    use super::*;
+    use crate::net::RustlsCertificatePairBuilder;
+    use rustls_pki_types::pem::PemObject;
+    use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+
+    const SCHEMES: &[SignatureScheme] = &[SignatureScheme::ECDSA_NISTP256_SHA256];
+
+    fn cert_pair(cert: &str, key: &str) -> RustlsCertificatePair {
+        let mut builder = RustlsCertificatePairBuilder::default();
+        builder.set_certs(vec![
+            CertificateDer::from_pem_slice(cert.as_bytes()).unwrap(),
+        ]);
+        builder.set_key(PrivateKeyDer::from_pem_slice(key.as_bytes()).unwrap());
+        builder.build().unwrap()
+    }
+
+    fn resolver() -> MultipleCertResolver {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let mut resolver = MultipleCertResolver::with_capacity(2);
+        resolver
+            .push_cert_pair(&cert_pair(
+                include_str!("test_data/server_a.pem"),
+                include_str!("test_data/server_a.key"),
+            ))
+            .unwrap();
+        resolver
+            .push_cert_pair(&cert_pair(
+                include_str!("test_data/server_b.pem"),
+                include_str!("test_data/server_b.key"),
+            ))
+            .unwrap();
+        resolver
+    }
+
+    #[test]
+    fn select_by_sni() {
+        let resolver = resolver();
+
+        let ck = resolver.select(Some("b.example.com"), SCHEMES).unwrap();
+        assert!(Arc::ptr_eq(&ck, &resolver.keys[1]));
+        let ck = resolver.select(Some("a.example.com"), SCHEMES).unwrap();
+        assert!(Arc::ptr_eq(&ck, &resolver.keys[0]));
+        let ck = resolver.select(None, SCHEMES).unwrap();
+        assert!(Arc::ptr_eq(&ck, &resolver.keys[0]));
+
+        assert!(resolver.select(Some("c.example.com"), SCHEMES).is_none());
+        assert!(
+            resolver
+                .select(Some("a.example.com"), &[SignatureScheme::RSA_PSS_SHA256])
+                .is_none()
+        );
+        assert_eq!(resolver.stats().sni_fallback(), 0);
+    }
+
+    #[test]
+    fn select_fallback() {
+        let mut resolver = resolver();
+        resolver
+            .set_fallback_cert_pair(&cert_pair(
+                include_str!("test_data/server_b.pem"),
+                include_str!("test_data/server_b.key"),
+            ))
+            .unwrap();
+        resolver.set_log_selection(true);
+        let stats = resolver.stats();
+
+        let ck = resolver.select(Some("a.example.com"), SCHEMES).unwrap();
+        assert!(Arc::ptr_eq(&ck, &resolver.keys[0]));
+        assert_eq!(stats.sni_fallback(), 0);
+
+        let ck = resolver.select(Some("c.example.com"), SCHEMES).unwrap();
+        assert!(Arc::ptr_eq(&ck, resolver.fallback.as_ref().unwrap()));
+        assert_eq!(stats.sni_fallback(), 1);
     }
 }
@@ -6,14 +6,15 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::anyhow;
+use anyhow::{Context, anyhow};
 #[cfg(feature = "quinn")]
 use quinn::crypto::rustls::QuicClientConfig;
-use rustls::client::Resumption;
+use rustls::client::{AlwaysResolvesClientRawPublicKeys, Resumption};
 use rustls::{ClientConfig, RootCertStore};
-use rustls_pki_types::CertificateDer;
+use rustls_pki_types::{CertificateDer, SubjectPublicKeyInfoDer};
 
-use super::RustlsCertificatePair;
+use super::rpk::RawPublicKeyVerifier;
+use super::{RustlsCertificatePair, RustlsRawPublicKey};
 use crate::net::tls::AlpnProtocol;
 
 const MINIMAL_HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(100);
@@ -38,9 +39,11 @@ pub struct RustlsClientConfigBuilder {
     disable_sni: bool,
     max_fragment_size: Option<usize>,
     client_cert_pair: Option<RustlsCertificatePair>,
+    raw_public_key: Option<RustlsRawPublicKey>,
     ca_certs: Vec<CertificateDer<'static>>,
     no_default_ca_certs: bool,
     use_builtin_ca_certs: bool,
+    server_raw_public_keys: Option<Vec<SubjectPublicKeyInfoDer<'static>>>,
     handshake_timeout: Duration,
 }
 
@@ -51,9 +54,11 @@ impl Default for RustlsClientConfigBuilder {
             disable_sni: false,
             max_fragment_size: None,
             client_cert_pair: None,
+            raw_public_key: None,
             ca_certs: vec![],
             no_default_ca_certs: false,
             use_builtin_ca_certs: false,
+            server_raw_public_keys: None,
             handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
         }
     }
@@ -64,6 +69,21 @@ impl RustlsClientConfigBuilder {
         if self.handshake_timeout < MINIMAL_HANDSHAKE_TIMEOUT {
             self.handshake_timeout = MINIMAL_HANDSHAKE_TIMEOUT;
         }
+        if self.raw_public_key.is_some() && self.client_cert_pair.is_some() {
+            return Err(anyhow!(
+                "raw public key should not be used together with client cert pair"
+            ));
+        }
+        if let Some(keys) = &self.server_raw_public_keys {
+            if keys.is_empty() {
+                return Err(anyhow!("empty server raw public key list"));
+            }
+            if !self.ca_certs.is_empty() || self.use_builtin_ca_certs {
+                return Err(anyhow!(
+                    "server raw public keys should not be used together with ca certificates"
+                ));
+            }
+        }
 
         Ok(())
     }
@@ -84,6 +104,18 @@ impl RustlsClientConfigBuilder {
         self.client_cert_pair.replace(pair)
     }
 
+    /// Send the public key of this private key as a RFC 7250 raw public key for client auth.
+    /// This can not be used together with client cert pair.
+    pub fn set_raw_public_key(&mut self, key: RustlsRawPublicKey) {
+        self.raw_public_key = Some(key);
+    }
+
+    /// Require the server to authenticate by a RFC 7250 raw public key, which should be one of
+    /// the keys set here. This can not be used together with ca certificates.
+    pub fn set_server_raw_public_keys(&mut self, keys: Vec<SubjectPublicKeyInfoDer<'static>>) {
+        self.server_raw_public_keys = Some(keys);
+    }
+
     pub fn set_ca_certificates(&mut self, certs: Vec<CertificateDer<'static>>) {
         self.ca_certs = certs;
     }
@@ -100,12 +132,7 @@ impl RustlsClientConfigBuilder {
         self.use_builtin_ca_certs = true;
     }
 
-    fn build_client_config(
-        &self,
-        alpn_protocols: Option<Vec<AlpnProtocol>>,
-    ) -> anyhow::Result<ClientConfig> {
-        let config_builder = ClientConfig::builder();
-
+    fn build_root_store(&self) -> anyhow::Result<RootCertStore> {
         let mut root_store = RootCertStore::empty();
         if !self.no_default_ca_certs {
             if self.use_builtin_ca_certs {
@@ -127,9 +154,36 @@ impl RustlsClientConfigBuilder {
             })?;
         }
 
-        let config_builder = config_builder.with_root_certificates(root_store);
+        Ok(root_store)
+    }
+
+    fn build_client_config(
+        &self,
+        alpn_protocols: Option<Vec<AlpnProtocol>>,
+    ) -> anyhow::Result<ClientConfig> {
+        let config_builder =
+            if self.raw_public_key.is_some() || self.server_raw_public_keys.is_some() {
+                // raw public keys are only supported in TLS 1.3
+                ClientConfig::builder_with_protocol_versions(&[&rustls::version::TLS13])
+            } else {
+                ClientConfig::builder()
+            };
+
+        let config_builder = if let Some(keys) = &self.server_raw_public_keys {
+            let verifier = RawPublicKeyVerifier::new(keys.clone())
+                .context("failed to build server raw public key verifier")?;
+            config_builder
+                .dangerous()
+                .with_custom_certificate_verifier(Arc::new(verifier))
+        } else {
+            config_builder.with_root_certificates(self.build_root_store()?)
+        };
 
-        let mut config = if let Some(pair) = &self.client_cert_pair {
+        let mut config = if let Some(key) = &self.raw_public_key {
+            let ck = key.load().context("failed to load client raw public key")?;
+            config_builder
+                .with_client_cert_resolver(Arc::new(AlwaysResolvesClientRawPublicKeys::new(ck)))
+        } else if let Some(pair) = &self.client_cert_pair {
             config_builder
                 .with_client_auth_cert(pair.certs_owned(), pair.key_owned())
                 .map_err(|e| anyhow!("unable to add client auth certificate: {e:?}"))?
@@ -0,0 +1,302 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, anyhow};
+use arc_swap::ArcSwap;
+use log::warn;
+use rustls::client::danger::HandshakeSignatureValid;
+use rustls::server::WebPkiClientVerifier;
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use rustls::{DigitallySignedStruct, DistinguishedName, Error, RootCertStore, SignatureScheme};
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, CertificateRevocationListDer, UnixTime};
+
+/// The certificate revocation lists used to verify client certificates
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RustlsClientAuthCrlConfig {
+    files: Vec<PathBuf>,
+    refresh_interval: Option<Duration>,
+}
+
+impl RustlsClientAuthCrlConfig {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        RustlsClientAuthCrlConfig {
+            files,
+            refresh_interval: None,
+        }
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.files.is_empty() {
+            return Err(anyhow!("no crl file set"));
+        }
+        if self.refresh_interval.is_some_and(|d| d.is_zero()) {
+            return Err(anyhow!("crl refresh interval should not be zero"));
+        }
+        Ok(())
+    }
+
+    /// Reload the CRL files periodically when doing client auth
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = Some(interval);
+    }
+
+    #[inline]
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    #[inline]
+    pub fn refresh_interval(&self) -> Option<Duration> {
+        self.refresh_interval
+    }
+
+    /// Load all the CRLs from the files, which can be in PEM or DER format
+    pub fn load(&self) -> anyhow::Result<Vec<CertificateRevocationListDer<'static>>> {
+        let mut crls = Vec::new();
+        for path in &self.files {
+            let this_crls = load_crl_file(path)
+                .context(format!("failed to load crl file {}", path.display()))?;
+            crls.extend(this_crls);
+        }
+        Ok(crls)
+    }
+}
+
+fn load_crl_file(path: &Path) -> anyhow::Result<Vec<CertificateRevocationListDer<'static>>> {
+    let data = std::fs::read(path).map_err(|e| anyhow!("failed to read file: {e}"))?;
+    if memchr::memmem::find(&data, b"-----BEGIN").is_some() {
+        let crls = CertificateRevocationListDer::pem_slice_iter(&data)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| anyhow!("invalid pem crl: {e:?}"))?;
+        if crls.is_empty() {
+            return Err(anyhow!("no crl found in pem file"));
+        }
+        Ok(crls)
+    } else if data.is_empty() {
+        Err(anyhow!("empty file"))
+    } else {
+        Ok(vec![CertificateRevocationListDer::from(data)])
+    }
+}
+
+pub(super) fn build_client_verifier(
+    roots: Arc<RootCertStore>,
+    allow_unauthenticated: bool,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+) -> anyhow::Result<Arc<dyn ClientCertVerifier>> {
+    let mut builder = WebPkiClientVerifier::builder(roots);
+    if allow_unauthenticated {
+        builder = builder.allow_unauthenticated();
+    }
+    if !crls.is_empty() {
+        builder = builder.with_crls(crls);
+    }
+    builder
+        .build()
+        .map_err(|e| anyhow!("failed to build client cert verifier: {e}"))
+}
+
+/// A client cert verifier which will reload the CRL files periodically.
+///
+/// The reload is done in the handshake that first finds the refresh interval elapsed,
+/// and the old CRLs will be kept if the reload failed.
+#[derive(Debug)]
+pub(super) struct RustlsCrlRefreshClientVerifier {
+    roots: Arc<RootCertStore>,
+    allow_unauthenticated: bool,
+    config: RustlsClientAuthCrlConfig,
+    refresh_interval: Duration,
+    root_hint_subjects: Vec<DistinguishedName>,
+    inner: ArcSwap<Arc<dyn ClientCertVerifier>>,
+    next_refresh: Mutex<Instant>,
+}
+
+impl RustlsCrlRefreshClientVerifier {
+    pub(super) fn new(
+        roots: Arc<RootCertStore>,
+        allow_unauthenticated: bool,
+        config: RustlsClientAuthCrlConfig,
+        refresh_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        let crls = config.load()?;
+        let inner = build_client_verifier(roots.clone(), allow_unauthenticated, crls)?;
+        Ok(RustlsCrlRefreshClientVerifier {
+            roots,
+            allow_unauthenticated,
+            config,
+            refresh_interval,
+            root_hint_subjects: inner.root_hint_subjects().to_vec(),
+            inner: ArcSwap::new(Arc::new(inner)),
+            next_refresh: Mutex::new(Instant::now() + refresh_interval),
+        })
+    }
+
+    fn reload(&self) -> anyhow::Result<()> {
+        let crls = self.config.load()?;
+        let inner = build_client_verifier(self.roots.clone(), self.allow_unauthenticated, crls)?;
+        self.inner.store(Arc::new(inner));
+        Ok(())
+    }
+
+    fn refresh_if_needed(&self) {
+        // skip if another handshake is doing the refresh
+        let Ok(mut next_refresh) = self.next_refresh.try_lock() else {
+            return;
+        };
+        let now = Instant::now();
+        if now < *next_refresh {
+            return;
+        }
+        *next_refresh = now + self.refresh_interval;
+        if let Err(e) = self.reload() {
+            warn!("failed to refresh client auth crl, the old one will be used: {e:?}");
+        }
+    }
+}
+
+impl ClientCertVerifier for RustlsCrlRefreshClientVerifier {
+    fn offer_client_auth(&self) -> bool {
+        true
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        !self.allow_unauthenticated
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &self.root_hint_subjects
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, Error> {
+        self.refresh_if_needed();
+        self.inner
+            .load()
+            .verify_client_cert(end_entity, intermediates, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.load().verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.load().verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.load().supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CA_PEM: &str = include_str!("test_data/client_ca.pem");
+    const TEST_CRL_PEM: &str = include_str!("test_data/client_ca_crl.pem");
+
+    fn test_roots() -> Arc<RootCertStore> {
+        let mut roots = RootCertStore::empty();
+        let ca = CertificateDer::from_pem_slice(TEST_CA_PEM.as_bytes()).unwrap();
+        roots.add(ca).unwrap();
+        Arc::new(roots)
+    }
+
+    #[test]
+    fn check() {
+        let config = RustlsClientAuthCrlConfig::new(Vec::new());
+        assert!(config.check().is_err());
+
+        let mut config = RustlsClientAuthCrlConfig::new(vec![PathBuf::from("a.crl")]);
+        assert!(config.check().is_ok());
+        config.set_refresh_interval(Duration::ZERO);
+        assert!(config.check().is_err());
+    }
+
+    #[test]
+    fn load_err() {
+        let dir = std::env::temp_dir().join(format!("g3-types-crl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let not_found = dir.join("not_found.crl");
+        let config = RustlsClientAuthCrlConfig::new(vec![not_found]);
+        assert!(config.load().is_err());
+
+        let empty = dir.join("empty.crl");
+        std::fs::write(&empty, b"").unwrap();
+        let config = RustlsClientAuthCrlConfig::new(vec![empty]);
+        assert!(config.load().is_err());
+
+        let bad_pem = dir.join("bad.pem");
+        std::fs::write(
+            &bad_pem,
+            b"-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n",
+        )
+        .unwrap();
+        let config = RustlsClientAuthCrlConfig::new(vec![bad_pem]);
+        assert!(config.load().is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn load_ok() {
+        let dir = std::env::temp_dir().join(format!("g3-types-crl-ok-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let pem_file = dir.join("crl.pem");
+        std::fs::write(&pem_file, TEST_CRL_PEM).unwrap();
+        let der = CertificateRevocationListDer::from_pem_slice(TEST_CRL_PEM.as_bytes()).unwrap();
+        let der_file = dir.join("crl.der");
+        std::fs::write(&der_file, der.as_ref()).unwrap();
+
+        let mut config = RustlsClientAuthCrlConfig::new(vec![pem_file, der_file]);
+        let crls = config.load().unwrap();
+        assert_eq!(crls.len(), 2);
+        assert_eq!(crls[0], crls[1]);
+        build_client_verifier(test_roots(), false, crls).unwrap();
+
+        config.set_refresh_interval(Duration::from_secs(60));
+        let verifier = RustlsCrlRefreshClientVerifier::new(
+            test_roots(),
+            true,
+            config,
+            Duration::from_secs(60),
+        )
+        .unwrap();
+        assert!(!verifier.client_auth_mandatory());
+        assert_eq!(verifier.root_hint_subjects().len(), 1);
+        verifier.reload().unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn build_invalid_crl() {
+        let crls = vec![CertificateRevocationListDer::from(vec![
+            0x30, 0x03, 0x02, 0x01, 0x01,
+        ])];
+        assert!(build_client_verifier(test_roots(), false, crls).is_err());
+    }
+}
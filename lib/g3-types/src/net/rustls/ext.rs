@@ -34,6 +34,7 @@ impl RustlsClientConnectionExt for ClientConnection {
 
 pub trait RustlsServerConfigExt {
     fn set_session_cache(&mut self, disable: bool);
+    fn set_shared_session_cache(&mut self, name: &str);
     fn set_session_ticketer<T: ProducesTickets + 'static>(
         &mut self,
         enable: bool,
@@ -50,6 +51,10 @@ impl RustlsServerConfigExt for ServerConfig {
         }
     }
 
+    fn set_shared_session_cache(&mut self, name: &str) {
+        self.session_storage = RustlsServerSessionCache::shared(name);
+    }
+
     fn set_session_ticketer<T: ProducesTickets + 'static>(
         &mut self,
         enable: bool,
@@ -23,7 +23,13 @@ mod cert_pair;
 pub use cert_pair::{RustlsCertificatePair, RustlsCertificatePairBuilder};
 
 mod cert_resolver;
-pub use cert_resolver::MultipleCertResolver;
+pub use cert_resolver::{MultipleCertResolver, MultipleCertResolverStats};
+
+mod crl;
+pub use crl::RustlsClientAuthCrlConfig;
+
+mod rpk;
+pub use rpk::RustlsRawPublicKey;
 
 mod ca_certs;
 pub use ca_certs::load_native_certs_for_rustls;
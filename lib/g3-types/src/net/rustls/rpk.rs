@@ -0,0 +1,300 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::{CryptoProvider, WebPkiSupportedAlgorithms};
+use rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use rustls::sign::CertifiedKey;
+use rustls::{
+    CertificateError, DigitallySignedStruct, DistinguishedName, Error, PeerIncompatible,
+    SignatureScheme,
+};
+use rustls_pki_types::{
+    CertificateDer, PrivateKeyDer, ServerName, SubjectPublicKeyInfoDer, UnixTime,
+};
+
+/// The local private key, whose public key will be sent to the peer as a RFC 7250 raw public key
+#[derive(Debug, Eq, PartialEq)]
+pub struct RustlsRawPublicKey {
+    key: PrivateKeyDer<'static>,
+}
+
+impl Clone for RustlsRawPublicKey {
+    fn clone(&self) -> Self {
+        RustlsRawPublicKey {
+            key: self.key.clone_key(),
+        }
+    }
+}
+
+impl RustlsRawPublicKey {
+    pub fn new(key: PrivateKeyDer<'static>) -> Self {
+        RustlsRawPublicKey { key }
+    }
+
+    pub fn key_ref(&self) -> &PrivateKeyDer<'static> {
+        &self.key
+    }
+
+    pub(super) fn load(&self) -> anyhow::Result<Arc<CertifiedKey>> {
+        let provider = default_provider()?;
+        let signing_key = provider
+            .key_provider
+            .load_private_key(self.key.clone_key())
+            .map_err(|e| anyhow!("failed to load private key: {e}"))?;
+        let Some(spki) = signing_key.public_key() else {
+            return Err(anyhow!("unable to get the public key of the private key"));
+        };
+        let cert = CertificateDer::from(spki.as_ref().to_vec());
+        Ok(Arc::new(CertifiedKey::new(vec![cert], signing_key)))
+    }
+}
+
+fn default_provider() -> anyhow::Result<&'static Arc<CryptoProvider>> {
+    CryptoProvider::get_default().ok_or_else(|| anyhow!("no rustls provider registered"))
+}
+
+/// Verify the peer by matching its RFC 7250 raw public key against the expected ones
+#[derive(Debug)]
+pub(super) struct RawPublicKeyVerifier {
+    trusted_keys: Vec<SubjectPublicKeyInfoDer<'static>>,
+    supported_algs: WebPkiSupportedAlgorithms,
+}
+
+impl RawPublicKeyVerifier {
+    pub(super) fn new(trusted_keys: Vec<SubjectPublicKeyInfoDer<'static>>) -> anyhow::Result<Self> {
+        if trusted_keys.is_empty() {
+            return Err(anyhow!("no trusted raw public key set"));
+        }
+        let provider = default_provider()?;
+        Ok(RawPublicKeyVerifier {
+            trusted_keys,
+            supported_algs: provider.signature_verification_algorithms,
+        })
+    }
+
+    fn verify_key(&self, end_entity: &CertificateDer<'_>) -> Result<(), Error> {
+        if self
+            .trusted_keys
+            .iter()
+            .any(|k| k.as_ref() == end_entity.as_ref())
+        {
+            Ok(())
+        } else {
+            Err(Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls13(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        rustls::crypto::verify_tls13_signature_with_raw_key(
+            message,
+            &SubjectPublicKeyInfoDer::from(cert.as_ref()),
+            dss,
+            &self.supported_algs,
+        )
+    }
+}
+
+impl ServerCertVerifier for RawPublicKeyVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        self.verify_key(end_entity)?;
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        Err(Error::PeerIncompatible(PeerIncompatible::Tls12NotOffered))
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verify_tls13(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.supported_algs.supported_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        true
+    }
+}
+
+impl ClientCertVerifier for RawPublicKeyVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        &[]
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _now: UnixTime,
+    ) -> Result<ClientCertVerified, Error> {
+        self.verify_key(end_entity)?;
+        Ok(ClientCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        _message: &[u8],
+        _cert: &CertificateDer<'_>,
+        _dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        Err(Error::PeerIncompatible(PeerIncompatible::Tls12NotOffered))
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.verify_tls13(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.supported_algs.supported_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(all(test, feature = "rustls-ring"))]
+mod tests {
+    use super::*;
+    use crate::net::{RustlsClientConfigBuilder, RustlsServerConfigBuilder};
+    use rustls::{ClientConnection, ServerConnection};
+    use rustls_pki_types::pem::PemObject;
+
+    const SERVER_KEY: &str = include_str!("test_data/rpk_server.key");
+    const SERVER_PUB: &str = include_str!("test_data/rpk_server.pub");
+    const CLIENT_KEY: &str = include_str!("test_data/rpk_client.key");
+    const CLIENT_PUB: &str = include_str!("test_data/rpk_client.pub");
+
+    fn raw_public_key(pem: &str) -> RustlsRawPublicKey {
+        RustlsRawPublicKey::new(PrivateKeyDer::from_pem_slice(pem.as_bytes()).unwrap())
+    }
+
+    fn public_key(pem: &str) -> SubjectPublicKeyInfoDer<'static> {
+        SubjectPublicKeyInfoDer::from_pem_slice(pem.as_bytes()).unwrap()
+    }
+
+    fn handshake(
+        client: &mut ClientConnection,
+        server: &mut ServerConnection,
+    ) -> Result<(), Error> {
+        let mut buf = Vec::new();
+        while client.is_handshaking() || server.is_handshaking() {
+            let mut progress = false;
+            while client.wants_write() {
+                buf.clear();
+                client.write_tls(&mut buf).unwrap();
+                server.read_tls(&mut buf.as_slice()).unwrap();
+                server.process_new_packets()?;
+                progress = true;
+            }
+            while server.wants_write() {
+                buf.clear();
+                server.write_tls(&mut buf).unwrap();
+                client.read_tls(&mut buf.as_slice()).unwrap();
+                client.process_new_packets()?;
+                progress = true;
+            }
+            if !progress {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn server_builder(client_pub: &str) -> RustlsServerConfigBuilder {
+        let mut builder = RustlsServerConfigBuilder::empty();
+        builder.set_raw_public_key(raw_public_key(SERVER_KEY));
+        builder.set_client_auth_raw_public_keys(vec![public_key(client_pub)]);
+        builder.check().unwrap();
+        builder
+    }
+
+    fn client_builder(server_pub: &str) -> RustlsClientConfigBuilder {
+        let mut builder = RustlsClientConfigBuilder::default();
+        builder.set_raw_public_key(raw_public_key(CLIENT_KEY));
+        builder.set_server_raw_public_keys(vec![public_key(server_pub)]);
+        builder.check().unwrap();
+        builder
+    }
+
+    fn connect(
+        server: RustlsServerConfigBuilder,
+        client: RustlsClientConfigBuilder,
+    ) -> Result<(), Error> {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let server_config = server.build().unwrap();
+        let client_config = client.build().unwrap();
+        let mut server = ServerConnection::new(server_config.driver).unwrap();
+        let mut client = ClientConnection::new(
+            client_config.driver,
+            ServerName::try_from("rpk.example.net").unwrap(),
+        )
+        .unwrap();
+        handshake(&mut client, &mut server)
+    }
+
+    #[test]
+    fn handshake_ok() {
+        connect(server_builder(CLIENT_PUB), client_builder(SERVER_PUB)).unwrap();
+    }
+
+    #[test]
+    fn handshake_unknown_key() {
+        assert!(connect(server_builder(CLIENT_PUB), client_builder(CLIENT_PUB)).is_err());
+        assert!(connect(server_builder(SERVER_PUB), client_builder(SERVER_PUB)).is_err());
+    }
+
+    #[test]
+    fn check_conflict() {
+        let mut builder = RustlsServerConfigBuilder::empty();
+        builder.set_raw_public_key(raw_public_key(SERVER_KEY));
+        builder.enable_client_auth();
+        builder.set_client_auth_raw_public_keys(vec![public_key(CLIENT_PUB)]);
+        assert!(builder.check().is_err());
+
+        let mut builder = RustlsServerConfigBuilder::empty();
+        builder.set_client_auth_raw_public_keys(Vec::new());
+        builder.set_raw_public_key(raw_public_key(SERVER_KEY));
+        assert!(builder.check().is_err());
+
+        let mut builder = client_builder(SERVER_PUB);
+        builder.set_use_builtin_ca_certificates();
+        assert!(builder.check().is_err());
+    }
+}
@@ -9,12 +9,15 @@ use std::time::Duration;
 use anyhow::{Context, anyhow};
 #[cfg(feature = "quinn")]
 use quinn::crypto::rustls::QuicServerConfig;
-use rustls::server::{ProducesTickets, WebPkiClientVerifier};
+use rustls::server::{AlwaysResolvesServerRawPublicKeys, ProducesTickets};
 use rustls::{RootCertStore, ServerConfig};
-use rustls_pki_types::CertificateDer;
+use rustls_pki_types::{CertificateDer, SubjectPublicKeyInfoDer};
 
+use super::crl::RustlsCrlRefreshClientVerifier;
+use super::rpk::RawPublicKeyVerifier;
 use super::{
-    MultipleCertResolver, RustlsCertificatePair, RustlsNoSessionTicketer, RustlsServerConfigExt,
+    MultipleCertResolver, MultipleCertResolverStats, RustlsCertificatePair,
+    RustlsClientAuthCrlConfig, RustlsNoSessionTicketer, RustlsRawPublicKey, RustlsServerConfigExt,
 };
 use crate::net::tls::AlpnProtocol;
 #[cfg(feature = "openssl")]
@@ -24,6 +27,7 @@ use crate::net::{OpensslTicketKey, RollingTicketer};
 pub struct RustlsServerConfig {
     pub driver: Arc<ServerConfig>,
     pub accept_timeout: Duration,
+    pub cert_resolver_stats: Option<Arc<MultipleCertResolverStats>>,
 }
 
 #[cfg(feature = "quinn")]
@@ -31,15 +35,25 @@ pub struct RustlsServerConfig {
 pub struct RustlsQuicServerConfig {
     pub driver: Arc<QuicServerConfig>,
     pub accept_timeout: Duration,
+    pub cert_resolver_stats: Option<Arc<MultipleCertResolverStats>>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RustlsServerConfigBuilder {
     cert_pairs: Vec<RustlsCertificatePair>,
+    fallback_cert_pair: Option<RustlsCertificatePair>,
+    log_cert_selection: bool,
+    raw_public_key: Option<RustlsRawPublicKey>,
     client_auth: bool,
+    client_auth_optional: bool,
     client_auth_certs: Option<Vec<CertificateDer<'static>>>,
+    client_auth_crl: Option<RustlsClientAuthCrlConfig>,
+    client_auth_raw_public_keys: Option<Vec<SubjectPublicKeyInfoDer<'static>>>,
     use_session_ticket: bool,
     no_session_cache: bool,
+    session_ticket_only: bool,
+    shared_session_cache: Option<String>,
+    alpn_protocols: Option<Vec<AlpnProtocol>>,
     accept_timeout: Duration,
 }
 
@@ -47,18 +61,81 @@ impl RustlsServerConfigBuilder {
     pub fn empty() -> Self {
         RustlsServerConfigBuilder {
             cert_pairs: Vec::with_capacity(1),
+            fallback_cert_pair: None,
+            log_cert_selection: false,
+            raw_public_key: None,
             client_auth: false,
+            client_auth_optional: false,
             client_auth_certs: None,
+            client_auth_crl: None,
+            client_auth_raw_public_keys: None,
             use_session_ticket: true,
             no_session_cache: false,
+            session_ticket_only: false,
+            shared_session_cache: None,
+            alpn_protocols: None,
             accept_timeout: Duration::from_secs(10),
         }
     }
 
     pub fn check(&self) -> anyhow::Result<()> {
-        if self.cert_pairs.is_empty() {
+        if self.raw_public_key.is_some() {
+            if !self.cert_pairs.is_empty() || self.fallback_cert_pair.is_some() {
+                return Err(anyhow!(
+                    "raw public key should not be used together with cert pairs"
+                ));
+            }
+        } else if self.cert_pairs.is_empty() {
             return Err(anyhow!("no cert pair is set"));
         }
+        if let Some(keys) = &self.client_auth_raw_public_keys {
+            if keys.is_empty() {
+                return Err(anyhow!("empty client auth raw public key list"));
+            }
+            if self.client_auth
+                || self.client_auth_certs.is_some()
+                || self.client_auth_crl.is_some()
+            {
+                return Err(anyhow!(
+                    "client auth raw public keys should not be used together with x509 client auth"
+                ));
+            }
+        }
+        if self.session_ticket_only && !self.use_session_ticket {
+            return Err(anyhow!(
+                "session ticket should not be disabled in session ticket only mode"
+            ));
+        }
+        if self.shared_session_cache.is_some() {
+            if self.no_session_cache {
+                return Err(anyhow!(
+                    "shared session cache is set but session cache is disabled"
+                ));
+            }
+            if self.session_ticket_only {
+                return Err(anyhow!(
+                    "shared session cache is set but session ticket only mode is enabled"
+                ));
+            }
+        }
+        if let Some(crl) = &self.client_auth_crl {
+            if !self.client_auth {
+                return Err(anyhow!(
+                    "client auth crl is set but client auth is not enabled"
+                ));
+            }
+            crl.check().context("invalid client auth crl config")?;
+        }
+        if let Some(protocols) = &self.alpn_protocols {
+            if protocols.is_empty() {
+                return Err(anyhow!("empty alpn protocol list"));
+            }
+            for (i, p) in protocols.iter().enumerate() {
+                if protocols[..i].contains(p) {
+                    return Err(anyhow!("duplicate alpn protocol {p}"));
+                }
+            }
+        }
 
         Ok(())
     }
@@ -75,18 +152,75 @@ impl RustlsServerConfigBuilder {
         self.no_session_cache = disable;
     }
 
+    /// Only do stateless session resumption by session ticket, and the stateful session cache
+    /// will be disabled.
+    ///
+    /// The tickets can be decrypted by all the workers, and also by all the servers and processes
+    /// if a shared rolling ticketer is used.
+    pub fn set_session_ticket_only(&mut self, enable: bool) {
+        self.session_ticket_only = enable;
+    }
+
+    /// Use the process wide session cache with the given name instead of a new one, so the
+    /// cached sessions will be kept when the config is rebuilt.
+    pub fn set_shared_session_cache(&mut self, name: String) {
+        self.shared_session_cache = Some(name);
+    }
+
+    /// Set the ALPN protocols to advertise, in preference order.
+    ///
+    /// If the server has its own supported protocol list, all the protocols set here should be in it.
+    pub fn set_alpn_protocols(&mut self, protocols: Vec<AlpnProtocol>) {
+        self.alpn_protocols = Some(protocols);
+    }
+
     pub fn enable_client_auth(&mut self) {
         self.client_auth = true;
     }
 
+    /// Request the client certificate, but also allow the clients that send no certificate
+    pub fn enable_optional_client_auth(&mut self) {
+        self.client_auth = true;
+        self.client_auth_optional = true;
+    }
+
     pub fn set_client_auth_certificates(&mut self, certs: Vec<CertificateDer<'static>>) {
         self.client_auth_certs = Some(certs);
     }
 
+    pub fn set_client_auth_crl(&mut self, crl: RustlsClientAuthCrlConfig) {
+        self.client_auth_crl = Some(crl);
+    }
+
+    /// Require the client to authenticate by a RFC 7250 raw public key, which should be one of
+    /// the keys set here. This can not be used together with x509 client auth.
+    pub fn set_client_auth_raw_public_keys(&mut self, keys: Vec<SubjectPublicKeyInfoDer<'static>>) {
+        self.client_auth_raw_public_keys = Some(keys);
+    }
+
+    /// Send the public key of this private key as a RFC 7250 raw public key instead of the
+    /// certificates. This can not be used together with cert pairs.
+    pub fn set_raw_public_key(&mut self, key: RustlsRawPublicKey) {
+        self.raw_public_key = Some(key);
+    }
+
     pub fn push_cert_pair(&mut self, cert_pair: RustlsCertificatePair) {
         self.cert_pairs.push(cert_pair);
     }
 
+    /// Set the cert pair to use if SNI is present but no cert matches it.
+    ///
+    /// The cert will be selected by SNI if there are multiple cert pairs or a fallback one is set,
+    /// and the handshake will fail if no cert matches and no fallback cert pair is set.
+    pub fn set_fallback_cert_pair(&mut self, cert_pair: RustlsCertificatePair) {
+        self.fallback_cert_pair = Some(cert_pair);
+    }
+
+    /// Log the selected cert for each handshake, only take effect if the cert will be selected by SNI
+    pub fn set_log_cert_selection(&mut self, enable: bool) {
+        self.log_cert_selection = enable;
+    }
+
     #[inline]
     pub fn set_accept_timeout(&mut self, timeout: Duration) {
         self.accept_timeout = timeout;
@@ -101,12 +235,22 @@ impl RustlsServerConfigBuilder {
         &self,
         alpn_protocols: Option<Vec<AlpnProtocol>>,
         ticketer: Option<Arc<T>>,
-    ) -> anyhow::Result<ServerConfig>
+    ) -> anyhow::Result<(ServerConfig, Option<Arc<MultipleCertResolverStats>>)>
     where
         T: ProducesTickets + 'static,
     {
-        let config_builder = ServerConfig::builder();
-        let config_builder = if self.client_auth {
+        let config_builder =
+            if self.raw_public_key.is_some() || self.client_auth_raw_public_keys.is_some() {
+                // raw public keys are only supported in TLS 1.3
+                ServerConfig::builder_with_protocol_versions(&[&rustls::version::TLS13])
+            } else {
+                ServerConfig::builder()
+            };
+        let config_builder = if let Some(keys) = &self.client_auth_raw_public_keys {
+            let verifier = RawPublicKeyVerifier::new(keys.clone())
+                .context("failed to build client raw public key verifier")?;
+            config_builder.with_client_cert_verifier(Arc::new(verifier))
+        } else if self.client_auth {
             let mut root_store = RootCertStore::empty();
             if let Some(certs) = &self.client_auth_certs {
                 for (i, cert) in certs.iter().enumerate() {
@@ -124,36 +268,89 @@ impl RustlsServerConfigBuilder {
                     })?;
                 }
             };
-            let client_verifier = WebPkiClientVerifier::builder(Arc::new(root_store))
-                .build()
-                .map_err(|e| anyhow!("failed to build client cert verifier: {e}"))?;
+            let root_store = Arc::new(root_store);
+            let client_verifier = match &self.client_auth_crl {
+                Some(crl) => {
+                    if let Some(interval) = crl.refresh_interval() {
+                        let verifier = RustlsCrlRefreshClientVerifier::new(
+                            root_store,
+                            self.client_auth_optional,
+                            crl.clone(),
+                            interval,
+                        )
+                        .context("failed to build client cert verifier with crl refresh")?;
+                        Arc::new(verifier)
+                    } else {
+                        let crls = crl.load().context("failed to load client auth crl")?;
+                        super::crl::build_client_verifier(
+                            root_store,
+                            self.client_auth_optional,
+                            crls,
+                        )?
+                    }
+                }
+                None => super::crl::build_client_verifier(
+                    root_store,
+                    self.client_auth_optional,
+                    Vec::new(),
+                )?,
+            };
             config_builder.with_client_cert_verifier(client_verifier)
         } else {
             config_builder.with_no_client_auth()
         };
 
-        let mut config = match self.cert_pairs.len() {
-            0 => return Err(anyhow!("no cert pair set")),
-            1 => {
-                let cert_pair = &self.cert_pairs[0];
-                config_builder
-                    .with_single_cert(cert_pair.certs_owned(), cert_pair.key_owned())
-                    .map_err(|e| anyhow!("failed to set server cert pair: {e:?}"))?
-            }
-            n => {
-                let mut cert_resolver = MultipleCertResolver::with_capacity(n);
-                for (i, pair) in self.cert_pairs.iter().enumerate() {
-                    cert_resolver
-                        .push_cert_pair(pair)
-                        .context(format!("failed to set server cert pair #{i}"))?;
+        let (mut config, cert_resolver_stats) = if let Some(key) = &self.raw_public_key {
+            let ck = key.load().context("failed to load server raw public key")?;
+            let resolver = AlwaysResolvesServerRawPublicKeys::new(ck);
+            (config_builder.with_cert_resolver(Arc::new(resolver)), None)
+        } else {
+            match self.cert_pairs.len() {
+                0 => return Err(anyhow!("no cert pair set")),
+                1 if self.fallback_cert_pair.is_none() => {
+                    let cert_pair = &self.cert_pairs[0];
+                    let config = config_builder
+                        .with_single_cert(cert_pair.certs_owned(), cert_pair.key_owned())
+                        .map_err(|e| anyhow!("failed to set server cert pair: {e:?}"))?;
+                    (config, None)
+                }
+                n => {
+                    let mut cert_resolver = MultipleCertResolver::with_capacity(n);
+                    for (i, pair) in self.cert_pairs.iter().enumerate() {
+                        cert_resolver
+                            .push_cert_pair(pair)
+                            .context(format!("failed to set server cert pair #{i}"))?;
+                    }
+                    if let Some(pair) = &self.fallback_cert_pair {
+                        cert_resolver
+                            .set_fallback_cert_pair(pair)
+                            .context("failed to set fallback server cert pair")?;
+                    }
+                    cert_resolver.set_log_selection(self.log_cert_selection);
+                    let stats = cert_resolver.stats();
+                    let config = config_builder.with_cert_resolver(Arc::new(cert_resolver));
+                    (config, Some(stats))
                 }
-                config_builder.with_cert_resolver(Arc::new(cert_resolver))
             }
         };
 
-        config.set_session_cache(self.no_session_cache);
+        if let Some(name) = &self.shared_session_cache {
+            config.set_shared_session_cache(name);
+        } else {
+            config.set_session_cache(self.no_session_cache || self.session_ticket_only);
+        }
         config.set_session_ticketer(self.use_session_ticket, ticketer)?;
 
+        let alpn_protocols = match (&self.alpn_protocols, alpn_protocols) {
+            (Some(configured), Some(supported)) => {
+                if let Some(p) = configured.iter().find(|p| !supported.contains(p)) {
+                    return Err(anyhow!("alpn protocol {p} is not supported by this server"));
+                }
+                Some(configured.clone())
+            }
+            (Some(configured), None) => Some(configured.clone()),
+            (None, supported) => supported,
+        };
         if let Some(protocols) = alpn_protocols {
             for proto in protocols {
                 config
@@ -162,7 +359,7 @@ impl RustlsServerConfigBuilder {
             }
         }
 
-        Ok(config)
+        Ok((config, cert_resolver_stats))
     }
 
     pub fn build_with_alpn_protocols<T>(
@@ -173,10 +370,11 @@ impl RustlsServerConfigBuilder {
     where
         T: ProducesTickets + 'static,
     {
-        let config = self.build_server_config(alpn_protocols, ticketer)?;
+        let (config, cert_resolver_stats) = self.build_server_config(alpn_protocols, ticketer)?;
         Ok(RustlsServerConfig {
             driver: Arc::new(config),
             accept_timeout: self.accept_timeout,
+            cert_resolver_stats,
         })
     }
 
@@ -201,12 +399,13 @@ impl RustlsServerConfigBuilder {
     where
         T: ProducesTickets + 'static,
     {
-        let config = self.build_server_config(alpn_protocols, ticketer)?;
+        let (config, cert_resolver_stats) = self.build_server_config(alpn_protocols, ticketer)?;
         let quic_config = QuicServerConfig::try_from(config)
             .map_err(|e| anyhow!("invalid quic tls config: {e}"))?;
         Ok(RustlsQuicServerConfig {
             driver: Arc::new(quic_config),
             accept_timeout: self.accept_timeout,
+            cert_resolver_stats,
         })
     }
 
@@ -223,3 +422,136 @@ impl RustlsServerConfigBuilder {
         self.build_quic_with_alpn_protocols::<RustlsNoSessionTicketer>(None, None)
     }
 }
+
+#[cfg(all(test, feature = "rustls-ring"))]
+mod tests {
+    use super::*;
+    use crate::net::{RustlsCertificatePairBuilder, RustlsClientConfigBuilder};
+    use rustls::pki_types::ServerName;
+    use rustls::{ClientConnection, Error, ServerConnection};
+    use rustls_pki_types::PrivateKeyDer;
+    use rustls_pki_types::pem::PemObject;
+
+    const CA_CERT: &str = include_str!("test_data/mtls_ca.pem");
+    const SERVER_CERT: &str = include_str!("test_data/mtls_server.pem");
+    const SERVER_KEY: &str = include_str!("test_data/mtls_server.key");
+    const CLIENT_CERT: &str = include_str!("test_data/mtls_client.pem");
+    const CLIENT_KEY: &str = include_str!("test_data/mtls_client.key");
+    // self-signed, not issued by the test ca
+    const UNTRUSTED_CERT: &str = include_str!("test_data/mtls_untrusted.pem");
+    const UNTRUSTED_KEY: &str = include_str!("test_data/mtls_untrusted.key");
+
+    fn ca_cert() -> CertificateDer<'static> {
+        CertificateDer::from_pem_slice(CA_CERT.as_bytes()).unwrap()
+    }
+
+    fn cert_pair(cert: &str, key: &str) -> RustlsCertificatePair {
+        let mut builder = RustlsCertificatePairBuilder::default();
+        builder.set_certs(vec![
+            CertificateDer::from_pem_slice(cert.as_bytes()).unwrap(),
+        ]);
+        builder.set_key(PrivateKeyDer::from_pem_slice(key.as_bytes()).unwrap());
+        builder.build().unwrap()
+    }
+
+    fn server_builder(optional: bool) -> RustlsServerConfigBuilder {
+        let mut builder = RustlsServerConfigBuilder::empty();
+        builder.push_cert_pair(cert_pair(SERVER_CERT, SERVER_KEY));
+        if optional {
+            builder.enable_optional_client_auth();
+        } else {
+            builder.enable_client_auth();
+        }
+        builder.set_client_auth_certificates(vec![ca_cert()]);
+        builder.check().unwrap();
+        builder
+    }
+
+    fn client_builder(cert_pair: Option<RustlsCertificatePair>) -> RustlsClientConfigBuilder {
+        let mut builder = RustlsClientConfigBuilder::default();
+        builder.set_no_default_ca_certificates();
+        builder.set_ca_certificates(vec![ca_cert()]);
+        if let Some(pair) = cert_pair {
+            builder.set_cert_pair(pair);
+        }
+        builder.check().unwrap();
+        builder
+    }
+
+    fn handshake(
+        client: &mut ClientConnection,
+        server: &mut ServerConnection,
+    ) -> Result<(), Error> {
+        let mut buf = Vec::new();
+        while client.is_handshaking() || server.is_handshaking() {
+            let mut progress = false;
+            while client.wants_write() {
+                buf.clear();
+                client.write_tls(&mut buf).unwrap();
+                server.read_tls(&mut buf.as_slice()).unwrap();
+                server.process_new_packets()?;
+                progress = true;
+            }
+            while server.wants_write() {
+                buf.clear();
+                server.write_tls(&mut buf).unwrap();
+                client.read_tls(&mut buf.as_slice()).unwrap();
+                client.process_new_packets()?;
+                progress = true;
+            }
+            if !progress {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the number of client certs received by the server
+    fn connect(
+        server: RustlsServerConfigBuilder,
+        client: RustlsClientConfigBuilder,
+    ) -> Result<usize, Error> {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let server_config = server.build().unwrap();
+        let client_config = client.build().unwrap();
+        let mut server = ServerConnection::new(server_config.driver).unwrap();
+        let mut client = ClientConnection::new(
+            client_config.driver,
+            ServerName::try_from("server.example.net").unwrap(),
+        )
+        .unwrap();
+        handshake(&mut client, &mut server)?;
+        Ok(server.peer_certificates().map(|v| v.len()).unwrap_or(0))
+    }
+
+    #[test]
+    fn optional_client_auth() {
+        // accept the client without cert
+        assert_eq!(connect(server_builder(true), client_builder(None)), Ok(0));
+
+        // the cert will be available if sent
+        let pair = cert_pair(CLIENT_CERT, CLIENT_KEY);
+        assert_eq!(
+            connect(server_builder(true), client_builder(Some(pair))),
+            Ok(1)
+        );
+
+        // reject the client with bad cert
+        let pair = cert_pair(UNTRUSTED_CERT, UNTRUSTED_KEY);
+        assert!(connect(server_builder(true), client_builder(Some(pair))).is_err());
+    }
+
+    #[test]
+    fn required_client_auth() {
+        assert!(connect(server_builder(false), client_builder(None)).is_err());
+
+        let pair = cert_pair(CLIENT_CERT, CLIENT_KEY);
+        assert_eq!(
+            connect(server_builder(false), client_builder(Some(pair))),
+            Ok(1)
+        );
+
+        let pair = cert_pair(UNTRUSTED_CERT, UNTRUSTED_KEY);
+        assert!(connect(server_builder(false), client_builder(Some(pair))).is_err());
+    }
+}
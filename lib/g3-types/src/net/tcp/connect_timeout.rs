@@ -0,0 +1,128 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+use ip_network::IpNetwork;
+use ip_network_table::IpNetworkTable;
+use radix_trie::Trie;
+
+use crate::net::Host;
+use crate::resolve::reverse_idna_domain;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TcpConnectTimeoutRulesBuilder {
+    exact_domain: BTreeMap<Arc<str>, Duration>,
+    child_domain: BTreeMap<String, Duration>,
+    subnet: BTreeMap<IpNetwork, Duration>,
+}
+
+impl TcpConnectTimeoutRulesBuilder {
+    pub fn add_exact_domain(&mut self, domain: Arc<str>, timeout: Duration) {
+        self.exact_domain.insert(domain, timeout);
+    }
+
+    pub fn add_child_domain(&mut self, domain: String, timeout: Duration) {
+        self.child_domain.insert(domain, timeout);
+    }
+
+    pub fn add_subnet(&mut self, net: IpNetwork, timeout: Duration) {
+        self.subnet.insert(net, timeout);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.exact_domain.is_empty() && self.child_domain.is_empty() && self.subnet.is_empty()
+    }
+
+    pub fn build(&self) -> TcpConnectTimeoutRules {
+        let mut exact_domain = AHashMap::with_capacity(self.exact_domain.len());
+        for (domain, timeout) in &self.exact_domain {
+            exact_domain.insert(domain.clone(), *timeout);
+        }
+
+        let mut child_domain = Trie::new();
+        for (domain, timeout) in &self.child_domain {
+            child_domain.insert(reverse_idna_domain(domain), *timeout);
+        }
+
+        let mut subnet = IpNetworkTable::new();
+        for (net, timeout) in &self.subnet {
+            subnet.insert(*net, *timeout);
+        }
+
+        TcpConnectTimeoutRules {
+            exact_domain,
+            child_domain,
+            subnet,
+        }
+    }
+}
+
+/// Per destination tcp connect timeout.
+///
+/// Domain upstreams will be matched against the exact and child domain rules,
+/// and ip upstreams will be matched against the subnet rules.
+pub struct TcpConnectTimeoutRules {
+    exact_domain: AHashMap<Arc<str>, Duration>,
+    child_domain: Trie<String, Duration>,
+    subnet: IpNetworkTable<Duration>,
+}
+
+impl TcpConnectTimeoutRules {
+    pub fn get(&self, host: &Host) -> Option<Duration> {
+        match host {
+            Host::Ip(ip) => self.subnet.longest_match(*ip).map(|(_, v)| *v),
+            Host::Domain(domain) => {
+                if let Some(v) = self.exact_domain.get(domain) {
+                    return Some(*v);
+                }
+                let reversed = reverse_idna_domain(domain);
+                self.child_domain.get_ancestor_value(&reversed).copied()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::IpAddr;
+    use std::str::FromStr;
+
+    #[test]
+    fn get() {
+        let mut builder = TcpConnectTimeoutRulesBuilder::default();
+        builder.add_exact_domain(Arc::from("slow.example.net"), Duration::from_secs(60));
+        builder.add_child_domain("example.net".to_string(), Duration::from_secs(20));
+        builder.add_subnet(
+            IpNetwork::from_str("10.0.0.0/8").unwrap(),
+            Duration::from_secs(2),
+        );
+        builder.add_subnet(
+            IpNetwork::from_str("10.1.0.0/16").unwrap(),
+            Duration::from_secs(1),
+        );
+        let rules = builder.build();
+
+        let get_domain = |s: &str| rules.get(&Host::Domain(Arc::from(s)));
+        let get_ip = |s: &str| rules.get(&Host::Ip(IpAddr::from_str(s).unwrap()));
+
+        assert_eq!(
+            get_domain("slow.example.net"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(get_domain("www.example.net"), Some(Duration::from_secs(20)));
+        assert_eq!(get_domain("example.net"), Some(Duration::from_secs(20)));
+        assert_eq!(get_domain("myexample.net"), None);
+        assert_eq!(get_domain("example.org"), None);
+
+        assert_eq!(get_ip("10.2.0.1"), Some(Duration::from_secs(2)));
+        assert_eq!(get_ip("10.1.0.1"), Some(Duration::from_secs(1)));
+        assert_eq!(get_ip("192.168.0.1"), None);
+    }
+}
@@ -5,9 +5,15 @@
 
 use std::time::Duration;
 
+use anyhow::anyhow;
+
 use g3_std_ext::core::OptionExt;
 
 const DEFAULT_TCP_KEEPALIVE_IDLE: Duration = Duration::from_secs(60);
+// the same as MAX_TCP_KEEPIDLE and MAX_TCP_KEEPINTVL in linux kernel
+const MAX_TCP_KEEPALIVE_TIME: Duration = Duration::from_secs(32767);
+// the same as MAX_TCP_KEEPCNT in linux kernel
+const MAX_TCP_KEEPALIVE_PROBE_COUNT: u32 = 127;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct TcpKeepAliveConfig {
@@ -74,6 +80,27 @@ impl TcpKeepAliveConfig {
         self.probe_count
     }
 
+    /// Check the values against the limits of the OS socket options.
+    ///
+    /// The time values are set in seconds, so sub-second values are not allowed.
+    pub fn check(&self) -> anyhow::Result<()> {
+        check_time_value(self.idle_time).map_err(|e| anyhow!("invalid idle time: {e}"))?;
+        if let Some(interval) = self.probe_interval {
+            check_time_value(interval).map_err(|e| anyhow!("invalid probe interval: {e}"))?;
+        }
+        if let Some(count) = self.probe_count {
+            if count == 0 {
+                return Err(anyhow!("probe count should not be zero"));
+            }
+            if count > MAX_TCP_KEEPALIVE_PROBE_COUNT {
+                return Err(anyhow!(
+                    "probe count should not be larger than {MAX_TCP_KEEPALIVE_PROBE_COUNT}"
+                ));
+            }
+        }
+        Ok(())
+    }
+
     #[must_use]
     pub fn adjust_to(self, other: Self) -> Self {
         if self.enabled || other.enabled {
@@ -92,3 +119,48 @@ impl TcpKeepAliveConfig {
         }
     }
 }
+
+fn check_time_value(time: Duration) -> anyhow::Result<()> {
+    if time < Duration::from_secs(1) {
+        return Err(anyhow!("should be at least 1s"));
+    }
+    if time > MAX_TCP_KEEPALIVE_TIME {
+        return Err(anyhow!(
+            "should not be larger than {}s",
+            MAX_TCP_KEEPALIVE_TIME.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check() {
+        let mut config = TcpKeepAliveConfig::default_enabled();
+        assert!(config.check().is_ok());
+
+        config.set_probe_interval(Duration::from_secs(10));
+        config.set_probe_count(9);
+        assert!(config.check().is_ok());
+
+        config.set_probe_count(0);
+        assert!(config.check().is_err());
+        config.set_probe_count(128);
+        assert!(config.check().is_err());
+        config.set_probe_count(3);
+
+        config.set_probe_interval(Duration::from_millis(500));
+        assert!(config.check().is_err());
+        config.set_probe_interval(Duration::from_secs(10));
+
+        config.set_idle_time(Duration::ZERO);
+        assert!(config.check().is_err());
+        config.set_idle_time(Duration::from_secs(32768));
+        assert!(config.check().is_err());
+        config.set_idle_time(Duration::from_secs(32767));
+        assert!(config.check().is_ok());
+    }
+}
@@ -43,6 +43,9 @@ pub struct TcpListenConfig {
     scale: usize,
     follow_cpu_affinity: bool,
     keepalive: Option<TcpKeepAliveConfig>,
+    max_conn_per_source: usize,
+    source_ipv4_prefix: u8,
+    source_ipv6_prefix: u8,
 }
 
 impl Default for TcpListenConfig {
@@ -74,6 +77,9 @@ impl TcpListenConfig {
             scale: 0,
             follow_cpu_affinity: false,
             keepalive: None,
+            max_conn_per_source: 0,
+            source_ipv4_prefix: 32,
+            source_ipv6_prefix: 128,
         }
     }
 
@@ -221,4 +227,43 @@ impl TcpListenConfig {
     pub fn set_follow_cpu_affinity(&mut self, enable: bool) {
         self.follow_cpu_affinity = enable;
     }
+
+    /// The max number of concurrent connections from the same source network, 0 means no limit
+    #[inline]
+    pub fn max_conn_per_source(&self) -> usize {
+        self.max_conn_per_source
+    }
+
+    #[inline]
+    pub fn set_max_conn_per_source(&mut self, max: usize) {
+        self.max_conn_per_source = max;
+    }
+
+    /// The prefix length used to aggregate IPv4 source addresses
+    #[inline]
+    pub fn source_ipv4_prefix(&self) -> u8 {
+        self.source_ipv4_prefix
+    }
+
+    pub fn set_source_ipv4_prefix(&mut self, prefix: u8) -> anyhow::Result<()> {
+        if prefix == 0 || prefix > 32 {
+            return Err(anyhow!("invalid ipv4 prefix length {prefix}"));
+        }
+        self.source_ipv4_prefix = prefix;
+        Ok(())
+    }
+
+    /// The prefix length used to aggregate IPv6 source addresses
+    #[inline]
+    pub fn source_ipv6_prefix(&self) -> u8 {
+        self.source_ipv6_prefix
+    }
+
+    pub fn set_source_ipv6_prefix(&mut self, prefix: u8) -> anyhow::Result<()> {
+        if prefix == 0 || prefix > 128 {
+            return Err(anyhow!("invalid ipv6 prefix length {prefix}"));
+        }
+        self.source_ipv6_prefix = prefix;
+        Ok(())
+    }
 }
@@ -8,8 +8,14 @@ mod keepalive;
 mod listen;
 mod sockopt;
 
+#[cfg(feature = "acl-rule")]
+mod connect_timeout;
+
 pub use connect::{HappyEyeballsConfig, TcpConnectConfig};
 pub use listen::TcpListenConfig;
 
 pub use keepalive::TcpKeepAliveConfig;
 pub use sockopt::TcpMiscSockOpts;
+
+#[cfg(feature = "acl-rule")]
+pub use connect_timeout::{TcpConnectTimeoutRules, TcpConnectTimeoutRulesBuilder};
@@ -22,6 +22,8 @@ pub struct TcpMiscSockOpts {
     pub type_of_service: Option<u8>,
     #[cfg(not(windows))]
     pub traffic_class: Option<u8>,
+    pub send_buffer_size: Option<usize>,
+    pub recv_buffer_size: Option<usize>,
     #[cfg(any(
         target_os = "linux",
         target_os = "freebsd",
@@ -54,6 +56,10 @@ impl TcpMiscSockOpts {
         self.congestion_control.as_ref().map(|v| v.as_bytes())
     }
 
+    pub fn socket_buffer_set(&self) -> bool {
+        self.send_buffer_size.is_some() || self.recv_buffer_size.is_some()
+    }
+
     #[must_use]
     pub fn adjust_to(&self, other: &Self) -> Self {
         let no_delay = match (self.no_delay, other.no_delay) {
@@ -70,6 +76,8 @@ impl TcpMiscSockOpts {
             type_of_service: other.type_of_service.or(self.type_of_service),
             #[cfg(not(windows))]
             traffic_class: other.traffic_class.or(self.traffic_class),
+            send_buffer_size: other.send_buffer_size.or(self.send_buffer_size),
+            recv_buffer_size: other.recv_buffer_size.or(self.recv_buffer_size),
             #[cfg(any(
                 target_os = "linux",
                 target_os = "freebsd",
@@ -263,6 +271,8 @@ mod tests {
             type_of_service: None,
             #[cfg(not(windows))]
             traffic_class: Some(0x80),
+            send_buffer_size: Some(65536),
+            recv_buffer_size: None,
             #[cfg(any(
                 target_os = "linux",
                 target_os = "freebsd",
@@ -282,6 +292,8 @@ mod tests {
             type_of_service: Some(0x04),  // should win (other takes precedence)
             #[cfg(not(windows))]
             traffic_class: None, // config1 value should remain
+            send_buffer_size: Some(1048576), // should win (other takes precedence)
+            recv_buffer_size: None,       // config1 value should remain
             #[cfg(any(
                 target_os = "linux",
                 target_os = "freebsd",
@@ -302,6 +314,8 @@ mod tests {
         assert_eq!(result.type_of_service, Some(0x04));
         #[cfg(not(windows))]
         assert_eq!(result.traffic_class, Some(0x80));
+        assert_eq!(result.send_buffer_size, Some(1048576));
+        assert_eq!(result.recv_buffer_size, None);
         #[cfg(any(
             target_os = "linux",
             target_os = "freebsd",
@@ -5,10 +5,12 @@
 
 use std::cmp::Ordering;
 use std::fmt;
+use std::str::FromStr;
 
+use anyhow::anyhow;
 use thiserror::Error;
 
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub enum AlpnProtocol {
     Http10,
     Http11,
@@ -98,6 +100,15 @@ impl AlpnProtocol {
     }
 }
 
+impl FromStr for AlpnProtocol {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AlpnProtocol::from_selected(s.as_bytes())
+            .ok_or_else(|| anyhow!("unsupported alpn protocol {s}"))
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum TlsAlpnError {
     #[error("not enough data: {0}")]
@@ -202,6 +213,22 @@ mod tests {
         assert_eq!(AlpnProtocol::DnsOverQuic.as_str(), "doq");
     }
 
+    #[test]
+    fn protocol_from_str() {
+        assert_eq!(
+            AlpnProtocol::from_str("http/1.1").unwrap(),
+            AlpnProtocol::Http11
+        );
+        assert_eq!(AlpnProtocol::from_str("h2").unwrap(), AlpnProtocol::Http2);
+        assert_eq!(
+            AlpnProtocol::from_str("doq").unwrap(),
+            AlpnProtocol::DnsOverQuic
+        );
+        assert!(AlpnProtocol::from_str("H2").is_err());
+        assert!(AlpnProtocol::from_str("spdy/3").is_err());
+        assert!(AlpnProtocol::from_str("").is_err());
+    }
+
     #[test]
     fn protocol_sequence() {
         assert_eq!(
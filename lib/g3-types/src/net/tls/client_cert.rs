@@ -0,0 +1,151 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+#[cfg(feature = "openssl")]
+use std::fmt::Write;
+
+#[cfg(feature = "openssl")]
+use openssl::error::ErrorStack;
+#[cfg(feature = "openssl")]
+use openssl::hash::MessageDigest;
+#[cfg(feature = "openssl")]
+use openssl::x509::{X509, X509NameRef, X509Ref};
+
+/// The identity info of the certificate sent by the client in TLS client auth
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsClientCertInfo {
+    subject: String,
+    issuer: String,
+    fingerprint: String,
+}
+
+impl TlsClientCertInfo {
+    /// All control chars in the params will be replaced with '?'
+    pub fn new(subject: &str, issuer: &str, fingerprint: &str) -> Self {
+        TlsClientCertInfo {
+            subject: sanitize(subject),
+            issuer: sanitize(issuer),
+            fingerprint: sanitize(fingerprint),
+        }
+    }
+
+    #[inline]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    #[inline]
+    pub fn issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// The lower case hex encoded SHA-256 digest of the DER encoded certificate
+    #[inline]
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
+#[cfg(feature = "openssl")]
+impl TlsClientCertInfo {
+    pub fn parse_der(der: &[u8]) -> Result<Self, ErrorStack> {
+        let cert = X509::from_der(der)?;
+        TlsClientCertInfo::from_x509(&cert)
+    }
+
+    pub fn from_x509(cert: &X509Ref) -> Result<Self, ErrorStack> {
+        let digest = cert.digest(MessageDigest::sha256())?;
+        let mut fingerprint = String::with_capacity(digest.len() * 2);
+        for b in digest.iter() {
+            let _ = write!(fingerprint, "{b:02x}");
+        }
+        Ok(TlsClientCertInfo {
+            subject: format_name(cert.subject_name()),
+            issuer: format_name(cert.issuer_name()),
+            fingerprint,
+        })
+    }
+}
+
+#[cfg(feature = "openssl")]
+fn format_name(name: &X509NameRef) -> String {
+    let mut s = String::new();
+    for entry in name.entries() {
+        if !s.is_empty() {
+            s.push_str(", ");
+        }
+        let key = entry.object().nid().short_name().unwrap_or("UNDEF");
+        s.push_str(key);
+        s.push('=');
+        match entry.data().as_utf8() {
+            Ok(v) => s.push_str(&sanitize(&v)),
+            Err(_) => s.push('?'),
+        }
+    }
+    s
+}
+
+/// Make sure the value is safe to be used in log fields and protocol headers
+fn sanitize(s: &str) -> String {
+    s.chars()
+        .map(|c| if c.is_control() { '?' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let info = TlsClientCertInfo::new("CN=a\r\nX-Inject: 1", "CN=ca", "00ff");
+        assert_eq!(info.subject(), "CN=a??X-Inject: 1");
+        assert_eq!(info.issuer(), "CN=ca");
+        assert_eq!(info.fingerprint(), "00ff");
+    }
+
+    #[cfg(feature = "openssl")]
+    mod openssl_tests {
+        use super::*;
+        use openssl::asn1::Asn1Time;
+        use openssl::ec::{EcGroup, EcKey};
+        use openssl::nid::Nid;
+        use openssl::pkey::PKey;
+        use openssl::x509::X509NameBuilder;
+
+        #[test]
+        fn parse_der() {
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+            let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+            let mut name = X509NameBuilder::new().unwrap();
+            name.append_entry_by_nid(Nid::ORGANIZATIONNAME, "G3")
+                .unwrap();
+            name.append_entry_by_nid(Nid::COMMONNAME, "client").unwrap();
+            let name = name.build();
+
+            let mut builder = X509::builder().unwrap();
+            builder.set_version(2).unwrap();
+            builder.set_subject_name(&name).unwrap();
+            builder.set_issuer_name(&name).unwrap();
+            builder.set_pubkey(&key).unwrap();
+            builder
+                .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+                .unwrap();
+            builder
+                .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+                .unwrap();
+            builder.sign(&key, MessageDigest::sha256()).unwrap();
+            let cert = builder.build();
+
+            let info = TlsClientCertInfo::parse_der(&cert.to_der().unwrap()).unwrap();
+            assert_eq!(info.subject(), "O=G3, CN=client");
+            assert_eq!(info.issuer(), "O=G3, CN=client");
+            assert_eq!(info.fingerprint().len(), 64);
+
+            assert!(TlsClientCertInfo::parse_der(b"invalid").is_err());
+        }
+    }
+}
@@ -29,3 +29,6 @@ pub use version::TlsVersion;
 
 mod alert;
 pub use alert::{TlsAlert, TlsAlertType};
+
+mod client_cert;
+pub use client_cert::TlsClientCertInfo;
@@ -7,6 +7,8 @@ mod redirect;
 mod strategy;
 
 pub use redirect::{ResolveRedirection, ResolveRedirectionBuilder, ResolveRedirectionValue};
+#[cfg(feature = "resolve-test-seed")]
+pub use strategy::set_thread_pick_seed;
 pub use strategy::{PickStrategy, QueryStrategy, ResolveStrategy};
 
 /// the input domain should be valid IDNA domain
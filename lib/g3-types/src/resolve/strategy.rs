@@ -3,10 +3,45 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+#[cfg(any(test, feature = "resolve-test-seed"))]
+use std::cell::RefCell;
 use std::str::FromStr;
 
 use anyhow::anyhow;
 
+#[cfg(any(test, feature = "resolve-test-seed"))]
+thread_local! {
+    static PICK_TEST_RNG: RefCell<Option<fastrand::Rng>> = const { RefCell::new(None) };
+}
+
+/// Set a fixed seed for the random pick in the current thread, or reset to use entropy if None.
+///
+/// This is only available in tests, so the pick result in production won't be predictable.
+#[cfg(any(test, feature = "resolve-test-seed"))]
+pub fn set_thread_pick_seed(seed: Option<u64>) {
+    PICK_TEST_RNG.with_borrow_mut(|rng| *rng = seed.map(fastrand::Rng::with_seed));
+}
+
+fn random_shuffle<T>(all: &mut [T]) {
+    #[cfg(any(test, feature = "resolve-test-seed"))]
+    if PICK_TEST_RNG.with_borrow_mut(|rng| rng.as_mut().map(|rng| rng.shuffle(all)).is_some()) {
+        return;
+    }
+    fastrand::shuffle(all);
+}
+
+fn random_choice<T>(all: Vec<T>) -> Option<T> {
+    #[cfg(any(test, feature = "resolve-test-seed"))]
+    let all = match PICK_TEST_RNG.with_borrow_mut(|rng| match rng.as_mut() {
+        Some(rng) => Ok(rng.choice(all)),
+        None => Err(all),
+    }) {
+        Ok(v) => return v,
+        Err(all) => all,
+    };
+    fastrand::choice(all)
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub enum QueryStrategy {
     Ipv4Only,
@@ -124,7 +159,7 @@ impl ResolveStrategy {
                     all.truncate(count);
                 }
                 PickStrategy::Random => {
-                    fastrand::shuffle(&mut all);
+                    random_shuffle(&mut all);
                     all.truncate(count);
                 }
             }
@@ -136,7 +171,7 @@ impl ResolveStrategy {
         if all.len() > 1 {
             match self.pick {
                 PickStrategy::Serial => all.into_iter().next(),
-                PickStrategy::Random => fastrand::choice(all),
+                PickStrategy::Random => random_choice(all),
             }
         } else {
             all.pop()
@@ -203,4 +238,27 @@ mod tests {
         assert_eq!(s.pick_best(vec![1, 2]), Some(1));
         assert_eq!(s.pick_many(vec![1, 2, 3], 2), vec![1, 2]);
     }
+
+    #[test]
+    fn t_pick_seed() {
+        let s = ResolveStrategy::default();
+        let all: Vec<u32> = (0..32).collect();
+
+        set_thread_pick_seed(Some(42));
+        let many1 = s.pick_many(all.clone(), 8);
+        let best1 = s.pick_best(all.clone());
+
+        set_thread_pick_seed(Some(42));
+        let many2 = s.pick_many(all.clone(), 8);
+        let best2 = s.pick_best(all.clone());
+        assert_eq!(many1, many2);
+        assert_eq!(best1, best2);
+
+        set_thread_pick_seed(Some(7));
+        let many3 = s.pick_many(all.clone(), 8);
+        assert_ne!(many1, many3);
+
+        set_thread_pick_seed(None);
+        assert_eq!(s.pick_many(all, 8).len(), 8);
+    }
 }
@@ -4,7 +4,7 @@
  */
 
 use std::collections::HashMap;
-use std::collections::hash_map::Drain;
+use std::collections::hash_map::{Drain, ValuesMut};
 
 use foldhash::fast::FixedState;
 
@@ -37,6 +37,11 @@ impl<T> GlobalStatsMap<T> {
         self.inner.insert(stat_id, v)
     }
 
+    #[inline]
+    pub fn get(&self, stat_id: &StatId) -> Option<&T> {
+        self.inner.get(stat_id)
+    }
+
     #[inline]
     pub fn get_or_insert_with<F>(&mut self, stat_id: StatId, default: F) -> &mut T
     where
@@ -53,6 +58,11 @@ impl<T> GlobalStatsMap<T> {
         self.inner.retain(|_, v| f(v))
     }
 
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, StatId, T> {
+        self.inner.values_mut()
+    }
+
     #[inline]
     pub fn drain(&mut self) -> Drain<'_, StatId, T> {
         self.inner.drain()
@@ -42,6 +42,9 @@ pub use udp::{ThreadedUdpIoStats, UdpIoSnapshot, UdpIoStats};
 mod pool;
 pub use pool::ConnectionPoolStats;
 
+mod rate;
+pub use rate::IoRateEstimator;
+
 mod map;
 pub use map::GlobalStatsMap;
 
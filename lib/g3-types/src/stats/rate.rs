@@ -0,0 +1,123 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::time::{Duration, Instant};
+
+use super::TcpIoSnapshot;
+
+/// Estimate the io rate by using exponentially weighted moving average over cumulative byte counters.
+///
+/// The `update` method should be called periodically, and the time constant of the average
+/// is the same as the expected update interval.
+pub struct IoRateEstimator {
+    period: f64,
+    last_time: Option<Instant>,
+    last_snapshot: TcpIoSnapshot,
+    in_rate: f64,
+    out_rate: f64,
+}
+
+impl IoRateEstimator {
+    pub fn new(period: Duration) -> Self {
+        IoRateEstimator {
+            period: period.as_secs_f64().max(f64::EPSILON),
+            last_time: None,
+            last_snapshot: TcpIoSnapshot::default(),
+            in_rate: 0.0,
+            out_rate: 0.0,
+        }
+    }
+
+    pub fn update(&mut self, snapshot: TcpIoSnapshot, now: Instant) {
+        let Some(last_time) = self.last_time.replace(now) else {
+            self.last_snapshot = snapshot;
+            return;
+        };
+        let elapsed = now.saturating_duration_since(last_time).as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+
+        let in_diff = snapshot.in_bytes.wrapping_sub(self.last_snapshot.in_bytes);
+        let out_diff = snapshot
+            .out_bytes
+            .wrapping_sub(self.last_snapshot.out_bytes);
+        self.last_snapshot = snapshot;
+
+        // use the real elapsed time to calculate the weight, so jitters of the update interval are handled
+        let alpha = 1.0 - (-elapsed / self.period).exp();
+        self.in_rate += alpha * (in_diff as f64 / elapsed - self.in_rate);
+        self.out_rate += alpha * (out_diff as f64 / elapsed - self.out_rate);
+    }
+
+    /// The estimated incoming rate in bytes per second
+    pub fn in_rate(&self) -> u64 {
+        self.in_rate.round() as u64
+    }
+
+    /// The estimated outgoing rate in bytes per second
+    pub fn out_rate(&self) -> u64 {
+        self.out_rate.round() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(in_bytes: u64, out_bytes: u64) -> TcpIoSnapshot {
+        TcpIoSnapshot {
+            in_bytes,
+            out_bytes,
+        }
+    }
+
+    #[test]
+    fn first_update() {
+        let mut estimator = IoRateEstimator::new(Duration::from_secs(1));
+        estimator.update(snapshot(1000, 2000), Instant::now());
+        assert_eq!(estimator.in_rate(), 0);
+        assert_eq!(estimator.out_rate(), 0);
+    }
+
+    #[test]
+    fn steady_rate() {
+        let mut estimator = IoRateEstimator::new(Duration::from_secs(1));
+        let start = Instant::now();
+        for i in 0..=20u64 {
+            estimator.update(snapshot(i * 1000, i * 500), start + Duration::from_secs(i));
+        }
+        assert_eq!(estimator.in_rate(), 1000);
+        assert_eq!(estimator.out_rate(), 500);
+    }
+
+    #[test]
+    fn decay_to_zero() {
+        let mut estimator = IoRateEstimator::new(Duration::from_secs(1));
+        let start = Instant::now();
+        estimator.update(snapshot(0, 0), start);
+        estimator.update(snapshot(1000, 1000), start + Duration::from_secs(1));
+        let peak = estimator.in_rate();
+        assert!(peak > 0 && peak < 1000);
+
+        for i in 2..30u64 {
+            estimator.update(snapshot(1000, 1000), start + Duration::from_secs(i));
+        }
+        assert_eq!(estimator.in_rate(), 0);
+        assert_eq!(estimator.out_rate(), 0);
+    }
+
+    #[test]
+    fn counter_wrap() {
+        let mut estimator = IoRateEstimator::new(Duration::from_secs(1));
+        let start = Instant::now();
+        let mut v = u64::MAX - 500;
+        for i in 0..=20u64 {
+            estimator.update(snapshot(v, 0), start + Duration::from_secs(i));
+            v = v.wrapping_add(100);
+        }
+        assert_eq!(estimator.in_rate(), 100);
+    }
+}
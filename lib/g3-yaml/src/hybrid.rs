@@ -3,6 +3,7 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use std::cell::RefCell;
 use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
 
@@ -11,9 +12,21 @@ use yaml_rust::{Yaml, yaml};
 
 use super::YamlDocPosition;
 
+const INCLUDE_KEY: &str = "include";
+
+/// Load maps from a mix of paths and inline maps.
+///
+/// Besides plain paths, `{ include: <path> }` maps can be used to splice in the content of another
+/// file or directory. A relative path is resolved against the directory of the including file, or
+/// the conf directory if not in a file. Each map doc in the included file is loaded as a separate
+/// object, and each sequence doc is spliced in the place of the include, with its entries handled
+/// in the same way as the top level sequence. Maps are never merged with each other.
 pub struct HybridParser {
     conf_dir: PathBuf,
     conf_extension: Option<OsString>,
+    #[cfg(feature = "json")]
+    json: bool,
+    include_stack: RefCell<Vec<PathBuf>>,
 }
 
 impl HybridParser {
@@ -21,9 +34,23 @@ impl HybridParser {
         HybridParser {
             conf_dir: PathBuf::from(conf_dir),
             conf_extension: conf_extension.map(|v| v.to_os_string()),
+            #[cfg(feature = "json")]
+            json: false,
+            include_stack: RefCell::new(Vec::new()),
         }
     }
 
+    /// Also load files with the json extension, each as a single doc
+    #[cfg(feature = "json")]
+    pub fn enable_json(&mut self) {
+        self.json = true;
+    }
+
+    #[cfg(feature = "json")]
+    fn is_json_file(&self, path: &Path) -> bool {
+        self.json && super::json::is_json_file(path)
+    }
+
     pub fn foreach_map<F>(&self, value: &Yaml, f: F) -> anyhow::Result<()>
     where
         F: Fn(&yaml::Hash, Option<YamlDocPosition>) -> anyhow::Result<()>,
@@ -45,11 +72,25 @@ impl HybridParser {
         if path.is_absolute() {
             return Ok(path);
         }
-        let mut final_path = self.conf_dir.clone();
+        let mut final_path = match self.include_stack.borrow().last().and_then(|p| p.parent()) {
+            Some(dir) => dir.to_path_buf(),
+            None => self.conf_dir.clone(),
+        };
         final_path.push(path);
         Ok(final_path.canonicalize()?)
     }
 
+    fn get_include_path(map: &yaml::Hash) -> Option<anyhow::Result<&str>> {
+        if map.len() != 1 {
+            return None;
+        }
+        let v = map.get(&Yaml::String(INCLUDE_KEY.to_string()))?;
+        match v {
+            Yaml::String(path) => Some(Ok(path)),
+            _ => Some(Err(anyhow!("invalid path value for key {INCLUDE_KEY}"))),
+        }
+    }
+
     fn load_array<F>(&self, entries: &[Yaml], f: &F) -> anyhow::Result<()>
     where
         F: Fn(&yaml::Hash, Option<YamlDocPosition>) -> anyhow::Result<()>,
@@ -60,7 +101,13 @@ impl HybridParser {
                     .load_path(path, f)
                     .context(format!("#{i}: failed to load path {path}"))?,
                 Yaml::Hash(value) => {
-                    f(value, None).context(format!("#{i}: failed to load map {value:?}"))?
+                    if let Some(r) = Self::get_include_path(value) {
+                        let path = r.context(format!("#{i}: invalid include value"))?;
+                        self.load_path(path, f)
+                            .context(format!("#{i}: failed to include path {path}"))?
+                    } else {
+                        f(value, None).context(format!("#{i}: failed to load map {value:?}"))?
+                    }
                 }
                 _ => return Err(anyhow!("#{i}: value should be a path or a map")),
             }
@@ -100,7 +147,11 @@ impl HybridParser {
             let d_entry = d_entry?;
 
             let file_name = d_entry.path();
-            if let Some(conf_extension) = &self.conf_extension {
+            #[cfg(feature = "json")]
+            let is_json = self.is_json_file(&file_name);
+            #[cfg(not(feature = "json"))]
+            let is_json = false;
+            if !is_json && let Some(conf_extension) = &self.conf_extension {
                 let extension = match file_name.extension() {
                     Some(ext) => ext,
                     None => continue,
@@ -137,8 +188,54 @@ impl HybridParser {
     where
         F: Fn(&yaml::Hash, Option<YamlDocPosition>) -> anyhow::Result<()>,
     {
-        super::foreach_doc(path, |i, doc| match doc {
+        let real_path = path.canonicalize()?;
+        if self.include_stack.borrow().contains(&real_path) {
+            let mut chain = String::new();
+            for p in self.include_stack.borrow().iter() {
+                chain.push_str(&format!("{} -> ", p.display()));
+            }
+            return Err(anyhow!(
+                "include cycle detected: {chain}{}",
+                real_path.display()
+            ));
+        }
+
+        self.include_stack.borrow_mut().push(real_path);
+        let r = self.load_file_docs(path, f);
+        self.include_stack.borrow_mut().pop();
+        r
+    }
+
+    fn load_file_docs<F>(&self, path: &Path, f: &F) -> anyhow::Result<()>
+    where
+        F: Fn(&yaml::Hash, Option<YamlDocPosition>) -> anyhow::Result<()>,
+    {
+        #[cfg(feature = "json")]
+        if self.is_json_file(path) {
+            let doc = super::json::load_doc(path)?;
+            return self.load_doc(path, 0, &doc, f);
+        }
+
+        super::foreach_doc(path, |i, doc| self.load_doc(path, i, doc, f))
+    }
+
+    fn load_doc<F>(&self, path: &Path, i: usize, doc: &Yaml, f: &F) -> anyhow::Result<()>
+    where
+        F: Fn(&yaml::Hash, Option<YamlDocPosition>) -> anyhow::Result<()>,
+    {
+        match doc {
             Yaml::Hash(value) => {
+                if let Some(r) = Self::get_include_path(value) {
+                    let include = r.context(format!(
+                        "invalid include value in conf file {} doc {i}",
+                        path.display()
+                    ))?;
+                    return self.load_path(include, f).context(format!(
+                        "failed to include path {include} in conf file {} doc {i}",
+                        path.display()
+                    ));
+                }
+
                 let position = YamlDocPosition {
                     path: PathBuf::from(path),
                     index: i,
@@ -149,8 +246,15 @@ impl HybridParser {
                     i
                 ))
             }
-            _ => Err(anyhow!("doc {i} in {} should be a map", path.display())),
-        })
+            Yaml::Array(seq) => self.load_array(seq, f).context(format!(
+                "failed to load sequence in conf file {} doc {i}",
+                path.display()
+            )),
+            _ => Err(anyhow!(
+                "doc {i} in {} should be a map or a sequence",
+                path.display()
+            )),
+        }
     }
 }
 
@@ -345,6 +449,125 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn load_json() {
+        let test_dir = create_test_dir();
+        let mut parser = HybridParser::new(&test_dir, Some(OsStr::new("yaml")));
+        create_test_file(&test_dir, "a.yaml", "name: a\n");
+        create_test_file(&test_dir, "b.json", r#"{"name": "b"}"#);
+
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        parser
+            .load_dir(&test_dir, &|_, _| {
+                count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        parser.enable_json();
+        parser
+            .load_dir(&test_dir, &|_, _| {
+                count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count.load(std::sync::atomic::Ordering::Relaxed), 3);
+
+        let result = parser.load_file(&test_dir.join("b.json"), &|map, position| {
+            assert_eq!(position.unwrap().index, 0);
+            assert_eq!(
+                map.get(&Yaml::String("name".to_string())),
+                Some(&Yaml::String("b".to_string()))
+            );
+            Ok(())
+        });
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn include() {
+        let test_dir = create_test_dir();
+        let parser = HybridParser::new(&test_dir, Some(OsStr::new("yaml")));
+
+        let sub_dir = test_dir.join("sub");
+        create_test_file(&sub_dir, "a.yaml", "name: a\n---\ninclude: b.yaml\n");
+        create_test_file(&sub_dir, "b.yaml", "- name: b1\n- include: ../c.yaml\n");
+        create_test_file(&test_dir, "c.yaml", "name: c\n");
+
+        let names = RefCell::new(Vec::new());
+        let value = yaml_rust::YamlLoader::load_from_str("- include: sub/a.yaml\n- name: d\n")
+            .unwrap()
+            .remove(0);
+        parser
+            .foreach_map(&value, |map, _| {
+                let name = map.get(&Yaml::String("name".to_string())).unwrap();
+                names.borrow_mut().push(name.as_str().unwrap().to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(names.into_inner(), ["a", "b1", "c", "d"]);
+        assert!(parser.include_stack.borrow().is_empty());
+
+        // map with other keys is not an include
+        let value = yaml_rust::YamlLoader::load_from_str("- include: c.yaml\n  name: e\n")
+            .unwrap()
+            .remove(0);
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        parser
+            .foreach_map(&value, |map, _| {
+                assert_eq!(map.len(), 2);
+                count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count.load(std::sync::atomic::Ordering::Relaxed), 1);
+
+        // invalid include value
+        let value = yaml_rust::YamlLoader::load_from_str("- include: 1\n")
+            .unwrap()
+            .remove(0);
+        assert!(parser.foreach_map(&value, |_, _| Ok(())).is_err());
+    }
+
+    #[test]
+    fn include_cycle() {
+        let test_dir = create_test_dir();
+        let parser = HybridParser::new(&test_dir, Some(OsStr::new("yaml")));
+
+        create_test_file(&test_dir, "x.yaml", "name: x\n---\ninclude: y.yaml\n");
+        create_test_file(&test_dir, "y.yaml", "- include: x.yaml\n");
+        create_test_file(&test_dir, "self.yaml", "include: self.yaml\n");
+
+        let e = parser
+            .foreach_map(&Yaml::String("x.yaml".to_string()), |_, _| Ok(()))
+            .unwrap_err();
+        assert!(format!("{e:#}").contains("include cycle detected"));
+        assert!(parser.include_stack.borrow().is_empty());
+
+        let e = parser
+            .foreach_map(&Yaml::String("self.yaml".to_string()), |_, _| Ok(()))
+            .unwrap_err();
+        assert!(format!("{e:#}").contains("include cycle detected"));
+
+        // the same file can be included more than once if not nested
+        create_test_file(
+            &test_dir,
+            "z.yaml",
+            "- include: x2.yaml\n- include: x2.yaml\n",
+        );
+        create_test_file(&test_dir, "x2.yaml", "name: x2\n");
+        let count = std::sync::atomic::AtomicUsize::new(0);
+        parser
+            .foreach_map(&Yaml::String("z.yaml".to_string()), |_, _| {
+                count.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(count.load(std::sync::atomic::Ordering::Relaxed), 2);
+    }
+
     #[test]
     fn error_handling() {
         let test_dir = create_test_dir();
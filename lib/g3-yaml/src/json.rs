@@ -0,0 +1,117 @@
+/*
+ * SPDX-License-Identifier: Apache-2.0
+ * Copyright 2025 ByteDance and/or its affiliates.
+ */
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::anyhow;
+use serde_json::Value;
+use yaml_rust::{Yaml, yaml};
+
+const JSON_FILE_EXTENSION: &str = "json";
+
+/// Check if the file should be loaded as a JSON file, by extension
+pub fn is_json_file(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| ext.eq_ignore_ascii_case(JSON_FILE_EXTENSION))
+        .unwrap_or(false)
+}
+
+/// Convert the JSON value to the YAML value, so the same parse functions can be used
+pub fn to_yaml(value: &Value) -> anyhow::Result<Yaml> {
+    let v = match value {
+        Value::Null => Yaml::Null,
+        Value::Bool(b) => Yaml::Boolean(*b),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Yaml::Integer(i)
+            } else if n.is_u64() {
+                return Err(anyhow!("too large integer value {n}"));
+            } else {
+                Yaml::Real(n.to_string())
+            }
+        }
+        Value::String(s) => Yaml::String(s.clone()),
+        Value::Array(seq) => {
+            let mut array = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                let v = to_yaml(v).map_err(|e| anyhow!("invalid value for #{i}: {e}"))?;
+                array.push(v);
+            }
+            Yaml::Array(array)
+        }
+        Value::Object(map) => {
+            let mut hash = yaml::Hash::new();
+            for (k, v) in map {
+                let v = to_yaml(v).map_err(|e| anyhow!("invalid value for key {k}: {e}"))?;
+                hash.insert(Yaml::String(k.clone()), v);
+            }
+            Yaml::Hash(hash)
+        }
+    };
+    Ok(v)
+}
+
+/// Load the JSON file as a single YAML doc
+pub fn load_doc(path: &Path) -> anyhow::Result<Yaml> {
+    let mut conf = String::new();
+    File::open(path)?.read_to_string(&mut conf)?;
+
+    let value: Value = serde_json::from_str(&conf)?;
+    to_yaml(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn json_file() {
+        assert!(is_json_file(Path::new("/etc/g3statsd/exporter.json")));
+        assert!(is_json_file(Path::new("exporter.JSON")));
+        assert!(!is_json_file(Path::new("exporter.yaml")));
+        assert!(!is_json_file(Path::new("json")));
+    }
+
+    #[test]
+    fn same_as_yaml() {
+        let json = r#"{
+            "name": "graphite",
+            "type": "graphite",
+            "enable": true,
+            "port": 2003,
+            "ratio": 0.5,
+            "nil": null,
+            "tags": ["a", "b"],
+            "map": {"k": "v"}
+        }"#;
+        // keys are sorted in serde_json map
+        let yaml = r#"
+            enable: true
+            map:
+              k: v
+            name: graphite
+            nil: ~
+            port: 2003
+            ratio: 0.5
+            tags:
+              - a
+              - b
+            type: graphite
+        "#;
+        let value: Value = serde_json::from_str(json).unwrap();
+        let from_json = to_yaml(&value).unwrap();
+        let from_yaml = YamlLoader::load_from_str(yaml).unwrap().remove(0);
+        assert_eq!(from_json, from_yaml);
+    }
+
+    #[test]
+    fn too_large_integer() {
+        let value: Value = serde_json::from_str(r#"{"v": 18446744073709551615}"#).unwrap();
+        assert!(to_yaml(&value).is_err());
+    }
+}
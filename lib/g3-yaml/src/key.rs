@@ -3,10 +3,57 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
+use anyhow::anyhow;
+
+use crate::YamlDocPosition;
+
 pub fn normalize(raw: &str) -> String {
     raw.to_lowercase().replace('-', "_")
 }
 
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0usize; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Find the most similar one in the valid keys for the invalid key.
+///
+/// The key will be normalized before compared, and None will be returned if nothing is close enough.
+pub fn suggest<'a>(key: &str, valid_keys: &[&'a str]) -> Option<&'a str> {
+    let key = normalize(key);
+    let max_distance = key.chars().count().div_ceil(3);
+    valid_keys
+        .iter()
+        .map(|v| (edit_distance(&key, v), *v))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, v)| v)
+}
+
+/// Get the error for invalid key, with a suggestion from the valid keys if possible.
+pub fn invalid(key: &str, valid_keys: &[&str]) -> anyhow::Error {
+    invalid_at(key, valid_keys, None)
+}
+
+/// Get the error for invalid key, with the doc position and a suggestion from the valid keys if possible.
+pub fn invalid_at(key: &str, valid_keys: &[&str], doc: Option<&YamlDocPosition>) -> anyhow::Error {
+    let position = doc.map(|p| format!(" in {p}")).unwrap_or_default();
+    match suggest(key, valid_keys) {
+        Some(s) => anyhow!("invalid key {key}{position}, did you mean {s}?"),
+        None => anyhow!("invalid key {key}{position}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -18,4 +65,53 @@ mod tests {
         assert_eq!(normalize("A-B-C"), "a_b_c");
         assert_eq!(normalize("A-B_C"), "a_b_c");
     }
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", "abc"), 0);
+        assert_eq!(edit_distance("abc", "abd"), 1);
+        assert_eq!(edit_distance("abc", "ac"), 1);
+        assert_eq!(edit_distance("shfit", "shift"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn suggestion() {
+        const KEYS: &[&str] = &["shift_millis", "upload", "download", "north", "south"];
+        assert_eq!(suggest("shift_milis", KEYS), Some("shift_millis"));
+        assert_eq!(suggest("Shift-Millis", KEYS), Some("shift_millis"));
+        assert_eq!(suggest("uplaod", KEYS), Some("upload"));
+        assert_eq!(suggest("downlod", KEYS), Some("download"));
+        assert_eq!(suggest("nort", KEYS), Some("north"));
+        assert_eq!(suggest("timeout", KEYS), None);
+        assert_eq!(suggest("", KEYS), None);
+        assert_eq!(suggest("upload", &[]), None);
+    }
+
+    #[test]
+    fn error() {
+        const KEYS: &[&str] = &["upload", "download"];
+        assert_eq!(
+            invalid("uplod", KEYS).to_string(),
+            "invalid key uplod, did you mean upload?"
+        );
+        assert_eq!(invalid("foo", KEYS).to_string(), "invalid key foo");
+
+        let doc = YamlDocPosition {
+            path: "/etc/g3proxy/server.d/http.yaml".into(),
+            index: 1,
+        };
+        assert_eq!(
+            invalid_at("uplod", KEYS, Some(&doc)).to_string(),
+            "invalid key uplod in /etc/g3proxy/server.d/http.yaml#1, did you mean upload?"
+        );
+        assert_eq!(
+            invalid_at("foo", KEYS, Some(&doc)).to_string(),
+            "invalid key foo in /etc/g3proxy/server.d/http.yaml#1"
+        );
+        assert_eq!(invalid_at("foo", KEYS, None).to_string(), "invalid key foo");
+    }
 }
@@ -15,6 +15,9 @@ pub mod humanize;
 pub mod key;
 pub mod value;
 
+#[cfg(feature = "json")]
+pub mod json;
+
 pub use callback::YamlMapCallback;
 pub use hash::{
     foreach_kv, get_required as hash_get_required, get_required_str as hash_get_required_str,
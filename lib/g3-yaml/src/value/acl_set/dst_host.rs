@@ -9,6 +9,17 @@ use yaml_rust::Yaml;
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 
 pub fn as_dst_host_rule_set_builder(value: &Yaml) -> anyhow::Result<AclDstHostRuleSetBuilder> {
+    const KEYS: &[&str] = &[
+        "exact_match",
+        "exact",
+        "child_match",
+        "child",
+        "regex_match",
+        "regex",
+        "subnet_match",
+        "subnet",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = AclDstHostRuleSetBuilder::default();
 
@@ -37,7 +48,7 @@ pub fn as_dst_host_rule_set_builder(value: &Yaml) -> anyhow::Result<AclDstHostRu
                 builder.subnet = Some(subnet_builder);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
         Ok(builder)
     } else {
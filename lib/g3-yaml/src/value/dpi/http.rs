@@ -9,6 +9,19 @@ use yaml_rust::Yaml;
 use g3_dpi::{H1InterceptionConfig, H2InterceptionConfig};
 
 pub fn as_h1_interception_config(value: &Yaml) -> anyhow::Result<H1InterceptionConfig> {
+    const KEYS: &[&str] = &[
+        "pipeline_size",
+        "pipeline_read_idle_timeout",
+        "req_header_recv_timeout",
+        "rsp_header_recv_timeout",
+        "req_header_max_size",
+        "rsp_header_max_size",
+        "rsp_header_allow_obs_fold",
+        "rsp_reason_max_length",
+        "body_line_max_length",
+        "steal_forwarded_for",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = H1InterceptionConfig::default();
 
@@ -42,6 +55,15 @@ pub fn as_h1_interception_config(value: &Yaml) -> anyhow::Result<H1InterceptionC
                     .context(format!("invalid humanize usize value for key {k}"))?;
                 Ok(())
             }
+            "rsp_header_allow_obs_fold" => {
+                config.rsp_head_allow_obs_fold = crate::value::as_bool(v)?;
+                Ok(())
+            }
+            "rsp_reason_max_length" => {
+                config.rsp_reason_max_len = crate::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
             "body_line_max_length" => {
                 config.body_line_max_len = crate::value::as_usize(v)
                     .context(format!("invalid usize value for key {k}"))?;
@@ -51,7 +73,7 @@ pub fn as_h1_interception_config(value: &Yaml) -> anyhow::Result<H1InterceptionC
                 config.steal_forwarded_for = crate::value::as_bool(v)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -63,6 +85,22 @@ pub fn as_h1_interception_config(value: &Yaml) -> anyhow::Result<H1InterceptionC
 }
 
 pub fn as_h2_interception_config(value: &Yaml) -> anyhow::Result<H2InterceptionConfig> {
+    const KEYS: &[&str] = &[
+        "max_header_list_size",
+        "max_header_size",
+        "max_concurrent_streams",
+        "max_frame_size",
+        "stream_window_size",
+        "connection_window_size",
+        "max_send_buffer_size",
+        "upstream_handshake_timeout",
+        "upstream_stream_open_timeout",
+        "client_handshake_timeout",
+        "ping_interval",
+        "rsp_header_recv_timeout",
+        "silent_drop_expect_header",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = H2InterceptionConfig::default();
 
@@ -128,7 +166,7 @@ pub fn as_h2_interception_config(value: &Yaml) -> anyhow::Result<H2InterceptionC
                 config.silent_drop_expect_header = crate::value::as_bool(v)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -157,6 +195,8 @@ mod tests {
                 rsp_header_recv_timeout: 60s
                 req_header_max_size: 64KB
                 rsp_header_max_size: 64KB
+                rsp_header_allow_obs_fold: true
+                rsp_reason_max_length: 512
                 body_line_max_length: 8192
                 steal_forwarded_for: true
             "
@@ -168,6 +208,8 @@ mod tests {
         assert_eq!(config.rsp_head_recv_timeout, Duration::from_secs(60));
         assert_eq!(config.req_head_max_size, 64000);
         assert_eq!(config.rsp_head_max_size, 64000);
+        assert!(config.rsp_head_allow_obs_fold);
+        assert_eq!(config.rsp_reason_max_len, 512);
         assert_eq!(config.body_line_max_len, 8192);
         assert!(config.steal_forwarded_for);
 
@@ -180,6 +222,8 @@ mod tests {
         assert_eq!(config.rsp_head_recv_timeout, Duration::from_secs(60));
         assert_eq!(config.req_head_max_size, 65536);
         assert_eq!(config.rsp_head_max_size, 65536);
+        assert!(!config.rsp_head_allow_obs_fold);
+        assert_eq!(config.rsp_reason_max_len, 1024);
         assert_eq!(config.body_line_max_len, 8192);
         assert!(!config.steal_forwarded_for);
 
@@ -9,6 +9,16 @@ use yaml_rust::Yaml;
 use g3_dpi::ImapInterceptionConfig;
 
 pub fn as_imap_interception_config(value: &Yaml) -> anyhow::Result<ImapInterceptionConfig> {
+    const KEYS: &[&str] = &[
+        "greeting_timeout",
+        "authenticate_timeout",
+        "logout_wait_timeout",
+        "command_line_max_size",
+        "response_line_max_size",
+        "forward_max_idle_count",
+        "transfer_max_idle_count",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = ImapInterceptionConfig::default();
 
@@ -44,7 +54,7 @@ pub fn as_imap_interception_config(value: &Yaml) -> anyhow::Result<ImapIntercept
                 config.transfer_max_idle_count = crate::value::as_usize(v)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -12,6 +12,19 @@ pub fn parse_inspect_size_limit(
     config: &mut ProtocolInspectionSizeLimit,
     value: &Yaml,
 ) -> anyhow::Result<()> {
+    const KEYS: &[&str] = &[
+        "ftp_greeting_msg",
+        "ftp_server_greeting_msg",
+        "http_request_uri",
+        "http_client_request_uri",
+        "imap_greeting_msg",
+        "imap_server_greeting_msg",
+        "nats_info_line",
+        "nats_server_info_line",
+        "smtp_greeting_msg",
+        "smtp_server_greeting_msg",
+    ];
+
     if let Yaml::Hash(map) = value {
         crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
             "ftp_greeting_msg" | "ftp_server_greeting_msg" => {
@@ -39,7 +52,7 @@ pub fn parse_inspect_size_limit(
                 Ok(())
             }
             "smtp_greeting_msg" | "smtp_server_greeting_msg" => Ok(()),
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })
     } else {
         Err(anyhow!(
@@ -49,6 +62,14 @@ pub fn parse_inspect_size_limit(
 }
 
 pub fn as_protocol_inspection_config(value: &Yaml) -> anyhow::Result<ProtocolInspectionConfig> {
+    const KEYS: &[&str] = &[
+        "data0_buffer_size",
+        "inspect_max_depth",
+        "data0_wait_timeout",
+        "data0_read_timeout",
+        "data0_size_limit",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = ProtocolInspectionConfig::default();
 
@@ -78,7 +99,7 @@ pub fn as_protocol_inspection_config(value: &Yaml) -> anyhow::Result<ProtocolIns
             }
             "data0_size_limit" => parse_inspect_size_limit(config.size_limit_mut(), v)
                 .context(format!("invalid inspect size limit value for key {k}")),
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
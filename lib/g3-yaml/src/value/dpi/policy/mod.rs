@@ -55,6 +55,16 @@ fn as_protocol_inspect_action(value: &Yaml) -> anyhow::Result<ProtocolInspectAct
 pub fn as_protocol_inspect_policy_builder(
     value: &Yaml,
 ) -> anyhow::Result<ProtocolInspectPolicyBuilder> {
+    const KEYS: &[&str] = &[
+        "default",
+        "exact_match",
+        "exact",
+        "child_match",
+        "child",
+        "subnet_match",
+        "subnet",
+    ];
+
     match value {
         Yaml::Hash(map) => {
             let mut builder = ProtocolInspectPolicyBuilder::default();
@@ -84,7 +94,7 @@ pub fn as_protocol_inspect_policy_builder(
                     builder.subnet = Some(subnet_builder);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
             Ok(builder)
         }
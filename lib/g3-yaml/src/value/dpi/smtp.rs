@@ -9,6 +9,20 @@ use yaml_rust::Yaml;
 use g3_dpi::SmtpInterceptionConfig;
 
 pub fn as_smtp_interception_config(value: &Yaml) -> anyhow::Result<SmtpInterceptionConfig> {
+    const KEYS: &[&str] = &[
+        "greeting_timeout",
+        "quit_wait_timeout",
+        "command_wait_timeout",
+        "response_wait_timeout",
+        "data_initiation_timeout",
+        "data_termination_timeout",
+        "allow_on_demand_mail_relay",
+        "allow_odmr",
+        "allow_data_chunking",
+        "allow_burl_data",
+        "allow_burl",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = SmtpInterceptionConfig::default();
 
@@ -55,7 +69,7 @@ pub fn as_smtp_interception_config(value: &Yaml) -> anyhow::Result<SmtpIntercept
                 config.allow_burl_data = crate::value::as_bool(v)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -34,6 +34,17 @@ pub fn as_continent_code(value: &Yaml) -> anyhow::Result<ContinentCode> {
 }
 
 pub fn as_ip_location(value: &Yaml) -> anyhow::Result<IpLocation> {
+    const KEYS: &[&str] = &[
+        "network",
+        "net",
+        "country",
+        "continent",
+        "as_number",
+        "asn",
+        "isp_name",
+        "isp_domain",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = IpLocationBuilder::default();
 
@@ -74,7 +85,7 @@ pub fn as_ip_location(value: &Yaml) -> anyhow::Result<IpLocation> {
                 builder.set_isp_domain(domain);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         builder.build()
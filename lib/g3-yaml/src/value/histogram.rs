@@ -50,6 +50,8 @@ pub fn as_quantile_list(value: &Yaml) -> anyhow::Result<BTreeSet<Quantile>> {
 }
 
 pub fn as_histogram_metrics_config(value: &Yaml) -> anyhow::Result<HistogramMetricsConfig> {
+    const KEYS: &[&str] = &["quantile", "rotate"];
+
     if let Yaml::Hash(map) = value {
         let mut config = HistogramMetricsConfig::default();
         crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
@@ -65,7 +67,7 @@ pub fn as_histogram_metrics_config(value: &Yaml) -> anyhow::Result<HistogramMetr
                 config.set_rotate_interval(rotate);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
         Ok(config)
     } else {
@@ -186,6 +186,8 @@ pub fn as_weighted_upstream_addr(
     value: &Yaml,
     default_port: u16,
 ) -> anyhow::Result<WeightedUpstreamAddr> {
+    const KEYS: &[&str] = &["addr", "address", "weight"];
+
     match value {
         Yaml::String(_) => {
             let addr =
@@ -205,7 +207,7 @@ pub fn as_weighted_upstream_addr(
                     weight = crate::value::as_f64(v).context("invalid weight")?;
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
 
             if addr.is_empty() {
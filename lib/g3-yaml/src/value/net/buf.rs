@@ -9,6 +9,8 @@ use yaml_rust::Yaml;
 use g3_types::net::SocketBufferConfig;
 
 pub fn as_socket_buffer_config(value: &Yaml) -> anyhow::Result<SocketBufferConfig> {
+    const KEYS: &[&str] = &["recv", "receive", "read", "send", "write"];
+
     let mut config = SocketBufferConfig::default();
 
     match value {
@@ -32,7 +34,7 @@ pub fn as_socket_buffer_config(value: &Yaml) -> anyhow::Result<SocketBufferConfi
                     config.set_send_size(size);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid yaml value: {:?}", value)),
@@ -26,6 +26,7 @@ pub fn as_dns_encryption_protocol_builder(
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<DnsEncryptionConfigBuilder> {
     const KEY_TLS_NAME: &str = "tls_name";
+    const KEYS: &[&str] = &[KEY_TLS_NAME, "protocol", "tls_client"];
 
     match value {
         Yaml::Hash(map) => {
@@ -49,7 +50,7 @@ pub fn as_dns_encryption_protocol_builder(
                     config.set_tls_client_config(builder);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
 
             Ok(config)
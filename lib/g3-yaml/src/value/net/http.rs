@@ -11,10 +11,13 @@ use http::{HeaderName, HeaderValue};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    HttpForwardCapability, HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId,
+    HttpForwardCapability, HttpForwardedHeaderType, HttpHeaderRule, HttpHeaderRuleAction,
+    HttpHeaderRuleSet, HttpKeepAliveConfig, HttpServerId, HttpViaHeaderConfig,
 };
 
 pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig> {
+    const KEYS: &[&str] = &["enable", "idle_expire"];
+
     let mut config = HttpKeepAliveConfig::default();
 
     match v {
@@ -30,7 +33,7 @@ pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig>
                     config.set_idle_expire(idle_expire);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         Yaml::Boolean(enable) => {
@@ -65,6 +68,14 @@ pub fn as_http_forwarded_header_type(value: &Yaml) -> anyhow::Result<HttpForward
 }
 
 pub fn as_http_forward_capability(value: &Yaml) -> anyhow::Result<HttpForwardCapability> {
+    const KEYS: &[&str] = &[
+        "forward_https",
+        "forward_ftp_get",
+        "forward_ftp_put",
+        "forward_ftp_del",
+        "forward_ftp",
+    ];
+
     let mut cap = HttpForwardCapability::default();
 
     match value {
@@ -97,7 +108,7 @@ pub fn as_http_forward_capability(value: &Yaml) -> anyhow::Result<HttpForwardCap
                     Ok(())
                 }
                 "forward_ftp" => Ok(()),
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid yaml value type for HttpForwardCapability")),
@@ -117,6 +128,49 @@ pub fn as_http_server_id(value: &Yaml) -> anyhow::Result<HttpServerId> {
     }
 }
 
+pub fn as_http_via_header_config(value: &Yaml) -> anyhow::Result<HttpViaHeaderConfig> {
+    const KEYS: &[&str] = &["pseudonym", "request", "response", "collapse"];
+
+    let mut config = HttpViaHeaderConfig::default();
+
+    match value {
+        Yaml::Hash(map) => {
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "pseudonym" => {
+                    let pseudonym = as_http_server_id(v)
+                        .context(format!("invalid http server id value for key {k}"))?;
+                    config.pseudonym = Some(pseudonym);
+                    Ok(())
+                }
+                "request" => {
+                    config.request = crate::value::as_bool(v)?;
+                    Ok(())
+                }
+                "response" => {
+                    config.response = crate::value::as_bool(v)?;
+                    Ok(())
+                }
+                "collapse" => {
+                    config.collapse = crate::value::as_bool(v)?;
+                    Ok(())
+                }
+                _ => Err(crate::key::invalid(k, KEYS)),
+            })?;
+        }
+        Yaml::String(_) => {
+            let pseudonym = as_http_server_id(value)?;
+            config.pseudonym = Some(pseudonym);
+        }
+        _ => {
+            return Err(anyhow!(
+                "yaml value type for 'HttpViaHeaderConfig' should be 'map' or 'string'"
+            ));
+        }
+    }
+
+    Ok(config)
+}
+
 pub fn as_http_header_name(value: &Yaml) -> anyhow::Result<HeaderName> {
     if let Yaml::String(s) = value {
         HeaderName::from_str(s).map_err(|e| anyhow!(e))
@@ -133,6 +187,57 @@ pub fn as_http_header_value_string(value: &Yaml) -> anyhow::Result<String> {
     Ok(s)
 }
 
+fn as_http_header_rule(value: &Yaml) -> anyhow::Result<HttpHeaderRule> {
+    const KEYS: &[&str] = &["action", "name", "value"];
+
+    let Yaml::Hash(map) = value else {
+        return Err(anyhow!(
+            "yaml value type for 'HttpHeaderRule' should be 'map'"
+        ));
+    };
+
+    let mut action = None;
+    let mut name = None;
+    let mut header_value = None;
+    crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+        "action" => {
+            let s = crate::value::as_string(v)?;
+            let a = HttpHeaderRuleAction::from_str(&s)
+                .map_err(|_| anyhow!("invalid http header rule action {s}"))?;
+            action = Some(a);
+            Ok(())
+        }
+        "name" => {
+            let n =
+                as_http_header_name(v).context(format!("invalid header name value for key {k}"))?;
+            name = Some(n);
+            Ok(())
+        }
+        "value" => {
+            let s = as_http_header_value_string(v)?;
+            header_value = Some(HeaderValue::from_str(&s)?);
+            Ok(())
+        }
+        _ => Err(crate::key::invalid(k, KEYS)),
+    })?;
+
+    let Some(action) = action else {
+        return Err(anyhow!("no action set"));
+    };
+    let Some(name) = name else {
+        return Err(anyhow!("no header name set"));
+    };
+    HttpHeaderRule::new(action, name, header_value)
+}
+
+pub fn as_http_header_rule_set(value: &Yaml) -> anyhow::Result<HttpHeaderRuleSet> {
+    let mut set = HttpHeaderRuleSet::default();
+    for rule in crate::value::as_list(value, as_http_header_rule)? {
+        set.push(rule);
+    }
+    Ok(set)
+}
+
 pub fn as_http_path_and_query(value: &Yaml) -> anyhow::Result<PathAndQuery> {
     if let Yaml::String(s) = value {
         PathAndQuery::from_str(s).map_err(|e| anyhow!(e))
@@ -352,6 +457,68 @@ mod tests {
         assert!(as_http_forward_capability(&yaml).is_err());
     }
 
+    #[test]
+    fn as_http_header_rule_set_ok() {
+        let yaml = yaml_doc!(
+            r#"
+                - action: remove
+                  name: X-Tracking-Id
+                - action: add
+                  name: X-Forwarded-Proto
+                  value: https
+                - action: set
+                  name: User-Agent
+                  value: g3proxy
+            "#
+        );
+        let set = as_http_header_rule_set(&yaml).unwrap();
+        let rules = set.rules();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].action(), HttpHeaderRuleAction::Remove);
+        assert_eq!(rules[0].name().as_str(), "x-tracking-id");
+        assert_eq!(rules[1].action(), HttpHeaderRuleAction::Add);
+        assert_eq!(rules[2].action(), HttpHeaderRuleAction::Set);
+
+        // Valid config with a single rule
+        let yaml = yaml_doc!(
+            r#"
+                action: append
+                name: Via
+                value: 1.1 proxy
+            "#
+        );
+        let set = as_http_header_rule_set(&yaml).unwrap();
+        assert_eq!(set.rules().len(), 1);
+        assert_eq!(set.rules()[0].action(), HttpHeaderRuleAction::Append);
+    }
+
+    #[test]
+    fn as_http_header_rule_set_err() {
+        // Invalid action
+        let yaml = yaml_doc!("{ action: replace, name: X-A, value: a }");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+
+        // Missing value
+        let yaml = yaml_doc!("{ action: add, name: X-A }");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+
+        // Missing name
+        let yaml = yaml_doc!("{ action: remove }");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+
+        // Reserved header
+        let yaml = yaml_doc!("{ action: set, name: Host, value: example.net }");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+
+        // Invalid key
+        let yaml = yaml_doc!("{ action: remove, name: X-A, key: b }");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+
+        // Unsupported type
+        let yaml = yaml_str!("X-A");
+        assert!(as_http_header_rule_set(&yaml).is_err());
+    }
+
     #[test]
     fn as_http_server_id_ok() {
         // Valid config with string value
@@ -367,6 +534,40 @@ mod tests {
         assert!(as_http_server_id(&yaml).is_err());
     }
 
+    #[test]
+    fn as_http_via_header_config_ok() {
+        let yaml = yaml_str!("proxy-a");
+        let config = as_http_via_header_config(&yaml).unwrap();
+        assert_eq!(config.pseudonym.unwrap().as_str(), "proxy-a");
+        assert!(config.request);
+        assert!(config.response);
+        assert!(!config.collapse);
+
+        let yaml = yaml_doc!(
+            r#"
+                response: false
+                collapse: true
+            "#
+        );
+        let config = as_http_via_header_config(&yaml).unwrap();
+        assert!(config.pseudonym.is_none());
+        assert!(config.request);
+        assert!(!config.response);
+        assert!(config.collapse);
+    }
+
+    #[test]
+    fn as_http_via_header_config_err() {
+        let yaml = yaml_str!("proxy,a");
+        assert!(as_http_via_header_config(&yaml).is_err());
+
+        let yaml = yaml_doc!("invalid_key: true");
+        assert!(as_http_via_header_config(&yaml).is_err());
+
+        let yaml = Yaml::Integer(1);
+        assert!(as_http_via_header_config(&yaml).is_err());
+    }
+
     #[test]
     fn as_http_header_name_ok() {
         // Valid header name
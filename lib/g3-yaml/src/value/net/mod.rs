@@ -35,7 +35,7 @@ pub use tcp::{
     as_happy_eyeballs_config, as_tcp_connect_config, as_tcp_keepalive_config, as_tcp_listen_config,
     as_tcp_misc_sock_opts,
 };
-pub use tls::as_tls_version;
+pub use tls::{as_alpn_protocol, as_tls_version};
 pub use udp::{as_udp_listen_config, as_udp_misc_sock_opts};
 
 #[cfg(unix)]
@@ -43,12 +43,14 @@ pub use interface::as_interface;
 
 #[cfg(feature = "acl-rule")]
 pub use base::as_ip_network;
+#[cfg(feature = "acl-rule")]
+pub use tcp::as_tcp_connect_timeout_rules_builder;
 
 #[cfg(feature = "http")]
 pub use self::http::{
     as_http_forward_capability, as_http_forwarded_header_type, as_http_header_name,
-    as_http_header_value_string, as_http_keepalive_config, as_http_path_and_query,
-    as_http_server_id,
+    as_http_header_rule_set, as_http_header_value_string, as_http_keepalive_config,
+    as_http_path_and_query, as_http_server_id, as_http_via_header_config,
 };
 
 #[cfg(feature = "rustls")]
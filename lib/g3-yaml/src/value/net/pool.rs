@@ -9,6 +9,13 @@ use yaml_rust::Yaml;
 use g3_types::net::ConnectionPoolConfig;
 
 pub fn as_connection_pool_config(value: &Yaml) -> anyhow::Result<ConnectionPoolConfig> {
+    const KEYS: &[&str] = &[
+        "check_interval",
+        "max_idle_count",
+        "min_idle_count",
+        "idle_timeout",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut config = ConnectionPoolConfig::default();
         crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
@@ -34,7 +41,7 @@ pub fn as_connection_pool_config(value: &Yaml) -> anyhow::Result<ConnectionPoolC
                 config.set_idle_timeout(timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
         Ok(config)
     } else {
@@ -44,6 +44,8 @@ pub fn as_ports(value: &Yaml) -> anyhow::Result<Ports> {
 }
 
 pub fn as_port_range(value: &Yaml) -> anyhow::Result<PortRange> {
+    const KEYS: &[&str] = &["start", "from", "end", "to"];
+
     match value {
         Yaml::String(s) => PortRange::from_str(s),
         Yaml::Hash(map) => {
@@ -61,7 +63,7 @@ pub fn as_port_range(value: &Yaml) -> anyhow::Result<PortRange> {
                         .context(format!("invalid port number for key {k}"))?;
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })
             .context("invalid port range map value")?;
 
@@ -4,10 +4,14 @@
  */
 
 use std::str::FromStr;
+#[cfg(feature = "acl-rule")]
+use std::sync::Arc;
 
 use anyhow::{Context, anyhow};
 use yaml_rust::Yaml;
 
+#[cfg(feature = "acl-rule")]
+use g3_types::net::TcpConnectTimeoutRulesBuilder;
 use g3_types::net::{
     HappyEyeballsConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpListenConfig, TcpMiscSockOpts,
 };
@@ -58,6 +62,27 @@ fn set_tcp_listen_scale(config: &mut TcpListenConfig, v: &Yaml) -> anyhow::Resul
 }
 
 pub fn as_tcp_listen_config(value: &Yaml) -> anyhow::Result<TcpListenConfig> {
+    const KEYS: &[&str] = &[
+        "addr",
+        "address",
+        "interface",
+        "backlog",
+        "ipv6only",
+        "ipv6_only",
+        "instance",
+        "instance_count",
+        "netfilter_mark",
+        "fwmark",
+        "mark",
+        "scale",
+        "follow_cpu_affinity",
+        "keepalive",
+        "max_conn_per_source",
+        "max_connection_per_source",
+        "source_ipv4_prefix",
+        "source_ipv6_prefix",
+    ];
+
     let mut config = TcpListenConfig::default();
 
     match value {
@@ -131,7 +156,23 @@ pub fn as_tcp_listen_config(value: &Yaml) -> anyhow::Result<TcpListenConfig> {
                     config.set_keepalive(keepalive);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                "max_conn_per_source" | "max_connection_per_source" => {
+                    let max = crate::value::as_usize(v)
+                        .context(format!("invalid usize value for key {k}"))?;
+                    config.set_max_conn_per_source(max);
+                    Ok(())
+                }
+                "source_ipv4_prefix" => {
+                    let prefix =
+                        crate::value::as_u8(v).context(format!("invalid u8 value for key {k}"))?;
+                    config.set_source_ipv4_prefix(prefix)
+                }
+                "source_ipv6_prefix" => {
+                    let prefix =
+                        crate::value::as_u8(v).context(format!("invalid u8 value for key {k}"))?;
+                    config.set_source_ipv6_prefix(prefix)
+                }
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid value type")),
@@ -142,6 +183,8 @@ pub fn as_tcp_listen_config(value: &Yaml) -> anyhow::Result<TcpListenConfig> {
 }
 
 pub fn as_tcp_connect_config(v: &Yaml) -> anyhow::Result<TcpConnectConfig> {
+    const KEYS: &[&str] = &["max_retry", "each_timeout"];
+
     if let Yaml::Hash(map) = v {
         let mut config = TcpConnectConfig::default();
 
@@ -156,7 +199,7 @@ pub fn as_tcp_connect_config(v: &Yaml) -> anyhow::Result<TcpConnectConfig> {
                 config.set_each_timeout(each_timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -168,6 +211,14 @@ pub fn as_tcp_connect_config(v: &Yaml) -> anyhow::Result<TcpConnectConfig> {
 }
 
 pub fn as_happy_eyeballs_config(v: &Yaml) -> anyhow::Result<HappyEyeballsConfig> {
+    const KEYS: &[&str] = &[
+        "resolution_delay",
+        "first_resolution_delay",
+        "second_resolution_timeout",
+        "first_address_family_count",
+        "connection_attempt_delay",
+    ];
+
     if let Yaml::Hash(map) = v {
         let mut config = HappyEyeballsConfig::default();
 
@@ -192,7 +243,7 @@ pub fn as_happy_eyeballs_config(v: &Yaml) -> anyhow::Result<HappyEyeballsConfig>
                 config.set_connection_attempt_delay(delay);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -204,6 +255,8 @@ pub fn as_happy_eyeballs_config(v: &Yaml) -> anyhow::Result<HappyEyeballsConfig>
 }
 
 pub fn as_tcp_keepalive_config(v: &Yaml) -> anyhow::Result<TcpKeepAliveConfig> {
+    const KEYS: &[&str] = &["enable", "idle_time", "probe_interval", "probe_count"];
+
     let mut config = TcpKeepAliveConfig::default();
 
     match v {
@@ -229,7 +282,7 @@ pub fn as_tcp_keepalive_config(v: &Yaml) -> anyhow::Result<TcpKeepAliveConfig> {
                     config.set_probe_count(count);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         Yaml::Boolean(enable) => {
@@ -243,10 +296,42 @@ pub fn as_tcp_keepalive_config(v: &Yaml) -> anyhow::Result<TcpKeepAliveConfig> {
         }
     }
 
+    config.check().context("invalid tcp keepalive config")?;
     Ok(config)
 }
 
+fn as_socket_buffer_size(v: &Yaml) -> anyhow::Result<usize> {
+    let size = crate::humanize::as_usize(v)?;
+    if size == 0 {
+        return Err(anyhow!("the size should not be zero"));
+    }
+    // the kernel will take the value as a c int
+    if size > i32::MAX as usize {
+        return Err(anyhow!("the size should not be larger than {}", i32::MAX));
+    }
+    Ok(size)
+}
+
 pub fn as_tcp_misc_sock_opts(v: &Yaml) -> anyhow::Result<TcpMiscSockOpts> {
+    const KEYS: &[&str] = &[
+        "no_delay",
+        "max_segment_size",
+        "mss",
+        "time_to_live",
+        "ttl",
+        "hop_limit",
+        "type_of_service",
+        "tos",
+        "traffic_class",
+        "send_buffer_size",
+        "sndbuf",
+        "recv_buffer_size",
+        "rcvbuf",
+        "congestion_control",
+        "netfilter_mark",
+        "mark",
+    ];
+
     let mut config = TcpMiscSockOpts::default();
 
     if let Yaml::Hash(map) = v {
@@ -288,6 +373,18 @@ pub fn as_tcp_misc_sock_opts(v: &Yaml) -> anyhow::Result<TcpMiscSockOpts> {
                 config.traffic_class = Some(class);
                 Ok(())
             }
+            "send_buffer_size" | "sndbuf" => {
+                let size = as_socket_buffer_size(v)
+                    .context(format!("invalid socket buffer size value for key {k}"))?;
+                config.send_buffer_size = Some(size);
+                Ok(())
+            }
+            "recv_buffer_size" | "rcvbuf" => {
+                let size = as_socket_buffer_size(v)
+                    .context(format!("invalid socket buffer size value for key {k}"))?;
+                config.recv_buffer_size = Some(size);
+                Ok(())
+            }
             #[cfg(any(
                 target_os = "linux",
                 target_os = "freebsd",
@@ -306,7 +403,7 @@ pub fn as_tcp_misc_sock_opts(v: &Yaml) -> anyhow::Result<TcpMiscSockOpts> {
                 config.netfilter_mark = Some(mark);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -317,6 +414,67 @@ pub fn as_tcp_misc_sock_opts(v: &Yaml) -> anyhow::Result<TcpMiscSockOpts> {
     }
 }
 
+#[cfg(feature = "acl-rule")]
+pub fn as_tcp_connect_timeout_rules_builder(
+    v: &Yaml,
+) -> anyhow::Result<TcpConnectTimeoutRulesBuilder> {
+    const KEYS: &[&str] = &[
+        "timeout",
+        "exact_match",
+        "exact",
+        "child_match",
+        "child",
+        "subnet_match",
+        "subnet",
+    ];
+
+    let Yaml::Array(seq) = v else {
+        return Err(anyhow!(
+            "yaml value type for 'TcpConnectTimeoutRules' should be 'array'"
+        ));
+    };
+
+    let mut builder = TcpConnectTimeoutRulesBuilder::default();
+    for (i, v) in seq.iter().enumerate() {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("yaml value type for #{i} should be map"));
+        };
+
+        let v = crate::hash::get_required(map, "timeout")
+            .context(format!("no timeout set for rule #{i}"))?;
+        let timeout = crate::humanize::as_duration(v)
+            .context(format!("invalid timeout value for rule #{i}"))?;
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "timeout" => Ok(()),
+            "exact_match" | "exact" => {
+                let domains = crate::value::as_list(v, crate::value::as_domain)?;
+                for domain in domains {
+                    builder.add_exact_domain(Arc::from(domain), timeout);
+                }
+                Ok(())
+            }
+            "child_match" | "child" => {
+                let domains = crate::value::as_list(v, crate::value::as_domain)?;
+                for domain in domains {
+                    builder.add_child_domain(domain, timeout);
+                }
+                Ok(())
+            }
+            "subnet_match" | "subnet" => {
+                let nets = crate::value::as_list(v, crate::value::as_ip_network)?;
+                for net in nets {
+                    builder.add_subnet(net, timeout);
+                }
+                Ok(())
+            }
+            _ => Err(crate::key::invalid(k, KEYS)),
+        })
+        .context(format!("invalid tcp connect timeout rule #{i}"))?;
+    }
+    Ok(builder)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,6 +533,19 @@ mod tests {
         let config = as_tcp_listen_config(&yaml).unwrap();
         assert_eq!(config.is_ipv6only(), Some(false));
 
+        let yaml = yaml_doc!(
+            r#"
+                address: "[::]:8085"
+                max_conn_per_source: 10
+                source_ipv4_prefix: 24
+                source_ipv6_prefix: 64
+            "#
+        );
+        let config = as_tcp_listen_config(&yaml).unwrap();
+        assert_eq!(config.max_conn_per_source(), 10);
+        assert_eq!(config.source_ipv4_prefix(), 24);
+        assert_eq!(config.source_ipv6_prefix(), 64);
+
         let yaml_map = yaml_doc!("scale: \"50%\"");
         let mut cfg = TcpListenConfig::default();
         assert!(set_tcp_listen_scale(&mut cfg, &yaml_map["scale"]).is_ok());
@@ -404,6 +575,14 @@ mod tests {
         let yaml = yaml_doc!("\"not_an_address\"");
         assert!(as_tcp_listen_config(&yaml).is_err());
 
+        let yaml = yaml_doc!(
+            r#"
+                address: "[::]:8085"
+                source_ipv4_prefix: 33
+            "#
+        );
+        assert!(as_tcp_listen_config(&yaml).is_err());
+
         let yaml_map = yaml_doc!("scale: true");
         let mut cfg = TcpListenConfig::default();
         assert!(set_tcp_listen_scale(&mut cfg, &yaml_map["scale"]).is_err());
@@ -427,6 +606,10 @@ mod tests {
         let yaml = yaml_doc!("invalid_key: 123");
         assert!(as_tcp_listen_config(&yaml).is_err());
 
+        let yaml = yaml_doc!("fwmrak: 1");
+        let e = as_tcp_listen_config(&yaml).unwrap_err();
+        assert!(format!("{e:#}").ends_with("invalid key fwmrak, did you mean fwmark?"));
+
         let yaml = yaml_doc!("[1, 2, 3]");
         assert!(as_tcp_listen_config(&yaml).is_err());
     }
@@ -566,6 +749,15 @@ mod tests {
 
         let yaml = yaml_doc!("enable: \"not_a_bool\"");
         assert!(as_tcp_keepalive_config(&yaml).is_err());
+
+        let yaml = yaml_doc!("idle_time: 500ms");
+        assert!(as_tcp_keepalive_config(&yaml).is_err());
+
+        let yaml = yaml_doc!("probe_count: 0");
+        assert!(as_tcp_keepalive_config(&yaml).is_err());
+
+        let yaml = yaml_doc!("probe_count: 128");
+        assert!(as_tcp_keepalive_config(&yaml).is_err());
     }
 
     #[test]
@@ -577,9 +769,13 @@ mod tests {
                 time_to_live: 64
                 hop_limit: 64
                 type_of_service: 0x10
+                send_buffer_size: 4MiB
+                recv_buffer_size: 1048576
             "#
         );
         let config = as_tcp_misc_sock_opts(&yaml).unwrap();
+        assert_eq!(config.send_buffer_size, Some(4 * 1024 * 1024));
+        assert_eq!(config.recv_buffer_size, Some(1024 * 1024));
         assert_eq!(config.no_delay, Some(true));
         assert_eq!(config.max_segment_size, Some(1460));
         assert_eq!(config.time_to_live, Some(64));
@@ -613,5 +809,60 @@ mod tests {
 
         let yaml = yaml_doc!("type_of_service: \"not_u8\"");
         assert!(as_tcp_misc_sock_opts(&yaml).is_err());
+
+        let yaml = yaml_doc!("send_buffer_size: 0");
+        assert!(as_tcp_misc_sock_opts(&yaml).is_err());
+
+        let yaml = yaml_doc!("recv_buffer_size: 4GiB");
+        assert!(as_tcp_misc_sock_opts(&yaml).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "acl-rule")]
+    fn as_tcp_connect_timeout_rules_builder_ok() {
+        use g3_types::net::Host;
+
+        let yaml = yaml_doc!(
+            r#"
+                - exact_match: slow.example.net
+                  timeout: 60s
+                - child_match: [example.org, example.com]
+                  timeout: 10s
+                - subnet_match: 10.0.0.0/8
+                  timeout: 1s
+            "#
+        );
+        let rules = as_tcp_connect_timeout_rules_builder(&yaml).unwrap().build();
+        assert_eq!(
+            rules.get(&Host::Domain(Arc::from("slow.example.net"))),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            rules.get(&Host::Domain(Arc::from("www.example.com"))),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            rules.get(&Host::Ip(IpAddr::from([10, 1, 2, 3]))),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(rules.get(&Host::Ip(IpAddr::from([192, 168, 0, 1]))), None);
+    }
+
+    #[test]
+    #[cfg(feature = "acl-rule")]
+    fn as_tcp_connect_timeout_rules_builder_err() {
+        let yaml = yaml_doc!("exact_match: example.net");
+        assert!(as_tcp_connect_timeout_rules_builder(&yaml).is_err());
+
+        let yaml = yaml_doc!("- exact_match: example.net");
+        assert!(as_tcp_connect_timeout_rules_builder(&yaml).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                - unknown_match: example.net
+                  timeout: 1s
+            "#
+        );
+        assert!(as_tcp_connect_timeout_rules_builder(&yaml).is_err());
     }
 }
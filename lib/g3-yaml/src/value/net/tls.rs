@@ -8,7 +8,7 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use yaml_rust::Yaml;
 
-use g3_types::net::TlsVersion;
+use g3_types::net::{AlpnProtocol, TlsVersion};
 
 pub fn as_tls_version(value: &Yaml) -> anyhow::Result<TlsVersion> {
     match value {
@@ -23,6 +23,16 @@ pub fn as_tls_version(value: &Yaml) -> anyhow::Result<TlsVersion> {
     }
 }
 
+pub fn as_alpn_protocol(value: &Yaml) -> anyhow::Result<AlpnProtocol> {
+    if let Yaml::String(s) = value {
+        AlpnProtocol::from_str(s)
+    } else {
+        Err(anyhow!(
+            "yaml value type for alpn protocol should be 'string'"
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -75,4 +85,23 @@ mod tests {
         assert!(as_tls_version(&Yaml::Integer(1)).is_err());
         assert!(as_tls_version(&Yaml::Array(vec![])).is_err());
     }
+
+    #[test]
+    fn as_alpn_protocol_ok() {
+        assert_eq!(
+            as_alpn_protocol(&yaml_str!("http/1.1")).unwrap(),
+            AlpnProtocol::Http11
+        );
+        assert_eq!(
+            as_alpn_protocol(&yaml_str!("h2")).unwrap(),
+            AlpnProtocol::Http2
+        );
+    }
+
+    #[test]
+    fn as_alpn_protocol_err() {
+        assert!(as_alpn_protocol(&yaml_str!("h2c")).is_err());
+        assert!(as_alpn_protocol(&yaml_str!("")).is_err());
+        assert!(as_alpn_protocol(&Yaml::Integer(2)).is_err());
+    }
 }
@@ -11,6 +11,17 @@ use yaml_rust::Yaml;
 use g3_types::net::{UdpListenConfig, UdpMiscSockOpts};
 
 pub fn as_udp_misc_sock_opts(v: &Yaml) -> anyhow::Result<UdpMiscSockOpts> {
+    const KEYS: &[&str] = &[
+        "time_to_live",
+        "ttl",
+        "hop_limit",
+        "type_of_service",
+        "tos",
+        "traffic_class",
+        "netfilter_mark",
+        "mark",
+    ];
+
     let mut config = UdpMiscSockOpts::default();
 
     if let Yaml::Hash(map) = v {
@@ -47,7 +58,7 @@ pub fn as_udp_misc_sock_opts(v: &Yaml) -> anyhow::Result<UdpMiscSockOpts> {
                 config.netfilter_mark = Some(mark);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         Ok(config)
@@ -104,6 +115,19 @@ fn set_udp_listen_scale(config: &mut UdpListenConfig, v: &Yaml) -> anyhow::Resul
 }
 
 pub fn as_udp_listen_config(value: &Yaml) -> anyhow::Result<UdpListenConfig> {
+    const KEYS: &[&str] = &[
+        "addr",
+        "address",
+        "interface",
+        "ipv6only",
+        "ipv6_only",
+        "socket_buffer",
+        "socket_misc_opts",
+        "instance",
+        "instance_count",
+        "scale",
+    ];
+
     let mut config = UdpListenConfig::default();
 
     match value {
@@ -165,7 +189,7 @@ pub fn as_udp_listen_config(value: &Yaml) -> anyhow::Result<UdpListenConfig> {
                 }
                 "scale" => set_udp_listen_scale(&mut config, v)
                     .context(format!("invalid scale value for key {k}")),
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid value type")),
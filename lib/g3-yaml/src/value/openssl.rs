@@ -96,6 +96,8 @@ pub fn as_openssl_certificate_pair(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<OpensslCertificatePair> {
+    const KEYS: &[&str] = &["certificate", "cert", "private_key", "key"];
+
     if let Yaml::Hash(map) = value {
         let mut pair = OpensslCertificatePair::default();
 
@@ -114,7 +116,7 @@ pub fn as_openssl_certificate_pair(
                     .context("failed to set private key")?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         pair.check()?;
@@ -130,6 +132,17 @@ pub fn as_openssl_tlcp_certificate_pair(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<OpensslTlcpCertificatePair> {
+    const KEYS: &[&str] = &[
+        "sign_certificate",
+        "sign_cert",
+        "enc_certificate",
+        "enc_cert",
+        "sign_private_key",
+        "sign_key",
+        "enc_private_key",
+        "enc_key",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut pair = OpensslTlcpCertificatePair::default();
 
@@ -162,7 +175,7 @@ pub fn as_openssl_tlcp_certificate_pair(
                     .context("failed to set private key")?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         pair.check()?;
@@ -214,6 +227,43 @@ fn set_openssl_tls_client_config_builder(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<OpensslClientConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "protocol",
+        "min_tls_version",
+        "tls_version_min",
+        "max_tls_version",
+        "tls_version_max",
+        "ciphers",
+        "disable_sni",
+        "certificate",
+        "cert",
+        "private_key",
+        "key",
+        "cert_pair",
+        "tlcp_cert_pair",
+        "ca_certificate",
+        "ca_cert",
+        "server_auth_certificate",
+        "server_auth_cert",
+        "no_default_ca_certificate",
+        "no_default_ca_cert",
+        "handshake_timeout",
+        "negotiation_timeout",
+        "no_session_cache",
+        "disable_session_cache",
+        "session_cache_disabled",
+        "use_builtin_session_cache",
+        "session_cache_lru_max_sites",
+        "session_cache_each_capacity",
+        "session_cache_each_cap",
+        "supported_groups",
+        "use_ocsp_stapling",
+        "enable_sct",
+        "enable_grease",
+        "permute_extensions",
+        "insecure",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut cert_pair = OpensslCertificatePair::default();
 
@@ -358,7 +408,7 @@ fn set_openssl_tls_client_config_builder(
                 builder.set_insecure(enable);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         if cert_pair.is_set() && builder.set_cert_pair(cert_pair).is_some() {
@@ -394,6 +444,33 @@ pub fn as_tls_interception_client_config_builder(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<OpensslInterceptionClientConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "min_tls_version",
+        "tls_version_min",
+        "max_tls_version",
+        "tls_version_max",
+        "ca_certificate",
+        "ca_cert",
+        "server_auth_certificate",
+        "server_auth_cert",
+        "no_default_ca_certificate",
+        "no_default_ca_cert",
+        "handshake_timeout",
+        "negotiation_timeout",
+        "no_session_cache",
+        "disable_session_cache",
+        "session_cache_disabled",
+        "session_cache_lru_max_sites",
+        "session_cache_each_capacity",
+        "session_cache_each_cap",
+        "supported_groups",
+        "use_ocsp_stapling",
+        "enable_sct",
+        "enable_grease",
+        "permute_extensions",
+        "insecure",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = OpensslInterceptionClientConfigBuilder::default();
 
@@ -482,7 +559,7 @@ pub fn as_tls_interception_client_config_builder(
                 builder.set_insecure(enable);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         builder.check()?;
@@ -498,6 +575,24 @@ pub fn as_openssl_tls_server_config_builder(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<OpensslServerConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "cert_pairs",
+        "tlcp_cert_pairs",
+        "enable_client_auth",
+        "session_id_context",
+        "no_session_ticket",
+        "disable_session_ticket",
+        "no_session_cache",
+        "disable_session_cache",
+        "ca_certificate",
+        "ca_cert",
+        "client_auth_certificate",
+        "client_auth_cert",
+        "handshake_timeout",
+        "negotiation_timeout",
+        "accept_timeout",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = OpensslServerConfigBuilder::empty();
 
@@ -572,7 +667,7 @@ pub fn as_openssl_tls_server_config_builder(
                 builder.set_accept_timeout(timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         builder.check()?;
@@ -587,6 +682,8 @@ pub fn as_openssl_tls_server_config_builder(
 pub fn as_tls_interception_server_config_builder(
     value: &Yaml,
 ) -> anyhow::Result<OpensslInterceptionServerConfigBuilder> {
+    const KEYS: &[&str] = &["handshake_timeout", "negotiation_timeout", "accept_timeout"];
+
     if let Yaml::Hash(map) = value {
         let mut builder = OpensslInterceptionServerConfigBuilder::default();
 
@@ -597,7 +694,7 @@ pub fn as_tls_interception_server_config_builder(
                 builder.set_accept_timeout(timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         builder.check()?;
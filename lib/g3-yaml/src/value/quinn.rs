@@ -9,6 +9,14 @@ use yaml_rust::Yaml;
 use g3_types::net::QuinnTransportConfigBuilder;
 
 pub fn as_quinn_transport_config(value: &Yaml) -> anyhow::Result<QuinnTransportConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "max_idle_timeout",
+        "keep_alive_interval",
+        "stream_receive_window",
+        "receive_window",
+        "send_window",
+    ];
+
     let Yaml::Hash(map) = value else {
         return Err(anyhow!(
             "yaml value type for quinn transport config should be 'map'"
@@ -48,7 +56,7 @@ pub fn as_quinn_transport_config(value: &Yaml) -> anyhow::Result<QuinnTransportC
             config.set_send_window(size);
             Ok(())
         }
-        _ => Err(anyhow!("invalid key {k}")),
+        _ => Err(crate::key::invalid(k, KEYS)),
     })?;
     Ok(config)
 }
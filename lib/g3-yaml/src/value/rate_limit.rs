@@ -12,6 +12,8 @@ use yaml_rust::Yaml;
 use g3_types::limit::RateLimitQuota;
 
 pub fn as_rate_limit_quota(v: &Yaml) -> anyhow::Result<RateLimitQuota> {
+    const KEYS: &[&str] = &["rate", "replenish_interval", "max_burst"];
+
     match v {
         Yaml::Integer(_) => {
             let count = crate::value::as_nonzero_u32(v)?;
@@ -45,7 +47,7 @@ pub fn as_rate_limit_quota(v: &Yaml) -> anyhow::Result<RateLimitQuota> {
                     );
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
 
             match quota {
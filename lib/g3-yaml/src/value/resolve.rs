@@ -34,6 +34,8 @@ pub fn as_pick_strategy(v: &Yaml) -> anyhow::Result<PickStrategy> {
 }
 
 pub fn as_resolve_strategy(v: &Yaml) -> anyhow::Result<ResolveStrategy> {
+    const KEYS: &[&str] = &["query", "pick"];
+
     let mut config = ResolveStrategy::default();
 
     match v {
@@ -51,7 +53,7 @@ pub fn as_resolve_strategy(v: &Yaml) -> anyhow::Result<ResolveStrategy> {
                     config.pick = as_pick_strategy(v)?;
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
             Ok(config)
         }
@@ -104,6 +104,8 @@ where
 }
 
 fn add_alpn_matched_backend(obj: &mut AlpnMatch<NodeName>, value: &Yaml) -> anyhow::Result<()> {
+    const KEYS: &[&str] = &["set_default", "protocol", "backend"];
+
     let mut protocol_vs = vec![];
     let mut set_default = false;
     let mut name = NodeName::default();
@@ -133,7 +135,7 @@ fn add_alpn_matched_backend(obj: &mut AlpnMatch<NodeName>, value: &Yaml) -> anyh
                 name = crate::value::as_metric_node_name(v)?;
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
     } else {
         name = crate::value::as_metric_node_name(value)?;
@@ -3,16 +3,16 @@
  * Copyright 2023-2025 ByteDance and/or its affiliates.
  */
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, anyhow};
 use rustls_pki_types::pem::PemObject;
-use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, ServerName, SubjectPublicKeyInfoDer};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    RustlsCertificatePair, RustlsCertificatePairBuilder, RustlsClientConfigBuilder,
-    RustlsServerConfigBuilder,
+    RustlsCertificatePair, RustlsCertificatePairBuilder, RustlsClientAuthCrlConfig,
+    RustlsClientConfigBuilder, RustlsRawPublicKey, RustlsServerConfigBuilder,
 };
 
 pub fn as_rustls_server_name(value: &Yaml) -> anyhow::Result<ServerName<'static>> {
@@ -91,10 +91,61 @@ pub fn as_rustls_private_key(
         .map_err(|e| anyhow!("invalid private key file {}: {e:?}", path.display()))
 }
 
+fn as_public_keys_from_single_element(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<Vec<SubjectPublicKeyInfoDer<'static>>> {
+    let mut keys = Vec::new();
+    if let Yaml::String(s) = value
+        && s.trim_start().starts_with("--")
+    {
+        for (i, r) in SubjectPublicKeyInfoDer::pem_slice_iter(s.as_bytes()).enumerate() {
+            let key = r.map_err(|e| anyhow!("invalid public key #{i}: {e:?}"))?;
+            keys.push(key);
+        }
+        return if keys.is_empty() {
+            Err(anyhow!("no valid public key found"))
+        } else {
+            Ok(keys)
+        };
+    }
+
+    let (file, path) = crate::value::as_file(value, lookup_dir).context("invalid file")?;
+    for (i, r) in SubjectPublicKeyInfoDer::pem_reader_iter(file).enumerate() {
+        let key = r.map_err(|e| anyhow!("invalid public key {}#{i}: {e:?}", path.display()))?;
+        keys.push(key);
+    }
+    if keys.is_empty() {
+        Err(anyhow!("no valid public key found"))
+    } else {
+        Ok(keys)
+    }
+}
+
+/// Parse the PEM encoded RFC 7250 raw public keys
+pub fn as_rustls_public_keys(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<Vec<SubjectPublicKeyInfoDer<'static>>> {
+    if let Yaml::Array(seq) = value {
+        let mut keys = Vec::new();
+        for (i, v) in seq.iter().enumerate() {
+            let this_keys = as_public_keys_from_single_element(v, lookup_dir)
+                .context(format!("invalid public keys value for element #{i}"))?;
+            keys.extend(this_keys);
+        }
+        Ok(keys)
+    } else {
+        as_public_keys_from_single_element(value, lookup_dir)
+    }
+}
+
 pub fn as_rustls_certificate_pair(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<RustlsCertificatePair> {
+    const KEYS: &[&str] = &["certificate", "cert", "private_key", "key"];
+
     if let Yaml::Hash(map) = value {
         let mut pair_builder = RustlsCertificatePairBuilder::default();
         crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
@@ -110,7 +161,7 @@ pub fn as_rustls_certificate_pair(
                 pair_builder.set_key(key);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
         pair_builder.build()
     } else {
@@ -120,10 +171,83 @@ pub fn as_rustls_certificate_pair(
     }
 }
 
+fn as_crl_file_path(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<PathBuf> {
+    if let Some(dir) = lookup_dir {
+        crate::value::as_file_path(value, dir, false)
+    } else {
+        crate::value::as_absolute_path(value)
+    }
+}
+
+pub fn as_rustls_client_auth_crl_config(
+    value: &Yaml,
+    lookup_dir: Option<&Path>,
+) -> anyhow::Result<RustlsClientAuthCrlConfig> {
+    const KEYS: &[&str] = &["file", "files", "refresh_interval"];
+
+    let as_files = |v: &Yaml| crate::value::as_list(v, |v| as_crl_file_path(v, lookup_dir));
+
+    let config = if let Yaml::Hash(map) = value {
+        let mut files = Vec::new();
+        let mut refresh_interval = None;
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "file" | "files" => {
+                files = as_files(v).context(format!("invalid file path list value for key {k}"))?;
+                Ok(())
+            }
+            "refresh_interval" => {
+                let interval = crate::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                refresh_interval = Some(interval);
+                Ok(())
+            }
+            _ => Err(crate::key::invalid(k, KEYS)),
+        })?;
+        let mut config = RustlsClientAuthCrlConfig::new(files);
+        if let Some(interval) = refresh_interval {
+            config.set_refresh_interval(interval);
+        }
+        config
+    } else {
+        let files = as_files(value).context("invalid file path list value")?;
+        RustlsClientAuthCrlConfig::new(files)
+    };
+    config.check()?;
+    // make sure the files are valid at config load time
+    config.load()?;
+    Ok(config)
+}
+
 pub fn as_rustls_client_config_builder(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<RustlsClientConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "no_session_cache",
+        "disable_session_cache",
+        "session_cache_disabled",
+        "disable_sni",
+        "max_fragment_size",
+        "certificate",
+        "cert",
+        "private_key",
+        "key",
+        "cert_pair",
+        "raw_public_key",
+        "server_raw_public_key",
+        "server_raw_public_keys",
+        "ca_certificate",
+        "ca_cert",
+        "server_auth_certificate",
+        "server_auth_cert",
+        "no_default_ca_certificate",
+        "no_default_ca_cert",
+        "use_builtin_ca_certificate",
+        "use_builtin_ca_cert",
+        "handshake_timeout",
+        "negotiation_timeout",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = RustlsClientConfigBuilder::default();
         let mut cert_pair_builder = RustlsCertificatePairBuilder::default();
@@ -169,6 +293,18 @@ pub fn as_rustls_client_config_builder(
                 builder.set_cert_pair(pair);
                 Ok(())
             }
+            "raw_public_key" => {
+                let key = as_rustls_private_key(v, lookup_dir)
+                    .context(format!("invalid private key value for key {k}"))?;
+                builder.set_raw_public_key(RustlsRawPublicKey::new(key));
+                Ok(())
+            }
+            "server_raw_public_key" | "server_raw_public_keys" => {
+                let keys = as_rustls_public_keys(v, lookup_dir)
+                    .context(format!("invalid public keys value for key {k}"))?;
+                builder.set_server_raw_public_keys(keys);
+                Ok(())
+            }
             "ca_certificate" | "ca_cert" | "server_auth_certificate" | "server_auth_cert" => {
                 let certs = as_rustls_certificates(v, lookup_dir)
                     .context(format!("invalid certificates value for key {k}"))?;
@@ -197,7 +333,7 @@ pub fn as_rustls_client_config_builder(
                 builder.set_negotiation_timeout(timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         if let Ok(cert_pair) = cert_pair_builder.build()
@@ -219,6 +355,39 @@ pub fn as_rustls_server_config_builder(
     value: &Yaml,
     lookup_dir: Option<&Path>,
 ) -> anyhow::Result<RustlsServerConfigBuilder> {
+    const KEYS: &[&str] = &[
+        "cert_pairs",
+        "certificate",
+        "cert",
+        "private_key",
+        "key",
+        "enable_client_auth",
+        "enable_optional_client_auth",
+        "use_session_ticket",
+        "no_session_ticket",
+        "disable_session_ticket",
+        "no_session_cache",
+        "disable_session_cache",
+        "session_ticket_only",
+        "shared_session_cache",
+        "alpn",
+        "alpn_protocols",
+        "raw_public_key",
+        "client_auth_raw_public_key",
+        "client_auth_raw_public_keys",
+        "fallback_cert_pair",
+        "log_cert_selection",
+        "client_auth_crl",
+        "ca_crl",
+        "ca_certificate",
+        "ca_cert",
+        "client_auth_certificate",
+        "client_auth_cert",
+        "handshake_timeout",
+        "negotiation_timeout",
+        "accept_timeout",
+    ];
+
     if let Yaml::Hash(map) = value {
         let mut builder = RustlsServerConfigBuilder::empty();
         let mut cert_pair_builder = RustlsCertificatePairBuilder::default();
@@ -257,6 +426,13 @@ pub fn as_rustls_server_config_builder(
                 }
                 Ok(())
             }
+            "enable_optional_client_auth" => {
+                let enable = crate::value::as_bool(v)?;
+                if enable {
+                    builder.enable_optional_client_auth();
+                }
+                Ok(())
+            }
             "use_session_ticket" => {
                 let enable = crate::value::as_bool(v)?;
                 builder.set_use_session_ticket(enable);
@@ -272,6 +448,51 @@ pub fn as_rustls_server_config_builder(
                 builder.set_disable_session_cache(disable);
                 Ok(())
             }
+            "session_ticket_only" => {
+                let enable = crate::value::as_bool(v)?;
+                builder.set_session_ticket_only(enable);
+                Ok(())
+            }
+            "shared_session_cache" => {
+                let name = crate::value::as_string(v)?;
+                builder.set_shared_session_cache(name);
+                Ok(())
+            }
+            "alpn" | "alpn_protocols" => {
+                let protocols = crate::value::as_list(v, crate::value::as_alpn_protocol)
+                    .context(format!("invalid alpn protocol list value for key {k}"))?;
+                builder.set_alpn_protocols(protocols);
+                Ok(())
+            }
+            "raw_public_key" => {
+                let key = as_rustls_private_key(v, lookup_dir)
+                    .context(format!("invalid private key value for key {k}"))?;
+                builder.set_raw_public_key(RustlsRawPublicKey::new(key));
+                Ok(())
+            }
+            "client_auth_raw_public_key" | "client_auth_raw_public_keys" => {
+                let keys = as_rustls_public_keys(v, lookup_dir)
+                    .context(format!("invalid public keys value for key {k}"))?;
+                builder.set_client_auth_raw_public_keys(keys);
+                Ok(())
+            }
+            "fallback_cert_pair" => {
+                let pair = as_rustls_certificate_pair(v, lookup_dir)
+                    .context(format!("invalid rustls cert pair value for key {k}"))?;
+                builder.set_fallback_cert_pair(pair);
+                Ok(())
+            }
+            "log_cert_selection" => {
+                let enable = crate::value::as_bool(v)?;
+                builder.set_log_cert_selection(enable);
+                Ok(())
+            }
+            "client_auth_crl" | "ca_crl" => {
+                let crl = as_rustls_client_auth_crl_config(v, lookup_dir)
+                    .context(format!("invalid client auth crl config value for key {k}"))?;
+                builder.set_client_auth_crl(crl);
+                Ok(())
+            }
             "ca_certificate" | "ca_cert" | "client_auth_certificate" | "client_auth_cert" => {
                 let certs = as_rustls_certificates(v, lookup_dir)
                     .context(format!("invalid value for key {k}"))?;
@@ -284,7 +505,7 @@ pub fn as_rustls_server_config_builder(
                 builder.set_accept_timeout(timeout);
                 Ok(())
             }
-            _ => Err(anyhow!("invalid key {k}")),
+            _ => Err(crate::key::invalid(k, KEYS)),
         })?;
 
         if let Ok(cert_pair) = cert_pair_builder.build() {
@@ -303,6 +524,7 @@ pub fn as_rustls_server_config_builder(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use g3_types::net::AlpnProtocol;
     use std::fs;
     use std::path::{Path, PathBuf};
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -311,6 +533,9 @@ mod tests {
 
     const TEST_CERT_PEM: &str = include_str!("./test_data/test_cert1.pem");
     const TEST_KEY_PEM: &str = include_str!("./test_data/test_key1.pem");
+    const TEST_CRL_PEM: &str = include_str!("./test_data/test_crl.pem");
+    const TEST_PUBKEY_PEM: &str = include_str!("./test_data/test_pubkey1.pem");
+    const TEST_RPK_KEY_PEM: &str = include_str!("./test_data/test_rpk_key1.pem");
 
     static TEST_DIR_ID_COUNTER: AtomicUsize = AtomicUsize::new(0);
 
@@ -687,6 +912,10 @@ mod tests {
         let cert_pair1 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
         let cert_pair2 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
         let cert_pair3 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
+        let cert_pair4 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
+        let cert_pair5 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
+        let cert_pair6 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
+        let cert_pair7 = as_rustls_certificate_pair(&yaml[0], Some(test_dir_path)).unwrap();
 
         let yaml = YamlLoader::load_from_str(&format!(
             r#"
@@ -754,6 +983,88 @@ mod tests {
         let mut expected = RustlsServerConfigBuilder::empty();
         expected.push_cert_pair(cert_pair3);
         assert_eq!(builder, expected);
+
+        // session resumption fields
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: |-
+                    {}
+                private_key: |-
+                    {}
+                shared_session_cache: site
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        let builder = as_rustls_server_config_builder(&yaml[0], None).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.push_cert_pair(cert_pair4);
+        expected.set_shared_session_cache("site".to_string());
+        assert_eq!(builder, expected);
+
+        // alpn protocols
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: |-
+                    {}
+                private_key: |-
+                    {}
+                alpn: [h2, http/1.1]
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        let builder = as_rustls_server_config_builder(&yaml[0], None).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.push_cert_pair(cert_pair5);
+        expected.set_alpn_protocols(vec![AlpnProtocol::Http2, AlpnProtocol::Http11]);
+        assert_eq!(builder, expected);
+
+        // optional client auth
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: |-
+                    {}
+                private_key: |-
+                    {}
+                enable_optional_client_auth: true
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        let builder = as_rustls_server_config_builder(&yaml[0], None).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.push_cert_pair(cert_pair6);
+        expected.enable_optional_client_auth();
+        assert_eq!(builder, expected);
+
+        // fallback cert pair
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: |-
+                    {0}
+                private_key: |-
+                    {1}
+                fallback_cert_pair:
+                  certificate: |-
+                      {0}
+                  private_key: |-
+                      {1}
+                log_cert_selection: true
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        let builder = as_rustls_server_config_builder(&yaml[0], None).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.push_cert_pair(cert_pair7.clone());
+        expected.set_fallback_cert_pair(cert_pair7);
+        expected.set_log_cert_selection(true);
+        assert_eq!(builder, expected);
     }
 
     #[test]
@@ -793,5 +1104,247 @@ mod tests {
             "#
         );
         assert!(as_rustls_server_config_builder(&yaml[0], None).is_err());
+
+        // conflict session resumption fields
+        let temp_dir = TempDir::new("rustls_server_config_builder_err");
+        let test_dir_path = temp_dir.path();
+        let cert_path = test_dir_path.join("test_cert.pem");
+        fs::write(&cert_path, TEST_CERT_PEM).unwrap();
+        let key_path = test_dir_path.join("test_key.pem");
+        fs::write(&key_path, TEST_KEY_PEM).unwrap();
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: {}
+                private_key: {}
+                session_ticket_only: true
+                no_session_ticket: true
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        assert!(as_rustls_server_config_builder(&yaml[0], None).is_err());
+
+        // invalid alpn protocols
+        for alpn in ["[h2, spdy/3]", "[]", "[h2, h2]"] {
+            let yaml = YamlLoader::load_from_str(&format!(
+                r#"
+                    certificate: {}
+                    private_key: {}
+                    alpn: {alpn}
+                "#,
+                cert_path.display(),
+                key_path.display()
+            ))
+            .unwrap();
+            assert!(as_rustls_server_config_builder(&yaml[0], None).is_err());
+        }
+    }
+
+    #[test]
+    fn as_rustls_client_auth_crl_config_ok() {
+        let temp_dir = TempDir::new("rustls_client_auth_crl_ok");
+        let test_dir_path = temp_dir.path();
+        let crl_path = test_dir_path.join("test_crl.pem");
+        fs::write(&crl_path, TEST_CRL_PEM).unwrap();
+
+        // relative path
+        let yaml = yaml_str!("test_crl.pem");
+        let config = as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).unwrap();
+        assert_eq!(
+            config,
+            RustlsClientAuthCrlConfig::new(vec![crl_path.clone()])
+        );
+
+        // absolute path list
+        let yaml = YamlLoader::load_from_str(&format!("[{}]", crl_path.display())).unwrap();
+        let config = as_rustls_client_auth_crl_config(&yaml[0], None).unwrap();
+        assert_eq!(
+            config,
+            RustlsClientAuthCrlConfig::new(vec![crl_path.clone()])
+        );
+
+        // map
+        let yaml = yaml_doc!(
+            r#"
+                file: test_crl.pem
+                refresh_interval: 1h
+            "#
+        );
+        let config = as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).unwrap();
+        let mut expected = RustlsClientAuthCrlConfig::new(vec![crl_path]);
+        expected.set_refresh_interval(Duration::from_secs(3600));
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn as_rustls_client_auth_crl_config_err() {
+        let temp_dir = TempDir::new("rustls_client_auth_crl_err");
+        let test_dir_path = temp_dir.path();
+        let cert_path = test_dir_path.join("test_cert.pem");
+        fs::write(&cert_path, TEST_CERT_PEM).unwrap();
+        let key_path = test_dir_path.join("test_key.pem");
+        fs::write(&key_path, TEST_KEY_PEM).unwrap();
+        let crl_path = test_dir_path.join("test_crl.pem");
+        fs::write(&crl_path, TEST_CRL_PEM).unwrap();
+
+        // not existed file
+        let yaml = yaml_str!("not_existed.pem");
+        assert!(as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).is_err());
+
+        // no crl in file
+        let yaml = yaml_str!("test_cert.pem");
+        assert!(as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).is_err());
+
+        // relative path without lookup dir
+        let yaml = yaml_str!("test_crl.pem");
+        assert!(as_rustls_client_auth_crl_config(&yaml, None).is_err());
+
+        // no file
+        let yaml = yaml_doc!("refresh_interval: 1h");
+        assert!(as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).is_err());
+
+        // zero refresh interval
+        let yaml = yaml_doc!(
+            r#"
+                file: test_crl.pem
+                refresh_interval: 0
+            "#
+        );
+        assert!(as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).is_err());
+
+        // unknown key
+        let yaml = yaml_doc!(
+            r#"
+                file: test_crl.pem
+                unknown_key: 1
+            "#
+        );
+        assert!(as_rustls_client_auth_crl_config(&yaml, Some(test_dir_path)).is_err());
+
+        // client auth not enabled
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: {}
+                private_key: {}
+                client_auth_crl: test_crl.pem
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        assert!(as_rustls_server_config_builder(&yaml[0], Some(test_dir_path)).is_err());
+
+        let yaml = YamlLoader::load_from_str(&format!(
+            r#"
+                certificate: {}
+                private_key: {}
+                enable_client_auth: true
+                client_auth_crl: test_crl.pem
+            "#,
+            cert_path.display(),
+            key_path.display()
+        ))
+        .unwrap();
+        assert!(as_rustls_server_config_builder(&yaml[0], Some(test_dir_path)).is_ok());
+    }
+
+    #[test]
+    fn as_rustls_public_keys_ok() {
+        let temp_dir = TempDir::new("rustls_public_keys_ok");
+        let test_dir_path = temp_dir.path();
+        let key_path = test_dir_path.join("test_pubkey.pem");
+        fs::write(&key_path, TEST_PUBKEY_PEM).unwrap();
+        let expected = SubjectPublicKeyInfoDer::from_pem_slice(TEST_PUBKEY_PEM.as_bytes()).unwrap();
+
+        let yaml = Yaml::String(TEST_PUBKEY_PEM.to_string());
+        let keys = as_rustls_public_keys(&yaml, None).unwrap();
+        assert_eq!(keys, vec![expected.clone()]);
+
+        let yaml = yaml_doc!(
+            r#"
+                - test_pubkey.pem
+                - test_pubkey.pem
+            "#
+        );
+        let keys = as_rustls_public_keys(&yaml, Some(test_dir_path)).unwrap();
+        assert_eq!(keys, vec![expected.clone(), expected]);
+    }
+
+    #[test]
+    fn as_rustls_public_keys_err() {
+        let yaml = yaml_str!("non_existent_pubkey.pem");
+        assert!(as_rustls_public_keys(&yaml, None).is_err());
+
+        let yaml = Yaml::String(TEST_CERT_PEM.to_string());
+        assert!(as_rustls_public_keys(&yaml, None).is_err());
+
+        let yaml = Yaml::Integer(1);
+        assert!(as_rustls_public_keys(&yaml, None).is_err());
+    }
+
+    #[test]
+    fn as_rustls_raw_public_key_config() {
+        let temp_dir = TempDir::new("rustls_raw_public_key_config");
+        let test_dir_path = temp_dir.path();
+        fs::write(test_dir_path.join("test_pubkey.pem"), TEST_PUBKEY_PEM).unwrap();
+        fs::write(test_dir_path.join("test_rpk_key.pem"), TEST_RPK_KEY_PEM).unwrap();
+        fs::write(test_dir_path.join("test_cert.pem"), TEST_CERT_PEM).unwrap();
+        let public_key =
+            SubjectPublicKeyInfoDer::from_pem_slice(TEST_PUBKEY_PEM.as_bytes()).unwrap();
+        let raw_public_key = RustlsRawPublicKey::new(
+            PrivateKeyDer::from_pem_slice(TEST_RPK_KEY_PEM.as_bytes()).unwrap(),
+        );
+
+        let yaml = yaml_doc!(
+            r#"
+                raw_public_key: test_rpk_key.pem
+                client_auth_raw_public_keys: test_pubkey.pem
+            "#
+        );
+        let builder = as_rustls_server_config_builder(&yaml, Some(test_dir_path)).unwrap();
+        let mut expected = RustlsServerConfigBuilder::empty();
+        expected.set_raw_public_key(raw_public_key.clone());
+        expected.set_client_auth_raw_public_keys(vec![public_key.clone()]);
+        assert_eq!(builder, expected);
+
+        let yaml = yaml_doc!(
+            r#"
+                raw_public_key: test_rpk_key.pem
+                server_raw_public_key: test_pubkey.pem
+            "#
+        );
+        let builder = as_rustls_client_config_builder(&yaml, Some(test_dir_path)).unwrap();
+        let mut expected = RustlsClientConfigBuilder::default();
+        expected.set_raw_public_key(raw_public_key);
+        expected.set_server_raw_public_keys(vec![public_key]);
+        assert_eq!(builder, expected);
+
+        // can not be mixed with x509 config
+        let yaml = yaml_doc!(
+            r#"
+                raw_public_key: test_rpk_key.pem
+                certificate: test_cert.pem
+                private_key: test_rpk_key.pem
+            "#
+        );
+        assert!(as_rustls_server_config_builder(&yaml, Some(test_dir_path)).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                raw_public_key: test_rpk_key.pem
+                enable_client_auth: true
+                client_auth_raw_public_keys: test_pubkey.pem
+            "#
+        );
+        assert!(as_rustls_server_config_builder(&yaml, Some(test_dir_path)).is_err());
+
+        let yaml = yaml_doc!(
+            r#"
+                server_raw_public_keys: test_pubkey.pem
+                ca_certificate: test_cert.pem
+            "#
+        );
+        assert!(as_rustls_client_config_builder(&yaml, Some(test_dir_path)).is_err());
     }
 }
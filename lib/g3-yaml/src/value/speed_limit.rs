@@ -10,6 +10,19 @@ use g3_types::limit::{GlobalDatagramSpeedLimitConfig, GlobalStreamSpeedLimitConf
 use g3_types::net::{TcpSockSpeedLimitConfig, UdpSockSpeedLimitConfig};
 
 pub fn as_tcp_sock_speed_limit(v: &Yaml) -> anyhow::Result<TcpSockSpeedLimitConfig> {
+    const KEYS: &[&str] = &[
+        "shift",
+        "shift_millis",
+        "upload",
+        "north",
+        "upload_bytes",
+        "north_bytes",
+        "download",
+        "south",
+        "download_bytes",
+        "south_bytes",
+    ];
+
     let mut config = TcpSockSpeedLimitConfig::default();
     match v {
         Yaml::String(_) | Yaml::Integer(_) => {
@@ -35,7 +48,7 @@ pub fn as_tcp_sock_speed_limit(v: &Yaml) -> anyhow::Result<TcpSockSpeedLimitConf
                         .context(format!("invalid humanize usize value for key {k}"))?;
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid yaml value type")),
@@ -45,6 +58,19 @@ pub fn as_tcp_sock_speed_limit(v: &Yaml) -> anyhow::Result<TcpSockSpeedLimitConf
 }
 
 pub fn as_udp_sock_speed_limit(v: &Yaml) -> anyhow::Result<UdpSockSpeedLimitConfig> {
+    const KEYS: &[&str] = &[
+        "shift",
+        "shift_millis",
+        "upload_packets",
+        "north_packets",
+        "download_packets",
+        "south_packets",
+        "upload_bytes",
+        "north_bytes",
+        "download_bytes",
+        "south_bytes",
+    ];
+
     let mut config = UdpSockSpeedLimitConfig::default();
     match v {
         Yaml::String(_) | Yaml::Integer(_) => {
@@ -80,7 +106,7 @@ pub fn as_udp_sock_speed_limit(v: &Yaml) -> anyhow::Result<UdpSockSpeedLimitConf
                         .context(format!("invalid humanize usize value for key {k}"))?;
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
         }
         _ => return Err(anyhow!("invalid yaml value type")),
@@ -90,6 +116,8 @@ pub fn as_udp_sock_speed_limit(v: &Yaml) -> anyhow::Result<UdpSockSpeedLimitConf
 }
 
 pub fn as_global_stream_speed_limit(v: &Yaml) -> anyhow::Result<GlobalStreamSpeedLimitConfig> {
+    const KEYS: &[&str] = &["replenish_interval", "replenish_bytes", "max_burst_bytes"];
+
     match v {
         Yaml::String(_) | Yaml::Integer(_) => {
             let limit = crate::humanize::as_u64(v).context("invalid humanize u64 value")?;
@@ -116,7 +144,7 @@ pub fn as_global_stream_speed_limit(v: &Yaml) -> anyhow::Result<GlobalStreamSpee
                     config.set_max_burst_bytes(size);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
             config.check()?;
             Ok(config)
@@ -126,6 +154,14 @@ pub fn as_global_stream_speed_limit(v: &Yaml) -> anyhow::Result<GlobalStreamSpee
 }
 
 pub fn as_global_datagram_speed_limit(v: &Yaml) -> anyhow::Result<GlobalDatagramSpeedLimitConfig> {
+    const KEYS: &[&str] = &[
+        "replenish_interval",
+        "replenish_bytes",
+        "replenish_packets",
+        "max_burst_bytes",
+        "max_burst_packets",
+    ];
+
     match v {
         Yaml::String(_) | Yaml::Integer(_) => {
             let limit = crate::humanize::as_u64(v).context("invalid humanize u64 value")?;
@@ -164,7 +200,7 @@ pub fn as_global_datagram_speed_limit(v: &Yaml) -> anyhow::Result<GlobalDatagram
                     config.set_max_burst_packets(size);
                     Ok(())
                 }
-                _ => Err(anyhow!("invalid key {k}")),
+                _ => Err(crate::key::invalid(k, KEYS)),
             })?;
             config.check()?;
             Ok(config)
@@ -262,6 +298,18 @@ mod tests {
         );
         assert!(as_tcp_sock_speed_limit(&yaml).is_err());
 
+        // invalid key with suggestion
+        let yaml = yaml_doc!(
+            r#"
+                uplaod: 100
+            "#
+        );
+        let e = as_tcp_sock_speed_limit(&yaml).unwrap_err();
+        assert_eq!(
+            format!("{:#}", e),
+            "failed to parse value of key uplaod: invalid key uplaod, did you mean upload?"
+        );
+
         // shift value too large
         let yaml = yaml_doc!(
             r#"